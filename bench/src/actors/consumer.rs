@@ -25,7 +25,7 @@ use iggy::client::{ConsumerGroupClient, MessageClient};
 use iggy::clients::client::IggyClient;
 use iggy::consumer::Consumer as IggyConsumer;
 use iggy::error::IggyError;
-use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingKind, PollingStrategy};
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
 use iggy::utils::sizeable::Sizeable;
@@ -167,6 +167,7 @@ impl Consumer {
                         &strategy,
                         messages_per_batch,
                         auto_commit,
+                        &PollMessagesOptions::default(),
                     )
                     .await?;
 
@@ -229,6 +230,7 @@ impl Consumer {
                     &strategy,
                     messages_per_batch,
                     auto_commit,
+                    &PollMessagesOptions::default(),
                 )
                 .await;
             if let Err(e) = polled_messages {
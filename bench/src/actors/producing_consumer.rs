@@ -26,7 +26,7 @@ use iggy::client::MessageClient;
 use iggy::clients::client::IggyClient;
 use iggy::consumer::Consumer as IggyConsumer;
 use iggy::error::IggyError;
-use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
@@ -199,6 +199,7 @@ impl ProducingConsumer {
                         &strategy,
                         messages_per_batch,
                         auto_commit,
+                        &PollMessagesOptions::default(),
                     )
                     .await?;
 
@@ -268,6 +269,7 @@ impl ProducingConsumer {
                     &strategy,
                     messages_per_batch,
                     auto_commit,
+                    &PollMessagesOptions::default(),
                 )
                 .await?;
 
@@ -23,6 +23,7 @@ use iggy::client::{StreamClient, TopicClient};
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use iggy_bench_report::benchmark_kind::BenchmarkKind;
@@ -100,7 +101,7 @@ pub trait Benchmarkable {
             if streams.iter().all(|s| s.id != stream_id) {
                 info!("Creating the test stream {}", stream_id);
                 let name = format!("stream {}", stream_id);
-                client.create_stream(&name, Some(stream_id)).await?;
+                client.create_stream(&name, Some(stream_id), None).await?;
                 let name = format!("topic {}", topic_id);
                 let max_topic_size = match self.args().max_topic_size() {
                     Some(size) => MaxTopicSize::Custom(size),
@@ -122,6 +123,7 @@ pub trait Benchmarkable {
                         None,
                         IggyExpiry::NeverExpire,
                         max_topic_size,
+                        CleanupPolicy::default(),
                     )
                     .await?;
             }
@@ -25,7 +25,8 @@ use crate::{
 };
 use async_trait::async_trait;
 use iggy::{
-    client::ConsumerGroupClient, clients::client::IggyClient, error::IggyError,
+    client::ConsumerGroupClient, clients::client::IggyClient,
+    consumer_groups::assignment_strategy::AssignmentStrategy, error::IggyError,
     messages::poll_messages::PollingKind,
 };
 use iggy_bench_report::{
@@ -71,6 +72,7 @@ impl ProducerAndConsumerGroupBenchmark {
                     &topic_id.try_into().unwrap(),
                     &consumer_group_name,
                     Some(consumer_group_id),
+                    AssignmentStrategy::default(),
                 )
                 .await;
             if cg.is_err() {
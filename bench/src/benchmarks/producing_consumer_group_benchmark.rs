@@ -24,6 +24,7 @@ use crate::rate_limiter::RateLimiter;
 use async_trait::async_trait;
 use iggy::client::ConsumerGroupClient;
 use iggy::clients::client::IggyClient;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::messages::poll_messages::PollingKind;
 use iggy_bench_report::benchmark_kind::BenchmarkKind;
@@ -69,6 +70,7 @@ impl EndToEndProducingConsumerGroupBenchmark {
                     &topic_id.try_into().unwrap(),
                     &consumer_group_name,
                     Some(consumer_group_id),
+                    AssignmentStrategy::default(),
                 )
                 .await;
             if cg.is_err() {
@@ -27,13 +27,16 @@ pub fn create_client_factory(args: &IggyBenchArgs) -> Arc<dyn ClientFactory> {
     match &args.transport() {
         Transport::Http => Arc::new(HttpClientFactory {
             server_addr: args.server_address().to_owned(),
+            ..Default::default()
         }),
         Transport::Tcp => Arc::new(TcpClientFactory {
             server_addr: args.server_address().to_owned(),
             nodelay: args.nodelay(),
+            ..Default::default()
         }),
         Transport::Quic => Arc::new(QuicClientFactory {
             server_addr: args.server_address().to_owned(),
+            ..Default::default()
         }),
     }
 }
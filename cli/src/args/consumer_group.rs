@@ -18,6 +18,7 @@
 
 use crate::args::common::ListMode;
 use clap::{Args, Subcommand};
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::identifier::Identifier;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -33,6 +34,7 @@ pub(crate) enum ConsumerGroupAction {
     ///  iggy consumer-group create stream 2 test
     ///  iggy consumer-group create 2 topic receiver
     ///  iggy consumer-group create -g 4 stream topic group
+    ///  iggy consumer-group create -a range stream topic group
     #[clap(verbatim_doc_comment, visible_alias = "c")]
     Create(ConsumerGroupCreateArgs),
     /// Delete consumer group with given ID for given stream ID and topic ID
@@ -100,6 +102,11 @@ pub(crate) struct ConsumerGroupCreateArgs {
     pub(crate) group_id: Option<u32>,
     /// Consumer group name to create
     pub(crate) name: String,
+    /// Strategy of assigning the partitions to the consumer group members
+    ///
+    /// Available strategies are "range", "round_robin" and "sticky"
+    #[clap(short, long, value_parser = clap::value_parser!(AssignmentStrategy), default_value_t = AssignmentStrategy::default())]
+    pub(crate) assignment_strategy: AssignmentStrategy,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -54,6 +54,19 @@ pub(crate) enum ConsumerOffsetAction {
     ///  iggy consumer-offset set consumer stream topic 1 100
     #[clap(verbatim_doc_comment, visible_alias = "s")]
     Set(ConsumerOffsetSetArgs),
+    /// Retrieve the lag of a consumer or a consumer group in each partition of a given topic
+    ///
+    /// Consumer ID can be specified as a consumer (or consumer group) name or ID
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    ///
+    /// Examples:
+    ///  iggy consumer-offset lag 1 3 5
+    ///  iggy consumer-offset lag consumer stream topic
+    ///  iggy consumer-offset lag --group 1 3 5
+    ///  iggy consumer-offset lag -g group stream topic
+    #[clap(verbatim_doc_comment, visible_alias = "l")]
+    Lag(ConsumerOffsetLagArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -103,3 +116,26 @@ pub(crate) struct ConsumerOffsetSetArgs {
     /// Offset to set
     pub(crate) offset: u64,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ConsumerOffsetLagArgs {
+    /// Consumer (or consumer group) for which the lag is retrieved
+    ///
+    /// Consumer ID can be specified as a consumer (or consumer group) name or ID
+    #[clap(verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) consumer_id: Identifier,
+    /// Stream ID for which consumer lag is retrieved
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// Topic ID for which consumer lag is retrieved
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Retrieve the lag of the consumer group instead of the regular consumer
+    #[arg(short, long, default_value_t = false)]
+    pub(crate) group: bool,
+}
@@ -21,7 +21,9 @@ use clap::{ArgGroup, Args, Subcommand};
 use iggy::error::IggyError;
 use iggy::error::IggyError::InvalidFormat;
 use iggy::identifier::Identifier;
+use iggy::messages::header_filter::HeaderFilter;
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::duration::IggyDuration;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -196,6 +198,29 @@ pub(crate) struct PollMessagesArgs {
     #[clap(verbatim_doc_comment)]
     #[clap(short, long, default_value_t = false)]
     pub(crate) show_headers: bool,
+    /// Poll only the committed messages
+    ///
+    /// Flag indicates whether to skip the messages sent as a part
+    /// of the open or aborted transactions.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, default_value_t = false)]
+    pub(crate) read_committed: bool,
+    /// Poll only the messages matching the filter over their headers
+    ///
+    /// Filter is evaluated by the server, e.g. 'tenant == "acme" AND priority > 3'.
+    /// Comparisons with ==, !=, >, >=, < and <= can be combined with AND, OR
+    /// and parentheses. Messages without the header don't match the comparison.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, value_parser = clap::value_parser!(HeaderFilter))]
+    pub(crate) filter: Option<HeaderFilter>,
+    /// Wait for the messages on the server if there are none yet
+    ///
+    /// Maximum time for which the server holds the request until
+    /// any message is available, e.g. "500ms" or "30s".
+    /// By default, the request is completed immediately.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, value_parser = clap::value_parser!(IggyDuration))]
+    pub(crate) max_wait: Option<IggyDuration>,
     /// Store polled message into file in binary format
     ///
     /// Polled messages will be stored in the file in binary format.
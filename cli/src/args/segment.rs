@@ -17,6 +17,7 @@
  */
 use clap::{Args, Subcommand};
 use iggy::identifier::Identifier;
+use iggy::segments::restore_segments::RestoreRangeKind;
 
 #[derive(Debug, Clone, Subcommand)]
 pub(crate) enum SegmentAction {
@@ -34,6 +35,24 @@ pub(crate) enum SegmentAction {
     ///  iggy segment delete 1 sensor 2 16
     #[clap(verbatim_doc_comment, visible_alias = "d")]
     Delete(SegmentDeleteArgs),
+    /// Restore archived segments into the specified topic ID,
+    /// stream ID and partition ID.
+    ///
+    /// Segments archived for the source stream, topic and partition
+    /// containing the messages from the given offset or timestamp range
+    /// are restored with their original offsets, their checksums
+    /// are validated and indexes rebuilt.
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    ///
+    /// Examples
+    ///  iggy segment restore 1 1 1 1 1 1
+    ///  iggy segment restore prod sensor 1 1 2 1 --start 1000 --end 2000
+    ///  iggy segment restore test sensor 1 1 1 1 --kind timestamp --start 1700000000000000
+    ///  iggy segment restore 2 1 1 1 1 1 --archive-path /var/backup/archive
+    #[clap(verbatim_doc_comment, visible_alias = "r")]
+    Restore(SegmentRestoreArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -55,3 +74,42 @@ pub(crate) struct SegmentDeleteArgs {
     #[arg(value_parser = clap::value_parser!(u32).range(1..100_001))]
     pub(crate) segments_count: u32,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct SegmentRestoreArgs {
+    /// Stream ID to restore segments into
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// Topic ID to restore segments into
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Partition ID to restore segments into
+    pub(crate) partition_id: u32,
+    /// Numeric ID of the stream the segments were archived for
+    pub(crate) source_stream_id: u32,
+    /// Numeric ID of the topic the segments were archived for
+    pub(crate) source_topic_id: u32,
+    /// Numeric ID of the partition the segments were archived for
+    pub(crate) source_partition_id: u32,
+    /// Kind of the range of the messages to restore
+    ///
+    /// Possible values: offset (o), timestamp (t)
+    #[arg(short, long, default_value = "offset", value_parser = clap::value_parser!(RestoreRangeKind))]
+    pub(crate) kind: RestoreRangeKind,
+    /// Start of the range (inclusive), offset or timestamp in microseconds
+    #[arg(short, long, default_value_t = 0)]
+    pub(crate) start: u64,
+    /// End of the range (inclusive), offset or timestamp in microseconds
+    #[arg(short, long, default_value_t = u64::MAX)]
+    pub(crate) end: u64,
+    /// Path to the directory on the server containing the archive
+    /// written by the disk archiver
+    ///
+    /// If not specified, the archiver configured on the server is used
+    #[arg(short, long)]
+    pub(crate) archive_path: Option<String>,
+}
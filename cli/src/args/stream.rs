@@ -19,6 +19,8 @@
 use crate::args::common::ListMode;
 use clap::{Args, Subcommand};
 use iggy::identifier::Identifier;
+use iggy::models::stream_limits::StreamLimits;
+use iggy::utils::byte_size::IggyByteSize;
 
 #[derive(Debug, Clone, Subcommand)]
 pub(crate) enum StreamAction {
@@ -29,6 +31,7 @@ pub(crate) enum StreamAction {
     /// Examples:
     ///  iggy stream create prod
     ///  iggy stream create -s 1 test
+    ///  iggy stream create --max-size 10GB --max-topics 5 --max-partitions 50 tenant
     #[clap(verbatim_doc_comment, visible_alias = "c")]
     Create(StreamCreateArgs),
     /// Delete stream with given ID
@@ -40,13 +43,16 @@ pub(crate) enum StreamAction {
     ///  iggy stream delete test
     #[clap(verbatim_doc_comment, visible_alias = "d")]
     Delete(StreamDeleteArgs),
-    /// Update stream name for given stream ID
+    /// Update stream name and limits for given stream ID
     ///
     /// Stream ID can be specified as a stream name or ID
+    /// If any of the limits is provided, all the limits are replaced
+    /// and the omitted ones become unlimited, otherwise the limits are not changed
     ///
     /// Examples:
     ///  iggy stream update 1 production
     ///  iggy stream update test development
+    ///  iggy stream update --max-size 20GB --max-topics 10 tenant tenant
     #[clap(verbatim_doc_comment, visible_alias = "u")]
     Update(StreamUpdateArgs),
     /// Get details of a single stream with given ID
@@ -85,6 +91,22 @@ pub(crate) struct StreamCreateArgs {
     pub(crate) stream_id: Option<u32>,
     /// Name of the stream
     pub(crate) name: String,
+    /// Maximum size of all the topics in the stream
+    ///
+    /// Can be specified in bytes or with a unit (e.g. 10GB, 512MiB).
+    /// The value of 0 means that the size is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_size: Option<IggyByteSize>,
+    /// Maximum number of the topics in the stream
+    ///
+    /// The value of 0 means that the number of topics is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_topics: Option<u32>,
+    /// Maximum number of the partitions across all the topics in the stream
+    ///
+    /// The value of 0 means that the number of partitions is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_partitions: Option<u32>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -103,6 +125,50 @@ pub(crate) struct StreamUpdateArgs {
     pub(crate) stream_id: Identifier,
     /// New name for the stream
     pub(crate) name: String,
+    /// Maximum size of all the topics in the stream
+    ///
+    /// Can be specified in bytes or with a unit (e.g. 10GB, 512MiB).
+    /// The value of 0 means that the size is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_size: Option<IggyByteSize>,
+    /// Maximum number of the topics in the stream
+    ///
+    /// The value of 0 means that the number of topics is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_topics: Option<u32>,
+    /// Maximum number of the partitions across all the topics in the stream
+    ///
+    /// The value of 0 means that the number of partitions is unlimited.
+    #[clap(long, verbatim_doc_comment)]
+    pub(crate) max_partitions: Option<u32>,
+}
+
+impl StreamCreateArgs {
+    pub(crate) fn limits(&self) -> Option<StreamLimits> {
+        map_limits(self.max_size, self.max_topics, self.max_partitions)
+    }
+}
+
+impl StreamUpdateArgs {
+    pub(crate) fn limits(&self) -> Option<StreamLimits> {
+        map_limits(self.max_size, self.max_topics, self.max_partitions)
+    }
+}
+
+fn map_limits(
+    max_size: Option<IggyByteSize>,
+    max_topics: Option<u32>,
+    max_partitions: Option<u32>,
+) -> Option<StreamLimits> {
+    if max_size.is_none() && max_topics.is_none() && max_partitions.is_none() {
+        return None;
+    }
+
+    Some(StreamLimits {
+        max_size: max_size.filter(|size| size.as_bytes_u64() > 0),
+        max_topics: max_topics.filter(|topics| *topics > 0),
+        max_partitions: max_partitions.filter(|partitions| *partitions > 0),
+    })
 }
 
 #[derive(Debug, Clone, Args)]
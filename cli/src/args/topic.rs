@@ -20,6 +20,7 @@ use crate::args::common::ListMode;
 use clap::{Args, Subcommand};
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::identifier::Identifier;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 
@@ -124,6 +125,12 @@ pub(crate) struct TopicCreateArgs {
     /// Replication factor for the topic
     #[arg(short, long, default_value = "1")]
     pub(crate) replication_factor: u8,
+    /// Cleanup policy for the topic, set to "delete", "compact" or "compact:<header>"
+    ///
+    /// "compact" keeps only the latest message for each messages key used for partitioning
+    /// "compact:<header>" keeps only the latest message for each value of the given header
+    #[arg(short, long, default_value = "delete", verbatim_doc_comment)]
+    pub(crate) cleanup_policy: CleanupPolicy,
     /// Message expiry time in human-readable format like "unlimited" or "15days 2min 2s"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) expiry time
@@ -171,6 +178,12 @@ pub(crate) struct TopicUpdateArgs {
     #[arg(short, long, default_value = "1")]
     /// New replication factor for the topic
     pub(crate) replication_factor: u8,
+    /// New cleanup policy for the topic, set to "delete", "compact" or "compact:<header>"
+    ///
+    /// "compact" keeps only the latest message for each messages key used for partitioning
+    /// "compact:<header>" keeps only the latest message for each value of the given header
+    #[arg(short, long, default_value = "delete", verbatim_doc_comment)]
+    pub(crate) cleanup_policy: CleanupPolicy,
     /// New message expiry time in human-readable format like "unlimited" or "15days 2min 2s"
     ///
     /// "server_default" or skipping parameter makes CLI to use server default (from current server config) expiry time
@@ -21,6 +21,7 @@ use crate::args::permissions::stream::StreamPermissionsArg;
 use crate::args::permissions::UserStatusArg;
 use clap::{Args, Subcommand};
 use iggy::identifier::Identifier;
+use iggy::utils::byte_size::IggyByteSize;
 
 use super::permissions::global::GlobalPermissionsArg;
 
@@ -100,6 +101,19 @@ pub(crate) enum UserAction {
     ///  iggy user permissions client
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Permissions(UserPermissionsArgs),
+    /// Set quotas for user with given ID
+    ///
+    /// The user ID can be specified as either a username or an ID. Quotas limit
+    /// how fast the user can send and poll the messages and invoke the commands,
+    /// across all the clients of the user. The rates which are not provided are
+    /// unlimited, thus invoking the command without any option removes the quotas.
+    ///
+    /// Examples:
+    ///  iggy user quotas 2 --produce 10MB --consume 20MB
+    ///  iggy user quotas client -p 1MB -r 100
+    ///  iggy user quotas testuser
+    #[clap(verbatim_doc_comment, visible_alias = "q")]
+    Quotas(UserQuotasArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -303,3 +317,24 @@ pub(crate) struct UserPermissionsArgs {
     #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
     pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UserQuotasArgs {
+    /// User ID to update
+    ///
+    /// The user ID can be specified as either a username or an ID
+    pub(crate) user_id: Identifier,
+    /// Maximum size of the messages sent per second
+    ///
+    /// Can be specified in bytes or with a unit (e.g. 1MB, 512KiB).
+    #[clap(short, long, verbatim_doc_comment)]
+    pub(crate) produce: Option<IggyByteSize>,
+    /// Maximum size of the messages polled per second
+    ///
+    /// Can be specified in bytes or with a unit (e.g. 1MB, 512KiB).
+    #[clap(short, long, verbatim_doc_comment)]
+    pub(crate) consume: Option<IggyByteSize>,
+    /// Maximum number of the requests invoked per second
+    #[clap(short, long)]
+    pub(crate) requests: Option<u32>,
+}
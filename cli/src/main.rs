@@ -41,6 +41,7 @@ use iggy::args::Args;
 use iggy::cli::context::common::ContextManager;
 use iggy::cli::context::use_context::UseContextCmd;
 use iggy::cli::segments::delete_segments::DeleteSegmentsCmd;
+use iggy::cli::segments::restore_segments::RestoreSegmentsCmd;
 use iggy::cli::system::snapshot::GetSnapshotCmd;
 use iggy::cli::{
     client::{get_client::GetClientCmd, get_clients::GetClientsCmd},
@@ -50,7 +51,8 @@ use iggy::cli::{
         get_consumer_groups::GetConsumerGroupsCmd,
     },
     consumer_offset::{
-        get_consumer_offset::GetConsumerOffsetCmd, set_consumer_offset::SetConsumerOffsetCmd,
+        get_consumer_lag::GetConsumerLagCmd, get_consumer_offset::GetConsumerOffsetCmd,
+        set_consumer_offset::SetConsumerOffsetCmd,
     },
     context::get_contexts::GetContextsCmd,
     message::{
@@ -85,6 +87,8 @@ use iggy::cli::{
 use iggy::cli_command::{CliCommand, PRINT_TARGET};
 use iggy::client_provider::{self, ClientProviderConfig};
 use iggy::clients::client::IggyClient;
+use iggy::models::user_quotas::{GlobalQuotas, UserQuotas};
+use iggy::segments::restore_segments::{ArchiveSource, RestoreRange};
 use iggy::utils::crypto::{Aes256GcmEncryptor, EncryptorKind};
 use iggy::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
 use std::sync::Arc;
@@ -107,13 +111,16 @@ fn get_command(
     #[warn(clippy::let_and_return)]
     match command {
         Command::Stream(command) => match command {
-            StreamAction::Create(args) => {
-                Box::new(CreateStreamCmd::new(args.stream_id, args.name.clone()))
-            }
+            StreamAction::Create(args) => Box::new(CreateStreamCmd::new(
+                args.stream_id,
+                args.name.clone(),
+                args.limits(),
+            )),
             StreamAction::Delete(args) => Box::new(DeleteStreamCmd::new(args.stream_id.clone())),
             StreamAction::Update(args) => Box::new(UpdateStreamCmd::new(
                 args.stream_id.clone(),
                 args.name.clone(),
+                args.limits(),
             )),
             StreamAction::Get(args) => Box::new(GetStreamCmd::new(args.stream_id.clone())),
             StreamAction::List(args) => Box::new(GetStreamsCmd::new(args.list_mode.into())),
@@ -129,6 +136,7 @@ fn get_command(
                 args.message_expiry.clone().into(),
                 args.max_topic_size,
                 args.replication_factor,
+                args.cleanup_policy.clone(),
             )),
             TopicAction::Delete(args) => Box::new(DeleteTopicCmd::new(
                 args.stream_id.clone(),
@@ -142,6 +150,7 @@ fn get_command(
                 args.message_expiry.clone().into(),
                 args.max_topic_size,
                 args.replication_factor,
+                args.cleanup_policy.clone(),
             )),
             TopicAction::Get(args) => Box::new(GetTopicCmd::new(
                 args.stream_id.clone(),
@@ -175,6 +184,22 @@ fn get_command(
                 args.partition_id,
                 args.segments_count,
             )),
+            SegmentAction::Restore(args) => Box::new(RestoreSegmentsCmd::new(
+                args.stream_id.clone(),
+                args.topic_id.clone(),
+                args.partition_id,
+                ArchiveSource {
+                    stream_id: args.source_stream_id,
+                    topic_id: args.source_topic_id,
+                    partition_id: args.source_partition_id,
+                    archive_path: args.archive_path.clone(),
+                },
+                RestoreRange {
+                    kind: args.kind,
+                    start: args.start,
+                    end: args.end,
+                },
+            )),
         },
         Command::Ping(args) => Box::new(PingCmd::new(args.count)),
         Command::Me => Box::new(GetMeCmd::new()),
@@ -233,6 +258,21 @@ fn get_command(
                 change_pwd_args.current_password,
                 change_pwd_args.new_password,
             )),
+            UserAction::Quotas(quotas_args) => Box::new(UpdateUserCmd::new(
+                quotas_args.user_id.clone(),
+                UpdateUserType::Quotas(UserQuotas {
+                    global: GlobalQuotas {
+                        produce_bytes_per_second: quotas_args
+                            .produce
+                            .map_or(0, |size| size.as_bytes_u64()),
+                        consume_bytes_per_second: quotas_args
+                            .consume
+                            .map_or(0, |size| size.as_bytes_u64()),
+                        requests_per_second: quotas_args.requests.unwrap_or(0),
+                    },
+                    streams: None,
+                }),
+            )),
             UserAction::Permissions(permissions_args) => Box::new(UpdatePermissionsCmd::new(
                 permissions_args.user_id.clone(),
                 PermissionsArgs::new(
@@ -254,6 +294,7 @@ fn get_command(
                 create_args.topic_id.clone(),
                 create_args.name.clone(),
                 create_args.group_id,
+                create_args.assignment_strategy,
             )),
             ConsumerGroupAction::Delete(delete_args) => Box::new(DeleteConsumerGroupCmd::new(
                 delete_args.stream_id.clone(),
@@ -293,6 +334,9 @@ fn get_command(
                 poll_args.next,
                 poll_args.consumer.clone(),
                 poll_args.show_headers,
+                poll_args.read_committed,
+                poll_args.filter.clone(),
+                poll_args.max_wait,
                 poll_args.output_file.clone(),
             )),
             MessageAction::Flush(flush_args) => Box::new(FlushMessagesCmd::new(
@@ -316,6 +360,12 @@ fn get_command(
                 set_args.partition_id,
                 set_args.offset,
             )),
+            ConsumerOffsetAction::Lag(lag_args) => Box::new(GetConsumerLagCmd::new(
+                lag_args.consumer_id.clone(),
+                lag_args.stream_id.clone(),
+                lag_args.topic_id.clone(),
+                lag_args.group,
+            )),
         },
         Command::Context(command) => match command {
             ContextAction::List(list_args) => {
@@ -19,7 +19,7 @@
 use iggy::client::{Client, UserClient};
 use iggy::clients::builder::IggyClientBuilder;
 use iggy::consumer::Consumer;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::models::messages::PolledMessage;
 use iggy::users::defaults::*;
 use iggy::utils::duration::IggyDuration;
@@ -89,6 +89,7 @@ async fn consume_messages(client: &dyn Client) -> Result<(), Box<dyn Error>> {
                 &PollingStrategy::offset(offset),
                 messages_per_batch,
                 false,
+                &PollMessagesOptions::default(),
             )
             .await?;
 
@@ -17,6 +17,7 @@
  */
 
 use clap::Parser;
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
 use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
 use iggy::utils::duration::IggyDuration;
 use std::str::FromStr;
@@ -105,6 +106,12 @@ pub struct Args {
     #[arg(long, default_value = "false")]
     pub tcp_nodelay: bool,
 
+    #[arg(long, default_value = "false")]
+    pub tcp_multiplexing: bool,
+
+    #[arg(long)]
+    pub tcp_compression: Option<WireCompressionAlgorithm>,
+
     #[arg(long, default_value = "127.0.0.1:0")]
     pub quic_client_address: String,
 
@@ -155,6 +162,9 @@ pub struct Args {
 
     #[arg(long, default_value = "5s")]
     pub quic_heartbeat_interval: String,
+
+    #[arg(long)]
+    pub quic_compression: Option<WireCompressionAlgorithm>,
 }
 
 impl Args {
@@ -198,6 +208,8 @@ impl Default for Args {
             tcp_tls_enabled: false,
             tcp_tls_domain: "localhost".to_string(),
             tcp_nodelay: true,
+            tcp_multiplexing: false,
+            tcp_compression: None,
             quic_client_address: "127.0.0.1:0".to_string(),
             quic_server_address: "127.0.0.1:8080".to_string(),
             quic_server_name: "localhost".to_string(),
@@ -215,6 +227,7 @@ impl Default for Args {
             quic_max_idle_timeout: 10000,
             quic_validate_certificate: false,
             quic_heartbeat_interval: "5s".to_string(),
+            quic_compression: None,
         }
     }
 }
@@ -226,6 +239,9 @@ impl Args {
             encryption_key: self.encryption_key.clone(),
             http_api_url: self.http_api_url.clone(),
             http_retries: self.http_retries,
+            http_tls_ca_file: None,
+            http_tls_certificate_file: None,
+            http_tls_key_file: None,
             username: self.username.clone(),
             password: self.password.clone(),
             tcp_server_address: self.tcp_server_address.clone(),
@@ -237,7 +253,11 @@ impl Args {
             tcp_tls_enabled: self.tcp_tls_enabled,
             tcp_tls_domain: self.tcp_tls_domain.clone(),
             tcp_tls_ca_file: None,
+            tcp_tls_certificate_file: None,
+            tcp_tls_key_file: None,
             tcp_nodelay: self.tcp_nodelay,
+            tcp_multiplexing: self.tcp_multiplexing,
+            tcp_compression: self.tcp_compression,
             quic_client_address: self.quic_client_address.clone(),
             quic_server_address: self.quic_server_address.clone(),
             quic_server_name: self.quic_server_name.clone(),
@@ -254,7 +274,10 @@ impl Args {
             quic_keep_alive_interval: self.quic_keep_alive_interval,
             quic_max_idle_timeout: self.quic_max_idle_timeout,
             quic_validate_certificate: self.quic_validate_certificate,
+            quic_client_certificate_file: None,
+            quic_client_key_file: None,
             quic_heartbeat_interval: self.quic_heartbeat_interval.clone(),
+            quic_compression: self.quic_compression,
         }
     }
 
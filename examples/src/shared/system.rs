@@ -22,8 +22,9 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use tracing::info;
@@ -90,7 +91,7 @@ pub async fn init_by_producer(args: &Args, client: &dyn Client) -> Result<(), Ig
     }
 
     info!("Stream does not exist, creating...");
-    client.create_stream(&args.stream_id, None).await?;
+    client.create_stream(&args.stream_id, None, None).await?;
     client
         .create_topic(
             &stream_id,
@@ -101,6 +102,7 @@ pub async fn init_by_producer(args: &Args, client: &dyn Client) -> Result<(), Ig
             None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await?;
     Ok(())
@@ -143,6 +145,7 @@ pub async fn consume_messages(
                 &PollingStrategy::next(),
                 args.messages_per_batch,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await?;
         if polled_messages.messages.is_empty() {
@@ -23,16 +23,26 @@ use iggy::http::client::HttpClient;
 use iggy::http::config::HttpClientConfig;
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct HttpClientFactory {
     pub server_addr: String,
+    pub tls_ca_file: Option<String>,
+    pub tls_certificate_file: Option<String>,
+    pub tls_key_file: Option<String>,
 }
 
 #[async_trait]
 impl ClientFactory for HttpClientFactory {
     async fn create_client(&self) -> Box<dyn Client> {
+        let scheme = match self.tls_ca_file {
+            Some(_) => "https",
+            None => "http",
+        };
         let config = HttpClientConfig {
-            api_url: format!("http://{}", self.server_addr.clone()),
+            api_url: format!("{scheme}://{}", self.server_addr.clone()),
+            tls_ca_file: self.tls_ca_file.clone(),
+            tls_certificate_file: self.tls_certificate_file.clone(),
+            tls_key_file: self.tls_key_file.clone(),
             ..HttpClientConfig::default()
         };
         let client = HttpClient::create(Arc::new(config)).unwrap();
@@ -23,5 +23,9 @@ pub mod http_client;
 pub mod quic_client;
 #[allow(deprecated)]
 pub mod tcp_client;
+pub mod test_certificates;
+pub mod test_identity_provider;
 #[allow(deprecated)]
 pub mod test_server;
+#[allow(deprecated)]
+pub mod websocket_client;
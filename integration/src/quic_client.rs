@@ -19,13 +19,17 @@
 use crate::test_server::ClientFactory;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
 use iggy::quic::client::QuicClient;
 use iggy::quic::config::QuicClientConfig;
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct QuicClientFactory {
     pub server_addr: String,
+    pub compression: Option<WireCompressionAlgorithm>,
+    pub client_certificate_file: Option<String>,
+    pub client_key_file: Option<String>,
 }
 
 #[async_trait]
@@ -33,6 +37,9 @@ impl ClientFactory for QuicClientFactory {
     async fn create_client(&self) -> Box<dyn Client> {
         let config = QuicClientConfig {
             server_address: self.server_addr.clone(),
+            compression: self.compression,
+            client_certificate_file: self.client_certificate_file.clone(),
+            client_key_file: self.client_key_file.clone(),
             ..QuicClientConfig::default()
         };
         let client = QuicClient::create(Arc::new(config)).unwrap();
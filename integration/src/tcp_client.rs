@@ -18,15 +18,23 @@
 
 use crate::test_server::ClientFactory;
 use async_trait::async_trait;
-use iggy::client::Client;
+use iggy::client::{AutoLogin, Client, Credentials};
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
 use iggy::tcp::client::TcpClient;
 use iggy::tcp::config::TcpClientConfig;
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Default)]
 pub struct TcpClientFactory {
     pub server_addr: String,
     pub nodelay: bool,
+    pub multiplexing: bool,
+    pub compression: Option<WireCompressionAlgorithm>,
+    pub auto_login: bool,
+    pub tls_ca_file: Option<String>,
+    pub tls_certificate_file: Option<String>,
+    pub tls_key_file: Option<String>,
 }
 
 #[async_trait]
@@ -35,6 +43,20 @@ impl ClientFactory for TcpClientFactory {
         let config = TcpClientConfig {
             server_address: self.server_addr.clone(),
             nodelay: self.nodelay,
+            multiplexing: self.multiplexing,
+            compression: self.compression,
+            tls_enabled: self.tls_ca_file.is_some(),
+            tls_ca_file: self.tls_ca_file.clone(),
+            tls_certificate_file: self.tls_certificate_file.clone(),
+            tls_key_file: self.tls_key_file.clone(),
+            auto_login: if self.auto_login {
+                AutoLogin::Enabled(Credentials::UsernamePassword(
+                    DEFAULT_ROOT_USERNAME.to_string(),
+                    DEFAULT_ROOT_PASSWORD.to_string(),
+                ))
+            } else {
+                AutoLogin::Disabled
+            },
             ..TcpClientConfig::default()
         };
         let client = TcpClient::create(Arc::new(config)).unwrap_or_else(|e| {
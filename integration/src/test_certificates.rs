@@ -0,0 +1,132 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose,
+};
+use std::fs;
+use tempfile::TempDir;
+
+const CA_NAME: &str = "ca";
+const SERVER_NAME: &str = "server";
+
+/// The certificate authority with the server and client certificates signed by it,
+/// stored as the PEM files in the temporary directory removed on drop.
+pub struct TestCertificates {
+    directory: TempDir,
+}
+
+impl TestCertificates {
+    /// Generates the CA, the server certificate for `localhost` and the client certificate
+    /// for each of the given names, used as both, the common name and the subject alt name.
+    pub fn generate(client_names: &[&str]) -> Self {
+        let directory = tempfile::tempdir().unwrap();
+        let certificates = Self { directory };
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "Iggy Test CA");
+        ca_params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        certificates.write(CA_NAME, &ca, &ca_key);
+
+        let server_names = vec!["localhost".to_owned(), "127.0.0.1".to_owned()];
+        certificates.generate_signed(
+            SERVER_NAME,
+            server_names,
+            ExtendedKeyUsagePurpose::ServerAuth,
+            &ca,
+            &ca_key,
+        );
+        for name in client_names {
+            certificates.generate_signed(
+                name,
+                vec![name.to_string()],
+                ExtendedKeyUsagePurpose::ClientAuth,
+                &ca,
+                &ca_key,
+            );
+        }
+        certificates
+    }
+
+    pub fn ca_file(&self) -> String {
+        self.get_cert_file(CA_NAME)
+    }
+
+    pub fn server_cert_file(&self) -> String {
+        self.get_cert_file(SERVER_NAME)
+    }
+
+    pub fn server_key_file(&self) -> String {
+        self.get_key_file(SERVER_NAME)
+    }
+
+    pub fn client_cert_file(&self, name: &str) -> String {
+        self.get_cert_file(name)
+    }
+
+    pub fn client_key_file(&self, name: &str) -> String {
+        self.get_key_file(name)
+    }
+
+    fn generate_signed(
+        &self,
+        name: &str,
+        subject_alt_names: Vec<String>,
+        usage: ExtendedKeyUsagePurpose,
+        ca: &Certificate,
+        ca_key: &KeyPair,
+    ) {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(subject_alt_names).unwrap();
+        params.distinguished_name.push(DnType::CommonName, name);
+        params.extended_key_usages = vec![usage];
+        let certificate = params.signed_by(&key, ca, ca_key).unwrap();
+        self.write(name, &certificate, &key);
+    }
+
+    fn write(&self, name: &str, certificate: &Certificate, key: &KeyPair) {
+        fs::write(self.get_cert_file(name), certificate.pem()).unwrap();
+        fs::write(self.get_key_file(name), key.serialize_pem()).unwrap();
+    }
+
+    fn get_cert_file(&self, name: &str) -> String {
+        self.get_path(&format!("{name}_cert.pem"))
+    }
+
+    fn get_key_file(&self, name: &str) -> String {
+        self.get_path(&format!("{name}_key.pem"))
+    }
+
+    fn get_path(&self, file: &str) -> String {
+        self.directory
+            .path()
+            .join(file)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
@@ -0,0 +1,115 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use rcgen::KeyPair;
+use serde_json::{json, Value};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+
+pub const TEST_OIDC_AUDIENCE: &str = "iggy";
+const KEY_ID: &str = "test-key";
+
+/// The local stand-in for the OIDC identity provider, issuing the tokens signed with the generated
+/// EC P-256 key, whose public part is published as the JWKS file in the temporary directory removed on drop.
+pub struct TestIdentityProvider {
+    issuer: String,
+    key: EncodingKey,
+    directory: TempDir,
+}
+
+impl TestIdentityProvider {
+    pub fn generate(issuer: &str) -> Self {
+        let directory = tempfile::tempdir().unwrap();
+        let key_pair = KeyPair::generate().unwrap();
+        // The raw public key of P-256 is the uncompressed point: 0x04 || x || y.
+        let public_key = key_pair.public_key_raw();
+        let jwks = json!({
+            "keys": [{
+                "kty": "EC",
+                "crv": "P-256",
+                "alg": "ES256",
+                "use": "sig",
+                "kid": KEY_ID,
+                "x": URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+            }]
+        });
+        let provider = Self {
+            issuer: issuer.to_owned(),
+            key: EncodingKey::from_ec_pem(key_pair.serialize_pem().as_bytes()).unwrap(),
+            directory,
+        };
+        fs::write(provider.jwks_file(), jwks.to_string()).unwrap();
+        provider
+    }
+
+    /// Returns the trusted issuer in the `<issuer>|<jwks>` format expected by the server configuration.
+    pub fn issuer_config(&self) -> String {
+        format!("{}|{}", self.issuer, self.jwks_file())
+    }
+
+    pub fn jwks_file(&self) -> String {
+        self.directory
+            .path()
+            .join("jwks.json")
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    /// Issues the token for the given username and roles, valid for the next hour.
+    /// The username is used as the subject of the token too.
+    pub fn issue_token(&self, username: &str, roles: &[&str]) -> String {
+        self.issue(username, username, roles, Self::now() + 3600)
+    }
+
+    /// Issues the token for the given subject, username and roles, valid for the next hour.
+    pub fn issue_token_for_subject(&self, subject: &str, username: &str, roles: &[&str]) -> String {
+        self.issue(subject, username, roles, Self::now() + 3600)
+    }
+
+    pub fn issue_expired_token(&self, username: &str, roles: &[&str]) -> String {
+        self.issue(username, username, roles, Self::now() - 3600)
+    }
+
+    fn issue(&self, subject: &str, username: &str, roles: &[&str], expiry: u64) -> String {
+        let claims: Value = json!({
+            "iss": self.issuer,
+            "aud": TEST_OIDC_AUDIENCE,
+            "sub": subject,
+            "iat": Self::now(),
+            "exp": expiry,
+            "preferred_username": username,
+            "groups": roles,
+        });
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(KEY_ID.to_owned());
+        encode(&header, &claims, &self.key).unwrap()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
@@ -0,0 +1,55 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::test_server::ClientFactory;
+use async_trait::async_trait;
+use iggy::client::{AutoLogin, Client, Credentials};
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::websocket::client::WebSocketClient;
+use iggy::websocket::config::WebSocketClientConfig;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketClientFactory {
+    pub server_addr: String,
+    pub auto_login: bool,
+}
+
+#[async_trait]
+impl ClientFactory for WebSocketClientFactory {
+    async fn create_client(&self) -> Box<dyn Client> {
+        let config = WebSocketClientConfig {
+            server_address: format!("ws://{}/ws", self.server_addr),
+            auto_login: if self.auto_login {
+                AutoLogin::Enabled(Credentials::UsernamePassword(
+                    DEFAULT_ROOT_USERNAME.to_string(),
+                    DEFAULT_ROOT_PASSWORD.to_string(),
+                ))
+            } else {
+                AutoLogin::Disabled
+            },
+            ..WebSocketClientConfig::default()
+        };
+        let client = WebSocketClient::create(Arc::new(config)).unwrap();
+        iggy::client::Client::connect(&client).await.unwrap();
+        Box::new(client)
+    }
+}
+
+unsafe impl Send for WebSocketClientFactory {}
+unsafe impl Sync for WebSocketClientFactory {}
@@ -233,6 +233,10 @@ impl IggyCmdTest {
         test_case.verify_command(assert);
     }
 
+    pub(crate) fn get_local_data_path(&self) -> &str {
+        self.server.get_local_data_path()
+    }
+
     #[cfg(not(target_os = "macos"))]
     pub(crate) fn get_tcp_server_address(&self) -> Option<String> {
         self.server.get_raw_tcp_addr()
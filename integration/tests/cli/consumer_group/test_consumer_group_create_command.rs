@@ -23,6 +23,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -35,6 +37,7 @@ struct TestConsumerGroupCreateCmd {
     topic_name: String,
     group_id: Option<u32>,
     group_name: String,
+    assignment_strategy: Option<AssignmentStrategy>,
     using_stream_id: TestStreamId,
     using_topic_id: TestTopicId,
 }
@@ -48,6 +51,7 @@ impl TestConsumerGroupCreateCmd {
         topic_name: String,
         group_id: Option<u32>,
         group_name: String,
+        assignment_strategy: Option<AssignmentStrategy>,
         using_stream_id: TestStreamId,
         using_topic_id: TestTopicId,
     ) -> Self {
@@ -58,6 +62,7 @@ impl TestConsumerGroupCreateCmd {
             topic_name,
             group_id,
             group_name,
+            assignment_strategy,
             using_stream_id,
             using_topic_id,
         }
@@ -79,6 +84,11 @@ impl TestConsumerGroupCreateCmd {
             command.push(format!("{}", group_id));
         }
 
+        if let Some(assignment_strategy) = self.assignment_strategy {
+            command.push("-a".to_string());
+            command.push(format!("{}", assignment_strategy));
+        }
+
         command.push(self.group_name.clone());
 
         command
@@ -89,7 +99,7 @@ impl TestConsumerGroupCreateCmd {
 impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, self.stream_id.into())
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
             .await;
         assert!(stream.is_ok());
 
@@ -103,6 +113,7 @@ impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -132,8 +143,10 @@ impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
             None => "ID auto incremented".to_string(),
         };
 
-        let message = format!("Executing create consumer group: {}, name: {} for topic with ID: {} and stream with ID: {}\nConsumer group: {}, name: {} created for topic with ID: {} and stream with ID: {}\n",
-                              group_id, self.group_name, topic_id, stream_id, group_id, self.group_name, topic_id, stream_id);
+        let assignment_strategy = self.assignment_strategy.unwrap_or_default();
+
+        let message = format!("Executing create consumer group: {}, name: {}, assignment strategy: {} for topic with ID: {} and stream with ID: {}\nConsumer group: {}, name: {} created for topic with ID: {} and stream with ID: {}\n",
+                              group_id, self.group_name, assignment_strategy, topic_id, stream_id, group_id, self.group_name, topic_id, stream_id);
 
         command_state.success().stdout(diff(message));
     }
@@ -154,6 +167,10 @@ impl IggyCmdTestCase for TestConsumerGroupCreateCmd {
         if let Some(group_id) = self.group_id {
             assert_eq!(consumer_group_details.id, group_id);
         }
+        assert_eq!(
+            consumer_group_details.assignment_strategy,
+            self.assignment_strategy.unwrap_or_default()
+        );
 
         let topic = client
             .delete_topic(
@@ -184,6 +201,7 @@ pub async fn should_be_successful() {
             String::from("sync"),
             Some(1),
             String::from("group1"),
+            None,
             TestStreamId::Numeric,
             TestTopicId::Numeric,
         ))
@@ -196,6 +214,7 @@ pub async fn should_be_successful() {
             String::from("topic"),
             Some(3),
             String::from("group3"),
+            Some(AssignmentStrategy::Range),
             TestStreamId::Named,
             TestTopicId::Numeric,
         ))
@@ -208,6 +227,7 @@ pub async fn should_be_successful() {
             String::from("probe"),
             Some(7),
             String::from("group7"),
+            Some(AssignmentStrategy::RoundRobin),
             TestStreamId::Numeric,
             TestTopicId::Named,
         ))
@@ -220,6 +240,7 @@ pub async fn should_be_successful() {
             String::from("test"),
             Some(4),
             String::from("group4"),
+            Some(AssignmentStrategy::Sticky),
             TestStreamId::Named,
             TestTopicId::Named,
         ))
@@ -246,6 +267,7 @@ Examples:
  iggy consumer-group create stream 2 test
  iggy consumer-group create 2 topic receiver
  iggy consumer-group create -g 4 stream topic group
+ iggy consumer-group create -a range stream topic group
 
 {USAGE_PREFIX} consumer-group create [OPTIONS] <STREAM_ID> <TOPIC_ID> <NAME>
 
@@ -267,6 +289,13 @@ Options:
   -g, --group-id <GROUP_ID>
           Consumer group ID to create
 
+  -a, --assignment-strategy <ASSIGNMENT_STRATEGY>
+          Strategy of assigning the partitions to the consumer group members
+{CLAP_INDENT}
+          Available strategies are "range", "round_robin" and "sticky"
+{CLAP_INDENT}
+          [default: sticky]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -294,8 +323,12 @@ Arguments:
   <NAME>       Consumer group name to create
 
 Options:
-  -g, --group-id <GROUP_ID>  Consumer group ID to create
-  -h, --help                 Print help (see more with '--help')
+  -g, --group-id <GROUP_ID>
+          Consumer group ID to create
+  -a, --assignment-strategy <ASSIGNMENT_STRATEGY>
+          Strategy of assigning the partitions to the consumer group members [default: sticky]
+  -h, --help
+          Print help (see more with '--help')
 "#,
             ),
         ))
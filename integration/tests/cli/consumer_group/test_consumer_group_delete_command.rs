@@ -23,6 +23,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -90,7 +92,7 @@ impl TestConsumerGroupDeleteCmd {
 impl IggyCmdTestCase for TestConsumerGroupDeleteCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, Some(self.stream_id))
+            .create_stream(&self.stream_name, Some(self.stream_id), None)
             .await;
         assert!(stream.is_ok());
 
@@ -104,6 +106,7 @@ impl IggyCmdTestCase for TestConsumerGroupDeleteCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -114,6 +117,7 @@ impl IggyCmdTestCase for TestConsumerGroupDeleteCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.group_name,
                 Some(self.group_id),
+                AssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
@@ -23,6 +23,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -90,7 +92,7 @@ impl TestConsumerGroupGetCmd {
 impl IggyCmdTestCase for TestConsumerGroupGetCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, Some(self.stream_id))
+            .create_stream(&self.stream_name, Some(self.stream_id), None)
             .await;
         assert!(stream.is_ok());
 
@@ -104,6 +106,7 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -114,6 +117,7 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.group_name,
                 self.group_id.into(),
+                AssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
@@ -155,6 +159,10 @@ impl IggyCmdTestCase for TestConsumerGroupGetCmd {
             .stdout(contains(format!(
                 "Consumer group name | {}",
                 self.group_name
+            )))
+            .stdout(contains(format!(
+                "Assignment strategy | {}",
+                AssignmentStrategy::default()
             )));
     }
 
@@ -23,6 +23,8 @@ use crate::cli::common::{
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -87,7 +89,7 @@ impl TestConsumerGroupListCmd {
 impl IggyCmdTestCase for TestConsumerGroupListCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, self.stream_id.into())
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
             .await;
         assert!(stream.is_ok());
 
@@ -101,6 +103,7 @@ impl IggyCmdTestCase for TestConsumerGroupListCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -111,6 +114,7 @@ impl IggyCmdTestCase for TestConsumerGroupListCmd {
                 &self.topic_id.try_into().unwrap(),
                 &self.consumer_group_name,
                 self.consumer_group_id.into(),
+                AssignmentStrategy::default(),
             )
             .await;
         assert!(consumer_group.is_ok());
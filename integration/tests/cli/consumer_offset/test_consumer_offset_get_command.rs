@@ -26,6 +26,7 @@ use iggy::client::Client;
 use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::identifier::Identifier;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -103,7 +104,7 @@ impl TestConsumerOffsetGetCmd {
 impl IggyCmdTestCase for TestConsumerOffsetGetCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, self.stream_id.into())
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
             .await;
         assert!(stream.is_ok());
 
@@ -117,6 +118,7 @@ impl IggyCmdTestCase for TestConsumerOffsetGetCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -149,6 +151,7 @@ impl IggyCmdTestCase for TestConsumerOffsetGetCmd {
                 &self.topic_id.try_into().unwrap(),
                 Some(self.partition_id),
                 self.stored_offset,
+                None,
             )
             .await;
         assert!(offset.is_ok());
@@ -0,0 +1,293 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, TestStreamId, TestTopicId,
+    CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::{contains, starts_with};
+use serial_test::parallel;
+use std::str::FromStr;
+
+struct TestConsumerOffsetLagCmd {
+    consumer_name: String,
+    stream_id: u32,
+    stream_name: String,
+    topic_id: u32,
+    topic_name: String,
+    partition_id: u32,
+    using_stream_id: TestStreamId,
+    using_topic_id: TestTopicId,
+    messages_count: u32,
+    stored_offset: u64,
+}
+
+impl TestConsumerOffsetLagCmd {
+    fn new(
+        consumer_name: String,
+        stream_id: u32,
+        stream_name: String,
+        topic_id: u32,
+        topic_name: String,
+        using_stream_id: TestStreamId,
+        using_topic_id: TestTopicId,
+    ) -> Self {
+        Self {
+            consumer_name,
+            stream_id,
+            stream_name,
+            topic_id,
+            topic_name,
+            partition_id: 1,
+            using_stream_id,
+            using_topic_id,
+            messages_count: 100,
+            stored_offset: 66,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut command = vec![self.consumer_name.clone()];
+
+        command.push(match self.using_stream_id {
+            TestStreamId::Numeric => format!("{}", self.stream_id),
+            TestStreamId::Named => self.stream_name.clone(),
+        });
+
+        command.push(match self.using_topic_id {
+            TestTopicId::Numeric => format!("{}", self.topic_id),
+            TestTopicId::Named => self.topic_name.clone(),
+        });
+
+        command
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestConsumerOffsetLagCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
+            .await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_name,
+                1,
+                Default::default(),
+                None,
+                Some(self.topic_id),
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        let mut messages = (1..=self.messages_count)
+            .filter_map(|id| Message::from_str(format!("Test message {id}").as_str()).ok())
+            .collect::<Vec<_>>();
+
+        let send_status = client
+            .send_messages(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                &Partitioning::partition_id(self.partition_id),
+                &mut messages,
+            )
+            .await;
+        assert!(send_status.is_ok());
+
+        let offset = client
+            .store_consumer_offset(
+                &Consumer::new(Identifier::named(self.consumer_name.as_str()).unwrap()),
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                Some(self.partition_id),
+                self.stored_offset,
+                None,
+            )
+            .await;
+        assert!(offset.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("consumer-offset")
+            .arg("lag")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let stream_id = match self.using_stream_id {
+            TestStreamId::Numeric => format!("{}", self.stream_id),
+            TestStreamId::Named => self.stream_name.clone(),
+        };
+
+        let topic_id = match self.using_topic_id {
+            TestTopicId::Numeric => format!("{}", self.topic_id),
+            TestTopicId::Named => self.topic_name.clone(),
+        };
+
+        let message = format!(
+            "Executing get consumer lag for consumer with ID: {} for stream with ID: {} and topic with ID: {}",
+            self.consumer_name, stream_id, topic_id,
+        );
+        let lag_messages = self.messages_count as u64 - 1 - self.stored_offset;
+
+        command_state
+            .success()
+            .stdout(starts_with(message))
+            .stdout(contains(format!("| {} ", self.stored_offset)))
+            .stdout(contains(format!("| {} ", self.messages_count - 1)))
+            .stdout(contains(format!("| {lag_messages} ")));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let topic = client
+            .delete_topic(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        let stream = client
+            .delete_stream(&self.stream_id.try_into().unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    let test_parameters = vec![
+        (TestStreamId::Numeric, TestTopicId::Numeric),
+        (TestStreamId::Named, TestTopicId::Numeric),
+        (TestStreamId::Numeric, TestTopicId::Named),
+        (TestStreamId::Named, TestTopicId::Named),
+    ];
+
+    iggy_cmd_test.setup().await;
+    for (using_stream_id, using_topic_id) in test_parameters {
+        iggy_cmd_test
+            .execute_test(TestConsumerOffsetLagCmd::new(
+                String::from("consumer"),
+                2,
+                String::from("stream"),
+                3,
+                String::from("topic"),
+                using_stream_id,
+                using_topic_id,
+            ))
+            .await;
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["consumer-offset", "lag", "--help"],
+            format!(
+                r#"Retrieve the lag of a consumer or a consumer group in each partition of a given topic
+
+Consumer ID can be specified as a consumer (or consumer group) name or ID
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+
+Examples:
+ iggy consumer-offset lag 1 3 5
+ iggy consumer-offset lag consumer stream topic
+ iggy consumer-offset lag --group 1 3 5
+ iggy consumer-offset lag -g group stream topic
+
+{USAGE_PREFIX} consumer-offset lag [OPTIONS] <CONSUMER_ID> <STREAM_ID> <TOPIC_ID>
+
+Arguments:
+  <CONSUMER_ID>
+          Consumer (or consumer group) for which the lag is retrieved
+{CLAP_INDENT}
+          Consumer ID can be specified as a consumer (or consumer group) name or ID
+
+  <STREAM_ID>
+          Stream ID for which consumer lag is retrieved
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          Topic ID for which consumer lag is retrieved
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+Options:
+  -g, --group
+          Retrieve the lag of the consumer group instead of the regular consumer
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["consumer-offset", "lag", "-h"],
+            format!(
+                r#"Retrieve the lag of a consumer or a consumer group in each partition of a given topic
+
+{USAGE_PREFIX} consumer-offset lag [OPTIONS] <CONSUMER_ID> <STREAM_ID> <TOPIC_ID>
+
+Arguments:
+  <CONSUMER_ID>  Consumer (or consumer group) for which the lag is retrieved
+  <STREAM_ID>    Stream ID for which consumer lag is retrieved
+  <TOPIC_ID>     Topic ID for which consumer lag is retrieved
+
+Options:
+  -g, --group  Retrieve the lag of the consumer group instead of the regular consumer
+  -h, --help   Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -24,6 +24,7 @@ use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
 use iggy::identifier::Identifier;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -94,7 +95,7 @@ impl TestMessageFetchCmd {
 #[async_trait]
 impl IggyCmdTestCase for TestMessageFetchCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
-        let stream = client.create_stream(&self.stream_name, None).await;
+        let stream = client.create_stream(&self.stream_name, None, None).await;
         assert!(stream.is_ok());
 
         let stream_id = Identifier::from_str(&self.stream_name);
@@ -111,6 +112,7 @@ impl IggyCmdTestCase for TestMessageFetchCmd {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
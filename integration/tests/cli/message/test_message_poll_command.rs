@@ -27,6 +27,7 @@ use iggy::client::Client;
 use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -127,7 +128,7 @@ impl TestMessagePollCmd {
 impl IggyCmdTestCase for TestMessagePollCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, self.stream_id.into())
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
             .await;
         assert!(stream.is_ok());
 
@@ -141,6 +142,7 @@ impl IggyCmdTestCase for TestMessagePollCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -425,6 +427,26 @@ Options:
           Flag indicates whether to include headers in the output
           after polling the messages.
 
+      --read-committed
+          Poll only the committed messages
+{CLAP_INDENT}
+          Flag indicates whether to skip the messages sent as a part
+          of the open or aborted transactions.
+
+      --filter <FILTER>
+          Poll only the messages matching the filter over their headers
+{CLAP_INDENT}
+          Filter is evaluated by the server, e.g. 'tenant == "acme" AND priority > 3'.
+          Comparisons with ==, !=, >, >=, < and <= can be combined with AND, OR
+          and parentheses. Messages without the header don't match the comparison.
+
+      --max-wait <MAX_WAIT>
+          Wait for the messages on the server if there are none yet
+{CLAP_INDENT}
+          Maximum time for which the server holds the request until
+          any message is available, e.g. "500ms" or "30s".
+          By default, the request is completed immediately.
+
       --output-file <OUTPUT_FILE>
           Store polled message into file in binary format
 {CLAP_INDENT}
@@ -470,6 +492,9 @@ Options:
   -n, --next                           Polling strategy - start polling from the next message
   -c, --consumer <CONSUMER>            Regular consumer which will poll messages [default: 1]
   -s, --show-headers                   Include the message headers in the output
+      --read-committed                 Poll only the committed messages
+      --filter <FILTER>                Poll only the messages matching the filter over their headers
+      --max-wait <MAX_WAIT>            Wait for the messages on the server if there are none yet
       --output-file <OUTPUT_FILE>      Store polled message into file in binary format
   -h, --help                           Print help (see more with '--help')
 "#,
@@ -25,6 +25,7 @@ use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, is_match, starts_with};
@@ -100,7 +101,7 @@ impl<'a> TestMessagePollToFileCmd<'a> {
 #[async_trait]
 impl IggyCmdTestCase for TestMessagePollToFileCmd<'_> {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
-        let stream = client.create_stream(&self.stream_name, None).await;
+        let stream = client.create_stream(&self.stream_name, None, None).await;
         assert!(stream.is_ok());
 
         let stream_id = Identifier::from_str(self.stream_name.as_str());
@@ -117,6 +118,7 @@ impl IggyCmdTestCase for TestMessagePollToFileCmd<'_> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
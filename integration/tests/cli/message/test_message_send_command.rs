@@ -24,8 +24,9 @@ use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
 use iggy::consumer::Consumer;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::diff;
@@ -160,7 +161,7 @@ impl TestMessageSendCmd {
 impl IggyCmdTestCase for TestMessageSendCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, self.stream_id.into())
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
             .await;
         assert!(stream.is_ok());
 
@@ -174,6 +175,7 @@ impl IggyCmdTestCase for TestMessageSendCmd {
                 Some(self.topic_id),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -231,6 +233,7 @@ impl IggyCmdTestCase for TestMessageSendCmd {
                 &PollingStrategy::offset(0),
                 self.messages.len() as u32,
                 false,
+                &PollMessagesOptions::default(),
             )
             .await;
 
@@ -24,9 +24,10 @@ use iggy::bytes_serializable::BytesSerializable;
 use iggy::client::Client;
 use iggy::consumer::Consumer;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::messages::send_messages::Message;
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{ends_with, is_match, starts_with};
@@ -85,7 +86,7 @@ impl<'a> TestMessageSendFromFileCmd<'a> {
 #[async_trait]
 impl IggyCmdTestCase for TestMessageSendFromFileCmd<'_> {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
-        let stream = client.create_stream(&self.stream_name, None).await;
+        let stream = client.create_stream(&self.stream_name, None, None).await;
         assert!(stream.is_ok());
 
         let stream_id = Identifier::from_str(self.stream_name.as_str());
@@ -102,6 +103,7 @@ impl IggyCmdTestCase for TestMessageSendFromFileCmd<'_> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -200,6 +202,7 @@ impl IggyCmdTestCase for TestMessageSendFromFileCmd<'_> {
                 &PollingStrategy::offset(0),
                 self.message_count as u32 * 2,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await;
         assert!(messages.is_ok());
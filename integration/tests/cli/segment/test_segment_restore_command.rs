@@ -0,0 +1,398 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, TestStreamId, TestTopicId,
+    CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use bytes::Bytes;
+use iggy::client::Client;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::diff;
+use serial_test::parallel;
+use std::path::Path;
+use tempfile::TempDir;
+
+const PARTITION_ID: u32 = 1;
+const SOURCE_TOPIC_NAME: &str = "source";
+
+struct TestSegmentRestoreCmd {
+    stream_id: u32,
+    stream_name: String,
+    source_topic_id: u32,
+    topic_id: u32,
+    topic_name: String,
+    messages_count: u32,
+    local_data_path: String,
+    archive_path: String,
+    using_stream_id: TestStreamId,
+    using_topic_id: TestTopicId,
+}
+
+impl TestSegmentRestoreCmd {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        stream_id: u32,
+        stream_name: String,
+        source_topic_id: u32,
+        topic_id: u32,
+        topic_name: String,
+        messages_count: u32,
+        local_data_path: String,
+        archive_path: String,
+        using_stream_id: TestStreamId,
+        using_topic_id: TestTopicId,
+    ) -> Self {
+        Self {
+            stream_id,
+            stream_name,
+            source_topic_id,
+            topic_id,
+            topic_name,
+            messages_count,
+            local_data_path,
+            archive_path,
+            using_stream_id,
+            using_topic_id,
+        }
+    }
+
+    fn get_stream_id(&self) -> String {
+        match self.using_stream_id {
+            TestStreamId::Numeric => format!("{}", self.stream_id),
+            TestStreamId::Named => self.stream_name.clone(),
+        }
+    }
+
+    fn get_topic_id(&self) -> String {
+        match self.using_topic_id {
+            TestTopicId::Numeric => format!("{}", self.topic_id),
+            TestTopicId::Named => self.topic_name.clone(),
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        vec![
+            self.get_stream_id(),
+            self.get_topic_id(),
+            format!("{PARTITION_ID}"),
+            format!("{}", self.stream_id),
+            format!("{}", self.source_topic_id),
+            format!("{PARTITION_ID}"),
+            String::from("--archive-path"),
+            self.archive_path.clone(),
+        ]
+    }
+
+    fn create_message_payload(offset: u32) -> Bytes {
+        Bytes::from(format!("message {offset}"))
+    }
+
+    /// Copies the segments of the source partition into the archive, the same way as the disk archiver does.
+    fn archive_source_partition(&self) {
+        let partition_path = format!(
+            "{}/streams/{}/topics/{}/partitions/{PARTITION_ID}",
+            self.local_data_path, self.stream_id, self.source_topic_id
+        );
+        let archived_partition_path =
+            Path::new(&self.archive_path).join(partition_path.trim_start_matches('/'));
+        std::fs::create_dir_all(&archived_partition_path).unwrap();
+        for entry in std::fs::read_dir(&partition_path).unwrap() {
+            let path = entry.unwrap().path();
+            let is_segment_file = path
+                .extension()
+                .is_some_and(|extension| extension == "log" || extension == "index");
+            if is_segment_file {
+                std::fs::copy(
+                    &path,
+                    archived_partition_path.join(path.file_name().unwrap()),
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestSegmentRestoreCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client
+            .create_stream(&self.stream_name, self.stream_id.into(), None)
+            .await;
+        assert!(stream.is_ok());
+
+        for (topic_id, topic_name) in [
+            (self.source_topic_id, SOURCE_TOPIC_NAME),
+            (self.topic_id, self.topic_name.as_str()),
+        ] {
+            let topic = client
+                .create_topic(
+                    &self.stream_id.try_into().unwrap(),
+                    topic_name,
+                    1,
+                    Default::default(),
+                    None,
+                    Some(topic_id),
+                    IggyExpiry::NeverExpire,
+                    MaxTopicSize::ServerDefault,
+                    CleanupPolicy::default(),
+                )
+                .await;
+            assert!(topic.is_ok());
+        }
+
+        let mut messages = (0..self.messages_count)
+            .map(|offset| {
+                let payload = Self::create_message_payload(offset);
+                Message::new(None, payload, None)
+            })
+            .collect::<Vec<_>>();
+        let send_status = client
+            .send_messages(
+                &self.stream_id.try_into().unwrap(),
+                &self.source_topic_id.try_into().unwrap(),
+                &Partitioning::partition_id(PARTITION_ID),
+                &mut messages,
+            )
+            .await;
+        assert!(send_status.is_ok());
+
+        let flush_status = client
+            .flush_unsaved_buffer(
+                &self.stream_id.try_into().unwrap(),
+                &self.source_topic_id.try_into().unwrap(),
+                PARTITION_ID,
+                true,
+            )
+            .await;
+        assert!(flush_status.is_ok());
+        self.archive_source_partition();
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("segment")
+            .arg("restore")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let stream_id = self.get_stream_id();
+        let topic_id = self.get_topic_id();
+        let range = format!("offset range: 0 - {}", u64::MAX);
+        let message = format!(
+            "Executing restore segments with {range} archived for stream with ID: {}, topic with ID: {} and partition with ID: {PARTITION_ID} \
+            into topic with ID: {topic_id}, stream with ID: {stream_id} and partition with ID: {PARTITION_ID}\n\
+            Restored segments with {range} into topic with ID: {topic_id}, stream with ID: {stream_id} and partition with ID: {PARTITION_ID}\n",
+            self.stream_id, self.source_topic_id
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let polled_messages = client
+            .poll_messages(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                Some(PARTITION_ID),
+                &Consumer::default(),
+                &PollingStrategy::offset(0),
+                self.messages_count,
+                false,
+                &PollMessagesOptions::default(),
+            )
+            .await;
+        assert!(polled_messages.is_ok());
+        let polled_messages = polled_messages.unwrap();
+        assert_eq!(polled_messages.messages.len() as u32, self.messages_count);
+        for (offset, message) in polled_messages.messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            assert_eq!(message.payload, Self::create_message_payload(offset as u32));
+        }
+
+        let delete_stream = client
+            .delete_stream(&Identifier::numeric(self.stream_id).unwrap())
+            .await;
+        assert!(delete_stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+    let archive = TempDir::new().unwrap();
+    let archive_path = archive.path().to_str().unwrap().to_string();
+    let local_data_path = iggy_cmd_test.get_local_data_path().to_string();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestSegmentRestoreCmd::new(
+            1,
+            String::from("main"),
+            1,
+            2,
+            String::from("restored"),
+            10,
+            local_data_path.clone(),
+            archive_path.clone(),
+            TestStreamId::Numeric,
+            TestTopicId::Numeric,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestSegmentRestoreCmd::new(
+            2,
+            String::from("backup"),
+            1,
+            2,
+            String::from("restored"),
+            25,
+            local_data_path,
+            archive_path,
+            TestStreamId::Named,
+            TestTopicId::Named,
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["segment", "restore", "--help"],
+            format!(
+                r#"Restore archived segments into the specified topic ID,
+stream ID and partition ID.
+
+Segments archived for the source stream, topic and partition
+containing the messages from the given offset or timestamp range
+are restored with their original offsets, their checksums
+are validated and indexes rebuilt.
+
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+
+Examples
+ iggy segment restore 1 1 1 1 1 1
+ iggy segment restore prod sensor 1 1 2 1 --start 1000 --end 2000
+ iggy segment restore test sensor 1 1 1 1 --kind timestamp --start 1700000000000000
+ iggy segment restore 2 1 1 1 1 1 --archive-path /var/backup/archive
+
+{USAGE_PREFIX} segment restore [OPTIONS] <STREAM_ID> <TOPIC_ID> <PARTITION_ID> <SOURCE_STREAM_ID> <SOURCE_TOPIC_ID> <SOURCE_PARTITION_ID>
+
+Arguments:
+  <STREAM_ID>
+          Stream ID to restore segments into
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          Topic ID to restore segments into
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+  <PARTITION_ID>
+          Partition ID to restore segments into
+
+  <SOURCE_STREAM_ID>
+          Numeric ID of the stream the segments were archived for
+
+  <SOURCE_TOPIC_ID>
+          Numeric ID of the topic the segments were archived for
+
+  <SOURCE_PARTITION_ID>
+          Numeric ID of the partition the segments were archived for
+
+Options:
+  -k, --kind <KIND>
+          Kind of the range of the messages to restore
+{CLAP_INDENT}
+          Possible values: offset (o), timestamp (t)
+{CLAP_INDENT}
+          [default: offset]
+
+  -s, --start <START>
+          Start of the range (inclusive), offset or timestamp in microseconds
+{CLAP_INDENT}
+          [default: 0]
+
+  -e, --end <END>
+          End of the range (inclusive), offset or timestamp in microseconds
+{CLAP_INDENT}
+          [default: 18446744073709551615]
+
+  -a, --archive-path <ARCHIVE_PATH>
+          Path to the directory on the server containing the archive written by the disk archiver
+{CLAP_INDENT}
+          If not specified, the archiver configured on the server is used
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["segment", "restore", "-h"],
+            format!(
+                r#"Restore archived segments into the specified topic ID,
+stream ID and partition ID.
+
+{USAGE_PREFIX} segment restore [OPTIONS] <STREAM_ID> <TOPIC_ID> <PARTITION_ID> <SOURCE_STREAM_ID> <SOURCE_TOPIC_ID> <SOURCE_PARTITION_ID>
+
+Arguments:
+  <STREAM_ID>            Stream ID to restore segments into
+  <TOPIC_ID>             Topic ID to restore segments into
+  <PARTITION_ID>         Partition ID to restore segments into
+  <SOURCE_STREAM_ID>     Numeric ID of the stream the segments were archived for
+  <SOURCE_TOPIC_ID>      Numeric ID of the topic the segments were archived for
+  <SOURCE_PARTITION_ID>  Numeric ID of the partition the segments were archived for
+
+Options:
+  -k, --kind <KIND>                  Kind of the range of the messages to restore [default: offset]
+  -s, --start <START>                Start of the range (inclusive), offset or timestamp in microseconds [default: 0]
+  -e, --end <END>                    End of the range (inclusive), offset or timestamp in microseconds [default: 18446744073709551615]
+  -a, --archive-path <ARCHIVE_PATH>  Path to the directory on the server containing the archive written by the disk archiver
+  -h, --help                         Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
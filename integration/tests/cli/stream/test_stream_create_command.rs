@@ -16,21 +16,37 @@
  * under the License.
  */
 
-use crate::cli::common::{IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, USAGE_PREFIX};
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
 use assert_cmd::assert::Assert;
 use async_trait::async_trait;
 use iggy::client::Client;
+use iggy::models::stream_limits::StreamLimits;
 use predicates::str::diff;
 use serial_test::parallel;
 
 struct TestStreamCreateCmd {
     stream_id: Option<u32>,
     name: String,
+    limits: Option<StreamLimits>,
 }
 
 impl TestStreamCreateCmd {
     fn new(stream_id: Option<u32>, name: String) -> Self {
-        Self { stream_id, name }
+        Self {
+            stream_id,
+            name,
+            limits: None,
+        }
+    }
+
+    fn with_limits(stream_id: Option<u32>, name: String, limits: StreamLimits) -> Self {
+        Self {
+            stream_id,
+            name,
+            limits: Some(limits),
+        }
     }
 
     fn to_args(&self) -> Vec<String> {
@@ -41,6 +57,17 @@ impl TestStreamCreateCmd {
             args.push(format!("{}", stream_id));
         }
 
+        if let Some(limits) = &self.limits {
+            if let Some(max_topics) = limits.max_topics {
+                args.push("--max-topics".to_string());
+                args.push(format!("{}", max_topics));
+            }
+            if let Some(max_partitions) = limits.max_partitions {
+                args.push("--max-partitions".to_string());
+                args.push(format!("{}", max_partitions));
+            }
+        }
+
         args.push(self.name.clone());
 
         args
@@ -64,6 +91,10 @@ impl IggyCmdTestCase for TestStreamCreateCmd {
             Some(stream_id) => format!("ID: {}", stream_id),
             None => "ID auto incremented".to_string(),
         };
+        let stream_id = match self.limits {
+            Some(limits) => format!("{stream_id} and limits: {limits}"),
+            None => stream_id,
+        };
 
         let message = format!(
             "Executing create stream with name: {} and {}\nStream with name: {} and {} created\n",
@@ -105,6 +136,19 @@ pub async fn should_be_successful() {
     iggy_cmd_test
         .execute_test(TestStreamCreateCmd::new(None, String::from("prod")))
         .await;
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestStreamCreateCmd::with_limits(
+            Some(7),
+            String::from("tenant"),
+            StreamLimits {
+                max_size: None,
+                max_topics: Some(5),
+                max_partitions: Some(50),
+            },
+        ))
+        .await;
 }
 
 #[tokio::test]
@@ -123,6 +167,7 @@ If stream ID is not provided then the server will automatically assign it
 Examples:
  iggy stream create prod
  iggy stream create -s 1 test
+ iggy stream create --max-size 10GB --max-topics 5 --max-partitions 50 tenant
 
 {USAGE_PREFIX} stream create [OPTIONS] <NAME>
 
@@ -134,6 +179,22 @@ Options:
   -s, --stream-id <STREAM_ID>
           Stream ID to create
 
+      --max-size <MAX_SIZE>
+          Maximum size of all the topics in the stream
+{CLAP_INDENT}
+          Can be specified in bytes or with a unit (e.g. 10GB, 512MiB).
+          The value of 0 means that the size is unlimited.
+
+      --max-topics <MAX_TOPICS>
+          Maximum number of the topics in the stream
+{CLAP_INDENT}
+          The value of 0 means that the number of topics is unlimited.
+
+      --max-partitions <MAX_PARTITIONS>
+          Maximum number of the partitions across all the topics in the stream
+{CLAP_INDENT}
+          The value of 0 means that the number of partitions is unlimited.
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -159,8 +220,16 @@ Arguments:
   <NAME>  Name of the stream
 
 Options:
-  -s, --stream-id <STREAM_ID>  Stream ID to create
-  -h, --help                   Print help (see more with '--help')
+  -s, --stream-id <STREAM_ID>
+          Stream ID to create
+      --max-size <MAX_SIZE>
+          Maximum size of all the topics in the stream
+      --max-topics <MAX_TOPICS>
+          Maximum number of the topics in the stream
+      --max-partitions <MAX_PARTITIONS>
+          Maximum number of the partitions across all the topics in the stream
+  -h, --help
+          Print help (see more with '--help')
 "#,
             ),
         ))
@@ -35,7 +35,7 @@ pub async fn should_help_match() {
 Commands:
   create  Create stream with given name [aliases: c]
   delete  Delete stream with given ID [aliases: d]
-  update  Update stream name for given stream ID [aliases: u]
+  update  Update stream name and limits for given stream ID [aliases: u]
   get     Get details of a single stream with given ID [aliases: g]
   list    List all streams [aliases: l]
   purge   Purge all topics in given stream ID [aliases: p]
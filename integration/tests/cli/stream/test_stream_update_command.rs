@@ -56,7 +56,9 @@ impl TestStreamUpdateCmd {
 #[async_trait]
 impl IggyCmdTestCase for TestStreamUpdateCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
-        let stream = client.create_stream(&self.name, Some(self.stream_id)).await;
+        let stream = client
+            .create_stream(&self.name, Some(self.stream_id), None)
+            .await;
         assert!(stream.is_ok());
     }
 
@@ -118,15 +120,18 @@ pub async fn should_help_match() {
         .execute_test_for_help_command(TestHelpCmd::new(
             vec!["stream", "update", "--help"],
             format!(
-                r#"Update stream name for given stream ID
+                r#"Update stream name and limits for given stream ID
 
 Stream ID can be specified as a stream name or ID
+If any of the limits is provided, all the limits are replaced
+and the omitted ones become unlimited, otherwise the limits are not changed
 
 Examples:
  iggy stream update 1 production
  iggy stream update test development
+ iggy stream update --max-size 20GB --max-topics 10 tenant tenant
 
-{USAGE_PREFIX} stream update <STREAM_ID> <NAME>
+{USAGE_PREFIX} stream update [OPTIONS] <STREAM_ID> <NAME>
 
 Arguments:
   <STREAM_ID>
@@ -138,6 +143,22 @@ Arguments:
           New name for the stream
 
 Options:
+      --max-size <MAX_SIZE>
+          Maximum size of all the topics in the stream
+{CLAP_INDENT}
+          Can be specified in bytes or with a unit (e.g. 10GB, 512MiB).
+          The value of 0 means that the size is unlimited.
+
+      --max-topics <MAX_TOPICS>
+          Maximum number of the topics in the stream
+{CLAP_INDENT}
+          The value of 0 means that the number of topics is unlimited.
+
+      --max-partitions <MAX_PARTITIONS>
+          Maximum number of the partitions across all the topics in the stream
+{CLAP_INDENT}
+          The value of 0 means that the number of partitions is unlimited.
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -155,16 +176,23 @@ pub async fn should_short_help_match() {
         .execute_test_for_help_command(TestHelpCmd::new(
             vec!["stream", "update", "-h"],
             format!(
-                r#"Update stream name for given stream ID
+                r#"Update stream name and limits for given stream ID
 
-{USAGE_PREFIX} stream update <STREAM_ID> <NAME>
+{USAGE_PREFIX} stream update [OPTIONS] <STREAM_ID> <NAME>
 
 Arguments:
   <STREAM_ID>  Stream ID to update
   <NAME>       New name for the stream
 
 Options:
-  -h, --help  Print help (see more with '--help')
+      --max-size <MAX_SIZE>
+          Maximum size of all the topics in the stream
+      --max-topics <MAX_TOPICS>
+          Maximum number of the topics in the stream
+      --max-partitions <MAX_PARTITIONS>
+          Maximum number of the partitions across all the topics in the stream
+  -h, --help
+          Print help (see more with '--help')
 "#,
             ),
         ))
@@ -24,6 +24,7 @@ use async_trait::async_trait;
 use iggy::cli::system::stats::GetStatsOutput;
 use iggy::client::Client;
 use iggy::identifier::Identifier;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use predicates::str::{contains, starts_with};
@@ -64,7 +65,9 @@ impl TestStatsCmd {
 impl IggyCmdTestCase for TestStatsCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream_id = Identifier::from_str_value("logs").unwrap();
-        let stream = client.create_stream(&stream_id.as_string(), Some(1)).await;
+        let stream = client
+            .create_stream(&stream_id.as_string(), Some(1), None)
+            .await;
         assert!(stream.is_ok());
 
         let topic = client
@@ -77,6 +80,7 @@ impl IggyCmdTestCase for TestStatsCmd {
                 Some(1),
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
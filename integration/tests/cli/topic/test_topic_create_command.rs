@@ -102,7 +102,7 @@ impl TestTopicCreateCmd {
 impl IggyCmdTestCase for TestTopicCreateCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, Some(self.stream_id))
+            .create_stream(&self.stream_name, Some(self.stream_id), None)
             .await;
         assert!(stream.is_ok());
     }
@@ -322,6 +322,14 @@ Options:
 {CLAP_INDENT}
           [default: 1]
 
+  -c, --cleanup-policy <CLEANUP_POLICY>
+          Cleanup policy for the topic, set to "delete", "compact" or "compact:<header>"
+{CLAP_INDENT}
+          "compact" keeps only the latest message for each messages key used for partitioning
+          "compact:<header>" keeps only the latest message for each value of the given header
+{CLAP_INDENT}
+          [default: delete]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -356,6 +364,8 @@ Options:
           Max topic size in human-readable format like "unlimited" or "15GB" [default: server_default]
   -r, --replication-factor <REPLICATION_FACTOR>
           Replication factor for the topic [default: 1]
+  -c, --cleanup-policy <CLEANUP_POLICY>
+          Cleanup policy for the topic, set to "delete", "compact" or "compact:<header>" [default: delete]
   -h, --help
           Print help (see more with '--help')
 "#,
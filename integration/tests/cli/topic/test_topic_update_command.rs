@@ -25,6 +25,7 @@ use async_trait::async_trait;
 use humantime::Duration as HumanDuration;
 use iggy::client::Client;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -123,7 +124,7 @@ impl TestTopicUpdateCmd {
 impl IggyCmdTestCase for TestTopicUpdateCmd {
     async fn prepare_server_state(&mut self, client: &dyn Client) {
         let stream = client
-            .create_stream(&self.stream_name, Some(self.stream_id))
+            .create_stream(&self.stream_name, Some(self.stream_id), None)
             .await;
         assert!(stream.is_ok());
 
@@ -147,6 +148,7 @@ impl IggyCmdTestCase for TestTopicUpdateCmd {
                 Some(self.topic_id),
                 message_expiry,
                 self.max_topic_size,
+                CleanupPolicy::default(),
             )
             .await;
         assert!(topic.is_ok());
@@ -426,6 +428,14 @@ Options:
 {CLAP_INDENT}
           [default: 1]
 
+  -c, --cleanup-policy <CLEANUP_POLICY>
+          New cleanup policy for the topic, set to "delete", "compact" or "compact:<header>"
+{CLAP_INDENT}
+          "compact" keeps only the latest message for each messages key used for partitioning
+          "compact:<header>" keeps only the latest message for each value of the given header
+{CLAP_INDENT}
+          [default: delete]
+
   -h, --help
           Print help (see a summary with '-h')
 "#,
@@ -459,6 +469,8 @@ Options:
           New max topic size in human-readable format like "unlimited" or "15GB" [default: server_default]
   -r, --replication-factor <REPLICATION_FACTOR>
           New replication factor for the topic [default: 1]
+  -c, --cleanup-policy <CLEANUP_POLICY>
+          New cleanup policy for the topic, set to "delete", "compact" or "compact:<header>" [default: delete]
   -h, --help
           Print help (see more with '--help')
 "#,
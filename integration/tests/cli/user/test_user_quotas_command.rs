@@ -0,0 +1,258 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, TestUserId, CLAP_INDENT,
+    USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::models::user_info::UserId;
+use iggy::models::user_quotas::{GlobalQuotas, UserQuotas};
+use iggy::models::user_status::UserStatus;
+use predicates::str::diff;
+use serial_test::parallel;
+
+struct TestUserQuotasCmd {
+    username: String,
+    args: Vec<String>,
+    quotas: GlobalQuotas,
+    using_identifier: TestUserId,
+    user_id: Option<UserId>,
+}
+
+impl TestUserQuotasCmd {
+    fn new(
+        username: String,
+        args: Vec<String>,
+        quotas: GlobalQuotas,
+        using_identifier: TestUserId,
+    ) -> Self {
+        Self {
+            username,
+            args,
+            quotas,
+            using_identifier,
+            user_id: None,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut args = match self.using_identifier {
+            TestUserId::Named => vec![self.username.clone()],
+            TestUserId::Numeric => vec![format!("{}", self.user_id.unwrap())],
+        };
+        args.extend(self.args.clone());
+        args
+    }
+
+    fn to_message(&self) -> String {
+        format!(
+            "quotas: produce: {} B/s, consume: {} B/s, requests: {}/s",
+            self.quotas.produce_bytes_per_second,
+            self.quotas.consume_bytes_per_second,
+            self.quotas.requests_per_second
+        )
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestUserQuotasCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let create_user = client
+            .create_user(&self.username, "secret", UserStatus::Active, None)
+            .await;
+        assert!(create_user.is_ok());
+        let user = client
+            .get_user(&self.username.clone().try_into().unwrap())
+            .await;
+        assert!(user.is_ok());
+        let user = user.unwrap().expect("User not found");
+        self.user_id = Some(user.id);
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("user")
+            .arg("quotas")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let user_id = match self.using_identifier {
+            TestUserId::Named => self.username.clone(),
+            TestUserId::Numeric => format!("{}", self.user_id.unwrap()),
+        };
+        let message = format!(
+            "Executing update user with ID: {user_id} with {}\nUser with ID: {user_id} updated with {}\n",
+            self.to_message(),
+            self.to_message()
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let user = client
+            .get_user(&self.user_id.unwrap().try_into().unwrap())
+            .await;
+        assert!(user.is_ok());
+        let user = user.unwrap().expect("User not found");
+        let expected_quotas = UserQuotas {
+            global: self.quotas,
+            streams: None,
+        };
+        if expected_quotas.is_unlimited() {
+            assert!(user.quotas.is_none());
+        } else {
+            assert_eq!(user.quotas, Some(expected_quotas));
+        }
+
+        let deleted = client
+            .delete_user(&self.user_id.unwrap().try_into().unwrap())
+            .await;
+        assert!(deleted.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestUserQuotasCmd::new(
+            String::from("producer"),
+            vec![
+                String::from("--produce"),
+                String::from("1KB"),
+                String::from("--consume"),
+                String::from("2KB"),
+                String::from("--requests"),
+                String::from("10"),
+            ],
+            GlobalQuotas {
+                produce_bytes_per_second: 1000,
+                consume_bytes_per_second: 2000,
+                requests_per_second: 10,
+            },
+            TestUserId::Numeric,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestUserQuotasCmd::new(
+            String::from("consumer"),
+            vec![String::from("-c"), String::from("5000")],
+            GlobalQuotas {
+                produce_bytes_per_second: 0,
+                consume_bytes_per_second: 5000,
+                requests_per_second: 0,
+            },
+            TestUserId::Named,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestUserQuotasCmd::new(
+            String::from("unlimited"),
+            vec![],
+            GlobalQuotas::default(),
+            TestUserId::Named,
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["user", "quotas", "--help"],
+            format!(
+                r#"Set quotas for user with given ID
+
+The user ID can be specified as either a username or an ID. Quotas limit
+how fast the user can send and poll the messages and invoke the commands,
+across all the clients of the user. The rates which are not provided are
+unlimited, thus invoking the command without any option removes the quotas.
+
+Examples:
+ iggy user quotas 2 --produce 10MB --consume 20MB
+ iggy user quotas client -p 1MB -r 100
+ iggy user quotas testuser
+
+{USAGE_PREFIX} user quotas [OPTIONS] <USER_ID>
+
+Arguments:
+  <USER_ID>
+          User ID to update
+{CLAP_INDENT}
+          The user ID can be specified as either a username or an ID
+
+Options:
+  -p, --produce <PRODUCE>
+          Maximum size of the messages sent per second
+{CLAP_INDENT}
+          Can be specified in bytes or with a unit (e.g. 1MB, 512KiB).
+
+  -c, --consume <CONSUME>
+          Maximum size of the messages polled per second
+{CLAP_INDENT}
+          Can be specified in bytes or with a unit (e.g. 1MB, 512KiB).
+
+  -r, --requests <REQUESTS>
+          Maximum number of the requests invoked per second
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["user", "quotas", "-h"],
+            format!(
+                r#"Set quotas for user with given ID
+
+{USAGE_PREFIX} user quotas [OPTIONS] <USER_ID>
+
+Arguments:
+  <USER_ID>  User ID to update
+
+Options:
+  -p, --produce <PRODUCE>    Maximum size of the messages sent per second
+  -c, --consume <CONSUME>    Maximum size of the messages polled per second
+  -r, --requests <REQUESTS>  Maximum number of the requests invoked per second
+  -h, --help                 Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -27,6 +27,7 @@ use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::tcp::client::TcpClient;
 use iggy::tcp::config::TcpClientConfig;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::users::defaults::*;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -125,7 +126,7 @@ impl<'a> IggyExampleTest<'a> {
             .unwrap();
         if existing_stream_and_topic {
             self.client
-                .create_stream("sample-stream", Some(1))
+                .create_stream("sample-stream", Some(1), None)
                 .await
                 .unwrap();
             self.client
@@ -138,6 +139,7 @@ impl<'a> IggyExampleTest<'a> {
                     None,
                     IggyExpiry::NeverExpire,
                     MaxTopicSize::ServerDefault,
+                    CleanupPolicy::default(),
                 )
                 .await
                 .unwrap();
@@ -17,10 +17,18 @@
  */
 
 use crate::server::scenarios::{
-    create_message_payload, stream_size_validation_scenario, system_scenario, user_scenario,
+    client_certificate_scenario, create_message_payload, dead_letter_scenario,
+    long_polling_scenario, message_filter_scenario, messages_tail_scenario, oidc_scenario,
+    segment_restore_scenario, stream_size_validation_scenario, system_scenario, user_scenario,
+};
+use integration::{
+    http_client::HttpClientFactory,
+    test_certificates::TestCertificates,
+    test_identity_provider::TestIdentityProvider,
+    test_server::{IpAddrKind, TestServer},
 };
-use integration::{http_client::HttpClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::collections::HashMap;
 
 #[tokio::test]
 #[parallel]
@@ -28,7 +36,10 @@ async fn create_message_payload_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_http_api_addr().unwrap();
-    let client_factory = HttpClientFactory { server_addr };
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     create_message_payload::run(&client_factory).await;
 }
 
@@ -38,17 +49,36 @@ async fn message_headers_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_http_api_addr().unwrap();
-    let client_factory = HttpClientFactory { server_addr };
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     create_message_payload::run(&client_factory).await;
 }
 
+#[tokio::test]
+#[parallel]
+async fn message_filter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    message_filter_scenario::run(&client_factory).await;
+}
+
 #[tokio::test]
 #[parallel]
 async fn stream_size_validation_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_http_api_addr().unwrap();
-    let client_factory = HttpClientFactory { server_addr };
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     stream_size_validation_scenario::run(&client_factory).await;
 }
 
@@ -58,7 +88,10 @@ async fn system_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_http_api_addr().unwrap();
-    let client_factory = HttpClientFactory { server_addr };
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     system_scenario::run(&client_factory).await;
 }
 
@@ -68,6 +101,110 @@ async fn user_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_http_api_addr().unwrap();
-    let client_factory = HttpClientFactory { server_addr };
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     user_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn dead_letter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    dead_letter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn long_polling_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    long_polling_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn messages_tail_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    messages_tail_scenario::run(&server_addr).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn client_certificate_scenario_should_be_valid() {
+    let certificates = TestCertificates::generate(&["iggy", "unknown"]);
+    let mut envs = HashMap::new();
+    envs.insert("IGGY_HTTP_TLS_ENABLED".to_string(), "true".to_string());
+    envs.insert(
+        "IGGY_HTTP_TLS_CERT_FILE".to_string(),
+        certificates.server_cert_file(),
+    );
+    envs.insert(
+        "IGGY_HTTP_TLS_KEY_FILE".to_string(),
+        certificates.server_key_file(),
+    );
+    envs.insert(
+        "IGGY_HTTP_TLS_CLIENT_CERTIFICATE_ENABLED".to_string(),
+        "true".to_string(),
+    );
+    envs.insert(
+        "IGGY_HTTP_TLS_CLIENT_CERTIFICATE_CA_FILE".to_string(),
+        certificates.ca_file(),
+    );
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let create_factory = |name: &str| HttpClientFactory {
+        server_addr: server_addr.clone(),
+        tls_ca_file: Some(certificates.ca_file()),
+        tls_certificate_file: Some(certificates.client_cert_file(name)),
+        tls_key_file: Some(certificates.client_key_file(name)),
+    };
+    client_certificate_scenario::run(&create_factory("iggy"), &create_factory("unknown")).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn oidc_scenario_should_be_valid() {
+    let provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let untrusted_provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let envs = oidc_scenario::create_server_envs(&provider);
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    oidc_scenario::run(&client_factory, &provider, &untrusted_provider).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn segment_restore_scenario_should_be_valid() {
+    let archive = tempfile::tempdir().unwrap();
+    let archive_path = archive.path().to_str().unwrap();
+    let envs = segment_restore_scenario::server_envs(archive_path);
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = HttpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    segment_restore_scenario::run(&client_factory, archive_path).await;
+}
@@ -20,3 +20,4 @@ mod http_server;
 mod quic_server;
 mod scenarios;
 mod tcp_server;
+mod websocket_server;
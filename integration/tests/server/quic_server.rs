@@ -17,12 +17,23 @@
  */
 
 use crate::server::scenarios::{
-    consumer_group_join_scenario, consumer_group_with_multiple_clients_polling_messages_scenario,
+    client_certificate_scenario, consumer_group_join_scenario,
+    consumer_group_with_multiple_clients_polling_messages_scenario,
     consumer_group_with_single_client_polling_messages_scenario, create_message_payload,
-    message_headers_scenario, stream_size_validation_scenario, system_scenario, user_scenario,
+    dead_letter_scenario, handshake_scenario, long_polling_scenario, message_headers_scenario,
+    oidc_scenario, push_subscription_scenario, quotas_scenario, stream_limits_scenario,
+    stream_size_validation_scenario, system_scenario, user_scenario, wire_compression_scenario,
+};
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
+use iggy::models::handshake::{ProtocolFeature, ProtocolFeatures};
+use integration::{
+    quic_client::QuicClientFactory,
+    test_certificates::TestCertificates,
+    test_identity_provider::TestIdentityProvider,
+    test_server::{IpAddrKind, TestServer},
 };
-use integration::{quic_client::QuicClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::collections::HashMap;
 
 #[tokio::test]
 #[parallel]
@@ -30,7 +41,10 @@ async fn system_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     system_scenario::run(&client_factory).await;
 }
 
@@ -40,7 +54,10 @@ async fn user_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     user_scenario::run(&client_factory).await;
 }
 
@@ -50,7 +67,10 @@ async fn message_headers_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     message_headers_scenario::run(&client_factory).await;
 }
 
@@ -60,7 +80,10 @@ async fn create_message_payload_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     create_message_payload::run(&client_factory).await;
 }
 
@@ -70,7 +93,10 @@ async fn consumer_group_join_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     consumer_group_join_scenario::run(&client_factory).await;
 }
 
@@ -80,7 +106,10 @@ async fn consumer_group_with_single_client_polling_messages_scenario_should_be_v
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     consumer_group_with_single_client_polling_messages_scenario::run(&client_factory).await;
 }
 
@@ -90,7 +119,10 @@ async fn consumer_group_with_multiple_clients_polling_messages_scenario_should_b
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     consumer_group_with_multiple_clients_polling_messages_scenario::run(&client_factory).await;
 }
 
@@ -100,6 +132,146 @@ async fn stream_size_validation_scenario_should_be_valid() {
     let mut test_server = TestServer::default();
     test_server.start();
     let server_addr = test_server.get_quic_udp_addr().unwrap();
-    let client_factory = QuicClientFactory { server_addr };
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
     stream_size_validation_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn dead_letter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    dead_letter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn quotas_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    quotas_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn stream_limits_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    stream_limits_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn handshake_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    let expected_features = ProtocolFeatures::new(&[
+        ProtocolFeature::Subscriptions,
+        ProtocolFeature::ConsumerGroupGenerations,
+    ]);
+    handshake_scenario::run(&client_factory, expected_features).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn long_polling_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    long_polling_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn push_subscription_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    push_subscription_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn wire_compression_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        compression: Some(WireCompressionAlgorithm::Zstd),
+        ..Default::default()
+    };
+    wire_compression_scenario::run(&client_factory, WireCompressionAlgorithm::Zstd).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn client_certificate_scenario_should_be_valid() {
+    let certificates = TestCertificates::generate(&["iggy", "unknown"]);
+    let mut envs = HashMap::new();
+    envs.insert(
+        "IGGY_QUIC_CLIENT_CERTIFICATE_ENABLED".to_string(),
+        "true".to_string(),
+    );
+    envs.insert(
+        "IGGY_QUIC_CLIENT_CERTIFICATE_CA_FILE".to_string(),
+        certificates.ca_file(),
+    );
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let create_factory = |name: &str| QuicClientFactory {
+        server_addr: server_addr.clone(),
+        client_certificate_file: Some(certificates.client_cert_file(name)),
+        client_key_file: Some(certificates.client_key_file(name)),
+        ..Default::default()
+    };
+    client_certificate_scenario::run(&create_factory("iggy"), &create_factory("unknown")).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn oidc_scenario_should_be_valid() {
+    let provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let untrusted_provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let envs = oidc_scenario::create_server_envs(&provider);
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_quic_udp_addr().unwrap();
+    let client_factory = QuicClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    oidc_scenario::run(&client_factory, &provider, &untrusted_provider).await;
+}
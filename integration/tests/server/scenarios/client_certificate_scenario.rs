@@ -0,0 +1,59 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{create_client, STREAM_ID, STREAM_NAME};
+use iggy::client::StreamClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+pub async fn run(
+    authenticated_client_factory: &dyn ClientFactory,
+    unauthenticated_client_factory: &dyn ClientFactory,
+) {
+    // 1. The client presenting the certificate of the root user should be authenticated without login
+    let client = create_client(authenticated_client_factory).await;
+    let streams = client.get_streams().await.unwrap();
+    assert!(streams.is_empty());
+
+    // 2. The authenticated client should be allowed to manage the streams
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+    let stream = client
+        .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap()
+        .expect("Stream should exist");
+    assert_eq!(stream.name, STREAM_NAME);
+    client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    assert_clean_system(&client).await;
+
+    // 3. The client presenting the certificate of an unknown user should remain unauthenticated
+    let client = create_client(unauthenticated_client_factory).await;
+    let result = client.get_streams().await;
+    assert!(matches!(result, Err(IggyError::Unauthenticated)));
+
+    // 4. The unauthenticated client should still be able to login with the credentials
+    login_root(&client).await;
+    assert_clean_system(&client).await;
+}
@@ -0,0 +1,134 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{create_client, STREAM_ID, STREAM_NAME};
+use iggy::client::{StreamClient, SystemClient};
+use iggy::clients::client::IggyClient;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::models::cluster::ClusterNodeRole;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const ATTEMPTS: u32 = 100;
+const ATTEMPT_INTERVAL: Duration = Duration::from_millis(100);
+
+pub async fn run(node_factories: &[&dyn ClientFactory]) {
+    let mut clients = Vec::with_capacity(node_factories.len());
+    for factory in node_factories {
+        let client = create_client(*factory).await;
+        login_root(&client).await;
+        clients.push(client);
+    }
+
+    // 1. All the nodes should agree on the single leader
+    let leader_id = wait_for_leader(&clients).await;
+    let leader_index = (leader_id - 1) as usize;
+    let metadata = clients[leader_index].get_cluster_metadata().await.unwrap();
+    assert_eq!(metadata.id, leader_id);
+    assert_eq!(metadata.nodes.len(), clients.len());
+    let leaders = metadata
+        .nodes
+        .iter()
+        .filter(|node| node.role == ClusterNodeRole::Leader)
+        .count();
+    assert_eq!(leaders, 1);
+
+    // 2. The stream created on the leader should be replicated to the followers
+    let leader = &clients[leader_index];
+    leader
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+    for (index, follower) in clients.iter().enumerate() {
+        if index == leader_index {
+            continue;
+        }
+
+        wait_for_stream(follower).await;
+    }
+
+    // 3. Changing the metadata on the follower should fail
+    let follower = &clients[(leader_index + 1) % clients.len()];
+    let result = follower.create_stream("follower-stream", None, None).await;
+    assert!(matches!(result, Err(IggyError::NotClusterLeader)));
+
+    // 4. The stream deleted on the leader should be removed from the followers
+    leader
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+    assert_clean_system(leader).await;
+    for (index, follower) in clients.iter().enumerate() {
+        if index == leader_index {
+            continue;
+        }
+
+        wait_for_clean_system(follower).await;
+    }
+}
+
+async fn wait_for_leader(clients: &[IggyClient]) -> u32 {
+    for _ in 0..ATTEMPTS {
+        let mut leader_ids = Vec::with_capacity(clients.len());
+        for client in clients {
+            let metadata = client.get_cluster_metadata().await.unwrap();
+            leader_ids.push(metadata.leader_id);
+        }
+
+        if let Some(Some(leader_id)) = leader_ids.first() {
+            if leader_ids.iter().all(|id| *id == Some(*leader_id)) {
+                return *leader_id;
+            }
+        }
+
+        sleep(ATTEMPT_INTERVAL).await;
+    }
+
+    panic!("Cluster nodes didn't agree on the leader.");
+}
+
+async fn wait_for_stream(client: &IggyClient) {
+    for _ in 0..ATTEMPTS {
+        if let Some(stream) = client
+            .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+            .await
+            .unwrap()
+        {
+            assert_eq!(stream.name, STREAM_NAME);
+            return;
+        }
+
+        sleep(ATTEMPT_INTERVAL).await;
+    }
+
+    panic!("Stream wasn't replicated to the follower.");
+}
+
+async fn wait_for_clean_system(client: &IggyClient) {
+    for _ in 0..ATTEMPTS {
+        if client.get_streams().await.unwrap().is_empty() {
+            return;
+        }
+
+        sleep(ATTEMPT_INTERVAL).await;
+    }
+
+    panic!("Stream wasn't removed from the follower.");
+}
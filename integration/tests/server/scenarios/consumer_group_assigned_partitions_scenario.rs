@@ -0,0 +1,199 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, get_consumer_group, join_consumer_group, CONSUMER_GROUP_ID,
+    CONSUMER_GROUP_NAME, PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, SystemClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MESSAGES_PER_PARTITION: u32 = 5;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let system_client = create_client(client_factory).await;
+    let client1 = create_client(client_factory).await;
+    let client2 = create_client(client_factory).await;
+    login_root(&system_client).await;
+    login_root(&client1).await;
+    login_root(&client2).await;
+    init_system(&system_client).await;
+
+    let consumer_group = get_consumer_group(&system_client).await;
+    assert_eq!(
+        consumer_group.assignment_strategy,
+        AssignmentStrategy::Range
+    );
+
+    // 1. Join the consumer group by client 1, which gets all the partitions
+    join_consumer_group(&client1).await;
+    assert_eq!(
+        get_member_partitions(&system_client, &client1).await,
+        (1..=PARTITIONS_COUNT).collect::<Vec<_>>()
+    );
+
+    // 2. Poll the messages from all the assigned partitions at once
+    let partitions_messages = poll_assigned_messages(&client1).await;
+    assert_eq!(partitions_messages.len(), PARTITIONS_COUNT as usize);
+    for (polled_messages, partition_id) in partitions_messages.iter().zip(1..=PARTITIONS_COUNT) {
+        assert_eq!(polled_messages.partition_id, partition_id);
+        assert!(polled_messages.generation_id > 0);
+        assert_eq!(
+            polled_messages.messages.len(),
+            MESSAGES_PER_PARTITION as usize
+        );
+        for (message, offset) in polled_messages.messages.iter().zip(0..) {
+            assert_eq!(message.offset, offset);
+            assert_eq!(message.payload, create_payload(partition_id, offset as u32));
+        }
+    }
+
+    // 3. The offsets have been committed, so polling again returns no messages
+    let partitions_messages = poll_assigned_messages(&client1).await;
+    assert_eq!(partitions_messages.len(), PARTITIONS_COUNT as usize);
+    assert!(partitions_messages
+        .iter()
+        .all(|polled_messages| polled_messages.messages.is_empty()));
+
+    // 4. Join the consumer group by client 2, the partitions are split into the contiguous ranges
+    join_consumer_group(&client2).await;
+    let client1_partitions = get_member_partitions(&system_client, &client1).await;
+    let client2_partitions = get_member_partitions(&system_client, &client2).await;
+    let client1_id = client1.get_me().await.unwrap().client_id;
+    let client2_id = client2.get_me().await.unwrap().client_id;
+    if client1_id < client2_id {
+        assert_eq!(client1_partitions, vec![1, 2]);
+        assert_eq!(client2_partitions, vec![3]);
+    } else {
+        assert_eq!(client1_partitions, vec![3]);
+        assert_eq!(client2_partitions, vec![1, 2]);
+    }
+
+    // 5. Each member polls only its own partitions
+    let partitions_messages = poll_assigned_messages(&client2).await;
+    assert_eq!(
+        partitions_messages
+            .iter()
+            .map(|polled_messages| polled_messages.partition_id)
+            .collect::<Vec<_>>(),
+        client2_partitions
+    );
+    assert!(partitions_messages[0].messages.is_empty());
+
+    cleanup(&system_client, false).await;
+    assert_clean_system(&system_client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_consumer_group(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            CONSUMER_GROUP_NAME,
+            Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::Range,
+        )
+        .await
+        .unwrap();
+
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let mut messages = (0..MESSAGES_PER_PARTITION)
+            .map(|index| {
+                let payload = create_payload(partition_id, index);
+                Message {
+                    id: 0,
+                    length: payload.len() as u32,
+                    payload,
+                    headers: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(partition_id),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn create_payload(partition_id: u32, index: u32) -> Bytes {
+    Bytes::from(format!("message {partition_id}-{index}"))
+}
+
+async fn get_member_partitions(system_client: &IggyClient, client: &IggyClient) -> Vec<u32> {
+    let client_id = client.get_me().await.unwrap().client_id;
+    let consumer_group = get_consumer_group(system_client).await;
+    let mut partitions = consumer_group
+        .members
+        .into_iter()
+        .find(|member| member.id == client_id)
+        .expect("Consumer group member not found")
+        .partitions;
+    partitions.sort_unstable();
+    partitions
+}
+
+async fn poll_assigned_messages(client: &IggyClient) -> Vec<PolledMessages> {
+    client
+        .poll_assigned_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Identifier::numeric(CONSUMER_GROUP_ID).unwrap(),
+            &PollingStrategy::next(),
+            MESSAGES_PER_PARTITION * 2,
+            true,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap()
+}
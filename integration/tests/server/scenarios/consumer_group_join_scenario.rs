@@ -24,9 +24,11 @@ use crate::server::scenarios::{
 use iggy::client::{ConsumerGroupClient, StreamClient, SystemClient, TopicClient};
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::identifier::Identifier;
 use iggy::models::client_info::ClientInfoDetails;
 use iggy::models::consumer_group::ConsumerGroupDetails;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{
@@ -44,7 +46,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
 
     // 1. Create the stream
     system_client
-        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
         .await
         .unwrap();
 
@@ -59,6 +61,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -70,6 +73,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::default(),
         )
         .await
         .unwrap();
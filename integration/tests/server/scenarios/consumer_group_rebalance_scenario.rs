@@ -0,0 +1,198 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, get_consumer_group, join_consumer_group, CONSUMER_GROUP_ID,
+    CONSUMER_GROUP_NAME, PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{
+    ConsumerGroupClient, ConsumerOffsetClient, MessageClient, StreamClient, SystemClient,
+    TopicClient,
+};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let system_client = create_client(client_factory).await;
+    let client1 = create_client(client_factory).await;
+    let client2 = create_client(client_factory).await;
+    let client3 = create_client(client_factory).await;
+    login_root(&system_client).await;
+    login_root(&client1).await;
+    login_root(&client2).await;
+    login_root(&client3).await;
+    init_system(&system_client).await;
+
+    // 1. Join the consumer group by all the clients, each member gets a single partition
+    join_consumer_group(&client1).await;
+    join_consumer_group(&client2).await;
+    join_consumer_group(&client3).await;
+    let client1_partitions = get_member_partitions(&system_client, &client1).await;
+    let client2_partitions = get_member_partitions(&system_client, &client2).await;
+    assert_eq!(client1_partitions.len(), 1);
+    assert_eq!(client2_partitions.len(), 1);
+
+    // 2. Poll the messages by client 1 and store the offset with the returned generation
+    let generation_id = poll_generation_id(&client1).await;
+    assert!(generation_id > 0);
+    store_offset(&client1, Some(generation_id)).await.unwrap();
+
+    // 3. Leave the consumer group by client 3, the remaining members keep their partitions
+    client3
+        .leave_consumer_group(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Identifier::numeric(CONSUMER_GROUP_ID).unwrap(),
+        )
+        .await
+        .unwrap();
+    let consumer_group = get_consumer_group(&system_client).await;
+    assert_eq!(consumer_group.members_count, 2);
+    let client1_new_partitions = get_member_partitions(&system_client, &client1).await;
+    let client2_new_partitions = get_member_partitions(&system_client, &client2).await;
+    assert!(client1_new_partitions.contains(&client1_partitions[0]));
+    assert!(client2_new_partitions.contains(&client2_partitions[0]));
+    assert_eq!(
+        client1_new_partitions.len() + client2_new_partitions.len(),
+        PARTITIONS_COUNT as usize
+    );
+
+    // 4. Storing the offset with the stale generation should fail
+    let result = store_offset(&client1, Some(generation_id)).await;
+    assert!(matches!(
+        result,
+        Err(IggyError::StaleConsumerGroupGeneration(..))
+    ));
+
+    // 5. Polling the messages again returns the new generation, which can be used to store the offset
+    let new_generation_id = poll_generation_id(&client1).await;
+    assert!(new_generation_id > generation_id);
+    store_offset(&client1, Some(new_generation_id))
+        .await
+        .unwrap();
+
+    // 6. Storing the offset without the generation is still allowed
+    store_offset(&client1, None).await.unwrap();
+
+    cleanup(&system_client, false).await;
+    assert_clean_system(&system_client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_consumer_group(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            CONSUMER_GROUP_NAME,
+            Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::default(),
+        )
+        .await
+        .unwrap();
+
+    for partition_id in 1..=PARTITIONS_COUNT {
+        let payload = Bytes::from(format!("message {partition_id}"));
+        let mut messages = vec![Message {
+            id: partition_id as u128,
+            length: payload.len() as u32,
+            payload,
+            headers: None,
+        }];
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(partition_id),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+async fn get_member_partitions(system_client: &IggyClient, client: &IggyClient) -> Vec<u32> {
+    let client_id = client.get_me().await.unwrap().client_id;
+    let consumer_group = get_consumer_group(system_client).await;
+    consumer_group
+        .members
+        .into_iter()
+        .find(|member| member.id == client_id)
+        .expect("Consumer group member not found")
+        .partitions
+}
+
+async fn poll_generation_id(client: &IggyClient) -> u32 {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            None,
+            &Consumer::group(Identifier::numeric(CONSUMER_GROUP_ID).unwrap()),
+            &PollingStrategy::next(),
+            1,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap();
+    polled_messages.generation_id
+}
+
+async fn store_offset(client: &IggyClient, generation_id: Option<u32>) -> Result<(), IggyError> {
+    client
+        .store_consumer_offset(
+            &Consumer::group(Identifier::numeric(CONSUMER_GROUP_ID).unwrap()),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            None,
+            0,
+            generation_id,
+        )
+        .await
+}
@@ -25,10 +25,12 @@ use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, SystemClien
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::consumer_group::ConsumerGroupDetails;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{
@@ -60,7 +62,7 @@ async fn init_system(
 ) {
     // 1. Create the stream
     system_client
-        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
         .await
         .unwrap();
 
@@ -75,6 +77,7 @@ async fn init_system(
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -86,6 +89,7 @@ async fn init_system(
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::default(),
         )
         .await
         .unwrap();
@@ -157,6 +161,7 @@ async fn poll_messages(client: &IggyClient) -> u32 {
                 &PollingStrategy::next(),
                 1,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -233,6 +238,7 @@ async fn validate_message_polling(client: &IggyClient, consumer_group: &Consumer
                 &PollingStrategy::next(),
                 1,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -257,6 +263,7 @@ async fn validate_message_polling(client: &IggyClient, consumer_group: &Consumer
             &PollingStrategy::next(),
             1,
             true,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -25,9 +25,11 @@ use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, SystemClien
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{assert_clean_system, login_root, ClientFactory};
@@ -51,7 +53,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
 async fn init_system(client: &IggyClient) {
     // 1. Create the stream
     client
-        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
         .await
         .unwrap();
 
@@ -66,6 +68,7 @@ async fn init_system(client: &IggyClient) {
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -77,6 +80,7 @@ async fn init_system(client: &IggyClient) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::default(),
         )
         .await
         .unwrap();
@@ -127,6 +131,7 @@ async fn execute_using_messages_key_key(client: &IggyClient) {
                 &PollingStrategy::next(),
                 1,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -178,6 +183,7 @@ async fn execute_using_none_key(client: &IggyClient) {
                 &PollingStrategy::next(),
                 1,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -208,6 +214,7 @@ async fn execute_using_none_key(client: &IggyClient) {
                 &PollingStrategy::next(),
                 1,
                 true,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -0,0 +1,316 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::nack_message::{
+    DEAD_LETTER_FAILED_DELIVERIES_HEADER, DEAD_LETTER_OFFSET_HEADER,
+    DEAD_LETTER_PARTITION_ID_HEADER, DEAD_LETTER_STREAM_ID_HEADER, DEAD_LETTER_TOPIC_ID_HEADER,
+};
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::MessageState;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const MESSAGES_COUNT: u32 = 3;
+const POISONED_MESSAGE_OFFSET: u64 = 1;
+const MAX_FAILED_DELIVERIES: u32 = 3;
+const DEAD_LETTER_TOPIC_NAME: &str = "test-topic-dlq";
+const REGULAR_TOPIC_NAME: &str = "regular-topic-dlq";
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send messages with the included headers
+    let mut messages = Vec::new();
+    for offset in 0..MESSAGES_COUNT {
+        let id = (offset + 1) as u128;
+        let payload = create_message_payload(offset as u64);
+        messages.push(Message {
+            id,
+            length: payload.len() as u32,
+            payload,
+            headers: Some(create_message_headers()),
+        });
+    }
+
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 2. Nacking the message which doesn't exist should fail
+    let result = client
+        .nack_message(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            MESSAGES_COUNT as u64,
+        )
+        .await;
+    assert!(result.is_err());
+
+    // 3. Nack the message until the last allowed failed delivery, the dead letter topic should not be created yet
+    for _ in 1..MAX_FAILED_DELIVERIES {
+        nack_poisoned_message(&client).await;
+    }
+
+    let dead_letter_topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(dead_letter_topic.is_none());
+
+    // 4. Nack the message for the last time, it should be moved to the dead letter topic
+    nack_poisoned_message(&client).await;
+
+    let dead_letter_topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Dead letter topic should be created");
+    assert_eq!(dead_letter_topic.partitions_count, 1);
+    assert_eq!(dead_letter_topic.messages_count, 1);
+
+    // 5. Poll the dead letter topic and validate the moved message
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+            Some(1),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            10,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(polled_messages.messages.len(), 1);
+    let message = &polled_messages.messages[0];
+    assert_eq!(message.state, MessageState::Poisoned);
+    assert_eq!(message.id, (POISONED_MESSAGE_OFFSET + 1) as u128);
+    assert_eq!(
+        message.payload,
+        create_message_payload(POISONED_MESSAGE_OFFSET)
+    );
+    let headers = message.headers.as_ref().unwrap();
+    assert_eq!(
+        headers
+            .get(&HeaderKey::new("key_1").unwrap())
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        "Value 1"
+    );
+    assert_eq!(
+        get_header(headers, DEAD_LETTER_STREAM_ID_HEADER)
+            .as_uint32()
+            .unwrap(),
+        STREAM_ID
+    );
+    assert_eq!(
+        get_header(headers, DEAD_LETTER_TOPIC_ID_HEADER)
+            .as_uint32()
+            .unwrap(),
+        TOPIC_ID
+    );
+    assert_eq!(
+        get_header(headers, DEAD_LETTER_PARTITION_ID_HEADER)
+            .as_uint32()
+            .unwrap(),
+        PARTITION_ID
+    );
+    assert_eq!(
+        get_header(headers, DEAD_LETTER_OFFSET_HEADER)
+            .as_uint64()
+            .unwrap(),
+        POISONED_MESSAGE_OFFSET
+    );
+    assert_eq!(
+        get_header(headers, DEAD_LETTER_FAILED_DELIVERIES_HEADER)
+            .as_uint32()
+            .unwrap(),
+        MAX_FAILED_DELIVERIES
+    );
+
+    // 6. The original message should still be in the source topic, marked as poisoned
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(POISONED_MESSAGE_OFFSET),
+            1,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len(), 1);
+    assert_eq!(polled_messages.messages[0].state, MessageState::Poisoned);
+
+    // 7. Nacking the poisoned message again should not move it to the dead letter topic once more
+    nack_poisoned_message(&client).await;
+
+    let dead_letter_topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Dead letter topic should exist");
+    assert_eq!(dead_letter_topic.messages_count, 1);
+
+    // 8. Nacking the message in the dead letter topic should fail
+    let result = client
+        .nack_message(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(DEAD_LETTER_TOPIC_NAME).unwrap(),
+            1,
+            0,
+        )
+        .await;
+    assert!(result.is_err());
+
+    // 9. Nacking the message in the regular topic having the dead letter suffix should succeed
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            REGULAR_TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            None,
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+    let payload = create_message_payload(0);
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(REGULAR_TOPIC_NAME).unwrap(),
+            &Partitioning::partition_id(1),
+            &mut [Message {
+                id: 1,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }],
+        )
+        .await
+        .unwrap();
+    client
+        .nack_message(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::named(REGULAR_TOPIC_NAME).unwrap(),
+            1,
+            0,
+        )
+        .await
+        .unwrap();
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn nack_poisoned_message(client: &IggyClient) {
+    client
+        .nack_message(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            POISONED_MESSAGE_OFFSET,
+        )
+        .await
+        .unwrap();
+}
+
+fn get_header(headers: &HashMap<HeaderKey, HeaderValue>, key: &str) -> HeaderValue {
+    headers.get(&HeaderKey::new(key).unwrap()).unwrap().clone()
+}
+
+fn create_message_payload(offset: u64) -> Bytes {
+    Bytes::from(format!("message {}", offset))
+}
+
+fn create_message_headers() -> HashMap<HeaderKey, HeaderValue> {
+    let mut headers = HashMap::new();
+    headers.insert(
+        HeaderKey::new("key_1").unwrap(),
+        HeaderValue::from_str("Value 1").unwrap(),
+    );
+    headers
+}
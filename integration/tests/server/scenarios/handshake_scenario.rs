@@ -0,0 +1,37 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::server::scenarios::create_client;
+use iggy::client::Client;
+use iggy::models::handshake::ProtocolFeatures;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+pub async fn run(client_factory: &dyn ClientFactory, expected_features: ProtocolFeatures) {
+    // 1. The connected client should expose the features negotiated during the handshake
+    let client = create_client(client_factory).await;
+    let features = client.connect().await.unwrap();
+    assert_eq!(features, expected_features);
+
+    // 2. The features should be negotiated again after reconnecting
+    client.disconnect().await.unwrap();
+    let features = client.connect().await.unwrap();
+    assert_eq!(features, expected_features);
+
+    // 3. The client should work as usual with the negotiated features
+    login_root(&client).await;
+    assert_clean_system(&client).await;
+}
@@ -0,0 +1,156 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, ProducerSequence};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MESSAGES_COUNT: u32 = 3;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Obtain the unique producer IDs
+    let producer_id = client.init_producer().await.unwrap();
+    let another_producer_id = client.init_producer().await.unwrap();
+    assert!(producer_id > 0);
+    assert_ne!(producer_id, another_producer_id);
+
+    // 2. Send the first batch
+    send_messages(&client, producer_id, 1, 0).await.unwrap();
+    assert_eq!(poll_messages_count(&client).await, MESSAGES_COUNT);
+
+    // 3. Retry the first batch after reconnecting, its messages should not be appended again
+    let reconnected_client = create_client(client_factory).await;
+    login_root(&reconnected_client).await;
+    send_messages(&reconnected_client, producer_id, 1, 0)
+        .await
+        .unwrap();
+    assert_eq!(poll_messages_count(&client).await, MESSAGES_COUNT);
+
+    // 4. Sending the batch with the sequence number skipping the next expected one should fail
+    let result = send_messages(&client, producer_id, 3, MESSAGES_COUNT).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::OutOfOrderSequence(producer_id, 2, 3).as_code()
+    );
+    assert_eq!(poll_messages_count(&client).await, MESSAGES_COUNT);
+
+    // 5. The batch of the unknown producer should fail, unless it's its first one
+    let result = send_messages(&client, another_producer_id, 2, MESSAGES_COUNT).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::UnknownProducer(another_producer_id, 2).as_code()
+    );
+    assert_eq!(poll_messages_count(&client).await, MESSAGES_COUNT);
+
+    // 6. Send the next batch and the first batch of another producer
+    send_messages(&client, producer_id, 2, MESSAGES_COUNT)
+        .await
+        .unwrap();
+    send_messages(&client, another_producer_id, 1, 2 * MESSAGES_COUNT)
+        .await
+        .unwrap();
+    assert_eq!(poll_messages_count(&client).await, 3 * MESSAGES_COUNT);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(
+    client: &IggyClient,
+    producer_id: u64,
+    sequence: u64,
+    first_offset: u32,
+) -> Result<(), IggyError> {
+    let mut messages = Vec::new();
+    for offset in first_offset..first_offset + MESSAGES_COUNT {
+        let payload = Bytes::from(format!("message {}", offset));
+        messages.push(Message::new(None, payload, None));
+    }
+
+    client
+        .send_idempotent_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &ProducerSequence {
+                producer_id,
+                sequence,
+            },
+            &mut messages,
+        )
+        .await
+}
+
+async fn poll_messages_count(client: &IggyClient) -> u32 {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap();
+    polled_messages.messages.len() as u32
+}
@@ -0,0 +1,146 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy, PollingWait};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const MESSAGES_COUNT: u32 = 10;
+const SEND_DELAY: Duration = Duration::from_millis(500);
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Without any messages, the poll should return the empty batch once the wait time is up
+    let max_wait = IggyDuration::from_str("200ms").unwrap();
+    let started_at = Instant::now();
+    let polled_messages = poll_messages(&client, 0, PollingWait::messages(max_wait, 0)).await;
+    assert!(polled_messages.messages.is_empty());
+    assert!(started_at.elapsed() >= max_wait.get_duration());
+
+    // 2. The waiting poll should be completed as soon as the message is sent
+    let max_wait = IggyDuration::from_str("30s").unwrap();
+    let producer = create_client(client_factory).await;
+    login_root(&producer).await;
+    let sender = tokio::spawn(async move {
+        sleep(SEND_DELAY).await;
+        send_messages(&producer, 1).await;
+        producer
+    });
+    let started_at = Instant::now();
+    let polled_messages = poll_messages(&client, 0, PollingWait::messages(max_wait, 0)).await;
+    assert_eq!(polled_messages.messages.len(), 1);
+    assert!(started_at.elapsed() >= SEND_DELAY);
+    assert!(started_at.elapsed() < max_wait.get_duration());
+    let producer = sender.await.unwrap();
+
+    // 3. The poll should wait until the minimum number of messages is available
+    let sender = tokio::spawn(async move {
+        for _ in 0..3 {
+            sleep(SEND_DELAY).await;
+            send_messages(&producer, 1).await;
+        }
+    });
+    let polled_messages = poll_messages(&client, 1, PollingWait::messages(max_wait, 3)).await;
+    assert_eq!(polled_messages.messages.len(), 3);
+    sender.await.unwrap();
+
+    // 4. The messages already available should be returned immediately
+    let started_at = Instant::now();
+    let polled_messages = poll_messages(&client, 0, PollingWait::messages(max_wait, 4)).await;
+    assert_eq!(polled_messages.messages.len(), 4);
+    assert!(started_at.elapsed() < max_wait.get_duration());
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient, count: u32) {
+    let mut messages = (0..count)
+        .map(|index| Message::new(None, Bytes::from(format!("message {index}")), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(client: &IggyClient, offset: u64, wait: PollingWait) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(offset),
+            MESSAGES_COUNT,
+            false,
+            &PollMessagesOptions::default().wait(wait),
+        )
+        .await
+        .unwrap()
+}
@@ -0,0 +1,220 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{ConsumerOffsetClient, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::header_filter::HeaderFilter;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const MESSAGES_COUNT: u32 = 10;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the messages with the even offsets to the "acme" tenant and the odd ones to the "other" tenant
+    send_messages(&client).await;
+
+    // 2. Only the messages matching the filter should be returned
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::offset(0),
+        MESSAGES_COUNT,
+        false,
+        r#"tenant == "acme" AND priority > 3"#,
+    )
+    .await;
+    assert_offsets(&polled_messages, &[4, 6, 8]);
+
+    // 3. The skipped messages should be replaced with the following ones, up to the specified count
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::next(),
+        2,
+        true,
+        r#"tenant == "other""#,
+    )
+    .await;
+    assert_offsets(&polled_messages, &[1, 3]);
+    assert_stored_offset(&client, 3).await;
+
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::next(),
+        2,
+        true,
+        r#"tenant == "other" OR priority == 0"#,
+    )
+    .await;
+    assert_offsets(&polled_messages, &[5, 7]);
+    assert_stored_offset(&client, 7).await;
+
+    // 4. The offset should be committed past the skipped messages, even if none of them matches the filter
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::next(),
+        MESSAGES_COUNT,
+        true,
+        "priority > 100",
+    )
+    .await;
+    assert!(polled_messages.messages.is_empty());
+    assert_stored_offset(&client, (MESSAGES_COUNT - 1) as u64).await;
+
+    // 5. With the manual commit, the offset should be stored only if none of the scanned messages matches the filter
+    send_messages(&client).await;
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::next(),
+        MESSAGES_COUNT,
+        false,
+        "priority > 100",
+    )
+    .await;
+    assert!(polled_messages.messages.is_empty());
+    assert_stored_offset(&client, (2 * MESSAGES_COUNT - 1) as u64).await;
+
+    send_messages(&client).await;
+    let polled_messages = poll_messages(
+        &client,
+        &PollingStrategy::next(),
+        2,
+        false,
+        r#"tenant == "acme""#,
+    )
+    .await;
+    assert_offsets(&polled_messages, &[20, 22]);
+    assert_stored_offset(&client, (2 * MESSAGES_COUNT - 1) as u64).await;
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient) {
+    let mut messages = Vec::new();
+    for offset in 0..MESSAGES_COUNT {
+        let tenant = if offset % 2 == 0 { "acme" } else { "other" };
+        let mut headers = HashMap::new();
+        headers.insert(
+            HeaderKey::new("tenant").unwrap(),
+            HeaderValue::from_str(tenant).unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new("priority").unwrap(),
+            HeaderValue::from_uint32(offset).unwrap(),
+        );
+        let payload = Bytes::from(format!("message {offset}"));
+        messages.push(Message::new(None, payload, Some(headers)));
+    }
+
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(
+    client: &IggyClient,
+    strategy: &PollingStrategy,
+    count: u32,
+    auto_commit: bool,
+    filter: &str,
+) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            strategy,
+            count,
+            auto_commit,
+            &PollMessagesOptions::default().filter(HeaderFilter::new(filter).unwrap()),
+        )
+        .await
+        .unwrap()
+}
+
+async fn assert_stored_offset(client: &IggyClient, offset: u64) {
+    let consumer_offset = client
+        .get_consumer_offset(
+            &Consumer::default(),
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+        )
+        .await
+        .unwrap()
+        .expect("Consumer offset should be stored");
+    assert_eq!(consumer_offset.stored_offset, offset);
+}
+
+fn assert_offsets(polled_messages: &PolledMessages, offsets: &[u64]) {
+    let polled_offsets = polled_messages
+        .messages
+        .iter()
+        .map(|message| message.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(polled_offsets, offsets);
+}
@@ -26,9 +26,10 @@ use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{assert_clean_system, login_root, ClientFactory};
@@ -75,6 +76,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::offset(0),
             MESSAGES_COUNT,
             false,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -114,7 +116,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
 async fn init_system(client: &IggyClient) {
     // 1. Create the stream
     client
-        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
         .await
         .unwrap();
 
@@ -129,6 +131,7 @@ async fn init_system(client: &IggyClient) {
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
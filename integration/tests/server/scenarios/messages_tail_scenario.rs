@@ -0,0 +1,148 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient, UserClient};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::http::client::HttpClient;
+use iggy::http::messages_tail::MessagesTail;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{IsolationLevel, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+const MESSAGES_COUNT: u32 = 3;
+const SEND_DELAY: Duration = Duration::from_millis(500);
+const MESSAGES_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn run(server_addr: &str) {
+    let client = HttpClient::new(&format!("http://{server_addr}")).unwrap();
+    client
+        .login_user(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD)
+        .await
+        .unwrap();
+    init_system(&client).await;
+    send_messages(&client, MESSAGES_COUNT).await;
+
+    // 1. The messages already available should be streamed right away
+    let mut tail = tail_messages(&client, TOPIC_ID).await.unwrap();
+    assert_offsets(next(&mut tail).await, 0, MESSAGES_COUNT);
+
+    // 2. The newly appended message should be streamed as soon as it's sent
+    let producer = HttpClient::new(&format!("http://{server_addr}")).unwrap();
+    producer
+        .login_user(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD)
+        .await
+        .unwrap();
+    let sender = tokio::spawn(async move {
+        sleep(SEND_DELAY).await;
+        send_messages(&producer, 1).await;
+    });
+    assert_offsets(next(&mut tail).await, MESSAGES_COUNT as u64, 1);
+    sender.await.unwrap();
+
+    // 3. Tailing the non-existing topic should be rejected upfront
+    assert!(tail_messages(&client, TOPIC_ID + 1).await.is_err());
+
+    client
+        .delete_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap();
+}
+
+async fn init_system(client: &HttpClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &HttpClient, count: u32) {
+    let mut messages = (0..count)
+        .map(|index| Message::new(None, Bytes::from(format!("message {index}")), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn tail_messages(client: &HttpClient, topic_id: u32) -> Result<MessagesTail, IggyError> {
+    client
+        .tail_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(topic_id).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            100,
+            false,
+            IsolationLevel::default(),
+            None,
+        )
+        .await
+}
+
+async fn next(tail: &mut MessagesTail) -> PolledMessages {
+    timeout(MESSAGES_TIMEOUT, tail.next())
+        .await
+        .expect("Messages were not streamed in time")
+        .unwrap()
+        .expect("Messages tail was closed")
+}
+
+fn assert_offsets(polled_messages: PolledMessages, first_offset: u64, count: u32) {
+    assert_eq!(polled_messages.partition_id, PARTITION_ID);
+    assert_eq!(polled_messages.messages.len() as u32, count);
+    for (index, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.offset, first_offset + index as u64);
+    }
+}
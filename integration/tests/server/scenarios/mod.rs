@@ -23,15 +23,35 @@ use iggy::identifier::Identifier;
 use iggy::models::consumer_group::ConsumerGroupDetails;
 use integration::test_server::{delete_user, ClientFactory};
 
+pub mod client_certificate_scenario;
+pub mod cluster_scenario;
+pub mod consumer_group_assigned_partitions_scenario;
 pub mod consumer_group_join_scenario;
+pub mod consumer_group_rebalance_scenario;
 pub mod consumer_group_with_multiple_clients_polling_messages_scenario;
 pub mod consumer_group_with_single_client_polling_messages_scenario;
 pub mod create_message_payload;
+pub mod dead_letter_scenario;
+pub mod handshake_scenario;
+pub mod idempotent_producer_scenario;
+pub mod long_polling_scenario;
+pub mod message_filter_scenario;
 pub mod message_headers_scenario;
 pub mod message_size_scenario;
+pub mod messages_tail_scenario;
+pub mod multiplexing_scenario;
+pub mod oidc_scenario;
+pub mod push_subscription_scenario;
+pub mod quotas_scenario;
+pub mod replication_scenario;
+pub mod segment_restore_scenario;
+pub mod stream_limits_scenario;
 pub mod stream_size_validation_scenario;
 pub mod system_scenario;
+pub mod transactions_scenario;
 pub mod user_scenario;
+pub mod websocket_multiplexing_scenario;
+pub mod wire_compression_scenario;
 
 const STREAM_ID: u32 = 1;
 const TOPIC_ID: u32 = 1;
@@ -0,0 +1,167 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use futures::future::join_all;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy, PollingWait};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const CONCURRENT_REQUESTS: u32 = 100;
+const SEND_DELAY: Duration = Duration::from_millis(500);
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = Arc::new(create_client(client_factory).await);
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. The waiting poll should not hold back the message sent over the same connection meanwhile
+    let max_wait = IggyDuration::from_str("30s").unwrap();
+    let poller = tokio::spawn({
+        let client = client.clone();
+        async move { poll_messages(&client, 0, PollingWait::messages(max_wait, 1)).await }
+    });
+    let started_at = Instant::now();
+    sleep(SEND_DELAY).await;
+    send_messages(&client, 1).await;
+    let polled_messages = poller.await.unwrap();
+    assert_eq!(polled_messages.messages.len(), 1);
+    assert!(started_at.elapsed() < max_wait.get_duration());
+
+    // 2. The concurrent sends should all complete over the same connection
+    let sends = (0..CONCURRENT_REQUESTS).map(|_| {
+        let client = client.clone();
+        async move { send_messages(&client, 1).await }
+    });
+    join_all(sends).await;
+    let polled_messages = poll_messages(
+        &client,
+        0,
+        PollingWait::messages(IggyDuration::from_str("1s").unwrap(), 0),
+    )
+    .await;
+    assert_eq!(
+        polled_messages.messages.len() as u32,
+        CONCURRENT_REQUESTS + 1
+    );
+
+    // 3. Each of the concurrent requests should receive its own response, including the errors
+    let requests = (0..CONCURRENT_REQUESTS).map(|index| {
+        let client = client.clone();
+        async move {
+            let stream_id = if index % 2 == 0 {
+                STREAM_ID
+            } else {
+                STREAM_ID + 1
+            };
+            (
+                stream_id,
+                client
+                    .get_stream(&Identifier::numeric(stream_id).unwrap())
+                    .await,
+            )
+        }
+    });
+    for (stream_id, result) in join_all(requests).await {
+        match result {
+            Ok(Some(stream)) => assert_eq!(stream.id, STREAM_ID),
+            Ok(None) => assert_eq!(stream_id, STREAM_ID + 1),
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+    }
+    let result = client
+        .delete_stream(&Identifier::numeric(STREAM_ID + 1).unwrap())
+        .await;
+    assert!(matches!(result, Err(IggyError::StreamIdNotFound(_))));
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient, count: u32) {
+    let mut messages = (0..count)
+        .map(|index| Message::new(None, Bytes::from(format!("message {index}")), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(client: &IggyClient, offset: u64, wait: PollingWait) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(offset),
+            CONCURRENT_REQUESTS * 2,
+            false,
+            &PollMessagesOptions::default().wait(wait),
+        )
+        .await
+        .unwrap()
+}
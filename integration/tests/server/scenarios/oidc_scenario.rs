@@ -0,0 +1,119 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{create_client, STREAM_ID, STREAM_NAME};
+use iggy::client::{StreamClient, UserClient};
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::users::defaults::DEFAULT_ROOT_USERNAME;
+use integration::test_identity_provider::{TestIdentityProvider, TEST_OIDC_AUDIENCE};
+use integration::test_server::{assert_clean_system, delete_user, login_root, ClientFactory};
+use std::collections::HashMap;
+
+pub const OIDC_ISSUER: &str = "https://idp.example.com";
+const PROVISIONED_USERNAME: &str = "oidc-reader";
+const READERS_ROLE: &str = "iggy-readers";
+
+/// Returns the server configuration trusting the given provider, and provisioning the unknown users.
+pub fn create_server_envs(provider: &TestIdentityProvider) -> HashMap<String, String> {
+    let mut envs = HashMap::new();
+    envs.insert("IGGY_OIDC_ENABLED".to_string(), "true".to_string());
+    envs.insert(
+        "IGGY_OIDC_ISSUERS".to_string(),
+        format!("[{}]", provider.issuer_config()),
+    );
+    envs.insert(
+        "IGGY_OIDC_AUDIENCES".to_string(),
+        format!("[{TEST_OIDC_AUDIENCE}]"),
+    );
+    envs.insert("IGGY_OIDC_AUTO_PROVISION".to_string(), "true".to_string());
+    envs.insert(
+        "IGGY_OIDC_ROLE_RULES".to_string(),
+        format!("[{READERS_ROLE}=read_streams+poll_messages]"),
+    );
+    envs
+}
+
+pub async fn run(
+    client_factory: &dyn ClientFactory,
+    provider: &TestIdentityProvider,
+    untrusted_provider: &TestIdentityProvider,
+) {
+    // 1. The token issued for the username of the local user should not authenticate the client as that user
+    let client = create_client(client_factory).await;
+    let token = provider.issue_token(DEFAULT_ROOT_USERNAME, &[]);
+    assert!(client.login_with_oidc_token(&token).await.is_err());
+    let result = client.get_streams().await;
+    assert!(matches!(result, Err(IggyError::Unauthenticated)));
+
+    // 2. The token issued for the unknown user should provision it with the permissions granted by its roles
+    let client = create_client(client_factory).await;
+    let token = provider.issue_token(PROVISIONED_USERNAME, &[READERS_ROLE, "other"]);
+    let identity = client.login_with_oidc_token(&token).await.unwrap();
+    let streams = client.get_streams().await.unwrap();
+    assert!(streams.is_empty());
+    let result = client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await;
+    assert!(matches!(result, Err(IggyError::Unauthorized)));
+
+    // 3. The provisioned user should be reused on the subsequent login
+    let client = create_client(client_factory).await;
+    let other_identity = client.login_with_oidc_token(&token).await.unwrap();
+    assert_eq!(identity.user_id, other_identity.user_id);
+
+    let root_client = create_client(client_factory).await;
+    login_root(&root_client).await;
+    let user = root_client
+        .get_user(&Identifier::named(PROVISIONED_USERNAME).unwrap())
+        .await
+        .unwrap()
+        .expect("Provisioned user should exist");
+    assert_eq!(user.id, identity.user_id);
+    let permissions = user.permissions.expect("Permissions should be granted");
+    assert!(permissions.global.read_streams);
+    assert!(permissions.global.poll_messages);
+    assert!(!permissions.global.manage_streams);
+    assert!(!permissions.global.send_messages);
+
+    // 4. The permissions should follow the roles of the subsequent login
+    let client = create_client(client_factory).await;
+    let token = provider.issue_token(PROVISIONED_USERNAME, &["other"]);
+    let other_identity = client.login_with_oidc_token(&token).await.unwrap();
+    assert_eq!(identity.user_id, other_identity.user_id);
+    let result = client.get_streams().await;
+    assert!(matches!(result, Err(IggyError::Unauthorized)));
+
+    // 5. The token issued for the other subject with the username of the provisioned user should be rejected
+    let client = create_client(client_factory).await;
+    let token =
+        provider.issue_token_for_subject("other-subject", PROVISIONED_USERNAME, &[READERS_ROLE]);
+    assert!(client.login_with_oidc_token(&token).await.is_err());
+
+    // 6. The expired token, and the token signed with the untrusted key should be rejected
+    let client = create_client(client_factory).await;
+    let token = provider.issue_expired_token(PROVISIONED_USERNAME, &[READERS_ROLE]);
+    assert!(client.login_with_oidc_token(&token).await.is_err());
+    let token = untrusted_provider.issue_token(DEFAULT_ROOT_USERNAME, &[]);
+    assert!(client.login_with_oidc_token(&token).await.is_err());
+    let result = client.get_streams().await;
+    assert!(matches!(result, Err(IggyError::Unauthenticated)));
+
+    delete_user(&root_client, PROVISIONED_USERNAME).await;
+    assert_clean_system(&root_client).await;
+}
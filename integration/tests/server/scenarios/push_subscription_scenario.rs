@@ -0,0 +1,198 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::binary::subscription::MessageSubscription;
+use iggy::client::{ConsumerOffsetClient, MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::messages::subscribe_messages::{AckMode, CreditKind, SubscriptionOptions};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+const MESSAGES_COUNT: u32 = 10;
+const BATCH_SIZE: u32 = 4;
+const SEND_DELAY: Duration = Duration::from_millis(500);
+const NO_MESSAGES_TIMEOUT: Duration = Duration::from_millis(500);
+const MESSAGES_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+    send_messages(&client, MESSAGES_COUNT).await;
+
+    // 1. The messages already available should be pushed in batches, as long as there is the credit
+    let consumer = Consumer::new(Identifier::numeric(1).unwrap());
+    let mut subscription = subscribe(&client, &consumer, AckMode::Delivered, 6).await;
+    let polled_messages = next(&mut subscription).await;
+    assert_offsets(&polled_messages, 0, BATCH_SIZE);
+    let polled_messages = next(&mut subscription).await;
+    assert_offsets(&polled_messages, 4, 2);
+
+    // 2. Once the credit is exhausted, no more messages should be pushed
+    assert!(timeout(NO_MESSAGES_TIMEOUT, subscription.next())
+        .await
+        .is_err());
+
+    // 3. The delivered messages should have their offset stored
+    assert_eq!(get_stored_offset(&client, &consumer).await, Some(5));
+
+    // 4. Granting the credit should resume pushing the remaining messages
+    subscription.grant_credit(10).await.unwrap();
+    let polled_messages = next(&mut subscription).await;
+    assert_offsets(&polled_messages, 6, BATCH_SIZE);
+
+    // 5. The newly appended message should be pushed right away
+    let producer = create_client(client_factory).await;
+    login_root(&producer).await;
+    let sender = tokio::spawn(async move {
+        sleep(SEND_DELAY).await;
+        send_messages(&producer, 1).await;
+    });
+    let polled_messages = next(&mut subscription).await;
+    assert_offsets(&polled_messages, MESSAGES_COUNT as u64, 1);
+    sender.await.unwrap();
+    subscription.close().await.unwrap();
+
+    // 6. With the acknowledged mode, the offset should be stored only once the messages are acknowledged
+    let consumer = Consumer::new(Identifier::numeric(2).unwrap());
+    let mut subscription = subscribe(&client, &consumer, AckMode::Acknowledged, 100).await;
+    let polled_messages = next(&mut subscription).await;
+    assert_offsets(&polled_messages, 0, BATCH_SIZE);
+    assert_eq!(get_stored_offset(&client, &consumer).await, None);
+    subscription.ack(PARTITION_ID, 3).await.unwrap();
+    assert_eq!(get_stored_offset(&client, &consumer).await, Some(3));
+    subscription.close().await.unwrap();
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient, count: u32) {
+    let mut messages = (0..count)
+        .map(|index| Message::new(None, Bytes::from(format!("message {index}")), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn subscribe(
+    client: &IggyClient,
+    consumer: &Consumer,
+    ack_mode: AckMode,
+    credit: u64,
+) -> MessageSubscription {
+    client
+        .subscribe_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            consumer,
+            &PollingStrategy::offset(0),
+            BATCH_SIZE,
+            &PollMessagesOptions::default(),
+            &SubscriptionOptions::default()
+                .ack_mode(ack_mode)
+                .credit(CreditKind::Messages, credit),
+        )
+        .await
+        .unwrap()
+}
+
+async fn next(subscription: &mut MessageSubscription) -> PolledMessages {
+    timeout(MESSAGES_TIMEOUT, subscription.next())
+        .await
+        .expect("Messages were not pushed in time")
+        .unwrap()
+        .expect("Subscription was closed")
+}
+
+fn assert_offsets(polled_messages: &PolledMessages, first_offset: u64, count: u32) {
+    assert_eq!(polled_messages.partition_id, PARTITION_ID);
+    assert_eq!(polled_messages.messages.len() as u32, count);
+    for (index, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.offset, first_offset + index as u64);
+    }
+}
+
+// The offset is stored by the server in the background of the subscription, so it might take a moment.
+async fn get_stored_offset(client: &IggyClient, consumer: &Consumer) -> Option<u64> {
+    let mut stored_offset = None;
+    for _ in 0..10 {
+        stored_offset = client
+            .get_consumer_offset(
+                consumer,
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                Some(PARTITION_ID),
+            )
+            .await
+            .unwrap()
+            .map(|offset| offset.stored_offset);
+        if stored_offset.is_some() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    stored_offset
+}
@@ -0,0 +1,180 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME, USERNAME_1,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient, UserClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::user_quotas::{GlobalQuotas, UserQuotas};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{
+    assert_clean_system, create_user, delete_user, login_root, login_user, ClientFactory,
+};
+use tokio::time::sleep;
+
+const QUOTA_BYTES_PER_SECOND: u64 = 1000;
+const MESSAGES_COUNT: u32 = 10;
+const MESSAGE_PAYLOAD_SIZE: usize = 200;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let root_client = create_client(client_factory).await;
+    login_root(&root_client).await;
+    init_system(&root_client).await;
+    create_user(&root_client, USERNAME_1).await;
+
+    // 1. Set the produce and consume quotas for the user
+    let quotas = UserQuotas {
+        global: GlobalQuotas {
+            produce_bytes_per_second: QUOTA_BYTES_PER_SECOND,
+            consume_bytes_per_second: QUOTA_BYTES_PER_SECOND,
+            requests_per_second: 0,
+        },
+        streams: None,
+    };
+    root_client
+        .update_user(
+            &Identifier::named(USERNAME_1).unwrap(),
+            None,
+            None,
+            Some(quotas.clone()),
+        )
+        .await
+        .unwrap();
+
+    // 2. The quotas should be returned along with the user details
+    let user = root_client
+        .get_user(&Identifier::named(USERNAME_1).unwrap())
+        .await
+        .unwrap()
+        .expect("User should exist");
+    assert_eq!(user.quotas, Some(quotas));
+
+    let client = create_client(client_factory).await;
+    login_user(&client, USERNAME_1).await;
+
+    // 3. The batch exceeding the produce quota should be accepted when the quota has not been used yet
+    send_messages(&client).await.unwrap();
+
+    // 4. The next batch should be rejected with the retry-after hint
+    let error = send_messages(&client).await.unwrap_err();
+    assert!(matches!(error, IggyError::QuotaExceeded(_)));
+    let retry_after = error
+        .retry_after()
+        .expect("Retry-after hint should be provided");
+    assert!(!retry_after.is_zero());
+
+    // 5. The batch should be accepted again after the retry-after time
+    sleep(retry_after).await;
+    send_messages(&client).await.unwrap();
+
+    // 6. The messages exceeding the consume quota should be polled when the quota has not been used yet
+    let polled_messages = poll_messages(&client).await.unwrap();
+    assert_eq!(polled_messages, MESSAGES_COUNT * 2);
+
+    // 7. The next poll should be rejected with the retry-after hint
+    let error = poll_messages(&client).await.unwrap_err();
+    assert!(matches!(error, IggyError::QuotaExceeded(_)));
+    assert!(error.retry_after().is_some());
+
+    // 8. Removing the quotas should make the rates unlimited
+    root_client
+        .update_user(
+            &Identifier::named(USERNAME_1).unwrap(),
+            None,
+            None,
+            Some(UserQuotas::default()),
+        )
+        .await
+        .unwrap();
+    let user = root_client
+        .get_user(&Identifier::named(USERNAME_1).unwrap())
+        .await
+        .unwrap()
+        .expect("User should exist");
+    assert!(user.quotas.is_none());
+    send_messages(&client).await.unwrap();
+    poll_messages(&client).await.unwrap();
+
+    delete_user(&root_client, USERNAME_1).await;
+    cleanup(&root_client, false).await;
+    assert_clean_system(&root_client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient) -> Result<(), IggyError> {
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|_| Message::new(None, Bytes::from(vec![1; MESSAGE_PAYLOAD_SIZE]), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+}
+
+async fn poll_messages(client: &IggyClient) -> Result<u32, IggyError> {
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT * 2,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await?;
+    Ok(polled_messages.messages.len() as u32)
+}
@@ -0,0 +1,158 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID,
+    TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessage;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MESSAGES_COUNT: u32 = 10;
+const REPLICATION_FACTOR: u8 = 2;
+
+pub async fn run(leader_factory: &dyn ClientFactory, follower_factory: &dyn ClientFactory) {
+    let leader = create_client(leader_factory).await;
+    login_root(&leader).await;
+    let follower = create_client(follower_factory).await;
+    login_root(&follower).await;
+    init_system(&leader).await;
+
+    // 1. Send messages to the leader, the server confirmation waits for the follower acknowledgement
+    let mut messages = create_messages();
+    leader
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // 2. Replicated stream and topic should be created on the follower
+    let stream = follower
+        .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+        .await
+        .unwrap()
+        .expect("Replicated stream should exist on the follower");
+    assert_eq!(stream.name, STREAM_NAME);
+    assert_eq!(stream.topics.len(), 1);
+    let topic = &stream.topics[0];
+    assert_eq!(topic.id, TOPIC_ID);
+    assert_eq!(topic.name, TOPIC_NAME);
+    assert_eq!(topic.partitions_count, PARTITIONS_COUNT);
+    assert_eq!(topic.replication_factor, REPLICATION_FACTOR);
+
+    // 3. Messages polled from the follower should be the same as the ones on the leader
+    let consumer = Consumer::default();
+    let leader_messages = poll_messages(&leader, &consumer).await;
+    let follower_messages = poll_messages(&follower, &consumer).await;
+    assert_eq!(leader_messages.len() as u32, MESSAGES_COUNT);
+    assert_eq!(follower_messages.len(), leader_messages.len());
+    for (leader_message, follower_message) in leader_messages.iter().zip(&follower_messages) {
+        assert_eq!(follower_message.offset, leader_message.offset);
+        assert_eq!(follower_message.id, leader_message.id);
+        assert_eq!(follower_message.timestamp, leader_message.timestamp);
+        assert_eq!(follower_message.checksum, leader_message.checksum);
+        assert_eq!(follower_message.payload, leader_message.payload);
+    }
+
+    // 4. Sending messages to the replicated topic on the follower should fail
+    let mut messages = create_messages();
+    let result = follower
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await;
+    assert!(matches!(result, Err(IggyError::NotReplicationLeader)));
+
+    cleanup(&leader, false).await;
+    assert_clean_system(&leader).await;
+    cleanup(&follower, false).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the replicated topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            Some(REPLICATION_FACTOR),
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(client: &IggyClient, consumer: &Consumer) -> Vec<PolledMessage> {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            consumer,
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap()
+        .messages
+}
+
+fn create_messages() -> Vec<Message> {
+    (0..MESSAGES_COUNT)
+        .map(|offset| {
+            let payload = Bytes::from(format!("message {offset}"));
+            Message {
+                id: (offset + 1) as u128,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }
+        })
+        .collect()
+}
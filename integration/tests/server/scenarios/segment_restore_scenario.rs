@@ -0,0 +1,226 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, SegmentClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::segments::restore_segments::{ArchiveSource, RestoreRange};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BATCHES_COUNT: u32 = 3;
+const MESSAGES_PER_BATCH: u32 = 10;
+const PAYLOAD_SIZE: usize = 100;
+const ARCHIVE_TIMEOUT: Duration = Duration::from_secs(10);
+const ARCHIVE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Returns the server configuration archiving the oldest closed segments to the disk,
+/// with the segments small enough for each batch of the scenario to close its own segment.
+pub fn server_envs(archive_path: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (
+            "IGGY_DATA_MAINTENANCE_ARCHIVER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_ARCHIVER_DISK_PATH".to_string(),
+            archive_path.to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_MESSAGES_ARCHIVER_ENABLED".to_string(),
+            "true".to_string(),
+        ),
+        (
+            "IGGY_DATA_MAINTENANCE_MESSAGES_INTERVAL".to_string(),
+            "1 s".to_string(),
+        ),
+        ("IGGY_SYSTEM_SEGMENT_SIZE".to_string(), "1 KB".to_string()),
+        (
+            "IGGY_SYSTEM_PARTITION_MESSAGES_REQUIRED_TO_SAVE".to_string(),
+            "1".to_string(),
+        ),
+    ])
+}
+
+pub async fn run(client_factory: &dyn ClientFactory, archive_path: &str) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Send the batches of messages, each one stored in its own segment
+    let messages_count = BATCHES_COUNT * MESSAGES_PER_BATCH;
+    for batch in 0..BATCHES_COUNT {
+        let mut messages = (0..MESSAGES_PER_BATCH)
+            .map(|index| {
+                let payload = create_message_payload(batch * MESSAGES_PER_BATCH + index);
+                Message::new(None, payload, None)
+            })
+            .collect::<Vec<_>>();
+        client
+            .send_messages(
+                &Identifier::numeric(STREAM_ID).unwrap(),
+                &Identifier::numeric(TOPIC_ID).unwrap(),
+                &Partitioning::partition_id(PARTITION_ID),
+                &mut messages,
+            )
+            .await
+            .unwrap();
+    }
+    let segments_count = get_segments_count(&client).await;
+    assert_eq!(segments_count, BATCHES_COUNT);
+
+    // 2. Wait until the oldest closed segment is archived
+    wait_until_segment_is_archived(archive_path).await;
+
+    // 3. Delete the oldest segment
+    client
+        .delete_segments(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            1,
+        )
+        .await
+        .unwrap();
+    assert_eq!(get_segments_count(&client).await, segments_count - 1);
+
+    // 4. Restore the deleted segment from the archive
+    client
+        .restore_segments(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            PARTITION_ID,
+            &ArchiveSource {
+                stream_id: STREAM_ID,
+                topic_id: TOPIC_ID,
+                partition_id: PARTITION_ID,
+                archive_path: None,
+            },
+            &RestoreRange::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(get_segments_count(&client).await, segments_count);
+
+    // 5. Poll all the messages, including the ones from the restored segment
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            messages_count,
+            false,
+            &PollMessagesOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u32, messages_count);
+    for (offset, message) in polled_messages.messages.iter().enumerate() {
+        assert_eq!(message.offset, offset as u64);
+        assert_eq!(message.payload, create_message_payload(offset as u32));
+    }
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic with the single partition
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn get_segments_count(client: &IggyClient) -> u32 {
+    let topic = client
+        .get_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Topic not found");
+    topic
+        .partitions
+        .iter()
+        .find(|partition| partition.id == PARTITION_ID)
+        .expect("Partition not found")
+        .segments_count
+}
+
+async fn wait_until_segment_is_archived(archive_path: &str) {
+    let mut elapsed = Duration::ZERO;
+    while !contains_log_file(Path::new(archive_path)) {
+        assert!(
+            elapsed < ARCHIVE_TIMEOUT,
+            "Segment has not been archived in: {archive_path}"
+        );
+        sleep(ARCHIVE_CHECK_INTERVAL).await;
+        elapsed += ARCHIVE_CHECK_INTERVAL;
+    }
+}
+
+fn contains_log_file(path: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        match path.is_dir() {
+            true => contains_log_file(&path),
+            false => path.extension().is_some_and(|extension| extension == "log"),
+        }
+    })
+}
+
+fn create_message_payload(offset: u32) -> Bytes {
+    Bytes::from(format!("{offset:0>PAYLOAD_SIZE$}"))
+}
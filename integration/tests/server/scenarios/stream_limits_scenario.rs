@@ -0,0 +1,171 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITION_ID, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, PartitionClient, StreamClient, TopicClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::stream_limits::StreamLimits;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MAX_TOPICS: u32 = 1;
+const MAX_PARTITIONS: u32 = 2;
+const MAX_SIZE_BYTES: u64 = 1000;
+const MESSAGES_COUNT: u32 = 10;
+const MESSAGE_PAYLOAD_SIZE: usize = 200;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+
+    // 1. Create the stream with the topics and partitions limits
+    let limits = StreamLimits {
+        max_size: None,
+        max_topics: Some(MAX_TOPICS),
+        max_partitions: Some(MAX_PARTITIONS),
+    };
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), Some(limits))
+        .await
+        .unwrap();
+
+    // 2. The topic exceeding the partitions limit should be rejected
+    let error = create_topic(&client, TOPIC_ID, MAX_PARTITIONS + 1)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        IggyError::StreamPartitionsLimitReached(_, _)
+    ));
+
+    // 3. The topic within the limits should be created
+    create_topic(&client, TOPIC_ID, MAX_PARTITIONS)
+        .await
+        .unwrap();
+
+    // 4. The next topic should be rejected due to the topics limit
+    let error = create_topic(&client, TOPIC_ID + 1, 1).await.unwrap_err();
+    assert!(matches!(error, IggyError::StreamTopicsLimitReached(_, _)));
+
+    // 5. The partitions exceeding the limit should not be added to the existing topic
+    let error = client
+        .create_partitions(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            1,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        IggyError::StreamPartitionsLimitReached(_, _)
+    ));
+
+    // 6. Update the stream limits to bound its size only
+    client
+        .update_stream(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            STREAM_NAME,
+            Some(StreamLimits {
+                max_size: Some(IggyByteSize::from(MAX_SIZE_BYTES)),
+                max_topics: None,
+                max_partitions: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+    // 7. The topics and partitions should not be limited anymore
+    create_topic(&client, TOPIC_ID + 1, MAX_PARTITIONS + 1)
+        .await
+        .unwrap();
+
+    // 8. The batch exceeding the max size should be accepted when the stream is not full yet
+    send_messages(&client).await.unwrap();
+
+    // 9. The next batch should be rejected as the stream is full
+    let error = send_messages(&client).await.unwrap_err();
+    assert!(matches!(error, IggyError::StreamFull(_)));
+
+    // 10. Updating the stream without the limits should keep the existing ones
+    client
+        .update_stream(&Identifier::numeric(STREAM_ID).unwrap(), STREAM_NAME, None)
+        .await
+        .unwrap();
+    let error = send_messages(&client).await.unwrap_err();
+    assert!(matches!(error, IggyError::StreamFull(_)));
+
+    // 11. Removing the limits should make the stream unlimited
+    client
+        .update_stream(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            STREAM_NAME,
+            Some(StreamLimits::default()),
+        )
+        .await
+        .unwrap();
+    send_messages(&client).await.unwrap();
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn create_topic(
+    client: &IggyClient,
+    topic_id: u32,
+    partitions_count: u32,
+) -> Result<(), IggyError> {
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &format!("{TOPIC_NAME}-{topic_id}"),
+            partitions_count,
+            CompressionAlgorithm::default(),
+            None,
+            Some(topic_id),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn send_messages(client: &IggyClient) -> Result<(), IggyError> {
+    let mut messages = (0..MESSAGES_COUNT)
+        .map(|_| Message::new(None, Bytes::from(vec![1; MESSAGE_PAYLOAD_SIZE]), None))
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+}
@@ -23,6 +23,7 @@ use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::identifier::Identifier;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use integration::test_server::{assert_clean_system, login_root, ClientFactory};
@@ -153,6 +154,7 @@ async fn create_topic_assert_empty(client: &IggyClient, stream_name: &str, topic
             None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -163,7 +165,7 @@ async fn create_topic_assert_empty(client: &IggyClient, stream_name: &str, topic
 
 async fn create_stream_assert_empty(client: &IggyClient, stream_name: &str) {
     // 1. Create stream
-    client.create_stream(stream_name, None).await.unwrap();
+    client.create_stream(stream_name, None, None).await.unwrap();
 
     // 2. Validate stream size and number of messages
     validate_stream(client, stream_name, 0, 0).await;
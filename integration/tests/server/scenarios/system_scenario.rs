@@ -17,6 +17,7 @@
  */
 
 use bytes::Bytes;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use std::str::FromStr;
 
 use crate::server::scenarios::{
@@ -31,9 +32,10 @@ use iggy::client::{
 use iggy::clients::client::IggyClient;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::Consumer;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::messages::PolledMessage;
 use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
@@ -66,7 +68,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
 
     // 3. Create the stream
     let stream = client
-        .create_stream(STREAM_NAME, Some(STREAM_ID))
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
         .await
         .unwrap();
 
@@ -109,12 +111,14 @@ pub async fn run(client_factory: &dyn ClientFactory) {
 
     // 7. Try to create the stream with the same ID but the different name and validate that it fails
     let create_stream_result = client
-        .create_stream(&format!("{}-2", STREAM_NAME), Some(STREAM_ID))
+        .create_stream(&format!("{}-2", STREAM_NAME), Some(STREAM_ID), None)
         .await;
     assert!(create_stream_result.is_err());
 
     // 8. Try to create the stream with the same name but the different ID and validate that it fails
-    let create_stream_result = client.create_stream(STREAM_NAME, Some(STREAM_ID + 1)).await;
+    let create_stream_result = client
+        .create_stream(STREAM_NAME, Some(STREAM_ID + 1), None)
+        .await;
     assert!(create_stream_result.is_err());
 
     // 9. Create the topic
@@ -128,6 +132,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -218,6 +223,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             Some(TOPIC_ID),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await;
     assert!(create_topic_result.is_err());
@@ -233,6 +239,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             Some(TOPIC_ID + 1),
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await;
     assert!(create_topic_result.is_err());
@@ -259,6 +266,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::offset(0),
             MESSAGES_COUNT,
             false,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -283,6 +291,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
                 &PollingStrategy::offset(start_offset),
                 batch_size,
                 false,
+                &PollMessagesOptions::default(),
             )
             .await
             .unwrap();
@@ -326,6 +335,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::offset(0),
             MESSAGES_COUNT,
             false,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -352,6 +362,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             Some(PARTITION_ID),
             stored_offset,
+            None,
         )
         .await
         .unwrap();
@@ -402,6 +413,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             Some(PARTITION_ID),
             stored_offset,
+            None,
         )
         .await
         .unwrap();
@@ -417,6 +429,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::next(),
             messages_count,
             true,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -427,7 +440,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
     assert_eq!(first_offset, stored_offset + 1);
     assert_eq!(last_offset, expected_last_offset);
 
-    // 28. Get the existing customer offset and ensure that auto commit during poll has worked
+    // 28. Get the existing customer offset and ensure that auto commit during poll has worked, along with the consumer lag
     let offset = client
         .get_consumer_offset(
             &consumer,
@@ -441,6 +454,26 @@ pub async fn run(client_factory: &dyn ClientFactory) {
     assert_eq!(offset.partition_id, PARTITION_ID);
     assert_eq!(offset.current_offset, (MESSAGES_COUNT - 1) as u64);
     assert_eq!(offset.stored_offset, expected_last_offset);
+    let lag = client
+        .get_consumer_lag(
+            &consumer,
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+        )
+        .await
+        .unwrap();
+    let partition_lag = lag
+        .partitions
+        .iter()
+        .find(|partition| partition.partition_id == PARTITION_ID)
+        .expect("Failed to get partition lag");
+    assert_eq!(lag.stream_id, STREAM_ID);
+    assert_eq!(lag.topic_id, TOPIC_ID);
+    assert_eq!(partition_lag.stored_offset, Some(expected_last_offset));
+    assert_eq!(
+        partition_lag.lag_messages,
+        (MESSAGES_COUNT - 1) as u64 - expected_last_offset
+    );
 
     // 29. Get the consumer groups and validate that there are no groups
     let consumer_groups = client
@@ -460,6 +493,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::numeric(TOPIC_ID).unwrap(),
             CONSUMER_GROUP_NAME,
             Some(CONSUMER_GROUP_ID),
+            AssignmentStrategy::default(),
         )
         .await
         .unwrap();
@@ -624,6 +658,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             Some(updated_replication_factor),
             IggyExpiry::ExpireDuration(message_expiry_duration),
             updated_max_topic_size,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -667,6 +702,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::offset(0),
             MESSAGES_COUNT,
             false,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -680,6 +716,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
         .update_stream(
             &Identifier::numeric(STREAM_ID).unwrap(),
             &updated_stream_name,
+            None,
         )
         .await
         .unwrap();
@@ -718,6 +755,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &PollingStrategy::offset(0),
             MESSAGES_COUNT,
             false,
+            &PollMessagesOptions::default(),
         )
         .await
         .unwrap();
@@ -741,7 +779,10 @@ pub async fn run(client_factory: &dyn ClientFactory) {
     // 43. Create the stream with automatically generated ID on the server
     let stream_name = format!("{}-auto", STREAM_NAME);
     let stream_id = STREAM_ID + 1;
-    client.create_stream(&stream_name, None).await.unwrap();
+    client
+        .create_stream(&stream_name, None, None)
+        .await
+        .unwrap();
 
     let stream = client
         .get_stream(&Identifier::numeric(stream_id).unwrap())
@@ -765,6 +806,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             None,
             IggyExpiry::NeverExpire,
             MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
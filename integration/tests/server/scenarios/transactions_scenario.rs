@@ -0,0 +1,193 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    cleanup, create_client, PARTITIONS_COUNT, STREAM_ID, STREAM_NAME, TOPIC_ID, TOPIC_NAME,
+};
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient, TransactionClient};
+use iggy::clients::client::IggyClient;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{IsolationLevel, PollMessagesOptions, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::messages::PolledMessages;
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{assert_clean_system, login_root, ClientFactory};
+
+const MESSAGES_COUNT: u32 = 3;
+const FIRST_PARTITION_ID: u32 = 1;
+const SECOND_PARTITION_ID: u32 = 2;
+
+pub async fn run(client_factory: &dyn ClientFactory) {
+    let client = create_client(client_factory).await;
+    login_root(&client).await;
+    init_system(&client).await;
+
+    // 1. Begin the transaction, another one can't be opened until it's completed
+    let transaction_id = client.begin_transaction().await.unwrap();
+    assert!(transaction_id > 0);
+    let result = client.begin_transaction().await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::TransactionAlreadyOpen(transaction_id).as_code()
+    );
+
+    // 2. Send the messages to multiple partitions as a part of the transaction
+    send_messages(&client, FIRST_PARTITION_ID, 0).await;
+    send_messages(&client, SECOND_PARTITION_ID, 0).await;
+
+    // 3. The messages should be visible only to the read uncommitted consumers
+    for partition_id in [FIRST_PARTITION_ID, SECOND_PARTITION_ID] {
+        let polled_messages =
+            poll_messages(&client, partition_id, 0, IsolationLevel::ReadCommitted).await;
+        assert!(polled_messages.messages.is_empty());
+        let polled_messages =
+            poll_messages(&client, partition_id, 0, IsolationLevel::ReadUncommitted).await;
+        assert_eq!(polled_messages.messages.len(), MESSAGES_COUNT as usize);
+    }
+
+    // 4. Commit the transaction, the messages should be visible to the read committed consumers
+    client.commit_transaction(transaction_id).await.unwrap();
+    for partition_id in [FIRST_PARTITION_ID, SECOND_PARTITION_ID] {
+        let polled_messages =
+            poll_messages(&client, partition_id, 0, IsolationLevel::ReadCommitted).await;
+        assert_eq!(polled_messages.messages.len(), MESSAGES_COUNT as usize);
+    }
+
+    // 5. Committing the already completed transaction should fail
+    let result = client.commit_transaction(transaction_id).await;
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::TransactionNotFound(transaction_id).as_code()
+    );
+
+    // 6. Send the messages as a part of another transaction and abort it
+    let aborted_transaction_id = client.begin_transaction().await.unwrap();
+    assert!(aborted_transaction_id > transaction_id);
+    send_messages(&client, FIRST_PARTITION_ID, MESSAGES_COUNT).await;
+    client
+        .abort_transaction(aborted_transaction_id)
+        .await
+        .unwrap();
+
+    // 7. Send the messages outside of the transaction
+    send_messages(&client, FIRST_PARTITION_ID, 2 * MESSAGES_COUNT).await;
+
+    // 8. The read committed consumers should skip the aborted messages
+    let polled_messages = poll_messages(
+        &client,
+        FIRST_PARTITION_ID,
+        MESSAGES_COUNT as u64,
+        IsolationLevel::ReadCommitted,
+    )
+    .await;
+    assert_eq!(polled_messages.messages.len(), MESSAGES_COUNT as usize);
+    for (index, message) in polled_messages.messages.iter().enumerate() {
+        let offset = (2 * MESSAGES_COUNT) as u64 + index as u64;
+        assert_eq!(message.offset, offset);
+        assert_eq!(message.payload, create_message_payload(offset));
+    }
+
+    // 9. The read uncommitted consumers should still see all the messages
+    let polled_messages = poll_messages(
+        &client,
+        FIRST_PARTITION_ID,
+        MESSAGES_COUNT as u64,
+        IsolationLevel::ReadUncommitted,
+    )
+    .await;
+    assert_eq!(polled_messages.messages.len(), 2 * MESSAGES_COUNT as usize);
+
+    cleanup(&client, false).await;
+    assert_clean_system(&client).await;
+}
+
+async fn init_system(client: &IggyClient) {
+    // 1. Create the stream
+    client
+        .create_stream(STREAM_NAME, Some(STREAM_ID), None)
+        .await
+        .unwrap();
+
+    // 2. Create the topic
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            TOPIC_NAME,
+            PARTITIONS_COUNT,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+            CleanupPolicy::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn send_messages(client: &IggyClient, partition_id: u32, first_offset: u32) {
+    let mut messages = Vec::new();
+    for offset in first_offset..first_offset + MESSAGES_COUNT {
+        let payload = create_message_payload(offset as u64);
+        messages.push(Message::new(None, payload, None));
+    }
+
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
+
+async fn poll_messages(
+    client: &IggyClient,
+    partition_id: u32,
+    offset: u64,
+    isolation_level: IsolationLevel,
+) -> PolledMessages {
+    client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(partition_id),
+            &Consumer::default(),
+            &PollingStrategy::offset(offset),
+            10,
+            false,
+            &PollMessagesOptions {
+                isolation_level,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+}
+
+fn create_message_payload(offset: u64) -> Bytes {
+    Bytes::from(format!("message {}", offset))
+}
@@ -211,6 +211,7 @@ pub async fn run(client_factory: &dyn ClientFactory) {
             &Identifier::named(test_user).unwrap(),
             Some(updated_test_user),
             Some(UserStatus::Inactive),
+            None,
         )
         .await
         .unwrap();
@@ -0,0 +1,60 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::{BufMut, BytesMut};
+use iggy::binary::BinaryTransport;
+use iggy::client::{Client, SystemClient, UserClient};
+use iggy::command::{GET_ME_CODE, REQUEST_ID_FLAG};
+use iggy::error::IggyError;
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_USER_ID};
+use iggy::websocket::client::WebSocketClient;
+use iggy::websocket::config::WebSocketClientConfig;
+use std::sync::Arc;
+
+const REQUEST_ID: u64 = 1;
+
+pub async fn run(server_addr: &str) {
+    let client = WebSocketClient::create(Arc::new(WebSocketClientConfig {
+        server_address: format!("ws://{server_addr}/ws"),
+        ..WebSocketClientConfig::default()
+    }))
+    .unwrap();
+    Client::connect(&client).await.unwrap();
+    client
+        .login_user(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD)
+        .await
+        .unwrap();
+
+    // 1. The request carrying the request ID should be rejected, as the WebSocket responses can't be multiplexed
+    let mut payload = BytesMut::with_capacity(8);
+    payload.put_u64_le(REQUEST_ID);
+    let result = client
+        .send_raw_with_response(GET_ME_CODE | REQUEST_ID_FLAG, payload.freeze())
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().as_code(),
+        IggyError::FeatureUnavailable.as_code()
+    );
+
+    // 2. The connection should keep serving the regular requests afterwards
+    client.ping().await.unwrap();
+    let me = client.get_me().await.unwrap();
+    assert_eq!(me.user_id, Some(DEFAULT_ROOT_USER_ID));
+    assert_eq!(me.transport, "WebSocket");
+}
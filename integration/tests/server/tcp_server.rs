@@ -17,13 +17,28 @@
  */
 
 use crate::server::scenarios::{
-    consumer_group_join_scenario, consumer_group_with_multiple_clients_polling_messages_scenario,
+    client_certificate_scenario, cluster_scenario, consumer_group_assigned_partitions_scenario,
+    consumer_group_join_scenario, consumer_group_rebalance_scenario,
+    consumer_group_with_multiple_clients_polling_messages_scenario,
     consumer_group_with_single_client_polling_messages_scenario, create_message_payload,
-    message_headers_scenario, message_size_scenario, stream_size_validation_scenario,
-    system_scenario, user_scenario,
+    dead_letter_scenario, handshake_scenario, idempotent_producer_scenario, long_polling_scenario,
+    message_filter_scenario, message_headers_scenario, message_size_scenario,
+    multiplexing_scenario, oidc_scenario, push_subscription_scenario, quotas_scenario,
+    replication_scenario, segment_restore_scenario, stream_limits_scenario,
+    stream_size_validation_scenario, system_scenario, transactions_scenario, user_scenario,
+    wire_compression_scenario,
+};
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
+use iggy::models::handshake::{ProtocolFeature, ProtocolFeatures};
+use integration::{
+    tcp_client::TcpClientFactory,
+    test_certificates::TestCertificates,
+    test_identity_provider::TestIdentityProvider,
+    test_server::{ClientFactory, IpAddrKind, TestServer},
 };
-use integration::{tcp_client::TcpClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::collections::HashMap;
+use std::net::TcpListener;
 
 #[tokio::test]
 #[parallel]
@@ -116,6 +131,32 @@ async fn consumer_group_with_multiple_clients_polling_messages_scenario_should_b
     consumer_group_with_multiple_clients_polling_messages_scenario::run(&client_factory).await;
 }
 
+#[tokio::test]
+#[parallel]
+async fn consumer_group_rebalance_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    consumer_group_rebalance_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn consumer_group_assigned_partitions_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    consumer_group_assigned_partitions_scenario::run(&client_factory).await;
+}
+
 #[tokio::test]
 #[parallel]
 async fn stream_size_validation_scenario_should_be_valid() {
@@ -141,3 +182,348 @@ async fn message_size_scenario_should_be_valid() {
     };
     message_size_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn dead_letter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    dead_letter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn quotas_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    quotas_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn stream_limits_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    stream_limits_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn multiplexing_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        multiplexing: true,
+        ..Default::default()
+    };
+    multiplexing_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn handshake_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    let expected_features = ProtocolFeatures::new(&[
+        ProtocolFeature::Subscriptions,
+        ProtocolFeature::ConsumerGroupGenerations,
+    ]);
+    handshake_scenario::run(&client_factory, expected_features).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn handshake_with_multiplexing_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        multiplexing: true,
+        ..Default::default()
+    };
+    let expected_features = ProtocolFeatures::new(&[
+        ProtocolFeature::Multiplexing,
+        ProtocolFeature::Subscriptions,
+        ProtocolFeature::ConsumerGroupGenerations,
+    ]);
+    handshake_scenario::run(&client_factory, expected_features).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn wire_compression_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        compression: Some(WireCompressionAlgorithm::Lz4),
+        ..Default::default()
+    };
+    wire_compression_scenario::run(&client_factory, WireCompressionAlgorithm::Lz4).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn wire_compression_with_multiplexing_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        multiplexing: true,
+        compression: Some(WireCompressionAlgorithm::Zstd),
+        ..Default::default()
+    };
+    wire_compression_scenario::run(&client_factory, WireCompressionAlgorithm::Zstd).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn transactions_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    transactions_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn idempotent_producer_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    idempotent_producer_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn message_filter_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    message_filter_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn replication_scenario_should_be_valid() {
+    let mut leader_envs = HashMap::new();
+    leader_envs.insert(
+        "IGGY_SYSTEM_REPLICATION_ENABLED".to_string(),
+        "true".to_string(),
+    );
+    leader_envs.insert(
+        "IGGY_SYSTEM_SEGMENT_SERVER_CONFIRMATION".to_string(),
+        "quorum".to_string(),
+    );
+    let mut leader_server = TestServer::new(Some(leader_envs), true, None, IpAddrKind::V4);
+    leader_server.start();
+    let leader_addr = leader_server.get_raw_tcp_addr().unwrap();
+
+    let mut follower_envs = HashMap::new();
+    follower_envs.insert(
+        "IGGY_SYSTEM_REPLICATION_ENABLED".to_string(),
+        "true".to_string(),
+    );
+    follower_envs.insert(
+        "IGGY_SYSTEM_REPLICATION_NODE_ID".to_string(),
+        "2".to_string(),
+    );
+    follower_envs.insert(
+        "IGGY_CLUSTER_IDENTITY_ROLE".to_string(),
+        "follower".to_string(),
+    );
+    follower_envs.insert(
+        "IGGY_SYSTEM_REPLICATION_LEADER_ADDRESS".to_string(),
+        leader_addr.clone(),
+    );
+    let mut follower_server = TestServer::new(Some(follower_envs), true, None, IpAddrKind::V4);
+    follower_server.start();
+    let follower_addr = follower_server.get_raw_tcp_addr().unwrap();
+
+    let leader_factory = TcpClientFactory {
+        server_addr: leader_addr,
+        ..Default::default()
+    };
+    let follower_factory = TcpClientFactory {
+        server_addr: follower_addr,
+        ..Default::default()
+    };
+    replication_scenario::run(&leader_factory, &follower_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn cluster_scenario_should_be_valid() {
+    const NODES_COUNT: u32 = 3;
+    let addresses = (0..NODES_COUNT)
+        .map(|_| {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    let nodes = addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| format!("{}@{address}", index + 1))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut servers = Vec::with_capacity(addresses.len());
+    for (index, address) in addresses.iter().enumerate() {
+        let mut envs = HashMap::new();
+        envs.insert("IGGY_CLUSTER_ENABLED".to_string(), "true".to_string());
+        envs.insert("IGGY_CLUSTER_ID".to_string(), (index + 1).to_string());
+        envs.insert("IGGY_CLUSTER_NODES".to_string(), format!("[{nodes}]"));
+        envs.insert("IGGY_TCP_ADDRESS".to_string(), address.clone());
+        envs.insert("IGGY_HTTP_ADDRESS".to_string(), "127.0.0.1:0".to_string());
+        envs.insert("IGGY_QUIC_ADDRESS".to_string(), "127.0.0.1:0".to_string());
+        let mut server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+        server.start();
+        servers.push(server);
+    }
+
+    let factories = servers
+        .iter()
+        .map(|server| TcpClientFactory {
+            server_addr: server.get_raw_tcp_addr().unwrap(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let factories = factories
+        .iter()
+        .map(|factory| factory as &dyn ClientFactory)
+        .collect::<Vec<_>>();
+    cluster_scenario::run(&factories).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn long_polling_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    long_polling_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn push_subscription_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    // The subscription uses the dedicated connection, which has to sign in on its own.
+    let client_factory = TcpClientFactory {
+        server_addr,
+        auto_login: true,
+        ..Default::default()
+    };
+    push_subscription_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn client_certificate_scenario_should_be_valid() {
+    let certificates = TestCertificates::generate(&["iggy", "unknown"]);
+    let mut envs = HashMap::new();
+    envs.insert("IGGY_TCP_TLS_ENABLED".to_string(), "true".to_string());
+    envs.insert(
+        "IGGY_TCP_TLS_CERT_FILE".to_string(),
+        certificates.server_cert_file(),
+    );
+    envs.insert(
+        "IGGY_TCP_TLS_KEY_FILE".to_string(),
+        certificates.server_key_file(),
+    );
+    envs.insert(
+        "IGGY_TCP_TLS_CLIENT_CERTIFICATE_ENABLED".to_string(),
+        "true".to_string(),
+    );
+    envs.insert(
+        "IGGY_TCP_TLS_CLIENT_CERTIFICATE_CA_FILE".to_string(),
+        certificates.ca_file(),
+    );
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let create_factory = |name: &str| TcpClientFactory {
+        server_addr: server_addr.clone(),
+        tls_ca_file: Some(certificates.ca_file()),
+        tls_certificate_file: Some(certificates.client_cert_file(name)),
+        tls_key_file: Some(certificates.client_key_file(name)),
+        ..Default::default()
+    };
+    client_certificate_scenario::run(&create_factory("iggy"), &create_factory("unknown")).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn oidc_scenario_should_be_valid() {
+    let provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let untrusted_provider = TestIdentityProvider::generate(oidc_scenario::OIDC_ISSUER);
+    let envs = oidc_scenario::create_server_envs(&provider);
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    oidc_scenario::run(&client_factory, &provider, &untrusted_provider).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn segment_restore_scenario_should_be_valid() {
+    let archive = tempfile::tempdir().unwrap();
+    let archive_path = archive.path().to_str().unwrap();
+    let envs = segment_restore_scenario::server_envs(archive_path);
+    let mut test_server = TestServer::new(Some(envs), true, None, IpAddrKind::V4);
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client_factory = TcpClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    segment_restore_scenario::run(&client_factory, archive_path).await;
+}
@@ -0,0 +1,125 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::server::scenarios::{
+    long_polling_scenario, message_headers_scenario, push_subscription_scenario, quotas_scenario,
+    stream_limits_scenario, system_scenario, user_scenario, websocket_multiplexing_scenario,
+};
+use integration::{test_server::TestServer, websocket_client::WebSocketClientFactory};
+use serial_test::parallel;
+
+#[tokio::test]
+#[parallel]
+async fn system_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    system_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn user_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    user_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn message_headers_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    message_headers_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn long_polling_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    long_polling_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn push_subscription_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    // The subscription uses the dedicated connection, which has to sign in on its own.
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        auto_login: true,
+    };
+    push_subscription_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn quotas_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    quotas_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn stream_limits_scenario_should_be_valid() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    let client_factory = WebSocketClientFactory {
+        server_addr,
+        ..Default::default()
+    };
+    stream_limits_scenario::run(&client_factory).await;
+}
+
+#[tokio::test]
+#[parallel]
+async fn multiplexing_scenario_should_be_rejected() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_http_api_addr().unwrap();
+    websocket_multiplexing_scenario::run(&server_addr).await;
+}
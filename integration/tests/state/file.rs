@@ -25,6 +25,7 @@ use server::state::command::EntryCommand;
 use server::state::entry::StateEntry;
 use server::state::models::{CreateStreamWithId, CreateUserWithId};
 use server::state::State;
+use server::streaming::users::oidc::OidcSubject;
 
 #[tokio::test]
 async fn should_be_empty_given_initialized_state() {
@@ -50,6 +51,7 @@ async fn should_apply_single_entry() {
             status: Default::default(),
             permissions: None,
         },
+        oidc_subject: None,
     });
     let command_bytes = command.to_bytes();
 
@@ -61,6 +63,70 @@ async fn should_apply_single_entry() {
     assert_entry(entry, 0, setup.version(), user_id, command_bytes);
 }
 
+#[tokio::test]
+async fn should_append_created_entry_only_once_requested() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let command = EntryCommand::CreateStream(CreateStreamWithId {
+        stream_id: 1,
+        command: CreateStream {
+            stream_id: Some(1),
+            name: "test".to_string(),
+            limits: None,
+        },
+    });
+    let command_bytes = command.to_bytes();
+
+    let entry = state.create_entry(user_id, command);
+    assert!(state.load_entries().await.unwrap().is_empty());
+    assert_eq!(state.entries_count(), 0);
+
+    state.append_entry(&entry).await.unwrap();
+
+    let mut entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = entries.remove(0);
+    assert_entry(entry, 0, setup.version(), user_id, command_bytes);
+}
+
+#[tokio::test]
+async fn should_apply_entry_with_oidc_subject() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let oidc_subject = OidcSubject {
+        issuer: "https://idp.example.com".to_string(),
+        subject: "subject".to_string(),
+    };
+    let command = EntryCommand::CreateUser(CreateUserWithId {
+        user_id,
+        command: CreateUser {
+            username: "test".to_string(),
+            password: "secret".to_string(),
+            status: Default::default(),
+            permissions: None,
+        },
+        oidc_subject: Some(oidc_subject.clone()),
+    });
+    let command_bytes = command.to_bytes();
+
+    state.apply(user_id, command).await.unwrap();
+
+    let mut entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = entries.remove(0);
+    let EntryCommand::CreateUser(loaded_command) = entry.command().unwrap() else {
+        panic!("Invalid entry command");
+    };
+    assert_eq!(loaded_command.oidc_subject, Some(oidc_subject));
+    assert_entry(entry, 0, setup.version(), user_id, command_bytes);
+}
+
 #[tokio::test]
 async fn should_apply_encrypted_entry() {
     let setup = StateSetup::init_with_encryptor().await;
@@ -76,6 +142,7 @@ async fn should_apply_encrypted_entry() {
             status: Default::default(),
             permissions: None,
         },
+        oidc_subject: None,
     });
     let command_bytes = command.to_bytes();
 
@@ -107,6 +174,7 @@ async fn should_apply_multiple_entries() {
             status: Default::default(),
             permissions: None,
         },
+        oidc_subject: None,
     });
     let create_user_bytes = create_user.to_bytes();
 
@@ -122,6 +190,7 @@ async fn should_apply_multiple_entries() {
         command: CreateStream {
             stream_id: Some(stream_id),
             name: "test".to_string(),
+            limits: None,
         },
     });
     let create_stream_bytes = create_stream.to_bytes();
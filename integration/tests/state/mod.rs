@@ -26,6 +26,7 @@ use tokio::fs::create_dir;
 use uuid::Uuid;
 
 mod file;
+mod raft;
 mod system;
 
 pub struct StateSetup {
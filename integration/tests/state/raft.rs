@@ -0,0 +1,243 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::cluster::append_entries::AppendEntries;
+use iggy::cluster::request_vote::RequestVote;
+use iggy::error::IggyError;
+use iggy::models::cluster::ClusterNodeRole;
+use iggy::streams::create_stream::CreateStream;
+use iggy::users::create_user::CreateUser;
+use iggy::utils::duration::IggyDuration;
+use server::configs::cluster::{ClusterConfig, ClusterNodeConfig};
+use server::state::command::EntryCommand;
+use server::state::file::FileState;
+use server::state::models::{CreateStreamWithId, CreateUserWithId};
+use server::state::raft::RaftState;
+use server::state::State;
+use server::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+use server::versioning::SemanticVersion;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::fs::create_dir;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+const ELECTION_TIMEOUT: &str = "100 ms";
+
+struct RaftStateSetup {
+    directory_path: String,
+    state: RaftState,
+}
+
+impl RaftStateSetup {
+    async fn init(node_id: u32, nodes_count: u32) -> RaftStateSetup {
+        let directory_path = format!("raft_state_{}", Uuid::now_v7().to_u128_le());
+        create_dir(&directory_path).await.unwrap();
+
+        let config = ClusterConfig {
+            enabled: true,
+            id: node_id,
+            nodes: (1..=nodes_count)
+                .map(|id| ClusterNodeConfig {
+                    id,
+                    address: format!("127.0.0.1:{}", 8090 + id),
+                })
+                .collect(),
+            election_timeout: IggyDuration::from_str(ELECTION_TIMEOUT).unwrap(),
+            heartbeat_interval: IggyDuration::from_str("10 ms").unwrap(),
+            ..ClusterConfig::default()
+        };
+        let version = SemanticVersion::from_str("1.2.3").unwrap();
+        let persister = Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {}));
+        let log = FileState::new(
+            &format!("{directory_path}/log"),
+            &version,
+            persister.clone(),
+            None,
+        );
+        let state = RaftState::new(
+            &config,
+            log,
+            &format!("{directory_path}/metadata"),
+            persister,
+        );
+
+        Self {
+            directory_path,
+            state,
+        }
+    }
+}
+
+impl Drop for RaftStateSetup {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.directory_path).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn single_node_should_become_leader_and_commit_entries() {
+    let setup = RaftStateSetup::init(1, 1).await;
+    let state = &setup.state;
+    let entries = state.init().await.unwrap();
+    assert!(entries.is_empty());
+
+    state.apply(1, create_user_command()).await.unwrap();
+    let result = state.apply(1, create_stream_command()).await;
+    assert!(matches!(result, Err(IggyError::NotClusterLeader)));
+
+    become_leader(state).await;
+    assert!(matches!(
+        state.ensure_leader().await,
+        Err(IggyError::NotClusterLeader)
+    ));
+    let entries = state.take_committed_entries().await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].command, EntryCommand::Noop.to_bytes());
+    state.ensure_leader().await.unwrap();
+
+    let entry = state.apply(1, create_stream_command()).await.unwrap();
+    assert_eq!(entry.index, 2);
+    assert!(state.has_unapplied_entries().await);
+    let entries = state.take_committed_entries().await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].command, create_stream_command().to_bytes());
+
+    let metadata = state.get_metadata().await;
+    assert_eq!(metadata.id, 1);
+    assert_eq!(metadata.term, 1);
+    assert_eq!(metadata.leader_id, Some(1));
+    assert_eq!(metadata.nodes.len(), 1);
+    assert_eq!(metadata.nodes[0].role, ClusterNodeRole::Leader);
+
+    let entries = state.init().await.unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].term, 0);
+    assert_eq!(entries[1].term, 1);
+    assert_eq!(entries[1].command, EntryCommand::Noop.to_bytes());
+    assert_eq!(entries[2].term, 1);
+    assert_eq!(entries[2].leader_id, 1);
+}
+
+#[tokio::test]
+async fn follower_should_append_and_commit_leader_entries() {
+    let leader_setup = RaftStateSetup::init(1, 1).await;
+    let leader = &leader_setup.state;
+    leader.init().await.unwrap();
+    leader.apply(1, create_user_command()).await.unwrap();
+    become_leader(leader).await;
+    leader.take_committed_entries().await;
+    leader.apply(1, create_stream_command()).await.unwrap();
+    let leader_entries = leader.load_entries().await.unwrap();
+
+    let follower_setup = RaftStateSetup::init(2, 2).await;
+    let follower = &follower_setup.state;
+    follower.init().await.unwrap();
+    follower.apply(1, create_user_command()).await.unwrap();
+    assert!(!follower.has_unapplied_entries().await);
+
+    let response = follower
+        .handle_append_entries(AppendEntries {
+            term: 1,
+            leader_id: 1,
+            prev_log_index: 0,
+            prev_log_term: 0,
+            leader_commit: 3,
+            entries: leader_entries[1..]
+                .iter()
+                .map(|entry| entry.to_bytes())
+                .collect(),
+        })
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(response.term, 1);
+    assert_eq!(response.log_length, 3);
+    assert!(follower.has_unapplied_entries().await);
+
+    let entries = follower.take_committed_entries().await;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].index, 1);
+    assert_eq!(entries[0].command, EntryCommand::Noop.to_bytes());
+    assert_eq!(entries[1].index, 2);
+    assert_eq!(entries[1].command, leader_entries[2].command);
+    assert!(!follower.has_unapplied_entries().await);
+    assert!(matches!(
+        follower.ensure_leader().await,
+        Err(IggyError::NotClusterLeader)
+    ));
+    assert_eq!(follower.get_metadata().await.leader_id, Some(1));
+}
+
+#[tokio::test]
+async fn node_should_vote_only_once_per_term() {
+    let setup = RaftStateSetup::init(1, 3).await;
+    let state = &setup.state;
+    state.init().await.unwrap();
+    state.apply(1, create_user_command()).await.unwrap();
+
+    let response = state.handle_request_vote(request_vote(2)).await.unwrap();
+    assert!(response.vote_granted);
+    assert_eq!(response.term, 1);
+
+    let response = state.handle_request_vote(request_vote(3)).await.unwrap();
+    assert!(!response.vote_granted);
+
+    let response = state.handle_request_vote(request_vote(2)).await.unwrap();
+    assert!(response.vote_granted);
+}
+
+async fn become_leader(state: &RaftState) {
+    let election_timeout = IggyDuration::from_str(ELECTION_TIMEOUT).unwrap();
+    sleep(2 * election_timeout.get_duration()).await;
+    state.tick().await;
+}
+
+fn request_vote(candidate_id: u32) -> RequestVote {
+    RequestVote {
+        term: 1,
+        candidate_id,
+        last_log_index: 0,
+        last_log_term: 0,
+    }
+}
+
+fn create_user_command() -> EntryCommand {
+    EntryCommand::CreateUser(CreateUserWithId {
+        user_id: 1,
+        command: CreateUser {
+            username: "test".to_string(),
+            password: "secret".to_string(),
+            status: Default::default(),
+            permissions: None,
+        },
+        oidc_subject: None,
+    })
+}
+
+fn create_stream_command() -> EntryCommand {
+    EntryCommand::CreateStream(CreateStreamWithId {
+        stream_id: 1,
+        command: CreateStream {
+            stream_id: Some(1),
+            name: "test".to_string(),
+            limits: None,
+        },
+    })
+}
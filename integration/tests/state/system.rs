@@ -17,11 +17,13 @@
  */
 
 use crate::state::StateSetup;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
+use iggy::topics::cleanup_policy::{CleanupPolicy, CompactionKey};
 use iggy::topics::create_topic::CreateTopic;
 use iggy::users::create_user::CreateUser;
 use iggy::utils::expiry::IggyExpiry;
@@ -57,11 +59,13 @@ async fn should_be_initialized_based_on_state_entries() {
     let create_stream1 = CreateStream {
         stream_id: Some(stream1_id),
         name: "stream1".to_string(),
+        limits: None,
     };
 
     let create_stream1_clone = CreateStream {
         stream_id: Some(stream1_id),
         name: "stream1".to_string(),
+        limits: None,
     };
 
     let topic1_id = 1;
@@ -74,6 +78,7 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        cleanup_policy: CleanupPolicy::Compact(CompactionKey::MessagesKey),
     };
 
     let create_topic1_clone = CreateTopic {
@@ -85,12 +90,14 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic1".to_string(),
         replication_factor: None,
+        cleanup_policy: CleanupPolicy::Compact(CompactionKey::MessagesKey),
     };
 
     let stream2_id = 2;
     let create_stream2 = CreateStream {
         stream_id: Some(stream2_id),
         name: "stream2".to_string(),
+        limits: None,
     };
 
     let topic2_id = 2;
@@ -103,6 +110,7 @@ async fn should_be_initialized_based_on_state_entries() {
         max_topic_size: Default::default(),
         name: "topic2".to_string(),
         replication_factor: None,
+        cleanup_policy: Default::default(),
     };
 
     let create_partitions = CreatePartitions {
@@ -137,6 +145,7 @@ async fn should_be_initialized_based_on_state_entries() {
         topic_id: topic1_id.try_into().unwrap(),
         group_id: Some(group_id),
         name: "test".to_string(),
+        assignment_strategy: AssignmentStrategy::RoundRobin,
     };
 
     let create_consumer_group_clone = CreateConsumerGroup {
@@ -144,6 +153,7 @@ async fn should_be_initialized_based_on_state_entries() {
         topic_id: topic1_id.try_into().unwrap(),
         group_id: Some(group_id),
         name: "test".to_string(),
+        assignment_strategy: AssignmentStrategy::RoundRobin,
     };
 
     state
@@ -152,6 +162,7 @@ async fn should_be_initialized_based_on_state_entries() {
             EntryCommand::CreateUser(CreateUserWithId {
                 user_id,
                 command: create_user,
+                oidc_subject: None,
             }),
         )
         .await
@@ -172,6 +183,7 @@ async fn should_be_initialized_based_on_state_entries() {
             EntryCommand::CreateTopic(CreateTopicWithId {
                 topic_id: topic1_id,
                 command: create_topic1,
+                dead_letter: true,
             }),
         )
         .await
@@ -192,6 +204,7 @@ async fn should_be_initialized_based_on_state_entries() {
             EntryCommand::CreateTopic(CreateTopicWithId {
                 topic_id: topic2_id,
                 command: create_topic2,
+                dead_letter: false,
             }),
         )
         .await
@@ -262,6 +275,8 @@ async fn should_be_initialized_based_on_state_entries() {
         .unwrap();
     assert_eq!(topic.id, create_topic1_clone.topic_id.unwrap());
     assert_eq!(topic.name, create_topic1_clone.name);
+    assert_eq!(topic.cleanup_policy, create_topic1_clone.cleanup_policy);
+    assert!(topic.dead_letter);
     assert_eq!(topic.partitions.len(), 3);
 
     assert_eq!(topic.consumer_groups.len(), 1);
@@ -272,4 +287,8 @@ async fn should_be_initialized_based_on_state_entries() {
         create_consumer_group_clone.group_id.unwrap()
     );
     assert_eq!(consumer_group.name, create_consumer_group_clone.name);
+    assert_eq!(
+        consumer_group.assignment_strategy,
+        create_consumer_group_clone.assignment_strategy
+    );
 }
@@ -25,10 +25,12 @@ use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMutFn;
 use iggy::models::messages::{MessageState, PolledMessage};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use iggy::utils::{checksum, timestamp::IggyTimestamp};
+use server::configs::cluster::ClusterConfig;
 use server::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
 use server::configs::system::{SegmentConfig, SystemConfig};
 use server::streaming::local_sizeable::LocalSizeable;
@@ -41,6 +43,7 @@ use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
+use test_case::test_case;
 use tokio::fs;
 use tokio::time::sleep;
 
@@ -59,6 +62,7 @@ async fn should_persist_segment() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -96,6 +100,7 @@ async fn should_load_existing_segment_from_disk() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -122,6 +127,7 @@ async fn should_load_existing_segment_from_disk() {
             start_offset,
             setup.config.clone(),
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -158,6 +164,7 @@ async fn should_persist_and_load_segment_with_messages() {
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -208,6 +215,7 @@ async fn should_persist_and_load_segment_with_messages() {
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -223,6 +231,223 @@ async fn should_persist_and_load_segment_with_messages() {
     assert_eq!(messages.len(), messages_count as usize);
 }
 
+#[tokio::test]
+async fn should_compact_closed_segment_and_load_it_from_disk() {
+    let setup = TestSetup::init().await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let start_offset = 0;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages_count = 10;
+    let mut messages = Vec::new();
+    let mut batch_size = IggyByteSize::default();
+    for i in 0..messages_count {
+        let message = create_message(i, "test", IggyTimestamp::now());
+
+        let retained_message = Arc::new(RetainedMessage {
+            id: message.id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            checksum: message.checksum,
+            message_state: message.state,
+            headers: message.headers.map(|headers| headers.to_bytes()),
+            payload: message.payload.clone(),
+        });
+        batch_size += retained_message.get_size_bytes();
+        messages.push(retained_message);
+    }
+
+    segment
+        .append_batch(batch_size, messages_count as u32, &messages)
+        .await
+        .unwrap();
+    segment.persist_messages(None).await.unwrap();
+    segment.is_closed = true;
+    let size_bytes = segment.size_bytes;
+
+    let compacted_segment = segment
+        .write_compacted(|message| message.offset % 2 == 0)
+        .await
+        .unwrap()
+        .expect("Segment should be compacted");
+    let removed_messages = segment
+        .replace_with_compacted(compacted_segment)
+        .await
+        .unwrap();
+
+    // The last message is always retained, even if it doesn't match the predicate.
+    let expected_offsets = vec![0, 2, 4, 6, 8, 9];
+    assert_eq!(removed_messages, 4);
+    assert!(segment.size_bytes < size_bytes);
+    let offsets = segment
+        .get_messages_by_offset(0, messages_count as u32)
+        .await
+        .unwrap()
+        .iter()
+        .map(|message| message.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, expected_offsets);
+
+    let mut loaded_segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+    loaded_segment.load_from_disk().await.unwrap();
+    assert_eq!(loaded_segment.current_offset, messages_count - 1);
+    assert_eq!(loaded_segment.size_bytes, segment.size_bytes);
+    let offsets = loaded_segment
+        .get_messages_by_offset(3, 3)
+        .await
+        .unwrap()
+        .iter()
+        .map(|message| message.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![4]);
+}
+
+#[tokio::test]
+async fn should_recover_interrupted_compaction_of_segment() {
+    let setup = TestSetup::init().await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let start_offset = 0;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages_count = 10;
+    let mut messages = Vec::new();
+    let mut batch_size = IggyByteSize::default();
+    for i in 0..messages_count {
+        let message = create_message(i, "test", IggyTimestamp::now());
+        let retained_message = Arc::new(RetainedMessage {
+            id: message.id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            checksum: message.checksum,
+            message_state: message.state,
+            headers: message.headers.map(|headers| headers.to_bytes()),
+            payload: message.payload.clone(),
+        });
+        batch_size += retained_message.get_size_bytes();
+        messages.push(retained_message);
+    }
+
+    segment
+        .append_batch(batch_size, messages_count as u32, &messages)
+        .await
+        .unwrap();
+    segment.persist_messages(None).await.unwrap();
+    segment.is_closed = true;
+    let log_path = segment.log_path.clone();
+    let index_path = segment.index_path.clone();
+    let compacted_log_path = format!("{log_path}.compacted");
+    let compacted_index_path = format!("{index_path}.compacted");
+    let original_log = tokio::fs::read(&log_path).await.unwrap();
+
+    // None of the files was replaced yet, so the compaction is discarded.
+    segment
+        .write_compacted(|message| message.offset % 2 == 0)
+        .await
+        .unwrap()
+        .expect("Segment should be compacted");
+    Segment::recover_compaction(&log_path, &index_path)
+        .await
+        .unwrap();
+    assert!(!tokio::fs::try_exists(&compacted_log_path).await.unwrap());
+    assert!(!tokio::fs::try_exists(&compacted_index_path).await.unwrap());
+    assert_eq!(tokio::fs::read(&log_path).await.unwrap(), original_log);
+
+    // Only the log file was replaced, so the compaction is completed.
+    segment
+        .write_compacted(|message| message.offset % 2 == 0)
+        .await
+        .unwrap()
+        .expect("Segment should be compacted");
+    let compacted_index = tokio::fs::read(&compacted_index_path).await.unwrap();
+    tokio::fs::rename(&compacted_log_path, &log_path)
+        .await
+        .unwrap();
+    Segment::recover_compaction(&log_path, &index_path)
+        .await
+        .unwrap();
+    assert!(!tokio::fs::try_exists(&compacted_index_path).await.unwrap());
+    assert_eq!(tokio::fs::read(&index_path).await.unwrap(), compacted_index);
+
+    let mut loaded_segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+    loaded_segment.load_from_disk().await.unwrap();
+    let offsets = loaded_segment
+        .get_messages_by_offset(0, messages_count as u32)
+        .await
+        .unwrap()
+        .iter()
+        .map(|message| message.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![0, 2, 4, 6, 8, 9]);
+}
+
 #[tokio::test]
 async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation() {
     let setup = TestSetup::init_with_config(SystemConfig {
@@ -244,6 +469,7 @@ async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation(
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -298,6 +524,7 @@ async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation(
         start_offset,
         setup.config.clone(),
         IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -313,6 +540,101 @@ async fn should_persist_and_load_segment_with_messages_with_nowait_confirmation(
     assert_eq!(messages.len(), messages_count as usize);
 }
 
+#[test_case(CompressionAlgorithm::Gzip; "gzip")]
+#[test_case(CompressionAlgorithm::Zstd; "zstd")]
+#[test_case(CompressionAlgorithm::Lz4; "lz4")]
+#[tokio::test]
+async fn should_persist_and_load_compressed_segment_with_messages(
+    compression_algorithm: CompressionAlgorithm,
+) {
+    let setup = TestSetup::init().await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let start_offset = 0;
+    let mut segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        compression_algorithm,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+
+    setup
+        .create_partition_directory(stream_id, topic_id, partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    let messages_count = 100;
+    let payload = "compressible payload ".repeat(10);
+    let mut messages = Vec::new();
+    let mut batch_size = IggyByteSize::default();
+    for i in 0..messages_count {
+        let message = create_message(i, &payload, IggyTimestamp::now());
+
+        let retained_message = Arc::new(RetainedMessage {
+            id: message.id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            checksum: message.checksum,
+            message_state: message.state,
+            headers: message.headers.map(|headers| headers.to_bytes()),
+            payload: message.payload.clone(),
+        });
+        // Each message is prefixed with its length when stored in the batch
+        batch_size += retained_message.get_size_bytes() + IggyByteSize::from(4);
+        messages.push(retained_message);
+    }
+
+    segment
+        .append_batch(batch_size, messages_count as u32, &messages)
+        .await
+        .unwrap();
+    segment.persist_messages(None).await.unwrap();
+    assert!(segment.size_bytes < segment.uncompressed_size_bytes);
+    let log_file_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    assert_eq!(segment.size_bytes.as_bytes_u64(), log_file_size);
+
+    let mut loaded_segment = Segment::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        start_offset,
+        setup.config.clone(),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    );
+    loaded_segment.load_from_disk().await.unwrap();
+    assert_eq!(loaded_segment.size_bytes, segment.size_bytes);
+    assert_eq!(
+        loaded_segment.uncompressed_size_bytes,
+        segment.uncompressed_size_bytes
+    );
+    let loaded_messages = loaded_segment
+        .get_messages_by_offset(0, messages_count as u32)
+        .await
+        .unwrap();
+    assert_eq!(loaded_messages.len(), messages_count as usize);
+    for (loaded_message, message) in loaded_messages.iter().zip(messages.iter()) {
+        assert_eq!(loaded_message.offset, message.offset);
+        assert_eq!(loaded_message.payload, message.payload);
+        assert_eq!(loaded_message.checksum, message.checksum);
+    }
+}
+
 #[tokio::test]
 async fn given_all_expired_messages_segment_should_be_expired() {
     let setup = TestSetup::init().await;
@@ -329,6 +651,7 @@ async fn given_all_expired_messages_segment_should_be_expired() {
         start_offset,
         setup.config.clone(),
         message_expiry,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -396,6 +719,7 @@ async fn given_at_least_one_not_expired_message_segment_should_not_be_expired()
         start_offset,
         setup.config.clone(),
         message_expiry,
+        CompressionAlgorithm::None,
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
         Arc::new(AtomicU64::new(0)),
@@ -471,6 +795,7 @@ async fn should_delete_persisted_segments() -> Result<(), Box<dyn std::error::Er
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
 
     // Properties.
@@ -492,7 +817,12 @@ async fn should_delete_persisted_segments() -> Result<(), Box<dyn std::error::Er
     system.init().await.unwrap();
 
     system
-        .create_stream(&session, Some(stream_id.get_u32_value()?), stream_name)
+        .create_stream(
+            &session,
+            Some(stream_id.get_u32_value()?),
+            stream_name,
+            None,
+        )
         .await
         .unwrap();
 
@@ -507,6 +837,7 @@ async fn should_delete_persisted_segments() -> Result<(), Box<dyn std::error::Er
             CompressionAlgorithm::default(),
             MaxTopicSize::default(),
             None,
+            CleanupPolicy::default(),
         )
         .await?;
 
@@ -18,6 +18,7 @@
 
 use crate::streaming::common::test_setup::TestSetup;
 use iggy::snapshot::{SnapshotCompression, SystemSnapshotType};
+use server::configs::cluster::ClusterConfig;
 use server::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
 use server::streaming::session::Session;
 use server::streaming::systems::system::System;
@@ -32,6 +33,7 @@ async fn should_create_snapshot_file() {
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
 
     system.init().await.unwrap();
@@ -22,6 +22,8 @@ use ahash::AHashMap;
 use iggy::identifier::Identifier;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Partitioning;
+use iggy::models::stream_limits::StreamLimits;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -78,6 +80,7 @@ async fn should_load_existing_stream_from_disk() {
             id: stream_id,
             name: name.clone(),
             created_at: IggyTimestamp::now(),
+            limits: StreamLimits::default(),
             topics: AHashMap::new(),
         };
         loaded_stream.load(state).await.unwrap();
@@ -137,6 +140,7 @@ async fn should_purge_existing_stream_on_disk() {
                 Default::default(),
                 MaxTopicSize::ServerDefault,
                 1,
+                CleanupPolicy::default(),
             )
             .await
             .unwrap();
@@ -18,6 +18,7 @@
 
 use crate::streaming::common::test_setup::TestSetup;
 use iggy::identifier::Identifier;
+use server::configs::cluster::ClusterConfig;
 use server::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
 use server::streaming::session::Session;
 use server::streaming::systems::system::System;
@@ -31,6 +32,7 @@ async fn should_initialize_system_and_base_directories() {
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
 
     system.init().await.unwrap();
@@ -54,6 +56,7 @@ async fn should_create_and_persist_stream() {
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
     let stream_id = 1;
     let stream_name = "test";
@@ -61,7 +64,7 @@ async fn should_create_and_persist_stream() {
     system.init().await.unwrap();
 
     system
-        .create_stream(&session, Some(stream_id), stream_name)
+        .create_stream(&session, Some(stream_id), stream_name, None)
         .await
         .unwrap();
 
@@ -75,6 +78,7 @@ async fn should_create_and_persist_stream_with_automatically_generated_id() {
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
     let stream_id = 1;
     let stream_name = "test";
@@ -82,7 +86,7 @@ async fn should_create_and_persist_stream_with_automatically_generated_id() {
     system.init().await.unwrap();
 
     system
-        .create_stream(&session, None, stream_name)
+        .create_stream(&session, None, stream_name, None)
         .await
         .unwrap();
 
@@ -96,13 +100,14 @@ async fn should_delete_persisted_stream() {
         setup.config.clone(),
         DataMaintenanceConfig::default(),
         PersonalAccessTokenConfig::default(),
+        ClusterConfig::default(),
     );
     let stream_id = 1;
     let stream_name = "test";
     let session = Session::new(1, 1, SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234));
     system.init().await.unwrap();
     system
-        .create_stream(&session, Some(stream_id), stream_name)
+        .create_stream(&session, Some(stream_id), stream_name, None)
         .await
         .unwrap();
     assert_persisted_stream(&setup.config.get_streams_path(), stream_id).await;
@@ -22,6 +22,7 @@ use ahash::AHashMap;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Partitioning;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -58,6 +59,7 @@ async fn should_persist_topics_with_partitions_directories_and_info_file() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -96,6 +98,7 @@ async fn should_load_existing_topic_from_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -134,6 +137,8 @@ async fn should_load_existing_topic_from_disk() {
             message_expiry: IggyExpiry::NeverExpire,
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
+            cleanup_policy: CleanupPolicy::default(),
+            dead_letter: false,
             created_at: Default::default(),
         };
         loaded_topic.load(topic_state).await.unwrap();
@@ -173,6 +178,7 @@ async fn should_delete_existing_topic_from_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -213,6 +219,7 @@ async fn should_purge_existing_topic_on_disk() {
             CompressionAlgorithm::default(),
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -21,6 +21,7 @@ use bytes::Bytes;
 use iggy::locking::IggySharedMutFn;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -261,6 +262,7 @@ async fn init_topic(setup: &TestSetup, partitions_count: u32) -> Topic {
         Default::default(),
         MaxTopicSize::ServerDefault,
         1,
+        CleanupPolicy::default(),
     )
     .await
     .unwrap();
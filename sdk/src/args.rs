@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::compression::wire_compression::WireCompressionAlgorithm;
 use crate::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
@@ -215,6 +216,15 @@ pub struct Args {
     /// The optional number of retries for the HTTP transport
     pub http_retries: u32,
 
+    /// The optional CA file for the HTTP transport
+    pub http_tls_ca_file: Option<String>,
+
+    /// The optional client certificate file for the HTTP transport
+    pub http_tls_certificate_file: Option<String>,
+
+    /// The optional client certificate key file for the HTTP transport
+    pub http_tls_key_file: Option<String>,
+
     // The optional username for initial login
     pub username: String,
 
@@ -248,9 +258,21 @@ pub struct Args {
     /// The optional CA file for the TCP transport
     pub tcp_tls_ca_file: Option<String>,
 
+    /// The optional client certificate file for the TCP transport
+    pub tcp_tls_certificate_file: Option<String>,
+
+    /// The optional client certificate key file for the TCP transport
+    pub tcp_tls_key_file: Option<String>,
+
     /// Disable nodelay for the TCP transport
     pub tcp_nodelay: bool,
 
+    /// Flag to enable the multiplexing of the requests for the TCP transport
+    pub tcp_multiplexing: bool,
+
+    /// The optional algorithm offered to compress the payloads for the TCP transport
+    pub tcp_compression: Option<WireCompressionAlgorithm>,
+
     /// The optional client address for the QUIC transport
     pub quic_client_address: String,
 
@@ -299,8 +321,17 @@ pub struct Args {
     /// Flag to enable certificate validation for QUIC
     pub quic_validate_certificate: bool,
 
+    /// The optional client certificate file for QUIC
+    pub quic_client_certificate_file: Option<String>,
+
+    /// The optional client certificate key file for QUIC
+    pub quic_client_key_file: Option<String>,
+
     /// The optional heartbeat interval for the QUIC transport
     pub quic_heartbeat_interval: String,
+
+    /// The optional algorithm offered to compress the payloads for the QUIC transport
+    pub quic_compression: Option<WireCompressionAlgorithm>,
 }
 
 const QUIC_TRANSPORT: &str = "quic";
@@ -330,6 +361,9 @@ impl Default for Args {
             encryption_key: "".to_string(),
             http_api_url: "http://localhost:3000".to_string(),
             http_retries: 3,
+            http_tls_ca_file: None,
+            http_tls_certificate_file: None,
+            http_tls_key_file: None,
             username: DEFAULT_ROOT_USERNAME.to_string(),
             password: DEFAULT_ROOT_PASSWORD.to_string(),
             tcp_server_address: "127.0.0.1:8090".to_string(),
@@ -341,7 +375,11 @@ impl Default for Args {
             tcp_tls_enabled: false,
             tcp_tls_domain: "localhost".to_string(),
             tcp_tls_ca_file: None,
+            tcp_tls_certificate_file: None,
+            tcp_tls_key_file: None,
             tcp_nodelay: false,
+            tcp_multiplexing: false,
+            tcp_compression: None,
             quic_client_address: "127.0.0.1:0".to_string(),
             quic_server_address: "127.0.0.1:8080".to_string(),
             quic_server_name: "localhost".to_string(),
@@ -358,7 +396,10 @@ impl Default for Args {
             quic_keep_alive_interval: 5000,
             quic_max_idle_timeout: 10000,
             quic_validate_certificate: false,
+            quic_client_certificate_file: None,
+            quic_client_key_file: None,
             quic_heartbeat_interval: "5s".to_string(),
+            quic_compression: None,
         }
     }
 }
@@ -21,10 +21,12 @@ use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::ConsumerOffsetClient;
 use crate::consumer::Consumer;
 use crate::consumer_offsets::delete_consumer_offset::DeleteConsumerOffset;
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use crate::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use crate::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::models::consumer_lag::ConsumerLag;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 
 #[async_trait::async_trait]
@@ -36,6 +38,7 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation_id: Option<u32>,
     ) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&StoreConsumerOffset {
@@ -44,6 +47,7 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
             topic_id: topic_id.clone(),
             partition_id,
             offset,
+            generation_id,
         })
         .await?;
         Ok(())
@@ -89,4 +93,21 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
         .await?;
         Ok(())
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<ConsumerLag, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&GetConsumerLag {
+                consumer: consumer.clone(),
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+            })
+            .await?;
+        mapper::map_consumer_lag(response)
+    }
 }
@@ -18,10 +18,14 @@
 
 use crate::bytes_serializable::BytesSerializable;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::error::IggyError;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails, ConsumerGroupInfo};
+use crate::models::cluster::{ClusterMetadata, ClusterNode, ClusterNodeRole, ClusterNodeStatus};
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMember};
+use crate::models::consumer_lag::{ConsumerLag, PartitionLag};
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
+use crate::models::handshake::{HandshakeInfo, ProtocolFeature, ProtocolFeatures};
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::{MessageState, PolledMessage, PolledMessages};
 use crate::models::partition::Partition;
@@ -31,9 +35,12 @@ use crate::models::stats::{CacheMetrics, CacheMetricsKey, Stats};
 use crate::models::stream::{Stream, StreamDetails};
 use crate::models::topic::{Topic, TopicDetails};
 use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::expiry::IggyExpiry;
+use crate::utils::sizeable::Sizeable;
 use crate::utils::topic_size::MaxTopicSize;
 use bytes::Bytes;
 use std::collections::HashMap;
@@ -47,6 +54,49 @@ const EMPTY_USERS: Vec<UserInfo> = vec![];
 const EMPTY_PERSONAL_ACCESS_TOKENS: Vec<PersonalAccessTokenInfo> = vec![];
 const EMPTY_CONSUMER_GROUPS: Vec<ConsumerGroup> = vec![];
 
+pub fn map_handshake_info(payload: Bytes) -> Result<HandshakeInfo, IggyError> {
+    if payload.len() < 9 {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let semver = u32::from_le_bytes(
+        payload[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let features = u32::from_le_bytes(
+        payload[4..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let version_length = payload[8] as usize;
+    let version = from_utf8(
+        payload
+            .get(9..9 + version_length)
+            .ok_or(IggyError::InvalidCommand)?,
+    )
+    .map_err(|_| IggyError::InvalidUtf8)?
+    .to_string();
+    // The servers not verifying the client certificates yet don't send the authenticated user.
+    let user_id_position = 9 + version_length;
+    let user_id = match payload.get(user_id_position) {
+        Some(1) => Some(u32::from_le_bytes(
+            payload
+                .get(user_id_position + 1..user_id_position + 5)
+                .ok_or(IggyError::InvalidCommand)?
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        )),
+        _ => None,
+    };
+    Ok(HandshakeInfo {
+        iggy_server_version: version,
+        iggy_server_semver: if semver == 0 { None } else { Some(semver) },
+        features: ProtocolFeatures::from_flags(features),
+        user_id,
+    })
+}
+
 pub fn map_stats(payload: Bytes) -> Result<Stats, IggyError> {
     let process_id = u32::from_le_bytes(
         payload[..4]
@@ -404,8 +454,71 @@ pub fn map_consumer_offset(payload: Bytes) -> Result<ConsumerOffsetInfo, IggyErr
     })
 }
 
+pub fn map_consumer_lag(payload: Bytes) -> Result<ConsumerLag, IggyError> {
+    let stream_id = u32::from_le_bytes(
+        payload[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let topic_id = u32::from_le_bytes(
+        payload[4..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let mut partitions = Vec::new();
+    let mut position = 8;
+    while position < payload.len() {
+        let partition_id = u32::from_le_bytes(
+            payload[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let current_offset = u64::from_le_bytes(
+            payload[position + 4..position + 12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let has_stored_offset = payload[position + 12] == 1;
+        let stored_offset = u64::from_le_bytes(
+            payload[position + 13..position + 21]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let lag_messages = u64::from_le_bytes(
+            payload[position + 21..position + 29]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let lag_bytes = u64::from_le_bytes(
+            payload[position + 29..position + 37]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let lag_time = u64::from_le_bytes(
+            payload[position + 37..position + 45]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        partitions.push(PartitionLag {
+            partition_id,
+            current_offset,
+            stored_offset: has_stored_offset.then_some(stored_offset),
+            lag_messages,
+            lag_bytes: lag_bytes.into(),
+            lag_time: lag_time.into(),
+        });
+        position += 45;
+    }
+    Ok(ConsumerLag {
+        stream_id,
+        topic_id,
+        partitions,
+    })
+}
+
 pub fn map_user(payload: Bytes) -> Result<UserInfoDetails, IggyError> {
     let (user, position) = map_to_user_info(payload.clone(), 0)?;
+    let mut position = position;
     let has_permissions = payload[position];
     let permissions = if has_permissions == 1 {
         let permissions_length = u32::from_le_bytes(
@@ -414,7 +527,22 @@ pub fn map_user(payload: Bytes) -> Result<UserInfoDetails, IggyError> {
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         ) as usize;
         let permissions = payload.slice(position + 5..position + 5 + permissions_length);
+        position += 5 + permissions_length;
         Some(Permissions::from_bytes(permissions)?)
+    } else {
+        position += 1;
+        None
+    };
+
+    // The quotas are missing in the responses sent by the older servers.
+    let quotas = if payload.len() > position + 5 && payload[position] == 1 {
+        let quotas_length = u32::from_le_bytes(
+            payload[position + 1..position + 5]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let quotas = payload.slice(position + 5..position + 5 + quotas_length);
+        Some(UserQuotas::from_bytes(quotas)?)
     } else {
         None
     };
@@ -425,6 +553,7 @@ pub fn map_user(payload: Bytes) -> Result<UserInfoDetails, IggyError> {
         status: user.status,
         username: user.username,
         permissions,
+        quotas,
     };
     Ok(user)
 }
@@ -485,6 +614,24 @@ pub fn map_raw_pat(payload: Bytes) -> Result<RawPersonalAccessToken, IggyError>
     Ok(RawPersonalAccessToken { token })
 }
 
+pub fn map_producer_id(payload: Bytes) -> Result<u64, IggyError> {
+    let producer_id = u64::from_le_bytes(
+        payload[..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(producer_id)
+}
+
+pub fn map_transaction_id(payload: Bytes) -> Result<u64, IggyError> {
+    let transaction_id = u64::from_le_bytes(
+        payload[..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(transaction_id)
+}
+
 pub fn map_client(payload: Bytes) -> Result<ClientInfoDetails, IggyError> {
     let (client, mut position) = map_to_client_info(payload.clone(), 0)?;
     let mut consumer_groups = Vec::new();
@@ -545,12 +692,105 @@ pub fn map_clients(payload: Bytes) -> Result<Vec<ClientInfo>, IggyError> {
     Ok(clients)
 }
 
-pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError> {
+pub fn map_cluster_metadata(payload: Bytes) -> Result<ClusterMetadata, IggyError> {
+    let id = u32::from_le_bytes(
+        payload[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let term = u64::from_le_bytes(
+        payload[4..12]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let leader_id = u32::from_le_bytes(
+        payload[12..16]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let leader_id = match leader_id {
+        0 => None,
+        _ => Some(leader_id),
+    };
+    let name_length = payload[16] as usize;
+    let name = from_utf8(&payload[17..17 + name_length])
+        .map_err(|_| IggyError::InvalidUtf8)?
+        .to_string();
+    let mut nodes = Vec::new();
+    let mut position = 17 + name_length;
+    while position < payload.len() {
+        let node_id = u32::from_le_bytes(
+            payload[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let role = ClusterNodeRole::from_code(payload[position + 4])?;
+        let status = ClusterNodeStatus::from_code(payload[position + 5])?;
+        let address_length = payload[position + 6] as usize;
+        let address = from_utf8(&payload[position + 7..position + 7 + address_length])
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        nodes.push(ClusterNode {
+            id: node_id,
+            address,
+            role,
+            status,
+        });
+        position += 7 + address_length;
+    }
+    nodes.sort_by_key(|node| node.id);
+    Ok(ClusterMetadata {
+        name,
+        id,
+        term,
+        leader_id,
+        nodes,
+    })
+}
+
+pub fn map_assigned_polled_messages(
+    payload: Bytes,
+    features: ProtocolFeatures,
+) -> Result<Vec<PolledMessages>, IggyError> {
+    let mut partitions_messages = Vec::new();
+    let length = payload.len();
+    let mut position = 0;
+    while position < length {
+        if length < position + 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let messages_length = u32::from_le_bytes(
+            payload[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        position += 4;
+        if length < position + messages_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let polled_messages = map_polled_messages(
+            payload.slice(position..position + messages_length),
+            features,
+        )?;
+        partitions_messages.push(polled_messages);
+        position += messages_length;
+    }
+    Ok(partitions_messages)
+}
+
+/// The generation of the consumer group is present only if it has been negotiated with the server.
+pub fn map_polled_messages(
+    payload: Bytes,
+    features: ProtocolFeatures,
+) -> Result<PolledMessages, IggyError> {
     if payload.is_empty() {
         return Ok(PolledMessages {
             messages: EMPTY_MESSAGES,
             partition_id: 0,
             current_offset: 0,
+            generation_id: 0,
         });
     }
 
@@ -565,13 +805,24 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
+    let mut position = 12;
+    let generation_id = if features.contains(ProtocolFeature::ConsumerGroupGenerations) {
+        position += 4;
+        u32::from_le_bytes(
+            payload[12..16]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        )
+    } else {
+        0
+    };
     // Currently ignored
     let _messages_count = u32::from_le_bytes(
-        payload[12..16]
+        payload[position..position + 4]
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
-    let mut position = 16;
+    position += 4;
     let mut messages = Vec::new();
     while position < length {
         let offset = u64::from_le_bytes(
@@ -641,6 +892,7 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
     Ok(PolledMessages {
         partition_id,
         current_offset,
+        generation_id,
         messages,
     })
 }
@@ -759,16 +1011,22 @@ pub fn map_topic(payload: Bytes) -> Result<TopicDetails, IggyError> {
     }
 
     partitions.sort_by(|x, y| x.id.cmp(&y.id));
+    let uncompressed_size = partitions
+        .iter()
+        .map(|partition| partition.uncompressed_size)
+        .sum();
     let topic = TopicDetails {
         id: topic.id,
         created_at: topic.created_at,
         name: topic.name,
         size: topic.size,
+        uncompressed_size,
         messages_count: topic.messages_count,
         message_expiry: topic.message_expiry,
         compression_algorithm: topic.compression_algorithm,
         max_topic_size: topic.max_topic_size,
         replication_factor: topic.replication_factor,
+        cleanup_policy: topic.cleanup_policy,
         #[allow(clippy::cast_possible_truncation)]
         partitions_count: partitions.len() as u32,
         partitions,
@@ -823,7 +1081,20 @@ fn map_to_topic(payload: Bytes, position: usize) -> Result<(Topic, usize), IggyE
     let name = from_utf8(&payload[position + 51..position + 51 + name_length as usize])
         .map_err(|_| IggyError::InvalidUtf8)?
         .to_string();
-    let read_bytes = 4 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + name_length as usize;
+    let cleanup_policy =
+        CleanupPolicy::from_bytes(payload.slice(position + 51 + name_length as usize..))?;
+    let read_bytes = 4
+        + 8
+        + 4
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 1
+        + name_length as usize
+        + cleanup_policy.get_size_bytes().as_bytes_usize();
     Ok((
         Topic {
             id,
@@ -836,6 +1107,7 @@ fn map_to_topic(payload: Bytes, position: usize) -> Result<(Topic, usize), IggyE
             compression_algorithm,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
         },
         read_bytes,
     ))
@@ -869,12 +1141,18 @@ fn map_to_partition(payload: Bytes, position: usize) -> Result<(Partition, usize
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     )
     .into();
-    let messages_count = u64::from_le_bytes(
+    let uncompressed_size_bytes = u64::from_le_bytes(
         payload[position + 32..position + 40]
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    )
+    .into();
+    let messages_count = u64::from_le_bytes(
+        payload[position + 40..position + 48]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
-    let read_bytes = 4 + 8 + 4 + 8 + 8 + 8;
+    let read_bytes = 4 + 8 + 4 + 8 + 8 + 8 + 8;
     Ok((
         Partition {
             id,
@@ -882,6 +1160,7 @@ fn map_to_partition(payload: Bytes, position: usize) -> Result<(Partition, usize
             segments_count,
             current_offset,
             size: size_bytes,
+            uncompressed_size: uncompressed_size_bytes,
             messages_count,
         },
         read_bytes,
@@ -920,6 +1199,7 @@ pub fn map_consumer_group(payload: Bytes) -> Result<ConsumerGroupDetails, IggyEr
         name: consumer_group.name,
         partitions_count: consumer_group.partitions_count,
         members_count: consumer_group.members_count,
+        assignment_strategy: consumer_group.assignment_strategy,
         members,
     };
     Ok(consumer_group_details)
@@ -944,16 +1224,18 @@ fn map_to_consumer_group(
             .try_into()
             .map_err(|_| IggyError::InvalidNumberEncoding)?,
     );
-    let name_length = payload[position + 12];
-    let name = from_utf8(&payload[position + 13..position + 13 + name_length as usize])
+    let assignment_strategy = AssignmentStrategy::from_code(payload[position + 12])?;
+    let name_length = payload[position + 13];
+    let name = from_utf8(&payload[position + 14..position + 14 + name_length as usize])
         .map_err(|_| IggyError::InvalidUtf8)?
         .to_string();
-    let read_bytes = 13 + name_length as usize;
+    let read_bytes = 14 + name_length as usize;
     Ok((
         ConsumerGroup {
             id,
             partitions_count,
             members_count,
+            assignment_strategy,
             name,
         },
         read_bytes,
@@ -1019,6 +1301,7 @@ fn map_to_client_info(
     let transport = match transport {
         1 => "TCP",
         2 => "QUIC",
+        3 => "WebSocket",
         _ => "Unknown",
     }
     .to_string();
@@ -17,15 +17,20 @@
  */
 
 use crate::binary::binary_client::BinaryClient;
+use crate::binary::subscription::MessageSubscription;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::MessageClient;
-use crate::command::{POLL_MESSAGES_CODE, SEND_MESSAGES_CODE};
+use crate::command::{POLL_MESSAGES_CODE, SEND_IDEMPOTENT_MESSAGES_CODE, SEND_MESSAGES_CODE};
 use crate::consumer::Consumer;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::messages::flush_unsaved_buffer::FlushUnsavedBuffer;
-use crate::messages::poll_messages::PollingStrategy;
-use crate::messages::send_messages::{Message, Partitioning};
+use crate::messages::init_producer::InitProducer;
+use crate::messages::nack_message::NackMessage;
+use crate::messages::poll_assigned_messages::PollAssignedMessages;
+use crate::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use crate::messages::send_messages::{Message, Partitioning, ProducerSequence};
+use crate::messages::subscribe_messages::{SubscribeMessages, SubscriptionOptions};
 use crate::messages::{poll_messages, send_messages};
 use crate::models::messages::PolledMessages;
 
@@ -40,6 +45,7 @@ impl<B: BinaryClient> MessageClient for B {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+        options: &PollMessagesOptions,
     ) -> Result<PolledMessages, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self
@@ -53,10 +59,70 @@ impl<B: BinaryClient> MessageClient for B {
                     strategy,
                     count,
                     auto_commit,
+                    options.isolation_level,
+                    options.filter.as_ref(),
+                    &options.wait,
                 ),
             )
             .await?;
-        mapper::map_polled_messages(response)
+        mapper::map_polled_messages(response, self.get_features().await)
+    }
+
+    async fn poll_assigned_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        group_id: &Identifier,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        options: &PollMessagesOptions,
+    ) -> Result<Vec<PolledMessages>, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        if options.wait.is_enabled() {
+            return Err(IggyError::FeatureUnavailable);
+        }
+        let response = self
+            .send_with_response(&PollAssignedMessages {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                group_id: group_id.clone(),
+                strategy: *strategy,
+                count,
+                auto_commit,
+                isolation_level: options.isolation_level,
+                filter: options.filter.clone(),
+            })
+            .await?;
+        mapper::map_assigned_polled_messages(response, self.get_features().await)
+    }
+
+    async fn subscribe_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        options: &PollMessagesOptions,
+        subscription: &SubscriptionOptions,
+    ) -> Result<MessageSubscription, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.open_subscription(&SubscribeMessages {
+            consumer: consumer.clone(),
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id,
+            strategy: *strategy,
+            count,
+            ack_mode: subscription.ack_mode,
+            credit_kind: subscription.credit_kind,
+            credit: subscription.credit,
+            isolation_level: options.isolation_level,
+            filter: options.filter.clone(),
+        })
+        .await
     }
 
     async fn send_messages(
@@ -69,7 +135,36 @@ impl<B: BinaryClient> MessageClient for B {
         fail_if_not_authenticated(self).await?;
         self.send_raw_with_response(
             SEND_MESSAGES_CODE,
-            send_messages::as_bytes(stream_id, topic_id, partitioning, messages),
+            send_messages::as_bytes(stream_id, topic_id, partitioning, None, messages),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn init_producer(&self) -> Result<u64, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self.send_with_response(&InitProducer {}).await?;
+        mapper::map_producer_id(response)
+    }
+
+    async fn send_idempotent_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_raw_with_response(
+            SEND_IDEMPOTENT_MESSAGES_CODE,
+            send_messages::as_bytes(
+                stream_id,
+                topic_id,
+                &Partitioning::partition_id(partition_id),
+                Some(producer_sequence),
+                messages,
+            ),
         )
         .await?;
         Ok(())
@@ -92,4 +187,22 @@ impl<B: BinaryClient> MessageClient for B {
         .await?;
         Ok(())
     }
+
+    async fn nack_message(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&NackMessage {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id,
+            offset,
+        })
+        .await?;
+        Ok(())
+    }
 }
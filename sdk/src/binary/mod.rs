@@ -16,13 +16,18 @@
  * under the License.
  */
 
+use crate::binary::subscription::MessageSubscription;
 use crate::command::Command;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
+use crate::messages::subscribe_messages::SubscribeMessages;
+use crate::models::handshake::ProtocolFeatures;
+use crate::system::handshake::Handshake;
 use crate::utils::duration::IggyDuration;
 use async_trait::async_trait;
 use bytes::Bytes;
 use derive_more::Display;
+use tracing::{info, warn};
 
 #[allow(deprecated)]
 pub mod binary_client;
@@ -33,6 +38,7 @@ pub mod consumer_offsets;
 mod mapper;
 #[allow(deprecated)]
 pub mod messages;
+pub(crate) mod multiplexer;
 #[allow(deprecated)]
 pub mod partitions;
 #[allow(deprecated)]
@@ -41,11 +47,14 @@ pub mod personal_access_tokens;
 pub mod segments;
 #[allow(deprecated)]
 pub mod streams;
+pub mod subscription;
 #[allow(deprecated)]
 pub mod system;
 #[allow(deprecated)]
 pub mod topics;
 #[allow(deprecated)]
+pub mod transactions;
+#[allow(deprecated)]
 pub mod users;
 
 /// The state of the client.
@@ -77,10 +86,17 @@ pub trait BinaryTransport {
     async fn get_state(&self) -> ClientState;
     /// Sets the state of the client.
     async fn set_state(&self, state: ClientState);
+    /// Gets the protocol features negotiated during the handshake.
+    async fn get_features(&self) -> ProtocolFeatures;
     async fn publish_event(&self, event: DiagnosticEvent);
     /// Sends a command and returns the response.
     async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError>;
     async fn send_raw_with_response(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError>;
+    /// Starts the subscription on the dedicated stream, over which the server pushes the messages.
+    async fn open_subscription(
+        &self,
+        command: &SubscribeMessages,
+    ) -> Result<MessageSubscription, IggyError>;
     fn get_heartbeat_interval(&self) -> IggyDuration;
 }
 
@@ -94,3 +110,36 @@ async fn fail_if_not_authenticated<T: BinaryTransport>(transport: &T) -> Result<
         ClientState::Authenticated => Ok(()),
     }
 }
+
+/// Sends the handshake right after establishing the connection and returns the negotiated protocol features.
+/// The server not supporting the handshake yet responds with `InvalidCommand`, so no features are negotiated then.
+/// The session authenticated by the server with the client certificate doesn't require signing in.
+pub(crate) async fn handshake<T: BinaryTransport>(
+    transport: &T,
+    features: ProtocolFeatures,
+) -> Result<ProtocolFeatures, IggyError> {
+    let command = Handshake {
+        features,
+        ..Default::default()
+    };
+    let response = match transport.send_with_response(&command).await {
+        Ok(response) => response,
+        Err(IggyError::InvalidCommand) => {
+            warn!("Server does not support the handshake, no protocol features are negotiated.");
+            return Ok(ProtocolFeatures::default());
+        }
+        Err(error) => return Err(error),
+    };
+    let handshake_info = mapper::map_handshake_info(response)?;
+    let negotiated_features = features.intersection(&handshake_info.features);
+    info!(
+        "Server version: {} has enabled the protocol features: {}, negotiated: {negotiated_features}",
+        handshake_info.iggy_server_version, handshake_info.features
+    );
+    if let Some(user_id) = handshake_info.user_id {
+        info!("Server has authenticated the user with ID: {user_id} using the client certificate.");
+        transport.set_state(ClientState::Authenticated).await;
+        transport.publish_event(DiagnosticEvent::SignedIn).await;
+    }
+    Ok(negotiated_features)
+}
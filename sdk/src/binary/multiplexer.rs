@@ -0,0 +1,379 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::binary::subscription::{SubscriptionReader, SubscriptionWriter};
+use crate::command::REQUEST_ID_FLAG;
+use crate::compression::wire_compression::{compress_frame, decompress_frame, WireCompression};
+use crate::error::IggyError;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, trace};
+
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+const REQUEST_ID_BYTES_LENGTH: usize = 8;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 16;
+
+type ResponseSender = oneshot::Sender<Result<Bytes, IggyError>>;
+
+/// Multiplexes the requests over a single connection, each of them carrying its request ID.
+/// The requests are pipelined by the writer task, while the reader task completes them by the ID
+/// found in the response, in whatever order the server sends the responses back.
+///
+/// Request: `[length u32][code | REQUEST_ID_FLAG u32][request ID u64][payload]`, where the length includes the code and the ID.
+///
+/// Response: `[status u32][length u32][request ID u64][payload]`, where the length is the payload length only.
+///
+/// If the compression has been negotiated, the payloads are compressed and decompressed here, apart from the request ID.
+pub(crate) struct Multiplexer {
+    requests: UnboundedSender<Bytes>,
+    compression: Option<WireCompression>,
+    pending: Arc<PendingResponses>,
+    next_request_id: AtomicU64,
+    reader: JoinHandle<()>,
+    writer: JoinHandle<()>,
+}
+
+impl Debug for Multiplexer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Multiplexer")
+            .field("next_request_id", &self.next_request_id)
+            .finish()
+    }
+}
+
+/// The requests awaiting their responses, `None` once the connection has been closed.
+#[derive(Debug)]
+struct PendingResponses {
+    senders: Mutex<Option<HashMap<u64, ResponseSender>>>,
+}
+
+impl PendingResponses {
+    fn register(&self, request_id: u64) -> Option<oneshot::Receiver<Result<Bytes, IggyError>>> {
+        let mut senders = self.senders.lock().unwrap();
+        let senders = senders.as_mut()?;
+        let (sender, receiver) = oneshot::channel();
+        senders.insert(request_id, sender);
+        Some(receiver)
+    }
+
+    fn remove(&self, request_id: u64) -> Option<ResponseSender> {
+        self.senders
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|senders| senders.remove(&request_id))
+    }
+
+    /// Fails all the pending requests, as their responses will never arrive.
+    fn close(&self) {
+        if let Some(senders) = self.senders.lock().unwrap().take() {
+            for (_, sender) in senders {
+                let _ = sender.send(Err(IggyError::Disconnected));
+            }
+        }
+    }
+}
+
+impl Multiplexer {
+    pub fn new(
+        reader: SubscriptionReader,
+        writer: SubscriptionWriter,
+        compression: Option<WireCompression>,
+    ) -> Self {
+        let pending = Arc::new(PendingResponses {
+            senders: Mutex::new(Some(HashMap::new())),
+        });
+        let (requests, receiver) = mpsc::unbounded_channel();
+        Self {
+            requests,
+            compression,
+            next_request_id: AtomicU64::new(1),
+            reader: tokio::spawn(read_responses(reader, pending.clone(), compression)),
+            writer: tokio::spawn(write_requests(writer, receiver, pending.clone())),
+            pending,
+        }
+    }
+
+    /// Sends the request and waits for its response, other requests may be sent meanwhile.
+    pub async fn send(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let Some(response) = self.pending.register(request_id) else {
+            trace!("Cannot send data. Multiplexed connection is closed.");
+            return Err(IggyError::Disconnected);
+        };
+
+        let (code, payload) = compress_frame(self.compression.as_ref(), code, payload);
+        let length = REQUEST_INITIAL_BYTES_LENGTH + REQUEST_ID_BYTES_LENGTH + payload.len();
+        let mut frame = BytesMut::with_capacity(REQUEST_INITIAL_BYTES_LENGTH + length);
+        frame.put_u32_le(length as u32);
+        frame.put_u32_le(code | REQUEST_ID_FLAG);
+        frame.put_u64_le(request_id);
+        frame.put_slice(&payload);
+        trace!("Sending a multiplexed request with code: {code}, request ID: {request_id}");
+        if self.requests.send(frame.freeze()).is_err() {
+            self.pending.remove(request_id);
+            return Err(IggyError::Disconnected);
+        }
+
+        response.await.unwrap_or(Err(IggyError::Disconnected))
+    }
+
+    /// Closes the connection, the pending requests fail with `Disconnected`.
+    pub fn close(&self) {
+        self.reader.abort();
+        self.writer.abort();
+        self.pending.close();
+    }
+}
+
+impl Drop for Multiplexer {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+async fn write_requests(
+    mut writer: SubscriptionWriter,
+    mut requests: UnboundedReceiver<Bytes>,
+    pending: Arc<PendingResponses>,
+) {
+    'requests: while let Some(frame) = requests.recv().await {
+        if let Err(error) = writer.write_all(&frame).await {
+            error!("Failed to send a multiplexed request: {error}");
+            break;
+        }
+
+        // The requests queued meanwhile are written before flushing, so they leave in as few packets as possible.
+        while let Ok(frame) = requests.try_recv() {
+            if let Err(error) = writer.write_all(&frame).await {
+                error!("Failed to send a multiplexed request: {error}");
+                break 'requests;
+            }
+        }
+
+        if let Err(error) = writer.flush().await {
+            error!("Failed to flush the multiplexed requests: {error}");
+            break;
+        }
+    }
+    pending.close();
+}
+
+async fn read_responses(
+    mut reader: SubscriptionReader,
+    pending: Arc<PendingResponses>,
+    compression: Option<WireCompression>,
+) {
+    loop {
+        match read_response(&mut reader, compression.as_ref()).await {
+            Ok((request_id, response)) => {
+                let Some(sender) = pending.remove(request_id) else {
+                    debug!("Received a response for the unknown request ID: {request_id}.");
+                    continue;
+                };
+                let _ = sender.send(response);
+            }
+            Err(error) => {
+                debug!("Multiplexed connection has been closed: {error}");
+                break;
+            }
+        }
+    }
+    pending.close();
+}
+
+async fn read_response(
+    reader: &mut SubscriptionReader,
+    compression: Option<&WireCompression>,
+) -> Result<(u64, Result<Bytes, IggyError>), IggyError> {
+    let mut header = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+    reader
+        .read_exact(&mut header)
+        .await
+        .map_err(|_| IggyError::Disconnected)?;
+    let status = u32::from_le_bytes(
+        header[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let length = u32::from_le_bytes(
+        header[4..8]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let request_id = u64::from_le_bytes(
+        header[8..]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let mut payload = vec![0u8; length as usize];
+    reader
+        .read_exact(&mut payload)
+        .await
+        .map_err(|_| IggyError::Disconnected)?;
+    let (status, payload) = match decompress_frame(compression, status, Bytes::from(payload)) {
+        Ok(response) => response,
+        Err(error) => return Ok((request_id, Err(error))),
+    };
+    trace!("Received a multiplexed response with status: {status}, request ID: {request_id}");
+    if status != 0 {
+        debug!(
+            "Received an invalid response with status: {} ({}), request ID: {request_id}.",
+            status,
+            IggyError::from_code_as_string(status),
+        );
+        if payload.is_empty() {
+            return Ok((request_id, Err(IggyError::from_code(status))));
+        }
+
+        return Ok((
+            request_id,
+            Err(IggyError::from_code_and_details(status, &payload)),
+        ));
+    }
+
+    if payload.len() <= 1 {
+        return Ok((request_id, Ok(Bytes::new())));
+    }
+
+    Ok((request_id, Ok(payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::wire_compression::{WireCompressionAlgorithm, COMPRESSION_FLAG};
+    use crate::utils::byte_size::IggyByteSize;
+    use tokio::io::{duplex, split, AsyncRead, AsyncWrite};
+
+    async fn read_request<T: AsyncRead + AsyncWrite + Unpin>(
+        server: &mut T,
+    ) -> (u32, u64, Vec<u8>) {
+        let mut header = [0u8; 16];
+        server.read_exact(&mut header).await.unwrap();
+        let length = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        let code = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let request_id = u64::from_le_bytes(header[8..].try_into().unwrap());
+        let mut payload = vec![0u8; length - 12];
+        server.read_exact(&mut payload).await.unwrap();
+        (code, request_id, payload)
+    }
+
+    async fn send_response<T: AsyncRead + AsyncWrite + Unpin>(
+        server: &mut T,
+        request_id: u64,
+        payload: &[u8],
+    ) {
+        let mut frame = BytesMut::new();
+        frame.put_u32_le(0);
+        frame.put_u32_le(payload.len() as u32);
+        frame.put_u64_le(request_id);
+        frame.put_slice(payload);
+        server.write_all(&frame).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn responses_should_complete_requests_by_id_in_any_order() {
+        let (client, mut server) = duplex(1024);
+        let (reader, writer) = split(client);
+        let multiplexer = Arc::new(Multiplexer::new(Box::new(reader), Box::new(writer), None));
+
+        let first = tokio::spawn({
+            let multiplexer = multiplexer.clone();
+            async move { multiplexer.send(1, Bytes::from_static(b"first")).await }
+        });
+        let (code, first_id, payload) = read_request(&mut server).await;
+        assert_eq!(code, 1 | REQUEST_ID_FLAG);
+        assert_eq!(payload, b"first");
+
+        let second = tokio::spawn({
+            let multiplexer = multiplexer.clone();
+            async move { multiplexer.send(2, Bytes::from_static(b"second")).await }
+        });
+        let (code, second_id, payload) = read_request(&mut server).await;
+        assert_eq!(code, 2 | REQUEST_ID_FLAG);
+        assert_eq!(payload, b"second");
+        assert_ne!(first_id, second_id);
+
+        send_response(&mut server, second_id, b"second response").await;
+        assert_eq!(second.await.unwrap().unwrap(), &b"second response"[..]);
+        send_response(&mut server, first_id, b"first response").await;
+        assert_eq!(first.await.unwrap().unwrap(), &b"first response"[..]);
+    }
+
+    #[tokio::test]
+    async fn pending_requests_should_fail_when_connection_is_closed() {
+        let (client, mut server) = duplex(1024);
+        let (reader, writer) = split(client);
+        let multiplexer = Arc::new(Multiplexer::new(Box::new(reader), Box::new(writer), None));
+
+        let request = tokio::spawn({
+            let multiplexer = multiplexer.clone();
+            async move { multiplexer.send(1, Bytes::new()).await }
+        });
+        read_request(&mut server).await;
+        drop(server);
+
+        assert!(matches!(
+            request.await.unwrap(),
+            Err(IggyError::Disconnected)
+        ));
+        assert!(matches!(
+            multiplexer.send(1, Bytes::new()).await,
+            Err(IggyError::Disconnected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn payloads_should_be_compressed_and_decompressed_when_compression_is_negotiated() {
+        let (client, mut server) = duplex(64 * 1024);
+        let (reader, writer) = split(client);
+        let compression =
+            WireCompression::new(WireCompressionAlgorithm::Zstd, IggyByteSize::from(100));
+        let multiplexer = Arc::new(Multiplexer::new(
+            Box::new(reader),
+            Box::new(writer),
+            Some(compression),
+        ));
+        let payload = Bytes::from("payload".repeat(100));
+
+        let request = tokio::spawn({
+            let multiplexer = multiplexer.clone();
+            let payload = payload.clone();
+            async move { multiplexer.send(1, payload).await }
+        });
+        let (code, request_id, compressed) = read_request(&mut server).await;
+        assert_eq!(code, 1 | REQUEST_ID_FLAG | COMPRESSION_FLAG);
+        assert_eq!(compression.decompress(&compressed).unwrap(), payload);
+
+        let mut frame = BytesMut::new();
+        let response = compression.compress(&payload).unwrap();
+        frame.put_u32_le(COMPRESSION_FLAG);
+        frame.put_u32_le(response.len() as u32);
+        frame.put_u64_le(request_id);
+        frame.put_slice(&response);
+        server.write_all(&frame).await.unwrap();
+        assert_eq!(request.await.unwrap().unwrap(), payload);
+    }
+}
@@ -22,6 +22,7 @@ use crate::client::SegmentClient;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::segments::delete_segments::DeleteSegments;
+use crate::segments::restore_segments::{ArchiveSource, RestoreRange, RestoreSegments};
 
 #[async_trait::async_trait]
 impl<B: BinaryClient> SegmentClient for B {
@@ -42,4 +43,24 @@ impl<B: BinaryClient> SegmentClient for B {
         .await?;
         Ok(())
     }
+
+    async fn restore_segments(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&RestoreSegments {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id,
+            source: source.clone(),
+            range: *range,
+        })
+        .await?;
+        Ok(())
+    }
 }
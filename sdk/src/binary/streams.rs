@@ -22,6 +22,7 @@ use crate::client::StreamClient;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::models::stream::{Stream, StreamDetails};
+use crate::models::stream_limits::StreamLimits;
 use crate::streams::create_stream::CreateStream;
 use crate::streams::delete_stream::DeleteStream;
 use crate::streams::get_stream::GetStream;
@@ -55,22 +56,30 @@ impl<B: BinaryClient> StreamClient for B {
         &self,
         name: &str,
         stream_id: Option<u32>,
+        limits: Option<StreamLimits>,
     ) -> Result<StreamDetails, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self
             .send_with_response(&CreateStream {
                 name: name.to_string(),
                 stream_id,
+                limits,
             })
             .await?;
         mapper::map_stream(response)
     }
 
-    async fn update_stream(&self, stream_id: &Identifier, name: &str) -> Result<(), IggyError> {
+    async fn update_stream(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&UpdateStream {
             stream_id: stream_id.clone(),
             name: name.to_string(),
+            limits,
         })
         .await?;
         Ok(())
@@ -0,0 +1,206 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::mapper;
+use crate::command::Command;
+use crate::error::IggyError;
+use crate::messages::ack_messages::AckMessages;
+use crate::messages::grant_credit::GrantCredit;
+use crate::messages::subscribe_messages::{AckMode, CreditKind, SubscribeMessages};
+use crate::models::handshake::ProtocolFeatures;
+use crate::models::messages::PolledMessages;
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::crypto::EncryptorKind;
+use bytes::{Bytes, BytesMut};
+use std::fmt::{Debug, Formatter};
+use std::io::ErrorKind;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{error, trace};
+
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 8;
+
+pub(crate) type SubscriptionReader = Box<dyn AsyncRead + Unpin + Send>;
+pub(crate) type SubscriptionWriter = Box<dyn AsyncWrite + Unpin + Send>;
+
+/// The subscription created with `SubscribeMessages`, over which the server pushes the batches of messages
+/// as soon as they are appended, for as long as there is the credit granted by the client.
+/// It owns the dedicated TCP connection or the QUIC stream, which is closed once the subscription is dropped.
+pub struct MessageSubscription {
+    reader: SubscriptionReader,
+    writer: SubscriptionWriter,
+    ack_mode: AckMode,
+    credit_kind: CreditKind,
+    features: ProtocolFeatures,
+    encryptor: Option<Arc<EncryptorKind>>,
+}
+
+impl Debug for MessageSubscription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageSubscription")
+            .field("ack_mode", &self.ack_mode)
+            .field("credit_kind", &self.credit_kind)
+            .finish()
+    }
+}
+
+impl MessageSubscription {
+    /// Sends the command over the provided stream and waits for the server to confirm the subscription.
+    /// The features are the ones negotiated by the connection the stream belongs to.
+    pub(crate) async fn open(
+        reader: SubscriptionReader,
+        writer: SubscriptionWriter,
+        command: &SubscribeMessages,
+        features: ProtocolFeatures,
+    ) -> Result<Self, IggyError> {
+        let mut subscription = Self {
+            reader,
+            writer,
+            ack_mode: command.ack_mode,
+            credit_kind: command.credit_kind,
+            features,
+            encryptor: None,
+        };
+        subscription.send(command).await?;
+        match subscription.read_frame().await? {
+            Some(_) => Ok(subscription),
+            None => Err(IggyError::ConnectionClosed),
+        }
+    }
+
+    pub(crate) fn set_encryptor(&mut self, encryptor: Option<Arc<EncryptorKind>>) {
+        self.encryptor = encryptor;
+    }
+
+    /// Returns the ack mode of the subscription.
+    pub fn ack_mode(&self) -> AckMode {
+        self.ack_mode
+    }
+
+    /// Returns the unit in which the credit of the subscription is counted.
+    pub fn credit_kind(&self) -> CreditKind {
+        self.credit_kind
+    }
+
+    /// Waits for the next batch of messages pushed by the server.
+    /// Returns `None` once the subscription has been closed by the server.
+    pub async fn next(&mut self) -> Result<Option<PolledMessages>, IggyError> {
+        let Some(payload) = self.read_frame().await? else {
+            return Ok(None);
+        };
+
+        let mut polled_messages = mapper::map_polled_messages(payload, self.features)?;
+        if let Some(ref encryptor) = self.encryptor {
+            for message in &mut polled_messages.messages {
+                let payload = encryptor.decrypt(&message.payload)?;
+                message.payload = Bytes::from(payload);
+                message.length = IggyByteSize::from(message.payload.len() as u64);
+            }
+        }
+        Ok(Some(polled_messages))
+    }
+
+    /// Extends the credit of the subscription, counted in the unit chosen when subscribing.
+    pub async fn grant_credit(&mut self, credit: u64) -> Result<(), IggyError> {
+        self.send(&GrantCredit { credit }).await
+    }
+
+    /// Acknowledges the messages up to the specified offset, so it's stored on the server.
+    /// Available only in the `Acknowledged` ack mode.
+    pub async fn ack(&mut self, partition_id: u32, offset: u64) -> Result<(), IggyError> {
+        if self.ack_mode != AckMode::Acknowledged {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        self.send(&AckMessages {
+            partition_id,
+            offset,
+        })
+        .await
+    }
+
+    /// Closes the subscription, the server stops pushing the messages.
+    pub async fn close(mut self) -> Result<(), IggyError> {
+        self.writer.shutdown().await.map_err(|error| {
+            error!("Failed to close the subscription: {error}");
+            IggyError::Disconnected
+        })
+    }
+
+    async fn send<T: Command>(&mut self, command: &T) -> Result<(), IggyError> {
+        command.validate()?;
+        let code = command.code();
+        let payload = command.to_bytes();
+        let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
+        let mut frame = BytesMut::with_capacity(4 + payload_length);
+        frame.extend_from_slice(&(payload_length as u32).to_le_bytes());
+        frame.extend_from_slice(&code.to_le_bytes());
+        frame.extend_from_slice(&payload);
+        trace!("Sending a subscription request with code: {code}");
+        self.writer.write_all(&frame).await.map_err(|error| {
+            error!("Failed to send the subscription request with code: {code}: {error}");
+            IggyError::Disconnected
+        })?;
+        self.writer.flush().await.map_err(|error| {
+            error!("Failed to flush the subscription request with code: {code}: {error}");
+            IggyError::Disconnected
+        })
+    }
+
+    async fn read_frame(&mut self) -> Result<Option<Bytes>, IggyError> {
+        let mut header = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+        if let Err(error) = self.reader.read_exact(&mut header).await {
+            if error.kind() == ErrorKind::UnexpectedEof {
+                return Ok(None);
+            }
+
+            error!("Failed to read the subscription response: {error}");
+            return Err(IggyError::Disconnected);
+        }
+
+        let status = u32::from_le_bytes(
+            header[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let length = u32::from_le_bytes(
+            header[4..]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut payload = vec![0u8; length as usize];
+        self.reader
+            .read_exact(&mut payload)
+            .await
+            .map_err(|error| {
+                error!("Failed to read the subscription response payload: {error}");
+                IggyError::Disconnected
+            })?;
+        if status != 0 {
+            error!(
+                "Received an invalid subscription response with status: {} ({}).",
+                status,
+                IggyError::from_code_as_string(status),
+            );
+            return Err(IggyError::from_code_and_details(status, &payload));
+        }
+
+        Ok(Some(Bytes::from(payload)))
+    }
+}
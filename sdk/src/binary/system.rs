@@ -19,8 +19,10 @@
 use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::SystemClient;
+use crate::cluster::get_cluster_metadata::GetClusterMetadata;
 use crate::error::IggyError;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
+use crate::models::cluster::ClusterMetadata;
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
@@ -39,6 +41,12 @@ impl<B: BinaryClient> SystemClient for B {
         mapper::map_stats(response)
     }
 
+    async fn get_cluster_metadata(&self) -> Result<ClusterMetadata, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self.send_with_response(&GetClusterMetadata {}).await?;
+        mapper::map_cluster_metadata(response)
+    }
+
     async fn get_me(&self) -> Result<ClientInfoDetails, IggyError> {
         fail_if_not_authenticated(self).await?;
         let response = self.send_with_response(&GetMe {}).await?;
@@ -25,6 +25,7 @@ use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
 use crate::users::create_user::CreateUser;
@@ -32,6 +33,7 @@ use crate::users::delete_user::DeleteUser;
 use crate::users::get_user::GetUser;
 use crate::users::get_users::GetUsers;
 use crate::users::login_user::LoginUser;
+use crate::users::login_with_oidc_token::LoginWithOidcToken;
 use crate::users::logout_user::LogoutUser;
 use crate::users::update_permissions::UpdatePermissions;
 use crate::users::update_user::UpdateUser;
@@ -91,12 +93,14 @@ impl<B: BinaryClient> UserClient for B {
         user_id: &Identifier,
         username: Option<&str>,
         status: Option<UserStatus>,
+        quotas: Option<UserQuotas>,
     ) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&UpdateUser {
             user_id: user_id.clone(),
             username: username.map(|s| s.to_string()),
             status,
+            quotas,
         })
         .await?;
         Ok(())
@@ -146,6 +150,17 @@ impl<B: BinaryClient> UserClient for B {
         mapper::map_identity_info(response)
     }
 
+    async fn login_with_oidc_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        let response = self
+            .send_with_response(&LoginWithOidcToken {
+                token: token.to_string(),
+            })
+            .await?;
+        self.set_state(ClientState::Authenticated).await;
+        self.publish_event(DiagnosticEvent::SignedIn).await;
+        mapper::map_identity_info(response)
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         fail_if_not_authenticated(self).await?;
         self.send_with_response(&LogoutUser {}).await?;
@@ -18,6 +18,7 @@
 
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use crate::identifier::Identifier;
 use anyhow::Context;
@@ -34,6 +35,7 @@ impl CreateConsumerGroupCmd {
         topic_id: Identifier,
         name: String,
         group_id: Option<u32>,
+        assignment_strategy: AssignmentStrategy,
     ) -> Self {
         Self {
             create_consumer_group: CreateConsumerGroup {
@@ -41,6 +43,7 @@ impl CreateConsumerGroupCmd {
                 topic_id,
                 name,
                 group_id,
+                assignment_strategy,
             },
         }
     }
@@ -57,9 +60,10 @@ impl CreateConsumerGroupCmd {
 impl CliCommand for CreateConsumerGroupCmd {
     fn explain(&self) -> String {
         format!(
-            "create consumer group: {}, name: {} for topic with ID: {} and stream with ID: {}",
+            "create consumer group: {}, name: {}, assignment strategy: {} for topic with ID: {} and stream with ID: {}",
             self.get_group_id_info(),
             self.create_consumer_group.name,
+            self.create_consumer_group.assignment_strategy,
             self.create_consumer_group.topic_id,
             self.create_consumer_group.stream_id,
         )
@@ -67,7 +71,7 @@ impl CliCommand for CreateConsumerGroupCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .create_consumer_group(&self.create_consumer_group.stream_id, &self.create_consumer_group.topic_id, &self.create_consumer_group.name, self.create_consumer_group.group_id)
+            .create_consumer_group(&self.create_consumer_group.stream_id, &self.create_consumer_group.topic_id, &self.create_consumer_group.name, self.create_consumer_group.group_id, self.create_consumer_group.assignment_strategy)
             .await
             .with_context(|| {
                 format!(
@@ -85,6 +85,10 @@ impl CliCommand for GetConsumerGroupCmd {
             "Members count",
             format!("{}", consumer_group.members_count).as_str(),
         ]);
+        table.add_row(vec![
+            "Assignment strategy",
+            format!("{}", consumer_group.assignment_strategy).as_str(),
+        ]);
 
         if consumer_group.members_count > 0 {
             let mut members_table = Table::new();
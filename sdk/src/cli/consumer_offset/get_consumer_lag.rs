@@ -0,0 +1,123 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::consumer::{Consumer, ConsumerKind};
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
+use crate::identifier::Identifier;
+use anyhow::Context;
+use async_trait::async_trait;
+use comfy_table::Table;
+use tracing::{event, Level};
+
+pub struct GetConsumerLagCmd {
+    get_consumer_lag: GetConsumerLag,
+}
+
+impl GetConsumerLagCmd {
+    pub fn new(
+        consumer_id: Identifier,
+        stream_id: Identifier,
+        topic_id: Identifier,
+        consumer_group: bool,
+    ) -> Self {
+        Self {
+            get_consumer_lag: GetConsumerLag {
+                consumer: Consumer {
+                    kind: match consumer_group {
+                        true => ConsumerKind::ConsumerGroup,
+                        false => ConsumerKind::Consumer,
+                    },
+                    id: consumer_id,
+                },
+                stream_id,
+                topic_id,
+            },
+        }
+    }
+
+    pub fn get_consumer_info(&self) -> String {
+        match self.get_consumer_lag.consumer.kind {
+            ConsumerKind::Consumer => {
+                format!("consumer with ID: {}", self.get_consumer_lag.consumer.id)
+            }
+            ConsumerKind::ConsumerGroup => format!(
+                "consumer group with ID: {}",
+                self.get_consumer_lag.consumer.id
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for GetConsumerLagCmd {
+    fn explain(&self) -> String {
+        format!(
+            "get consumer lag for {} for stream with ID: {} and topic with ID: {}",
+            self.get_consumer_info(),
+            self.get_consumer_lag.stream_id,
+            self.get_consumer_lag.topic_id,
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        let consumer_lag = client
+            .get_consumer_lag(
+                &self.get_consumer_lag.consumer,
+                &self.get_consumer_lag.stream_id,
+                &self.get_consumer_lag.topic_id,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem getting consumer lag for {} for stream with ID: {} and topic with ID: {}",
+                    self.get_consumer_info(),
+                    self.get_consumer_lag.stream_id,
+                    self.get_consumer_lag.topic_id
+                )
+            })?;
+
+        let mut table = Table::new();
+        table.set_header(vec![
+            "Partition ID",
+            "Current offset",
+            "Stored offset",
+            "Lag (messages)",
+            "Lag (size)",
+            "Lag (time)",
+        ]);
+        for partition in &consumer_lag.partitions {
+            table.add_row(vec![
+                format!("{}", partition.partition_id),
+                format!("{}", partition.current_offset),
+                partition
+                    .stored_offset
+                    .map(|offset| offset.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{}", partition.lag_messages),
+                partition.lag_bytes.as_human_string(),
+                partition.lag_time.as_human_time_string(),
+            ]);
+        }
+
+        event!(target: PRINT_TARGET, Level::INFO, "{table}");
+
+        Ok(())
+    }
+}
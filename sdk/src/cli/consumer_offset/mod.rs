@@ -16,5 +16,6 @@
  * under the License.
  */
 
+pub mod get_consumer_lag;
 pub mod get_consumer_offset;
 pub mod set_consumer_offset;
@@ -47,6 +47,7 @@ impl SetConsumerOffsetCmd {
                 topic_id,
                 partition_id: Some(partition_id),
                 offset,
+                generation_id: None,
             },
         }
     }
@@ -67,7 +68,7 @@ impl CliCommand for SetConsumerOffsetCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .store_consumer_offset(&self.set_consumer_offset.consumer, &self.set_consumer_offset.stream_id, &self.set_consumer_offset.topic_id, self.set_consumer_offset.partition_id, self.set_consumer_offset.offset)
+            .store_consumer_offset(&self.set_consumer_offset.consumer, &self.set_consumer_offset.stream_id, &self.set_consumer_offset.topic_id, self.set_consumer_offset.partition_id, self.set_consumer_offset.offset, self.set_consumer_offset.generation_id)
             .await
             .with_context(|| {
                 format!(
@@ -21,7 +21,10 @@ use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer::Consumer;
 use crate::identifier::Identifier;
-use crate::messages::poll_messages::{PollMessages, PollingStrategy};
+use crate::messages::header_filter::HeaderFilter;
+use crate::messages::poll_messages::{
+    IsolationLevel, PollMessages, PollMessagesOptions, PollingStrategy, PollingWait,
+};
 use crate::messages::send_messages::Message;
 use crate::models::header::{HeaderKey, HeaderKind};
 use crate::models::messages::PolledMessages;
@@ -56,6 +59,9 @@ impl PollMessagesCmd {
         next: bool,
         consumer: Identifier,
         show_headers: bool,
+        read_committed: bool,
+        filter: Option<HeaderFilter>,
+        max_wait: Option<IggyDuration>,
         output_file: Option<String>,
     ) -> Self {
         let strategy = match (offset, first, last, next) {
@@ -74,6 +80,15 @@ impl PollMessagesCmd {
                 strategy,
                 count: message_count,
                 auto_commit,
+                isolation_level: match read_committed {
+                    true => IsolationLevel::ReadCommitted,
+                    false => IsolationLevel::ReadUncommitted,
+                },
+                filter,
+                wait: match max_wait {
+                    Some(max_wait) => PollingWait::messages(max_wait, 0),
+                    None => PollingWait::none(),
+                },
             },
             show_headers,
             output_file,
@@ -177,6 +192,11 @@ impl CliCommand for PollMessagesCmd {
                 &self.poll_messages.strategy,
                 self.poll_messages.count,
                 self.poll_messages.auto_commit,
+                &PollMessagesOptions {
+                    isolation_level: self.poll_messages.isolation_level,
+                    filter: self.poll_messages.filter.clone(),
+                    wait: self.poll_messages.wait,
+                },
             )
             .await
             .with_context(|| {
@@ -1 +1,2 @@
 pub mod delete_segments;
+pub mod restore_segments;
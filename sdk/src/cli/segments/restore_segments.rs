@@ -0,0 +1,101 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::segments::restore_segments::{ArchiveSource, RestoreRange, RestoreSegments};
+use anyhow::Context;
+use async_trait::async_trait;
+use tracing::{event, Level};
+
+pub struct RestoreSegmentsCmd {
+    restore_segments: RestoreSegments,
+}
+
+impl RestoreSegmentsCmd {
+    pub fn new(
+        stream_id: Identifier,
+        topic_id: Identifier,
+        partition_id: u32,
+        source: ArchiveSource,
+        range: RestoreRange,
+    ) -> Self {
+        Self {
+            restore_segments: RestoreSegments {
+                stream_id,
+                topic_id,
+                partition_id,
+                source,
+                range,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for RestoreSegmentsCmd {
+    fn explain(&self) -> String {
+        format!(
+            "restore segments with {} range: {} - {} archived for stream with ID: {}, topic with ID: {} and partition with ID: {} into topic with ID: {}, stream with ID: {} and partition with ID: {}",
+            self.restore_segments.range.kind,
+            self.restore_segments.range.start,
+            self.restore_segments.range.end,
+            self.restore_segments.source.stream_id,
+            self.restore_segments.source.topic_id,
+            self.restore_segments.source.partition_id,
+            self.restore_segments.topic_id,
+            self.restore_segments.stream_id,
+            self.restore_segments.partition_id
+        )
+    }
+
+    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .restore_segments(
+                &self.restore_segments.stream_id,
+                &self.restore_segments.topic_id,
+                self.restore_segments.partition_id,
+                &self.restore_segments.source,
+                &self.restore_segments.range,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem restoring segments with {} range: {} - {} into topic with ID: {}, stream with ID: {} and partition with ID: {}",
+                    self.restore_segments.range.kind,
+                    self.restore_segments.range.start,
+                    self.restore_segments.range.end,
+                    self.restore_segments.topic_id,
+                    self.restore_segments.stream_id,
+                    self.restore_segments.partition_id
+                )
+            })?;
+
+        event!(target: PRINT_TARGET, Level::INFO,
+            "Restored segments with {} range: {} - {} into topic with ID: {}, stream with ID: {} and partition with ID: {}",
+            self.restore_segments.range.kind,
+            self.restore_segments.range.start,
+            self.restore_segments.range.end,
+            self.restore_segments.topic_id,
+            self.restore_segments.stream_id,
+            self.restore_segments.partition_id
+        );
+
+        Ok(())
+    }
+}
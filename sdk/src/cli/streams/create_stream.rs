@@ -18,6 +18,7 @@
 
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
+use crate::models::stream_limits::StreamLimits;
 use crate::streams::create_stream::CreateStream;
 use anyhow::Context;
 use async_trait::async_trait;
@@ -28,16 +29,24 @@ pub struct CreateStreamCmd {
 }
 
 impl CreateStreamCmd {
-    pub fn new(stream_id: Option<u32>, name: String) -> Self {
+    pub fn new(stream_id: Option<u32>, name: String, limits: Option<StreamLimits>) -> Self {
         Self {
-            create_stream: CreateStream { stream_id, name },
+            create_stream: CreateStream {
+                stream_id,
+                name,
+                limits,
+            },
         }
     }
 
     fn get_stream_id_info(&self) -> String {
-        match self.create_stream.stream_id {
+        let stream_id_info = match self.create_stream.stream_id {
             Some(stream_id) => format!("ID: {}", stream_id),
             None => "ID auto incremented".to_string(),
+        };
+        match self.create_stream.limits {
+            Some(limits) => format!("{stream_id_info} and limits: {limits}"),
+            None => stream_id_info,
         }
     }
 }
@@ -54,7 +63,11 @@ impl CliCommand for CreateStreamCmd {
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .create_stream(&self.create_stream.name, self.create_stream.stream_id)
+            .create_stream(
+                &self.create_stream.name,
+                self.create_stream.stream_id,
+                self.create_stream.limits,
+            )
             .await
             .with_context(|| {
                 format!(
@@ -19,6 +19,7 @@
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
+use crate::models::stream_limits::StreamLimits;
 use crate::streams::update_stream::UpdateStream;
 use anyhow::Context;
 use async_trait::async_trait;
@@ -29,9 +30,20 @@ pub struct UpdateStreamCmd {
 }
 
 impl UpdateStreamCmd {
-    pub fn new(stream_id: Identifier, name: String) -> Self {
+    pub fn new(stream_id: Identifier, name: String, limits: Option<StreamLimits>) -> Self {
         UpdateStreamCmd {
-            update_stream: UpdateStream { stream_id, name },
+            update_stream: UpdateStream {
+                stream_id,
+                name,
+                limits,
+            },
+        }
+    }
+
+    fn get_limits_info(&self) -> String {
+        match self.update_stream.limits {
+            Some(limits) => format!(" and limits: {limits}"),
+            None => String::new(),
         }
     }
 }
@@ -40,14 +52,20 @@ impl UpdateStreamCmd {
 impl CliCommand for UpdateStreamCmd {
     fn explain(&self) -> String {
         format!(
-            "update stream with ID: {} and name: {}",
-            self.update_stream.stream_id, self.update_stream.name
+            "update stream with ID: {} and name: {}{}",
+            self.update_stream.stream_id,
+            self.update_stream.name,
+            self.get_limits_info()
         )
     }
 
     async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
         client
-            .update_stream(&self.update_stream.stream_id, &self.update_stream.name)
+            .update_stream(
+                &self.update_stream.stream_id,
+                &self.update_stream.name,
+                self.update_stream.limits,
+            )
             .await
             .with_context(|| {
                 format!(
@@ -57,8 +75,8 @@ impl CliCommand for UpdateStreamCmd {
             })?;
 
         event!(target: PRINT_TARGET, Level::INFO,
-            "Stream with ID: {} updated name: {}",
-            self.update_stream.stream_id, self.update_stream.name
+            "Stream with ID: {} updated name: {}{}",
+            self.update_stream.stream_id, self.update_stream.name, self.get_limits_info()
         );
 
         Ok(())
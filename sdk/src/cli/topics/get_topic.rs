@@ -77,6 +77,10 @@ impl CliCommand for GetTopicCmd {
         ]);
         table.add_row(vec!["Topic name", topic.name.as_str()]);
         table.add_row(vec!["Topic size", format!("{}", topic.size).as_str()]);
+        table.add_row(vec![
+            "Uncompressed size",
+            format!("{}", topic.uncompressed_size).as_str(),
+        ]);
         table.add_row(vec![
             "Compression",
             topic.compression_algorithm.to_string().as_str(),
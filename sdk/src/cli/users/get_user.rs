@@ -198,6 +198,33 @@ impl CliCommand for GetUserCmd {
             }
         };
 
+        if let Some(quotas) = user.quotas {
+            table.add_row(vec![
+                "Quotas",
+                format!(
+                    "produce: {} B/s, consume: {} B/s, requests: {}/s",
+                    quotas.global.produce_bytes_per_second,
+                    quotas.global.consume_bytes_per_second,
+                    quotas.global.requests_per_second
+                )
+                .as_str(),
+            ]);
+
+            if let Some(streams) = quotas.streams {
+                streams.iter().for_each(|(stream_id, stream_quotas)| {
+                    table.add_row(vec![
+                        format!("Stream quotas: {}", stream_id).as_str(),
+                        format!(
+                            "produce: {} B/s, consume: {} B/s",
+                            stream_quotas.produce_bytes_per_second,
+                            stream_quotas.consume_bytes_per_second
+                        )
+                        .as_str(),
+                    ]);
+                });
+            }
+        }
+
         event!(target: PRINT_TARGET, Level::INFO, "{table}");
 
         Ok(())
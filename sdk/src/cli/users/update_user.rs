@@ -19,6 +19,7 @@
 use crate::cli_command::{CliCommand, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
 use crate::users::update_user::UpdateUser;
 use anyhow::Context;
@@ -29,6 +30,7 @@ use tracing::{event, Level};
 pub enum UpdateUserType {
     Name(String),
     Status(UserStatus),
+    Quotas(UserQuotas),
 }
 
 pub struct UpdateUserCmd {
@@ -38,9 +40,10 @@ pub struct UpdateUserCmd {
 
 impl UpdateUserCmd {
     pub fn new(user_id: Identifier, update_type: UpdateUserType) -> Self {
-        let (username, status) = match update_type.clone() {
-            UpdateUserType::Name(username) => (Some(username), None),
-            UpdateUserType::Status(status) => (None, Some(status)),
+        let (username, status, quotas) = match update_type.clone() {
+            UpdateUserType::Name(username) => (Some(username), None, None),
+            UpdateUserType::Status(status) => (None, Some(status), None),
+            UpdateUserType::Quotas(quotas) => (None, None, Some(quotas)),
         };
 
         UpdateUserCmd {
@@ -49,6 +52,7 @@ impl UpdateUserCmd {
                 user_id,
                 username,
                 status,
+                quotas,
             },
         }
     }
@@ -57,6 +61,12 @@ impl UpdateUserCmd {
         match &self.update_type {
             UpdateUserType::Name(username) => format!("username: {}", username),
             UpdateUserType::Status(status) => format!("status: {}", status),
+            UpdateUserType::Quotas(quotas) => format!(
+                "quotas: produce: {} B/s, consume: {} B/s, requests: {}/s",
+                quotas.global.produce_bytes_per_second,
+                quotas.global.consume_bytes_per_second,
+                quotas.global.requests_per_second
+            ),
         }
     }
 }
@@ -77,6 +87,7 @@ impl CliCommand for UpdateUserCmd {
                 &self.update_user.user_id,
                 self.update_user.username.as_deref(),
                 self.update_user.status,
+                self.update_user.quotas.clone(),
             )
             .await
             .with_context(|| {
@@ -16,16 +16,23 @@
  * under the License.
  */
 
+use crate::binary::subscription::MessageSubscription;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::compression::wire_compression::WireCompressionAlgorithm;
 use crate::consumer::Consumer;
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
-use crate::messages::poll_messages::PollingStrategy;
-use crate::messages::send_messages::{Message, Partitioning};
+use crate::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use crate::messages::send_messages::{Message, Partitioning, ProducerSequence};
+use crate::messages::subscribe_messages::SubscriptionOptions;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
+use crate::models::cluster::ClusterMetadata;
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_lag::ConsumerLag;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
+use crate::models::handshake::ProtocolFeatures;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::PolledMessages;
 use crate::models::permissions::Permissions;
@@ -33,11 +40,15 @@ use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalA
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
+use crate::models::stream_limits::StreamLimits;
 use crate::models::topic::{Topic, TopicDetails};
 use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
+use crate::segments::restore_segments::{ArchiveSource, RestoreRange};
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
 use crate::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::duration::IggyDuration;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
@@ -83,13 +94,15 @@ pub trait Client:
     + MessageClient
     + ConsumerOffsetClient
     + ConsumerGroupClient
+    + TransactionClient
     + Sync
     + Send
     + Debug
 {
     /// Connect to the server. Depending on the selected transport and provided configuration it might also perform authentication, retry logic etc.
     /// If the client is already connected, it will do nothing.
-    async fn connect(&self) -> Result<(), IggyError>;
+    /// Returns the protocol features negotiated with the server during the handshake, none for the transports without it.
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError>;
 
     /// Disconnect from the server. If the client is not connected, it will do nothing.
     async fn disconnect(&self) -> Result<(), IggyError>;
@@ -108,6 +121,10 @@ pub trait SystemClient {
     ///
     /// Authentication is required, and the permission to read the server info.
     async fn get_stats(&self) -> Result<Stats, IggyError>;
+    /// Get the metadata of the cluster such as its name, nodes, their roles and statuses.
+    ///
+    /// Authentication is required, and the permission to read the server info.
+    async fn get_cluster_metadata(&self) -> Result<ClusterMetadata, IggyError>;
     /// Get the info about the currently connected client (not to be confused with the user).
     ///
     /// Authentication is required.
@@ -159,6 +176,7 @@ pub trait UserClient {
     /// Authentication is required, and the permission to manage the users.
     async fn delete_user(&self, user_id: &Identifier) -> Result<(), IggyError>;
     /// Update a user by unique ID or username.
+    /// The optional quotas limit the rates of the produced and consumed bytes and the requests of the user.
     ///
     /// Authentication is required, and the permission to manage the users.
     async fn update_user(
@@ -166,6 +184,7 @@ pub trait UserClient {
         user_id: &Identifier,
         username: Option<&str>,
         status: Option<UserStatus>,
+        quotas: Option<UserQuotas>,
     ) -> Result<(), IggyError>;
     /// Update the permissions of a user by unique ID or username.
     ///
@@ -186,6 +205,10 @@ pub trait UserClient {
     ) -> Result<(), IggyError>;
     /// Login a user by username and password.
     async fn login_user(&self, username: &str, password: &str) -> Result<IdentityInfo, IggyError>;
+    /// Login a user with a JWT issued by one of the OIDC identity providers configured on the server.
+    ///
+    /// The user is matched by the configured username claim and might be provisioned on the first login.
+    async fn login_with_oidc_token(&self, token: &str) -> Result<IdentityInfo, IggyError>;
     /// Logout the currently authenticated user.
     async fn logout_user(&self) -> Result<(), IggyError>;
 }
@@ -224,15 +247,25 @@ pub trait StreamClient {
     /// Create a new stream.
     ///
     /// Authentication is required, and the permission to manage the streams.
+    ///
+    /// The optional limits bound the total size, topics and partitions count of the stream.
     async fn create_stream(
         &self,
         name: &str,
         stream_id: Option<u32>,
+        limits: Option<StreamLimits>,
     ) -> Result<StreamDetails, IggyError>;
     /// Update a stream by unique ID or name.
     ///
     /// Authentication is required, and the permission to manage the streams.
-    async fn update_stream(&self, stream_id: &Identifier, name: &str) -> Result<(), IggyError>;
+    ///
+    /// The limits are replaced only if provided.
+    async fn update_stream(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<(), IggyError>;
     /// Delete a stream by unique ID or name.
     ///
     /// Authentication is required, and the permission to manage the streams.
@@ -273,10 +306,12 @@ pub trait TopicClient {
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError>;
     /// Update a topic by unique ID or name.
     ///
     /// Authentication is required, and the permission to manage the topics.
+    #[allow(clippy::too_many_arguments)]
     async fn update_topic(
         &self,
         stream_id: &Identifier,
@@ -286,6 +321,7 @@ pub trait TopicClient {
         replication_factor: Option<u8>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError>;
     /// Delete a topic by unique ID or name.
     ///
@@ -347,12 +383,29 @@ pub trait SegmentClient {
         partition_id: u32,
         segments_count: u32,
     ) -> Result<(), IggyError>;
+
+    /// Restore the archived segments containing the messages from the specified range into a partition by unique ID or name.
+    ///
+    /// The segments are restored with their original offsets, so they must precede the messages stored in the partition, unless it's empty.
+    ///
+    /// Authentication is required, and the permission to manage the segments.
+    async fn restore_segments(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<(), IggyError>;
 }
 
 /// This trait defines the methods to interact with the messaging module.
 #[async_trait]
 pub trait MessageClient {
     /// Poll given amount of messages using the specified consumer and strategy from the specified stream and topic by unique IDs or names.
+    /// With the read committed isolation level, only the messages which were sent outside of any transaction or as a part of the committed one are returned.
+    /// With the header filter, only the matching messages are returned, while the skipped ones are still taken into account when committing the offset.
+    /// With the wait enabled, the server holds the request until enough messages are available or the maximum wait time expires.
     ///
     /// Authentication is required, and the permission to poll the messages.
     #[allow(clippy::too_many_arguments)]
@@ -365,7 +418,48 @@ pub trait MessageClient {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+        options: &PollMessagesOptions,
     ) -> Result<PolledMessages, IggyError>;
+    /// Poll given amount of messages from each of the partitions assigned to the client in the consumer group,
+    /// which has been joined before, for the specified stream and topic by unique IDs or names.
+    /// The messages are returned separately for each of the assigned partitions.
+    /// The isolation level and header filter are applied the same way as when polling the messages,
+    /// while the wait isn't supported and must be disabled.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_assigned_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        group_id: &Identifier,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        options: &PollMessagesOptions,
+    ) -> Result<Vec<PolledMessages>, IggyError>;
+    /// Subscribe to the messages appended to the specified stream and topic by unique IDs or names.
+    /// Instead of being polled, the batches of up to `count` messages are pushed by the server as soon as they are appended,
+    /// for as long as there is the credit granted by the client, counted either in messages or bytes.
+    /// The offsets are stored on the server according to the ack mode of the subscription options.
+    /// The isolation level and header filter are applied the same way as when polling the messages,
+    /// while the wait doesn't apply, as the messages are pushed as soon as they are appended.
+    /// The subscription is available only for the binary transports, and requires the automatic sign-in for TCP,
+    /// as it uses the dedicated connection.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    async fn subscribe_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        options: &PollMessagesOptions,
+        subscription: &SubscriptionOptions,
+    ) -> Result<MessageSubscription, IggyError>;
     /// Send messages using specified partitioning strategy to the given stream and topic by unique IDs or names.
     ///
     /// Authentication is required, and the permission to send the messages.
@@ -376,6 +470,23 @@ pub trait MessageClient {
         partitioning: &Partitioning,
         messages: &mut [Message],
     ) -> Result<(), IggyError>;
+    /// Obtain the unique producer ID assigned by the server, which is used to send the idempotent messages.
+    ///
+    /// Authentication is required.
+    async fn init_producer(&self) -> Result<u64, IggyError>;
+    /// Send messages as the idempotent producer to the given partition of the stream and topic by unique IDs or names.
+    /// The sequence number must be incremented for each batch sent to the same partition, the retried batch
+    /// with the already stored sequence number is acknowledged by the server without appending its messages again.
+    ///
+    /// Authentication is required, and the permission to send the messages.
+    async fn send_idempotent_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError>;
     /// Force flush of the `unsaved_messages` buffer to disk, optionally fsyncing the data.
     #[allow(clippy::too_many_arguments)]
     async fn flush_unsaved_buffer(
@@ -385,12 +496,27 @@ pub trait MessageClient {
         partition_id: u32,
         fsync: bool,
     ) -> Result<(), IggyError>;
+    /// Report that the message with the given offset couldn't be processed by the consumer (negative acknowledgement).
+    /// Once the message is nacked the number of times configured on the server, it's moved to the dead letter topic
+    /// together with its headers and the information about its origin (stream, topic, partition and offset).
+    /// The original message remains in the partition and is then polled with the `Poisoned` state.
+    /// The messages in the dead letter topic cannot be nacked.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    async fn nack_message(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError>;
 }
 
 /// This trait defines the methods to interact with the consumer offset module.
 #[async_trait]
 pub trait ConsumerOffsetClient {
     /// Store the consumer offset for a specific consumer or consumer group for the given stream and topic by unique IDs or names.
+    /// For the consumer group, the optional generation returned with the polled messages can be provided to reject the offset if the group has been rebalanced in the meantime.
     ///
     /// Authentication is required, and the permission to poll the messages.
     async fn store_consumer_offset(
@@ -400,6 +526,7 @@ pub trait ConsumerOffsetClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation_id: Option<u32>,
     ) -> Result<(), IggyError>;
     /// Get the consumer offset for a specific consumer or consumer group for the given stream and topic by unique IDs or names.
     ///
@@ -421,6 +548,16 @@ pub trait ConsumerOffsetClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
     ) -> Result<(), IggyError>;
+    /// Get the lag of a specific consumer or consumer group in each partition of the given stream and topic by unique IDs or names,
+    /// i.e. the number, size and age of the messages following the stored offsets.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<ConsumerLag, IggyError>;
 }
 
 /// This trait defines the methods to interact with the consumer group module.
@@ -452,6 +589,7 @@ pub trait ConsumerGroupClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: AssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError>;
     /// Delete a consumer group by unique ID or name for the given stream and topic by unique IDs or names.
     ///
@@ -482,6 +620,26 @@ pub trait ConsumerGroupClient {
     ) -> Result<(), IggyError>;
 }
 
+/// This trait defines the methods to interact with the transactions module.
+/// The transaction is bound to the client session, which means that it's available only for the stateful transports (TCP and QUIC).
+#[async_trait]
+pub trait TransactionClient {
+    /// Begin a new transaction and return its unique ID. Only one transaction can be open at a time for the client.
+    /// All the messages sent until the transaction is committed or aborted (even to the different partitions, topics or streams) become a part of it.
+    ///
+    /// Authentication is required.
+    async fn begin_transaction(&self) -> Result<u64, IggyError>;
+    /// Commit the open transaction by unique ID, which atomically makes all of its messages visible to the consumers polling with the read committed isolation level.
+    ///
+    /// Authentication is required.
+    async fn commit_transaction(&self, transaction_id: u64) -> Result<(), IggyError>;
+    /// Abort the open transaction by unique ID, so that none of its messages are ever returned to the consumers polling with the read committed isolation level.
+    /// The transaction is also aborted when the client disconnects before committing it.
+    ///
+    /// Authentication is required.
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError>;
+}
+
 impl FromStr for ConnectionString {
     type Err = IggyError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -567,6 +725,8 @@ impl ConnectionString {
         let mut reestablish_after = "5s".to_owned();
         let mut heartbeat_interval = "5s".to_owned();
         let mut nodelay = false;
+        let mut multiplexing = false;
+        let mut compression = None;
 
         for option in options {
             let option_parts = option.split('=').collect::<Vec<&str>>();
@@ -598,6 +758,15 @@ impl ConnectionString {
                 "nodelay" => {
                     nodelay = option_parts[1] == "true";
                 }
+                "multiplexing" => {
+                    multiplexing = option_parts[1] == "true";
+                }
+                "compression" => {
+                    compression = Some(
+                        WireCompressionAlgorithm::from_str(option_parts[1])
+                            .map_err(|_| IggyError::InvalidConnectionString)?,
+                    );
+                }
                 _ => {
                     return Err(IggyError::InvalidConnectionString);
                 }
@@ -625,6 +794,8 @@ impl ConnectionString {
                     .map_err(|_| IggyError::InvalidConnectionString)?,
             },
             nodelay,
+            multiplexing,
+            compression,
         })
     }
 }
@@ -637,6 +808,8 @@ struct ConnectionStringOptions {
     reconnection: TcpClientReconnectionConfig,
     heartbeat_interval: IggyDuration,
     nodelay: bool,
+    multiplexing: bool,
+    compression: Option<WireCompressionAlgorithm>,
 }
 
 impl Default for ConnectionStringOptions {
@@ -648,6 +821,8 @@ impl Default for ConnectionStringOptions {
             reconnection: Default::default(),
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             nodelay: false,
+            multiplexing: false,
+            compression: None,
         }
     }
 }
@@ -663,6 +838,9 @@ impl From<ConnectionString> for TcpClientConfig {
             reconnection: connection_string.options.reconnection,
             heartbeat_interval: connection_string.options.heartbeat_interval,
             nodelay: connection_string.options.nodelay,
+            multiplexing: connection_string.options.multiplexing,
+            compression: connection_string.options.compression,
+            ..Default::default()
         }
     }
 }
@@ -728,6 +906,8 @@ mod tests {
             IggyDuration::from_str("1s").unwrap()
         );
         assert!(!connection_string.options.nodelay);
+        assert!(!connection_string.options.multiplexing);
+        assert!(connection_string.options.compression.is_none());
     }
 
     #[test]
@@ -743,7 +923,9 @@ mod tests {
         let reestablish_after = "10s";
         let heartbeat_interval = "3s";
         let nodelay = true;
-        let value = format!("{CONNECTION_STRING_PREFIX}{username}:{password}@{server_address}?tls={tls}&tls_domain={tls_domain}&tls_ca_file={tls_ca_file}&reconnection_retries={reconnection_retries}&reconnection_interval={reconnection_interval}&reestablish_after={reestablish_after}&heartbeat_interval={heartbeat_interval}&nodelay={nodelay}");
+        let multiplexing = true;
+        let compression = "zstd";
+        let value = format!("{CONNECTION_STRING_PREFIX}{username}:{password}@{server_address}?tls={tls}&tls_domain={tls_domain}&tls_ca_file={tls_ca_file}&reconnection_retries={reconnection_retries}&reconnection_interval={reconnection_interval}&reestablish_after={reestablish_after}&heartbeat_interval={heartbeat_interval}&nodelay={nodelay}&multiplexing={multiplexing}&compression={compression}");
         let connection_string = ConnectionString::new(&value);
         assert!(connection_string.is_ok());
         let connection_string = connection_string.unwrap();
@@ -780,5 +962,10 @@ mod tests {
             IggyDuration::from_str(heartbeat_interval).unwrap()
         );
         assert_eq!(connection_string.options.nodelay, nodelay);
+        assert_eq!(connection_string.options.multiplexing, multiplexing);
+        assert_eq!(
+            connection_string.options.compression,
+            Some(WireCompressionAlgorithm::Zstd)
+        );
     }
 }
@@ -20,12 +20,14 @@ use crate::client::{AutoLogin, Client, Credentials};
 use crate::client_error::ClientError;
 #[allow(deprecated)]
 use crate::clients::client::IggyClient;
+use crate::compression::wire_compression::DEFAULT_COMPRESSION_THRESHOLD;
 use crate::http::client::HttpClient;
 use crate::http::config::HttpClientConfig;
 use crate::quic::client::QuicClient;
 use crate::quic::config::{QuicClientConfig, QuicClientReconnectionConfig};
 use crate::tcp::client::TcpClient;
 use crate::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use crate::utils::byte_size::IggyByteSize;
 use crate::utils::duration::IggyDuration;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -116,12 +118,19 @@ impl ClientProviderConfig {
                     keep_alive_interval: args.quic_keep_alive_interval,
                     max_idle_timeout: args.quic_max_idle_timeout,
                     validate_certificate: args.quic_validate_certificate,
+                    client_certificate_file: args.quic_client_certificate_file,
+                    client_key_file: args.quic_client_key_file,
+                    compression: args.quic_compression,
+                    compression_threshold: IggyByteSize::from(DEFAULT_COMPRESSION_THRESHOLD),
                 }));
             }
             HTTP_TRANSPORT => {
                 config.http = Some(Arc::new(HttpClientConfig {
                     api_url: args.http_api_url,
                     retries: args.http_retries,
+                    tls_ca_file: args.http_tls_ca_file,
+                    tls_certificate_file: args.http_tls_certificate_file,
+                    tls_key_file: args.http_tls_key_file,
                 }));
             }
             TCP_TRANSPORT => {
@@ -130,7 +139,12 @@ impl ClientProviderConfig {
                     tls_enabled: args.tcp_tls_enabled,
                     tls_domain: args.tcp_tls_domain,
                     tls_ca_file: args.tcp_tls_ca_file,
+                    tls_certificate_file: args.tcp_tls_certificate_file,
+                    tls_key_file: args.tcp_tls_key_file,
                     nodelay: args.tcp_nodelay,
+                    multiplexing: args.tcp_multiplexing,
+                    compression: args.tcp_compression,
+                    compression_threshold: IggyByteSize::from(DEFAULT_COMPRESSION_THRESHOLD),
                     heartbeat_interval: IggyDuration::from_str(&args.tcp_heartbeat_interval)
                         .unwrap(),
                     reconnection: TcpClientReconnectionConfig {
@@ -188,8 +202,8 @@ pub async fn get_raw_client(
             let quic_config = config.quic.as_ref().unwrap();
             let client = QuicClient::create(quic_config.clone())?;
             if establish_connection {
-                Client::connect(&client).await?
-            };
+                Client::connect(&client).await?;
+            }
             Ok(Box::new(client))
         }
         HTTP_TRANSPORT => {
@@ -201,8 +215,8 @@ pub async fn get_raw_client(
             let tcp_config = config.tcp.as_ref().unwrap();
             let client = TcpClient::create(tcp_config.clone())?;
             if establish_connection {
-                Client::connect(&client).await?
-            };
+                Client::connect(&client).await?;
+            }
             Ok(Box::new(client))
         }
         _ => Err(ClientError::InvalidTransport(transport)),
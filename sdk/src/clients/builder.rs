@@ -18,6 +18,7 @@
 
 use crate::client::{AutoLogin, Client};
 use crate::clients::client::IggyClient;
+use crate::compression::wire_compression::WireCompressionAlgorithm;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::config::HttpClientConfigBuilder;
@@ -28,6 +29,8 @@ use crate::tcp::client::TcpClient;
 use crate::tcp::config::TcpClientConfigBuilder;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
+use crate::websocket::client::WebSocketClient;
+use crate::websocket::config::WebSocketClientConfigBuilder;
 use std::sync::Arc;
 use tracing::error;
 
@@ -102,10 +105,20 @@ impl IggyClientBuilder {
         }
     }
 
+    /// This method provides fluent API for the WebSocket client configuration.
+    /// It returns the `WebSocketClientBuilder` instance, which allows to configure the WebSocket client with custom settings or using defaults.
+    /// This should be called after the non-protocol specific methods, such as `with_partitioner`, `with_encryptor` or `with_message_handler`.
+    pub fn with_websocket(self) -> WebSocketClientBuilder {
+        WebSocketClientBuilder {
+            config: WebSocketClientConfigBuilder::default(),
+            parent_builder: self,
+        }
+    }
+
     /// Build the `IggyClient` instance.
     /// This method returns an error if the client is not provided.
     /// If the client is provided, it creates the `IggyClient` instance with the provided configuration.
-    /// To provide the client configuration, use the `with_tcp`, `with_quic` or `with_http` methods.
+    /// To provide the client configuration, use the `with_tcp`, `with_quic`, `with_http` or `with_websocket` methods.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let Some(client) = self.client else {
             error!("Client is not provided");
@@ -169,12 +182,36 @@ impl TcpClientBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key presented to the server.
+    pub fn with_tls_client_certificate(
+        mut self,
+        certificate_file: String,
+        key_file: String,
+    ) -> Self {
+        self.config = self
+            .config
+            .with_tls_client_certificate(certificate_file, key_file);
+        self
+    }
+
     /// Sets the nodelay option for the TCP socket.
     pub fn with_no_delay(mut self) -> Self {
         self.config = self.config.with_no_delay();
         self
     }
 
+    /// Enables the multiplexing of the requests sent over the single connection.
+    pub fn with_multiplexing(mut self) -> Self {
+        self.config = self.config.with_multiplexing();
+        self
+    }
+
+    /// Sets the algorithm offered to compress the payloads sent over the wire.
+    pub fn with_compression(mut self, compression: WireCompressionAlgorithm) -> Self {
+        self.config = self.config.with_compression(compression);
+        self
+    }
+
     /// Builds the parent `IggyClient` with TCP configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = TcpClient::create(Arc::new(self.config.build()))?;
@@ -224,6 +261,20 @@ impl QuicClientBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key presented to the server.
+    pub fn with_client_certificate(mut self, certificate_file: String, key_file: String) -> Self {
+        self.config = self
+            .config
+            .with_client_certificate(certificate_file, key_file);
+        self
+    }
+
+    /// Sets the algorithm offered to compress the payloads sent over the wire.
+    pub fn with_compression(mut self, compression: WireCompressionAlgorithm) -> Self {
+        self.config = self.config.with_compression(compression);
+        self
+    }
+
     /// Builds the parent `IggyClient` with QUIC configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = QuicClient::create(Arc::new(self.config.build()))?;
@@ -251,6 +302,24 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Sets the path to the CA file for HTTPS.
+    pub fn with_tls_ca_file(mut self, tls_ca_file: String) -> Self {
+        self.config = self.config.with_tls_ca_file(tls_ca_file);
+        self
+    }
+
+    /// Sets the paths to the client certificate and its private key presented to the server.
+    pub fn with_tls_client_certificate(
+        mut self,
+        certificate_file: String,
+        key_file: String,
+    ) -> Self {
+        self.config = self
+            .config
+            .with_tls_client_certificate(certificate_file, key_file);
+        self
+    }
+
     /// Builds the parent `IggyClient` with HTTP configuration.
     pub fn build(self) -> Result<IggyClient, IggyError> {
         let client = HttpClient::create(Arc::new(self.config.build()))?;
@@ -258,3 +327,36 @@ impl HttpClientBuilder {
         Ok(client)
     }
 }
+
+#[derive(Debug, Default)]
+pub struct WebSocketClientBuilder {
+    config: WebSocketClientConfigBuilder,
+    parent_builder: IggyClientBuilder,
+}
+
+impl WebSocketClientBuilder {
+    /// Sets the server address for the WebSocket client.
+    pub fn with_server_address(mut self, server_address: String) -> Self {
+        self.config = self.config.with_server_address(server_address);
+        self
+    }
+
+    /// Sets the auto sign in during connection.
+    pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
+        self.config = self.config.with_auto_sign_in(auto_sign_in);
+        self
+    }
+
+    /// Sets the interval of heartbeats sent by the client.
+    pub fn with_heartbeat_interval(mut self, heartbeat_interval: IggyDuration) -> Self {
+        self.config = self.config.with_heartbeat_interval(heartbeat_interval);
+        self
+    }
+
+    /// Builds the parent `IggyClient` with WebSocket configuration.
+    pub fn build(self) -> Result<IggyClient, IggyError> {
+        let client = WebSocketClient::create(Arc::new(self.config.build()))?;
+        let client = self.parent_builder.with_client(Box::new(client)).build()?;
+        Ok(client)
+    }
+}
@@ -16,25 +16,32 @@
  * under the License.
  */
 
+use crate::binary::subscription::MessageSubscription;
 use crate::client::{
     Client, ConsumerGroupClient, ConsumerOffsetClient, MessageClient, PartitionClient,
-    PersonalAccessTokenClient, SegmentClient, StreamClient, SystemClient, TopicClient, UserClient,
+    PersonalAccessTokenClient, SegmentClient, StreamClient, SystemClient, TopicClient,
+    TransactionClient, UserClient,
 };
 use crate::clients::builder::IggyClientBuilder;
 use crate::clients::consumer::IggyConsumerBuilder;
 use crate::clients::producer::IggyProducerBuilder;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::consumer::Consumer;
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::locking::IggySharedMut;
 use crate::locking::IggySharedMutFn;
-use crate::messages::poll_messages::PollingStrategy;
-use crate::messages::send_messages::{Message, Partitioning};
+use crate::messages::poll_messages::{PollMessagesOptions, PollingStrategy};
+use crate::messages::send_messages::{Message, Partitioning, ProducerSequence};
+use crate::messages::subscribe_messages::SubscriptionOptions;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
+use crate::models::cluster::ClusterMetadata;
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_lag::ConsumerLag;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
+use crate::models::handshake::ProtocolFeatures;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::PolledMessages;
 use crate::models::permissions::Permissions;
@@ -42,12 +49,16 @@ use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalA
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
+use crate::models::stream_limits::StreamLimits;
 use crate::models::topic::{Topic, TopicDetails};
 use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
 use crate::partitioner::Partitioner;
+use crate::segments::restore_segments::{ArchiveSource, RestoreRange};
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
 use crate::tcp::client::TcpClient;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
@@ -191,11 +202,12 @@ impl IggyClient {
 
 #[async_trait]
 impl Client for IggyClient {
-    async fn connect(&self) -> Result<(), IggyError> {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
         let heartbeat_interval;
+        let features;
         {
             let client = self.client.read().await;
-            client.connect().await?;
+            features = client.connect().await?;
             heartbeat_interval = client.heartbeat_interval().await;
         }
 
@@ -215,7 +227,7 @@ impl Client for IggyClient {
                 sleep(heartbeat_interval.get_duration()).await
             }
         });
-        Ok(())
+        Ok(features)
     }
 
     async fn disconnect(&self) -> Result<(), IggyError> {
@@ -264,11 +276,12 @@ impl UserClient for IggyClient {
         user_id: &Identifier,
         username: Option<&str>,
         status: Option<UserStatus>,
+        quotas: Option<UserQuotas>,
     ) -> Result<(), IggyError> {
         self.client
             .read()
             .await
-            .update_user(user_id, username, status)
+            .update_user(user_id, username, status, quotas)
             .await
     }
 
@@ -305,6 +318,10 @@ impl UserClient for IggyClient {
             .await
     }
 
+    async fn login_with_oidc_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        self.client.read().await.login_with_oidc_token(token).await
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         self.client.read().await.logout_user().await
     }
@@ -354,6 +371,10 @@ impl SystemClient for IggyClient {
         self.client.read().await.get_stats().await
     }
 
+    async fn get_cluster_metadata(&self) -> Result<ClusterMetadata, IggyError> {
+        self.client.read().await.get_cluster_metadata().await
+    }
+
     async fn get_me(&self) -> Result<ClientInfoDetails, IggyError> {
         self.client.read().await.get_me().await
     }
@@ -401,19 +422,25 @@ impl StreamClient for IggyClient {
         &self,
         name: &str,
         stream_id: Option<u32>,
+        limits: Option<StreamLimits>,
     ) -> Result<StreamDetails, IggyError> {
         self.client
             .read()
             .await
-            .create_stream(name, stream_id)
+            .create_stream(name, stream_id, limits)
             .await
     }
 
-    async fn update_stream(&self, stream_id: &Identifier, name: &str) -> Result<(), IggyError> {
+    async fn update_stream(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<(), IggyError> {
         self.client
             .read()
             .await
-            .update_stream(stream_id, name)
+            .update_stream(stream_id, name, limits)
             .await
     }
 
@@ -454,6 +481,7 @@ impl TopicClient for IggyClient {
         topic_id: Option<u32>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<TopicDetails, IggyError> {
         self.client
             .read()
@@ -467,6 +495,7 @@ impl TopicClient for IggyClient {
                 topic_id,
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             )
             .await
     }
@@ -480,6 +509,7 @@ impl TopicClient for IggyClient {
         replication_factor: Option<u8>,
         message_expiry: IggyExpiry,
         max_topic_size: MaxTopicSize,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         self.client
             .read()
@@ -492,6 +522,7 @@ impl TopicClient for IggyClient {
                 replication_factor,
                 message_expiry,
                 max_topic_size,
+                cleanup_policy,
             )
             .await
     }
@@ -565,6 +596,21 @@ impl SegmentClient for IggyClient {
             .delete_segments(stream_id, topic_id, partition_id, segments_count)
             .await
     }
+
+    async fn restore_segments(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .restore_segments(stream_id, topic_id, partition_id, source, range)
+            .await
+    }
 }
 
 #[async_trait]
@@ -578,6 +624,7 @@ impl MessageClient for IggyClient {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+        options: &PollMessagesOptions,
     ) -> Result<PolledMessages, IggyError> {
         if count == 0 {
             return Err(IggyError::InvalidMessagesCount);
@@ -595,6 +642,7 @@ impl MessageClient for IggyClient {
                 strategy,
                 count,
                 auto_commit,
+                options,
             )
             .await?;
 
@@ -609,6 +657,82 @@ impl MessageClient for IggyClient {
         Ok(polled_messages)
     }
 
+    async fn poll_assigned_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        group_id: &Identifier,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        options: &PollMessagesOptions,
+    ) -> Result<Vec<PolledMessages>, IggyError> {
+        if count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        let mut partitions_messages = self
+            .client
+            .read()
+            .await
+            .poll_assigned_messages(
+                stream_id,
+                topic_id,
+                group_id,
+                strategy,
+                count,
+                auto_commit,
+                options,
+            )
+            .await?;
+
+        if let Some(ref encryptor) = self.encryptor {
+            for polled_messages in &mut partitions_messages {
+                for message in &mut polled_messages.messages {
+                    let payload = encryptor.decrypt(&message.payload)?;
+                    message.payload = Bytes::from(payload);
+                    message.length = IggyByteSize::from(message.payload.len() as u64);
+                }
+            }
+        }
+
+        Ok(partitions_messages)
+    }
+
+    async fn subscribe_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        options: &PollMessagesOptions,
+        subscription: &SubscriptionOptions,
+    ) -> Result<MessageSubscription, IggyError> {
+        if count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        let mut subscription = self
+            .client
+            .read()
+            .await
+            .subscribe_messages(
+                stream_id,
+                topic_id,
+                partition_id,
+                consumer,
+                strategy,
+                count,
+                options,
+                subscription,
+            )
+            .await?;
+        subscription.set_encryptor(self.encryptor.clone());
+        Ok(subscription)
+    }
+
     async fn send_messages(
         &self,
         stream_id: &Identifier,
@@ -634,6 +758,42 @@ impl MessageClient for IggyClient {
             .await
     }
 
+    async fn init_producer(&self) -> Result<u64, IggyError> {
+        self.client.read().await.init_producer().await
+    }
+
+    async fn send_idempotent_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        if messages.is_empty() {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        if let Some(encryptor) = &self.encryptor {
+            for message in &mut *messages {
+                message.payload = Bytes::from(encryptor.encrypt(&message.payload)?);
+                message.length = message.payload.len() as u32;
+            }
+        }
+
+        self.client
+            .read()
+            .await
+            .send_idempotent_messages(
+                stream_id,
+                topic_id,
+                partition_id,
+                producer_sequence,
+                messages,
+            )
+            .await
+    }
+
     async fn flush_unsaved_buffer(
         &self,
         stream_id: &Identifier,
@@ -647,6 +807,20 @@ impl MessageClient for IggyClient {
             .flush_unsaved_buffer(stream_id, topic_id, partition_id, fsync)
             .await
     }
+
+    async fn nack_message(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .nack_message(stream_id, topic_id, partition_id, offset)
+            .await
+    }
 }
 
 #[async_trait]
@@ -658,11 +832,19 @@ impl ConsumerOffsetClient for IggyClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation_id: Option<u32>,
     ) -> Result<(), IggyError> {
         self.client
             .read()
             .await
-            .store_consumer_offset(consumer, stream_id, topic_id, partition_id, offset)
+            .store_consumer_offset(
+                consumer,
+                stream_id,
+                topic_id,
+                partition_id,
+                offset,
+                generation_id,
+            )
             .await
     }
 
@@ -693,6 +875,19 @@ impl ConsumerOffsetClient for IggyClient {
             .delete_consumer_offset(consumer, stream_id, topic_id, partition_id)
             .await
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<ConsumerLag, IggyError> {
+        self.client
+            .read()
+            .await
+            .get_consumer_lag(consumer, stream_id, topic_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -728,11 +923,12 @@ impl ConsumerGroupClient for IggyClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: AssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError> {
         self.client
             .read()
             .await
-            .create_consumer_group(stream_id, topic_id, name, group_id)
+            .create_consumer_group(stream_id, topic_id, name, group_id, assignment_strategy)
             .await
     }
 
@@ -776,6 +972,29 @@ impl ConsumerGroupClient for IggyClient {
     }
 }
 
+#[async_trait]
+impl TransactionClient for IggyClient {
+    async fn begin_transaction(&self) -> Result<u64, IggyError> {
+        self.client.read().await.begin_transaction().await
+    }
+
+    async fn commit_transaction(&self, transaction_id: u64) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .commit_transaction(transaction_id)
+            .await
+    }
+
+    async fn abort_transaction(&self, transaction_id: u64) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .abort_transaction(transaction_id)
+            .await
+    }
+}
+
 #[async_trait]
 impl AsyncDrop for IggyClient {
     async fn async_drop(&mut self) {
@@ -16,17 +16,24 @@
  * under the License.
  */
 
+use crate::binary::subscription::MessageSubscription;
 use crate::client::Client;
 use crate::consumer::{Consumer, ConsumerKind};
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::locking::{IggySharedMut, IggySharedMutFn};
-use crate::messages::poll_messages::{PollingKind, PollingStrategy};
+use crate::messages::header_filter::HeaderFilter;
+use crate::messages::poll_messages::{
+    PollMessagesOptions, PollingKind, PollingStrategy, PollingWait,
+};
+use crate::messages::subscribe_messages::{AckMode, CreditKind, SubscriptionOptions};
 use crate::models::messages::{PolledMessage, PolledMessages};
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
+use crate::utils::sizeable::Sizeable;
 use crate::utils::timestamp::IggyTimestamp;
 use bytes::Bytes;
 use dashmap::DashMap;
@@ -39,6 +46,7 @@ use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tokio::time;
 use tokio::time::sleep;
 use tracing::{error, info, trace, warn};
@@ -95,6 +103,21 @@ pub enum AutoCommitAfter {
     ConsumingEveryNthMessage(u32),
 }
 
+/// The subscription over which the consumer receives the messages pushed by the server instead of polling them.
+/// It's opened lazily, and reopened from the current polling strategy once it has been closed.
+struct ConsumerSubscription {
+    credit_kind: CreditKind,
+    credit: u64,
+    active: Mutex<Option<ActiveSubscription>>,
+}
+
+struct ActiveSubscription {
+    subscription: MessageSubscription,
+    // The credit of the last received batch is granted back only when the next one is requested,
+    // so the server never pushes more than the consumer keeps up with.
+    consumed_credit: u64,
+}
+
 unsafe impl Send for IggyConsumer {}
 unsafe impl Sync for IggyConsumer {}
 
@@ -128,10 +151,13 @@ pub struct IggyConsumer {
     store_after_every_nth_message: u64,
     last_polled_at: Arc<AtomicU64>,
     current_partition_id: Arc<AtomicU32>,
+    current_generation_id: Arc<AtomicU32>,
     reconnection_retry_interval: IggyDuration,
     init_retries: Option<u32>,
     init_retry_interval: IggyDuration,
     allow_replay: bool,
+    options: Arc<PollMessagesOptions>,
+    subscription: Option<Arc<ConsumerSubscription>>,
 }
 
 impl IggyConsumer {
@@ -154,6 +180,8 @@ impl IggyConsumer {
         init_retries: Option<u32>,
         init_retry_interval: IggyDuration,
         allow_replay: bool,
+        options: PollMessagesOptions,
+        subscription: Option<(CreditKind, u64)>,
     ) -> Self {
         let (store_offset_sender, _) = flume::unbounded();
         Self {
@@ -204,10 +232,19 @@ impl IggyConsumer {
             },
             last_polled_at: Arc::new(AtomicU64::new(0)),
             current_partition_id: Arc::new(AtomicU32::new(0)),
+            current_generation_id: Arc::new(AtomicU32::new(0)),
             reconnection_retry_interval,
             init_retries,
             init_retry_interval,
             allow_replay,
+            options: Arc::new(options),
+            subscription: subscription.map(|(credit_kind, credit)| {
+                Arc::new(ConsumerSubscription {
+                    credit_kind,
+                    credit,
+                    active: Mutex::new(None),
+                })
+            }),
         }
     }
 
@@ -253,12 +290,32 @@ impl IggyConsumer {
             &self.topic_id,
             partition_id,
             offset,
+            Self::get_generation_id(&self.current_generation_id),
             &self.last_stored_offsets,
             self.allow_replay,
         )
         .await
     }
 
+    /// Reports that the message with the given offset couldn't be processed, either for the current partition or the provided partition ID.
+    /// Once the message is nacked the number of times configured on the server, it's moved to the dead letter topic.
+    pub async fn nack_message(
+        &self,
+        offset: u64,
+        partition_id: Option<u32>,
+    ) -> Result<(), IggyError> {
+        let partition_id = if let Some(partition_id) = partition_id {
+            partition_id
+        } else {
+            self.current_partition_id.load(ORDERING)
+        };
+        trace!("Nacking message with offset: {offset} for consumer: {}, partition ID: {partition_id}, topic: {}, stream: {}...", self.consumer, self.topic_id, self.stream_id);
+        let client = self.client.read().await;
+        client
+            .nack_message(&self.stream_id, &self.topic_id, partition_id, offset)
+            .await
+    }
+
     /// Deletes the consumer offset on the server either for the current partition or the provided partition ID.
     pub async fn delete_offset(&self, partition_id: Option<u32>) -> Result<(), IggyError> {
         let client = self.client.read().await;
@@ -361,6 +418,7 @@ impl IggyConsumer {
         let stream_id = self.stream_id.clone();
         let topic_id = self.topic_id.clone();
         let last_stored_offsets = self.last_stored_offsets.clone();
+        let current_generation_id = self.current_generation_id.clone();
         let (store_offset_sender, store_offset_receiver) = flume::unbounded();
         self.store_offset_sender = store_offset_sender;
 
@@ -374,6 +432,7 @@ impl IggyConsumer {
                     &topic_id,
                     partition_id,
                     offset,
+                    Self::get_generation_id(&current_generation_id),
                     &last_stored_offsets,
                     false,
                 )
@@ -397,6 +456,7 @@ impl IggyConsumer {
         topic_id: &Identifier,
         partition_id: u32,
         offset: u64,
+        generation_id: Option<u32>,
         last_stored_offsets: &DashMap<u32, AtomicU64>,
         allow_replay: bool,
     ) -> Result<(), IggyError> {
@@ -416,7 +476,14 @@ impl IggyConsumer {
 
         let client = client.read().await;
         if let Err(error) = client
-            .store_consumer_offset(consumer, stream_id, topic_id, Some(partition_id), offset)
+            .store_consumer_offset(
+                consumer,
+                stream_id,
+                topic_id,
+                Some(partition_id),
+                offset,
+                generation_id,
+            )
             .await
         {
             error!("Failed to store offset: {offset} for consumer: {consumer}, partition ID: {partition_id}, topic: {topic_id}, stream: {stream_id}. {error}");
@@ -438,6 +505,7 @@ impl IggyConsumer {
         let topic_id = self.topic_id.clone();
         let last_consumed_offsets = self.last_consumed_offsets.clone();
         let last_stored_offsets = self.last_stored_offsets.clone();
+        let current_generation_id = self.current_generation_id.clone();
         tokio::spawn(async move {
             loop {
                 sleep(interval.get_duration()).await;
@@ -451,6 +519,7 @@ impl IggyConsumer {
                         &topic_id,
                         partition_id,
                         consumed_offset,
+                        Self::get_generation_id(&current_generation_id),
                         &last_stored_offsets,
                         false,
                     )
@@ -460,6 +529,14 @@ impl IggyConsumer {
         });
     }
 
+    /// Returns the generation of the consumer group assignment, which is `None` for the regular consumer.
+    fn get_generation_id(current_generation_id: &AtomicU32) -> Option<u32> {
+        match current_generation_id.load(ORDERING) {
+            0 => None,
+            generation_id => Some(generation_id),
+        }
+    }
+
     pub(crate) fn send_store_offset(&self, partition_id: u32, offset: u64) {
         if let Err(error) = self.store_offset_sender.send((partition_id, offset)) {
             error!("Failed to send offset to store: {error}, please verify if `init()` on IggyConsumer object has been called.");
@@ -602,7 +679,10 @@ impl IggyConsumer {
         let retry_interval = self.reconnection_retry_interval;
         let last_stored_offset = self.last_stored_offsets.clone();
         let last_consumed_offset = self.last_consumed_offsets.clone();
+        let current_generation_id = self.current_generation_id.clone();
         let allow_replay = self.allow_replay;
+        let options = self.options.clone();
+        let subscription = self.subscription.clone();
 
         async move {
             if interval > 0 {
@@ -614,12 +694,12 @@ impl IggyConsumer {
                 sleep(retry_interval.get_duration()).await;
             }
 
-            trace!("Sending poll messages request");
             last_polled_at.store(IggyTimestamp::now().into(), ORDERING);
-            let polled_messages = client
-                .read()
-                .await
-                .poll_messages(
+            let polled_messages = if let Some(subscription) = subscription {
+                trace!("Receiving messages pushed by the subscription");
+                Self::receive_pushed_messages(
+                    &subscription,
+                    &client,
                     &stream_id,
                     &topic_id,
                     partition_id,
@@ -627,10 +707,37 @@ impl IggyConsumer {
                     &polling_strategy,
                     count,
                     auto_commit_after_polling,
+                    &options,
                 )
-                .await;
+                .await
+            } else {
+                trace!("Sending poll messages request");
+                client
+                    .read()
+                    .await
+                    .poll_messages(
+                        &stream_id,
+                        &topic_id,
+                        partition_id,
+                        &consumer,
+                        &polling_strategy,
+                        count,
+                        auto_commit_after_polling,
+                        &options,
+                    )
+                    .await
+            };
 
             if let Ok(mut polled_messages) = polled_messages {
+                let generation_id = polled_messages.generation_id;
+                if current_generation_id.swap(generation_id, ORDERING) != generation_id {
+                    // The consumer group has been rebalanced, so the offsets of the partitions which might have been assigned
+                    // to the other members can't be stored anymore, they would be rejected by the server anyway.
+                    trace!("Consumer group generation has changed to: {generation_id}, topic: {topic_id}, stream: {stream_id}, consumer: {consumer}");
+                    last_consumed_offset.clear();
+                    last_stored_offset.clear();
+                }
+
                 if polled_messages.messages.is_empty() {
                     return Ok(polled_messages);
                 }
@@ -655,6 +762,7 @@ impl IggyConsumer {
                         return Ok(PolledMessages {
                             messages: EMPTY_MESSAGES,
                             current_offset: polled_messages.current_offset,
+                            generation_id,
                             partition_id,
                         });
                     }
@@ -697,6 +805,7 @@ impl IggyConsumer {
                                 &topic_id,
                                 Some(partition_id),
                                 consumed_offset,
+                                Self::get_generation_id(&current_generation_id),
                             )
                             .await?;
                         if let Some(stored_offset_entry) = last_stored_offset.get(&partition_id) {
@@ -710,6 +819,7 @@ impl IggyConsumer {
                     return Ok(PolledMessages {
                         messages: EMPTY_MESSAGES,
                         current_offset: polled_messages.current_offset,
+                        generation_id,
                         partition_id,
                     });
                 }
@@ -730,6 +840,94 @@ impl IggyConsumer {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn receive_pushed_messages(
+        subscription: &ConsumerSubscription,
+        client: &IggySharedMut<Box<dyn Client>>,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        polling_strategy: &PollingStrategy,
+        count: u32,
+        auto_commit_after_polling: bool,
+        options: &PollMessagesOptions,
+    ) -> Result<PolledMessages, IggyError> {
+        let mut active = subscription.active.lock().await;
+        if active.is_none() {
+            // Storing the offset on polling maps to the delivery ack, while the other modes are handled by the consumer itself.
+            let ack_mode = if auto_commit_after_polling {
+                AckMode::Delivered
+            } else {
+                AckMode::None
+            };
+            let opened = client
+                .read()
+                .await
+                .subscribe_messages(
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    consumer,
+                    polling_strategy,
+                    count,
+                    options,
+                    &SubscriptionOptions::default()
+                        .ack_mode(ack_mode)
+                        .credit(subscription.credit_kind, subscription.credit),
+                )
+                .await?;
+            info!("Subscribed to messages for topic: {topic_id}, stream: {stream_id}, consumer: {consumer}");
+            active.replace(ActiveSubscription {
+                subscription: opened,
+                consumed_credit: 0,
+            });
+        }
+
+        let Some(current) = active.as_mut() else {
+            return Err(IggyError::NotConnected);
+        };
+
+        let result = if current.consumed_credit > 0 {
+            current
+                .subscription
+                .grant_credit(current.consumed_credit)
+                .await
+        } else {
+            Ok(())
+        };
+        let result = match result {
+            Ok(()) => {
+                current.consumed_credit = 0;
+                current.subscription.next().await
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(Some(polled_messages)) => {
+                current.consumed_credit = match subscription.credit_kind {
+                    CreditKind::Messages => polled_messages.messages.len() as u64,
+                    CreditKind::Bytes => polled_messages
+                        .messages
+                        .iter()
+                        .map(|message| message.get_size_bytes().as_bytes_u64())
+                        .sum(),
+                };
+                Ok(polled_messages)
+            }
+            Ok(None) => {
+                warn!("Subscription to messages for topic: {topic_id}, stream: {stream_id} has been closed by the server.");
+                active.take();
+                Err(IggyError::Disconnected)
+            }
+            Err(error) => {
+                active.take();
+                Err(error)
+            }
+        }
+    }
+
     async fn wait_before_polling(interval: u64, last_sent_at: u64) {
         if interval == 0 {
             return;
@@ -783,7 +981,13 @@ impl IggyConsumer {
 
             info!("Creating consumer group: {consumer_group_id} for topic: {topic_id}, stream: {stream_id}");
             client
-                .create_consumer_group(&stream_id, &topic_id, &name, id)
+                .create_consumer_group(
+                    &stream_id,
+                    &topic_id,
+                    &name,
+                    id,
+                    AssignmentStrategy::default(),
+                )
                 .await?;
         }
 
@@ -972,6 +1176,8 @@ pub struct IggyConsumerBuilder {
     init_retries: Option<u32>,
     init_retry_interval: IggyDuration,
     allow_replay: bool,
+    options: PollMessagesOptions,
+    subscription: Option<(CreditKind, u64)>,
 }
 
 impl IggyConsumerBuilder {
@@ -1007,6 +1213,8 @@ impl IggyConsumerBuilder {
             init_retries: None,
             init_retry_interval: IggyDuration::ONE_SECOND,
             allow_replay: false,
+            options: PollMessagesOptions::default(),
+            subscription: None,
         }
     }
 
@@ -1144,6 +1352,46 @@ impl IggyConsumerBuilder {
         }
     }
 
+    /// Polls only the messages sent outside of any transaction or as a part of the committed one.
+    /// By default, all the messages are polled, including the ones sent as a part of the open or aborted transactions.
+    pub fn read_committed(self) -> Self {
+        Self {
+            options: self.options.read_committed(),
+            ..self
+        }
+    }
+
+    /// Polls only the messages matching the filter over their headers, which is evaluated by the server.
+    /// The offsets of the skipped messages are committed as well, when the auto-commit happens on polling the messages.
+    pub fn filter(self, filter: HeaderFilter) -> Self {
+        Self {
+            options: self.options.filter(filter),
+            ..self
+        }
+    }
+
+    /// Lets the server hold each poll request until enough messages are available or the maximum wait time expires,
+    /// instead of returning the empty batch right away. The polling interval is cleared, as it's no longer needed.
+    pub fn wait(self, wait: PollingWait) -> Self {
+        Self {
+            options: self.options.wait(wait),
+            polling_interval: None,
+            ..self
+        }
+    }
+
+    /// Receives the messages pushed by the server over the subscription instead of polling them, which is available only for the binary transports.
+    /// The server keeps at most the specified credit, counted either in messages or bytes, in flight,
+    /// and the credit of each batch is granted back once the consumer asks for the next one.
+    /// The polling interval is cleared, as it's no longer needed.
+    pub fn subscription(self, credit_kind: CreditKind, credit: u64) -> Self {
+        Self {
+            subscription: Some((credit_kind, credit)),
+            polling_interval: None,
+            ..self
+        }
+    }
+
     /// Builds the consumer.
     ///
     /// Note: After building the consumer, `init()` must be invoked before producing messages.
@@ -1166,6 +1414,8 @@ impl IggyConsumerBuilder {
             self.init_retries,
             self.init_retry_interval,
             self.allow_replay,
+            self.options,
+            self.subscription,
         )
     }
 }
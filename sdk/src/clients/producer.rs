@@ -22,8 +22,9 @@ use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::locking::{IggySharedMut, IggySharedMutFn};
-use crate::messages::send_messages::{Message, Partitioning};
+use crate::messages::send_messages::{Message, Partitioning, PartitioningKind, ProducerSequence};
 use crate::partitioner::Partitioner;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::crypto::EncryptorKind;
 use crate::utils::duration::IggyDuration;
 use crate::utils::expiry::IggyExpiry;
@@ -31,9 +32,11 @@ use crate::utils::timestamp::IggyTimestamp;
 use crate::utils::topic_size::MaxTopicSize;
 use bytes::Bytes;
 use futures_util::StreamExt;
-use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tokio::time::{sleep, Interval};
 use tracing::{error, info, trace, warn};
 
@@ -67,6 +70,10 @@ pub struct IggyProducer {
     last_sent_at: Arc<AtomicU64>,
     send_retries_count: Option<u32>,
     send_retries_interval: Option<IggyDuration>,
+    idempotent: bool,
+    producer_id: Option<u64>,
+    sequences: Arc<Mutex<HashMap<u32, u64>>>,
+    next_partition_id: Arc<AtomicU32>,
 }
 
 impl IggyProducer {
@@ -90,6 +97,7 @@ impl IggyProducer {
         topic_max_size: MaxTopicSize,
         send_retries_count: Option<u32>,
         send_retries_interval: Option<IggyDuration>,
+        idempotent: bool,
     ) -> Self {
         Self {
             initialized: false,
@@ -115,6 +123,10 @@ impl IggyProducer {
             last_sent_at: Arc::new(AtomicU64::new(0)),
             send_retries_count,
             send_retries_interval,
+            idempotent,
+            producer_id: None,
+            sequences: Arc::new(Mutex::new(HashMap::new())),
+            next_partition_id: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -154,7 +166,7 @@ impl IggyProducer {
                 IdKind::String => (self.stream_id.get_string_value()?, None),
             };
             info!("Creating stream: {name}");
-            client.create_stream(&name, id).await?;
+            client.create_stream(&name, id, None).await?;
         }
 
         if client.get_topic(&stream_id, &topic_id).await?.is_none() {
@@ -184,10 +196,21 @@ impl IggyProducer {
                     id,
                     self.topic_message_expiry,
                     self.topic_max_size,
+                    CleanupPolicy::default(),
                 )
                 .await?;
         }
 
+        if self.idempotent {
+            if let Some(topic) = client.get_topic(&stream_id, &topic_id).await? {
+                self.topic_partitions_count = topic.partitions_count;
+            }
+
+            let producer_id = client.init_producer().await?;
+            info!("Initialized idempotent producer with ID: {producer_id} for stream: {stream_id} and topic: {topic_id}.");
+            self.producer_id = Some(producer_id);
+        }
+
         self.initialized = true;
         info!("Producer has been initialized for stream: {stream_id} and topic: {topic_id}.");
         Ok(())
@@ -399,18 +422,87 @@ impl IggyProducer {
         topic: &Identifier,
         partitioning: &Arc<Partitioning>,
         messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        let Some(producer_id) = self.producer_id else {
+            return self
+                .try_send_batch(stream, topic, partitioning, None, messages)
+                .await;
+        };
+
+        // The batches are sent one at a time, so the same sequence number is never assigned to the different batches,
+        // and the sequence number is incremented only once the batch has been stored by the server.
+        let mut sequences = self.sequences.lock().await;
+        let partition_id = self.get_idempotent_partition_id(partitioning)?;
+        let sequence = sequences.get(&partition_id).copied().unwrap_or_default() + 1;
+        let producer_sequence = ProducerSequence {
+            producer_id,
+            sequence,
+        };
+        let result = self
+            .try_send_batch(
+                stream,
+                topic,
+                partitioning,
+                Some((partition_id, producer_sequence)),
+                messages,
+            )
+            .await;
+        let sequence = match result {
+            Ok(()) => sequence,
+            // The producer state has expired on the server together with its messages, so the sequence starts over.
+            Err(IggyError::UnknownProducer(_, _)) if sequence > 1 => {
+                warn!("Producer with ID: {producer_id} is unknown for partition: {partition_id}, resetting its sequence number.");
+                let producer_sequence = ProducerSequence {
+                    producer_id,
+                    sequence: 1,
+                };
+                self.try_send_batch(
+                    stream,
+                    topic,
+                    partitioning,
+                    Some((partition_id, producer_sequence)),
+                    messages,
+                )
+                .await?;
+                1
+            }
+            Err(error) => return Err(error),
+        };
+        sequences.insert(partition_id, sequence);
+        Ok(())
+    }
+
+    async fn try_send_batch(
+        &self,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: &Arc<Partitioning>,
+        idempotence: Option<(u32, ProducerSequence)>,
+        messages: &mut [Message],
     ) -> Result<(), IggyError> {
         let client = self.client.read().await;
         let Some(max_retries) = self.send_retries_count else {
-            return client
-                .send_messages(stream, topic, partitioning, messages)
-                .await;
+            return Self::send_batch(
+                &**client,
+                stream,
+                topic,
+                partitioning,
+                idempotence,
+                messages,
+            )
+            .await;
         };
 
         if max_retries == 0 {
-            return client
-                .send_messages(stream, topic, partitioning, messages)
-                .await;
+            return Self::send_batch(
+                &**client,
+                stream,
+                topic,
+                partitioning,
+                idempotence,
+                messages,
+            )
+            .await;
         }
 
         let mut timer = if let Some(interval) = self.send_retries_interval {
@@ -428,12 +520,58 @@ impl IggyProducer {
             stream,
             topic,
             partitioning,
+            idempotence,
             messages,
             &mut timer,
         )
         .await
     }
 
+    async fn send_batch(
+        client: &dyn Client,
+        stream: &Identifier,
+        topic: &Identifier,
+        partitioning: &Partitioning,
+        idempotence: Option<(u32, ProducerSequence)>,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        match idempotence {
+            Some((partition_id, producer_sequence)) => {
+                client
+                    .send_idempotent_messages(
+                        stream,
+                        topic,
+                        partition_id,
+                        &producer_sequence,
+                        messages,
+                    )
+                    .await
+            }
+            None => {
+                client
+                    .send_messages(stream, topic, partitioning, messages)
+                    .await
+            }
+        }
+    }
+
+    /// Resolves the partition on the client side, as the sequence numbers are tracked separately for each partition.
+    fn get_idempotent_partition_id(&self, partitioning: &Partitioning) -> Result<u32, IggyError> {
+        match partitioning.kind {
+            PartitioningKind::PartitionId => partitioning
+                .value
+                .as_slice()
+                .try_into()
+                .map(u32::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding),
+            PartitioningKind::Balanced => {
+                let partitions_count = self.topic_partitions_count.max(1);
+                Ok(self.next_partition_id.fetch_add(1, ORDERING) % partitions_count + 1)
+            }
+            PartitioningKind::MessagesKey => Err(IggyError::InvalidIdempotentPartitioning),
+        }
+    }
+
     async fn wait_until_connected(
         &self,
         max_retries: u32,
@@ -468,21 +606,29 @@ impl IggyProducer {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn send_with_retries(
         &self,
         max_retries: u32,
         stream: &Identifier,
         topic: &Identifier,
         partitioning: &Arc<Partitioning>,
+        idempotence: Option<(u32, ProducerSequence)>,
         messages: &mut [Message],
         timer: &mut Option<Interval>,
     ) -> Result<(), IggyError> {
         let client = self.client.read().await;
         let mut retries = 0;
         loop {
-            match client
-                .send_messages(stream, topic, partitioning, messages)
-                .await
+            match Self::send_batch(
+                &**client,
+                stream,
+                topic,
+                partitioning,
+                idempotence,
+                messages,
+            )
+            .await
             {
                 Ok(_) => return Ok(()),
                 Err(error) => {
@@ -554,6 +700,7 @@ pub struct IggyProducerBuilder {
     send_retries_interval: Option<IggyDuration>,
     topic_message_expiry: IggyExpiry,
     topic_max_size: MaxTopicSize,
+    idempotent: bool,
 }
 
 impl IggyProducerBuilder {
@@ -586,6 +733,7 @@ impl IggyProducerBuilder {
             topic_max_size: MaxTopicSize::ServerDefault,
             send_retries_count: Some(3),
             send_retries_interval: Some(IggyDuration::ONE_SECOND),
+            idempotent: false,
         }
     }
 
@@ -736,6 +884,24 @@ impl IggyProducerBuilder {
         }
     }
 
+    /// Enables the idempotence, so the batches retried by the producer (e.g. after reconnecting to the server) are never appended twice.
+    /// The producer ID is assigned by the server on `init()` and each batch carries the sequence number tracked for its partition.
+    /// The partition is resolved by the producer, thus the messages key partitioning is not supported unless a custom partitioner is set.
+    pub fn enable_idempotence(self) -> Self {
+        Self {
+            idempotent: true,
+            ..self
+        }
+    }
+
+    /// Disables the idempotence, which is the default.
+    pub fn disable_idempotence(self) -> Self {
+        Self {
+            idempotent: false,
+            ..self
+        }
+    }
+
     /// Builds the producer.
     ///
     /// Note: After building the producer, `init()` must be invoked before producing messages.
@@ -759,6 +925,7 @@ impl IggyProducerBuilder {
             self.topic_max_size,
             self.send_retries_count,
             self.send_retries_interval,
+            self.idempotent,
         )
     }
 }
@@ -0,0 +1,238 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, APPEND_ENTRIES_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::fmt::Display;
+
+const HEADER_SIZE: usize = 8 + 4 + 8 + 8 + 8;
+
+/// `AppendEntries` command is used by the cluster leader to replicate the state log entries to the followers.
+/// When there are no entries to replicate, it serves as a heartbeat which prevents the followers from starting the election.
+/// It has additional payload:
+/// - `term` - election term of the leader.
+/// - `leader_id` - unique ID of the leader node.
+/// - `prev_log_index` - index of the state log entry immediately preceding the new ones.
+/// - `prev_log_term` - term of the state log entry immediately preceding the new ones.
+/// - `leader_commit` - number of the state log entries committed by the leader.
+/// - `entries` - serialized state log entries to append, might be empty.
+#[derive(Debug, Default, PartialEq)]
+pub struct AppendEntries {
+    /// Election term of the leader.
+    pub term: u64,
+    /// Unique ID of the leader node.
+    pub leader_id: u32,
+    /// Index of the state log entry immediately preceding the new ones.
+    pub prev_log_index: u64,
+    /// Term of the state log entry immediately preceding the new ones.
+    pub prev_log_term: u64,
+    /// Number of the state log entries committed by the leader.
+    pub leader_commit: u64,
+    /// Serialized state log entries to append, might be empty.
+    pub entries: Vec<Bytes>,
+}
+
+impl Command for AppendEntries {
+    fn code(&self) -> u32 {
+        APPEND_ENTRIES_CODE
+    }
+}
+
+impl Validatable<IggyError> for AppendEntries {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.term == 0 || self.leader_id == 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        if self.entries.iter().any(|entry| entry.is_empty()) {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for AppendEntries {
+    fn to_bytes(&self) -> Bytes {
+        let entries_size = self
+            .entries
+            .iter()
+            .map(|entry| 4 + entry.len())
+            .sum::<usize>();
+        let mut bytes = BytesMut::with_capacity(HEADER_SIZE + entries_size);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.leader_id);
+        bytes.put_u64_le(self.prev_log_index);
+        bytes.put_u64_le(self.prev_log_term);
+        bytes.put_u64_le(self.leader_commit);
+        for entry in &self.entries {
+            bytes.put_u32_le(entry.len() as u32);
+            bytes.put_slice(entry);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<AppendEntries, IggyError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let term = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let leader_id = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let prev_log_index = u64::from_le_bytes(
+            bytes[12..20]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let prev_log_term = u64::from_le_bytes(
+            bytes[20..28]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let leader_commit = u64::from_le_bytes(
+            bytes[28..36]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut entries = Vec::new();
+        let mut position = HEADER_SIZE;
+        while position < bytes.len() {
+            if bytes.len() < position + 4 {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            let length = u32::from_le_bytes(
+                bytes[position..position + 4]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            ) as usize;
+            position += 4;
+            if bytes.len() < position + length {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            entries.push(bytes.slice(position..position + length));
+            position += length;
+        }
+
+        let command = AppendEntries {
+            term,
+            leader_id,
+            prev_log_index,
+            prev_log_term,
+            leader_commit,
+            entries,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for AppendEntries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}",
+            self.term,
+            self.leader_id,
+            self.prev_log_index,
+            self.prev_log_term,
+            self.leader_commit,
+            self.entries.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = AppendEntries {
+            term: 2,
+            leader_id: 1,
+            prev_log_index: 4,
+            prev_log_term: 1,
+            leader_commit: 5,
+            entries: vec![Bytes::from_static(&[1, 2, 3]), Bytes::from_static(&[4])],
+        };
+
+        let bytes = command.to_bytes();
+        let term = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let leader_id = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let prev_log_index = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+        let prev_log_term = u64::from_le_bytes(bytes[20..28].try_into().unwrap());
+        let leader_commit = u64::from_le_bytes(bytes[28..36].try_into().unwrap());
+        let first_entry_length = u32::from_le_bytes(bytes[36..40].try_into().unwrap());
+        let first_entry = &bytes[40..43];
+        let second_entry_length = u32::from_le_bytes(bytes[43..47].try_into().unwrap());
+        let second_entry = &bytes[47..48];
+
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(term, command.term);
+        assert_eq!(leader_id, command.leader_id);
+        assert_eq!(prev_log_index, command.prev_log_index);
+        assert_eq!(prev_log_term, command.prev_log_term);
+        assert_eq!(leader_commit, command.leader_commit);
+        assert_eq!(first_entry_length, 3);
+        assert_eq!(first_entry, &command.entries[0][..]);
+        assert_eq!(second_entry_length, 1);
+        assert_eq!(second_entry, &command.entries[1][..]);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let term = 2u64;
+        let leader_id = 1u32;
+        let prev_log_index = 4u64;
+        let prev_log_term = 1u64;
+        let leader_commit = 5u64;
+        let entry = [1u8, 2, 3];
+        let mut bytes = BytesMut::with_capacity(HEADER_SIZE + 4 + entry.len());
+        bytes.put_u64_le(term);
+        bytes.put_u32_le(leader_id);
+        bytes.put_u64_le(prev_log_index);
+        bytes.put_u64_le(prev_log_term);
+        bytes.put_u64_le(leader_commit);
+        bytes.put_u32_le(entry.len() as u32);
+        bytes.put_slice(&entry);
+
+        let command = AppendEntries::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.term, term);
+        assert_eq!(command.leader_id, leader_id);
+        assert_eq!(command.prev_log_index, prev_log_index);
+        assert_eq!(command.prev_log_term, prev_log_term);
+        assert_eq!(command.leader_commit, leader_commit);
+        assert_eq!(command.entries.len(), 1);
+        assert_eq!(&command.entries[0][..], &entry);
+    }
+}
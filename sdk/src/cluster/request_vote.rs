@@ -0,0 +1,162 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, REQUEST_VOTE_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::fmt::Display;
+
+/// `RequestVote` command is used by the candidate node to request the vote of the other cluster nodes during the leader election.
+/// It has additional payload:
+/// - `term` - election term of the candidate.
+/// - `candidate_id` - unique ID of the candidate node.
+/// - `last_log_index` - index of the last entry in the state log of the candidate.
+/// - `last_log_term` - term of the last entry in the state log of the candidate.
+#[derive(Debug, Default, PartialEq)]
+pub struct RequestVote {
+    /// Election term of the candidate.
+    pub term: u64,
+    /// Unique ID of the candidate node.
+    pub candidate_id: u32,
+    /// Index of the last entry in the state log of the candidate.
+    pub last_log_index: u64,
+    /// Term of the last entry in the state log of the candidate.
+    pub last_log_term: u64,
+}
+
+impl Command for RequestVote {
+    fn code(&self) -> u32 {
+        REQUEST_VOTE_CODE
+    }
+}
+
+impl Validatable<IggyError> for RequestVote {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.term == 0 || self.candidate_id == 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for RequestVote {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(28);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.candidate_id);
+        bytes.put_u64_le(self.last_log_index);
+        bytes.put_u64_le(self.last_log_term);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<RequestVote, IggyError> {
+        if bytes.len() != 28 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let term = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let candidate_id = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let last_log_index = u64::from_le_bytes(
+            bytes[12..20]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let last_log_term = u64::from_le_bytes(
+            bytes[20..28]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let command = RequestVote {
+            term,
+            candidate_id,
+            last_log_index,
+            last_log_term,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for RequestVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}",
+            self.term, self.candidate_id, self.last_log_index, self.last_log_term
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = RequestVote {
+            term: 2,
+            candidate_id: 3,
+            last_log_index: 10,
+            last_log_term: 1,
+        };
+
+        let bytes = command.to_bytes();
+        let term = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let candidate_id = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let last_log_index = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+        let last_log_term = u64::from_le_bytes(bytes[20..28].try_into().unwrap());
+
+        assert!(!bytes.is_empty());
+        assert_eq!(term, command.term);
+        assert_eq!(candidate_id, command.candidate_id);
+        assert_eq!(last_log_index, command.last_log_index);
+        assert_eq!(last_log_term, command.last_log_term);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let term = 2u64;
+        let candidate_id = 3u32;
+        let last_log_index = 10u64;
+        let last_log_term = 1u64;
+        let mut bytes = BytesMut::with_capacity(28);
+        bytes.put_u64_le(term);
+        bytes.put_u32_le(candidate_id);
+        bytes.put_u64_le(last_log_index);
+        bytes.put_u64_le(last_log_term);
+
+        let command = RequestVote::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.term, term);
+        assert_eq!(command.candidate_id, candidate_id);
+        assert_eq!(command.last_log_index, last_log_index);
+        assert_eq!(command.last_log_term, last_log_term);
+    }
+}
@@ -27,10 +27,14 @@ pub trait Command: BytesSerializable + Validatable<IggyError> + Send + Sync + Di
 
 pub const PING: &str = "ping";
 pub const PING_CODE: u32 = 1;
+pub const HANDSHAKE: &str = "handshake";
+pub const HANDSHAKE_CODE: u32 = 2;
 pub const GET_STATS: &str = "stats";
 pub const GET_STATS_CODE: u32 = 10;
 pub const GET_SNAPSHOT_FILE: &str = "snapshot";
 pub const GET_SNAPSHOT_FILE_CODE: u32 = 11;
+pub const GET_CLUSTER_METADATA: &str = "cluster.metadata";
+pub const GET_CLUSTER_METADATA_CODE: u32 = 12;
 pub const GET_ME: &str = "me";
 pub const GET_ME_CODE: u32 = 20;
 pub const GET_CLIENT: &str = "client.get";
@@ -55,6 +59,8 @@ pub const LOGIN_USER: &str = "user.login";
 pub const LOGIN_USER_CODE: u32 = 38;
 pub const LOGOUT_USER: &str = "user.logout";
 pub const LOGOUT_USER_CODE: u32 = 39;
+pub const LOGIN_WITH_OIDC_TOKEN: &str = "user.login_oidc";
+pub const LOGIN_WITH_OIDC_TOKEN_CODE: u32 = 40;
 pub const GET_PERSONAL_ACCESS_TOKENS: &str = "personal_access_token.list";
 pub const GET_PERSONAL_ACCESS_TOKENS_CODE: u32 = 41;
 pub const CREATE_PERSONAL_ACCESS_TOKEN: &str = "personal_access_token.create";
@@ -69,12 +75,28 @@ pub const SEND_MESSAGES: &str = "message.send";
 pub const SEND_MESSAGES_CODE: u32 = 101;
 pub const FLUSH_UNSAVED_BUFFER: &str = "message.flush_unsaved_buffer";
 pub const FLUSH_UNSAVED_BUFFER_CODE: u32 = 102;
+pub const NACK_MESSAGE: &str = "message.nack";
+pub const NACK_MESSAGE_CODE: u32 = 103;
+pub const POLL_ASSIGNED_MESSAGES: &str = "message.poll_assigned";
+pub const POLL_ASSIGNED_MESSAGES_CODE: u32 = 104;
+pub const INIT_PRODUCER: &str = "message.init_producer";
+pub const INIT_PRODUCER_CODE: u32 = 105;
+pub const SEND_IDEMPOTENT_MESSAGES: &str = "message.send_idempotent";
+pub const SEND_IDEMPOTENT_MESSAGES_CODE: u32 = 106;
+pub const SUBSCRIBE_MESSAGES: &str = "message.subscribe";
+pub const SUBSCRIBE_MESSAGES_CODE: u32 = 107;
+pub const GRANT_CREDIT: &str = "message.grant_credit";
+pub const GRANT_CREDIT_CODE: u32 = 108;
+pub const ACK_MESSAGES: &str = "message.ack";
+pub const ACK_MESSAGES_CODE: u32 = 109;
 pub const GET_CONSUMER_OFFSET: &str = "consumer_offset.get";
 pub const GET_CONSUMER_OFFSET_CODE: u32 = 120;
 pub const STORE_CONSUMER_OFFSET: &str = "consumer_offset.store";
 pub const STORE_CONSUMER_OFFSET_CODE: u32 = 121;
 pub const DELETE_CONSUMER_OFFSET: &str = "consumer_offset.delete";
 pub const DELETE_CONSUMER_OFFSET_CODE: u32 = 122;
+pub const GET_CONSUMER_LAG: &str = "consumer_offset.lag";
+pub const GET_CONSUMER_LAG_CODE: u32 = 123;
 pub const GET_STREAM: &str = "stream.get";
 pub const GET_STREAM_CODE: u32 = 200;
 pub const GET_STREAMS: &str = "stream.list";
@@ -105,6 +127,8 @@ pub const DELETE_PARTITIONS: &str = "partition.delete";
 pub const DELETE_PARTITIONS_CODE: u32 = 403;
 pub const DELETE_SEGMENTS: &str = "segment.delete";
 pub const DELETE_SEGMENTS_CODE: u32 = 503;
+pub const RESTORE_SEGMENTS: &str = "segment.restore";
+pub const RESTORE_SEGMENTS_CODE: u32 = 504;
 pub const GET_CONSUMER_GROUP: &str = "consumer_group.get";
 pub const GET_CONSUMER_GROUP_CODE: u32 = 600;
 pub const GET_CONSUMER_GROUPS: &str = "consumer_group.list";
@@ -117,11 +141,30 @@ pub const JOIN_CONSUMER_GROUP: &str = "consumer_group.join";
 pub const JOIN_CONSUMER_GROUP_CODE: u32 = 604;
 pub const LEAVE_CONSUMER_GROUP: &str = "consumer_group.leave";
 pub const LEAVE_CONSUMER_GROUP_CODE: u32 = 605;
+pub const FETCH_REPLICA_MESSAGES: &str = "replica.messages.fetch";
+pub const FETCH_REPLICA_MESSAGES_CODE: u32 = 700;
+pub const REQUEST_VOTE: &str = "cluster.vote.request";
+pub const REQUEST_VOTE_CODE: u32 = 800;
+pub const APPEND_ENTRIES: &str = "cluster.entries.append";
+pub const APPEND_ENTRIES_CODE: u32 = 801;
+pub const BEGIN_TRANSACTION: &str = "transaction.begin";
+pub const BEGIN_TRANSACTION_CODE: u32 = 900;
+pub const COMMIT_TRANSACTION: &str = "transaction.commit";
+pub const COMMIT_TRANSACTION_CODE: u32 = 901;
+pub const ABORT_TRANSACTION: &str = "transaction.abort";
+pub const ABORT_TRANSACTION_CODE: u32 = 902;
+
+/// The flag set in the command code of a request followed by its `u64` request ID.
+/// The response to such a request carries the same ID, so many requests can be in flight
+/// over a single connection and their responses can arrive in any order.
+pub const REQUEST_ID_FLAG: u32 = 1 << 31;
 
 pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
     match code {
         PING_CODE => Ok(PING),
+        HANDSHAKE_CODE => Ok(HANDSHAKE),
         GET_STATS_CODE => Ok(GET_STATS),
+        GET_CLUSTER_METADATA_CODE => Ok(GET_CLUSTER_METADATA),
         GET_ME_CODE => Ok(GET_ME),
         GET_CLIENT_CODE => Ok(GET_CLIENT),
         GET_CLIENTS_CODE => Ok(GET_CLIENTS),
@@ -134,6 +177,7 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         CHANGE_PASSWORD_CODE => Ok(CHANGE_PASSWORD),
         LOGIN_USER_CODE => Ok(LOGIN_USER),
         LOGOUT_USER_CODE => Ok(LOGOUT_USER),
+        LOGIN_WITH_OIDC_TOKEN_CODE => Ok(LOGIN_WITH_OIDC_TOKEN),
         GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(GET_PERSONAL_ACCESS_TOKENS),
         CREATE_PERSONAL_ACCESS_TOKEN_CODE => Ok(CREATE_PERSONAL_ACCESS_TOKEN),
         DELETE_PERSONAL_ACCESS_TOKEN_CODE => Ok(DELETE_PERSONAL_ACCESS_TOKEN),
@@ -141,8 +185,16 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         SEND_MESSAGES_CODE => Ok(SEND_MESSAGES),
         POLL_MESSAGES_CODE => Ok(POLL_MESSAGES),
         FLUSH_UNSAVED_BUFFER_CODE => Ok(FLUSH_UNSAVED_BUFFER),
+        NACK_MESSAGE_CODE => Ok(NACK_MESSAGE),
+        POLL_ASSIGNED_MESSAGES_CODE => Ok(POLL_ASSIGNED_MESSAGES),
+        INIT_PRODUCER_CODE => Ok(INIT_PRODUCER),
+        SEND_IDEMPOTENT_MESSAGES_CODE => Ok(SEND_IDEMPOTENT_MESSAGES),
+        SUBSCRIBE_MESSAGES_CODE => Ok(SUBSCRIBE_MESSAGES),
+        GRANT_CREDIT_CODE => Ok(GRANT_CREDIT),
+        ACK_MESSAGES_CODE => Ok(ACK_MESSAGES),
         STORE_CONSUMER_OFFSET_CODE => Ok(STORE_CONSUMER_OFFSET),
         GET_CONSUMER_OFFSET_CODE => Ok(GET_CONSUMER_OFFSET),
+        GET_CONSUMER_LAG_CODE => Ok(GET_CONSUMER_LAG),
         GET_STREAM_CODE => Ok(GET_STREAM),
         GET_STREAMS_CODE => Ok(GET_STREAMS),
         CREATE_STREAM_CODE => Ok(CREATE_STREAM),
@@ -157,6 +209,8 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         PURGE_TOPIC_CODE => Ok(PURGE_TOPIC),
         CREATE_PARTITIONS_CODE => Ok(CREATE_PARTITIONS),
         DELETE_PARTITIONS_CODE => Ok(DELETE_PARTITIONS),
+        DELETE_SEGMENTS_CODE => Ok(DELETE_SEGMENTS),
+        RESTORE_SEGMENTS_CODE => Ok(RESTORE_SEGMENTS),
         GET_CONSUMER_GROUP_CODE => Ok(GET_CONSUMER_GROUP),
         GET_CONSUMER_GROUPS_CODE => Ok(GET_CONSUMER_GROUPS),
         CREATE_CONSUMER_GROUP_CODE => Ok(CREATE_CONSUMER_GROUP),
@@ -164,6 +218,12 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         JOIN_CONSUMER_GROUP_CODE => Ok(JOIN_CONSUMER_GROUP),
         LEAVE_CONSUMER_GROUP_CODE => Ok(LEAVE_CONSUMER_GROUP),
         GET_SNAPSHOT_FILE_CODE => Ok(GET_SNAPSHOT_FILE),
+        FETCH_REPLICA_MESSAGES_CODE => Ok(FETCH_REPLICA_MESSAGES),
+        REQUEST_VOTE_CODE => Ok(REQUEST_VOTE),
+        APPEND_ENTRIES_CODE => Ok(APPEND_ENTRIES),
+        BEGIN_TRANSACTION_CODE => Ok(BEGIN_TRANSACTION),
+        COMMIT_TRANSACTION_CODE => Ok(COMMIT_TRANSACTION),
+        ABORT_TRANSACTION_CODE => Ok(ABORT_TRANSACTION),
         _ => Err(IggyError::InvalidCommand),
     }
 }
@@ -22,12 +22,16 @@ use serde::{
 };
 use std::{
     fmt::{Display, Formatter},
+    io::{Read, Write},
     str::FromStr,
 };
 
 use crate::error::IggyError;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 
-// for now only those, in the future will add snappy, lz4, zstd (same as in confluent kafka) in addition to that
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+// in the future we might add snappy (same as in confluent kafka) in addition to that
 // we should consider brotli as well.
 /// Supported compression algorithms
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
@@ -37,6 +41,10 @@ pub enum CompressionAlgorithm {
     None,
     // Gzip compression algorithm
     Gzip,
+    // Zstandard compression algorithm
+    Zstd,
+    // LZ4 compression algorithm
+    Lz4,
 }
 
 impl FromStr for CompressionAlgorithm {
@@ -45,6 +53,8 @@ impl FromStr for CompressionAlgorithm {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "gzip" => Ok(CompressionAlgorithm::Gzip),
+            "zstd" => Ok(CompressionAlgorithm::Zstd),
+            "lz4" => Ok(CompressionAlgorithm::Lz4),
             "none" => Ok(CompressionAlgorithm::None),
             _ => Err(format!("Unknown compression type: {}", s)),
         }
@@ -56,6 +66,8 @@ impl CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => 1,
             CompressionAlgorithm::Gzip => 2,
+            CompressionAlgorithm::Zstd => 3,
+            CompressionAlgorithm::Lz4 => 4,
         }
     }
 
@@ -63,9 +75,47 @@ impl CompressionAlgorithm {
         match code {
             1 => Ok(CompressionAlgorithm::None),
             2 => Ok(CompressionAlgorithm::Gzip),
+            3 => Ok(CompressionAlgorithm::Zstd),
+            4 => Ok(CompressionAlgorithm::Lz4),
             _ => Err(IggyError::InvalidCommand),
         }
     }
+
+    /// Compresses the provided data, returns it unchanged for `CompressionAlgorithm::None`.
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, IggyError> {
+        match self {
+            CompressionAlgorithm::None => Ok(data.to_vec()),
+            CompressionAlgorithm::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(data)
+                    .map_err(|_| IggyError::CannotCompressData)?;
+                encoder.finish().map_err(|_| IggyError::CannotCompressData)
+            }
+            CompressionAlgorithm::Zstd => zstd::bulk::compress(data, ZSTD_COMPRESSION_LEVEL)
+                .map_err(|_| IggyError::CannotCompressData),
+            CompressionAlgorithm::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+        }
+    }
+
+    /// Decompresses the data previously compressed with the same algorithm.
+    /// The `uncompressed_size` is the expected size of the data after decompression.
+    pub fn decompress(&self, data: &[u8], uncompressed_size: usize) -> Result<Vec<u8>, IggyError> {
+        match self {
+            CompressionAlgorithm::None => Ok(data.to_vec()),
+            CompressionAlgorithm::Gzip => {
+                let mut decompressed = Vec::with_capacity(uncompressed_size);
+                GzDecoder::new(data)
+                    .read_to_end(&mut decompressed)
+                    .map_err(|_| IggyError::CannotDecompressData)?;
+                Ok(decompressed)
+            }
+            CompressionAlgorithm::Zstd => zstd::bulk::decompress(data, uncompressed_size)
+                .map_err(|_| IggyError::CannotDecompressData),
+            CompressionAlgorithm::Lz4 => lz4_flex::decompress_size_prepended(data)
+                .map_err(|_| IggyError::CannotDecompressData),
+        }
+    }
 }
 
 impl Display for CompressionAlgorithm {
@@ -73,6 +123,8 @@ impl Display for CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => write!(f, "none"),
             CompressionAlgorithm::Gzip => write!(f, "gzip"),
+            CompressionAlgorithm::Zstd => write!(f, "zstd"),
+            CompressionAlgorithm::Lz4 => write!(f, "lz4"),
         }
     }
 }
@@ -85,6 +137,8 @@ impl Serialize for CompressionAlgorithm {
         match self {
             CompressionAlgorithm::None => serializer.serialize_str("none"),
             CompressionAlgorithm::Gzip => serializer.serialize_str("gzip"),
+            CompressionAlgorithm::Zstd => serializer.serialize_str("zstd"),
+            CompressionAlgorithm::Lz4 => serializer.serialize_str("lz4"),
         }
     }
 }
@@ -94,6 +148,8 @@ impl From<CompressionAlgorithm> for String {
         match value {
             CompressionAlgorithm::None => "none".to_string(),
             CompressionAlgorithm::Gzip => "gzip".to_string(),
+            CompressionAlgorithm::Zstd => "zstd".to_string(),
+            CompressionAlgorithm::Lz4 => "lz4".to_string(),
         }
     }
 }
@@ -144,6 +200,14 @@ mod tests {
         let gzip_alg = CompressionAlgorithm::from_str("Gzip");
         assert!(gzip_alg.is_ok());
         assert_eq!(gzip_alg.unwrap(), CompressionAlgorithm::Gzip);
+
+        let zstd_alg = CompressionAlgorithm::from_str("zstd");
+        assert!(zstd_alg.is_ok());
+        assert_eq!(zstd_alg.unwrap(), CompressionAlgorithm::Zstd);
+
+        let lz4_alg = CompressionAlgorithm::from_str("LZ4");
+        assert!(lz4_alg.is_ok());
+        assert_eq!(lz4_alg.unwrap(), CompressionAlgorithm::Lz4);
     }
 
     #[test]
@@ -176,6 +240,14 @@ mod tests {
         let gzip = CompressionAlgorithm::Gzip;
         let gzip_code = gzip.as_code();
         assert_eq!(gzip_code, 2);
+
+        let zstd = CompressionAlgorithm::Zstd;
+        let zstd_code = zstd.as_code();
+        assert_eq!(zstd_code, 3);
+
+        let lz4 = CompressionAlgorithm::Lz4;
+        let lz4_code = lz4.as_code();
+        assert_eq!(lz4_code, 4);
     }
     #[test]
     fn test_from_code() {
@@ -186,6 +258,14 @@ mod tests {
         let gzip = CompressionAlgorithm::from_code(2);
         assert!(gzip.is_ok());
         assert_eq!(gzip.unwrap(), CompressionAlgorithm::Gzip);
+
+        let zstd = CompressionAlgorithm::from_code(3);
+        assert!(zstd.is_ok());
+        assert_eq!(zstd.unwrap(), CompressionAlgorithm::Zstd);
+
+        let lz4 = CompressionAlgorithm::from_code(4);
+        assert!(lz4.is_ok());
+        assert_eq!(lz4.unwrap(), CompressionAlgorithm::Lz4);
     }
     #[test]
     fn test_from_code_invalid_input() {
@@ -198,4 +278,36 @@ mod tests {
         let invalid_compression_kind = CompressionAlgorithm::from_code(255);
         assert!(invalid_compression_kind.is_err());
     }
+
+    #[test]
+    fn test_compress_and_decompress() {
+        let data = "iggy".repeat(1000).into_bytes();
+        for algorithm in [
+            CompressionAlgorithm::None,
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Lz4,
+        ] {
+            let compressed = algorithm.compress(&data).unwrap();
+            if algorithm != CompressionAlgorithm::None {
+                assert!(compressed.len() < data.len());
+            }
+            let decompressed = algorithm.decompress(&compressed, data.len()).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+
+    #[test]
+    fn test_decompress_invalid_input() {
+        let invalid_data = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert!(CompressionAlgorithm::Gzip
+            .decompress(&invalid_data, 8)
+            .is_err());
+        assert!(CompressionAlgorithm::Zstd
+            .decompress(&invalid_data, 8)
+            .is_err());
+        assert!(CompressionAlgorithm::Lz4
+            .decompress(&invalid_data, 8)
+            .is_err());
+    }
 }
@@ -17,3 +17,4 @@
  */
 
 pub mod compression_algorithm;
+pub mod wire_compression;
@@ -0,0 +1,249 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use crate::models::handshake::{ProtocolFeature, ProtocolFeatures};
+use crate::utils::byte_size::IggyByteSize;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The flag set on the request code or the response status of the frame with the compressed payload.
+pub const COMPRESSION_FLAG: u32 = 1 << 30;
+/// The default size of the payload above which it gets compressed, the smaller ones are sent as is.
+pub const DEFAULT_COMPRESSION_THRESHOLD: u64 = 1024;
+const UNCOMPRESSED_LENGTH_BYTES: usize = 4;
+const ZSTD_COMPRESSION_LEVEL: i32 = 1;
+
+/// `WireCompressionAlgorithm` represents the algorithm used to compress the frame payloads sent over the wire.
+/// It's negotiated per connection during the handshake and has nothing to do with the compression of the stored messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WireCompressionAlgorithm {
+    /// LZ4 compression, fast with the moderate ratio.
+    Lz4,
+    /// Zstandard compression, slower with the better ratio.
+    Zstd,
+}
+
+impl WireCompressionAlgorithm {
+    /// Returns the protocol feature offered during the handshake to enable the compression.
+    pub fn as_feature(&self) -> ProtocolFeature {
+        match self {
+            WireCompressionAlgorithm::Lz4 => ProtocolFeature::Lz4Compression,
+            WireCompressionAlgorithm::Zstd => ProtocolFeature::ZstdCompression,
+        }
+    }
+
+    /// Returns the algorithm enabled by the negotiated features, Zstandard is preferred if both of them are.
+    pub fn from_features(features: &ProtocolFeatures) -> Option<Self> {
+        if features.contains(ProtocolFeature::ZstdCompression) {
+            Some(WireCompressionAlgorithm::Zstd)
+        } else if features.contains(ProtocolFeature::Lz4Compression) {
+            Some(WireCompressionAlgorithm::Lz4)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the protocol features of all the supported algorithms.
+    pub fn all_features() -> ProtocolFeatures {
+        ProtocolFeatures::new(&[
+            ProtocolFeature::Lz4Compression,
+            ProtocolFeature::ZstdCompression,
+        ])
+    }
+}
+
+impl FromStr for WireCompressionAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lz4" => Ok(WireCompressionAlgorithm::Lz4),
+            "zstd" => Ok(WireCompressionAlgorithm::Zstd),
+            _ => Err(format!("Unknown wire compression algorithm: {s}")),
+        }
+    }
+}
+
+impl Display for WireCompressionAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WireCompressionAlgorithm::Lz4 => write!(f, "lz4"),
+            WireCompressionAlgorithm::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+/// `WireCompression` represents the compression of the frame payloads used by the connection.
+/// The compressed payload is prefixed with its uncompressed length `[u32 length][compressed bytes]`
+/// and the frame is marked with the `COMPRESSION_FLAG` set on its request code or response status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WireCompression {
+    algorithm: WireCompressionAlgorithm,
+    threshold: u64,
+}
+
+impl WireCompression {
+    /// Creates the compression of the payloads larger than the threshold.
+    pub fn new(algorithm: WireCompressionAlgorithm, threshold: IggyByteSize) -> Self {
+        Self {
+            algorithm,
+            threshold: threshold.as_bytes_u64(),
+        }
+    }
+
+    /// Returns the compression enabled by the negotiated features, if any.
+    pub fn negotiated(features: &ProtocolFeatures, threshold: IggyByteSize) -> Option<Self> {
+        WireCompressionAlgorithm::from_features(features)
+            .map(|algorithm| Self::new(algorithm, threshold))
+    }
+
+    pub fn algorithm(&self) -> WireCompressionAlgorithm {
+        self.algorithm
+    }
+
+    /// Compresses the payload exceeding the threshold, returns `None` if it should be sent as is,
+    /// either because it's too small or because the compression didn't make it any smaller.
+    pub fn compress(&self, payload: &[u8]) -> Option<Bytes> {
+        if (payload.len() as u64) <= self.threshold {
+            return None;
+        }
+
+        let compressed = match self.algorithm {
+            WireCompressionAlgorithm::Lz4 => lz4_flex::compress(payload),
+            WireCompressionAlgorithm::Zstd => {
+                zstd::bulk::compress(payload, ZSTD_COMPRESSION_LEVEL).ok()?
+            }
+        };
+        if compressed.len() + UNCOMPRESSED_LENGTH_BYTES >= payload.len() {
+            return None;
+        }
+
+        let mut bytes = BytesMut::with_capacity(UNCOMPRESSED_LENGTH_BYTES + compressed.len());
+        bytes.put_u32_le(payload.len() as u32);
+        bytes.put_slice(&compressed);
+        Some(bytes.freeze())
+    }
+
+    /// Decompresses the payload previously compressed with the same algorithm.
+    pub fn decompress(&self, payload: &[u8]) -> Result<Bytes, IggyError> {
+        if payload.len() < UNCOMPRESSED_LENGTH_BYTES {
+            return Err(IggyError::CannotDecompressData);
+        }
+
+        let length = u32::from_le_bytes(
+            payload[..UNCOMPRESSED_LENGTH_BYTES]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let compressed = &payload[UNCOMPRESSED_LENGTH_BYTES..];
+        let decompressed = match self.algorithm {
+            WireCompressionAlgorithm::Lz4 => lz4_flex::decompress(compressed, length)
+                .map_err(|_| IggyError::CannotDecompressData)?,
+            WireCompressionAlgorithm::Zstd => zstd::bulk::decompress(compressed, length)
+                .map_err(|_| IggyError::CannotDecompressData)?,
+        };
+        if decompressed.len() != length {
+            return Err(IggyError::CannotDecompressData);
+        }
+
+        Ok(Bytes::from(decompressed))
+    }
+}
+
+/// Compresses the frame payload if the compression is enabled and marks the code or status with the `COMPRESSION_FLAG`.
+pub fn compress_frame(
+    compression: Option<&WireCompression>,
+    code: u32,
+    payload: Bytes,
+) -> (u32, Bytes) {
+    match compression.and_then(|compression| compression.compress(&payload)) {
+        Some(compressed) => (code | COMPRESSION_FLAG, compressed),
+        None => (code, payload),
+    }
+}
+
+/// Decompresses the frame payload if the code or status is marked with the `COMPRESSION_FLAG`
+/// and returns the code or status without the flag.
+pub fn decompress_frame(
+    compression: Option<&WireCompression>,
+    code: u32,
+    payload: Bytes,
+) -> Result<(u32, Bytes), IggyError> {
+    if code & COMPRESSION_FLAG == 0 {
+        return Ok((code, payload));
+    }
+
+    let Some(compression) = compression else {
+        return Err(IggyError::CannotDecompressData);
+    };
+    Ok((code & !COMPRESSION_FLAG, compression.decompress(&payload)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_exceeding_threshold_should_be_compressed_and_decompressed() {
+        let payload = Bytes::from("message ".repeat(1000));
+        for algorithm in [
+            WireCompressionAlgorithm::Lz4,
+            WireCompressionAlgorithm::Zstd,
+        ] {
+            let compression = WireCompression::new(algorithm, IggyByteSize::from(1024));
+            let (code, compressed) = compress_frame(Some(&compression), 101, payload.clone());
+            assert_eq!(code, 101 | COMPRESSION_FLAG);
+            assert!(compressed.len() < payload.len());
+
+            let (code, decompressed) =
+                decompress_frame(Some(&compression), code, compressed).unwrap();
+            assert_eq!(code, 101);
+            assert_eq!(decompressed, payload);
+        }
+    }
+
+    #[test]
+    fn payload_below_threshold_or_without_negotiated_compression_should_be_sent_as_is() {
+        let payload = Bytes::from("message ".repeat(10));
+        let compression =
+            WireCompression::new(WireCompressionAlgorithm::Lz4, IggyByteSize::from(1024));
+        assert_eq!(
+            compress_frame(Some(&compression), 101, payload.clone()),
+            (101, payload.clone())
+        );
+        assert_eq!(compress_frame(None, 101, payload.clone()), (101, payload));
+        assert!(decompress_frame(None, 101 | COMPRESSION_FLAG, Bytes::new()).is_err());
+    }
+
+    #[test]
+    fn negotiated_features_should_prefer_zstd() {
+        let features = WireCompressionAlgorithm::all_features();
+        assert_eq!(
+            WireCompressionAlgorithm::from_features(&features),
+            Some(WireCompressionAlgorithm::Zstd)
+        );
+        assert_eq!(
+            WireCompressionAlgorithm::from_features(&ProtocolFeatures::default()),
+            None
+        );
+    }
+}
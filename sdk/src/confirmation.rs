@@ -25,6 +25,10 @@ pub enum Confirmation {
     #[default]
     Wait,
     NoWait,
+    /// Waits for the file operation to complete and for the majority of the topic replicas
+    /// (including the leader) to acknowledge the appended messages.
+    /// Behaves like `Wait` for the topics with the replication factor of 1.
+    Quorum,
 }
 
 #[cfg(test)]
@@ -36,6 +40,7 @@ mod tests {
     fn test_to_string() {
         assert_eq!(Confirmation::Wait.to_string(), "wait");
         assert_eq!(Confirmation::NoWait.to_string(), "no_wait");
+        assert_eq!(Confirmation::Quorum.to_string(), "quorum");
     }
 
     #[test]
@@ -45,6 +50,10 @@ mod tests {
             Confirmation::from_str("no_wait").unwrap(),
             Confirmation::NoWait
         );
+        assert_eq!(
+            Confirmation::from_str("quorum").unwrap(),
+            Confirmation::Quorum
+        );
     }
 
     #[test]
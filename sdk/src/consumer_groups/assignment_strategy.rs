@@ -0,0 +1,77 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::error::IggyError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// `AssignmentStrategy` determines how the partitions of the topic are assigned to the members of the consumer group.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentStrategy {
+    /// Each member gets a contiguous range of partitions.
+    Range,
+    /// The partitions are distributed one by one to the subsequent members.
+    RoundRobin,
+    /// The members keep the partitions they already own, and only the surplus ones are moved during the rebalancing.
+    #[default]
+    Sticky,
+}
+
+impl FromStr for AssignmentStrategy {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "range" => Ok(AssignmentStrategy::Range),
+            "round_robin" | "round-robin" => Ok(AssignmentStrategy::RoundRobin),
+            "sticky" => Ok(AssignmentStrategy::Sticky),
+            _ => Err(IggyError::InvalidAssignmentStrategy),
+        }
+    }
+}
+
+impl Display for AssignmentStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssignmentStrategy::Range => write!(f, "range"),
+            AssignmentStrategy::RoundRobin => write!(f, "round_robin"),
+            AssignmentStrategy::Sticky => write!(f, "sticky"),
+        }
+    }
+}
+
+impl AssignmentStrategy {
+    /// Returns the code of the assignment strategy.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            AssignmentStrategy::Range => 1,
+            AssignmentStrategy::RoundRobin => 2,
+            AssignmentStrategy::Sticky => 3,
+        }
+    }
+
+    /// Returns the assignment strategy from the code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(AssignmentStrategy::Range),
+            2 => Ok(AssignmentStrategy::RoundRobin),
+            3 => Ok(AssignmentStrategy::Sticky),
+            _ => Err(IggyError::InvalidAssignmentStrategy),
+        }
+    }
+}
@@ -18,6 +18,7 @@
 
 use crate::bytes_serializable::BytesSerializable;
 use crate::command::{Command, CREATE_CONSUMER_GROUP_CODE};
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::consumer_groups::MAX_NAME_LENGTH;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
@@ -34,6 +35,7 @@ use std::str::from_utf8;
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `group_id` - unique consumer group ID.
 /// - `name` - unique consumer group name, max length is 255 characters.
+/// - `assignment_strategy` - strategy of assigning the partitions to the consumer group members.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateConsumerGroup {
     /// Unique stream ID (numeric or name).
@@ -46,6 +48,9 @@ pub struct CreateConsumerGroup {
     pub group_id: Option<u32>,
     /// Unique consumer group name, max length is 255 characters.
     pub name: String,
+    /// Strategy of assigning the partitions to the consumer group members.
+    #[serde(default)]
+    pub assignment_strategy: AssignmentStrategy,
 }
 
 impl Command for CreateConsumerGroup {
@@ -61,6 +66,7 @@ impl Default for CreateConsumerGroup {
             topic_id: Identifier::default(),
             group_id: None,
             name: "consumer_group_1".to_string(),
+            assignment_strategy: AssignmentStrategy::default(),
         }
     }
 }
@@ -86,7 +92,7 @@ impl BytesSerializable for CreateConsumerGroup {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            4 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
+            6 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
         );
         bytes.put_slice(&stream_id_bytes);
         bytes.put_slice(&topic_id_bytes);
@@ -94,6 +100,7 @@ impl BytesSerializable for CreateConsumerGroup {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_u8(self.assignment_strategy.as_code());
         bytes.freeze()
     }
 
@@ -113,15 +120,25 @@ impl BytesSerializable for CreateConsumerGroup {
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         );
         let group_id = if group_id == 0 { None } else { Some(group_id) };
-        let name_length = bytes[position + 4];
-        let name = from_utf8(&bytes[position + 5..position + 5 + name_length as usize])
+        let name_length = bytes[position + 4] as usize;
+        if bytes.len() < position + 5 + name_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let name = from_utf8(&bytes[position + 5..position + 5 + name_length])
             .map_err(|_| IggyError::InvalidUtf8)?
             .to_string();
+        // The commands persisted before the assignment strategy was introduced don't contain it.
+        let assignment_strategy = match bytes.get(position + 5 + name_length) {
+            Some(code) => AssignmentStrategy::from_code(*code)?,
+            None => AssignmentStrategy::default(),
+        };
         let command = CreateConsumerGroup {
             stream_id,
             topic_id,
             group_id,
             name,
+            assignment_strategy,
         };
         Ok(command)
     }
@@ -131,11 +148,12 @@ impl Display for CreateConsumerGroup {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}",
             self.stream_id,
             self.topic_id,
             self.group_id.unwrap_or(0),
-            self.name
+            self.name,
+            self.assignment_strategy
         )
     }
 }
@@ -151,6 +169,7 @@ mod tests {
             topic_id: Identifier::numeric(2).unwrap(),
             group_id: Some(3),
             name: "test".to_string(),
+            assignment_strategy: AssignmentStrategy::Range,
         };
 
         let bytes = command.to_bytes();
@@ -163,11 +182,14 @@ mod tests {
 
         let name_length = bytes[position + 4];
         let name = from_utf8(&bytes[position + 5..position + 5 + name_length as usize]).unwrap();
+        let assignment_strategy =
+            AssignmentStrategy::from_code(bytes[position + 5 + name_length as usize]).unwrap();
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
         assert_eq!(topic_id, command.topic_id);
         assert_eq!(group_id, command.group_id.unwrap());
         assert_eq!(name, command.name);
+        assert_eq!(assignment_strategy, command.assignment_strategy);
     }
 
     #[test]
@@ -176,16 +198,18 @@ mod tests {
         let topic_id = Identifier::numeric(2).unwrap();
         let group_id = 3u32;
         let name = "test".to_string();
+        let assignment_strategy = AssignmentStrategy::RoundRobin;
         let stream_id_bytes = stream_id.to_bytes();
         let topic_id_bytes = topic_id.to_bytes();
         let mut bytes =
-            BytesMut::with_capacity(4 + stream_id_bytes.len() + topic_id_bytes.len() + name.len());
+            BytesMut::with_capacity(6 + stream_id_bytes.len() + topic_id_bytes.len() + name.len());
         bytes.put_slice(&stream_id_bytes);
         bytes.put_slice(&topic_id_bytes);
         bytes.put_u32_le(group_id);
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(name.len() as u8);
         bytes.put_slice(name.as_bytes());
+        bytes.put_u8(assignment_strategy.as_code());
         let command = CreateConsumerGroup::from_bytes(bytes.freeze());
         assert!(command.is_ok());
 
@@ -194,5 +218,6 @@ mod tests {
         assert_eq!(command.topic_id, topic_id);
         assert_eq!(command.group_id.unwrap(), group_id);
         assert_eq!(command.name, name);
+        assert_eq!(command.assignment_strategy, assignment_strategy);
     }
 }
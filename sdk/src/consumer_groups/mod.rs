@@ -16,6 +16,7 @@
  * under the License.
  */
 
+pub mod assignment_strategy;
 pub mod create_consumer_group;
 pub mod delete_consumer_group;
 pub mod get_consumer_group;
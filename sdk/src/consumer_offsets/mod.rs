@@ -17,5 +17,6 @@
  */
 
 pub mod delete_consumer_offset;
+pub mod get_consumer_lag;
 pub mod get_consumer_offset;
 pub mod store_consumer_offset;
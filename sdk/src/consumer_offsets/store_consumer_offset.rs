@@ -34,6 +34,7 @@ use std::fmt::Display;
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `partition_id` - partition ID on which the offset is stored. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
 /// - `offset` - offset to store.
+/// - `generation_id` - generation of the consumer group returned with the polled messages. If provided, the offset is rejected when the group has been rebalanced since then. It is ignored for the regular consumer.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct StoreConsumerOffset {
     /// The consumer that is storing the offset, either the regular consumer or the consumer group.
@@ -49,6 +50,8 @@ pub struct StoreConsumerOffset {
     pub partition_id: Option<u32>,
     /// Offset to store.
     pub offset: u64,
+    /// Generation of the consumer group returned with the polled messages. If provided, the offset is rejected when the group has been rebalanced since then. It is ignored for the regular consumer.
+    pub generation_id: Option<u32>,
 }
 
 impl Default for StoreConsumerOffset {
@@ -59,6 +62,7 @@ impl Default for StoreConsumerOffset {
             topic_id: Identifier::default(),
             partition_id: Some(1),
             offset: 0,
+            generation_id: None,
         }
     }
 }
@@ -81,7 +85,7 @@ impl BytesSerializable for StoreConsumerOffset {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            12 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
+            16 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
         );
         bytes.put_slice(&consumer_bytes);
         bytes.put_slice(&stream_id_bytes);
@@ -92,6 +96,9 @@ impl BytesSerializable for StoreConsumerOffset {
             bytes.put_u32_le(0);
         }
         bytes.put_u64_le(self.offset);
+        if let Some(generation_id) = self.generation_id {
+            bytes.put_u32_le(generation_id);
+        }
         bytes.freeze()
     }
 
@@ -127,12 +134,27 @@ impl BytesSerializable for StoreConsumerOffset {
                 .try_into()
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         );
+        // The generation is optional to remain compatible with the clients which don't send it.
+        let generation_id = match bytes.get(position + 12..position + 16) {
+            Some(generation_id) => u32::from_le_bytes(
+                generation_id
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            ),
+            None => 0,
+        };
+        let generation_id = if generation_id == 0 {
+            None
+        } else {
+            Some(generation_id)
+        };
         let command = StoreConsumerOffset {
             consumer,
             stream_id,
             topic_id,
             partition_id,
             offset,
+            generation_id,
         };
         Ok(command)
     }
@@ -142,12 +164,13 @@ impl Display for StoreConsumerOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}",
             self.consumer,
             self.stream_id,
             self.topic_id,
             self.partition_id.unwrap_or(0),
-            self.offset
+            self.offset,
+            self.generation_id.unwrap_or(0)
         )
     }
 }
@@ -164,6 +187,7 @@ mod tests {
             topic_id: Identifier::numeric(3).unwrap(),
             partition_id: Some(4),
             offset: 5,
+            generation_id: Some(6),
         };
 
         let bytes = command.to_bytes();
@@ -181,6 +205,8 @@ mod tests {
         position += topic_id.get_size_bytes().as_bytes_usize();
         let partition_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
         let offset = u64::from_le_bytes(bytes[position + 4..position + 12].try_into().unwrap());
+        let generation_id =
+            u32::from_le_bytes(bytes[position + 12..position + 16].try_into().unwrap());
 
         assert!(!bytes.is_empty());
         assert_eq!(consumer, command.consumer);
@@ -188,6 +214,7 @@ mod tests {
         assert_eq!(topic_id, command.topic_id);
         assert_eq!(Some(partition_id), command.partition_id);
         assert_eq!(offset, command.offset);
+        assert_eq!(Some(generation_id), command.generation_id);
     }
 
     #[test]
@@ -197,18 +224,20 @@ mod tests {
         let topic_id = Identifier::numeric(3).unwrap();
         let partition_id = 4u32;
         let offset = 5u64;
+        let generation_id = 6u32;
 
         let consumer_bytes = consumer.to_bytes();
         let stream_id_bytes = stream_id.to_bytes();
         let topic_id_bytes = topic_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            12 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
+            16 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
         );
         bytes.put_slice(&consumer_bytes);
         bytes.put_slice(&stream_id_bytes);
         bytes.put_slice(&topic_id_bytes);
         bytes.put_u32_le(partition_id);
         bytes.put_u64_le(offset);
+        bytes.put_u32_le(generation_id);
 
         let command = StoreConsumerOffset::from_bytes(bytes.freeze());
         assert!(command.is_ok());
@@ -219,5 +248,21 @@ mod tests {
         assert_eq!(command.topic_id, topic_id);
         assert_eq!(command.partition_id, Some(partition_id));
         assert_eq!(command.offset, offset);
+        assert_eq!(command.generation_id, Some(generation_id));
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_without_generation() {
+        let command = StoreConsumerOffset {
+            consumer: Consumer::new(Identifier::numeric(1).unwrap()),
+            stream_id: Identifier::numeric(2).unwrap(),
+            topic_id: Identifier::numeric(3).unwrap(),
+            partition_id: Some(4),
+            offset: 5,
+            generation_id: None,
+        };
+
+        let deserialized_command = StoreConsumerOffset::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized_command, command);
     }
 }
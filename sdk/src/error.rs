@@ -18,6 +18,8 @@
 
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::topic_size::MaxTopicSize;
+use bytes::Bytes;
+use std::time::Duration;
 use strum::{EnumDiscriminants, FromRepr, IntoStaticStr};
 use thiserror::Error;
 
@@ -72,6 +74,8 @@ pub enum IggyError {
     TcpError = 31,
     #[error("QUIC error")]
     QuicError = 32,
+    #[error("WebSocket error")]
+    WebSocketError = 35,
     #[error("Invalid server address")]
     InvalidServerAddress = 33,
     #[error("Invalid client address")]
@@ -108,6 +112,8 @@ pub enum IggyError {
     PersonalAccessTokenExpired(String, u32) = 54,
     #[error("Users limit reached.")]
     UsersLimitReached = 55,
+    #[error("Quota exceeded, retry after: {0} ms.")]
+    QuotaExceeded(u64) = 56,
     #[error("Not connected")]
     NotConnected = 61,
     #[error("Client shutdown")]
@@ -148,6 +154,10 @@ pub enum IggyError {
     InvalidBooleanValue = 83,
     #[error("Invalid number value")]
     InvalidNumberValue = 84,
+    #[error("Cannot compress data")]
+    CannotCompressData = 85,
+    #[error("Cannot decompress data")]
+    CannotDecompressData = 86,
     #[error("Client with ID: {0} was not found.")]
     ClientNotFound(u32) = 100,
     #[error("Invalid client ID")]
@@ -202,6 +212,12 @@ pub enum IggyError {
     InvalidStreamId = 1014,
     #[error("Cannot read streams")]
     CannotReadStreams = 1015,
+    #[error("Stream with ID: {0} is full.")]
+    StreamFull(u32) = 1016,
+    #[error("Stream with ID: {0} has reached the limit of {1} topics.")]
+    StreamTopicsLimitReached(u32, u32) = 1017,
+    #[error("Stream with ID: {0} has reached the limit of {1} partitions.")]
+    StreamPartitionsLimitReached(u32, u32) = 1018,
     #[error("Max topic size cannot be lower than segment size. Max topic size: {0} < segment size: {1}.")]
     InvalidTopicSize(MaxTopicSize, IggyByteSize) = 1019,
     #[error("Cannot create topics directory for stream with ID: {0}, Path: {1}")]
@@ -244,6 +260,8 @@ pub enum IggyError {
     CannotReadTopics(u32) = 2017,
     #[error("Invalid replication factor")]
     InvalidReplicationFactor = 2018,
+    #[error("Invalid cleanup policy")]
+    InvalidCleanupPolicy = 2019,
     #[error("Cannot create partition with ID: {0} for stream with ID: {1} and topic with ID: {2}")]
     CannotCreatePartition(u32, u32, u32) = 3000,
     #[error(
@@ -342,6 +360,28 @@ pub enum IggyError {
     CommandLengthError(String) = 4029,
     #[error("Incorrect Segments Count size: {0}")]
     InvalidSegmentsCount(u32) = 4030,
+    #[error("Invalid header filter")]
+    InvalidHeaderFilter = 4031,
+    #[error("Cannot fetch archived segment with start offset: {0} for partition with ID: {1}")]
+    CannotFetchArchivedSegment(u64, u32) = 4032,
+    #[error("Invalid restore range: {0} - {1}")]
+    InvalidRestoreRange(u64, u64) = 4033,
+    #[error("Invalid archive path")]
+    InvalidArchivePath = 4034,
+    #[error("Archiver is not enabled")]
+    ArchiverNotEnabled = 4035,
+    #[error("Archived segments for stream with ID: {0}, topic with ID: {1} and partition with ID: {2} were not found")]
+    ArchivedSegmentsNotFound(u32, u32, u32) = 4036,
+    #[error("Cannot restore segment with start offset: {0} for partition with ID: {1}")]
+    CannotRestoreSegment(u64, u32) = 4037,
+    #[error("Cannot create backup at path: {0}")]
+    CannotCreateBackup(String) = 4038,
+    #[error("Invalid backup at path: {0}")]
+    InvalidBackup(String) = 4039,
+    #[error("Invalid state index: {0} to restore the backup, the last backed up index is: {1}")]
+    InvalidBackupStateIndex(u64, u64) = 4040,
+    #[error("Cannot nack message in dead letter topic with ID: {0} for stream with ID: {1}")]
+    CannotNackDeadLetterMessage(u32, u32) = 4041,
     #[error("Cannot sed messages due to client disconnection")]
     CannotSendMessagesDueToClientDisconnection = 4050,
     #[error("Invalid offset: {0}")]
@@ -364,6 +404,10 @@ pub enum IggyError {
     CannotCreateConsumerGroupInfo(u32, u32, u32) = 5007,
     #[error("Failed to delete consumer group info file for ID: {0} for topic with ID: {1} for stream with ID: {2}.")]
     CannotDeleteConsumerGroupInfo(u32, u32, u32) = 5008,
+    #[error("Generation: {0} of consumer group with ID: {1} for topic with ID: {2} is stale, current generation: {3}.")]
+    StaleConsumerGroupGeneration(u32, u32, u32, u32) = 5009,
+    #[error("Invalid consumer group assignment strategy")]
+    InvalidAssignmentStrategy = 5010,
     #[error("Base offset is missing")]
     MissingBaseOffsetRetainedMessageBatch = 6000,
     #[error("Last offset delta is missing")]
@@ -414,6 +458,36 @@ pub enum IggyError {
     CannotReadIndexPosition = 10011,
     #[error("Cannot read index timestamp")]
     CannotReadIndexTimestamp = 10012,
+    #[error("Cannot append messages to the replicated topic on the follower node")]
+    NotReplicationLeader = 11000,
+    #[error("Replication quorum was not reached for offset: {0} in partition with ID: {1} for topic with ID: {2} and stream with ID: {3}")]
+    ReplicationQuorumNotReached(u64, u32, u32, u32) = 11001,
+    #[error("Cannot change the cluster metadata on the node which is not the cluster leader")]
+    NotClusterLeader = 11002,
+    #[error("State entry with index: {0} was not committed by the cluster")]
+    StateEntryNotCommitted(u64) = 11003,
+    #[error("Replica with ID: {0} is not allowed to fetch the messages")]
+    InvalidReplica(u32) = 11004,
+    #[error("Transaction with ID: {0} was not found")]
+    TransactionNotFound(u64) = 12000,
+    #[error("Transaction with ID: {0} is already open for the client")]
+    TransactionAlreadyOpen(u64) = 12001,
+    #[error("Invalid transaction ID")]
+    InvalidTransactionId = 12002,
+    #[error("Invalid isolation level")]
+    InvalidIsolationLevel = 12003,
+    #[error("Cannot append messages as a part of the transaction to the replicated topic with ID: {0} for stream with ID: {1}")]
+    TransactionalReplicatedTopic(u32, u32) = 12004,
+    #[error("Invalid producer ID")]
+    InvalidProducerId = 12100,
+    #[error("Out of order sequence number: {2} for producer with ID: {0}, expected: {1}")]
+    OutOfOrderSequence(u64, u64, u64) = 12101,
+    #[error("Idempotent messages must be sent to the partition with the provided ID")]
+    InvalidIdempotentPartitioning = 12102,
+    #[error("Cannot append idempotent messages to the replicated topic with ID: {0} for stream with ID: {1}")]
+    IdempotentReplicatedTopic(u32, u32) = 12103,
+    #[error("Unknown producer with ID: {0}, its first sequence number must be 1, got: {1}")]
+    UnknownProducer(u64, u64) = 12104,
 }
 
 impl IggyError {
@@ -431,6 +505,34 @@ impl IggyError {
         IggyError::from_repr(code).unwrap_or(IggyError::Error)
     }
 
+    /// Creates the error from its code and the details sent along with it in the binary response.
+    pub fn from_code_and_details(code: u32, details: &[u8]) -> Self {
+        match IggyError::from_code(code) {
+            IggyError::QuotaExceeded(_) if details.len() >= 8 => {
+                IggyError::QuotaExceeded(u64::from_le_bytes(details[..8].try_into().unwrap()))
+            }
+            error => error,
+        }
+    }
+
+    /// Returns the details sent along with the error code in the binary response, empty for most of the errors.
+    pub fn details_to_bytes(&self) -> Bytes {
+        match self {
+            IggyError::QuotaExceeded(retry_after) => {
+                Bytes::copy_from_slice(&retry_after.to_le_bytes())
+            }
+            _ => Bytes::new(),
+        }
+    }
+
+    /// Returns the time after which the rejected request can be retried, if the server provided one.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            IggyError::QuotaExceeded(retry_after) => Some(Duration::from_millis(*retry_after)),
+            _ => None,
+        }
+    }
+
     pub fn from_code_as_string(code: u32) -> &'static str {
         IggyErrorDiscriminants::from_repr(code)
             .map(|discriminant| discriminant.into())
@@ -470,6 +572,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn error_details_should_be_sent_along_with_code() {
+        let error = IggyError::QuotaExceeded(250);
+        let details = error.details_to_bytes();
+        let error = IggyError::from_code_and_details(error.as_code(), &details);
+        assert!(matches!(error, IggyError::QuotaExceeded(250)));
+        assert_eq!(error.retry_after(), Some(Duration::from_millis(250)));
+        assert!(IggyError::Unauthorized.details_to_bytes().is_empty());
+    }
+
     #[test]
     fn gets_string_from_code() {
         assert_eq!(
@@ -22,10 +22,13 @@ use crate::error::IggyError;
 use crate::http::config::HttpClientConfig;
 use crate::http::HttpTransport;
 use crate::locking::{IggySharedMut, IggySharedMutFn};
+use crate::models::handshake::ProtocolFeatures;
 use crate::models::identity_info::IdentityInfo;
+use crate::utils::certificates;
 use crate::utils::duration::IggyDuration;
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
 use reqwest::{Response, StatusCode, Url};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
@@ -33,6 +36,7 @@ use serde::Serialize;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::sync::Arc;
+use tracing::error;
 
 const PUBLIC_PATHS: &[&str] = &[
     "/",
@@ -40,6 +44,7 @@ const PUBLIC_PATHS: &[&str] = &[
     "/ping",
     "/stats",
     "/users/login",
+    "/users/login/oidc",
     "/users/refresh-token",
     "/personal-access-tokens/login",
 ];
@@ -53,13 +58,17 @@ pub struct HttpClient {
     pub(crate) heartbeat_interval: IggyDuration,
     client: ClientWithMiddleware,
     access_token: IggySharedMut<String>,
+    /// Whether the client certificate is presented, so the server may authenticate the requests without the access token.
+    client_certificate: bool,
     events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
 }
 
 #[async_trait]
 impl Client for HttpClient {
-    async fn connect(&self) -> Result<(), IggyError> {
-        HttpClient::connect(self).await
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
+        HttpClient::connect(self).await?;
+        // There's no handshake over HTTP, so no protocol features are negotiated.
+        Ok(ProtocolFeatures::default())
     }
 
     async fn disconnect(&self) -> Result<(), IggyError> {
@@ -272,7 +281,36 @@ impl HttpClient {
         }
         let api_url = api_url.unwrap();
         let retry_policy = ExponentialBackoff::builder().build_with_max_retries(config.retries);
-        let client = ClientBuilder::new(reqwest::Client::new())
+        let mut client = reqwest::Client::builder().use_rustls_tls();
+        let mut client_certificate = false;
+        if let Some(ca_file) = &config.tls_ca_file {
+            let pem = std::fs::read(ca_file).map_err(|error| {
+                error!("Failed to read the CA file: {ca_file}. {error}");
+                IggyError::InvalidTlsCertificatePath
+            })?;
+            for certificate in reqwest::Certificate::from_pem_bundle(&pem).map_err(|error| {
+                error!("Failed to read a certificate from the CA file: {ca_file}. {error}");
+                IggyError::InvalidTlsCertificate
+            })? {
+                client = client.add_root_certificate(certificate);
+            }
+        }
+        if let (Some(certificate_file), Some(key_file)) =
+            (&config.tls_certificate_file, &config.tls_key_file)
+        {
+            let pem = certificates::read_client_certificate_pem(certificate_file, key_file)?;
+            let identity = reqwest::Identity::from_pem(&pem).map_err(|error| {
+                error!("Failed to create the HTTP client identity from the client certificate. {error}");
+                IggyError::InvalidTlsCertificate
+            })?;
+            client = client.identity(identity);
+            client_certificate = true;
+        }
+        let client = client.build().map_err(|error| {
+            error!("Failed to create the HTTP client. {error}");
+            IggyError::InvalidConfiguration
+        })?;
+        let client = ClientBuilder::new(client)
             .with(RetryTransientMiddleware::new_with_policy(retry_policy))
             .build();
 
@@ -281,6 +319,7 @@ impl HttpClient {
             client,
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             access_token: IggySharedMut::new("".to_string()),
+            client_certificate,
             events: broadcast(1000),
         })
     }
@@ -290,8 +329,16 @@ impl HttpClient {
         match status.is_success() {
             true => Ok(response),
             false => {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<u64>().ok());
                 let reason = response.text().await.unwrap_or("error".to_string());
                 match status {
+                    StatusCode::TOO_MANY_REQUESTS => Err(IggyError::QuotaExceeded(
+                        retry_after.unwrap_or_default() * 1000,
+                    )),
                     StatusCode::UNAUTHORIZED => Err(IggyError::Unauthenticated),
                     StatusCode::FORBIDDEN => Err(IggyError::Unauthorized),
                     StatusCode::NOT_FOUND => Err(IggyError::ResourceNotFound(reason)),
@@ -302,7 +349,7 @@ impl HttpClient {
     }
 
     async fn fail_if_not_authenticated(&self, path: &str) -> Result<(), IggyError> {
-        if PUBLIC_PATHS.contains(&path) {
+        if PUBLIC_PATHS.contains(&path) || self.client_certificate {
             return Ok(());
         }
         if !self.is_authenticated().await {
@@ -23,6 +23,12 @@ pub struct HttpClientConfig {
     pub api_url: String,
     /// The number of retries to perform on transient errors.
     pub retries: u32,
+    /// The path to the CA file trusted in addition to the default roots when using HTTPS.
+    pub tls_ca_file: Option<String>,
+    /// The path to the PEM file with the client certificate presented to the server, if required.
+    pub tls_certificate_file: Option<String>,
+    /// The path to the PEM file with the private key of the client certificate.
+    pub tls_key_file: Option<String>,
 }
 
 impl Default for HttpClientConfig {
@@ -30,6 +36,9 @@ impl Default for HttpClientConfig {
         HttpClientConfig {
             api_url: "http://127.0.0.1:3000".to_string(),
             retries: 3,
+            tls_ca_file: None,
+            tls_certificate_file: None,
+            tls_key_file: None,
         }
     }
 }
@@ -38,6 +47,9 @@ impl Default for HttpClientConfig {
 /// Allows configuring the HTTP client with custom settings or using defaults:
 /// - `api_url`: Default is "http://127.0.0.1:3000"
 /// - `retries`: Default is 3.
+/// - `tls_ca_file`: Default is None.
+/// - `tls_certificate_file`: Default is None.
+/// - `tls_key_file`: Default is None.
 #[derive(Debug, Default)]
 pub struct HttpClientConfigBuilder {
     config: HttpClientConfig,
@@ -61,6 +73,23 @@ impl HttpClientConfigBuilder {
         self
     }
 
+    /// Sets the path to the CA file for HTTPS.
+    pub fn with_tls_ca_file(mut self, tls_ca_file: String) -> Self {
+        self.config.tls_ca_file = Some(tls_ca_file);
+        self
+    }
+
+    /// Sets the paths to the client certificate and its private key, presented to the server verifying the clients.
+    pub fn with_tls_client_certificate(
+        mut self,
+        certificate_file: String,
+        key_file: String,
+    ) -> Self {
+        self.config.tls_certificate_file = Some(certificate_file);
+        self.config.tls_key_file = Some(key_file);
+        self
+    }
+
     /// Builds the `HttpClientConfig` instance.
     pub fn build(self) -> HttpClientConfig {
         self.config
@@ -17,6 +17,7 @@
  */
 
 use crate::client::ConsumerGroupClient;
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use crate::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
@@ -76,6 +77,7 @@ impl ConsumerGroupClient for HttpClient {
         topic_id: &Identifier,
         name: &str,
         group_id: Option<u32>,
+        assignment_strategy: AssignmentStrategy,
     ) -> Result<ConsumerGroupDetails, IggyError> {
         let response = self
             .post(
@@ -85,6 +87,7 @@ impl ConsumerGroupClient for HttpClient {
                     topic_id: topic_id.clone(),
                     name: name.to_string(),
                     group_id,
+                    assignment_strategy,
                 },
             )
             .await?;
@@ -17,13 +17,15 @@
  */
 
 use crate::client::ConsumerOffsetClient;
-use crate::consumer::Consumer;
+use crate::consumer::{Consumer, ConsumerKind};
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use crate::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use crate::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
+use crate::models::consumer_lag::ConsumerLag;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use async_trait::async_trait;
 
@@ -36,6 +38,7 @@ impl ConsumerOffsetClient for HttpClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation_id: Option<u32>,
     ) -> Result<(), IggyError> {
         self.put(
             &get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
@@ -45,6 +48,7 @@ impl ConsumerOffsetClient for HttpClient {
                 topic_id: topic_id.clone(),
                 partition_id,
                 offset,
+                generation_id,
             },
         )
         .await?;
@@ -102,6 +106,40 @@ impl ConsumerOffsetClient for HttpClient {
         self.delete(&path).await?;
         Ok(())
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<ConsumerLag, IggyError> {
+        let stream_id = stream_id.as_cow_str();
+        let topic_id = topic_id.as_cow_str();
+        let response = match consumer.kind {
+            ConsumerKind::Consumer => {
+                self.get_with_query(
+                    &format!("streams/{stream_id}/topics/{topic_id}/consumer-lag"),
+                    &GetConsumerLag {
+                        consumer: consumer.clone(),
+                        ..Default::default()
+                    },
+                )
+                .await?
+            }
+            ConsumerKind::ConsumerGroup => {
+                self.get(&format!(
+                    "streams/{stream_id}/topics/{topic_id}/consumer-groups/{}/lag",
+                    consumer.id
+                ))
+                .await?
+            }
+        };
+        let lag = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(lag)
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str) -> String {
@@ -16,18 +16,28 @@
  * under the License.
  */
 
+use crate::binary::subscription::MessageSubscription;
 use crate::client::MessageClient;
 use crate::consumer::Consumer;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
+use crate::http::messages_tail::MessagesTail;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::messages::flush_unsaved_buffer::FlushUnsavedBuffer;
-use crate::messages::poll_messages::{PollMessages, PollingStrategy};
-use crate::messages::send_messages::{Message, Partitioning, SendMessages};
+use crate::messages::header_filter::HeaderFilter;
+use crate::messages::init_producer::InitProducer;
+use crate::messages::nack_message::NackMessage;
+use crate::messages::poll_messages::{
+    IsolationLevel, PollMessages, PollMessagesOptions, PollingStrategy, PollingWait,
+};
+use crate::messages::send_messages::{Message, Partitioning, ProducerSequence, SendMessages};
+use crate::messages::subscribe_messages::SubscriptionOptions;
 use crate::models::messages::PolledMessages;
 use async_trait::async_trait;
 
+const PRODUCERS_PATH: &str = "/producers";
+
 #[async_trait]
 impl MessageClient for HttpClient {
     async fn poll_messages(
@@ -39,6 +49,7 @@ impl MessageClient for HttpClient {
         strategy: &PollingStrategy,
         count: u32,
         auto_commit: bool,
+        options: &PollMessagesOptions,
     ) -> Result<PolledMessages, IggyError> {
         let response = self
             .get_with_query(
@@ -51,6 +62,9 @@ impl MessageClient for HttpClient {
                     strategy: *strategy,
                     count,
                     auto_commit,
+                    isolation_level: options.isolation_level,
+                    filter: options.filter.clone(),
+                    wait: options.wait,
                 },
             )
             .await?;
@@ -61,6 +75,33 @@ impl MessageClient for HttpClient {
         Ok(messages)
     }
 
+    async fn poll_assigned_messages(
+        &self,
+        _stream_id: &Identifier,
+        _topic_id: &Identifier,
+        _group_id: &Identifier,
+        _strategy: &PollingStrategy,
+        _count: u32,
+        _auto_commit: bool,
+        _options: &PollMessagesOptions,
+    ) -> Result<Vec<PolledMessages>, IggyError> {
+        Err(IggyError::FeatureUnavailable)
+    }
+
+    async fn subscribe_messages(
+        &self,
+        _stream_id: &Identifier,
+        _topic_id: &Identifier,
+        _partition_id: Option<u32>,
+        _consumer: &Consumer,
+        _strategy: &PollingStrategy,
+        _count: u32,
+        _options: &PollMessagesOptions,
+        _subscription: &SubscriptionOptions,
+    ) -> Result<MessageSubscription, IggyError> {
+        Err(IggyError::FeatureUnavailable)
+    }
+
     async fn send_messages(
         &self,
         stream_id: &Identifier,
@@ -75,6 +116,38 @@ impl MessageClient for HttpClient {
                 topic_id: topic_id.clone(),
                 partitioning: partitioning.clone(),
                 messages: messages.to_vec(),
+                producer_sequence: None,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn init_producer(&self) -> Result<u64, IggyError> {
+        let response = self.post(PRODUCERS_PATH, &InitProducer {}).await?;
+        let producer_id = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(producer_id)
+    }
+
+    async fn send_idempotent_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        producer_sequence: &ProducerSequence,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        self.post(
+            &get_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+            &SendMessages {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partitioning: Partitioning::partition_id(partition_id),
+                messages: messages.to_vec(),
+                producer_sequence: Some(*producer_sequence),
             },
         )
         .await?;
@@ -106,6 +179,65 @@ impl MessageClient for HttpClient {
             .await?;
         Ok(())
     }
+
+    async fn nack_message(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &get_path_nack_message(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+            &NackMessage {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+                offset,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+impl HttpClient {
+    /// Tail the messages of the partition, which are streamed by the server as they are appended,
+    /// starting from the provided polling strategy.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn tail_messages(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        consumer: &Consumer,
+        strategy: &PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: Option<&HeaderFilter>,
+    ) -> Result<MessagesTail, IggyError> {
+        let response = self
+            .get_with_query(
+                &get_path_events(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+                &PollMessages {
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.clone(),
+                    partition_id,
+                    consumer: consumer.clone(),
+                    strategy: *strategy,
+                    count,
+                    auto_commit,
+                    isolation_level,
+                    filter: filter.cloned(),
+                    wait: PollingWait::default(),
+                },
+            )
+            .await?;
+        Ok(MessagesTail::new(response))
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str) -> String {
@@ -120,3 +252,11 @@ fn get_path_flush_unsaved_buffer(
 ) -> String {
     format!("streams/{stream_id}/topics/{topic_id}/messages/flush/{partition_id}/fsync={fsync}")
 }
+
+fn get_path_events(stream_id: &str, topic_id: &str) -> String {
+    format!("{}/events", get_path(stream_id, topic_id))
+}
+
+fn get_path_nack_message(stream_id: &str, topic_id: &str) -> String {
+    format!("streams/{stream_id}/topics/{topic_id}/messages/nack")
+}
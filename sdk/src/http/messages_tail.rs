@@ -0,0 +1,142 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use crate::models::messages::PolledMessages;
+use reqwest::Response;
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+
+const EVENTS_SEPARATOR: &[u8] = b"\n\n";
+const EVENT_FIELD: &str = "event:";
+const DATA_FIELD: &str = "data:";
+const MESSAGES_EVENT: &str = "messages";
+const ERROR_EVENT: &str = "error";
+
+/// The messages of the partition streamed by the server as the server-sent events.
+/// The messages are returned in batches, as they are appended to the partition.
+pub struct MessagesTail {
+    response: Response,
+    buffer: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorEvent {
+    id: u32,
+}
+
+impl Debug for MessagesTail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessagesTail")
+            .field("url", self.response.url())
+            .finish()
+    }
+}
+
+impl MessagesTail {
+    pub(crate) fn new(response: Response) -> Self {
+        Self {
+            response,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the next batch of the messages, or `None` if the stream was closed by the server.
+    pub async fn next(&mut self) -> Result<Option<PolledMessages>, IggyError> {
+        loop {
+            while let Some(position) = self
+                .buffer
+                .windows(EVENTS_SEPARATOR.len())
+                .position(|window| window == EVENTS_SEPARATOR)
+            {
+                let event = self
+                    .buffer
+                    .drain(..position + EVENTS_SEPARATOR.len())
+                    .collect::<Vec<_>>();
+                if let Some(polled_messages) = parse_event(&event)? {
+                    return Ok(Some(polled_messages));
+                }
+            }
+
+            let chunk = self
+                .response
+                .chunk()
+                .await
+                .map_err(|_| IggyError::InvalidHttpRequest)?;
+            let Some(chunk) = chunk else {
+                return Ok(None);
+            };
+            self.buffer.extend_from_slice(&chunk);
+        }
+    }
+}
+
+// The keep-alive comments and the unknown events are skipped.
+fn parse_event(event: &[u8]) -> Result<Option<PolledMessages>, IggyError> {
+    let event = std::str::from_utf8(event).map_err(|_| IggyError::InvalidJsonResponse)?;
+    let mut name = "";
+    let mut data = String::new();
+    for line in event.lines() {
+        if let Some(value) = line.strip_prefix(EVENT_FIELD) {
+            name = value.trim();
+        } else if let Some(value) = line.strip_prefix(DATA_FIELD) {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+
+    match name {
+        MESSAGES_EVENT => serde_json::from_str(&data)
+            .map(Some)
+            .map_err(|_| IggyError::InvalidJsonResponse),
+        ERROR_EVENT => {
+            let error: ErrorEvent =
+                serde_json::from_str(&data).map_err(|_| IggyError::InvalidJsonResponse)?;
+            Err(IggyError::from_code(error.id))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_event_should_be_parsed() {
+        let event = b"event: messages\nid: 1\ndata: {\"partition_id\":1,\"current_offset\":1,\"messages\":[]}\n\n";
+        let polled_messages = parse_event(event).unwrap().unwrap();
+        assert_eq!(polled_messages.partition_id, 1);
+        assert_eq!(polled_messages.current_offset, 1);
+        assert!(polled_messages.messages.is_empty());
+    }
+
+    #[test]
+    fn error_event_should_be_mapped_to_error() {
+        let event = b"event: error\ndata: {\"id\":2010,\"code\":\"topic_id_not_found\",\"reason\":\"\",\"field\":null}\n\n";
+        let error = parse_event(event).unwrap_err();
+        assert_eq!(error.as_code(), 2010);
+    }
+
+    #[test]
+    fn keep_alive_comment_should_be_skipped() {
+        assert!(parse_event(b":\n\n").unwrap().is_none());
+    }
+}
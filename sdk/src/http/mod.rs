@@ -28,12 +28,14 @@ pub mod config;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod messages;
+pub mod messages_tail;
 pub mod partitions;
 pub mod personal_access_tokens;
 pub mod segments;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 
 #[async_trait]
@@ -21,6 +21,7 @@ use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::segments::delete_segments::DeleteSegments;
+use crate::segments::restore_segments::{ArchiveSource, RestoreRange, RestoreSegments};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -48,6 +49,35 @@ impl SegmentClient for HttpClient {
         .await?;
         Ok(())
     }
+
+    async fn restore_segments(
+        &self,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<(), IggyError> {
+        self.post(
+            &format!(
+                "{}/restore",
+                get_path(
+                    &stream_id.as_cow_str(),
+                    &topic_id.as_cow_str(),
+                    partition_id
+                )
+            ),
+            &RestoreSegments {
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+                source: source.clone(),
+                range: *range,
+            },
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str, partition_id: u32) -> String {
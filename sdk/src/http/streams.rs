@@ -22,6 +22,7 @@ use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::models::stream::{Stream, StreamDetails};
+use crate::models::stream_limits::StreamLimits;
 use crate::streams::create_stream::CreateStream;
 use crate::streams::update_stream::UpdateStream;
 use async_trait::async_trait;
@@ -60,6 +61,7 @@ impl StreamClient for HttpClient {
         &self,
         name: &str,
         stream_id: Option<u32>,
+        limits: Option<StreamLimits>,
     ) -> Result<StreamDetails, IggyError> {
         let response = self
             .post(
@@ -67,6 +69,7 @@ impl StreamClient for HttpClient {
                 &CreateStream {
                     name: name.to_string(),
                     stream_id,
+                    limits,
                 },
             )
             .await?;
@@ -77,12 +80,18 @@ impl StreamClient for HttpClient {
         Ok(stream)
     }
 
-    async fn update_stream(&self, stream_id: &Identifier, name: &str) -> Result<(), IggyError> {
+    async fn update_stream(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<(), IggyError> {
         self.put(
             &get_details_path(&stream_id.as_cow_str()),
             &UpdateStream {
                 stream_id: stream_id.clone(),
                 name: name.to_string(),
+                limits,
             },
         )
         .await?;
@@ -21,6 +21,7 @@ use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
+use crate::models::cluster::ClusterMetadata;
 use crate::models::snapshot::Snapshot;
 use crate::models::stats::Stats;
 use crate::snapshot::{SnapshotCompression, SystemSnapshotType};
@@ -30,6 +31,7 @@ use async_trait::async_trait;
 
 const PING: &str = "/ping";
 const CLIENTS: &str = "/clients";
+const CLUSTER_METADATA: &str = "/cluster/metadata";
 const STATS: &str = "/stats";
 const SNAPSHOT: &str = "/snapshot";
 
@@ -44,6 +46,15 @@ impl SystemClient for HttpClient {
         Ok(stats)
     }
 
+    async fn get_cluster_metadata(&self) -> Result<ClusterMetadata, IggyError> {
+        let response = self.get(CLUSTER_METADATA).await?;
+        let metadata = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        Ok(metadata)
+    }
+
     async fn get_me(&self) -> Result<ClientInfoDetails, IggyError> {
         Err(IggyError::FeatureUnavailable)
     }
@@ -24,10 +24,12 @@ use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
 use crate::users::create_user::CreateUser;
 use crate::users::login_user::LoginUser;
+use crate::users::login_with_oidc_token::LoginWithOidcToken;
 use crate::users::update_permissions::UpdatePermissions;
 use crate::users::update_user::UpdateUser;
 use async_trait::async_trait;
@@ -98,6 +100,7 @@ impl UserClient for HttpClient {
         user_id: &Identifier,
         username: Option<&str>,
         status: Option<UserStatus>,
+        quotas: Option<UserQuotas>,
     ) -> Result<(), IggyError> {
         self.put(
             &format!("{PATH}/{}", &user_id.as_cow_str()),
@@ -105,6 +108,7 @@ impl UserClient for HttpClient {
                 user_id: user_id.clone(),
                 username: username.map(|s| s.to_string()),
                 status,
+                quotas,
             },
         )
         .await?;
@@ -165,6 +169,23 @@ impl UserClient for HttpClient {
         Ok(identity_info)
     }
 
+    async fn login_with_oidc_token(&self, token: &str) -> Result<IdentityInfo, IggyError> {
+        let response = self
+            .post(
+                &format!("{PATH}/login/oidc"),
+                &LoginWithOidcToken {
+                    token: token.to_string(),
+                },
+            )
+            .await?;
+        let identity_info = response
+            .json()
+            .await
+            .map_err(|_| IggyError::InvalidJsonResponse)?;
+        self.set_token_from_identity(&identity_info).await?;
+        Ok(identity_info)
+    }
+
     async fn logout_user(&self) -> Result<(), IggyError> {
         self.delete(&format!("{PATH}/logout")).await?;
         self.set_access_token(None).await;
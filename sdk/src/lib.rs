@@ -29,6 +29,7 @@ pub mod client_error;
 pub mod client_provider;
 #[allow(deprecated)]
 pub mod clients;
+pub mod cluster;
 pub mod command;
 pub mod compression;
 pub mod confirmation;
@@ -47,6 +48,7 @@ pub mod partitioner;
 pub mod partitions;
 pub mod personal_access_tokens;
 pub mod quic;
+pub mod replication;
 pub mod segments;
 pub mod snapshot;
 pub mod stream_builder;
@@ -54,6 +56,8 @@ pub mod streams;
 pub mod system;
 pub mod tcp;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 pub mod utils;
 pub mod validatable;
+pub mod websocket;
@@ -0,0 +1,129 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, ACK_MESSAGES_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `AckMessages` command is used to acknowledge the messages pushed by the subscription created with `SubscribeMessages`
+/// in the `Acknowledged` ack mode, so the offset is stored on the server.
+/// It can be sent only on the connection (or the QUIC stream) of the subscription, and has no response.
+/// It has additional payload:
+/// - `partition_id` - partition ID from which the messages were pushed.
+/// - `offset` - offset of the last processed message, all the previous ones are acknowledged as well.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AckMessages {
+    /// Partition ID from which the messages were pushed.
+    pub partition_id: u32,
+    /// Offset of the last processed message, all the previous ones are acknowledged as well.
+    pub offset: u64,
+}
+
+impl Default for AckMessages {
+    fn default() -> Self {
+        AckMessages {
+            partition_id: 1,
+            offset: 0,
+        }
+    }
+}
+
+impl Command for AckMessages {
+    fn code(&self) -> u32 {
+        ACK_MESSAGES_CODE
+    }
+}
+
+impl Validatable<IggyError> for AckMessages {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.partition_id == 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for AckMessages {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(12);
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u64_le(self.offset);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<AckMessages, IggyError> {
+        if bytes.len() != 12 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let offset = u64::from_le_bytes(
+            bytes[4..12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let command = AckMessages {
+            partition_id,
+            offset,
+        };
+        command.validate()?;
+        Ok(command)
+    }
+}
+
+impl Display for AckMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.partition_id, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized() {
+        let command = AckMessages {
+            partition_id: 2,
+            offset: 100,
+        };
+
+        let deserialized = AckMessages::from_bytes(command.to_bytes()).unwrap();
+
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_without_partition_id() {
+        let command = AckMessages {
+            partition_id: 0,
+            offset: 100,
+        };
+
+        assert!(AckMessages::from_bytes(command.to_bytes()).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, GRANT_CREDIT_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `GrantCredit` command is used to extend the credit of the subscription created with `SubscribeMessages`.
+/// It can be sent only on the connection (or the QUIC stream) of the subscription, and has no response.
+/// It has additional payload:
+/// - `credit` - credit to add, counted in the unit chosen when subscribing.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GrantCredit {
+    /// Credit to add, counted in the unit chosen when subscribing.
+    pub credit: u64,
+}
+
+impl Command for GrantCredit {
+    fn code(&self) -> u32 {
+        GRANT_CREDIT_CODE
+    }
+}
+
+impl Validatable<IggyError> for GrantCredit {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for GrantCredit {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(8);
+        bytes.put_u64_le(self.credit);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<GrantCredit, IggyError> {
+        if bytes.len() != 8 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let credit = u64::from_le_bytes(
+            bytes[..8]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        Ok(GrantCredit { credit })
+    }
+}
+
+impl Display for GrantCredit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.credit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized() {
+        let command = GrantCredit { credit: 100 };
+
+        let deserialized = GrantCredit::from_bytes(command.to_bytes()).unwrap();
+
+        assert_eq!(deserialized, command);
+    }
+}
@@ -0,0 +1,535 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::models::header::{HeaderKey, HeaderKind, HeaderValue};
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::sizeable::Sizeable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::{from_utf8, Chars, FromStr};
+
+const MAX_EXPRESSION_LENGTH: usize = 4096;
+/// The parser is recursive, so the nesting of the parentheses is limited, as the filter is parsed by the server.
+const MAX_NESTING_DEPTH: usize = 32;
+
+/// `HeaderFilter` is the predicate over the message headers evaluated by the server when polling the messages,
+/// so that only the matching messages are returned to the consumer, e.g. `tenant == "acme" AND priority > 3`.
+///
+/// The expression consists of the comparisons of the header with a literal, which can be combined with `AND` and `OR`
+/// (`AND` takes precedence) and grouped with the parentheses, nested up to 32 levels. The supported operators are `==`, `!=`, `>`, `>=`, `<` and `<=`,
+/// while the literal is either a string in double quotes, an integer or float number, `true` or `false`.
+/// The comparison is false if the header is missing or its kind doesn't match the literal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderFilter {
+    expression: String,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Comparison {
+        key: HeaderKey,
+        operator: Operator,
+        literal: Literal,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    String(String),
+    Integer(i128),
+    Float(f64),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LeftParenthesis,
+    RightParenthesis,
+    Operator(Operator),
+    String(String),
+    Word(String),
+}
+
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl HeaderFilter {
+    /// Parses the filter from the expression.
+    pub fn new(expression: &str) -> Result<Self, IggyError> {
+        let expression = expression.trim();
+        if expression.is_empty() || expression.len() > MAX_EXPRESSION_LENGTH {
+            return Err(IggyError::InvalidHeaderFilter);
+        }
+
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            depth: 0,
+        };
+        let condition = parser.parse_or()?;
+        if parser.position != parser.tokens.len() {
+            return Err(IggyError::InvalidHeaderFilter);
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            condition,
+        })
+    }
+
+    /// Returns the expression of the filter.
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+
+    /// Checks whether the message with the given headers matches the filter.
+    pub fn matches(&self, headers: Option<&HashMap<HeaderKey, HeaderValue>>) -> bool {
+        self.condition.matches(headers)
+    }
+}
+
+impl Condition {
+    fn matches(&self, headers: Option<&HashMap<HeaderKey, HeaderValue>>) -> bool {
+        match self {
+            Condition::And(left, right) => left.matches(headers) && right.matches(headers),
+            Condition::Or(left, right) => left.matches(headers) || right.matches(headers),
+            Condition::Comparison {
+                key,
+                operator,
+                literal,
+            } => headers
+                .and_then(|headers| headers.get(key))
+                .and_then(|value| literal.compare(value))
+                .is_some_and(|ordering| operator.accepts(ordering)),
+        }
+    }
+}
+
+impl Operator {
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+        }
+    }
+}
+
+impl Literal {
+    fn parse(word: &str) -> Result<Self, IggyError> {
+        match word {
+            "true" => Ok(Literal::Bool(true)),
+            "false" => Ok(Literal::Bool(false)),
+            _ => {
+                if let Ok(value) = word.parse::<i128>() {
+                    return Ok(Literal::Integer(value));
+                }
+
+                match word.parse::<f64>() {
+                    Ok(value) if value.is_finite() => Ok(Literal::Float(value)),
+                    _ => Err(IggyError::InvalidHeaderFilter),
+                }
+            }
+        }
+    }
+
+    /// Compares the header value with the literal, returns `None` if the kinds don't match.
+    fn compare(&self, value: &HeaderValue) -> Option<Ordering> {
+        match self {
+            Literal::String(literal) => match value.kind {
+                HeaderKind::String => Some(value.as_str().ok()?.cmp(literal.as_str())),
+                HeaderKind::Raw => Some(value.as_raw().ok()?.cmp(literal.as_bytes())),
+                _ => None,
+            },
+            Literal::Bool(literal) => Some(value.as_bool().ok()?.cmp(literal)),
+            Literal::Integer(literal) => match Number::from_header_value(value)? {
+                Number::Integer(number) => Some(number.cmp(literal)),
+                Number::Float(number) => number.partial_cmp(&(*literal as f64)),
+            },
+            Literal::Float(literal) => match Number::from_header_value(value)? {
+                Number::Integer(number) => (number as f64).partial_cmp(literal),
+                Number::Float(number) => number.partial_cmp(literal),
+            },
+        }
+    }
+}
+
+impl Number {
+    fn from_header_value(value: &HeaderValue) -> Option<Self> {
+        let number = match value.kind {
+            HeaderKind::Int8 => Number::Integer(value.as_int8().ok()? as i128),
+            HeaderKind::Int16 => Number::Integer(value.as_int16().ok()? as i128),
+            HeaderKind::Int32 => Number::Integer(value.as_int32().ok()? as i128),
+            HeaderKind::Int64 => Number::Integer(value.as_int64().ok()? as i128),
+            HeaderKind::Int128 => Number::Integer(value.as_int128().ok()?),
+            HeaderKind::Uint8 => Number::Integer(value.as_uint8().ok()? as i128),
+            HeaderKind::Uint16 => Number::Integer(value.as_uint16().ok()? as i128),
+            HeaderKind::Uint32 => Number::Integer(value.as_uint32().ok()? as i128),
+            HeaderKind::Uint64 => Number::Integer(value.as_uint64().ok()? as i128),
+            HeaderKind::Uint128 => {
+                let value = value.as_uint128().ok()?;
+                match i128::try_from(value) {
+                    Ok(value) => Number::Integer(value),
+                    Err(_) => Number::Float(value as f64),
+                }
+            }
+            HeaderKind::Float32 => Number::Float(value.as_float32().ok()? as f64),
+            HeaderKind::Float64 => Number::Float(value.as_float64().ok()?),
+            HeaderKind::Raw | HeaderKind::String | HeaderKind::Bool => return None,
+        };
+        Some(number)
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<Condition, IggyError> {
+        let mut condition = self.parse_and()?;
+        while self.next_keyword_is("or") {
+            self.position += 1;
+            let right = self.parse_and()?;
+            condition = Condition::Or(Box::new(condition), Box::new(right));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, IggyError> {
+        let mut condition = self.parse_primary()?;
+        while self.next_keyword_is("and") {
+            self.position += 1;
+            let right = self.parse_primary()?;
+            condition = Condition::And(Box::new(condition), Box::new(right));
+        }
+        Ok(condition)
+    }
+
+    fn parse_primary(&mut self) -> Result<Condition, IggyError> {
+        match self.next_token()? {
+            Token::LeftParenthesis => {
+                if self.depth == MAX_NESTING_DEPTH {
+                    return Err(IggyError::InvalidHeaderFilter);
+                }
+
+                self.depth += 1;
+                let condition = self.parse_or()?;
+                self.depth -= 1;
+                match self.next_token()? {
+                    Token::RightParenthesis => Ok(condition),
+                    _ => Err(IggyError::InvalidHeaderFilter),
+                }
+            }
+            Token::Word(key) => {
+                let key = HeaderKey::new(&key).map_err(|_| IggyError::InvalidHeaderFilter)?;
+                let Token::Operator(operator) = self.next_token()? else {
+                    return Err(IggyError::InvalidHeaderFilter);
+                };
+                let literal = match self.next_token()? {
+                    Token::String(value) => Literal::String(value),
+                    Token::Word(word) => Literal::parse(&word)?,
+                    _ => return Err(IggyError::InvalidHeaderFilter),
+                };
+                Ok(Condition::Comparison {
+                    key,
+                    operator,
+                    literal,
+                })
+            }
+            _ => Err(IggyError::InvalidHeaderFilter),
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Token, IggyError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(IggyError::InvalidHeaderFilter)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_keyword_is(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.position), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, IggyError> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(char) = chars.next() {
+        let token = match char {
+            _ if char.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '"' => Token::String(read_string(&mut chars)?),
+            '=' | '!' | '>' | '<' => {
+                let has_equal_sign = chars.next_if_eq(&'=').is_some();
+                let operator = match (char, has_equal_sign) {
+                    ('=', true) => Operator::Equal,
+                    ('!', true) => Operator::NotEqual,
+                    ('>', false) => Operator::Greater,
+                    ('>', true) => Operator::GreaterOrEqual,
+                    ('<', false) => Operator::Less,
+                    ('<', true) => Operator::LessOrEqual,
+                    _ => return Err(IggyError::InvalidHeaderFilter),
+                };
+                Token::Operator(operator)
+            }
+            _ => {
+                let mut word = char.to_string();
+                while let Some(char) = chars.next_if(|char| is_word_char(*char)) {
+                    word.push(char);
+                }
+                Token::Word(word)
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn read_string(chars: &mut Peekable<Chars>) -> Result<String, IggyError> {
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(value),
+            Some('\\') => match chars.next() {
+                Some(char) => value.push(char),
+                None => return Err(IggyError::InvalidHeaderFilter),
+            },
+            Some(char) => value.push(char),
+            None => return Err(IggyError::InvalidHeaderFilter),
+        }
+    }
+}
+
+fn is_word_char(char: char) -> bool {
+    !char.is_whitespace() && !matches!(char, '(' | ')' | '"' | '=' | '!' | '>' | '<')
+}
+
+impl Sizeable for HeaderFilter {
+    fn get_size_bytes(&self) -> IggyByteSize {
+        IggyByteSize::from(4 + self.expression.len() as u64)
+    }
+}
+
+impl BytesSerializable for HeaderFilter {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(self.get_size_bytes().as_bytes_usize());
+        bytes.put_u32_le(self.expression.len() as u32);
+        bytes.put_slice(self.expression.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidHeaderFilter);
+        }
+
+        let length = u32::from_le_bytes(
+            bytes[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ) as usize;
+        let expression = bytes
+            .get(4..4 + length)
+            .ok_or(IggyError::InvalidHeaderFilter)?;
+        let expression = from_utf8(expression).map_err(|_| IggyError::InvalidUtf8)?;
+        HeaderFilter::new(expression)
+    }
+}
+
+impl FromStr for HeaderFilter {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        HeaderFilter::new(input)
+    }
+}
+
+impl Display for HeaderFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl Serialize for HeaderFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderFilter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        HeaderFilter::new(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> HashMap<HeaderKey, HeaderValue> {
+        let mut headers = HashMap::new();
+        headers.insert(
+            HeaderKey::new("tenant").unwrap(),
+            HeaderValue::from_str("acme").unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new("priority").unwrap(),
+            HeaderValue::from_uint8(5).unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new("score").unwrap(),
+            HeaderValue::from_float64(0.75).unwrap(),
+        );
+        headers.insert(
+            HeaderKey::new("urgent").unwrap(),
+            HeaderValue::from_bool(false).unwrap(),
+        );
+        headers
+    }
+
+    fn matches(expression: &str) -> bool {
+        HeaderFilter::new(expression)
+            .unwrap()
+            .matches(Some(&headers()))
+    }
+
+    #[test]
+    fn should_compare_headers_of_different_kinds() {
+        assert!(matches(r#"tenant == "acme""#));
+        assert!(!matches(r#"tenant != "acme""#));
+        assert!(matches(r#"tenant < "beta""#));
+        assert!(matches("priority > 3"));
+        assert!(matches("priority >= 5"));
+        assert!(!matches("priority < 5"));
+        assert!(matches("priority <= 5.5"));
+        assert!(matches("score > 0.5"));
+        assert!(matches("score < 1"));
+        assert!(matches("urgent == false"));
+        assert!(matches("urgent != true"));
+    }
+
+    #[test]
+    fn should_combine_comparisons() {
+        assert!(matches(r#"tenant == "acme" AND priority > 3"#));
+        assert!(!matches(r#"tenant == "acme" and priority > 5"#));
+        assert!(matches(r#"tenant == "other" OR priority > 3"#));
+        assert!(matches(
+            r#"tenant == "other" AND priority > 3 OR urgent == false"#
+        ));
+        assert!(!matches(
+            r#"tenant == "other" AND (priority > 3 OR urgent == false)"#
+        ));
+    }
+
+    #[test]
+    fn should_not_match_missing_headers_or_different_kinds() {
+        assert!(!matches(r#"region == "eu""#));
+        assert!(!matches(r#"region != "eu""#));
+        assert!(!matches(r#"priority == "5""#));
+        assert!(!matches("tenant > 1"));
+        assert!(!HeaderFilter::new("priority > 3").unwrap().matches(None));
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_expression() {
+        let expressions = [
+            "",
+            "tenant",
+            "tenant ==",
+            r#"tenant = "acme""#,
+            r#"tenant == "acme"#,
+            "priority > three",
+            r#"(tenant == "acme""#,
+            r#"tenant == "acme" AND"#,
+            r#"tenant == "acme" priority > 3"#,
+        ];
+        for expression in expressions {
+            assert_eq!(
+                HeaderFilter::new(expression),
+                Err(IggyError::InvalidHeaderFilter),
+                "expression: {expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_limit_nesting_depth() {
+        let nested =
+            |depth: usize| format!("{}priority > 3{}", "(".repeat(depth), ")".repeat(depth));
+
+        assert!(HeaderFilter::new(&nested(MAX_NESTING_DEPTH)).is_ok());
+        assert_eq!(
+            HeaderFilter::new(&nested(MAX_NESTING_DEPTH + 1)),
+            Err(IggyError::InvalidHeaderFilter)
+        );
+        assert_eq!(
+            HeaderFilter::new(&nested(500)),
+            Err(IggyError::InvalidHeaderFilter)
+        );
+    }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let filter = HeaderFilter::new(r#"tenant == "acme" AND priority > 3"#).unwrap();
+        let bytes = filter.to_bytes();
+        assert_eq!(bytes.len(), filter.get_size_bytes().as_bytes_usize());
+        assert_eq!(HeaderFilter::from_bytes(bytes).unwrap(), filter);
+    }
+}
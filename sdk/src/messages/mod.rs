@@ -16,9 +16,16 @@
  * under the License.
  */
 
+pub mod ack_messages;
 pub mod flush_unsaved_buffer;
+pub mod grant_credit;
+pub mod header_filter;
+pub mod init_producer;
+pub mod nack_message;
+pub mod poll_assigned_messages;
 pub mod poll_messages;
 pub mod send_messages;
+pub mod subscribe_messages;
 
 const MAX_HEADERS_SIZE: u32 = 100 * 1000;
 pub const MAX_PAYLOAD_SIZE: u32 = 10 * 1000 * 1000;
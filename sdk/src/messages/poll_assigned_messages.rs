@@ -0,0 +1,263 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, POLL_ASSIGNED_MESSAGES_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::messages::header_filter::HeaderFilter;
+use crate::messages::poll_messages::{IsolationLevel, PollingStrategy};
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `PollAssignedMessages` command is used to poll messages from all the partitions assigned to the consumer group member at once.
+/// The messages are returned separately for each of the partitions.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `group_id` - unique consumer group ID (numeric or name), which the client has joined.
+/// - `strategy` - polling strategy which specifies from where to start polling messages in each partition.
+/// - `count` - number of messages to poll from each partition.
+/// - `auto_commit` - whether to commit offset on the server automatically after polling the messages.
+/// - `isolation_level` - whether to return the messages sent as a part of the open or aborted transactions.
+/// - `filter` - optional filter over the message headers, only the matching messages are returned.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PollAssignedMessages {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Unique consumer group ID (numeric or name), which the client has joined.
+    #[serde(skip)]
+    pub group_id: Identifier,
+    /// Polling strategy which specifies from where to start polling messages in each partition.
+    pub strategy: PollingStrategy,
+    /// Number of messages to poll from each partition.
+    pub count: u32,
+    /// Whether to commit offset on the server automatically after polling the messages.
+    pub auto_commit: bool,
+    /// Whether to return the messages sent as a part of the open or aborted transactions.
+    #[serde(default)]
+    pub isolation_level: IsolationLevel,
+    /// Optional filter over the message headers, only the matching messages are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<HeaderFilter>,
+}
+
+impl Default for PollAssignedMessages {
+    fn default() -> Self {
+        PollAssignedMessages {
+            stream_id: Identifier::default(),
+            topic_id: Identifier::default(),
+            group_id: Identifier::default(),
+            strategy: PollingStrategy::default(),
+            count: 10,
+            auto_commit: false,
+            isolation_level: IsolationLevel::default(),
+            filter: None,
+        }
+    }
+}
+
+impl Command for PollAssignedMessages {
+    fn code(&self) -> u32 {
+        POLL_ASSIGNED_MESSAGES_CODE
+    }
+}
+
+impl Validatable<IggyError> for PollAssignedMessages {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for PollAssignedMessages {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let group_id_bytes = self.group_id.to_bytes();
+        let strategy_bytes = self.strategy.to_bytes();
+        let filter_bytes = self.filter.as_ref().map(|filter| filter.to_bytes());
+        let mut bytes = BytesMut::with_capacity(
+            6 + stream_id_bytes.len()
+                + topic_id_bytes.len()
+                + group_id_bytes.len()
+                + strategy_bytes.len()
+                + filter_bytes.as_ref().map_or(0, |bytes| bytes.len()),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_slice(&group_id_bytes);
+        bytes.put_slice(&strategy_bytes);
+        bytes.put_u32_le(self.count);
+        bytes.put_u8(if self.auto_commit { 1 } else { 0 });
+        bytes.put_u8(self.isolation_level.as_code());
+        if let Some(filter_bytes) = filter_bytes {
+            bytes.put_slice(&filter_bytes);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<PollAssignedMessages, IggyError> {
+        if bytes.len() < 24 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let group_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += group_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 15 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let strategy = PollingStrategy::from_bytes(bytes.slice(position..position + 9))?;
+        let count = u32::from_le_bytes(
+            bytes[position + 9..position + 13]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let auto_commit = matches!(bytes[position + 13], 1);
+        let isolation_level = IsolationLevel::from_code(bytes[position + 14])?;
+        let filter = match bytes.len() > position + 15 {
+            true => Some(HeaderFilter::from_bytes(bytes.slice(position + 15..))?),
+            false => None,
+        };
+        let command = PollAssignedMessages {
+            stream_id,
+            topic_id,
+            group_id,
+            strategy,
+            count,
+            auto_commit,
+            isolation_level,
+            filter,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for PollAssignedMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}",
+            self.stream_id,
+            self.topic_id,
+            self.group_id,
+            self.strategy,
+            self.count,
+            self.auto_commit,
+            self.isolation_level,
+            self.filter
+                .as_ref()
+                .map(|filter| filter.as_str())
+                .unwrap_or_default()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = PollAssignedMessages {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(2).unwrap(),
+            group_id: Identifier::named("group").unwrap(),
+            strategy: PollingStrategy::offset(3),
+            count: 4,
+            auto_commit: true,
+            isolation_level: IsolationLevel::ReadCommitted,
+            filter: Some(HeaderFilter::new("priority > 3").unwrap()),
+        };
+
+        let bytes = command.to_bytes();
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let group_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += group_id.get_size_bytes().as_bytes_usize();
+        let strategy = PollingStrategy::from_bytes(bytes.slice(position..position + 9)).unwrap();
+        let count = u32::from_le_bytes(bytes[position + 9..position + 13].try_into().unwrap());
+        let auto_commit = bytes[position + 13];
+        let isolation_level = IsolationLevel::from_code(bytes[position + 14]).unwrap();
+        let filter = HeaderFilter::from_bytes(bytes.slice(position + 15..)).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(group_id, command.group_id);
+        assert_eq!(strategy, command.strategy);
+        assert_eq!(count, command.count);
+        assert_eq!(auto_commit, 1);
+        assert_eq!(isolation_level, command.isolation_level);
+        assert_eq!(Some(filter), command.filter);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let group_id = Identifier::numeric(3).unwrap();
+        let strategy = PollingStrategy::next();
+        let count = 4u32;
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let group_id_bytes = group_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            15 + stream_id_bytes.len() + topic_id_bytes.len() + group_id_bytes.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_slice(&group_id_bytes);
+        bytes.put_slice(&strategy.to_bytes());
+        bytes.put_u32_le(count);
+        bytes.put_u8(0);
+        bytes.put_u8(IsolationLevel::ReadCommitted.as_code());
+
+        let command = PollAssignedMessages::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.group_id, group_id);
+        assert_eq!(command.strategy, strategy);
+        assert_eq!(command.count, count);
+        assert!(!command.auto_commit);
+        assert_eq!(command.isolation_level, IsolationLevel::ReadCommitted);
+        assert_eq!(command.filter, None);
+    }
+}
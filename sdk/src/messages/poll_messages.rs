@@ -21,6 +21,8 @@ use crate::command::{Command, POLL_MESSAGES_CODE};
 use crate::consumer::{Consumer, ConsumerKind};
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::messages::header_filter::HeaderFilter;
+use crate::utils::duration::IggyDuration;
 use crate::utils::sizeable::Sizeable;
 use crate::utils::timestamp::IggyTimestamp;
 use crate::validatable::Validatable;
@@ -39,6 +41,9 @@ use std::str::FromStr;
 /// - `strategy` - polling strategy which specifies from where to start polling messages.
 /// - `count` - number of messages to poll.
 /// - `auto_commit` - whether to commit offset on the server automatically after polling the messages.
+/// - `isolation_level` - whether to return the messages sent as a part of the open or aborted transactions.
+/// - `filter` - optional filter over the message headers, only the matching messages are returned.
+/// - `wait` - how long the server may hold the request until enough messages are available.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct PollMessages {
     /// Consumer which will poll messages. Either regular consumer or consumer group.
@@ -62,6 +67,16 @@ pub struct PollMessages {
     #[serde(default)]
     /// Whether to commit offset on the server automatically after polling the messages.
     pub auto_commit: bool,
+    #[serde(default)]
+    /// Whether to return the messages sent as a part of the open or aborted transactions.
+    pub isolation_level: IsolationLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional filter over the message headers, only the matching messages are returned.
+    /// The skipped messages are still taken into account when committing the offset.
+    pub filter: Option<HeaderFilter>,
+    /// How long the server may hold the request until enough messages are available.
+    #[serde(default, flatten)]
+    pub wait: PollingWait,
 }
 
 /// `PollingStrategy` specifies from where to start polling messages.
@@ -101,6 +116,58 @@ pub enum PollingKind {
     Next,
 }
 
+/// `PollingWait` specifies how long the server may hold the poll request until enough messages are available.
+/// The request is completed as soon as any of the thresholds is reached or `max_wait` expires,
+/// in which case the messages available so far (possibly none) are returned.
+/// It has the following fields:
+/// - `max_wait` - maximum time to wait for the messages, zero means returning immediately.
+/// - `min_messages` - number of messages to wait for, if neither of the thresholds is set, a single message is enough.
+/// - `min_bytes` - size of messages to wait for.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Copy, Clone)]
+pub struct PollingWait {
+    /// Maximum time to wait for the messages, zero means returning immediately.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default)]
+    pub max_wait: IggyDuration,
+    /// Number of messages to wait for, if neither of the thresholds is set, a single message is enough.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default)]
+    pub min_messages: u32,
+    /// Size of messages to wait for.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default)]
+    pub min_bytes: u32,
+}
+
+/// `IsolationLevel` specifies which of the messages sent as a part of the transactions are visible to the consumer.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationLevel {
+    #[default]
+    /// Return all the messages, including the ones sent as a part of the open or aborted transactions.
+    ReadUncommitted,
+    /// Return only the messages sent outside of any transaction or as a part of the committed one.
+    /// Polling stops before the first message of the oldest transaction which is still open in the partition.
+    ReadCommitted,
+}
+
+/// `PollMessagesOptions` specifies which of the messages are returned when polling them, apart from the strategy and count.
+/// It has the following fields:
+/// - `isolation_level` - whether to return the messages sent as a part of the open or aborted transactions.
+/// - `filter` - optional filter over the message headers, only the matching messages are returned.
+/// - `wait` - how long the server may hold the request until enough messages are available.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct PollMessagesOptions {
+    /// Whether to return the messages sent as a part of the open or aborted transactions.
+    pub isolation_level: IsolationLevel,
+    /// Optional filter over the message headers, only the matching messages are returned.
+    /// The skipped messages are still taken into account when committing the offset.
+    pub filter: Option<HeaderFilter>,
+    /// How long the server may hold the request until enough messages are available.
+    pub wait: PollingWait,
+}
+
 impl Default for PollMessages {
     fn default() -> Self {
         Self {
@@ -111,6 +178,9 @@ impl Default for PollMessages {
             strategy: default_strategy(),
             count: default_count(),
             auto_commit: false,
+            isolation_level: IsolationLevel::default(),
+            filter: None,
+            wait: PollingWait::default(),
         }
     }
 }
@@ -205,6 +275,75 @@ impl PollingStrategy {
     }
 }
 
+impl PollingWait {
+    /// Return immediately, even if there are no messages available.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Wait up to `max_wait` until at least `min_messages` messages are available.
+    pub fn messages(max_wait: IggyDuration, min_messages: u32) -> Self {
+        Self {
+            max_wait,
+            min_messages,
+            min_bytes: 0,
+        }
+    }
+
+    /// Wait up to `max_wait` until at least `min_bytes` of messages are available.
+    pub fn bytes(max_wait: IggyDuration, min_bytes: u32) -> Self {
+        Self {
+            max_wait,
+            min_messages: 0,
+            min_bytes,
+        }
+    }
+
+    /// Returns whether the server may hold the request at all.
+    pub fn is_enabled(&self) -> bool {
+        // `IggyDuration::is_zero` ignores the fraction of a second, while the sub-second waits are perfectly valid.
+        self.max_wait.as_micros() > 0
+    }
+
+    /// Returns whether the polled messages are enough to complete the request.
+    /// The full batch of `count` messages is always enough, as no more of them could be returned anyway.
+    pub fn is_satisfied(&self, count: u32, messages_count: u32, size_bytes: u64) -> bool {
+        if !self.is_enabled() || messages_count >= count {
+            return true;
+        }
+
+        if self.min_messages == 0 && self.min_bytes == 0 {
+            return messages_count > 0;
+        }
+
+        (self.min_messages > 0 && messages_count >= self.min_messages)
+            || (self.min_bytes > 0 && size_bytes >= self.min_bytes as u64)
+    }
+}
+
+impl PollMessagesOptions {
+    /// Return only the messages sent outside of any transaction or as a part of the committed one.
+    pub fn read_committed(self) -> Self {
+        Self {
+            isolation_level: IsolationLevel::ReadCommitted,
+            ..self
+        }
+    }
+
+    /// Return only the messages matching the filter over their headers.
+    pub fn filter(self, filter: HeaderFilter) -> Self {
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Let the server hold the request until enough messages are available.
+    pub fn wait(self, wait: PollingWait) -> Self {
+        Self { wait, ..self }
+    }
+}
+
 impl PollingKind {
     /// Returns code of the polling kind.
     pub fn as_code(&self) -> u8 {
@@ -230,6 +369,45 @@ impl PollingKind {
     }
 }
 
+impl IsolationLevel {
+    /// Returns code of the isolation level.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            IsolationLevel::ReadUncommitted => 1,
+            IsolationLevel::ReadCommitted => 2,
+        }
+    }
+
+    /// Returns isolation level from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(IsolationLevel::ReadUncommitted),
+            2 => Ok(IsolationLevel::ReadCommitted),
+            _ => Err(IggyError::InvalidIsolationLevel),
+        }
+    }
+}
+
+impl FromStr for IsolationLevel {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "u" | "read_uncommitted" => Ok(IsolationLevel::ReadUncommitted),
+            "c" | "read_committed" => Ok(IsolationLevel::ReadCommitted),
+            _ => Err(IggyError::InvalidIsolationLevel),
+        }
+    }
+}
+
+impl Display for IsolationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IsolationLevel::ReadUncommitted => write!(f, "read_uncommitted"),
+            IsolationLevel::ReadCommitted => write!(f, "read_committed"),
+        }
+    }
+}
+
 impl FromStr for PollingKind {
     type Err = IggyError;
     fn from_str(input: &str) -> Result<Self, Self::Err> {
@@ -266,6 +444,9 @@ impl BytesSerializable for PollMessages {
             &self.strategy,
             self.count,
             self.auto_commit,
+            self.isolation_level,
+            self.filter.as_ref(),
+            &self.wait,
         )
     }
 
@@ -313,6 +494,63 @@ impl BytesSerializable for PollMessages {
         );
         let auto_commit = bytes[position + 12];
         let auto_commit = matches!(auto_commit, 1);
+        // The isolation level is optional to remain compatible with the clients which don't send it.
+        let isolation_level = match bytes.get(position + 13) {
+            Some(code) => IsolationLevel::from_code(*code)?,
+            None => IsolationLevel::default(),
+        };
+        // The filter is optional as well, and follows the isolation level, where the zero length means no filter.
+        position += 14;
+        let filter = match bytes.len() > position {
+            true => {
+                let filter_length = u32::from_le_bytes(
+                    bytes
+                        .get(position..position + 4)
+                        .ok_or(IggyError::InvalidCommand)?
+                        .try_into()
+                        .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                );
+                match filter_length {
+                    0 => {
+                        position += 4;
+                        None
+                    }
+                    _ => {
+                        let filter = HeaderFilter::from_bytes(bytes.slice(position..))?;
+                        position += filter.get_size_bytes().as_bytes_usize();
+                        Some(filter)
+                    }
+                }
+            }
+            false => None,
+        };
+        // The wait is optional and follows the filter.
+        let wait = match bytes.len() > position {
+            true => {
+                if bytes.len() < position + 16 {
+                    return Err(IggyError::InvalidCommand);
+                }
+                PollingWait {
+                    max_wait: u64::from_le_bytes(
+                        bytes[position..position + 8]
+                            .try_into()
+                            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                    )
+                    .into(),
+                    min_messages: u32::from_le_bytes(
+                        bytes[position + 8..position + 12]
+                            .try_into()
+                            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                    ),
+                    min_bytes: u32::from_le_bytes(
+                        bytes[position + 12..position + 16]
+                            .try_into()
+                            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                    ),
+                }
+            }
+            false => PollingWait::default(),
+        };
         let command = PollMessages {
             consumer,
             stream_id,
@@ -321,12 +559,16 @@ impl BytesSerializable for PollMessages {
             strategy,
             count,
             auto_commit,
+            isolation_level,
+            filter,
+            wait,
         };
         Ok(command)
     }
 }
 
 // This method is used by the new version of `IggyClient` to serialize `PollMessages` without cloning the args.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn as_bytes(
     stream_id: &Identifier,
     topic_id: &Identifier,
@@ -335,13 +577,16 @@ pub(crate) fn as_bytes(
     strategy: &PollingStrategy,
     count: u32,
     auto_commit: bool,
+    isolation_level: IsolationLevel,
+    filter: Option<&HeaderFilter>,
+    wait: &PollingWait,
 ) -> Bytes {
     let consumer_bytes = consumer.to_bytes();
     let stream_id_bytes = stream_id.to_bytes();
     let topic_id_bytes = topic_id.to_bytes();
     let strategy_bytes = strategy.to_bytes();
     let mut bytes = BytesMut::with_capacity(
-        9 + consumer_bytes.len()
+        10 + consumer_bytes.len()
             + stream_id_bytes.len()
             + topic_id_bytes.len()
             + strategy_bytes.len(),
@@ -361,6 +606,17 @@ pub(crate) fn as_bytes(
     } else {
         bytes.put_u8(0);
     }
+    bytes.put_u8(isolation_level.as_code());
+    if let Some(filter) = filter {
+        bytes.put_slice(&filter.to_bytes());
+    } else if wait.is_enabled() {
+        bytes.put_u32_le(0);
+    }
+    if wait.is_enabled() {
+        bytes.put_u64_le(wait.max_wait.as_micros());
+        bytes.put_u32_le(wait.min_messages);
+        bytes.put_u32_le(wait.min_bytes);
+    }
 
     bytes.freeze()
 }
@@ -369,14 +625,30 @@ impl Display for PollMessages {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
             self.consumer,
             self.stream_id,
             self.topic_id,
             self.partition_id.unwrap_or(0),
             self.strategy,
             self.count,
-            auto_commit_to_string(self.auto_commit)
+            auto_commit_to_string(self.auto_commit),
+            self.isolation_level,
+            self.filter
+                .as_ref()
+                .map(|filter| filter.as_str())
+                .unwrap_or_default(),
+            self.wait
+        )
+    }
+}
+
+impl Display for PollingWait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}",
+            self.max_wait, self.min_messages, self.min_bytes
         )
     }
 }
@@ -433,6 +705,9 @@ mod tests {
             strategy: PollingStrategy::offset(2),
             count: 3,
             auto_commit: true,
+            isolation_level: IsolationLevel::ReadCommitted,
+            filter: Some(HeaderFilter::new(r#"tenant == "acme""#).unwrap()),
+            wait: PollingWait::messages(IggyDuration::ONE_SECOND, 2),
         };
 
         let bytes = command.to_bytes();
@@ -459,6 +734,13 @@ mod tests {
         let count = u32::from_le_bytes(bytes[position + 8..position + 12].try_into().unwrap());
         let auto_commit = bytes[position + 12];
         let auto_commit = matches!(auto_commit, 1);
+        let isolation_level = IsolationLevel::from_code(bytes[position + 13]).unwrap();
+        let filter = HeaderFilter::from_bytes(bytes.slice(position + 14..)).unwrap();
+        position += 14 + filter.get_size_bytes().as_bytes_usize();
+        let max_wait = u64::from_le_bytes(bytes[position..position + 8].try_into().unwrap());
+        let min_messages =
+            u32::from_le_bytes(bytes[position + 8..position + 12].try_into().unwrap());
+        let min_bytes = u32::from_le_bytes(bytes[position + 12..position + 16].try_into().unwrap());
 
         assert!(!bytes.is_empty());
         assert_eq!(consumer, command.consumer);
@@ -468,6 +750,11 @@ mod tests {
         assert_eq!(strategy, command.strategy);
         assert_eq!(count, command.count);
         assert_eq!(auto_commit, command.auto_commit);
+        assert_eq!(isolation_level, command.isolation_level);
+        assert_eq!(Some(filter), command.filter);
+        assert_eq!(IggyDuration::from(max_wait), command.wait.max_wait);
+        assert_eq!(min_messages, command.wait.min_messages);
+        assert_eq!(min_bytes, command.wait.min_bytes);
     }
 
     #[test]
@@ -511,5 +798,38 @@ mod tests {
         assert_eq!(command.strategy, strategy);
         assert_eq!(command.count, count);
         assert_eq!(command.auto_commit, auto_commit);
+        assert_eq!(command.isolation_level, IsolationLevel::ReadUncommitted);
+        assert_eq!(command.filter, None);
+        assert_eq!(command.wait, PollingWait::none());
+    }
+
+    #[test]
+    fn wait_without_filter_should_be_deserialized_from_bytes() {
+        let command = PollMessages {
+            wait: PollingWait::bytes(IggyDuration::ONE_SECOND, 1000),
+            ..PollMessages::default()
+        };
+
+        let deserialized = PollMessages::from_bytes(command.to_bytes()).unwrap();
+
+        assert_eq!(deserialized.filter, None);
+        assert_eq!(deserialized.wait, command.wait);
+    }
+
+    #[test]
+    fn wait_should_be_satisfied_by_any_threshold_or_the_full_batch() {
+        let wait = PollingWait {
+            max_wait: IggyDuration::ONE_SECOND,
+            min_messages: 5,
+            min_bytes: 1000,
+        };
+
+        assert!(!wait.is_satisfied(10, 4, 999));
+        assert!(wait.is_satisfied(10, 5, 0));
+        assert!(wait.is_satisfied(10, 1, 1000));
+        assert!(wait.is_satisfied(3, 3, 0));
+        assert!(!PollingWait::messages(IggyDuration::ONE_SECOND, 0).is_satisfied(10, 0, 0));
+        assert!(PollingWait::messages(IggyDuration::ONE_SECOND, 0).is_satisfied(10, 1, 0));
+        assert!(PollingWait::none().is_satisfied(10, 0, 0));
     }
 }
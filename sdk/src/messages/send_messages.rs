@@ -17,7 +17,7 @@
  */
 
 use crate::bytes_serializable::BytesSerializable;
-use crate::command::{Command, SEND_MESSAGES_CODE};
+use crate::command::{Command, SEND_IDEMPOTENT_MESSAGES_CODE, SEND_MESSAGES_CODE};
 use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::messages::{MAX_HEADERS_SIZE, MAX_PAYLOAD_SIZE};
@@ -44,6 +44,7 @@ const EMPTY_KEY_VALUE: Vec<u8> = vec![];
 /// - `topic_id` - unique topic ID (numeric or name).
 /// - `partitioning` - to which partition the messages should be sent - either provided by the client or calculated by the server.
 /// - `messages` - collection of messages to be sent.
+/// - `producer_sequence` - optional producer ID and sequence number of the batch, used by the server to discard the retried batches.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct SendMessages {
     /// Unique stream ID (numeric or name).
@@ -56,6 +57,21 @@ pub struct SendMessages {
     pub partitioning: Partitioning,
     /// Collection of messages to be sent.
     pub messages: Vec<Message>,
+    /// Optional producer ID and sequence number of the batch, used by the server to discard the retried batches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub producer_sequence: Option<ProducerSequence>,
+}
+
+/// `ProducerSequence` identifies the batch sent by the idempotent producer.
+/// It has the following fields:
+/// - `producer_id` - unique producer ID assigned by the server.
+/// - `sequence` - sequence number of the batch, incremented by the producer for each batch sent to the same partition.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct ProducerSequence {
+    /// Unique producer ID assigned by the server.
+    pub producer_id: u64,
+    /// Sequence number of the batch, incremented by the producer for each batch sent to the same partition.
+    pub sequence: u64,
 }
 
 /// `Partitioning` is used to specify to which partition the messages should be sent.
@@ -135,6 +151,7 @@ impl Default for SendMessages {
             topic_id: Identifier::default(),
             partitioning: Partitioning::default(),
             messages: vec![Message::default()],
+            producer_sequence: None,
         }
     }
 }
@@ -229,7 +246,10 @@ impl Sizeable for Partitioning {
 
 impl Command for SendMessages {
     fn code(&self) -> u32 {
-        SEND_MESSAGES_CODE
+        match self.producer_sequence {
+            Some(_) => SEND_IDEMPOTENT_MESSAGES_CODE,
+            None => SEND_MESSAGES_CODE,
+        }
     }
 }
 
@@ -246,6 +266,16 @@ impl Validatable<IggyError> for SendMessages {
             return Err(IggyError::InvalidKeyValueLength);
         }
 
+        if let Some(producer_sequence) = &self.producer_sequence {
+            if producer_sequence.producer_id == 0 {
+                return Err(IggyError::InvalidProducerId);
+            }
+
+            if self.partitioning.kind != PartitioningKind::PartitionId {
+                return Err(IggyError::InvalidIdempotentPartitioning);
+            }
+        }
+
         let mut headers_size = 0;
         let mut payload_size = 0;
         for message in &self.messages {
@@ -263,10 +293,6 @@ impl Validatable<IggyError> for SendMessages {
             }
         }
 
-        if payload_size == 0 {
-            return Err(IggyError::EmptyMessagePayload);
-        }
-
         Ok(())
     }
 }
@@ -425,10 +451,6 @@ impl BytesSerializable for Message {
                 .try_into()
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         );
-        if payload_length == 0 {
-            return Err(IggyError::EmptyMessagePayload);
-        }
-
         let payload = bytes.slice(
             24 + headers_length as usize..24 + headers_length as usize + payload_length as usize,
         );
@@ -450,6 +472,7 @@ pub(crate) fn as_bytes(
     stream_id: &Identifier,
     topic_id: &Identifier,
     partitioning: &Partitioning,
+    producer_sequence: Option<&ProducerSequence>,
     messages: &[Message],
 ) -> Bytes {
     let messages_size = messages
@@ -463,11 +486,16 @@ pub(crate) fn as_bytes(
         stream_id_bytes.len()
             + topic_id_bytes.len()
             + key_bytes.len()
+            + 16
             + messages_size.as_bytes_usize(),
     );
     bytes.put_slice(&stream_id_bytes);
     bytes.put_slice(&topic_id_bytes);
     bytes.put_slice(&key_bytes);
+    if let Some(producer_sequence) = producer_sequence {
+        bytes.put_u64_le(producer_sequence.producer_id);
+        bytes.put_u64_le(producer_sequence.sequence);
+    }
     for message in messages {
         bytes.put_slice(&message.to_bytes());
     }
@@ -500,11 +528,26 @@ impl BytesSerializable for SendMessages {
             &self.stream_id,
             &self.topic_id,
             &self.partitioning,
+            self.producer_sequence.as_ref(),
             &self.messages,
         )
     }
 
     fn from_bytes(bytes: Bytes) -> Result<SendMessages, IggyError> {
+        SendMessages::from_bytes_with_producer_sequence(bytes, false)
+    }
+}
+
+impl SendMessages {
+    /// Deserializes the `SendMessages` command sent by the idempotent producer, containing the producer ID and the sequence number of the batch.
+    pub fn from_idempotent_bytes(bytes: Bytes) -> Result<SendMessages, IggyError> {
+        SendMessages::from_bytes_with_producer_sequence(bytes, true)
+    }
+
+    fn from_bytes_with_producer_sequence(
+        bytes: Bytes,
+        has_producer_sequence: bool,
+    ) -> Result<SendMessages, IggyError> {
         if bytes.len() < 11 {
             return Err(IggyError::InvalidCommand);
         }
@@ -516,6 +559,29 @@ impl BytesSerializable for SendMessages {
         position += topic_id.get_size_bytes().as_bytes_usize();
         let key = Partitioning::from_bytes(bytes.slice(position..))?;
         position += key.get_size_bytes().as_bytes_usize();
+        let producer_sequence = if has_producer_sequence {
+            if bytes.len() < position + 16 {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            let producer_id = u64::from_le_bytes(
+                bytes[position..position + 8]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            );
+            let sequence = u64::from_le_bytes(
+                bytes[position + 8..position + 16]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            );
+            position += 16;
+            Some(ProducerSequence {
+                producer_id,
+                sequence,
+            })
+        } else {
+            None
+        };
         let messages_payloads = bytes.slice(position..);
         position = 0;
         let mut messages = Vec::new();
@@ -530,6 +596,7 @@ impl BytesSerializable for SendMessages {
             topic_id,
             partitioning: key,
             messages,
+            producer_sequence,
         };
         Ok(command)
     }
@@ -548,7 +615,15 @@ impl Display for SendMessages {
                 .iter()
                 .map(Message::get_size_bytes)
                 .sum::<IggyByteSize>(),
-        )
+        )?;
+        if let Some(producer_sequence) = &self.producer_sequence {
+            write!(
+                f,
+                "|producer_id:{}|sequence:{}",
+                producer_sequence.producer_id, producer_sequence.sequence
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -594,6 +669,7 @@ mod tests {
             topic_id: Identifier::numeric(2).unwrap(),
             partitioning: Partitioning::partition_id(4),
             messages,
+            producer_sequence: None,
         };
 
         let bytes = command.to_bytes();
@@ -672,6 +748,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn idempotent_command_should_be_serialized_and_deserialized_with_producer_sequence() {
+        let command = SendMessages {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(2).unwrap(),
+            partitioning: Partitioning::partition_id(4),
+            messages: vec![
+                Message::new(Some(1), "hello 1".into(), None),
+                Message::new(Some(2), "hello 2".into(), None),
+            ],
+            producer_sequence: Some(ProducerSequence {
+                producer_id: 5,
+                sequence: 6,
+            }),
+        };
+
+        let bytes = command.to_bytes();
+        let deserialized_command = SendMessages::from_idempotent_bytes(bytes).unwrap();
+
+        assert_eq!(command.code(), SEND_IDEMPOTENT_MESSAGES_CODE);
+        assert_eq!(deserialized_command, command);
+    }
+
+    #[test]
+    fn idempotent_command_should_require_partition_id() {
+        let command = SendMessages {
+            partitioning: Partitioning::balanced(),
+            producer_sequence: Some(ProducerSequence {
+                producer_id: 1,
+                sequence: 1,
+            }),
+            ..SendMessages::default()
+        };
+
+        assert_eq!(
+            command.validate(),
+            Err(IggyError::InvalidIdempotentPartitioning)
+        );
+    }
+
     #[test]
     fn key_of_type_balanced_should_have_empty_value() {
         let key = Partitioning::balanced();
@@ -0,0 +1,426 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, SUBSCRIBE_MESSAGES_CODE};
+use crate::consumer::{Consumer, ConsumerKind};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::messages::header_filter::HeaderFilter;
+use crate::messages::poll_messages::{IsolationLevel, PollingKind, PollingStrategy};
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// `SubscribeMessages` command is used to subscribe to the messages appended to a topic in a stream.
+/// Instead of responding once, the server keeps pushing the batches of messages to the connection (or the QUIC stream)
+/// for as long as the client keeps granting the credit, which makes it the flow control of the subscription.
+/// It has additional payload:
+/// - `consumer` - consumer which will receive messages. Either regular consumer or consumer group.
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partition_id` - partition ID from which messages will be pushed. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
+/// - `strategy` - polling strategy which specifies from where to start pushing messages.
+/// - `count` - maximum number of messages in a single batch.
+/// - `ack_mode` - when the offsets of the pushed messages are stored on the server.
+/// - `credit_kind` - whether the credit is counted in messages or bytes.
+/// - `credit` - initial credit, which can be extended later on with `GrantCredit`.
+/// - `isolation_level` - whether to push the messages sent as a part of the open or aborted transactions.
+/// - `filter` - optional filter over the message headers, only the matching messages are pushed.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SubscribeMessages {
+    /// Consumer which will receive messages. Either regular consumer or consumer group.
+    #[serde(flatten)]
+    pub consumer: Consumer,
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Partition ID from which messages will be pushed. Has to be specified for the regular consumer. For consumer group it is ignored (use `None`).
+    pub partition_id: Option<u32>,
+    /// Polling strategy which specifies from where to start pushing messages.
+    #[serde(flatten)]
+    pub strategy: PollingStrategy,
+    /// Maximum number of messages in a single batch.
+    pub count: u32,
+    /// When the offsets of the pushed messages are stored on the server.
+    pub ack_mode: AckMode,
+    /// Whether the credit is counted in messages or bytes.
+    pub credit_kind: CreditKind,
+    /// Initial credit, which can be extended later on with `GrantCredit`.
+    pub credit: u64,
+    /// Whether to push the messages sent as a part of the open or aborted transactions.
+    #[serde(default)]
+    pub isolation_level: IsolationLevel,
+    /// Optional filter over the message headers, only the matching messages are pushed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<HeaderFilter>,
+}
+
+/// `AckMode` specifies when the offsets of the messages pushed by the subscription are stored on the server.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AckMode {
+    /// The offsets are never stored by the subscription.
+    None,
+    #[default]
+    /// The offset is stored as soon as the batch of messages has been written to the client.
+    Delivered,
+    /// The offset is stored when the client acknowledges the messages with `AckMessages`.
+    Acknowledged,
+}
+
+/// `CreditKind` specifies the unit in which the credit of the subscription is counted.
+/// The credit is consumed by each pushed batch, and once it's exhausted, no more messages are pushed until the client grants more.
+/// As the batches are never split, the size of the last one may exceed the remaining credit in bytes.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditKind {
+    #[default]
+    /// The credit is the number of messages.
+    Messages,
+    /// The credit is the size of messages in bytes.
+    Bytes,
+}
+
+/// `SubscriptionOptions` specifies how the messages are pushed by the subscription, apart from the strategy and count.
+/// It has the following fields:
+/// - `ack_mode` - when the offsets of the pushed messages are stored on the server.
+/// - `credit_kind` - whether the credit is counted in messages or bytes.
+/// - `credit` - initial credit, which can be extended later on with `GrantCredit`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SubscriptionOptions {
+    /// When the offsets of the pushed messages are stored on the server.
+    pub ack_mode: AckMode,
+    /// Whether the credit is counted in messages or bytes.
+    pub credit_kind: CreditKind,
+    /// Initial credit, which can be extended later on with `GrantCredit`.
+    pub credit: u64,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            ack_mode: AckMode::default(),
+            credit_kind: CreditKind::default(),
+            credit: 100,
+        }
+    }
+}
+
+impl SubscriptionOptions {
+    /// Store the offsets of the pushed messages on the server according to the ack mode.
+    pub fn ack_mode(self, ack_mode: AckMode) -> Self {
+        Self { ack_mode, ..self }
+    }
+
+    /// Push the messages for as long as there is the initial or later granted credit, counted either in messages or bytes.
+    pub fn credit(self, credit_kind: CreditKind, credit: u64) -> Self {
+        Self {
+            credit_kind,
+            credit,
+            ..self
+        }
+    }
+}
+
+impl Default for SubscribeMessages {
+    fn default() -> Self {
+        Self {
+            consumer: Consumer::default(),
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(1).unwrap(),
+            partition_id: Some(1),
+            strategy: PollingStrategy::default(),
+            count: 10,
+            ack_mode: AckMode::default(),
+            credit_kind: CreditKind::default(),
+            credit: 100,
+            isolation_level: IsolationLevel::default(),
+            filter: None,
+        }
+    }
+}
+
+impl Command for SubscribeMessages {
+    fn code(&self) -> u32 {
+        SUBSCRIBE_MESSAGES_CODE
+    }
+}
+
+impl Validatable<IggyError> for SubscribeMessages {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        Ok(())
+    }
+}
+
+impl AckMode {
+    /// Returns code of the ack mode.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            AckMode::None => 1,
+            AckMode::Delivered => 2,
+            AckMode::Acknowledged => 3,
+        }
+    }
+
+    /// Returns ack mode from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(AckMode::None),
+            2 => Ok(AckMode::Delivered),
+            3 => Ok(AckMode::Acknowledged),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl CreditKind {
+    /// Returns code of the credit kind.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            CreditKind::Messages => 1,
+            CreditKind::Bytes => 2,
+        }
+    }
+
+    /// Returns credit kind from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(CreditKind::Messages),
+            2 => Ok(CreditKind::Bytes),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl FromStr for AckMode {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "n" | "none" => Ok(AckMode::None),
+            "d" | "delivered" => Ok(AckMode::Delivered),
+            "a" | "acknowledged" => Ok(AckMode::Acknowledged),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for AckMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AckMode::None => write!(f, "none"),
+            AckMode::Delivered => write!(f, "delivered"),
+            AckMode::Acknowledged => write!(f, "acknowledged"),
+        }
+    }
+}
+
+impl FromStr for CreditKind {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "m" | "messages" => Ok(CreditKind::Messages),
+            "b" | "bytes" => Ok(CreditKind::Bytes),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for CreditKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CreditKind::Messages => write!(f, "messages"),
+            CreditKind::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+impl BytesSerializable for SubscribeMessages {
+    fn to_bytes(&self) -> Bytes {
+        let consumer_bytes = self.consumer.to_bytes();
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            28 + consumer_bytes.len() + stream_id_bytes.len() + topic_id_bytes.len(),
+        );
+        bytes.put_slice(&consumer_bytes);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id.unwrap_or(0));
+        bytes.put_u8(self.strategy.kind.as_code());
+        bytes.put_u64_le(self.strategy.value);
+        bytes.put_u32_le(self.count);
+        bytes.put_u8(self.ack_mode.as_code());
+        bytes.put_u8(self.credit_kind.as_code());
+        bytes.put_u64_le(self.credit);
+        bytes.put_u8(self.isolation_level.as_code());
+        if let Some(filter) = &self.filter {
+            bytes.put_slice(&filter.to_bytes());
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        if bytes.len() < 38 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let consumer_kind = ConsumerKind::from_code(bytes[0])?;
+        let consumer_id = Identifier::from_bytes(bytes.slice(1..))?;
+        position += 1 + consumer_id.get_size_bytes().as_bytes_usize();
+        let consumer = Consumer {
+            kind: consumer_kind,
+            id: consumer_id,
+        };
+        let stream_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 28 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let partition_id = match partition_id {
+            0 => None,
+            partition_id => Some(partition_id),
+        };
+        let strategy = PollingStrategy {
+            kind: PollingKind::from_code(bytes[position + 4])?,
+            value: u64::from_le_bytes(
+                bytes[position + 5..position + 13]
+                    .try_into()
+                    .map_err(|_| IggyError::InvalidNumberEncoding)?,
+            ),
+        };
+        let count = u32::from_le_bytes(
+            bytes[position + 13..position + 17]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let ack_mode = AckMode::from_code(bytes[position + 17])?;
+        let credit_kind = CreditKind::from_code(bytes[position + 18])?;
+        let credit = u64::from_le_bytes(
+            bytes[position + 19..position + 27]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let isolation_level = IsolationLevel::from_code(bytes[position + 27])?;
+        // The filter is optional and takes the rest of the payload.
+        position += 28;
+        let filter = match bytes.len() > position {
+            true => Some(HeaderFilter::from_bytes(bytes.slice(position..))?),
+            false => None,
+        };
+        let command = SubscribeMessages {
+            consumer,
+            stream_id,
+            topic_id,
+            partition_id,
+            strategy,
+            count,
+            ack_mode,
+            credit_kind,
+            credit,
+            isolation_level,
+            filter,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for SubscribeMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.consumer,
+            self.stream_id,
+            self.topic_id,
+            self.partition_id.unwrap_or(0),
+            self.strategy.kind,
+            self.strategy.value,
+            self.count,
+            self.ack_mode,
+            self.credit_kind,
+            self.credit,
+            self.isolation_level
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized() {
+        let command = SubscribeMessages {
+            consumer: Consumer::group(Identifier::numeric(7).unwrap()),
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::named("topic").unwrap(),
+            partition_id: None,
+            strategy: PollingStrategy::offset(10),
+            count: 50,
+            ack_mode: AckMode::Acknowledged,
+            credit_kind: CreditKind::Bytes,
+            credit: 1024 * 1024,
+            isolation_level: IsolationLevel::ReadCommitted,
+            filter: Some(HeaderFilter::new(r#"tenant == "acme""#).unwrap()),
+        };
+
+        let bytes = command.to_bytes();
+        let deserialized = SubscribeMessages::from_bytes(bytes).unwrap();
+
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_truncated_bytes() {
+        let bytes = SubscribeMessages::default().to_bytes();
+
+        let command = SubscribeMessages::from_bytes(bytes.slice(..bytes.len() - 1));
+
+        assert!(command.is_err());
+    }
+
+    #[test]
+    fn should_not_be_valid_without_count() {
+        let command = SubscribeMessages {
+            count: 0,
+            ..SubscribeMessages::default()
+        };
+
+        assert!(command.validate().is_err());
+    }
+}
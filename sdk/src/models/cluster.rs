@@ -0,0 +1,171 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// `ClusterMetadata` represents the state of the cluster as seen by the node handling the request.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ClusterMetadata {
+    /// The name of the cluster.
+    pub name: String,
+    /// The unique identifier of the node which returned the metadata.
+    pub id: u32,
+    /// The current election term.
+    pub term: u64,
+    /// The unique identifier of the current leader, if known.
+    pub leader_id: Option<u32>,
+    /// The nodes of the cluster.
+    pub nodes: Vec<ClusterNode>,
+}
+
+/// `ClusterNode` represents a single node of the cluster.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ClusterNode {
+    /// The unique identifier of the node.
+    pub id: u32,
+    /// The TCP address of the node.
+    pub address: String,
+    /// The role of the node.
+    pub role: ClusterNodeRole,
+    /// The status of the node.
+    pub status: ClusterNodeStatus,
+}
+
+impl ClusterMetadata {
+    /// Returns the current leader node, if known.
+    pub fn get_leader(&self) -> Option<&ClusterNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.role == ClusterNodeRole::Leader)
+    }
+}
+
+/// `ClusterNodeRole` represents the role of the node in the cluster.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterNodeRole {
+    /// The node accepts the metadata changes and replicates them to the followers.
+    Leader,
+    /// The node replicates the metadata changes from the leader.
+    #[default]
+    Follower,
+    /// The node is requesting the votes to become the leader.
+    Candidate,
+}
+
+/// `ClusterNodeStatus` represents the status of the node in the cluster.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterNodeStatus {
+    /// The node is reachable.
+    Healthy,
+    /// The node could not be reached.
+    Unavailable,
+    /// The node wasn't contacted yet.
+    #[default]
+    Unknown,
+}
+
+impl FromStr for ClusterNodeRole {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "leader" => Ok(ClusterNodeRole::Leader),
+            "follower" => Ok(ClusterNodeRole::Follower),
+            "candidate" => Ok(ClusterNodeRole::Candidate),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for ClusterNodeRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClusterNodeRole::Leader => write!(f, "leader"),
+            ClusterNodeRole::Follower => write!(f, "follower"),
+            ClusterNodeRole::Candidate => write!(f, "candidate"),
+        }
+    }
+}
+
+impl ClusterNodeRole {
+    /// Returns the code of the node role.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            ClusterNodeRole::Leader => 1,
+            ClusterNodeRole::Follower => 2,
+            ClusterNodeRole::Candidate => 3,
+        }
+    }
+
+    /// Returns the node role from the code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(ClusterNodeRole::Leader),
+            2 => Ok(ClusterNodeRole::Follower),
+            3 => Ok(ClusterNodeRole::Candidate),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl FromStr for ClusterNodeStatus {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "healthy" => Ok(ClusterNodeStatus::Healthy),
+            "unavailable" => Ok(ClusterNodeStatus::Unavailable),
+            "unknown" => Ok(ClusterNodeStatus::Unknown),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Display for ClusterNodeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClusterNodeStatus::Healthy => write!(f, "healthy"),
+            ClusterNodeStatus::Unavailable => write!(f, "unavailable"),
+            ClusterNodeStatus::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl ClusterNodeStatus {
+    /// Returns the code of the node status.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            ClusterNodeStatus::Healthy => 1,
+            ClusterNodeStatus::Unavailable => 2,
+            ClusterNodeStatus::Unknown => 3,
+        }
+    }
+
+    /// Returns the node status from the code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(ClusterNodeStatus::Healthy),
+            2 => Ok(ClusterNodeStatus::Unavailable),
+            3 => Ok(ClusterNodeStatus::Unknown),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::consumer_groups::assignment_strategy::AssignmentStrategy;
 use serde::{Deserialize, Serialize};
 
 /// `ConsumerGroup` represents the information about a consumer group.
@@ -24,6 +25,7 @@ use serde::{Deserialize, Serialize};
 /// - `name`: the name of the consumer group.
 /// - `partitions_count`: the number of partitions the consumer group is consuming.
 /// - `members_count`: the number of members in the consumer group.
+/// - `assignment_strategy`: the strategy of assigning the partitions to the members.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroup {
     /// The unique identifier (numeric) of the consumer group.
@@ -34,6 +36,9 @@ pub struct ConsumerGroup {
     pub partitions_count: u32,
     /// The number of members in the consumer group.
     pub members_count: u32,
+    /// The strategy of assigning the partitions to the members.
+    #[serde(default)]
+    pub assignment_strategy: AssignmentStrategy,
 }
 
 /// `ConsumerGroupDetails` represents the detailed information about a consumer group.
@@ -42,6 +47,7 @@ pub struct ConsumerGroup {
 /// - `name`: the name of the consumer group.
 /// - `partitions_count`: the number of partitions the consumer group is consuming.
 /// - `members_count`: the number of members in the consumer group.
+/// - `assignment_strategy`: the strategy of assigning the partitions to the members.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroupDetails {
     /// The unique identifier (numeric) of the consumer group.
@@ -52,6 +58,9 @@ pub struct ConsumerGroupDetails {
     pub partitions_count: u32,
     /// The number of members in the consumer group.
     pub members_count: u32,
+    /// The strategy of assigning the partitions to the members.
+    #[serde(default)]
+    pub assignment_strategy: AssignmentStrategy,
     /// The collection of members in the consumer group.
     pub members: Vec<ConsumerGroupMember>,
 }
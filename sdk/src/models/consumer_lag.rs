@@ -0,0 +1,70 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::duration::IggyDuration;
+use serde::{Deserialize, Serialize};
+
+/// `ConsumerLag` represents how far behind the consumer (or the consumer group) is in each partition of the topic.
+/// It consists of the following fields:
+/// - `stream_id`: the unique identifier of the stream.
+/// - `topic_id`: the unique identifier of the topic.
+/// - `partitions`: the lag in each partition of the topic.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConsumerLag {
+    /// The unique identifier of the stream.
+    pub stream_id: u32,
+    /// The unique identifier of the topic.
+    pub topic_id: u32,
+    /// The lag in each partition of the topic.
+    pub partitions: Vec<PartitionLag>,
+}
+
+/// `PartitionLag` represents how far behind the consumer (or the consumer group) is in a single partition.
+/// It consists of the following fields:
+/// - `partition_id`: the unique identifier of the partition.
+/// - `current_offset`: the current offset of the partition.
+/// - `stored_offset`: the offset stored by the consumer in the partition, if any.
+/// - `lag_messages`: the number of messages which are yet to be consumed.
+/// - `lag_bytes`: the size of the messages which are yet to be consumed.
+/// - `lag_time`: the time elapsed since the first message which is yet to be consumed was appended.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PartitionLag {
+    /// The unique identifier of the partition.
+    pub partition_id: u32,
+    /// The current offset of the partition.
+    pub current_offset: u64,
+    /// The offset stored by the consumer in the partition, if any.
+    pub stored_offset: Option<u64>,
+    /// The number of messages which are yet to be consumed.
+    pub lag_messages: u64,
+    /// The size of the messages which are yet to be consumed.
+    pub lag_bytes: IggyByteSize,
+    /// The time elapsed since the first message which is yet to be consumed was appended.
+    pub lag_time: IggyDuration,
+}
+
+impl ConsumerLag {
+    /// Returns the total number of messages which are yet to be consumed in all the partitions.
+    pub fn total_lag_messages(&self) -> u64 {
+        self.partitions
+            .iter()
+            .map(|partition| partition.lag_messages)
+            .sum()
+    }
+}
@@ -0,0 +1,178 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::models::user_info::UserId;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// `ProtocolFeature` represents the optional feature of the binary protocol, which is used
+/// only when both the client and the server support it, as agreed on during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolFeature {
+    /// The requests carry their request IDs, so many of them can be in flight over the single connection.
+    Multiplexing,
+    /// The server pushes the messages to the subscribed client.
+    Subscriptions,
+    /// The frame payloads exceeding the threshold are compressed with LZ4.
+    Lz4Compression,
+    /// The frame payloads exceeding the threshold are compressed with Zstandard.
+    ZstdCompression,
+    /// The polled messages carry the generation of the consumer group, used to reject the offsets stored after the rebalance.
+    ConsumerGroupGenerations,
+}
+
+impl ProtocolFeature {
+    /// Returns the bit representing the feature in the set of features sent over the wire.
+    pub fn as_flag(&self) -> u32 {
+        match self {
+            ProtocolFeature::Multiplexing => 1,
+            ProtocolFeature::Subscriptions => 1 << 1,
+            ProtocolFeature::Lz4Compression => 1 << 2,
+            ProtocolFeature::ZstdCompression => 1 << 3,
+            ProtocolFeature::ConsumerGroupGenerations => 1 << 4,
+        }
+    }
+}
+
+impl Display for ProtocolFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolFeature::Multiplexing => write!(f, "multiplexing"),
+            ProtocolFeature::Subscriptions => write!(f, "subscriptions"),
+            ProtocolFeature::Lz4Compression => write!(f, "lz4_compression"),
+            ProtocolFeature::ZstdCompression => write!(f, "zstd_compression"),
+            ProtocolFeature::ConsumerGroupGenerations => write!(f, "consumer_group_generations"),
+        }
+    }
+}
+
+/// `ProtocolFeatures` represents the set of the protocol features, sent over the wire as the `u32` flags.
+/// The unknown flags are kept, so the features added by the newer peers are simply not negotiated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolFeatures {
+    flags: u32,
+}
+
+impl ProtocolFeatures {
+    /// Creates the set of the provided features.
+    pub fn new(features: &[ProtocolFeature]) -> Self {
+        Self {
+            flags: features
+                .iter()
+                .fold(0, |flags, feature| flags | feature.as_flag()),
+        }
+    }
+
+    /// Creates the set of features from the flags received over the wire.
+    pub fn from_flags(flags: u32) -> Self {
+        Self { flags }
+    }
+
+    /// Returns the flags sent over the wire.
+    pub fn as_flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Checks if the set contains the feature.
+    pub fn contains(&self, feature: ProtocolFeature) -> bool {
+        self.flags & feature.as_flag() != 0
+    }
+
+    /// Returns the features contained in either of the sets.
+    pub fn union(&self, other: &ProtocolFeatures) -> ProtocolFeatures {
+        Self {
+            flags: self.flags | other.flags,
+        }
+    }
+
+    /// Returns the features supported by both sides, which are the negotiated ones.
+    pub fn intersection(&self, other: &ProtocolFeatures) -> ProtocolFeatures {
+        Self {
+            flags: self.flags & other.flags,
+        }
+    }
+
+    /// Returns the known features contained in the set.
+    pub fn features(&self) -> Vec<ProtocolFeature> {
+        ProtocolFeature::iter()
+            .filter(|feature| self.contains(*feature))
+            .collect()
+    }
+}
+
+impl Display for ProtocolFeatures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let features = self.features();
+        if features.is_empty() {
+            return write!(f, "none");
+        }
+
+        let features = features
+            .iter()
+            .map(|feature| feature.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", features.join(", "))
+    }
+}
+
+/// `HandshakeInfo` represents the server side of the handshake.
+/// It consists of the following fields:
+/// - `iggy_server_version`: the version of the server.
+/// - `iggy_server_semver`: the numeric semantic version of the server, if it could be parsed.
+/// - `features`: the protocol features enabled on the server for the used transport.
+/// - `user_id`: the ID of the user authenticated with the client certificate, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandshakeInfo {
+    /// The version of the server.
+    pub iggy_server_version: String,
+    /// The numeric semantic version of the server, if it could be parsed.
+    pub iggy_server_semver: Option<u32>,
+    /// The protocol features enabled on the server for the used transport.
+    pub features: ProtocolFeatures,
+    /// The ID of the user authenticated with the client certificate presented during the TLS handshake, if any.
+    pub user_id: Option<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection_should_keep_only_features_supported_by_both_sides() {
+        let client = ProtocolFeatures::new(&[
+            ProtocolFeature::Multiplexing,
+            ProtocolFeature::Subscriptions,
+        ]);
+        let server = ProtocolFeatures::new(&[ProtocolFeature::Subscriptions]);
+        let negotiated = client.intersection(&server);
+        assert!(!negotiated.contains(ProtocolFeature::Multiplexing));
+        assert!(negotiated.contains(ProtocolFeature::Subscriptions));
+        assert_eq!(negotiated.to_string(), "subscriptions");
+    }
+
+    #[test]
+    fn unknown_flags_should_not_be_negotiated() {
+        let client =
+            ProtocolFeatures::from_flags(1 << 31 | ProtocolFeature::Multiplexing.as_flag());
+        let server = ProtocolFeatures::new(&[ProtocolFeature::Multiplexing]);
+        let negotiated = client.intersection(&server);
+        assert_eq!(negotiated.features(), vec![ProtocolFeature::Multiplexing]);
+        assert_eq!(ProtocolFeatures::default().to_string(), "none");
+    }
+}
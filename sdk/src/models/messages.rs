@@ -37,6 +37,7 @@ pub const POLLED_MESSAGE_METADATA: u32 = 8 + 1 + 8 + 4;
 /// It consists of the following fields:
 /// - `partition_id`: the identifier of the partition.
 /// - `current_offset`: the current offset of the partition.
+/// - `generation_id`: the generation of the consumer group partitions assignment.
 /// - `messages`: the collection of messages.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PolledMessages {
@@ -44,6 +45,11 @@ pub struct PolledMessages {
     pub partition_id: u32,
     /// The current offset of the partition.
     pub current_offset: u64,
+    /// The generation of the consumer group partitions assignment, which has to be provided when storing the offset to detect the rebalancing.
+    /// If it's '0', then the messages were polled by the regular consumer, or the generations haven't been negotiated with the server.
+    /// The generation is not persisted, so it starts again from '0' after the server restart.
+    #[serde(default)]
+    pub generation_id: u32,
     /// The collection of messages.
     pub messages: Vec<PolledMessage>,
 }
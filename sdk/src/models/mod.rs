@@ -17,8 +17,11 @@
  */
 
 pub mod client_info;
+pub mod cluster;
 pub mod consumer_group;
+pub mod consumer_lag;
 pub mod consumer_offset_info;
+pub mod handshake;
 pub mod header;
 pub mod identity_info;
 pub mod messages;
@@ -28,6 +31,8 @@ pub mod personal_access_token;
 pub mod snapshot;
 pub mod stats;
 pub mod stream;
+pub mod stream_limits;
 pub mod topic;
 pub mod user_info;
+pub mod user_quotas;
 pub mod user_status;
@@ -27,6 +27,7 @@ use serde::{Deserialize, Serialize};
 /// - `segments_count`: the number of segments in the partition.
 /// - `current_offset`: the current offset of the partition.
 /// - `size_bytes`: the size of the partition in bytes.
+/// - `uncompressed_size`: the size of the partition in bytes before compression.
 /// - `messages_count`: the number of messages in the partition.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Partition {
@@ -40,6 +41,8 @@ pub struct Partition {
     pub current_offset: u64,
     /// The size of the partition in bytes.
     pub size: IggyByteSize,
+    /// The size of the partition in bytes before compression.
+    pub uncompressed_size: IggyByteSize,
     /// The number of messages in the partition.
     pub messages_count: u64,
 }
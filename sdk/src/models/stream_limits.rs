@@ -0,0 +1,126 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::utils::byte_size::IggyByteSize;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const LIMITS_SIZE: usize = 16;
+
+/// `StreamLimits` is used to bound the resources that can be used by a single stream.
+/// It consists of the following fields:
+/// - `max_size`: the maximum total size of all the topics in the stream.
+/// - `max_topics`: the maximum number of topics in the stream.
+/// - `max_partitions`: the maximum number of partitions across all the topics in the stream.
+///
+/// The value of None means that the resource is unlimited.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct StreamLimits {
+    /// The maximum total size of all the topics in the stream.
+    #[serde(default)]
+    pub max_size: Option<IggyByteSize>,
+    /// The maximum number of topics in the stream.
+    #[serde(default)]
+    pub max_topics: Option<u32>,
+    /// The maximum number of partitions across all the topics in the stream.
+    #[serde(default)]
+    pub max_partitions: Option<u32>,
+}
+
+impl StreamLimits {
+    /// Returns true if none of the resources is limited.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_size.is_none() && self.max_topics.is_none() && self.max_partitions.is_none()
+    }
+}
+
+impl Display for StreamLimits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max_size = self
+            .max_size
+            .map_or("unlimited".to_string(), |size| size.as_human_string());
+        let max_topics = self
+            .max_topics
+            .map_or("unlimited".to_string(), |topics| topics.to_string());
+        let max_partitions = self
+            .max_partitions
+            .map_or("unlimited".to_string(), |partitions| partitions.to_string());
+        write!(
+            f,
+            "max_size: {max_size}, max_topics: {max_topics}, max_partitions: {max_partitions}"
+        )
+    }
+}
+
+impl BytesSerializable for StreamLimits {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(LIMITS_SIZE);
+        bytes.put_u64_le(self.max_size.map_or(0, |size| size.as_bytes_u64()));
+        bytes.put_u32_le(self.max_topics.unwrap_or(0));
+        bytes.put_u32_le(self.max_partitions.unwrap_or(0));
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() != LIMITS_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut bytes = bytes;
+        let max_size = bytes.get_u64_le();
+        let max_topics = bytes.get_u32_le();
+        let max_partitions = bytes.get_u32_le();
+        Ok(Self {
+            max_size: (max_size > 0).then(|| IggyByteSize::from(max_size)),
+            max_topics: (max_topics > 0).then_some(max_topics),
+            max_partitions: (max_partitions > 0).then_some(max_partitions),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let limits = StreamLimits {
+            max_size: Some(IggyByteSize::from(1_000_000)),
+            max_topics: Some(10),
+            max_partitions: None,
+        };
+
+        let bytes = limits.to_bytes();
+        let deserialized_limits = StreamLimits::from_bytes(bytes).unwrap();
+        assert_eq!(limits, deserialized_limits);
+    }
+
+    #[test]
+    fn should_be_unlimited_by_default() {
+        let limits = StreamLimits::default();
+        assert!(limits.is_unlimited());
+        let limits = StreamLimits::from_bytes(limits.to_bytes()).unwrap();
+        assert!(limits.is_unlimited());
+    }
+}
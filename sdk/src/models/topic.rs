@@ -18,6 +18,7 @@
 
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::models::partition::Partition;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::byte_size::IggyByteSize;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::timestamp::IggyTimestamp;
@@ -33,6 +34,7 @@ use serde::{Deserialize, Serialize};
 /// - `message_expiry`: the expiry of the messages in the topic.
 /// - `max_topic_size`: the maximum size of the topic.
 /// - `replication_factor`: replication factor for the topic.
+/// - `cleanup_policy`: the policy of removing the messages from the topic.
 /// - `messages_count`: the total number of messages in the topic.
 /// - `partitions_count`: the total number of partitions in the topic.
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,6 +56,9 @@ pub struct Topic {
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: u8,
+    /// The policy of removing the messages from the topic.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// The total number of messages in the topic.
     pub messages_count: u64,
     /// The total number of partitions in the topic.
@@ -66,9 +71,11 @@ pub struct Topic {
 /// - `created_at`: the timestamp when the topic was created.
 /// - `name`: the unique name of the topic.
 /// - `size`: the total size of the topic.
+/// - `uncompressed_size`: the total size of the topic before compression.
 /// - `message_expiry`: the expiry of the messages in the topic.
 /// - `max_topic_size`: the maximum size of the topic.
 /// - `replication_factor`: replication factor for the topic.
+/// - `cleanup_policy`: the policy of removing the messages from the topic.
 /// - `messages_count`: the total number of messages in the topic.
 /// - `partitions_count`: the total number of partitions in the topic.
 /// - `partitions`: the collection of partitions in the topic.
@@ -82,6 +89,8 @@ pub struct TopicDetails {
     pub name: String,
     /// The total size of the topic.
     pub size: IggyByteSize,
+    /// The total size of the topic before compression.
+    pub uncompressed_size: IggyByteSize,
     /// The expiry of the messages in the topic.
     pub message_expiry: IggyExpiry,
     /// Compression algorithm for the topic.
@@ -91,6 +100,9 @@ pub struct TopicDetails {
     pub max_topic_size: MaxTopicSize,
     /// Replication factor for the topic.
     pub replication_factor: u8,
+    /// The policy of removing the messages from the topic.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
     /// The total number of messages in the topic.
     pub messages_count: u64,
     /// The total number of partitions in the topic.
@@ -0,0 +1,220 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use ahash::AHashMap;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `UserQuotas` is used to limit how fast a user can send and poll the messages and invoke the commands.
+/// It consists of global quotas and stream quotas, the value of 0 means that the rate is unlimited.
+/// Global quotas are applied to all the requests of the user, regardless of the stream.
+/// Stream quotas are applied to a specific stream, in addition to the global quotas.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct UserQuotas {
+    /// Global quotas are applied to all the requests of the user.
+    pub global: GlobalQuotas,
+
+    /// Stream quotas are applied to a specific stream.
+    pub streams: Option<AHashMap<u32, StreamQuotas>>,
+}
+
+/// `GlobalQuotas` are applied to all the requests of the user, across all the clients and streams.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct GlobalQuotas {
+    /// The maximum number of bytes of the messages that can be sent per second.
+    pub produce_bytes_per_second: u64,
+
+    /// The maximum number of bytes of the messages that can be polled per second.
+    pub consume_bytes_per_second: u64,
+
+    /// The maximum number of the requests that can be invoked per second.
+    pub requests_per_second: u32,
+}
+
+/// `StreamQuotas` are applied to the messages sent to and polled from a specific stream.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct StreamQuotas {
+    /// The maximum number of bytes of the messages that can be sent to the stream per second.
+    pub produce_bytes_per_second: u64,
+
+    /// The maximum number of bytes of the messages that can be polled from the stream per second.
+    pub consume_bytes_per_second: u64,
+}
+
+impl UserQuotas {
+    /// Returns true if none of the rates is limited.
+    pub fn is_unlimited(&self) -> bool {
+        self.global.produce_bytes_per_second == 0
+            && self.global.consume_bytes_per_second == 0
+            && self.global.requests_per_second == 0
+            && self
+                .streams
+                .iter()
+                .flat_map(|streams| streams.values())
+                .all(|stream| {
+                    stream.produce_bytes_per_second == 0 && stream.consume_bytes_per_second == 0
+                })
+    }
+}
+
+impl Display for UserQuotas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut result = String::new();
+        result.push_str(&format!(
+            "produce_bytes_per_second: {}\n",
+            self.global.produce_bytes_per_second
+        ));
+        result.push_str(&format!(
+            "consume_bytes_per_second: {}\n",
+            self.global.consume_bytes_per_second
+        ));
+        result.push_str(&format!(
+            "requests_per_second: {}\n",
+            self.global.requests_per_second
+        ));
+        if let Some(streams) = &self.streams {
+            for (stream_id, stream) in streams {
+                result.push_str(&format!("stream_id: {}\n", stream_id));
+                result.push_str(&format!(
+                    "produce_bytes_per_second: {}\n",
+                    stream.produce_bytes_per_second
+                ));
+                result.push_str(&format!(
+                    "consume_bytes_per_second: {}\n",
+                    stream.consume_bytes_per_second
+                ));
+            }
+        }
+
+        write!(f, "{}", result)
+    }
+}
+
+impl BytesSerializable for UserQuotas {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        bytes.put_u64_le(self.global.produce_bytes_per_second);
+        bytes.put_u64_le(self.global.consume_bytes_per_second);
+        bytes.put_u32_le(self.global.requests_per_second);
+        if let Some(streams) = &self.streams {
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u32_le(streams.len() as u32);
+            for (stream_id, stream) in streams {
+                bytes.put_u32_le(*stream_id);
+                bytes.put_u64_le(stream.produce_bytes_per_second);
+                bytes.put_u64_le(stream.consume_bytes_per_second);
+            }
+        } else {
+            bytes.put_u32_le(0);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < 24 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut bytes = bytes;
+        let produce_bytes_per_second = bytes.get_u64_le();
+        let consume_bytes_per_second = bytes.get_u64_le();
+        let requests_per_second = bytes.get_u32_le();
+        let streams_count = bytes.get_u32_le() as usize;
+        if bytes.remaining() < streams_count * 20 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut streams = None;
+        if streams_count > 0 {
+            let mut streams_map = AHashMap::with_capacity(streams_count);
+            for _ in 0..streams_count {
+                let stream_id = bytes.get_u32_le();
+                let produce_bytes_per_second = bytes.get_u64_le();
+                let consume_bytes_per_second = bytes.get_u64_le();
+                streams_map.insert(
+                    stream_id,
+                    StreamQuotas {
+                        produce_bytes_per_second,
+                        consume_bytes_per_second,
+                    },
+                );
+            }
+            streams = Some(streams_map);
+        }
+
+        Ok(Self {
+            global: GlobalQuotas {
+                produce_bytes_per_second,
+                consume_bytes_per_second,
+                requests_per_second,
+            },
+            streams,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let quotas = UserQuotas {
+            global: GlobalQuotas {
+                produce_bytes_per_second: 1_000_000,
+                consume_bytes_per_second: 2_000_000,
+                requests_per_second: 100,
+            },
+            streams: Some(AHashMap::from([
+                (
+                    1,
+                    StreamQuotas {
+                        produce_bytes_per_second: 1000,
+                        consume_bytes_per_second: 0,
+                    },
+                ),
+                (
+                    2,
+                    StreamQuotas {
+                        produce_bytes_per_second: 0,
+                        consume_bytes_per_second: 2000,
+                    },
+                ),
+            ])),
+        };
+
+        let bytes = quotas.to_bytes();
+        let deserialized_quotas = UserQuotas::from_bytes(bytes).unwrap();
+        assert_eq!(quotas, deserialized_quotas);
+    }
+
+    #[test]
+    fn should_be_unlimited_by_default() {
+        let quotas = UserQuotas::default();
+        assert!(quotas.is_unlimited());
+        let quotas = UserQuotas::from_bytes(quotas.to_bytes()).unwrap();
+        assert!(quotas.is_unlimited());
+        assert!(quotas.streams.is_none());
+    }
+}
@@ -17,12 +17,19 @@
  */
 
 use crate::binary::binary_client::BinaryClient;
-use crate::binary::{BinaryTransport, ClientState};
+use crate::binary::subscription::MessageSubscription;
+use crate::binary::{handshake, BinaryTransport, ClientState};
 use crate::client::{AutoLogin, Client, Credentials, PersonalAccessTokenClient, UserClient};
 use crate::command::Command;
+use crate::compression::wire_compression::{
+    compress_frame, decompress_frame, WireCompression, COMPRESSION_FLAG,
+};
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::IggyError;
+use crate::messages::subscribe_messages::SubscribeMessages;
+use crate::models::handshake::{ProtocolFeature, ProtocolFeatures};
 use crate::quic::config::QuicClientConfig;
+use crate::utils::certificates;
 use crate::utils::duration::IggyDuration;
 use crate::utils::timestamp::IggyTimestamp;
 use async_broadcast::{broadcast, Receiver, Sender};
@@ -34,6 +41,7 @@ use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, Server
 use rustls::crypto::CryptoProvider;
 use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
 use rustls::{DigitallySignedStruct, Error, SignatureScheme};
+use rustls_platform_verifier::BuilderVerifierExt;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -55,6 +63,8 @@ pub struct QuicClient {
     pub(crate) state: Mutex<ClientState>,
     events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
     connected_at: Mutex<Option<IggyTimestamp>>,
+    features: Mutex<ProtocolFeatures>,
+    compression: Mutex<Option<WireCompression>>,
 }
 
 unsafe impl Send for QuicClient {}
@@ -68,7 +78,7 @@ impl Default for QuicClient {
 
 #[async_trait]
 impl Client for QuicClient {
-    async fn connect(&self) -> Result<(), IggyError> {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
         QuicClient::connect(self).await
     }
 
@@ -95,6 +105,10 @@ impl BinaryTransport for QuicClient {
         *self.state.lock().await = state;
     }
 
+    async fn get_features(&self) -> ProtocolFeatures {
+        *self.features.lock().await
+    }
+
     async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError> {
         command.validate()?;
         self.send_raw_with_response(command.code(), command.to_bytes())
@@ -128,6 +142,25 @@ impl BinaryTransport for QuicClient {
         self.send_raw(code, payload).await
     }
 
+    async fn open_subscription(
+        &self,
+        command: &SubscribeMessages,
+    ) -> Result<MessageSubscription, IggyError> {
+        let connection = self.connection.lock().await;
+        let Some(connection) = connection.as_ref() else {
+            error!("Cannot subscribe. Client is not connected.");
+            return Err(IggyError::NotConnected);
+        };
+
+        // The stream is kept open in both directions, so the credit can be granted while the messages are pushed.
+        let (send, recv) = connection.open_bi().await.map_err(|error| {
+            error!("Failed to open a bidirectional stream: {error}");
+            IggyError::QuicError
+        })?;
+        let features = *self.features.lock().await;
+        MessageSubscription::open(Box::new(recv), Box::new(send), command, features).await
+    }
+
     async fn publish_event(&self, event: DiagnosticEvent) {
         if let Err(error) = self.events.0.broadcast(event).await {
             error!("Failed to send a QUIC diagnostic event: {error}");
@@ -200,10 +233,16 @@ impl QuicClient {
             state: Mutex::new(ClientState::Disconnected),
             events: broadcast(1000),
             connected_at: Mutex::new(None),
+            features: Mutex::new(ProtocolFeatures::default()),
+            compression: Mutex::new(None),
         })
     }
 
-    async fn handle_response(&self, recv: &mut RecvStream) -> Result<Bytes, IggyError> {
+    async fn handle_response(
+        &self,
+        recv: &mut RecvStream,
+        compression: Option<&WireCompression>,
+    ) -> Result<Bytes, IggyError> {
         let buffer = recv
             .read_to_end(self.config.response_buffer_size as usize)
             .await
@@ -220,32 +259,37 @@ impl QuicClient {
                 .try_into()
                 .map_err(|_| IggyError::InvalidNumberEncoding)?,
         );
-        if status != 0 {
+        let length = u32::from_le_bytes(
+            buffer[4..RESPONSE_INITIAL_BYTES_LENGTH]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        if status & !COMPRESSION_FLAG != 0 {
             error!(
                 "Received an invalid response with status: {} ({}).",
                 status,
                 IggyError::from_code_as_string(status)
             );
 
-            return Err(IggyError::from_code(status));
+            let details = buffer
+                .get(RESPONSE_INITIAL_BYTES_LENGTH..RESPONSE_INITIAL_BYTES_LENGTH + length as usize)
+                .unwrap_or_default();
+            return Err(IggyError::from_code_and_details(status, details));
         }
 
-        let length = u32::from_le_bytes(
-            buffer[4..RESPONSE_INITIAL_BYTES_LENGTH]
-                .try_into()
-                .map_err(|_| IggyError::InvalidNumberEncoding)?,
-        );
         trace!("Status: OK. Response length: {}", length);
         if length <= 1 {
             return Ok(Bytes::new());
         }
 
-        Ok(Bytes::copy_from_slice(
+        let response = Bytes::copy_from_slice(
             &buffer[RESPONSE_INITIAL_BYTES_LENGTH..RESPONSE_INITIAL_BYTES_LENGTH + length as usize],
-        ))
+        );
+        let (_, response) = decompress_frame(compression, status, response)?;
+        Ok(response)
     }
 
-    async fn connect(&self) -> Result<(), IggyError> {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
         match self.get_state().await {
             ClientState::Shutdown => {
                 trace!("Cannot connect. Client is shutdown.");
@@ -253,11 +297,11 @@ impl QuicClient {
             }
             ClientState::Connected | ClientState::Authenticating | ClientState::Authenticated => {
                 trace!("Client is already connected.");
-                return Ok(());
+                return Ok(*self.features.lock().await);
             }
             ClientState::Connecting => {
                 trace!("Client is already connecting.");
-                return Ok(());
+                return Ok(*self.features.lock().await);
             }
             _ => {}
         }
@@ -341,11 +385,15 @@ impl QuicClient {
         self.connection.lock().await.replace(connection);
         self.connected_at.lock().await.replace(now);
         self.publish_event(DiagnosticEvent::Connected).await;
+        let features = handshake(self, self.get_supported_features()).await?;
+        *self.features.lock().await = features;
+        *self.compression.lock().await =
+            WireCompression::negotiated(&features, self.config.compression_threshold);
 
         match &self.config.auto_login {
             AutoLogin::Disabled => {
                 info!("Automatic sign-in is disabled.");
-                Ok(())
+                Ok(features)
             }
             AutoLogin::Enabled(credentials) => {
                 info!(
@@ -358,7 +406,7 @@ impl QuicClient {
                         self.login_user(username, password).await?;
                         self.publish_event(DiagnosticEvent::SignedIn).await;
                         info!("{NAME} client: {} has signed in with the user credentials, username: {username}", self.config.client_address);
-                        Ok(())
+                        Ok(features)
                     }
                     Credentials::PersonalAccessToken(token) => {
                         self.login_with_personal_access_token(token).await?;
@@ -367,7 +415,7 @@ impl QuicClient {
                             "{NAME} client: {} has signed in with a personal access token.",
                             self.config.client_address
                         );
-                        Ok(())
+                        Ok(features)
                     }
                 }
             }
@@ -403,6 +451,8 @@ impl QuicClient {
         );
         self.set_state(ClientState::Disconnected).await;
         self.connection.lock().await.take();
+        *self.features.lock().await = ProtocolFeatures::default();
+        self.compression.lock().await.take();
         self.endpoint.wait_idle().await;
         self.publish_event(DiagnosticEvent::Disconnected).await;
         let now = IggyTimestamp::now();
@@ -436,6 +486,8 @@ impl QuicClient {
             _ => {}
         }
 
+        let compression = *self.compression.lock().await;
+        let (code, payload) = compress_frame(compression.as_ref(), code, payload);
         let connection = self.connection.lock().await;
         if let Some(connection) = connection.as_ref() {
             let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
@@ -463,12 +515,24 @@ impl QuicClient {
                 IggyError::QuicError
             })?;
             trace!("Sent a QUIC request with code: {code}, waiting for a response...");
-            return self.handle_response(&mut recv).await;
+            return self.handle_response(&mut recv, compression.as_ref()).await;
         }
 
         error!("Cannot send data. Client is not connected.");
         Err(IggyError::NotConnected)
     }
+
+    /// Returns the protocol features offered to the server during the handshake.
+    fn get_supported_features(&self) -> ProtocolFeatures {
+        let mut features = vec![
+            ProtocolFeature::Subscriptions,
+            ProtocolFeature::ConsumerGroupGenerations,
+        ];
+        if let Some(compression) = self.config.compression {
+            features.push(compression.as_feature());
+        }
+        ProtocolFeatures::new(&features)
+    }
 }
 
 fn configure(config: &QuicClientConfig) -> Result<ClientConfig, IggyError> {
@@ -511,21 +575,30 @@ fn configure(config: &QuicClientConfig) -> Result<ClientConfig, IggyError> {
             warn!("Failed to install rustls crypto provider. Error: {:?}. This may be normal if another thread installed it first.", e);
         }
     }
-    let mut client_config = match config.validate_certificate {
-        true => ClientConfig::with_platform_verifier(),
-        false => {
-            match QuinnQuicClientConfig::try_from(
-                rustls::ClientConfig::builder()
-                    .dangerous()
-                    .with_custom_certificate_verifier(SkipServerVerification::new())
-                    .with_no_client_auth(),
-            ) {
-                Ok(config) => ClientConfig::new(Arc::new(config)),
-                Err(error) => {
-                    error!("Failed to create QUIC client configuration: {error}");
-                    return Err(IggyError::InvalidConfiguration);
-                }
-            }
+    let builder = match config.validate_certificate {
+        true => rustls::ClientConfig::builder().with_platform_verifier(),
+        false => rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(SkipServerVerification::new()),
+    };
+    let crypto = match (&config.client_certificate_file, &config.client_key_file) {
+        (Some(certificate_file), Some(key_file)) => {
+            let (certificates, key) =
+                certificates::load_client_certificate(certificate_file, key_file)?;
+            builder
+                .with_client_auth_cert(certificates, key)
+                .map_err(|error| {
+                    error!("Failed to use the client certificate for QUIC. {error}");
+                    IggyError::InvalidTlsCertificate
+                })?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+    let mut client_config = match QuinnQuicClientConfig::try_from(crypto) {
+        Ok(config) => ClientConfig::new(Arc::new(config)),
+        Err(error) => {
+            error!("Failed to create QUIC client configuration: {error}");
+            return Err(IggyError::InvalidConfiguration);
         }
     };
     client_config.transport_config(Arc::new(transport));
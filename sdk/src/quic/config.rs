@@ -17,6 +17,10 @@
  */
 
 use crate::client::AutoLogin;
+use crate::compression::wire_compression::{
+    WireCompressionAlgorithm, DEFAULT_COMPRESSION_THRESHOLD,
+};
+use crate::utils::byte_size::IggyByteSize;
 use crate::utils::duration::IggyDuration;
 use std::str::FromStr;
 
@@ -51,8 +55,16 @@ pub struct QuicClientConfig {
     pub max_idle_timeout: u64,
     /// Whether to validate the server certificate.
     pub validate_certificate: bool,
+    /// The path to the PEM file with the client certificate presented to the server, if required.
+    pub client_certificate_file: Option<String>,
+    /// The path to the PEM file with the private key of the client certificate.
+    pub client_key_file: Option<String>,
     /// Interval of heartbeats sent by the client
     pub heartbeat_interval: IggyDuration,
+    /// The algorithm offered to compress the request and response payloads sent over the wire, if any.
+    pub compression: Option<WireCompressionAlgorithm>,
+    /// The size of the request payload above which it gets compressed.
+    pub compression_threshold: IggyByteSize,
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +104,10 @@ impl Default for QuicClientConfig {
             keep_alive_interval: 5000,
             max_idle_timeout: 10000,
             validate_certificate: false,
+            client_certificate_file: None,
+            client_key_file: None,
+            compression: None,
+            compression_threshold: IggyByteSize::from(DEFAULT_COMPRESSION_THRESHOLD),
         }
     }
 }
@@ -113,6 +129,10 @@ impl Default for QuicClientConfig {
 /// - `keep_alive_interval`: Default is 5000 milliseconds.
 /// - `max_idle_timeout`: Default is 10,000 milliseconds.
 /// - `validate_certificate`: Default is false (certificate validation is disabled).
+/// - `client_certificate_file`: Default is None (no client certificate is presented).
+/// - `client_key_file`: Default is None.
+/// - `compression`: Default is None (the payloads are not compressed).
+/// - `compression_threshold`: Default is 1 KiB.
 #[derive(Debug, Default)]
 pub struct QuicClientConfigBuilder {
     config: QuicClientConfig,
@@ -219,12 +239,31 @@ impl QuicClientConfigBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key, presented to the server verifying the clients.
+    pub fn with_client_certificate(mut self, certificate_file: String, key_file: String) -> Self {
+        self.config.client_certificate_file = Some(certificate_file);
+        self.config.client_key_file = Some(key_file);
+        self
+    }
+
     /// Sets the heartbeat interval. Defaults to 5000ms.
     pub fn with_heartbeat_interval(mut self, interval: IggyDuration) -> Self {
         self.config.heartbeat_interval = interval;
         self
     }
 
+    /// Sets the algorithm offered to compress the payloads sent over the wire. Defaults to none.
+    pub fn with_compression(mut self, compression: WireCompressionAlgorithm) -> Self {
+        self.config.compression = Some(compression);
+        self
+    }
+
+    /// Sets the size of the request payload above which it gets compressed. Defaults to 1 KiB.
+    pub fn with_compression_threshold(mut self, threshold: IggyByteSize) -> Self {
+        self.config.compression_threshold = threshold;
+        self
+    }
+
     /// Finalizes the builder and returns the `QuicClientConfig`.
     pub fn build(self) -> QuicClientConfig {
         self.config
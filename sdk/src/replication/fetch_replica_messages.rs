@@ -0,0 +1,225 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, FETCH_REPLICA_MESSAGES_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `FetchReplicaMessages` command is used by the follower node to fetch the messages from the partition of the leader node.
+/// The requested offset is also the acknowledgement of all the messages preceding it, which were already replicated by the follower.
+/// The response consists of the retained message batch, or is empty if there are no new messages.
+/// It has additional payload:
+/// - `replica_id` - unique ID of the follower node.
+/// - `stream_id` - unique stream ID (numeric or name).
+/// - `topic_id` - unique topic ID (numeric or name).
+/// - `partition_id` - partition ID from which the messages should be fetched.
+/// - `offset` - offset of the first message to fetch.
+/// - `count` - maximum number of messages to fetch.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FetchReplicaMessages {
+    /// Unique ID of the follower node.
+    pub replica_id: u32,
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Partition ID from which the messages should be fetched.
+    pub partition_id: u32,
+    /// Offset of the first message to fetch.
+    pub offset: u64,
+    /// Maximum number of messages to fetch.
+    pub count: u32,
+}
+
+impl Default for FetchReplicaMessages {
+    fn default() -> Self {
+        FetchReplicaMessages {
+            replica_id: 1,
+            stream_id: Identifier::default(),
+            topic_id: Identifier::default(),
+            partition_id: 1,
+            offset: 0,
+            count: 1000,
+        }
+    }
+}
+
+impl Command for FetchReplicaMessages {
+    fn code(&self) -> u32 {
+        FETCH_REPLICA_MESSAGES_CODE
+    }
+}
+
+impl Validatable<IggyError> for FetchReplicaMessages {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.partition_id == 0 || self.count == 0 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for FetchReplicaMessages {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(20 + stream_id_bytes.len() + topic_id_bytes.len());
+        bytes.put_u32_le(self.replica_id);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u64_le(self.offset);
+        bytes.put_u32_le(self.count);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<FetchReplicaMessages, IggyError> {
+        if bytes.len() < 26 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let replica_id = u32::from_le_bytes(
+            bytes[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let mut position = 4;
+        let stream_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 16 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let partition_id = u32::from_le_bytes(
+            bytes[position..position + 4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let offset = u64::from_le_bytes(
+            bytes[position + 4..position + 12]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let count = u32::from_le_bytes(
+            bytes[position + 12..position + 16]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        );
+        let command = FetchReplicaMessages {
+            replica_id,
+            stream_id,
+            topic_id,
+            partition_id,
+            offset,
+            count,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for FetchReplicaMessages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}",
+            self.replica_id,
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            self.offset,
+            self.count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = FetchReplicaMessages {
+            replica_id: 2,
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::numeric(2).unwrap(),
+            partition_id: 3,
+            offset: 4,
+            count: 5,
+        };
+
+        let bytes = command.to_bytes();
+        let replica_id = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let mut position = 4;
+        let stream_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..)).unwrap();
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        let partition_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        let offset = u64::from_le_bytes(bytes[position + 4..position + 12].try_into().unwrap());
+        let count = u32::from_le_bytes(bytes[position + 12..position + 16].try_into().unwrap());
+
+        assert!(!bytes.is_empty());
+        assert_eq!(replica_id, command.replica_id);
+        assert_eq!(stream_id, command.stream_id);
+        assert_eq!(topic_id, command.topic_id);
+        assert_eq!(partition_id, command.partition_id);
+        assert_eq!(offset, command.offset);
+        assert_eq!(count, command.count);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let replica_id = 2u32;
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let partition_id = 3u32;
+        let offset = 4u64;
+        let count = 5u32;
+        let stream_id_bytes = stream_id.to_bytes();
+        let topic_id_bytes = topic_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(20 + stream_id_bytes.len() + topic_id_bytes.len());
+        bytes.put_u32_le(replica_id);
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(partition_id);
+        bytes.put_u64_le(offset);
+        bytes.put_u32_le(count);
+
+        let command = FetchReplicaMessages::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.replica_id, replica_id);
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.partition_id, partition_id);
+        assert_eq!(command.offset, offset);
+        assert_eq!(command.count, count);
+    }
+}
@@ -16,3 +16,4 @@
  * under the License.
  */
 pub mod delete_segments;
+pub mod restore_segments;
@@ -0,0 +1,374 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, RESTORE_SEGMENTS_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::utils::sizeable::Sizeable;
+use crate::utils::timestamp::IggyTimestamp;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+const MAX_ARCHIVE_PATH_LENGTH: usize = 4096;
+
+/// `RestoreSegments` command is used to restore the archived segments back into a partition.
+/// The whole segments containing the messages from the specified range are restored with their original offsets,
+/// their checksums are validated and indexes rebuilt. The restored segments must precede the messages stored in the partition,
+/// unless the partition is empty.
+/// It has additional payload:
+/// - `stream_id` - unique stream ID (numeric or name) to restore the segments into.
+/// - `topic_id` - unique topic ID (numeric or name) to restore the segments into.
+/// - `partition_id` - unique partition ID to restore the segments into.
+/// - `source` - the archived partition to restore the segments from.
+/// - `range` - the range of the offsets or timestamps of the messages to restore.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct RestoreSegments {
+    /// Unique stream ID (numeric or name).
+    #[serde(skip)]
+    pub stream_id: Identifier,
+    /// Unique topic ID (numeric or name).
+    #[serde(skip)]
+    pub topic_id: Identifier,
+    /// Unique partition ID.
+    #[serde(skip)]
+    pub partition_id: u32,
+    /// The archived partition to restore the segments from.
+    pub source: ArchiveSource,
+    /// The range of the offsets or timestamps of the messages to restore.
+    pub range: RestoreRange,
+}
+
+/// `ArchiveSource` specifies the archived partition to restore the segments from.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct ArchiveSource {
+    /// Numeric ID of the stream, the segments were archived for.
+    pub stream_id: u32,
+    /// Numeric ID of the topic, the segments were archived for.
+    pub topic_id: u32,
+    /// Numeric ID of the partition, the segments were archived for.
+    pub partition_id: u32,
+    /// Optional path to the directory on the server containing the archive written by the disk archiver.
+    /// Only the root user can specify it. If not specified, the archiver configured on the server is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+}
+
+/// `RestoreRange` specifies the inclusive range of the offsets or timestamps of the messages to restore.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
+pub struct RestoreRange {
+    /// Kind of the range.
+    #[serde(default)]
+    pub kind: RestoreRangeKind,
+    /// Start of the range (inclusive).
+    #[serde(default)]
+    pub start: u64,
+    /// End of the range (inclusive).
+    #[serde(default = "default_end")]
+    pub end: u64,
+}
+
+/// `RestoreRangeKind` specifies whether the range of the messages to restore is defined by the offsets or timestamps.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreRangeKind {
+    #[default]
+    /// Restore the messages with the offsets from the specified range.
+    Offset,
+    /// Restore the messages with the timestamps (in microseconds) from the specified range.
+    Timestamp,
+}
+
+fn default_end() -> u64 {
+    u64::MAX
+}
+
+impl Default for RestoreRange {
+    fn default() -> Self {
+        Self {
+            kind: RestoreRangeKind::Offset,
+            start: 0,
+            end: default_end(),
+        }
+    }
+}
+
+impl RestoreRange {
+    /// Restore the messages with the offsets from the specified range.
+    pub fn offsets(start: u64, end: u64) -> Self {
+        Self {
+            kind: RestoreRangeKind::Offset,
+            start,
+            end,
+        }
+    }
+
+    /// Restore the messages with the timestamps from the specified range.
+    pub fn timestamps(start: IggyTimestamp, end: IggyTimestamp) -> Self {
+        Self {
+            kind: RestoreRangeKind::Timestamp,
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    /// Returns `true` if the range overlaps the specified inclusive range of values.
+    pub fn overlaps(&self, start: u64, end: u64) -> bool {
+        self.start <= end && start <= self.end
+    }
+}
+
+impl RestoreRangeKind {
+    /// Returns code of the restore range kind.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            RestoreRangeKind::Offset => 1,
+            RestoreRangeKind::Timestamp => 2,
+        }
+    }
+
+    /// Returns restore range kind from the specified code.
+    pub fn from_code(code: u8) -> Result<Self, IggyError> {
+        match code {
+            1 => Ok(RestoreRangeKind::Offset),
+            2 => Ok(RestoreRangeKind::Timestamp),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl FromStr for RestoreRangeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "o" | "offset" => Ok(RestoreRangeKind::Offset),
+            "t" | "timestamp" => Ok(RestoreRangeKind::Timestamp),
+            _ => Err(format!("Invalid restore range kind: {s}")),
+        }
+    }
+}
+
+impl Display for RestoreRangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestoreRangeKind::Offset => write!(f, "offset"),
+            RestoreRangeKind::Timestamp => write!(f, "timestamp"),
+        }
+    }
+}
+
+impl Command for RestoreSegments {
+    fn code(&self) -> u32 {
+        RESTORE_SEGMENTS_CODE
+    }
+}
+
+impl Validatable<IggyError> for RestoreSegments {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.range.start > self.range.end {
+            return Err(IggyError::InvalidRestoreRange(
+                self.range.start,
+                self.range.end,
+            ));
+        }
+
+        if let Some(archive_path) = &self.source.archive_path {
+            if archive_path.is_empty() || archive_path.len() > MAX_ARCHIVE_PATH_LENGTH {
+                return Err(IggyError::InvalidArchivePath);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for RestoreSegments {
+    fn to_bytes(&self) -> Bytes {
+        let stream_id_bytes = self.stream_id.to_bytes();
+        let topic_id_bytes = self.topic_id.to_bytes();
+        let archive_path = self.source.archive_path.as_deref().unwrap_or_default();
+        let mut bytes = BytesMut::with_capacity(
+            stream_id_bytes.len() + topic_id_bytes.len() + 37 + archive_path.len(),
+        );
+        bytes.put_slice(&stream_id_bytes);
+        bytes.put_slice(&topic_id_bytes);
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u32_le(self.source.stream_id);
+        bytes.put_u32_le(self.source.topic_id);
+        bytes.put_u32_le(self.source.partition_id);
+        bytes.put_u8(self.range.kind.as_code());
+        bytes.put_u64_le(self.range.start);
+        bytes.put_u64_le(self.range.end);
+        bytes.put_u32_le(archive_path.len() as u32);
+        bytes.put_slice(archive_path.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<RestoreSegments, IggyError> {
+        if bytes.len() < 43 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 0;
+        let stream_id = Identifier::from_bytes(bytes.clone())?;
+        position += stream_id.get_size_bytes().as_bytes_usize();
+        let topic_id = Identifier::from_bytes(bytes.slice(position..))?;
+        position += topic_id.get_size_bytes().as_bytes_usize();
+        if bytes.len() < position + 37 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let read_u32 = |position: usize| {
+            bytes[position..position + 4]
+                .try_into()
+                .map(u32::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding)
+        };
+        let read_u64 = |position: usize| {
+            bytes[position..position + 8]
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding)
+        };
+        let partition_id = read_u32(position)?;
+        let source_stream_id = read_u32(position + 4)?;
+        let source_topic_id = read_u32(position + 8)?;
+        let source_partition_id = read_u32(position + 12)?;
+        let kind = RestoreRangeKind::from_code(bytes[position + 16])?;
+        let start = read_u64(position + 17)?;
+        let end = read_u64(position + 25)?;
+        let archive_path_length = read_u32(position + 33)? as usize;
+        position += 37;
+        if bytes.len() != position + archive_path_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let archive_path = match archive_path_length {
+            0 => None,
+            _ => Some(
+                String::from_utf8(bytes[position..].to_vec())
+                    .map_err(|_| IggyError::InvalidArchivePath)?,
+            ),
+        };
+        let command = RestoreSegments {
+            stream_id,
+            topic_id,
+            partition_id,
+            source: ArchiveSource {
+                stream_id: source_stream_id,
+                topic_id: source_topic_id,
+                partition_id: source_partition_id,
+                archive_path,
+            },
+            range: RestoreRange { kind, start, end },
+        };
+        Ok(command)
+    }
+}
+
+impl Display for RestoreSegments {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            self.source.stream_id,
+            self.source.topic_id,
+            self.source.partition_id,
+            self.range.kind,
+            self.range.start,
+            self.range.end,
+            self.source.archive_path.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes_and_deserialized() {
+        let command = RestoreSegments {
+            stream_id: Identifier::numeric(1).unwrap(),
+            topic_id: Identifier::named("topic").unwrap(),
+            partition_id: 3,
+            source: ArchiveSource {
+                stream_id: 4,
+                topic_id: 5,
+                partition_id: 6,
+                archive_path: Some("archive".to_string()),
+            },
+            range: RestoreRange::offsets(10, 20),
+        };
+
+        let bytes = command.to_bytes();
+        let deserialized = RestoreSegments::from_bytes(bytes).unwrap();
+
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_without_archive_path() {
+        let stream_id = Identifier::numeric(1).unwrap();
+        let topic_id = Identifier::numeric(2).unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&stream_id.to_bytes());
+        bytes.put_slice(&topic_id.to_bytes());
+        bytes.put_u32_le(3);
+        bytes.put_u32_le(1);
+        bytes.put_u32_le(2);
+        bytes.put_u32_le(3);
+        bytes.put_u8(RestoreRangeKind::Timestamp.as_code());
+        bytes.put_u64_le(100);
+        bytes.put_u64_le(200);
+        bytes.put_u32_le(0);
+
+        let command = RestoreSegments::from_bytes(bytes.freeze()).unwrap();
+
+        assert_eq!(command.stream_id, stream_id);
+        assert_eq!(command.topic_id, topic_id);
+        assert_eq!(command.partition_id, 3);
+        assert_eq!(command.source.stream_id, 1);
+        assert_eq!(command.source.topic_id, 2);
+        assert_eq!(command.source.partition_id, 3);
+        assert_eq!(command.source.archive_path, None);
+        assert_eq!(command.range.kind, RestoreRangeKind::Timestamp);
+        assert_eq!(command.range.start, 100);
+        assert_eq!(command.range.end, 200);
+    }
+
+    #[test]
+    fn command_with_invalid_range_should_not_be_valid() {
+        let command = RestoreSegments {
+            range: RestoreRange::offsets(20, 10),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            command.validate(),
+            Err(IggyError::InvalidRestoreRange(20, 10))
+        );
+    }
+}
@@ -22,6 +22,7 @@ use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::{IdKind, Identifier};
 use crate::stream_builder::IggyConsumerConfig;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::topic_size::MaxTopicSize;
 use tracing::{trace, warn};
@@ -64,7 +65,7 @@ pub(crate) async fn build_iggy_stream_topic_if_not_exists(
 
         let (name, id) = extract_name_id_from_identifier(stream_id, stream_name)?;
         trace!("Creating stream: {name}");
-        client.create_stream(&name, id).await?;
+        client.create_stream(&name, id, None).await?;
     }
 
     trace!("Check if topic exists.");
@@ -97,6 +98,7 @@ pub(crate) async fn build_iggy_stream_topic_if_not_exists(
                 id,
                 IggyExpiry::ServerDefault,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
     }
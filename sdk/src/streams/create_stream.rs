@@ -19,6 +19,7 @@
 use crate::bytes_serializable::BytesSerializable;
 use crate::command::{Command, CREATE_STREAM_CODE};
 use crate::error::IggyError;
+use crate::models::stream_limits::StreamLimits;
 use crate::streams::MAX_NAME_LENGTH;
 use crate::validatable::Validatable;
 use bytes::{BufMut, Bytes, BytesMut};
@@ -30,12 +31,16 @@ use std::str::from_utf8;
 /// It has additional payload:
 /// - `stream_id` - unique stream ID (numeric)
 /// - `name` - unique stream name (string), max length is 255 characters.
+/// - `limits` - optional limits of the stream size, topics and partitions count, unlimited by default.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateStream {
     /// Unique stream ID (numeric), if None is provided then the server will automatically assign it.
     pub stream_id: Option<u32>,
     /// Unique stream name (string), max length is 255 characters.
     pub name: String,
+    /// Optional limits of the stream size, topics and partitions count, unlimited if None is provided.
+    #[serde(default)]
+    pub limits: Option<StreamLimits>,
 }
 
 impl Command for CreateStream {
@@ -49,6 +54,7 @@ impl Default for CreateStream {
         CreateStream {
             stream_id: Some(1),
             name: "stream".to_string(),
+            limits: None,
         }
     }
 }
@@ -76,6 +82,12 @@ impl BytesSerializable for CreateStream {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        if let Some(limits) = &self.limits {
+            bytes.put_u8(1);
+            bytes.put_slice(&limits.to_bytes());
+        } else {
+            bytes.put_u8(0);
+        }
         bytes.freeze()
     }
 
@@ -95,33 +107,64 @@ impl BytesSerializable for CreateStream {
             Some(stream_id)
         };
         let name_length = bytes[4];
-        let name = from_utf8(&bytes[5..5 + name_length as usize])
+        let position = 5 + name_length as usize;
+        if bytes.len() < position {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let name = from_utf8(&bytes[5..position])
             .map_err(|_| IggyError::InvalidUtf8)?
             .to_string();
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
 
-        let command = CreateStream { stream_id, name };
+        // The limits are optional and may be missing in the commands sent by the older clients.
+        let limits = read_limits(&bytes, position)?;
+        let command = CreateStream {
+            stream_id,
+            name,
+            limits,
+        };
         Ok(command)
     }
 }
 
+pub(crate) fn read_limits(
+    bytes: &Bytes,
+    position: usize,
+) -> Result<Option<StreamLimits>, IggyError> {
+    if bytes.len() <= position {
+        return Ok(None);
+    }
+
+    match bytes[position] {
+        0 => Ok(None),
+        1 => Ok(Some(StreamLimits::from_bytes(bytes.slice(position + 1..))?)),
+        _ => Err(IggyError::InvalidCommand),
+    }
+}
+
 impl Display for CreateStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}|{}", self.stream_id.unwrap_or(0), self.name)
+        let limits = self
+            .limits
+            .map_or("unlimited".to_string(), |limits| limits.to_string());
+        write!(f, "{}|{}|{limits}", self.stream_id.unwrap_or(0), self.name)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::byte_size::IggyByteSize;
 
     #[test]
     fn should_be_serialized_as_bytes() {
         let command = CreateStream {
             stream_id: Some(1),
             name: "test".to_string(),
+            limits: None,
         };
 
         let bytes = command.to_bytes();
@@ -149,5 +192,22 @@ mod tests {
         let command = command.unwrap();
         assert_eq!(command.stream_id.unwrap(), stream_id);
         assert_eq!(command.name, name);
+        assert!(command.limits.is_none());
+    }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_with_limits() {
+        let command = CreateStream {
+            stream_id: Some(1),
+            name: "test".to_string(),
+            limits: Some(StreamLimits {
+                max_size: Some(IggyByteSize::from(1_000_000)),
+                max_topics: Some(5),
+                max_partitions: Some(50),
+            }),
+        };
+
+        let deserialized_command = CreateStream::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(command, deserialized_command);
     }
 }
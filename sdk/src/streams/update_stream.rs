@@ -20,6 +20,8 @@ use crate::bytes_serializable::BytesSerializable;
 use crate::command::{Command, UPDATE_STREAM_CODE};
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::models::stream_limits::StreamLimits;
+use crate::streams::create_stream::read_limits;
 use crate::streams::MAX_NAME_LENGTH;
 use crate::utils::sizeable::Sizeable;
 use crate::validatable::Validatable;
@@ -32,6 +34,7 @@ use std::str::from_utf8;
 /// It has additional payload:
 /// - `stream_id` - unique stream ID (numeric or name).
 /// - `name` - unique stream name (string), max length is 255 characters.
+/// - `limits` - new limits of the stream (optional), if provided, replace the existing ones.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct UpdateStream {
     /// Unique stream ID (numeric or name).
@@ -39,6 +42,9 @@ pub struct UpdateStream {
     pub stream_id: Identifier,
     /// Unique stream name (string), max length is 255 characters.
     pub name: String,
+    /// New limits of the stream size, topics and partitions count, the existing ones are kept if None is provided.
+    #[serde(default)]
+    pub limits: Option<StreamLimits>,
 }
 
 impl Command for UpdateStream {
@@ -52,6 +58,7 @@ impl Default for UpdateStream {
         UpdateStream {
             stream_id: Identifier::default(),
             name: "stream".to_string(),
+            limits: None,
         }
     }
 }
@@ -74,6 +81,12 @@ impl BytesSerializable for UpdateStream {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        if let Some(limits) = &self.limits {
+            bytes.put_u8(1);
+            bytes.put_slice(&limits.to_bytes());
+        } else {
+            bytes.put_u8(0);
+        }
         bytes.freeze()
     }
 
@@ -86,21 +99,36 @@ impl BytesSerializable for UpdateStream {
         let stream_id = Identifier::from_bytes(bytes.clone())?;
         position += stream_id.get_size_bytes().as_bytes_usize();
         let name_length = bytes[position];
-        let name = from_utf8(&bytes[position + 1..position + 1 + name_length as usize])
+        position += 1;
+        if bytes.len() < position + name_length as usize {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let name = from_utf8(&bytes[position..position + name_length as usize])
             .map_err(|_| IggyError::InvalidUtf8)?
             .to_string();
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
 
-        let command = UpdateStream { stream_id, name };
+        position += name_length as usize;
+        // The limits are optional and may be missing in the commands sent by the older clients.
+        let limits = read_limits(&bytes, position)?;
+        let command = UpdateStream {
+            stream_id,
+            name,
+            limits,
+        };
         Ok(command)
     }
 }
 
 impl Display for UpdateStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}|{}", self.stream_id, self.name)
+        let limits = self
+            .limits
+            .map_or("unchanged".to_string(), |limits| limits.to_string());
+        write!(f, "{}|{}|{limits}", self.stream_id, self.name)
     }
 }
 
@@ -113,6 +141,7 @@ mod tests {
         let command = UpdateStream {
             stream_id: Identifier::numeric(1).unwrap(),
             name: "test".to_string(),
+            limits: None,
         };
 
         let bytes = command.to_bytes();
@@ -146,5 +175,22 @@ mod tests {
         let command = command.unwrap();
         assert_eq!(command.stream_id, stream_id);
         assert_eq!(command.name, name);
+        assert!(command.limits.is_none());
+    }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_with_limits() {
+        let command = UpdateStream {
+            stream_id: Identifier::numeric(1).unwrap(),
+            name: "test".to_string(),
+            limits: Some(StreamLimits {
+                max_size: None,
+                max_topics: Some(3),
+                max_partitions: Some(30),
+            }),
+        };
+
+        let deserialized_command = UpdateStream::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(command, deserialized_command);
     }
 }
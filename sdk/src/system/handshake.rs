@@ -0,0 +1,127 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, HANDSHAKE_CODE};
+use crate::error::IggyError;
+use crate::models::handshake::ProtocolFeatures;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// `Handshake` command is sent right after establishing the connection to agree on the protocol features.
+/// The server responds with its version and the features it enables for the used transport.
+/// It has additional payload:
+/// - `version` - the version of the SDK, must be between 1 and 255 characters long.
+/// - `features` - the protocol features supported by the client.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Handshake {
+    /// The version of the SDK, must be between 1 and 255 characters long.
+    pub version: String,
+    /// The protocol features supported by the client.
+    pub features: ProtocolFeatures,
+}
+
+impl Command for Handshake {
+    fn code(&self) -> u32 {
+        HANDSHAKE_CODE
+    }
+}
+
+impl Default for Handshake {
+    fn default() -> Self {
+        Handshake {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            features: ProtocolFeatures::default(),
+        }
+    }
+}
+
+impl Validatable<IggyError> for Handshake {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.version.is_empty() || self.version.len() > 255 {
+            return Err(IggyError::InvalidVersion(self.version.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for Handshake {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(5 + self.version.len());
+        bytes.put_u32_le(self.features.as_flags());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.version.len() as u8);
+        bytes.put_slice(self.version.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Handshake, IggyError> {
+        if bytes.len() < 6 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let features = ProtocolFeatures::from_flags(u32::from_le_bytes(
+            bytes[..4]
+                .try_into()
+                .map_err(|_| IggyError::InvalidNumberEncoding)?,
+        ));
+        let version_length = bytes[4] as usize;
+        if bytes.len() != 5 + version_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let version = from_utf8(&bytes[5..])
+            .map_err(|_| IggyError::InvalidUtf8)?
+            .to_string();
+        Ok(Handshake { version, features })
+    }
+}
+
+impl Display for Handshake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.version, self.features)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::handshake::ProtocolFeature;
+
+    #[test]
+    fn should_be_serialized_as_bytes_and_deserialized_from_bytes() {
+        let command = Handshake {
+            version: "1.2.3".to_string(),
+            features: ProtocolFeatures::new(&[ProtocolFeature::Multiplexing]),
+        };
+        let bytes = command.to_bytes();
+        let deserialized = Handshake::from_bytes(bytes).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_truncated_bytes() {
+        let command = Handshake::default();
+        let bytes = command.to_bytes();
+        let truncated = bytes.slice(..bytes.len() - 1);
+        assert!(Handshake::from_bytes(truncated).is_err());
+    }
+}
@@ -21,4 +21,5 @@ pub mod get_clients;
 pub mod get_me;
 pub mod get_snapshot;
 pub mod get_stats;
+pub mod handshake;
 pub mod ping;
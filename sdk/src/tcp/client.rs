@@ -17,19 +17,28 @@
  */
 
 use crate::binary::binary_client::BinaryClient;
-use crate::binary::{BinaryTransport, ClientState};
+use crate::binary::multiplexer::Multiplexer;
+use crate::binary::subscription::{MessageSubscription, SubscriptionReader, SubscriptionWriter};
+use crate::binary::{handshake, BinaryTransport, ClientState};
 use crate::client::{
     AutoLogin, Client, ConnectionString, Credentials, PersonalAccessTokenClient, UserClient,
 };
 use crate::command::Command;
+use crate::compression::wire_compression::{
+    compress_frame, decompress_frame, WireCompression, COMPRESSION_FLAG,
+};
 use crate::diagnostic::DiagnosticEvent;
 use crate::error::{IggyError, IggyErrorDiscriminants};
+use crate::messages::subscribe_messages::SubscribeMessages;
+use crate::models::handshake::{ProtocolFeature, ProtocolFeatures};
 use crate::tcp::config::TcpClientConfig;
+use crate::utils::certificates;
 use crate::utils::duration::IggyDuration;
 use crate::utils::timestamp::IggyTimestamp;
 use async_broadcast::{broadcast, Receiver, Sender};
 use async_trait::async_trait;
 use bytes::{BufMut, Bytes, BytesMut};
+use rustls::crypto::CryptoProvider;
 use rustls::pki_types::{pem::PemObject, CertificateDer, ServerName};
 use std::fmt::Debug;
 use std::net::SocketAddr;
@@ -52,6 +61,9 @@ const NAME: &str = "Iggy";
 #[derive(Debug)]
 pub struct TcpClient {
     pub(crate) stream: Mutex<Option<ConnectionStreamKind>>,
+    multiplexer: Mutex<Option<Arc<Multiplexer>>>,
+    features: Mutex<ProtocolFeatures>,
+    compression: Mutex<Option<WireCompression>>,
     pub(crate) config: Arc<TcpClientConfig>,
     pub(crate) state: Mutex<ClientState>,
     client_address: Mutex<Option<SocketAddr>>,
@@ -101,6 +113,16 @@ impl ConnectionStreamKind {
             Self::TcpTls(c) => c.shutdown().await,
         }
     }
+
+    pub fn into_split(self) -> (SubscriptionReader, SubscriptionWriter) {
+        match self {
+            Self::Tcp(c) => (Box::new(c.reader), Box::new(c.writer)),
+            Self::TcpTls(c) => {
+                let (reader, writer) = tokio::io::split(c.stream);
+                (Box::new(reader), Box::new(writer))
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -230,7 +252,7 @@ impl Default for TcpClient {
 
 #[async_trait]
 impl Client for TcpClient {
-    async fn connect(&self) -> Result<(), IggyError> {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
         TcpClient::connect(self).await
     }
 
@@ -257,6 +279,10 @@ impl BinaryTransport for TcpClient {
         *self.state.lock().await = state;
     }
 
+    async fn get_features(&self) -> ProtocolFeatures {
+        *self.features.lock().await
+    }
+
     async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError> {
         command.validate()?;
         self.send_raw_with_response(command.code(), command.to_bytes())
@@ -298,6 +324,31 @@ impl BinaryTransport for TcpClient {
         self.send_raw(code, payload).await
     }
 
+    async fn open_subscription(
+        &self,
+        command: &SubscribeMessages,
+    ) -> Result<MessageSubscription, IggyError> {
+        // The messages are pushed over the dedicated connection, so the regular requests are not blocked meanwhile.
+        // It has to sign in on its own, thus it's available only with the automatic sign-in.
+        if let AutoLogin::Disabled = self.config.auto_login {
+            error!("Cannot subscribe. Automatic sign-in is required to open the subscription connection.");
+            return Err(IggyError::FeatureUnavailable);
+        }
+
+        let client = TcpClient::create(Arc::new(TcpClientConfig {
+            multiplexing: false,
+            ..(*self.config).clone()
+        }))?;
+        let features = client.connect().await?;
+        let Some(stream) = client.stream.lock().await.take() else {
+            error!("Cannot subscribe. Subscription connection is not established.");
+            return Err(IggyError::NotConnected);
+        };
+
+        let (reader, writer) = stream.into_split();
+        MessageSubscription::open(reader, writer, command, features).await
+    }
+
     async fn publish_event(&self, event: DiagnosticEvent) {
         if let Err(error) = self.events.0.broadcast(event).await {
             error!("Failed to send a TCP diagnostic event: {error}");
@@ -355,6 +406,9 @@ impl TcpClient {
             config,
             client_address: Mutex::new(None),
             stream: Mutex::new(None),
+            multiplexer: Mutex::new(None),
+            features: Mutex::new(ProtocolFeatures::default()),
+            compression: Mutex::new(None),
             state: Mutex::new(ClientState::Disconnected),
             events: broadcast(1000),
             connected_at: Mutex::new(None),
@@ -366,8 +420,9 @@ impl TcpClient {
         status: u32,
         length: u32,
         stream: &mut ConnectionStreamKind,
+        compression: Option<&WireCompression>,
     ) -> Result<Bytes, IggyError> {
-        if status != 0 {
+        if status & !COMPRESSION_FLAG != 0 {
             // TEMP: See https://github.com/apache/iggy/pull/604 for context.
             if status == IggyErrorDiscriminants::TopicIdAlreadyExists as u32
                 || status == IggyErrorDiscriminants::TopicNameAlreadyExists as u32
@@ -391,7 +446,14 @@ impl TcpClient {
                 );
             }
 
-            return Err(IggyError::from_code(status));
+            if length == 0 {
+                return Err(IggyError::from_code(status));
+            }
+
+            let mut details_buffer = BytesMut::with_capacity(length as usize);
+            details_buffer.put_bytes(0, length as usize);
+            stream.read(&mut details_buffer).await?;
+            return Err(IggyError::from_code_and_details(status, &details_buffer));
         }
 
         trace!("Status: OK. Response length: {}", length);
@@ -402,10 +464,11 @@ impl TcpClient {
         let mut response_buffer = BytesMut::with_capacity(length as usize);
         response_buffer.put_bytes(0, length as usize);
         stream.read(&mut response_buffer).await?;
-        Ok(response_buffer.freeze())
+        let (_, response) = decompress_frame(compression, status, response_buffer.freeze())?;
+        Ok(response)
     }
 
-    async fn connect(&self) -> Result<(), IggyError> {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
         match self.get_state().await {
             ClientState::Shutdown => {
                 trace!("Cannot connect. Client is shutdown.");
@@ -414,11 +477,11 @@ impl TcpClient {
             ClientState::Connected | ClientState::Authenticating | ClientState::Authenticated => {
                 let client_address = self.get_client_address_value().await;
                 trace!("Client: {client_address} is already connected.");
-                return Ok(());
+                return Ok(*self.features.lock().await);
             }
             ClientState::Connecting => {
                 trace!("Client is already connecting.");
-                return Ok(());
+                return Ok(*self.features.lock().await);
             }
             _ => {}
         }
@@ -533,14 +596,26 @@ impl TcpClient {
                 root_cert_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
             }
 
-            let config = rustls::ClientConfig::builder()
-                .with_root_certificates(root_cert_store)
-                .with_no_client_auth();
+            if CryptoProvider::get_default().is_none() {
+                if let Err(e) = rustls::crypto::ring::default_provider().install_default() {
+                    warn!("Failed to install rustls crypto provider. Error: {:?}. This may be normal if another thread installed it first.", e);
+                }
+            }
+            let config = rustls::ClientConfig::builder().with_root_certificates(root_cert_store);
+            let config = match (&self.config.tls_certificate_file, &self.config.tls_key_file) {
+                (Some(certificate_file), Some(key_file)) => {
+                    let (certificates, key) =
+                        certificates::load_client_certificate(certificate_file, key_file)?;
+                    config
+                        .with_client_auth_cert(certificates, key)
+                        .map_err(|error| {
+                            error!("Failed to use the client certificate for TLS. {error}");
+                            IggyError::InvalidTlsCertificate
+                        })?
+                }
+                _ => config.with_no_client_auth(),
+            };
             let connector = TlsConnector::from(Arc::new(config));
-            let stream = TcpStream::connect(client_address).await.map_err(|error| {
-                error!("Failed to establish TCP connection to the server: {error}",);
-                IggyError::CannotEstablishConnection
-            })?;
             let tls_domain = self.config.tls_domain.to_owned();
             let domain = ServerName::try_from(tls_domain).map_err(|error| {
                 error!("Failed to create a server name from the domain. {error}",);
@@ -565,10 +640,24 @@ impl TcpClient {
         self.set_state(ClientState::Connected).await;
         self.connected_at.lock().await.replace(now);
         self.publish_event(DiagnosticEvent::Connected).await;
+        let features = handshake(self, self.get_supported_features()).await?;
+        *self.features.lock().await = features;
+        let compression = WireCompression::negotiated(&features, self.config.compression_threshold);
+        *self.compression.lock().await = compression;
+        if features.contains(ProtocolFeature::Multiplexing) {
+            if let Some(stream) = self.stream.lock().await.take() {
+                let (reader, writer) = stream.into_split();
+                self.multiplexer
+                    .lock()
+                    .await
+                    .replace(Arc::new(Multiplexer::new(reader, writer, compression)));
+            }
+        }
+
         match &self.config.auto_login {
             AutoLogin::Disabled => {
                 info!("Automatic sign-in is disabled.");
-                Ok(())
+                Ok(features)
             }
             AutoLogin::Enabled(credentials) => {
                 info!("{NAME} client: {client_address} is signing in...");
@@ -577,12 +666,12 @@ impl TcpClient {
                     Credentials::UsernamePassword(username, password) => {
                         self.login_user(username, password).await?;
                         info!("{NAME} client: {client_address} has signed in with the user credentials, username: {username}",);
-                        Ok(())
+                        Ok(features)
                     }
                     Credentials::PersonalAccessToken(token) => {
                         self.login_with_personal_access_token(token).await?;
                         info!("{NAME} client: {client_address} has signed in with a personal access token.",);
-                        Ok(())
+                        Ok(features)
                     }
                 }
             }
@@ -598,6 +687,11 @@ impl TcpClient {
         info!("{NAME} client: {client_address} is disconnecting from server...");
         self.set_state(ClientState::Disconnected).await;
         self.stream.lock().await.take();
+        if let Some(multiplexer) = self.multiplexer.lock().await.take() {
+            multiplexer.close();
+        }
+        *self.features.lock().await = ProtocolFeatures::default();
+        self.compression.lock().await.take();
         self.publish_event(DiagnosticEvent::Disconnected).await;
         let now = IggyTimestamp::now();
         info!("{NAME} client: {client_address} has disconnected from server at: {now}.");
@@ -615,6 +709,9 @@ impl TcpClient {
         if let Some(mut stream) = stream {
             stream.shutdown().await?;
         }
+        if let Some(multiplexer) = self.multiplexer.lock().await.take() {
+            multiplexer.close();
+        }
         self.set_state(ClientState::Shutdown).await;
         self.publish_event(DiagnosticEvent::Shutdown).await;
         info!("{NAME} TCP client: {client_address} has been shutdown.");
@@ -638,6 +735,13 @@ impl TcpClient {
             _ => {}
         }
 
+        let multiplexer = self.multiplexer.lock().await.clone();
+        if let Some(multiplexer) = multiplexer {
+            return multiplexer.send(code, payload).await;
+        }
+
+        let compression = *self.compression.lock().await;
+        let (code, payload) = compress_frame(compression.as_ref(), code, payload);
         let mut stream = self.stream.lock().await;
         if let Some(stream) = stream.as_mut() {
             let payload_length = payload.len() + REQUEST_INITIAL_BYTES_LENGTH;
@@ -673,13 +777,30 @@ impl TcpClient {
                     .try_into()
                     .map_err(|_| IggyError::InvalidNumberEncoding)?,
             );
-            return self.handle_response(status, length, stream).await;
+            return self
+                .handle_response(status, length, stream, compression.as_ref())
+                .await;
         }
 
         error!("Cannot send data. Client is not connected.");
         Err(IggyError::NotConnected)
     }
 
+    /// Returns the protocol features offered to the server during the handshake.
+    fn get_supported_features(&self) -> ProtocolFeatures {
+        let mut features = vec![
+            ProtocolFeature::Subscriptions,
+            ProtocolFeature::ConsumerGroupGenerations,
+        ];
+        if self.config.multiplexing {
+            features.push(ProtocolFeature::Multiplexing);
+        }
+        if let Some(compression) = self.config.compression {
+            features.push(compression.as_feature());
+        }
+        ProtocolFeatures::new(&features)
+    }
+
     async fn get_client_address_value(&self) -> String {
         let client_address = self.client_address.lock().await;
         if let Some(client_address) = &*client_address {
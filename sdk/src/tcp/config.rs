@@ -17,6 +17,10 @@
  */
 
 use crate::client::AutoLogin;
+use crate::compression::wire_compression::{
+    WireCompressionAlgorithm, DEFAULT_COMPRESSION_THRESHOLD,
+};
+use crate::utils::byte_size::IggyByteSize;
 use crate::utils::duration::IggyDuration;
 use std::str::FromStr;
 
@@ -31,6 +35,10 @@ pub struct TcpClientConfig {
     pub tls_domain: String,
     /// The path to the CA file for TLS.
     pub tls_ca_file: Option<String>,
+    /// The path to the PEM file with the client certificate presented to the server, if required.
+    pub tls_certificate_file: Option<String>,
+    /// The path to the PEM file with the private key of the client certificate.
+    pub tls_key_file: Option<String>,
     /// Whether to automatically login user after establishing connection.
     pub auto_login: AutoLogin,
     /// Whether to automatically reconnect when disconnected.
@@ -39,6 +47,13 @@ pub struct TcpClientConfig {
     pub heartbeat_interval: IggyDuration,
     /// Disable Nagle algorithm for the TCP socket.
     pub nodelay: bool,
+    /// Whether to send the requests with their request IDs, so they can be in flight at the same time
+    /// over the single connection and their responses can arrive in any order.
+    pub multiplexing: bool,
+    /// The algorithm offered to compress the request and response payloads sent over the wire, if any.
+    pub compression: Option<WireCompressionAlgorithm>,
+    /// The size of the request payload above which it gets compressed.
+    pub compression_threshold: IggyByteSize,
 }
 
 #[derive(Debug, Clone)]
@@ -56,10 +71,15 @@ impl Default for TcpClientConfig {
             tls_enabled: false,
             tls_domain: "localhost".to_string(),
             tls_ca_file: None,
+            tls_certificate_file: None,
+            tls_key_file: None,
             heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
             auto_login: AutoLogin::Disabled,
             reconnection: TcpClientReconnectionConfig::default(),
             nodelay: false,
+            multiplexing: false,
+            compression: None,
+            compression_threshold: IggyByteSize::from(DEFAULT_COMPRESSION_THRESHOLD),
         }
     }
 }
@@ -83,6 +103,11 @@ impl Default for TcpClientReconnectionConfig {
 /// - `tls_enabled`: Default is false.
 /// - `tls_domain`: Default is "localhost".
 /// - `tls_ca_file`: Default is None.
+/// - `tls_certificate_file`: Default is None.
+/// - `tls_key_file`: Default is None.
+/// - `multiplexing`: Default is false.
+/// - `compression`: Default is None.
+/// - `compression_threshold`: Default is 1 KiB.
 #[derive(Debug, Default)]
 pub struct TcpClientConfigBuilder {
     config: TcpClientConfig,
@@ -140,12 +165,41 @@ impl TcpClientConfigBuilder {
         self
     }
 
+    /// Sets the paths to the client certificate and its private key, presented to the server verifying the clients.
+    pub fn with_tls_client_certificate(
+        mut self,
+        certificate_file: String,
+        key_file: String,
+    ) -> Self {
+        self.config.tls_certificate_file = Some(certificate_file);
+        self.config.tls_key_file = Some(key_file);
+        self
+    }
+
     /// Sets the nodelay option for the TCP socket.
     pub fn with_no_delay(mut self) -> Self {
         self.config.nodelay = true;
         self
     }
 
+    /// Enables the multiplexing of the requests sent over the single connection.
+    pub fn with_multiplexing(mut self) -> Self {
+        self.config.multiplexing = true;
+        self
+    }
+
+    /// Sets the algorithm offered to compress the payloads sent over the wire.
+    pub fn with_compression(mut self, compression: WireCompressionAlgorithm) -> Self {
+        self.config.compression = Some(compression);
+        self
+    }
+
+    /// Sets the size of the request payload above which it gets compressed.
+    pub fn with_compression_threshold(mut self, threshold: IggyByteSize) -> Self {
+        self.config.compression_threshold = threshold;
+        self
+    }
+
     /// Builds the TCP client configuration.
     pub fn build(self) -> TcpClientConfig {
         self.config
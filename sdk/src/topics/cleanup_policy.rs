@@ -0,0 +1,225 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::models::header::HeaderKey;
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::sizeable::Sizeable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
+use std::str::{from_utf8, FromStr};
+
+/// The header storing the `MessagesKey` partitioning value of the messages appended to the topic
+/// compacted by the messages key.
+pub const MESSAGES_KEY_HEADER: &str = "iggy-messages-key";
+
+/// `CleanupPolicy` determines how the messages are removed from the topic.
+#[derive(Debug, PartialEq, Default, Clone)]
+pub enum CleanupPolicy {
+    /// The whole segments are deleted once they expire or the topic reaches its max size.
+    #[default]
+    Delete,
+    /// Only the latest message for each key is kept in the closed segments,
+    /// and the message with an empty payload (tombstone) deletes the key.
+    Compact(CompactionKey),
+}
+
+/// `CompactionKey` determines where the key of the message is taken from when compacting the topic.
+#[derive(Debug, PartialEq, Clone)]
+pub enum CompactionKey {
+    /// The `MessagesKey` partitioning value used when sending the messages.
+    MessagesKey,
+    /// The value of the message header with the specified key.
+    Header(HeaderKey),
+}
+
+impl CleanupPolicy {
+    /// Returns the code of the cleanup policy.
+    pub fn as_code(&self) -> u8 {
+        match self {
+            CleanupPolicy::Delete => 1,
+            CleanupPolicy::Compact(CompactionKey::MessagesKey) => 2,
+            CleanupPolicy::Compact(CompactionKey::Header(_)) => 3,
+        }
+    }
+
+    /// Returns the key the messages are compacted by, if the compaction is enabled.
+    pub fn compaction_key(&self) -> Option<&CompactionKey> {
+        match self {
+            CleanupPolicy::Delete => None,
+            CleanupPolicy::Compact(key) => Some(key),
+        }
+    }
+}
+
+impl CompactionKey {
+    /// Returns the header storing the key of the message.
+    pub fn header_key(&self) -> HeaderKey {
+        match self {
+            CompactionKey::MessagesKey => HeaderKey::new(MESSAGES_KEY_HEADER).unwrap(),
+            CompactionKey::Header(key) => key.clone(),
+        }
+    }
+}
+
+impl Sizeable for CleanupPolicy {
+    fn get_size_bytes(&self) -> IggyByteSize {
+        match self {
+            CleanupPolicy::Compact(CompactionKey::Header(key)) => {
+                IggyByteSize::from(2 + key.as_str().len() as u64)
+            }
+            _ => IggyByteSize::from(1),
+        }
+    }
+}
+
+impl BytesSerializable for CleanupPolicy {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(self.get_size_bytes().as_bytes_usize());
+        bytes.put_u8(self.as_code());
+        if let CleanupPolicy::Compact(CompactionKey::Header(key)) = self {
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u8(key.as_str().len() as u8);
+            bytes.put_slice(key.as_str().as_bytes());
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.is_empty() {
+            return Err(IggyError::InvalidCleanupPolicy);
+        }
+
+        match bytes[0] {
+            1 => Ok(CleanupPolicy::Delete),
+            2 => Ok(CleanupPolicy::Compact(CompactionKey::MessagesKey)),
+            3 => {
+                if bytes.len() < 2 {
+                    return Err(IggyError::InvalidCleanupPolicy);
+                }
+
+                let key_length = bytes[1] as usize;
+                let key = bytes
+                    .get(2..2 + key_length)
+                    .ok_or(IggyError::InvalidCleanupPolicy)?;
+                let key = from_utf8(key).map_err(|_| IggyError::InvalidUtf8)?;
+                Ok(CleanupPolicy::Compact(CompactionKey::Header(
+                    HeaderKey::new(key)?,
+                )))
+            }
+            _ => Err(IggyError::InvalidCleanupPolicy),
+        }
+    }
+}
+
+impl FromStr for CleanupPolicy {
+    type Err = IggyError;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "delete" => Ok(CleanupPolicy::Delete),
+            "compact" => Ok(CleanupPolicy::Compact(CompactionKey::MessagesKey)),
+            _ => match input.strip_prefix("compact:") {
+                Some(key) => Ok(CleanupPolicy::Compact(CompactionKey::Header(
+                    HeaderKey::new(key).map_err(|_| IggyError::InvalidCleanupPolicy)?,
+                ))),
+                None => Err(IggyError::InvalidCleanupPolicy),
+            },
+        }
+    }
+}
+
+impl Display for CleanupPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CleanupPolicy::Delete => write!(f, "delete"),
+            CleanupPolicy::Compact(CompactionKey::MessagesKey) => write!(f, "compact"),
+            CleanupPolicy::Compact(CompactionKey::Header(key)) => write!(f, "compact:{key}"),
+        }
+    }
+}
+
+impl Serialize for CleanupPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for CleanupPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        CleanupPolicy::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let policies = [
+            CleanupPolicy::Delete,
+            CleanupPolicy::Compact(CompactionKey::MessagesKey),
+            CleanupPolicy::Compact(CompactionKey::Header(HeaderKey::new("user-id").unwrap())),
+        ];
+        for policy in policies {
+            let bytes = policy.to_bytes();
+            assert_eq!(bytes.len(), policy.get_size_bytes().as_bytes_usize());
+            assert_eq!(CleanupPolicy::from_bytes(bytes).unwrap(), policy);
+        }
+    }
+
+    #[test]
+    fn should_be_parsed_from_string() {
+        assert_eq!(
+            CleanupPolicy::from_str("delete").unwrap(),
+            CleanupPolicy::Delete
+        );
+        assert_eq!(
+            CleanupPolicy::from_str("compact").unwrap(),
+            CleanupPolicy::Compact(CompactionKey::MessagesKey)
+        );
+        assert_eq!(
+            CleanupPolicy::from_str("compact:user-id").unwrap(),
+            CleanupPolicy::Compact(CompactionKey::Header(HeaderKey::new("user-id").unwrap()))
+        );
+        assert_eq!(
+            CleanupPolicy::from_str("retain"),
+            Err(IggyError::InvalidCleanupPolicy)
+        );
+    }
+
+    #[test]
+    fn messages_key_should_be_stored_in_reserved_header() {
+        assert_eq!(
+            CompactionKey::MessagesKey.header_key().as_str(),
+            MESSAGES_KEY_HEADER
+        );
+    }
+}
@@ -21,6 +21,7 @@ use crate::command::{Command, CREATE_TOPIC_CODE};
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::topics::{MAX_NAME_LENGTH, MAX_PARTITIONS_COUNT};
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::sizeable::Sizeable;
@@ -41,6 +42,7 @@ use std::str::from_utf8;
 ///   Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `cleanup_policy` - cleanup policy, if `Compact` then only the latest message for each key is kept.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CreateTopic {
     /// Unique stream ID (numeric or name).
@@ -61,6 +63,9 @@ pub struct CreateTopic {
     pub replication_factor: Option<u8>,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
+    /// Cleanup policy, if `Compact` then only the latest message for each key is kept.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
 }
 
 impl Command for CreateTopic {
@@ -80,6 +85,7 @@ impl Default for CreateTopic {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
             name: "topic".to_string(),
+            cleanup_policy: CleanupPolicy::default(),
         }
     }
 }
@@ -104,6 +110,11 @@ impl Validatable<IggyError> for CreateTopic {
             if replication_factor == 0 {
                 return Err(IggyError::InvalidReplicationFactor);
             }
+
+            // The followers fetch the contiguous offsets, so the compacted segments can't be replicated.
+            if replication_factor > 1 && self.cleanup_policy.compaction_key().is_some() {
+                return Err(IggyError::InvalidCleanupPolicy);
+            }
         }
 
         Ok(())
@@ -113,7 +124,10 @@ impl Validatable<IggyError> for CreateTopic {
 impl BytesSerializable for CreateTopic {
     fn to_bytes(&self) -> Bytes {
         let stream_id_bytes = self.stream_id.to_bytes();
-        let mut bytes = BytesMut::with_capacity(23 + stream_id_bytes.len() + self.name.len());
+        let cleanup_policy_bytes = self.cleanup_policy.to_bytes();
+        let mut bytes = BytesMut::with_capacity(
+            23 + stream_id_bytes.len() + self.name.len() + cleanup_policy_bytes.len(),
+        );
         bytes.put_slice(&stream_id_bytes);
         bytes.put_u32_le(self.topic_id.unwrap_or(0));
         bytes.put_u32_le(self.partitions_count);
@@ -127,6 +141,7 @@ impl BytesSerializable for CreateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_slice(&cleanup_policy_bytes);
         bytes.freeze()
     }
 
@@ -172,6 +187,13 @@ impl BytesSerializable for CreateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        position += 27 + name_length as usize;
+        // The commands persisted before the cleanup policy was introduced don't contain it.
+        let cleanup_policy = if bytes.len() > position {
+            CleanupPolicy::from_bytes(bytes.slice(position..))?
+        } else {
+            CleanupPolicy::default()
+        };
         let command = CreateTopic {
             stream_id,
             topic_id,
@@ -181,6 +203,7 @@ impl BytesSerializable for CreateTopic {
             max_topic_size,
             replication_factor,
             name,
+            cleanup_policy,
         };
         Ok(command)
     }
@@ -190,14 +213,15 @@ impl Display for CreateTopic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}",
             self.stream_id,
             self.topic_id.unwrap_or(0),
             self.partitions_count,
             self.message_expiry,
             self.max_topic_size,
             self.replication_factor.unwrap_or(0),
-            self.name
+            self.name,
+            self.cleanup_policy
         )
     }
 }
@@ -205,6 +229,8 @@ impl Display for CreateTopic {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::header::HeaderKey;
+    use crate::topics::cleanup_policy::CompactionKey;
     use bytes::BufMut;
 
     #[test]
@@ -218,6 +244,7 @@ mod tests {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
             name: "test".to_string(),
+            cleanup_policy: CleanupPolicy::Compact(CompactionKey::MessagesKey),
         };
         let bytes = command.to_bytes();
         let mut position = 0;
@@ -238,6 +265,8 @@ mod tests {
         let name = from_utf8(&bytes[position + 27..(position + 27 + name_length as usize)])
             .unwrap()
             .to_string();
+        let cleanup_policy =
+            CleanupPolicy::from_bytes(bytes.slice(position + 27 + name_length as usize..)).unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -249,6 +278,7 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(cleanup_policy, command.cleanup_policy);
     }
 
     #[test]
@@ -261,6 +291,8 @@ mod tests {
         let message_expiry = IggyExpiry::NeverExpire;
         let max_topic_size = MaxTopicSize::ServerDefault;
         let replication_factor = 1;
+        let cleanup_policy =
+            CleanupPolicy::Compact(CompactionKey::Header(HeaderKey::new("user-id").unwrap()));
         let stream_id_bytes = stream_id.to_bytes();
         let mut bytes = BytesMut::with_capacity(14 + stream_id_bytes.len() + name.len());
         bytes.put_slice(&stream_id_bytes);
@@ -273,6 +305,7 @@ mod tests {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(name.len() as u8);
         bytes.put_slice(name.as_bytes());
+        bytes.put_slice(&cleanup_policy.to_bytes());
 
         let command = CreateTopic::from_bytes(bytes.freeze());
         assert!(command.is_ok());
@@ -287,5 +320,6 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor.unwrap(), replication_factor);
         assert_eq!(command.partitions_count, partitions_count);
+        assert_eq!(command.cleanup_policy, cleanup_policy);
     }
 }
@@ -16,6 +16,7 @@
  * under the License.
  */
 
+pub mod cleanup_policy;
 pub mod create_topic;
 pub mod delete_topic;
 pub mod get_topic;
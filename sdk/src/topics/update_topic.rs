@@ -21,6 +21,7 @@ use crate::command::{Command, UPDATE_TOPIC_CODE};
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::topics::cleanup_policy::CleanupPolicy;
 use crate::topics::MAX_NAME_LENGTH;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::sizeable::Sizeable;
@@ -40,6 +41,7 @@ use std::str::from_utf8;
 ///   Can't be lower than segment size in the config.
 /// - `replication_factor` - replication factor for the topic.
 /// - `name` - unique topic name, max length is 255 characters.
+/// - `cleanup_policy` - cleanup policy, if `Compact` then only the latest message for each key is kept.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct UpdateTopic {
     /// Unique stream ID (numeric or name).
@@ -59,6 +61,9 @@ pub struct UpdateTopic {
     pub replication_factor: Option<u8>,
     /// Unique topic name, max length is 255 characters.
     pub name: String,
+    /// Cleanup policy, if `Compact` then only the latest message for each key is kept.
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
 }
 
 impl Command for UpdateTopic {
@@ -77,6 +82,7 @@ impl Default for UpdateTopic {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: None,
             name: "topic".to_string(),
+            cleanup_policy: CleanupPolicy::default(),
         }
     }
 }
@@ -91,6 +97,11 @@ impl Validatable<IggyError> for UpdateTopic {
             if replication_factor == 0 {
                 return Err(IggyError::InvalidReplicationFactor);
             }
+
+            // The followers fetch the contiguous offsets, so the compacted segments can't be replicated.
+            if replication_factor > 1 && self.cleanup_policy.compaction_key().is_some() {
+                return Err(IggyError::InvalidCleanupPolicy);
+            }
         }
 
         Ok(())
@@ -101,8 +112,12 @@ impl BytesSerializable for UpdateTopic {
     fn to_bytes(&self) -> Bytes {
         let stream_id_bytes = self.stream_id.to_bytes();
         let topic_id_bytes = self.topic_id.to_bytes();
+        let cleanup_policy_bytes = self.cleanup_policy.to_bytes();
         let mut bytes = BytesMut::with_capacity(
-            19 + stream_id_bytes.len() + topic_id_bytes.len() + self.name.len(),
+            19 + stream_id_bytes.len()
+                + topic_id_bytes.len()
+                + self.name.len()
+                + cleanup_policy_bytes.len(),
         );
         bytes.put_slice(&stream_id_bytes.clone());
         bytes.put_slice(&topic_id_bytes.clone());
@@ -116,6 +131,7 @@ impl BytesSerializable for UpdateTopic {
         #[allow(clippy::cast_possible_truncation)]
         bytes.put_u8(self.name.len() as u8);
         bytes.put_slice(self.name.as_bytes());
+        bytes.put_slice(&cleanup_policy_bytes);
         bytes.freeze()
     }
 
@@ -153,6 +169,13 @@ impl BytesSerializable for UpdateTopic {
         if name.len() != name_length as usize {
             return Err(IggyError::InvalidCommand);
         }
+        position += 18 + name_length as usize;
+        // The commands persisted before the cleanup policy was introduced don't contain it.
+        let cleanup_policy = if bytes.len() > position {
+            CleanupPolicy::from_bytes(bytes.slice(position..))?
+        } else {
+            CleanupPolicy::default()
+        };
         let command = UpdateTopic {
             stream_id,
             topic_id,
@@ -161,6 +184,7 @@ impl BytesSerializable for UpdateTopic {
             max_topic_size,
             replication_factor,
             name,
+            cleanup_policy,
         };
         Ok(command)
     }
@@ -170,13 +194,14 @@ impl Display for UpdateTopic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}",
             self.stream_id,
             self.topic_id,
             self.message_expiry,
             self.max_topic_size,
             self.replication_factor.unwrap_or(0),
             self.name,
+            self.cleanup_policy,
         )
     }
 }
@@ -184,6 +209,7 @@ impl Display for UpdateTopic {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::topics::cleanup_policy::CompactionKey;
     use crate::utils::byte_size::IggyByteSize;
     use bytes::BufMut;
 
@@ -197,6 +223,7 @@ mod tests {
             max_topic_size: MaxTopicSize::ServerDefault,
             replication_factor: Some(1),
             name: "test".to_string(),
+            cleanup_policy: CleanupPolicy::Compact(CompactionKey::MessagesKey),
         };
 
         let bytes = command.to_bytes();
@@ -217,6 +244,8 @@ mod tests {
         let name = from_utf8(&bytes[position + 18..position + 18 + name_length as usize])
             .unwrap()
             .to_string();
+        let cleanup_policy =
+            CleanupPolicy::from_bytes(bytes.slice(position + 18 + name_length as usize..)).unwrap();
 
         assert!(!bytes.is_empty());
         assert_eq!(stream_id, command.stream_id);
@@ -227,6 +256,7 @@ mod tests {
         assert_eq!(replication_factor, command.replication_factor.unwrap());
         assert_eq!(name.len() as u8, command.name.len() as u8);
         assert_eq!(name, command.name);
+        assert_eq!(cleanup_policy, command.cleanup_policy);
     }
 
     #[test]
@@ -265,5 +295,6 @@ mod tests {
         assert_eq!(command.max_topic_size, max_topic_size);
         assert_eq!(command.replication_factor, Some(replication_factor));
         assert_eq!(command.name, name);
+        assert_eq!(command.cleanup_policy, CleanupPolicy::Delete);
     }
 }
@@ -23,6 +23,7 @@ pub mod delete_user;
 pub mod get_user;
 pub mod get_users;
 pub mod login_user;
+pub mod login_with_oidc_token;
 pub mod logout_user;
 pub mod update_permissions;
 pub mod update_user;
@@ -20,6 +20,7 @@ use crate::bytes_serializable::BytesSerializable;
 use crate::command::{Command, UPDATE_USER_CODE};
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::models::user_quotas::UserQuotas;
 use crate::models::user_status::UserStatus;
 use crate::users::defaults::*;
 use crate::utils::sizeable::Sizeable;
@@ -29,17 +30,20 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::str::from_utf8;
 
-/// `UpdateUser` command is used to update a user's username and status.
+/// `UpdateUser` command is used to update a user's username, status and quotas.
 /// It has additional payload:
 /// - `user_id` - unique user ID (numeric or name).
 /// - `username` - new username (optional), if provided, must be between 3 and 50 characters long.
 /// - `status` - new status (optional)
+/// - `quotas` - new quotas (optional), if provided, replace the existing ones, the value of 0 means that the rate is unlimited.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 pub struct UpdateUser {
     #[serde(skip)]
     pub user_id: Identifier,
     pub username: Option<String>,
     pub status: Option<UserStatus>,
+    #[serde(default)]
+    pub quotas: Option<UserQuotas>,
 }
 
 impl Command for UpdateUser {
@@ -85,6 +89,13 @@ impl BytesSerializable for UpdateUser {
         } else {
             bytes.put_u8(0);
         }
+        if let Some(quotas) = &self.quotas {
+            bytes.put_u8(1);
+            let quotas = quotas.to_bytes();
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u32_le(quotas.len() as u32);
+            bytes.put_slice(&quotas);
+        }
 
         bytes.freeze()
     }
@@ -126,11 +137,45 @@ impl BytesSerializable for UpdateUser {
         } else {
             None
         };
+        position += 1;
+
+        // The quotas are optional and may be missing in the commands sent by the older clients.
+        let quotas = if bytes.len() > position {
+            let has_quotas = bytes[position];
+            if has_quotas > 1 {
+                return Err(IggyError::InvalidCommand);
+            }
+
+            position += 1;
+            if has_quotas == 1 {
+                if bytes.len() < position + 4 {
+                    return Err(IggyError::InvalidCommand);
+                }
+
+                let quotas_length = u32::from_le_bytes(
+                    bytes[position..position + 4]
+                        .try_into()
+                        .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                ) as usize;
+                position += 4;
+                if bytes.len() < position + quotas_length {
+                    return Err(IggyError::InvalidCommand);
+                }
+
+                let quotas = bytes.slice(position..position + quotas_length);
+                Some(UserQuotas::from_bytes(quotas)?)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
 
         let command = UpdateUser {
             user_id,
             username,
             status,
+            quotas,
         };
         Ok(command)
     }
@@ -143,13 +188,18 @@ impl Display for UpdateUser {
             .status
             .as_ref()
             .map_or_else(String::new, |s| s.to_string());
-        write!(f, "{}|{username}|{status}", self.user_id)
+        let quotas = self
+            .quotas
+            .as_ref()
+            .map_or_else(String::new, |q| q.to_string());
+        write!(f, "{}|{username}|{status}|{quotas}", self.user_id)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::user_quotas::GlobalQuotas;
 
     #[test]
     fn should_be_serialized_as_bytes() {
@@ -157,6 +207,7 @@ mod tests {
             user_id: Identifier::numeric(1).unwrap(),
             username: Some("user".to_string()),
             status: Some(UserStatus::Active),
+            quotas: None,
         };
 
         let bytes = command.to_bytes();
@@ -201,4 +252,24 @@ mod tests {
         assert_eq!(command.username.unwrap(), username);
         assert_eq!(command.status.unwrap(), status);
     }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_with_quotas() {
+        let command = UpdateUser {
+            user_id: Identifier::numeric(1).unwrap(),
+            username: None,
+            status: Some(UserStatus::Active),
+            quotas: Some(UserQuotas {
+                global: GlobalQuotas {
+                    produce_bytes_per_second: 1000,
+                    consume_bytes_per_second: 2000,
+                    requests_per_second: 10,
+                },
+                streams: None,
+            }),
+        };
+
+        let deserialized_command = UpdateUser::from_bytes(command.to_bytes()).unwrap();
+        assert_eq!(deserialized_command, command);
+    }
 }
@@ -0,0 +1,61 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::error::IggyError;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tracing::error;
+
+/// Loads the client certificate chain and its private key from the PEM files.
+pub(crate) fn load_client_certificate(
+    certificate_file: &str,
+    key_file: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), IggyError> {
+    let certificates = CertificateDer::pem_file_iter(certificate_file)
+        .map_err(|error| {
+            error!("Failed to read the client certificate file: {certificate_file}. {error}");
+            IggyError::InvalidTlsCertificatePath
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| {
+            error!("Failed to read the client certificate from file: {certificate_file}. {error}");
+            IggyError::InvalidTlsCertificate
+        })?;
+    let key = PrivateKeyDer::from_pem_file(key_file).map_err(|error| {
+        error!("Failed to read the client certificate key from file: {key_file}. {error}");
+        IggyError::InvalidTlsCertificate
+    })?;
+    Ok((certificates, key))
+}
+
+/// Reads the client certificate chain followed by its private key as the single PEM buffer.
+pub(crate) fn read_client_certificate_pem(
+    certificate_file: &str,
+    key_file: &str,
+) -> Result<Vec<u8>, IggyError> {
+    let mut pem = Vec::new();
+    for file in [certificate_file, key_file] {
+        let mut content = std::fs::read(file).map_err(|error| {
+            error!("Failed to read the client certificate file: {file}. {error}");
+            IggyError::InvalidTlsCertificatePath
+        })?;
+        pem.append(&mut content);
+        pem.push(b'\n');
+    }
+    Ok(pem)
+}
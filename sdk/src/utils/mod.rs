@@ -17,6 +17,7 @@
  */
 
 pub mod byte_size;
+pub(crate) mod certificates;
 pub mod checksum;
 pub mod crypto;
 pub mod duration;
@@ -0,0 +1,451 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::binary_client::BinaryClient;
+use crate::binary::subscription::{MessageSubscription, SubscriptionReader, SubscriptionWriter};
+use crate::binary::{BinaryTransport, ClientState};
+use crate::client::{AutoLogin, Client, Credentials, PersonalAccessTokenClient, UserClient};
+use crate::command::Command;
+use crate::diagnostic::DiagnosticEvent;
+use crate::error::IggyError;
+use crate::messages::subscribe_messages::SubscribeMessages;
+use crate::models::handshake::ProtocolFeatures;
+use crate::utils::duration::IggyDuration;
+use crate::utils::timestamp::IggyTimestamp;
+use crate::websocket::config::WebSocketClientConfig;
+use async_broadcast::{broadcast, Receiver, Sender};
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{error, info, trace};
+
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 8;
+const BRIDGE_BUFFER_SIZE: usize = 64 * 1024;
+const NAME: &str = "Iggy";
+
+type WebSocketConnection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// WebSocket client for interacting with the Iggy API.
+/// It speaks the binary protocol over the WebSocket endpoint of the HTTP server,
+/// each request and response is sent as a single binary message.
+#[derive(Debug)]
+pub struct WebSocketClient {
+    pub(crate) connection: Mutex<Option<WebSocketConnection>>,
+    pub(crate) config: Arc<WebSocketClientConfig>,
+    pub(crate) state: Mutex<ClientState>,
+    events: (Sender<DiagnosticEvent>, Receiver<DiagnosticEvent>),
+}
+
+impl Default for WebSocketClient {
+    fn default() -> Self {
+        WebSocketClient::create(Arc::new(WebSocketClientConfig::default())).unwrap()
+    }
+}
+
+#[async_trait]
+impl Client for WebSocketClient {
+    async fn connect(&self) -> Result<ProtocolFeatures, IggyError> {
+        WebSocketClient::connect(self).await?;
+        // The handshake is not sent over WebSocket yet, so no protocol features are negotiated.
+        Ok(ProtocolFeatures::default())
+    }
+
+    async fn disconnect(&self) -> Result<(), IggyError> {
+        WebSocketClient::disconnect(self).await
+    }
+
+    async fn shutdown(&self) -> Result<(), IggyError> {
+        WebSocketClient::shutdown(self).await
+    }
+
+    async fn subscribe_events(&self) -> Receiver<DiagnosticEvent> {
+        self.events.1.clone()
+    }
+}
+
+#[async_trait]
+impl BinaryTransport for WebSocketClient {
+    async fn get_state(&self) -> ClientState {
+        *self.state.lock().await
+    }
+
+    async fn set_state(&self, state: ClientState) {
+        *self.state.lock().await = state;
+    }
+
+    async fn get_features(&self) -> ProtocolFeatures {
+        ProtocolFeatures::default()
+    }
+
+    async fn send_with_response<T: Command>(&self, command: &T) -> Result<Bytes, IggyError> {
+        command.validate()?;
+        self.send_raw_with_response(command.code(), command.to_bytes())
+            .await
+    }
+
+    async fn send_raw_with_response(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        self.send_raw(code, payload).await
+    }
+
+    async fn open_subscription(
+        &self,
+        command: &SubscribeMessages,
+    ) -> Result<MessageSubscription, IggyError> {
+        // The messages are pushed over the dedicated connection, so the regular requests are not blocked meanwhile.
+        // It has to sign in on its own, thus it's available only with the automatic sign-in.
+        if let AutoLogin::Disabled = self.config.auto_login {
+            error!("Cannot subscribe. Automatic sign-in is required to open the subscription connection.");
+            return Err(IggyError::FeatureUnavailable);
+        }
+
+        let client = WebSocketClient::create(self.config.clone())?;
+        client.connect().await?;
+        let Some(connection) = client.connection.lock().await.take() else {
+            error!("Cannot subscribe. Subscription connection is not established.");
+            return Err(IggyError::NotConnected);
+        };
+
+        let (reader, writer) = into_split(connection);
+        MessageSubscription::open(reader, writer, command, ProtocolFeatures::default()).await
+    }
+
+    async fn publish_event(&self, event: DiagnosticEvent) {
+        if let Err(error) = self.events.0.broadcast(event).await {
+            error!("Failed to send a WebSocket diagnostic event: {error}");
+        }
+    }
+
+    fn get_heartbeat_interval(&self) -> IggyDuration {
+        self.config.heartbeat_interval
+    }
+}
+
+impl BinaryClient for WebSocketClient {}
+
+impl WebSocketClient {
+    /// Create a new WebSocket client for the provided server address.
+    pub fn new(
+        server_address: &str,
+        auto_sign_in: AutoLogin,
+        heartbeat_interval: IggyDuration,
+    ) -> Result<Self, IggyError> {
+        Self::create(Arc::new(WebSocketClientConfig {
+            heartbeat_interval,
+            server_address: server_address.to_string(),
+            auto_login: auto_sign_in,
+        }))
+    }
+
+    /// Create a new WebSocket client based on the provided configuration.
+    pub fn create(config: Arc<WebSocketClientConfig>) -> Result<Self, IggyError> {
+        Ok(Self {
+            config,
+            connection: Mutex::new(None),
+            state: Mutex::new(ClientState::Disconnected),
+            events: broadcast(1000),
+        })
+    }
+
+    async fn connect(&self) -> Result<(), IggyError> {
+        match self.get_state().await {
+            ClientState::Shutdown => {
+                trace!("Cannot connect. Client is shutdown.");
+                return Err(IggyError::ClientShutdown);
+            }
+            ClientState::Connected | ClientState::Authenticating | ClientState::Authenticated => {
+                trace!("Client is already connected.");
+                return Ok(());
+            }
+            ClientState::Connecting => {
+                trace!("Client is already connecting.");
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        self.set_state(ClientState::Connecting).await;
+        info!(
+            "{NAME} client is connecting to server: {}...",
+            self.config.server_address
+        );
+        let connection = match tokio_tungstenite::connect_async(&self.config.server_address).await {
+            Ok((connection, _)) => connection,
+            Err(error) => {
+                error!(
+                    "Failed to establish WebSocket connection to the server: {}. {error}",
+                    self.config.server_address
+                );
+                self.set_state(ClientState::Disconnected).await;
+                self.publish_event(DiagnosticEvent::Disconnected).await;
+                return Err(IggyError::CannotEstablishConnection);
+            }
+        };
+
+        let now = IggyTimestamp::now();
+        info!(
+            "{NAME} client has connected to server: {} at: {now}",
+            self.config.server_address
+        );
+        self.connection.lock().await.replace(connection);
+        self.set_state(ClientState::Connected).await;
+        self.publish_event(DiagnosticEvent::Connected).await;
+        match &self.config.auto_login {
+            AutoLogin::Disabled => {
+                info!("Automatic sign-in is disabled.");
+                Ok(())
+            }
+            AutoLogin::Enabled(credentials) => {
+                info!("{NAME} client is signing in...");
+                self.set_state(ClientState::Authenticating).await;
+                match credentials {
+                    Credentials::UsernamePassword(username, password) => {
+                        self.login_user(username, password).await?;
+                        info!("{NAME} client has signed in with the user credentials, username: {username}",);
+                        Ok(())
+                    }
+                    Credentials::PersonalAccessToken(token) => {
+                        self.login_with_personal_access_token(token).await?;
+                        info!("{NAME} client has signed in with a personal access token.",);
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    async fn disconnect(&self) -> Result<(), IggyError> {
+        if self.get_state().await == ClientState::Disconnected {
+            return Ok(());
+        }
+
+        info!("{NAME} client is disconnecting from server...");
+        self.set_state(ClientState::Disconnected).await;
+        self.connection.lock().await.take();
+        self.publish_event(DiagnosticEvent::Disconnected).await;
+        let now = IggyTimestamp::now();
+        info!("{NAME} client has disconnected from server at: {now}.");
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), IggyError> {
+        if self.get_state().await == ClientState::Shutdown {
+            return Ok(());
+        }
+
+        info!("Shutting down the {NAME} WebSocket client.");
+        let connection = self.connection.lock().await.take();
+        if let Some(mut connection) = connection {
+            connection.close(None).await.map_err(|error| {
+                error!("Failed to close the WebSocket connection: {error}");
+                IggyError::WebSocketError
+            })?;
+        }
+        self.set_state(ClientState::Shutdown).await;
+        self.publish_event(DiagnosticEvent::Shutdown).await;
+        info!("{NAME} WebSocket client has been shutdown.");
+        Ok(())
+    }
+
+    async fn send_raw(&self, code: u32, payload: Bytes) -> Result<Bytes, IggyError> {
+        match self.get_state().await {
+            ClientState::Shutdown => {
+                trace!("Cannot send data. Client is shutdown.");
+                return Err(IggyError::ClientShutdown);
+            }
+            ClientState::Disconnected => {
+                trace!("Cannot send data. Client is not connected.");
+                return Err(IggyError::NotConnected);
+            }
+            ClientState::Connecting => {
+                trace!("Cannot send data. Client is still connecting.");
+                return Err(IggyError::NotConnected);
+            }
+            _ => {}
+        }
+
+        let mut connection = self.connection.lock().await;
+        let Some(connection) = connection.as_mut() else {
+            error!("Cannot send data. Client is not connected.");
+            return Err(IggyError::NotConnected);
+        };
+
+        let mut request = BytesMut::with_capacity(2 * REQUEST_INITIAL_BYTES_LENGTH + payload.len());
+        request.put_u32_le((payload.len() + REQUEST_INITIAL_BYTES_LENGTH) as u32);
+        request.put_u32_le(code);
+        request.put_slice(&payload);
+        trace!("Sending a WebSocket request with code: {code}");
+        connection
+            .send(Message::Binary(request.freeze()))
+            .await
+            .map_err(|error| {
+                error!("Failed to send WebSocket request with code: {code}: {error}");
+                IggyError::Disconnected
+            })?;
+        trace!("Sent a WebSocket request with code: {code}, waiting for a response...");
+
+        loop {
+            match connection.next().await {
+                Some(Ok(Message::Binary(response))) => return handle_response(response),
+                Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => continue,
+                Some(Ok(Message::Text(_))) => {
+                    error!("Received an unexpected WebSocket text message.");
+                    return Err(IggyError::InvalidBytesResponse);
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    error!("WebSocket connection was closed by the server.");
+                    return Err(IggyError::Disconnected);
+                }
+                Some(Err(error)) => {
+                    error!(
+                        "Failed to read response for WebSocket request with code: {code}: {error}"
+                    );
+                    return Err(IggyError::Disconnected);
+                }
+            }
+        }
+    }
+}
+
+fn handle_response(response: Bytes) -> Result<Bytes, IggyError> {
+    if response.len() < RESPONSE_INITIAL_BYTES_LENGTH {
+        error!("Received an invalid or empty response.");
+        return Err(IggyError::EmptyResponse);
+    }
+
+    let status = u32::from_le_bytes(
+        response[..4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    let length = u32::from_le_bytes(
+        response[4..RESPONSE_INITIAL_BYTES_LENGTH]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    ) as usize;
+    if status != 0 {
+        error!(
+            "Received an invalid response with status: {} ({}).",
+            status,
+            IggyError::from_code_as_string(status),
+        );
+        let details = response
+            .get(RESPONSE_INITIAL_BYTES_LENGTH..RESPONSE_INITIAL_BYTES_LENGTH + length)
+            .unwrap_or_default();
+        return Err(IggyError::from_code_and_details(status, details));
+    }
+
+    trace!("Status: OK. Response length: {}", length);
+    if response.len() < RESPONSE_INITIAL_BYTES_LENGTH + length {
+        error!("Received a truncated response.");
+        return Err(IggyError::InvalidBytesResponse);
+    }
+
+    Ok(response.slice(RESPONSE_INITIAL_BYTES_LENGTH..RESPONSE_INITIAL_BYTES_LENGTH + length))
+}
+
+/// Turns the connection into the byte stream used by the subscription,
+/// the requests and the pushed frames are relayed to and from the binary messages in the background.
+fn into_split(connection: WebSocketConnection) -> (SubscriptionReader, SubscriptionWriter) {
+    let (subscription, bridged_subscription) = tokio::io::duplex(BRIDGE_BUFFER_SIZE);
+    tokio::spawn(bridge(connection, bridged_subscription));
+    let (reader, writer) = tokio::io::split(subscription);
+    (Box::new(reader), Box::new(writer))
+}
+
+async fn bridge(connection: WebSocketConnection, subscription: DuplexStream) {
+    let (mut connection_sender, mut connection_receiver) = connection.split();
+    let (mut reader, mut writer) = tokio::io::split(subscription);
+    let requests = async {
+        let mut length = [0u8; REQUEST_INITIAL_BYTES_LENGTH];
+        while reader.read_exact(&mut length).await.is_ok() {
+            let request_length = u32::from_le_bytes(length) as usize;
+            let mut request = vec![0u8; REQUEST_INITIAL_BYTES_LENGTH + request_length];
+            request[..REQUEST_INITIAL_BYTES_LENGTH].copy_from_slice(&length);
+            if reader
+                .read_exact(&mut request[REQUEST_INITIAL_BYTES_LENGTH..])
+                .await
+                .is_err()
+            {
+                break;
+            }
+
+            if connection_sender
+                .send(Message::Binary(request.into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        let _ = connection_sender.send(Message::Close(None)).await;
+    };
+    let responses = async {
+        while let Some(Ok(message)) = connection_receiver.next().await {
+            match message {
+                Message::Binary(response) if writer.write_all(&response).await.is_err() => break,
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = requests => {}
+        _ = responses => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ok_response_should_return_payload() {
+        let mut response = BytesMut::new();
+        response.put_u32_le(0);
+        response.put_u32_le(3);
+        response.put_slice(b"abc");
+        let payload = handle_response(response.freeze()).unwrap();
+        assert_eq!(payload.as_ref(), b"abc");
+    }
+
+    #[test]
+    fn error_response_should_be_mapped_to_error() {
+        let mut response = BytesMut::new();
+        response.put_u32_le(IggyError::Unauthenticated.as_code());
+        response.put_u32_le(0);
+        let error = handle_response(response.freeze()).unwrap_err();
+        assert_eq!(error.as_code(), IggyError::Unauthenticated.as_code());
+    }
+
+    #[test]
+    fn truncated_response_should_fail() {
+        let mut response = BytesMut::new();
+        response.put_u32_le(0);
+        response.put_u32_le(10);
+        response.put_slice(b"abc");
+        assert!(handle_response(response.freeze()).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::client::AutoLogin;
+use crate::utils::duration::IggyDuration;
+use std::str::FromStr;
+
+/// Configuration for the WebSocket client.
+#[derive(Debug, Clone)]
+pub struct WebSocketClientConfig {
+    /// The URL of the WebSocket endpoint of the Iggy server, `wss://` scheme uses TLS.
+    pub server_address: String,
+    /// Whether to automatically login user after establishing connection.
+    pub auto_login: AutoLogin,
+    /// Interval of heartbeats sent by the client
+    pub heartbeat_interval: IggyDuration,
+}
+
+impl Default for WebSocketClientConfig {
+    fn default() -> WebSocketClientConfig {
+        WebSocketClientConfig {
+            server_address: "ws://127.0.0.1:3000/ws".to_string(),
+            auto_login: AutoLogin::Disabled,
+            heartbeat_interval: IggyDuration::from_str("5s").unwrap(),
+        }
+    }
+}
+
+/// Builder for the WebSocket client configuration.
+/// Allows configuring the WebSocket client with custom settings or using defaults:
+/// - `server_address`: Default is "ws://127.0.0.1:3000/ws"
+/// - `auto_login`: Default is AutoLogin::Disabled.
+/// - `heartbeat_interval`: Default is 5 seconds.
+#[derive(Debug, Default)]
+pub struct WebSocketClientConfigBuilder {
+    config: WebSocketClientConfig,
+}
+
+impl WebSocketClientConfigBuilder {
+    pub fn new() -> Self {
+        WebSocketClientConfigBuilder::default()
+    }
+
+    /// Sets the server address for the WebSocket client.
+    pub fn with_server_address(mut self, server_address: String) -> Self {
+        self.config.server_address = server_address;
+        self
+    }
+
+    /// Sets the auto sign in during connection.
+    pub fn with_auto_sign_in(mut self, auto_sign_in: AutoLogin) -> Self {
+        self.config.auto_login = auto_sign_in;
+        self
+    }
+
+    /// Sets the interval of heartbeats sent by the client.
+    pub fn with_heartbeat_interval(mut self, heartbeat_interval: IggyDuration) -> Self {
+        self.config.heartbeat_interval = heartbeat_interval;
+        self
+    }
+
+    /// Builds the WebSocket client configuration.
+    pub fn build(self) -> WebSocketClientConfig {
+        self.config
+    }
+}
@@ -20,7 +20,7 @@ use crate::archiver::{Archiver, COMPONENT};
 use crate::configs::server::DiskArchiverConfig;
 use crate::server_error::ArchiverError;
 use error_set::ErrContext;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::{debug, info};
 
@@ -33,6 +33,13 @@ impl DiskArchiver {
     pub fn new(config: DiskArchiverConfig) -> Self {
         DiskArchiver { config }
     }
+
+    /// The absolute file path is stored relative to the archive directory, as joining it would replace the whole path.
+    fn get_archived_path(&self, file: &str, base_directory: Option<&str>) -> PathBuf {
+        Path::new(&self.config.path)
+            .join(base_directory.unwrap_or_default())
+            .join(file.trim_start_matches('/'))
+    }
 }
 
 impl Archiver for DiskArchiver {
@@ -57,8 +64,7 @@ impl Archiver for DiskArchiver {
         base_directory: Option<String>,
     ) -> Result<bool, ArchiverError> {
         debug!("Checking if file: {file} is archived on disk.");
-        let base_directory = base_directory.as_deref().unwrap_or_default();
-        let path = Path::new(&self.config.path).join(base_directory).join(file);
+        let path = self.get_archived_path(file, base_directory.as_deref());
         let is_archived = path.exists();
         debug!("File: {file} is archived: {is_archived}");
         Ok(is_archived)
@@ -79,8 +85,7 @@ impl Archiver for DiskArchiver {
                 });
             }
 
-            let base_directory = base_directory.as_deref().unwrap_or_default();
-            let destination = Path::new(&self.config.path).join(base_directory).join(file);
+            let destination = self.get_archived_path(file, base_directory.as_deref());
             let destination_path = destination.to_str().unwrap_or_default().to_owned();
             fs::create_dir_all(destination.parent().unwrap())
                 .await
@@ -95,4 +100,64 @@ impl Archiver for DiskArchiver {
 
         Ok(())
     }
+
+    async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Fetching file: {file} from disk to: {destination}");
+        let source = self.get_archived_path(file, base_directory.as_deref());
+        if !source.exists() {
+            return Err(ArchiverError::ArchivedFileNotFound {
+                file_path: file.to_string(),
+            });
+        }
+
+        let destination_path = Path::new(destination);
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create directory for fetched file: {destination}")
+            })?;
+        }
+        fs::copy(source, destination_path).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to copy archived file: {file} to destination: {destination}")
+        })?;
+        debug!("Fetched file: {file} to: {destination}");
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        directory: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        debug!("Listing archived files in directory: {directory} on disk.");
+        let path = self.get_archived_path(directory, base_directory.as_deref());
+        if !path.exists() {
+            debug!("Directory: {directory} is not archived on disk.");
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&path).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read archived directory: {directory}")
+        })?;
+        while let Some(entry) = entries.next_entry().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read entry of archived directory: {directory}")
+        })? {
+            if !entry.path().is_file() {
+                continue;
+            }
+
+            let name = entry.file_name();
+            files.push(format!("{directory}/{}", name.to_string_lossy()));
+        }
+        debug!(
+            "Found {} archived files in directory: {directory}",
+            files.len()
+        );
+        Ok(files)
+    }
 }
@@ -65,6 +65,17 @@ pub trait Archiver: Send {
         files: &[&str],
         base_directory: Option<String>,
     ) -> impl Future<Output = Result<(), ArchiverError>> + Send;
+    fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> impl Future<Output = Result<(), ArchiverError>> + Send;
+    fn list(
+        &self,
+        directory: &str,
+        base_directory: Option<String>,
+    ) -> impl Future<Output = Result<Vec<String>, ArchiverError>> + Send;
 }
 
 #[derive(Debug)]
@@ -111,4 +122,27 @@ impl ArchiverKind {
             Self::S3(d) => d.archive(files, base_directory).await,
         }
     }
+
+    pub async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        match self {
+            Self::Disk(d) => d.fetch(file, destination, base_directory).await,
+            Self::S3(d) => d.fetch(file, destination, base_directory).await,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        directory: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        match self {
+            Self::Disk(d) => d.list(directory, base_directory).await,
+            Self::S3(d) => d.list(directory, base_directory).await,
+        }
+    }
 }
@@ -194,4 +194,102 @@ impl Archiver for S3Archiver {
         }
         Ok(())
     }
+
+    async fn fetch(
+        &self,
+        file: &str,
+        destination: &str,
+        base_directory: Option<String>,
+    ) -> Result<(), ArchiverError> {
+        debug!("Fetching file: {file} from S3 to: {destination}");
+        let base_directory = base_directory.as_deref().unwrap_or_default();
+        let source = Path::new(&base_directory).join(file);
+        let source_path = source.to_str().unwrap_or_default().to_owned();
+        let destination_path = Path::new(destination);
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create directory for fetched file: {destination}")
+            })?;
+        }
+
+        let mut output = fs::File::create(destination_path).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to create file: {destination} for fetching")
+        })?;
+        let response = self
+            .bucket
+            .get_object_to_writer(source_path, &mut output)
+            .await;
+        let error = match response {
+            Ok(200) => {
+                output.sync_all().await.with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to sync fetched file: {destination}"
+                    )
+                })?;
+                debug!("Fetched file: {file} from S3 to: {destination}");
+                return Ok(());
+            }
+            Ok(404) => {
+                error!("Cannot fetch file: {file} from S3, file was not found.");
+                ArchiverError::ArchivedFileNotFound {
+                    file_path: file.to_string(),
+                }
+            }
+            Ok(status) => {
+                error!(
+                    "Cannot fetch file: {file} from S3, received an invalid status code: {status}."
+                );
+                ArchiverError::CannotFetchArchivedFile {
+                    file_path: file.to_string(),
+                }
+            }
+            Err(error) => {
+                error!("Cannot fetch file: {file} from S3: {error}");
+                ArchiverError::CannotFetchArchivedFile {
+                    file_path: file.to_string(),
+                }
+            }
+        };
+        drop(output);
+        fs::remove_file(destination_path).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to remove file: {destination} after S3 failure")
+        })?;
+        Err(error)
+    }
+
+    async fn list(
+        &self,
+        directory: &str,
+        base_directory: Option<String>,
+    ) -> Result<Vec<String>, ArchiverError> {
+        debug!("Listing archived files in directory: {directory} on S3.");
+        let base_directory = base_directory.as_deref().unwrap_or_default();
+        let prefix = Path::new(&base_directory).join(directory);
+        let prefix = format!("{}/", prefix.to_str().unwrap_or_default());
+        let response = self.bucket.list(prefix.clone(), None).await;
+        if let Err(error) = response {
+            error!("Cannot list archived files in directory: {directory} on S3: {error}");
+            return Err(ArchiverError::CannotListArchivedFiles {
+                directory: directory.to_string(),
+            });
+        }
+
+        let files = response
+            .unwrap()
+            .into_iter()
+            .flat_map(|result| result.contents)
+            .filter_map(|object| {
+                let name = object.key.strip_prefix(&prefix)?;
+                if name.is_empty() || name.contains('/') {
+                    return None;
+                }
+                Some(format!("{directory}/{name}"))
+            })
+            .collect::<Vec<_>>();
+        debug!(
+            "Found {} archived files in directory: {directory} on S3.",
+            files.len()
+        );
+        Ok(files)
+    }
 }
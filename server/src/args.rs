@@ -30,4 +30,17 @@ pub struct Args {
         help = "Remove system path (local_data by default) before starting. THIS WILL REMOVE ALL SAVED DATA!"
     )]
     pub fresh: bool,
+
+    #[arg(
+        long,
+        help = "Restore the online backup from the given directory (containing its manifest) before starting. THIS WILL REPLACE THE CURRENT STATE AND STREAMS!"
+    )]
+    pub restore_backup: Option<String>,
+
+    #[arg(
+        long,
+        requires = "restore_backup",
+        help = "Truncate the state log of the restored backup to the given index, to restore the server metadata to the chosen point in time."
+    )]
+    pub restore_state_index: Option<u64>,
 }
@@ -0,0 +1,76 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::binary::sender::Sender;
+use crate::server_error::ServerError;
+use crate::streaming::clients::client_manager::Transport;
+use bytes::Bytes;
+use iggy::error::IggyError;
+
+/// Sender of the request handled concurrently with the others sent over the same multiplexed connection.
+/// It keeps the response, which is written to the connection along with the request ID once the request completes.
+#[derive(Debug)]
+pub struct BufferedSender {
+    pub(crate) transport: Transport,
+    pub(crate) response: Option<BufferedResponse>,
+}
+
+#[derive(Debug)]
+pub struct BufferedResponse {
+    pub status: u32,
+    pub payload: Bytes,
+}
+
+impl BufferedSender {
+    /// Takes the kept response, the request without any is completed with the empty OK response.
+    pub fn take_response(&mut self) -> BufferedResponse {
+        self.response.take().unwrap_or(BufferedResponse {
+            status: 0,
+            payload: Bytes::new(),
+        })
+    }
+}
+
+impl Sender for BufferedSender {
+    async fn read(&mut self, _buffer: &mut [u8]) -> Result<usize, IggyError> {
+        Err(IggyError::FeatureUnavailable)
+    }
+
+    async fn send_empty_ok_response(&mut self) -> Result<(), IggyError> {
+        self.send_ok_response(&[]).await
+    }
+
+    async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
+        self.response = Some(BufferedResponse {
+            status: 0,
+            payload: Bytes::copy_from_slice(payload),
+        });
+        Ok(())
+    }
+
+    async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
+        self.response = Some(BufferedResponse {
+            status: error.as_code(),
+            payload: error.details_to_bytes(),
+        });
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), ServerError> {
+        Ok(())
+    }
+}
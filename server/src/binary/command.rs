@@ -16,6 +16,9 @@
  * under the License.
  */
 
+use crate::binary::handlers::cluster::{
+    append_entries_handler, get_cluster_metadata_handler, request_vote_handler,
+};
 use crate::binary::handlers::consumer_groups::{
     create_consumer_group_handler, delete_consumer_group_handler, get_consumer_group_handler,
     get_consumer_groups_handler, join_consumer_group_handler, leave_consumer_group_handler,
@@ -27,13 +30,18 @@ use crate::binary::handlers::personal_access_tokens::{
     create_personal_access_token_handler, delete_personal_access_token_handler,
     get_personal_access_tokens_handler, login_with_personal_access_token_handler,
 };
+use crate::binary::handlers::replication::fetch_replica_messages_handler;
+use crate::binary::handlers::segments::{delete_segments_handler, restore_segments_handler};
 use crate::binary::handlers::streams::*;
 use crate::binary::handlers::system::*;
 use crate::binary::handlers::topics::*;
+use crate::binary::handlers::transactions::{
+    abort_transaction_handler, begin_transaction_handler, commit_transaction_handler,
+};
 use crate::binary::handlers::users::{
     change_password_handler, create_user_handler, delete_user_handler, get_user_handler,
-    get_users_handler, login_user_handler, logout_user_handler, update_permissions_handler,
-    update_user_handler,
+    get_users_handler, login_user_handler, login_with_oidc_token_handler, logout_user_handler,
+    update_permissions_handler, update_user_handler,
 };
 use crate::binary::sender::SenderKind;
 use crate::binary::COMPONENT;
@@ -42,6 +50,7 @@ use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use error_set::ErrContext;
 use iggy::error::IggyError;
+use std::time::Instant;
 use tracing::{debug, error};
 
 pub async fn handle(
@@ -50,7 +59,15 @@ pub async fn handle(
     session: &Session,
     system: SharedSystem,
 ) -> Result<(), IggyError> {
-    match try_handle(command, sender, session, &system).await {
+    let command_name = command.name();
+    let handled_at = Instant::now();
+    let result = try_handle(command, sender, session, &system).await;
+    system.read().await.metrics.record_command_latency(
+        command_name,
+        &sender.transport().to_string(),
+        handled_at.elapsed(),
+    );
+    match result {
         Ok(_) => {
             debug!("Command was handled successfully, session: {session}. TCP response was sent.");
             Ok(())
@@ -88,10 +105,25 @@ async fn try_handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("Handling command '{command}', session: {session}...");
+    // The heartbeats are not throttled, so the throttled clients are not considered as stale ones.
+    if !matches!(command, ServerCommand::Ping(_)) {
+        system
+            .read()
+            .await
+            .throttler
+            .throttle_request(session.get_user_id(), session.client_id)?;
+    }
+    if command.changes_metadata() {
+        system.read().await.ensure_cluster_leader().await?;
+    }
+
     match command {
         ServerCommand::Ping(command) => {
             ping_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::Handshake(command) => {
+            handshake_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetStats(command) => {
             get_stats_handler::handle(command, sender, session, system).await
         }
@@ -128,6 +160,9 @@ async fn try_handle(
         ServerCommand::LoginUser(command) => {
             login_user_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::LoginWithOidcToken(command) => {
+            login_with_oidc_token_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::LogoutUser(command) => {
             logout_user_handler::handle(command, sender, session, system).await
         }
@@ -158,6 +193,9 @@ async fn try_handle(
         ServerCommand::DeleteConsumerOffset(command) => {
             delete_consumer_offset_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::GetConsumerLag(command) => {
+            get_consumer_lag_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetStream(command) => {
             get_stream_handler::handle(command, sender, session, system).await
         }
@@ -200,6 +238,12 @@ async fn try_handle(
         ServerCommand::DeletePartitions(command) => {
             delete_partitions_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::DeleteSegments(command) => {
+            delete_segments_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::RestoreSegments(command) => {
+            restore_segments_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetConsumerGroup(command) => {
             get_consumer_group_handler::handle(command, sender, session, system).await
         }
@@ -221,8 +265,41 @@ async fn try_handle(
         ServerCommand::FlushUnsavedBuffer(command) => {
             flush_unsaved_buffer_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::NackMessage(command) => {
+            nack_message_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::InitProducer(command) => {
+            init_producer_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::PollAssignedMessages(command) => {
+            poll_assigned_messages_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::SubscribeMessages(command) => {
+            subscribe_messages_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetSnapshotFile(command) => {
             get_snapshot::handle(command, sender, session, system).await
         }
+        ServerCommand::FetchReplicaMessages(command) => {
+            fetch_replica_messages_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::GetClusterMetadata(command) => {
+            get_cluster_metadata_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::RequestVote(command) => {
+            request_vote_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::AppendEntries(command) => {
+            append_entries_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::BeginTransaction(command) => {
+            begin_transaction_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::CommitTransaction(command) => {
+            commit_transaction_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::AbortTransaction(command) => {
+            abort_transaction_handler::handle(command, sender, session, system).await
+        }
     }
 }
@@ -0,0 +1,78 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::compression::wire_compression::{decompress_frame, WireCompression};
+use iggy::error::IggyError;
+
+const CODE_BYTES_LENGTH: usize = 4;
+
+/// Decompresses the request `[code][payload]` if its code is marked with the `COMPRESSION_FLAG`,
+/// so it can be parsed as the regular command, otherwise the request is returned as is.
+pub(crate) fn decompress_request(
+    request: Bytes,
+    compression: Option<&WireCompression>,
+) -> Result<Bytes, IggyError> {
+    let Some(code) = request.get(..CODE_BYTES_LENGTH) else {
+        return Ok(request);
+    };
+
+    let code = u32::from_le_bytes(code.try_into().map_err(|_| IggyError::InvalidCommand)?);
+    let (decompressed_code, payload) =
+        decompress_frame(compression, code, request.slice(CODE_BYTES_LENGTH..))?;
+    if decompressed_code == code {
+        return Ok(request);
+    }
+
+    let mut decompressed = BytesMut::with_capacity(CODE_BYTES_LENGTH + payload.len());
+    decompressed.put_u32_le(decompressed_code);
+    decompressed.put_slice(&payload);
+    Ok(decompressed.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::compression::wire_compression::{compress_frame, WireCompressionAlgorithm};
+    use iggy::utils::byte_size::IggyByteSize;
+
+    fn request(code: u32, payload: &[u8]) -> Bytes {
+        let mut request = BytesMut::new();
+        request.put_u32_le(code);
+        request.put_slice(payload);
+        request.freeze()
+    }
+
+    #[test]
+    fn compressed_request_should_be_decompressed() {
+        let compression =
+            WireCompression::new(WireCompressionAlgorithm::Lz4, IggyByteSize::from(100));
+        let payload = Bytes::from("payload".repeat(100));
+        let (code, compressed) = compress_frame(Some(&compression), 101, payload.clone());
+
+        let decompressed =
+            decompress_request(request(code, &compressed), Some(&compression)).unwrap();
+        assert_eq!(decompressed, request(101, &payload));
+    }
+
+    #[test]
+    fn uncompressed_request_should_be_returned_as_is() {
+        let request = request(101, b"payload");
+        assert_eq!(decompress_request(request.clone(), None).unwrap(), request);
+    }
+}
@@ -0,0 +1,51 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::handlers::cluster::COMPONENT;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::cluster::request_vote::RequestVote;
+use iggy::error::IggyError;
+use tracing::{debug, instrument};
+
+#[instrument(skip_all, name = "trace_request_vote", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id, iggy_term = command.term, iggy_candidate_id = command.candidate_id))]
+pub async fn handle(
+    command: RequestVote,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let state = system.read().await.get_cluster_state(session)?;
+    let raft = state.as_raft().ok_or(IggyError::FeatureUnavailable)?;
+    let term = command.term;
+    let candidate_id = command.candidate_id;
+    let response = raft
+        .handle_request_vote(command)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to handle vote request from candidate with ID: {candidate_id}, term: {term}, session: {session}"
+            )
+        })?;
+    sender.send_ok_response(&response.to_bytes()).await?;
+    Ok(())
+}
@@ -34,37 +34,23 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.delete_consumer_group(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                &command.group_id,
-            )
-            .await.with_error_context(|error| format!(
-                "{COMPONENT} (error: {error}) - failed to delete consumer group with ID: {} for topic with ID: {} in stream with ID: {} for session: {}",
-                command.group_id, command.topic_id, command.stream_id, session
-            ))?;
-
-    let system = system.downgrade();
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
     let group_id = command.group_id.clone();
-
     system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::DeleteConsumerGroup(command),
-        )
+        .apply_entry(session, |system| {
+            system.validate_delete_consumer_group(
+                session,
+                &command.stream_id,
+                &command.topic_id,
+                &command.group_id,
+            )?;
+            Ok((EntryCommand::DeleteConsumerGroup(command), ()))
+        })
         .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete consumer group for stream ID: {}, topic ID: {}, group_id: {:?}, session: {}",
-                stream_id, topic_id, group_id, session
-            )
-        })?;
+        .with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - failed to delete consumer group with ID: {group_id} for topic with ID: {topic_id} in stream with ID: {stream_id} for session: {session}",
+        ))?;
     sender.send_empty_ok_response().await?;
     Ok(())
 }
@@ -17,6 +17,7 @@
  */
 
 pub mod delete_consumer_offset_handler;
+pub mod get_consumer_lag_handler;
 pub mod get_consumer_offset_handler;
 pub mod store_consumer_offset_handler;
 
@@ -41,6 +41,7 @@ pub async fn handle(
             &command.topic_id,
             command.partition_id,
             command.offset,
+            command.generation_id,
         )
         .await
         .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset for stream ID: {}, topic ID: {}, partition_id: {:?}, offset: {}, session: {}",
@@ -35,22 +35,28 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let system = system.read().await;
     let messages = system
-        .poll_messages(
+        .poll_messages_with_wait(
             session,
             &command.consumer,
             &command.stream_id,
             &command.topic_id,
             command.partition_id,
-            PollingArgs::new(command.strategy, command.count, command.auto_commit),
+            PollingArgs::new(
+                command.strategy,
+                command.count,
+                command.auto_commit,
+                command.isolation_level,
+                command.filter,
+            ),
+            &command.wait,
         )
         .await
         .with_error_context(|error| format!(
             "{COMPONENT} (error: {error}) - failed to poll messages for consumer: {}, stream ID: {}, topic ID: {}, partition_id: {:?}, session: {}.",
             command.consumer, command.stream_id, command.topic_id, command.partition_id, session
         ))?;
-    let messages = mapper::map_polled_messages(&messages);
+    let messages = mapper::map_polled_messages(&messages, session.get_features());
     sender.send_ok_response(&messages).await?;
     Ok(())
 }
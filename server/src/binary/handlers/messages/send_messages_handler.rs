@@ -34,10 +34,10 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let system = system.read().await;
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
     let partitioning = command.partitioning.clone();
+    let producer_sequence = command.producer_sequence;
     let mut messages = command.messages;
     messages.iter_mut().for_each(|msg| {
         if msg.id == 0 {
@@ -45,8 +45,18 @@ pub async fn handle(
         }
     });
     // TODO(haze): Add confirmation level after testing is complete
-    system
-        .append_messages(session, stream_id, topic_id, partitioning, messages, None)
+    let acknowledgement = system
+        .read()
+        .await
+        .append_messages(
+            session,
+            stream_id,
+            topic_id,
+            partitioning,
+            producer_sequence,
+            messages,
+            None,
+        )
         .await
         .with_error_context(|error| {
             format!(
@@ -54,6 +64,15 @@ pub async fn handle(
                 command.stream_id, command.topic_id, command.partitioning, session
             )
         })?;
+    // The system lock is already released, so waiting for the replicas doesn't block the other requests.
+    if let Some(acknowledgement) = acknowledgement {
+        acknowledgement.wait().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to replicate messages for stream ID: {}, topic ID: {}, session: {}",
+                command.stream_id, command.topic_id, session
+            )
+        })?;
+    }
     sender.send_empty_ok_response().await?;
     Ok(())
 }
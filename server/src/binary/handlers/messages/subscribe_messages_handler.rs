@@ -0,0 +1,296 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::handlers::messages::COMPONENT;
+use crate::binary::mapper;
+use crate::binary::sender::SenderKind;
+use crate::streaming::session::Session;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use bytes::Bytes;
+use error_set::ErrContext;
+use futures::StreamExt;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::command::{ACK_MESSAGES_CODE, GRANT_CREDIT_CODE};
+use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
+use iggy::messages::ack_messages::AckMessages;
+use iggy::messages::grant_credit::GrantCredit;
+use iggy::messages::poll_messages::{PollingStrategy, PollingWait};
+use iggy::messages::subscribe_messages::{AckMode, CreditKind, SubscribeMessages};
+use iggy::messages::MAX_PAYLOAD_SIZE;
+use iggy::models::messages::PolledMessages;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::sizeable::Sizeable;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, trace};
+
+const STATUS_OK: &[u8] = &[0; 4];
+const REQUEST_INITIAL_BYTES_LENGTH: usize = 4;
+// Each poll of the subscription waits at most this long, so the partitions of the consumer group member are rotated.
+const SUBSCRIPTION_WAIT: IggyDuration = IggyDuration::ONE_SECOND;
+
+pub async fn handle(
+    command: SubscribeMessages,
+    sender: &mut SenderKind,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    // The access is verified upfront, so the failed subscription is rejected with the regular error response.
+    system
+        .read()
+        .await
+        .resolve_partition_to_wait_on(
+            session,
+            &command.consumer,
+            &command.stream_id,
+            &command.topic_id,
+            command.partition_id,
+        )
+        .await
+        .with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - failed to subscribe to messages for consumer: {}, stream ID: {}, topic ID: {}, partition_id: {:?}, session: {}.",
+            command.consumer, command.stream_id, command.topic_id, command.partition_id, session
+        ))?;
+
+    let (reader, mut writer) = sender.split();
+    write_frame(&mut writer, &[]).await?;
+    let requests = futures::stream::unfold(reader, |mut reader| async move {
+        let request = read_request(&mut reader).await;
+        Some((request, reader))
+    });
+    tokio::pin!(requests);
+
+    let mut credit = command.credit;
+    // The offsets to continue from are tracked for each partition, as the consumer group member may switch between them.
+    let mut next_offsets = HashMap::new();
+    loop {
+        tokio::select! {
+            request = requests.next() => {
+                let Some((code, payload)) = request.transpose()?.flatten() else {
+                    debug!("Subscription was closed by the client, session: {session}.");
+                    return Ok(());
+                };
+
+                match code {
+                    GRANT_CREDIT_CODE => {
+                        let grant = GrantCredit::from_bytes(payload)?;
+                        trace!("Granted credit: {} for subscription, session: {session}.", grant.credit);
+                        credit = credit.saturating_add(grant.credit);
+                    }
+                    ACK_MESSAGES_CODE => {
+                        if command.ack_mode != AckMode::Acknowledged {
+                            return Err(IggyError::InvalidCommand);
+                        }
+
+                        let ack = AckMessages::from_bytes(payload)?;
+                        store_offset(&command, ack.partition_id, ack.offset, session, system).await?;
+                    }
+                    _ => return Err(IggyError::InvalidCommand),
+                }
+            }
+            polled = poll_batch(&command, credit, &next_offsets, session, system), if credit > 0 => {
+                let (polled_messages, last_offset) = polled?;
+                // The offset is moved past the scanned messages, even if all of them were skipped by the filter.
+                if let Some(offset) = last_offset {
+                    next_offsets.insert(polled_messages.partition_id, offset + 1);
+                }
+
+                if polled_messages.messages.is_empty() {
+                    continue;
+                }
+
+                write_frame(&mut writer, &mapper::map_polled_messages(&polled_messages, session.get_features())).await?;
+                credit = credit.saturating_sub(consumed_credit(command.credit_kind, &polled_messages));
+                if command.ack_mode == AckMode::Delivered {
+                    if let Some(message) = polled_messages.messages.last() {
+                        store_offset(&command, polled_messages.partition_id, message.offset, session, system).await?;
+                    }
+                }
+            }
+        }
+
+        refresh_heartbeat(session, system).await;
+    }
+}
+
+async fn poll_batch(
+    command: &SubscribeMessages,
+    credit: u64,
+    next_offsets: &HashMap<u32, u64>,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(PolledMessages, Option<u64>), IggyError> {
+    let resolved_partition = system
+        .read()
+        .await
+        .resolve_partition_to_wait_on(
+            session,
+            &command.consumer,
+            &command.stream_id,
+            &command.topic_id,
+            command.partition_id,
+        )
+        .await?;
+    let Some((partition_id, _)) = resolved_partition else {
+        // The consumer group member has no partitions assigned at the moment.
+        tokio::time::sleep(SUBSCRIPTION_WAIT.get_duration()).await;
+        return Ok(empty_batch());
+    };
+
+    let strategy = next_offsets
+        .get(&partition_id)
+        .map_or(command.strategy, |offset| PollingStrategy::offset(*offset));
+    let count = match command.credit_kind {
+        CreditKind::Messages => command.count.min(credit.min(u32::MAX as u64) as u32),
+        CreditKind::Bytes => command.count,
+    };
+    let polled = system
+        .poll_messages_with_wait_and_last_offset(
+            session,
+            &command.consumer,
+            &command.stream_id,
+            &command.topic_id,
+            Some(partition_id),
+            PollingArgs::new(
+                strategy,
+                count,
+                false,
+                command.isolation_level,
+                command.filter.clone(),
+            ),
+            &PollingWait::messages(SUBSCRIPTION_WAIT, 1),
+        )
+        .await;
+    // The subscription is slowed down rather than closed, when the consume quota of the user is exceeded.
+    if let Err(IggyError::QuotaExceeded(retry_after)) = polled {
+        tokio::time::sleep(Duration::from_millis(retry_after)).await;
+        return Ok(empty_batch());
+    }
+
+    polled
+}
+
+fn empty_batch() -> (PolledMessages, Option<u64>) {
+    (
+        PolledMessages {
+            partition_id: 0,
+            current_offset: 0,
+            generation_id: 0,
+            messages: Vec::new(),
+        },
+        None,
+    )
+}
+
+async fn store_offset(
+    command: &SubscribeMessages,
+    partition_id: u32,
+    offset: u64,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    system
+        .read()
+        .await
+        .store_consumer_offset(
+            session,
+            command.consumer.clone(),
+            &command.stream_id,
+            &command.topic_id,
+            Some(partition_id),
+            offset,
+            None,
+        )
+        .await
+        .with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - failed to store offset: {offset} of the subscription for consumer: {}, stream ID: {}, topic ID: {}, partition_id: {partition_id}, session: {}.",
+            command.consumer, command.stream_id, command.topic_id, session
+        ))
+}
+
+fn consumed_credit(credit_kind: CreditKind, polled_messages: &PolledMessages) -> u64 {
+    match credit_kind {
+        CreditKind::Messages => polled_messages.messages.len() as u64,
+        CreditKind::Bytes => polled_messages
+            .messages
+            .iter()
+            .map(|message| message.get_size_bytes().as_bytes_u64())
+            .sum(),
+    }
+}
+
+// The client is alive as long as its subscription is, even if it doesn't send the heartbeats over the dedicated connection.
+async fn refresh_heartbeat(session: &Session, system: &SharedSystem) {
+    let system = system.read().await;
+    let client_manager = system.client_manager.read().await;
+    if let Some(client) = client_manager.try_get_client(session.client_id) {
+        client.write().await.last_heartbeat = IggyTimestamp::now();
+    }
+}
+
+async fn read_request<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<(u32, Bytes)>, IggyError> {
+    let mut length = [0u8; REQUEST_INITIAL_BYTES_LENGTH];
+    if let Err(error) = reader.read_exact(&mut length).await {
+        return match error.kind() {
+            ErrorKind::UnexpectedEof => Ok(None),
+            _ => Err(IggyError::ConnectionClosed),
+        };
+    }
+
+    let length = u32::from_le_bytes(length);
+    if length < REQUEST_INITIAL_BYTES_LENGTH as u32 || length > MAX_PAYLOAD_SIZE {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let mut request = vec![0u8; length as usize];
+    reader
+        .read_exact(&mut request)
+        .await
+        .map_err(|_| IggyError::ConnectionClosed)?;
+    let request = Bytes::from(request);
+    let code = u32::from_le_bytes(
+        request[..REQUEST_INITIAL_BYTES_LENGTH]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    );
+    Ok(Some((code, request.slice(REQUEST_INITIAL_BYTES_LENGTH..))))
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    payload: &[u8],
+) -> Result<(), IggyError> {
+    let length = (payload.len() as u32).to_le_bytes();
+    writer
+        .write_all(&[STATUS_OK, &length, payload].concat())
+        .await
+        .map_err(|_| IggyError::ConnectionClosed)?;
+    writer
+        .flush()
+        .await
+        .map_err(|_| IggyError::ConnectionClosed)
+}
@@ -34,37 +34,22 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.create_partitions(
+    let stream_id = command.stream_id.clone();
+    let topic_id = command.topic_id.clone();
+    system
+        .apply_entry(session, |system| {
+            system.validate_create_partitions(
                 session,
                 &command.stream_id,
                 &command.topic_id,
                 command.partitions_count,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create partitions for stream ID: {}, topic ID: {}, session: {}",
-                    command.stream_id, command.topic_id, session
-                )
-            })?;
-
-    let system = system.downgrade();
-    let stream_id = command.stream_id.clone();
-    let topic_id = command.topic_id.clone();
-
-    system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::CreatePartitions(command),
-        )
+            )?;
+            Ok((EntryCommand::CreatePartitions(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create partitions for stream ID: {}, topic ID: {}, session: {}",
-                stream_id, topic_id, session
+                "{COMPONENT} (error: {error}) - failed to create partitions for stream ID: {stream_id}, topic ID: {topic_id}, session: {session}"
             )
         })?;
     sender.send_empty_ok_response().await?;
@@ -36,33 +36,15 @@ pub async fn handle(
     debug!("session: {session}, command: {command}");
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
-
-    let mut system = system.write().await;
-    system.delete_partitions(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                command.partitions_count,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete partitions for topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
-                )
-            })?;
-
-    let system = system.downgrade();
     system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::DeletePartitions(command),
-        )
+        .apply_entry(session, |system| {
+            system.validate_delete_partitions(session, &command.stream_id, &command.topic_id)?;
+            Ok((EntryCommand::DeletePartitions(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete partitions for stream ID: {}, topic ID: {}, session: {}",
-                stream_id, topic_id, session
+                "{COMPONENT} (error: {error}) - failed to delete partitions for topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
             )
         })?;
     sender.send_empty_ok_response().await?;
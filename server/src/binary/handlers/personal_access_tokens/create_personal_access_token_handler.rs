@@ -27,6 +27,7 @@ use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
+use iggy::utils::timestamp::IggyTimestamp;
 use tracing::{debug, instrument};
 
 #[instrument(skip_all, name = "trace_create_personal_access_token", fields(iggy_user_id = session.get_user_id(), iggy_client_id = session.client_id))]
@@ -37,39 +38,31 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let system = system.read().await;
+    let name = command.name.clone();
     let token = system
-            .create_personal_access_token(session, &command.name, command.expiry)
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create personal access token with name: {}, session: {session}",
-                    command.name
-                )
-            })?;
-    let bytes = mapper::map_raw_pat(&token);
-    let token_hash = PersonalAccessToken::hash_token(&token);
-
-    system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash {
-                hash: token_hash,
-                command: CreatePersonalAccessToken {
-                    name: command.name.to_owned(),
-                    expiry: command.expiry,
-                }
-            }),
-        )
+        .apply_entry(session, |system| {
+            system.validate_create_personal_access_token(session, &command.name)?;
+            let (_, token) = PersonalAccessToken::new(
+                session.get_user_id(),
+                &command.name,
+                IggyTimestamp::now(),
+                command.expiry,
+            );
+            Ok((
+                EntryCommand::CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash {
+                    hash: PersonalAccessToken::hash_token(&token),
+                    command,
+                }),
+                token,
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to create personal access token with name: {}, session: {session}",
-                command.name
+                "{COMPONENT} (error: {error}) - failed to create personal access token with name: {name}, session: {session}"
             )
         })?;
+    let bytes = mapper::map_raw_pat(&token);
     sender.send_ok_response(&bytes).await?;
     Ok(())
 }
@@ -35,24 +35,14 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let token_name = command.name.clone();
-
-    let mut system = system.write().await;
-    system.delete_personal_access_token(session, &command.name)
-            .await
-            .with_error_context(|error| {format!(
-                "{COMPONENT} (error: {error}) - failed to delete personal access token with name: {token_name}, session: {session}"
-            )})?;
-
-    let system = system.downgrade();
     system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::DeletePersonalAccessToken(command),
-        )
+        .apply_entry(session, |system| {
+            system.validate_delete_personal_access_token(session, &command.name)?;
+            Ok((EntryCommand::DeletePersonalAccessToken(command), ()))
+        })
         .await
         .with_error_context(|error| {format!(
-            "{COMPONENT} (error: {error}) - failed to apply delete personal access token with name: {token_name}, session: {session}"
+            "{COMPONENT} (error: {error}) - failed to delete personal access token with name: {token_name}, session: {session}"
         )})?;
     sender.send_empty_ok_response().await?;
     Ok(())
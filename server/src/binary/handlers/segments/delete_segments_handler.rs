@@ -36,34 +36,20 @@ pub async fn handle(
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
     let partition_id = command.partition_id;
-
-    let mut system = system.write().await;
     system
-            .delete_segments(
+        .apply_entry(session, |system| {
+            system.validate_delete_segments(
                 session,
                 &command.stream_id,
                 &command.topic_id,
                 command.partition_id,
-                command.segments_count,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete segments for topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
-                )
-            })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::DeleteSegments(command),
-        )
+            )?;
+            Ok((EntryCommand::DeleteSegments(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply 'delete segments' command for partition with ID: {partition_id} in topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
+                "{COMPONENT} (error: {error}) - failed to delete segments for partition with ID: {partition_id} in topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
             )
         })?;
     sender.send_empty_ok_response().await?;
@@ -25,6 +25,7 @@ use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
+use iggy::identifier::Identifier;
 use iggy::streams::create_stream::CreateStream;
 use tracing::{debug, instrument};
 
@@ -36,34 +37,26 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let stream_id = command.stream_id;
-
-    let mut system = system.write().await;
-    let stream = system
-            .create_stream(session, command.stream_id, &command.name)
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create stream with ID: {:?}, name: {} session: {session}",
-                    stream_id, command.name
-                )
-            })?;
-    let stream_id = stream.stream_id;
-    let response = mapper::map_stream(stream);
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(session.get_user_id(), EntryCommand::CreateStream(CreateStreamWithId {
-            stream_id,
-            command
-        }))
+    let name = command.name.clone();
+    let stream_id = system
+        .apply_entry(session, |system| {
+            let stream_id =
+                system.validate_create_stream(session, command.stream_id, &command.name)?;
+            Ok((
+                EntryCommand::CreateStream(CreateStreamWithId { stream_id, command }),
+                stream_id,
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create stream with ID: {stream_id}, session: {session}",
+                "{COMPONENT} (error: {error}) - failed to create stream with name: {name}, session: {session}"
             )
         })?;
+
+    let system = system.read().await;
+    let stream = system.get_stream(&Identifier::numeric(stream_id)?)?;
+    let response = mapper::map_stream(stream);
     sender.send_ok_response(&response).await?;
     Ok(())
 }
@@ -34,23 +34,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let system = system.read().await;
     let stream_id = command.stream_id.clone();
-
     system
-        .purge_stream(session, &command.stream_id)
+        .apply_entry(session, |system| {
+            system.validate_purge_stream(session, &command.stream_id)?;
+            Ok((EntryCommand::PurgeStream(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to purge stream with id: {stream_id}, session: {session}")
         })?;
-
-    system
-        .state
-        .apply(session.get_user_id(), EntryCommand::PurgeStream(command))
-        .await
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to apply purge stream with id: {stream_id}, session: {session}")
-        })?;
     sender.send_empty_ok_response().await?;
     Ok(())
 }
@@ -35,21 +35,14 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let stream_id = command.stream_id.clone();
-
-    let mut system = system.write().await;
-    system.update_stream(session, &command.stream_id, &command.name)
-            .await
-            .with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to update stream with id: {stream_id}, session: {session}")
-            })?;
-
-    let system = system.downgrade();
     system
-        .state
-        .apply(session.get_user_id(), EntryCommand::UpdateStream(command))
+        .apply_entry(session, |system| {
+            system.validate_update_stream(session, &command.stream_id, &command.name)?;
+            Ok((EntryCommand::UpdateStream(command), ()))
+        })
         .await
         .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to apply update stream with id: {stream_id}, session: {session}")
+            format!("{COMPONENT} (error: {error}) - failed to update stream with id: {stream_id}, session: {session}")
         })?;
     sender.send_empty_ok_response().await?;
     Ok(())
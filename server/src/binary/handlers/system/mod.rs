@@ -21,6 +21,7 @@ pub mod get_clients_handler;
 pub mod get_me_handler;
 pub mod get_snapshot;
 pub mod get_stats_handler;
+pub mod handshake_handler;
 pub mod ping_handler;
 
 pub const COMPONENT: &str = "SYSTEM_HANDLER";
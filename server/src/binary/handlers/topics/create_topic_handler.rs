@@ -22,9 +22,11 @@ use crate::state::command::EntryCommand;
 use crate::state::models::CreateTopicWithId;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use crate::streaming::topics::topic::Topic;
 use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
+use iggy::identifier::Identifier;
 use iggy::topics::create_topic::CreateTopic;
 use tracing::{debug, instrument};
 
@@ -37,43 +39,42 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let stream_id = command.stream_id.clone();
-    let topic_id = command.topic_id;
-    let mut system = system.write().await;
-    let topic = system
-            .create_topic(
+    let name = command.name.clone();
+    let topic_id = system
+        .apply_entry(session, |system| {
+            let topic_id = system.validate_create_topic(
                 session,
                 &command.stream_id,
                 command.topic_id,
                 &command.name,
                 command.partitions_count,
-                command.message_expiry,
-                command.compression_algorithm,
                 command.max_topic_size,
-                command.replication_factor,
-            )
-            .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create topic for stream ID: {stream_id}, topic_id: {:?}",
-                topic_id
-            ))?;
-    command.message_expiry = topic.message_expiry;
-    command.max_topic_size = topic.max_topic_size;
-    let topic_id = topic.topic_id;
-    let response = mapper::map_topic(topic).await;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(session.get_user_id(), EntryCommand::CreateTopic(CreateTopicWithId {
-            topic_id,
-            command
-        }))
+            )?;
+            command.message_expiry =
+                Topic::get_message_expiry(command.message_expiry, &system.config);
+            command.max_topic_size =
+                Topic::get_max_topic_size(command.max_topic_size, &system.config)?;
+            Ok((
+                EntryCommand::CreateTopic(CreateTopicWithId {
+                    topic_id,
+                    command,
+                    dead_letter: false,
+                }),
+                topic_id,
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-            "{COMPONENT} (error: {error}) - failed to apply create topic for stream ID: {stream_id}, topic_id: {:?}",
-            topic_id
-        )
+                "{COMPONENT} (error: {error}) - failed to create topic with name: {name} for stream ID: {stream_id}, session: {session}"
+            )
         })?;
+
+    let system = system.read().await;
+    let topic = system
+        .get_stream(&stream_id)?
+        .get_topic(&Identifier::numeric(topic_id)?)?;
+    let response = mapper::map_topic(topic).await;
     sender.send_ok_response(&response).await?;
     Ok(())
 }
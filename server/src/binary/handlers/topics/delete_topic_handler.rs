@@ -36,21 +36,14 @@ pub async fn handle(
     debug!("session: {session}, command: {command}");
     let stream_id = command.stream_id.clone();
     let topic_id = command.topic_id.clone();
-
-    let mut system = system.write().await;
-    system.delete_topic(session, &command.stream_id, &command.topic_id)
-            .await
-            .with_error_context(|error| format!(
-                "{COMPONENT} (error: {error}) - failed to delete topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
-            ))?;
-
-    let system = system.downgrade();
     system
-        .state
-        .apply(session.get_user_id(), EntryCommand::DeleteTopic(command))
+        .apply_entry(session, |system| {
+            system.validate_delete_topic(session, &command.stream_id, &command.topic_id)?;
+            Ok((EntryCommand::DeleteTopic(command), ()))
+        })
         .await
         .with_error_context(|error| format!(
-            "{COMPONENT} (error: {error}) - failed to apply delete topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
+            "{COMPONENT} (error: {error}) - failed to delete topic with ID: {topic_id} in stream with ID: {stream_id}, session: {session}",
         ))?;
     sender.send_empty_ok_response().await?;
     Ok(())
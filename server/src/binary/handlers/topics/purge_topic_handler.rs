@@ -34,27 +34,18 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let system = system.read().await;
-    system
-        .purge_topic(session, &command.stream_id, &command.topic_id)
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to purge topic with id: {}, stream ID: {}",
-                command.topic_id, command.stream_id
-            )
-        })?;
-
     let topic_id = command.topic_id.clone();
     let stream_id = command.stream_id.clone();
     system
-        .state
-        .apply(session.get_user_id(), EntryCommand::PurgeTopic(command))
+        .apply_entry(session, |system| {
+            system.validate_purge_topic(session, &command.stream_id, &command.topic_id)?;
+            Ok((EntryCommand::PurgeTopic(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-            "{COMPONENT} (error: {error}) - failed to apply purge topic with id: {topic_id}, stream_id: {stream_id}",
-        )
+                "{COMPONENT} (error: {error}) - failed to purge topic with id: {topic_id}, stream ID: {stream_id}"
+            )
         })?;
     sender.send_empty_ok_response().await?;
     Ok(())
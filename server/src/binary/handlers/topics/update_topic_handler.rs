@@ -20,6 +20,7 @@ use crate::binary::{handlers::topics::COMPONENT, sender::SenderKind};
 use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use crate::streaming::topics::topic::Topic;
 use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
@@ -34,39 +35,26 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-
-    let topic = system
-            .update_topic(
+    let topic_id = command.topic_id.clone();
+    let stream_id = command.stream_id.clone();
+    system
+        .apply_entry(session, |system| {
+            system.validate_update_topic(
                 session,
                 &command.stream_id,
                 &command.topic_id,
                 &command.name,
-                command.message_expiry,
-                command.compression_algorithm,
                 command.max_topic_size,
-                command.replication_factor,
-            )
-            .await
-            .with_error_context(|error| format!(
-                "{COMPONENT} (error: {error}) - failed to update topic with id: {}, stream ID: {}, session: {session}",
-                command.topic_id, command.stream_id
-            ))?;
-    command.message_expiry = topic.message_expiry;
-    command.max_topic_size = topic.max_topic_size;
-
-    let topic_id = command.topic_id.clone();
-    let stream_id = command.stream_id.clone();
-    let system = system.downgrade();
-
-    system
-        .state
-        .apply(session.get_user_id(), EntryCommand::UpdateTopic(command))
+            )?;
+            command.message_expiry =
+                Topic::get_message_expiry(command.message_expiry, &system.config);
+            command.max_topic_size =
+                Topic::get_max_topic_size(command.max_topic_size, &system.config)?;
+            Ok((EntryCommand::UpdateTopic(command), ()))
+        })
         .await
         .with_error_context(|error| format!(
-            "{COMPONENT} (error: {error}) - failed to apply update topic with id: {}, stream ID: {}, session: {session}",
-            topic_id, stream_id
+            "{COMPONENT} (error: {error}) - failed to update topic with id: {topic_id}, stream ID: {stream_id}, session: {session}"
         ))?;
     sender.send_empty_ok_response().await?;
     Ok(())
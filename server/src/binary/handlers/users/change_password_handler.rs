@@ -35,39 +35,29 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.change_password(
+    let user_id = command.user_id.clone();
+    // For the security of the system, we hash the password before storing it in metadata.
+    let new_password = crypto::hash_password(&command.new_password);
+    system
+        .apply_entry(session, |system| {
+            system.validate_change_password(
                 session,
                 &command.user_id,
                 &command.current_password,
-                &command.new_password,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to change password for user ID: {}, session: {session}",
-                    command.user_id
-                )
-            })?;
-
-    // For the security of the system, we hash the password before storing it in metadata.
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::ChangePassword(ChangePassword {
-                user_id: command.user_id.to_owned(),
-                current_password: "".into(),
-                new_password: crypto::hash_password(&command.new_password),
-            }),
-        )
+            )?;
+            Ok((
+                EntryCommand::ChangePassword(ChangePassword {
+                    user_id: command.user_id,
+                    current_password: "".into(),
+                    new_password,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply change password for user ID: {}, session: {session}",
-                command.user_id
+                "{COMPONENT} (error: {error}) - failed to change password for user ID: {user_id}, session: {session}"
             )
         })?;
     sender.send_empty_ok_response().await?;
@@ -26,6 +26,7 @@ use crate::streaming::utils::crypto;
 use anyhow::Result;
 use error_set::ErrContext;
 use iggy::error::IggyError;
+use iggy::identifier::Identifier;
 use iggy::users::create_user::CreateUser;
 use tracing::{debug, instrument};
 
@@ -37,48 +38,36 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    let user = system.create_user(
-                session,
-                &command.username,
-                &command.password,
-                command.status,
-                command.permissions.clone(),
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create user with name: {}, session: {session}",
-                    command.username
-                )
-            })?;
-    let user_id = user.id;
-    let response = mapper::map_user(user);
-
+    let username = command.username.clone();
     // For the security of the system, we hash the password before storing it in metadata.
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::CreateUser(CreateUserWithId {
+    let password = crypto::hash_password(&command.password);
+    let user_id = system
+        .apply_entry(session, |system| {
+            let user_id = system.validate_create_user(session, &command.username)?;
+            Ok((
+                EntryCommand::CreateUser(CreateUserWithId {
+                    user_id,
+                    command: CreateUser {
+                        username: command.username,
+                        password,
+                        status: command.status,
+                        permissions: command.permissions,
+                    },
+                    oidc_subject: None,
+                }),
                 user_id,
-                command: CreateUser {
-                    username: command.username.to_owned(),
-                    password: crypto::hash_password(&command.password),
-                    status: command.status,
-                    permissions: command.permissions.clone(),
-                }
-            }),
-        )
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create user with name: {}, session: {session}",
-                command.username
+                "{COMPONENT} (error: {error}) - failed to create user with name: {username}, session: {session}"
             )
         })?;
+
+    let system = system.read().await;
+    let user = system.get_user(&Identifier::numeric(user_id)?)?;
+    let response = mapper::map_user(user);
     sender.send_ok_response(&response).await?;
     Ok(())
 }
@@ -34,26 +34,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.delete_user(session, &command.user_id)
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete user with ID: {}, session: {session}",
-                    command.user_id
-                )
-            })?;
-
-    let system = system.downgrade();
     let user_id = command.user_id.clone();
     system
-        .state
-        .apply(session.get_user_id(), EntryCommand::DeleteUser(command))
+        .apply_entry(session, |system| {
+            system.validate_delete_user(session, &command.user_id)?;
+            Ok((EntryCommand::DeleteUser(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete user with ID: {user_id}, session: {session}",
+                "{COMPONENT} (error: {error}) - failed to delete user with ID: {user_id}, session: {session}",
             )
         })?;
     sender.send_empty_ok_response().await?;
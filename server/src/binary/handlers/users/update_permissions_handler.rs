@@ -34,22 +34,18 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.update_permissions(session, &command.user_id, command.permissions.clone())
-            .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to update permissions for user ID: {}, session: {session}",
-                command.user_id
-            ))?;
-
-    let system = system.downgrade();
+    let user_id = command.user_id.clone();
     system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::UpdatePermissions(command),
-        )
-        .await?;
+        .apply_entry(session, |system| {
+            system.validate_update_permissions(session, &command.user_id)?;
+            Ok((EntryCommand::UpdatePermissions(command), ()))
+        })
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to update permissions for user ID: {user_id}, session: {session}"
+            )
+        })?;
     sender.send_empty_ok_response().await?;
     Ok(())
 }
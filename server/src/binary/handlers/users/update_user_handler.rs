@@ -34,32 +34,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-
-    let mut system = system.write().await;
-    system.update_user(
-                session,
-                &command.user_id,
-                command.username.clone(),
-                command.status,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to update user with user ID: {}, session: {session}",
-                    command.user_id
-                )
-            })?;
-
-    let system = system.downgrade();
     let user_id = command.user_id.clone();
-
     system
-        .state
-        .apply(session.get_user_id(), EntryCommand::UpdateUser(command))
+        .apply_entry(session, |system| {
+            system.validate_update_user(session, &command.user_id, command.username.as_deref())?;
+            Ok((EntryCommand::UpdateUser(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply update user with user ID: {user_id}, session: {session}",
+                "{COMPONENT} (error: {error}) - failed to update user with user ID: {user_id}, session: {session}",
             )
         })?;
     sender.send_empty_ok_response().await?;
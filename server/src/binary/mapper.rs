@@ -26,7 +26,10 @@ use crate::streaming::users::user::User;
 use bytes::{BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::models::cluster::ClusterMetadata;
+use iggy::models::consumer_lag::ConsumerLag;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
+use iggy::models::handshake::{HandshakeInfo, ProtocolFeature, ProtocolFeatures};
 use iggy::models::messages::PolledMessages;
 use iggy::models::stats::Stats;
 use iggy::models::user_info::UserId;
@@ -82,6 +85,39 @@ pub fn map_stats(stats: &Stats) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_handshake_info(handshake_info: &HandshakeInfo) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(14 + handshake_info.iggy_server_version.len());
+    bytes.put_u32_le(handshake_info.iggy_server_semver.unwrap_or_default());
+    bytes.put_u32_le(handshake_info.features.as_flags());
+    bytes.put_u8(handshake_info.iggy_server_version.len() as u8);
+    bytes.put_slice(handshake_info.iggy_server_version.as_bytes());
+    match handshake_info.user_id {
+        Some(user_id) => {
+            bytes.put_u8(1);
+            bytes.put_u32_le(user_id);
+        }
+        None => bytes.put_u8(0),
+    }
+    bytes.freeze()
+}
+
+pub fn map_cluster_metadata(metadata: &ClusterMetadata) -> Bytes {
+    let mut bytes = BytesMut::new();
+    bytes.put_u32_le(metadata.id);
+    bytes.put_u64_le(metadata.term);
+    bytes.put_u32_le(metadata.leader_id.unwrap_or_default());
+    bytes.put_u8(metadata.name.len() as u8);
+    bytes.put_slice(metadata.name.as_bytes());
+    for node in &metadata.nodes {
+        bytes.put_u32_le(node.id);
+        bytes.put_u8(node.role.as_code());
+        bytes.put_u8(node.status.as_code());
+        bytes.put_u8(node.address.len() as u8);
+        bytes.put_slice(node.address.as_bytes());
+    }
+    bytes.freeze()
+}
+
 pub fn map_consumer_offset(offset: &ConsumerOffsetInfo) -> Bytes {
     let mut bytes = BytesMut::with_capacity(20);
     bytes.put_u32_le(offset.partition_id);
@@ -90,6 +126,27 @@ pub fn map_consumer_offset(offset: &ConsumerOffsetInfo) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_consumer_lag(lag: &ConsumerLag) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(8 + 45 * lag.partitions.len());
+    bytes.put_u32_le(lag.stream_id);
+    bytes.put_u32_le(lag.topic_id);
+    for partition in &lag.partitions {
+        bytes.put_u32_le(partition.partition_id);
+        bytes.put_u64_le(partition.current_offset);
+        if let Some(stored_offset) = partition.stored_offset {
+            bytes.put_u8(1);
+            bytes.put_u64_le(stored_offset);
+        } else {
+            bytes.put_u8(0);
+            bytes.put_u64_le(0);
+        }
+        bytes.put_u64_le(partition.lag_messages);
+        bytes.put_u64_le(partition.lag_bytes.as_bytes_u64());
+        bytes.put_u64_le(partition.lag_time.as_micros());
+    }
+    bytes.freeze()
+}
+
 pub fn map_client(client: &Client) -> Bytes {
     let mut bytes = BytesMut::new();
     extend_client(client, &mut bytes);
@@ -120,7 +177,16 @@ pub fn map_user(user: &User) -> Bytes {
         bytes.put_u32_le(permissions.len() as u32);
         bytes.put_slice(&permissions);
     } else {
-        bytes.put_u32_le(0);
+        bytes.put_u8(0);
+    }
+    if let Some(quotas) = &user.quotas {
+        bytes.put_u8(1);
+        let quotas = quotas.to_bytes();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(quotas.len() as u32);
+        bytes.put_slice(&quotas);
+    } else {
+        bytes.put_u8(0);
     }
     bytes.freeze()
 }
@@ -154,7 +220,21 @@ pub fn map_personal_access_tokens(personal_access_tokens: &[PersonalAccessToken]
     bytes.freeze()
 }
 
-pub fn map_polled_messages(polled_messages: &PolledMessages) -> Bytes {
+pub fn map_assigned_polled_messages(
+    partitions_messages: &[PolledMessages],
+    features: ProtocolFeatures,
+) -> Bytes {
+    let mut bytes = BytesMut::new();
+    for polled_messages in partitions_messages {
+        let messages = map_polled_messages(polled_messages, features);
+        bytes.put_u32_le(messages.len() as u32);
+        bytes.put_slice(&messages);
+    }
+    bytes.freeze()
+}
+
+/// The generation of the consumer group is included only if the client has negotiated it, as the older ones don't expect it.
+pub fn map_polled_messages(polled_messages: &PolledMessages, features: ProtocolFeatures) -> Bytes {
     let messages_count = polled_messages.messages.len() as u32;
     let messages_size = polled_messages
         .messages
@@ -165,6 +245,9 @@ pub fn map_polled_messages(polled_messages: &PolledMessages) -> Bytes {
     let mut bytes = BytesMut::with_capacity(20 + messages_size.as_bytes_usize());
     bytes.put_u32_le(polled_messages.partition_id);
     bytes.put_u64_le(polled_messages.current_offset);
+    if features.contains(ProtocolFeature::ConsumerGroupGenerations) {
+        bytes.put_u32_le(polled_messages.generation_id);
+    }
     bytes.put_u32_le(messages_count);
     for message in polled_messages.messages.iter() {
         message.extend(&mut bytes);
@@ -255,6 +338,7 @@ fn extend_topic(topic: &Topic, bytes: &mut BytesMut) {
     bytes.put_u64_le(topic.get_messages_count());
     bytes.put_u8(topic.name.len() as u8);
     bytes.put_slice(topic.name.as_bytes());
+    bytes.put_slice(&topic.cleanup_policy.to_bytes());
 }
 
 fn extend_partition(partition: &Partition, bytes: &mut BytesMut) {
@@ -263,6 +347,7 @@ fn extend_partition(partition: &Partition, bytes: &mut BytesMut) {
     bytes.put_u32_le(partition.get_segments().len() as u32);
     bytes.put_u64_le(partition.current_offset);
     bytes.put_u64_le(partition.get_size_bytes().as_bytes_u64());
+    bytes.put_u64_le(partition.get_uncompressed_size_bytes().as_bytes_u64());
     bytes.put_u64_le(partition.get_messages_count());
 }
 
@@ -270,6 +355,7 @@ fn extend_consumer_group(consumer_group: &ConsumerGroup, bytes: &mut BytesMut) {
     bytes.put_u32_le(consumer_group.group_id);
     bytes.put_u32_le(consumer_group.partitions_count);
     bytes.put_u32_le(consumer_group.get_members().len() as u32);
+    bytes.put_u8(consumer_group.assignment_strategy.as_code());
     bytes.put_u8(consumer_group.name.len() as u8);
     bytes.put_slice(consumer_group.name.as_bytes());
 }
@@ -280,6 +366,7 @@ fn extend_client(client: &Client, bytes: &mut BytesMut) {
     let transport: u8 = match client.transport {
         Transport::Tcp => 1,
         Transport::Quic => 2,
+        Transport::WebSocket => 3,
     };
     bytes.put_u8(transport);
     let address = client.session.ip_address.to_string();
@@ -16,7 +16,9 @@
  * under the License.
  */
 
+pub mod buffered_sender;
 pub mod command;
+pub(crate) mod compression;
 mod handlers;
 mod mapper;
 pub mod sender;
@@ -18,13 +18,18 @@
 
 use std::future::Future;
 
+use crate::binary::buffered_sender::BufferedSender;
+use crate::http::websocket_sender::WebSocketSender;
+use crate::streaming::clients::client_manager::Transport;
 use crate::tcp::tcp_sender::TcpSender;
 use crate::tcp::tcp_tls_sender::TcpTlsSender;
 use crate::{quic::quic_sender::QuicSender, server_error::ServerError};
+use iggy::compression::wire_compression::WireCompression;
 use iggy::error::IggyError;
 use quinn::{RecvStream, SendStream};
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream};
 use tokio::net::TcpStream;
-use tokio_native_tls::TlsStream;
+use tokio_rustls::server::TlsStream;
 
 macro_rules! forward_async_methods {
     (
@@ -40,6 +45,8 @@ macro_rules! forward_async_methods {
                     Self::Tcp(d) => d.$method_name($( $arg ),*).await,
                     Self::TcpTls(s) => s.$method_name($( $arg ),*).await,
                     Self::Quic(s) => s.$method_name($( $arg ),*).await,
+                    Self::WebSocket(s) => s.$method_name($( $arg ),*).await,
+                    Self::Buffered(s) => s.$method_name($( $arg ),*).await,
                 }
             }
         )*
@@ -62,26 +69,97 @@ pub trait Sender {
 
 pub enum SenderKind {
     Tcp(TcpSender),
-    TcpTls(TcpTlsSender),
+    TcpTls(Box<TcpTlsSender>),
     Quic(QuicSender),
+    WebSocket(WebSocketSender),
+    Buffered(BufferedSender),
 }
 
 impl SenderKind {
     pub fn get_tcp_sender(stream: TcpStream) -> Self {
-        Self::Tcp(TcpSender { stream })
+        Self::Tcp(TcpSender {
+            stream,
+            compression: None,
+        })
     }
 
     pub fn get_tcp_tls_sender(stream: TlsStream<TcpStream>) -> Self {
-        Self::TcpTls(TcpTlsSender { stream })
+        Self::TcpTls(Box::new(TcpTlsSender {
+            stream,
+            compression: None,
+        }))
     }
 
     pub fn get_quic_sender(send_stream: SendStream, recv_stream: RecvStream) -> Self {
         Self::Quic(QuicSender {
             send: send_stream,
             recv: recv_stream,
+            compression: None,
         })
     }
 
+    pub fn get_websocket_sender(stream: DuplexStream) -> Self {
+        Self::WebSocket(WebSocketSender {
+            stream,
+            compression: None,
+        })
+    }
+
+    pub fn get_buffered_sender(transport: Transport) -> Self {
+        Self::Buffered(BufferedSender {
+            transport,
+            response: None,
+        })
+    }
+
+    pub fn transport(&self) -> Transport {
+        match self {
+            Self::Tcp(_) | Self::TcpTls(_) => Transport::Tcp,
+            Self::Quic(_) => Transport::Quic,
+            Self::WebSocket(_) => Transport::WebSocket,
+            Self::Buffered(sender) => sender.transport,
+        }
+    }
+
+    /// Sets the compression of the OK response payloads negotiated during the handshake.
+    /// The multiplexed responses are compressed while being written to the connection instead.
+    pub fn set_compression(&mut self, compression: Option<WireCompression>) {
+        match self {
+            Self::Tcp(sender) => sender.compression = compression,
+            Self::TcpTls(sender) => sender.compression = compression,
+            Self::Quic(sender) => sender.compression = compression,
+            Self::WebSocket(sender) => sender.compression = compression,
+            Self::Buffered(_) => {}
+        }
+    }
+
+    /// Splits the sender into the reading and writing halves, so they can be used at the same time,
+    /// e.g. by the subscription, which pushes the messages while the client keeps granting the credit.
+    pub fn split(
+        &mut self,
+    ) -> (
+        Box<dyn AsyncRead + Unpin + Send + '_>,
+        Box<dyn AsyncWrite + Unpin + Send + '_>,
+    ) {
+        match self {
+            Self::Tcp(sender) => {
+                let (reader, writer) = sender.stream.split();
+                (Box::new(reader), Box::new(writer))
+            }
+            Self::TcpTls(sender) => {
+                let (reader, writer) = tokio::io::split(&mut sender.stream);
+                (Box::new(reader), Box::new(writer))
+            }
+            Self::Quic(sender) => (Box::new(&mut sender.recv), Box::new(&mut sender.send)),
+            Self::WebSocket(sender) => {
+                let (reader, writer) = tokio::io::split(&mut sender.stream);
+                (Box::new(reader), Box::new(writer))
+            }
+            // The multiplexed requests share the connection, so there is nothing to split.
+            Self::Buffered(_) => (Box::new(tokio::io::empty()), Box::new(tokio::io::sink())),
+        }
+    }
+
     forward_async_methods! {
         async fn read(&mut self, buffer: &mut [u8]) -> Result<usize, IggyError>;
         async fn send_empty_ok_response(&mut self) -> Result<(), IggyError>;
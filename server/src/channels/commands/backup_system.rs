@@ -0,0 +1,149 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::{BackupMaintenanceConfig, ServerConfig};
+use crate::streaming::systems::system::SharedSystem;
+use flume::{Receiver, Sender};
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info, instrument, warn};
+
+pub struct SystemBackuper {
+    enabled: bool,
+    retained_backups: u32,
+    interval: IggyDuration,
+    sender: Sender<BackupSystemCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BackupSystemCommand {
+    retained_backups: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BackupSystemExecutor;
+
+impl SystemBackuper {
+    pub fn new(config: &BackupMaintenanceConfig, sender: Sender<BackupSystemCommand>) -> Self {
+        Self {
+            enabled: config.enabled,
+            retained_backups: config.retained_backups,
+            interval: config.interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.enabled {
+            info!("Online backup is disabled.");
+            return;
+        }
+
+        let retained_backups = self.retained_backups;
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Online backup is enabled, the whole server will be backed up every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            // The first tick completes immediately, while the backup is not needed right after the startup.
+            interval_timer.tick().await;
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(BackupSystemCommand { retained_backups })
+                    .unwrap_or_else(|err| {
+                        error!("Failed to send BackupSystemCommand. Error: {}", err);
+                    });
+            }
+        });
+    }
+}
+
+impl ServerCommand<BackupSystemCommand> for BackupSystemExecutor {
+    #[instrument(skip_all, name = "trace_backup_system")]
+    async fn execute(&mut self, system: &SharedSystem, command: BackupSystemCommand) {
+        info!("Backing up the server...");
+        // The exclusive lock ensures the consistent cut, while the open segments are copied once it's released.
+        let pending_backup = system.write().await.start_backup().await;
+        let pending_backup = match pending_backup {
+            Ok(pending_backup) => pending_backup,
+            Err(error) => {
+                error!("Failed to start the backup. Error: {error}");
+                return;
+            }
+        };
+
+        let backup_path = pending_backup.path.clone();
+        match pending_backup.complete().await {
+            Ok(manifest) => info!(
+                "Backed up the server at: {backup_path}, state index: {:?}.",
+                manifest.state_index
+            ),
+            Err(error) => {
+                error!("Failed to complete the backup at: {backup_path}. Error: {error}");
+                return;
+            }
+        }
+
+        if command.retained_backups == 0 {
+            return;
+        }
+
+        if let Err(error) = system
+            .read()
+            .await
+            .delete_old_backups(command.retained_backups)
+            .await
+        {
+            error!("Failed to delete old backups. Error: {error}");
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &ServerConfig,
+        sender: Sender<BackupSystemCommand>,
+    ) {
+        if !config.data_maintenance.backup.enabled {
+            return;
+        }
+
+        let system_backuper = SystemBackuper::new(&config.data_maintenance.backup, sender);
+        system_backuper.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        config: &ServerConfig,
+        receiver: Receiver<BackupSystemCommand>,
+    ) {
+        if !config.data_maintenance.backup.enabled {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            warn!("Online backup receiver stopped.");
+        });
+    }
+}
@@ -0,0 +1,124 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::channels::server_command::ServerCommand;
+use crate::configs::cluster::ClusterConfig;
+use crate::configs::server::ServerConfig;
+use crate::state::StateKind;
+use crate::streaming::systems::system::SharedSystem;
+use flume::{Receiver, Sender};
+use iggy::utils::duration::IggyDuration;
+use std::sync::Arc;
+use tokio::time;
+use tracing::{error, info, instrument, warn};
+
+pub struct ClusterMaintainer {
+    enabled: bool,
+    interval: IggyDuration,
+    sender: Sender<MaintainClusterCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MaintainClusterCommand;
+
+#[derive(Debug, Default)]
+pub struct MaintainClusterExecutor {
+    state: Option<Arc<StateKind>>,
+}
+
+impl ClusterMaintainer {
+    pub fn new(config: &ClusterConfig, sender: Sender<MaintainClusterCommand>) -> Self {
+        Self {
+            enabled: config.enabled,
+            interval: config.heartbeat_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.enabled {
+            info!("Cluster maintainer is disabled.");
+            return;
+        }
+
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Cluster maintainer is enabled, heartbeats and elections will be handled every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                // Don't queue the next round while the previous one is still in progress.
+                if !sender.is_empty() {
+                    continue;
+                }
+
+                sender.send(MaintainClusterCommand).unwrap_or_else(|error| {
+                    error!("Failed to send MaintainClusterCommand. Error: {error}");
+                });
+            }
+        });
+    }
+}
+
+impl ServerCommand<MaintainClusterCommand> for MaintainClusterExecutor {
+    #[instrument(skip_all, name = "trace_maintain_cluster")]
+    async fn execute(&mut self, system: &SharedSystem, _command: MaintainClusterCommand) {
+        let state = match &self.state {
+            Some(state) => state.clone(),
+            None => self.state.insert(system.read().await.state.clone()).clone(),
+        };
+        let Some(raft) = state.as_raft() else {
+            return;
+        };
+
+        raft.tick().await;
+        if !raft.has_unapplied_entries().await {
+            return;
+        }
+
+        let mut system = system.write().await;
+        let entries = raft.take_committed_entries().await;
+        system.apply_state_entries(entries).await;
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &ServerConfig,
+        sender: Sender<MaintainClusterCommand>,
+    ) {
+        let cluster_maintainer = ClusterMaintainer::new(&config.cluster, sender);
+        cluster_maintainer.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &ServerConfig,
+        receiver: Receiver<MaintainClusterCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            warn!("Server command handler stopped receiving commands.");
+        });
+    }
+}
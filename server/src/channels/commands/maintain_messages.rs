@@ -20,6 +20,8 @@ use crate::archiver::ArchiverKind;
 use crate::channels::server_command::ServerCommand;
 use crate::configs::server::MessagesMaintenanceConfig;
 use crate::map_toggle_str;
+use crate::streaming::partitions::archive::ArchivedSegment;
+use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::system::SharedSystem;
 use crate::streaming::topics::topic::Topic;
 use error_set::ErrContext;
@@ -135,6 +137,10 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                     continue;
                 }
 
+                if command.clean_messages {
+                    handle_compacted_segments(topic).await;
+                }
+
                 let deleted_expired_segments = expired_segments.unwrap();
                 let deleted_oldest_segments = oldest_segments.unwrap();
                 let deleted_segments = HandledSegments {
@@ -168,6 +174,34 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                     .metrics
                     .decrement_messages(deleted_segments.messages_count);
             }
+
+            let deleted_segments =
+                handle_oldest_stream_segments(stream, system.config.stream.delete_oldest_segments)
+                    .await;
+            if deleted_segments.is_err() {
+                error!(
+                    "Failed to delete oldest segments for stream ID: {}",
+                    stream.stream_id
+                );
+                continue;
+            }
+
+            let deleted_segments = deleted_segments.unwrap();
+            if deleted_segments.segments_count == 0 {
+                continue;
+            }
+
+            info!(
+                "Deleted {} segments and {} messages for full stream ID: {}",
+                deleted_segments.segments_count, deleted_segments.messages_count, stream.stream_id
+            );
+
+            system
+                .metrics
+                .decrement_segments(deleted_segments.segments_count);
+            system
+                .metrics
+                .decrement_messages(deleted_segments.messages_count);
         }
     }
 
@@ -256,6 +290,28 @@ async fn handle_expired_segments(
     }
 }
 
+async fn handle_compacted_segments(topic: &Topic) {
+    if topic.cleanup_policy.compaction_key().is_none() {
+        return;
+    }
+
+    match topic.compact_segments().await {
+        Ok(0) => trace!(
+            "No messages were compacted for stream ID: {}, topic ID: {}",
+            topic.stream_id,
+            topic.topic_id
+        ),
+        Ok(removed_messages) => info!(
+            "Compacted {removed_messages} messages for stream ID: {}, topic ID: {}",
+            topic.stream_id, topic.topic_id
+        ),
+        Err(error) => error!(
+            "Failed to compact segments for stream ID: {}, topic ID: {}. Error: {error}",
+            topic.stream_id, topic.topic_id
+        ),
+    }
+}
+
 async fn get_expired_segments(topic: &Topic, now: IggyTimestamp) -> Vec<SegmentsToHandle> {
     let expired_segments = topic
         .get_expired_segments_start_offsets_per_partition(now)
@@ -413,6 +469,80 @@ async fn get_oldest_segments(topic: &Topic) -> Vec<SegmentsToHandle> {
     oldest_segments
 }
 
+async fn handle_oldest_stream_segments(
+    stream: &Stream,
+    delete_oldest_segments: bool,
+) -> Result<HandledSegments, IggyError> {
+    if stream.is_unlimited() {
+        return Ok(HandledSegments::none());
+    }
+
+    if !delete_oldest_segments {
+        debug!(
+            "Delete oldest segments is disabled, oldest segments will not be deleted for stream ID: {}",
+            stream.stream_id
+        );
+        return Ok(HandledSegments::none());
+    }
+
+    if !stream.is_almost_full() {
+        debug!(
+            "Stream is not almost full, oldest segments will not be deleted for stream ID: {}",
+            stream.stream_id
+        );
+        return Ok(HandledSegments::none());
+    }
+
+    // Only the single oldest closed segment across all the topics of the stream is deleted,
+    // so that the topics with the most recent data are not affected.
+    let mut oldest_segment = None;
+    for topic in stream.get_topics() {
+        for partition in topic.partitions.values() {
+            let partition = partition.read().await;
+            let Some(segment) = partition.get_segments().first() else {
+                continue;
+            };
+
+            if !segment.is_closed {
+                continue;
+            }
+
+            let is_older = oldest_segment
+                .as_ref()
+                .is_none_or(|(_, _, _, end_timestamp)| segment.end_timestamp < *end_timestamp);
+            if is_older {
+                oldest_segment = Some((
+                    topic,
+                    partition.partition_id,
+                    segment.start_offset,
+                    segment.end_timestamp,
+                ));
+            }
+        }
+    }
+
+    let Some((topic, partition_id, start_offset, _)) = oldest_segment else {
+        debug!(
+            "No oldest segments found for full stream ID: {}",
+            stream.stream_id
+        );
+        return Ok(HandledSegments::none());
+    };
+
+    info!(
+        "Found the oldest segment with start offset: {start_offset} for full stream ID: {}, topic ID: {}, partition ID: {partition_id}.",
+        stream.stream_id, topic.topic_id
+    );
+    delete_segments(
+        topic,
+        &[SegmentsToHandle {
+            partition_id,
+            start_offsets: vec![start_offset],
+        }],
+    )
+    .await
+}
+
 #[derive()]
 struct SegmentsToHandle {
     partition_id: u32,
@@ -454,31 +584,56 @@ async fn archive_segments(
     for segment_to_archive in segments_to_archive {
         match topic.get_partition(segment_to_archive.partition_id) {
             Ok(partition) => {
-                let partition = partition.read().await;
-                for start_offset in &segment_to_archive.start_offsets {
-                    let segment = partition.get_segment(*start_offset);
-                    if segment.is_none() {
-                        error!(
-                            "Segment with start offset: {} not found for stream ID: {}, topic ID: {}, partition ID: {}",
+                let mut segments_to_register = Vec::new();
+                {
+                    let partition = partition.read().await;
+                    for start_offset in &segment_to_archive.start_offsets {
+                        let segment = partition.get_segment(*start_offset);
+                        if segment.is_none() {
+                            error!(
+                                "Segment with start offset: {} not found for stream ID: {}, topic ID: {}, partition ID: {}",
+                                start_offset, topic.stream_id, topic.topic_id, partition.partition_id
+                            );
+                            continue;
+                        }
+
+                        let segment = segment.unwrap();
+                        let files = [segment.index_path.as_ref(), segment.log_path.as_ref()];
+                        if let Err(error) = archiver.archive(&files, None).await {
+                            error!(
+                                "Failed to archive segment with start offset: {} for stream ID: {}, topic ID: {}, partition ID: {}. Error: {}",
+                                start_offset, topic.stream_id, topic.topic_id, partition.partition_id, error
+                            );
+                            continue;
+                        }
+                        info!(
+                            "Archived Segment with start offset: {}, for stream ID: {}, topic ID: {}, partition ID: {}",
                             start_offset, topic.stream_id, topic.topic_id, partition.partition_id
                         );
-                        continue;
+                        let end_timestamp = segment
+                            .get_messages_by_offset(segment.current_offset, 1)
+                            .await
+                            .ok()
+                            .and_then(|messages| messages.last().map(|message| message.timestamp))
+                            .unwrap_or(segment.end_timestamp);
+                        segments_to_register.push(ArchivedSegment {
+                            start_offset: segment.start_offset,
+                            end_offset: segment.current_offset,
+                            end_timestamp,
+                        });
+                        archived_segments += 1;
                     }
+                }
 
-                    let segment = segment.unwrap();
-                    let files = [segment.index_path.as_ref(), segment.log_path.as_ref()];
-                    if let Err(error) = archiver.archive(&files, None).await {
+                // The archived segments are registered, so their messages can still be polled once the local copies are deleted.
+                let mut partition = partition.write().await;
+                for archived_segment in segments_to_register {
+                    if let Err(error) = partition.add_archived_segment(archived_segment).await {
                         error!(
-                            "Failed to archive segment with start offset: {} for stream ID: {}, topic ID: {}, partition ID: {}. Error: {}",
-                            start_offset, topic.stream_id, topic.topic_id, partition.partition_id, error
+                            "Failed to register archived segment with start offset: {} for stream ID: {}, topic ID: {}, partition ID: {}. Error: {}",
+                            archived_segment.start_offset, topic.stream_id, topic.topic_id, partition.partition_id, error
                         );
-                        continue;
                     }
-                    info!(
-                        "Archived Segment with start offset: {}, for stream ID: {}, topic ID: {}, partition ID: {}",
-                        start_offset, topic.stream_id, topic.topic_id, partition.partition_id
-                    );
-                    archived_segments += 1;
                 }
             }
             Err(error) => {
@@ -17,8 +17,11 @@
  */
 
 pub mod archive_state;
+pub mod backup_system;
 pub mod clean_personal_access_tokens;
+pub mod maintain_cluster;
 pub mod maintain_messages;
 pub mod print_sysinfo;
+pub mod replicate_messages;
 pub mod save_messages;
 pub mod verify_heartbeats;
@@ -0,0 +1,437 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::channels::server_command::ServerCommand;
+use crate::configs::cluster::ClusterIdentityConfig;
+use crate::configs::server::ServerConfig;
+use crate::configs::system::ReplicationConfig;
+use crate::state::command::EntryCommand;
+use crate::state::models::{CreateStreamWithId, CreateTopicWithId};
+use crate::streaming::batching::iterator::IntoMessagesIterator;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use flume::{Receiver, Sender};
+use iggy::binary::{BinaryTransport, ClientState};
+use iggy::client::{AutoLogin, Client, Credentials, StreamClient};
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::models::stream::StreamDetails;
+use iggy::models::topic::Topic;
+use iggy::partitions::create_partitions::CreatePartitions;
+use iggy::replication::fetch_replica_messages::FetchReplicaMessages;
+use iggy::streams::create_stream::CreateStream;
+use iggy::tcp::client::TcpClient;
+use iggy::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use iggy::topics::create_topic::CreateTopic;
+use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
+use iggy::utils::duration::IggyDuration;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::time;
+use tracing::{error, info, instrument, warn};
+
+const COMPONENT: &str = "REPLICATE_MESSAGES";
+/// Limits the number of consecutive fetches from a single partition, so that the constantly
+/// written partition doesn't starve the other ones. Each fetch acknowledges the previous one.
+const MAX_FETCHES_PER_PARTITION: usize = 10;
+
+pub struct MessagesReplicator {
+    enabled: bool,
+    leader_address: String,
+    interval: IggyDuration,
+    sender: Sender<ReplicateMessagesCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ReplicateMessagesCommand;
+
+#[derive(Default)]
+pub struct ReplicateMessagesExecutor {
+    config: Option<Arc<ReplicationSettings>>,
+    client: Option<TcpClient>,
+}
+
+#[derive(Debug)]
+struct ReplicationSettings {
+    node_id: u32,
+    leader_address: String,
+    username: String,
+    password: String,
+    max_fetch_messages: u32,
+}
+
+impl MessagesReplicator {
+    pub fn new(
+        config: &ReplicationConfig,
+        identity: &ClusterIdentityConfig,
+        sender: Sender<ReplicateMessagesCommand>,
+    ) -> Self {
+        Self {
+            enabled: config.is_follower(identity),
+            leader_address: config.leader_address.clone(),
+            interval: config.fetch_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.enabled {
+            info!("Messages replicator is disabled.");
+            return;
+        }
+
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!(
+            "Messages replicator is enabled, messages will be fetched from the leader: {} every: {interval}.",
+            self.leader_address
+        );
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                // Don't queue the next replication round while the previous one is still in progress.
+                if !sender.is_empty() {
+                    continue;
+                }
+
+                sender
+                    .send(ReplicateMessagesCommand)
+                    .unwrap_or_else(|error| {
+                        error!("Failed to send ReplicateMessagesCommand. Error: {error}");
+                    });
+            }
+        });
+    }
+}
+
+impl ReplicateMessagesExecutor {
+    async fn get_client(&mut self) -> Result<&TcpClient, IggyError> {
+        let Some(settings) = &self.config else {
+            return Err(IggyError::FeatureUnavailable);
+        };
+
+        if let Some(client) = &self.client {
+            if client.get_state().await == ClientState::Authenticated {
+                return Ok(self.client.as_ref().unwrap());
+            }
+            let _ = client.shutdown().await;
+            self.client = None;
+        }
+
+        let client = TcpClient::create(Arc::new(TcpClientConfig {
+            server_address: settings.leader_address.clone(),
+            auto_login: AutoLogin::Enabled(Credentials::UsernamePassword(
+                settings.username.clone(),
+                settings.password.clone(),
+            )),
+            reconnection: TcpClientReconnectionConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        }))?;
+        client.connect().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to connect to the leader: {}",
+                settings.leader_address
+            )
+        })?;
+        Ok(self.client.insert(client))
+    }
+}
+
+impl ServerCommand<ReplicateMessagesCommand> for ReplicateMessagesExecutor {
+    #[instrument(skip_all, name = "trace_replicate_messages")]
+    async fn execute(&mut self, system: &SharedSystem, _command: ReplicateMessagesCommand) {
+        let Some(settings) = self.config.clone() else {
+            return;
+        };
+
+        let client = match self.get_client().await {
+            Ok(client) => client,
+            Err(error) => {
+                warn!("Cannot connect to the replication leader. Error: {error}");
+                return;
+            }
+        };
+
+        if let Err(error) = replicate(client, system, &settings).await {
+            error!("Failed to replicate messages from the leader. Error: {error}");
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &ServerConfig,
+        sender: Sender<ReplicateMessagesCommand>,
+    ) {
+        let replication = &config.system.replication;
+        self.config = Some(Arc::new(ReplicationSettings {
+            node_id: replication.node_id,
+            leader_address: replication.leader_address.clone(),
+            username: config.cluster.identity.username.clone(),
+            password: config.cluster.identity.password.clone(),
+            max_fetch_messages: replication.max_fetch_messages,
+        }));
+        let messages_replicator =
+            MessagesReplicator::new(replication, &config.cluster.identity, sender);
+        messages_replicator.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &ServerConfig,
+        receiver: Receiver<ReplicateMessagesCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            warn!("Server command handler stopped receiving commands.");
+        });
+    }
+}
+
+async fn replicate(
+    client: &TcpClient,
+    system: &SharedSystem,
+    settings: &ReplicationSettings,
+) -> Result<(), IggyError> {
+    for stream in client.get_streams().await? {
+        let Some(stream) = client.get_stream(&Identifier::numeric(stream.id)?).await? else {
+            continue;
+        };
+
+        let topics = stream
+            .topics
+            .iter()
+            .filter(|topic| topic.replication_factor > 1)
+            .collect::<Vec<_>>();
+        if topics.is_empty() {
+            continue;
+        }
+
+        sync_metadata(system, &stream, &topics)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to sync metadata for stream with ID: {}",
+                    stream.id
+                )
+            })?;
+        for topic in topics {
+            for partition_id in 1..=topic.partitions_count {
+                replicate_partition(client, system, settings, stream.id, topic.id, partition_id)
+                    .await
+                    .with_error_context(|error| {
+                        format!(
+                            "{COMPONENT} (error: {error}) - failed to replicate partition with ID: {partition_id} for stream with ID: {}, topic with ID: {}",
+                            stream.id, topic.id
+                        )
+                    })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the replicated stream, topics and partitions which exist on the leader, but are missing on this node.
+async fn sync_metadata(
+    system: &SharedSystem,
+    stream: &StreamDetails,
+    topics: &[&Topic],
+) -> Result<(), IggyError> {
+    let stream_id = Identifier::numeric(stream.id)?;
+    let (stream_exists, missing_partitions) = {
+        let system = system.read().await;
+        match system.get_stream(&stream_id) {
+            Ok(local_stream) => {
+                let mut missing_partitions = Vec::new();
+                for topic in topics {
+                    let local_partitions_count = local_stream
+                        .get_topic(&Identifier::numeric(topic.id)?)
+                        .map(|local_topic| local_topic.get_partitions_count())
+                        .ok();
+                    if local_partitions_count != Some(topic.partitions_count) {
+                        missing_partitions.push((topic, local_partitions_count));
+                    }
+                }
+                (true, missing_partitions)
+            }
+            Err(_) => (false, topics.iter().map(|topic| (topic, None)).collect()),
+        }
+    };
+
+    if stream_exists && missing_partitions.is_empty() {
+        return Ok(());
+    }
+
+    // The replicated metadata is created on behalf of the server, like the one applied from the state log.
+    let session = Session::stateless(
+        DEFAULT_ROOT_USER_ID,
+        SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+    );
+    if !stream_exists {
+        system
+            .apply_entry(&session, |system| {
+                let stream_id =
+                    system.validate_create_stream(&session, Some(stream.id), &stream.name)?;
+                let command = CreateStream {
+                    stream_id: Some(stream_id),
+                    name: stream.name.clone(),
+                    limits: None,
+                };
+                Ok((
+                    EntryCommand::CreateStream(CreateStreamWithId { stream_id, command }),
+                    (),
+                ))
+            })
+            .await?;
+        info!(
+            "Created replicated stream with ID: {}, name: {}.",
+            stream.id, stream.name
+        );
+    }
+
+    for (topic, local_partitions_count) in missing_partitions {
+        let topic_id = Identifier::numeric(topic.id)?;
+        match local_partitions_count {
+            None => {
+                system
+                    .apply_entry(&session, |system| {
+                        let topic_id = system.validate_create_topic(
+                            &session,
+                            &stream_id,
+                            Some(topic.id),
+                            &topic.name,
+                            topic.partitions_count,
+                            topic.max_topic_size,
+                        )?;
+                        let command = CreateTopic {
+                            stream_id: stream_id.clone(),
+                            topic_id: Some(topic_id),
+                            partitions_count: topic.partitions_count,
+                            compression_algorithm: topic.compression_algorithm,
+                            message_expiry: topic.message_expiry,
+                            max_topic_size: topic.max_topic_size,
+                            replication_factor: Some(topic.replication_factor),
+                            name: topic.name.clone(),
+                            cleanup_policy: topic.cleanup_policy.clone(),
+                        };
+                        Ok((
+                            EntryCommand::CreateTopic(CreateTopicWithId {
+                                topic_id,
+                                command,
+                                dead_letter: false,
+                            }),
+                            (),
+                        ))
+                    })
+                    .await?;
+                info!(
+                    "Created replicated topic with ID: {}, name: {} in stream with ID: {}.",
+                    topic.id, topic.name, stream.id
+                );
+            }
+            Some(partitions_count) if partitions_count < topic.partitions_count => {
+                let command = CreatePartitions {
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.clone(),
+                    partitions_count: topic.partitions_count - partitions_count,
+                };
+                system
+                    .apply_entry(&session, |system| {
+                        system.validate_create_partitions(
+                            &session,
+                            &command.stream_id,
+                            &command.topic_id,
+                            command.partitions_count,
+                        )?;
+                        Ok((EntryCommand::CreatePartitions(command), ()))
+                    })
+                    .await?;
+                info!(
+                    "Created {} replicated partitions for topic with ID: {} in stream with ID: {}.",
+                    topic.partitions_count - partitions_count,
+                    topic.id,
+                    stream.id
+                );
+            }
+            Some(_) => {
+                warn!(
+                    "Replicated topic with ID: {} in stream with ID: {} has more partitions than on the leader.",
+                    topic.id, stream.id
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn replicate_partition(
+    client: &TcpClient,
+    system: &SharedSystem,
+    settings: &ReplicationSettings,
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+) -> Result<(), IggyError> {
+    let stream_id = Identifier::numeric(stream_id)?;
+    let topic_id = Identifier::numeric(topic_id)?;
+    for _ in 0..MAX_FETCHES_PER_PARTITION {
+        let partition = system
+            .read()
+            .await
+            .get_stream(&stream_id)?
+            .get_topic(&topic_id)?
+            .get_partition(partition_id)?;
+        let offset = partition.read().await.get_next_offset();
+        let response = client
+            .send_with_response(&FetchReplicaMessages {
+                replica_id: settings.node_id,
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+                offset,
+                count: settings.max_fetch_messages,
+            })
+            .await?;
+        if response.is_empty() {
+            return Ok(());
+        }
+
+        let batch = RetainedMessageBatch::from_bytes(response)?;
+        let messages = batch.into_messages_iter().collect::<Vec<_>>();
+        partition
+            .write()
+            .await
+            .append_replicated_messages(messages)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -18,6 +18,9 @@
 
 use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
+use iggy::cluster::append_entries::AppendEntries;
+use iggy::cluster::get_cluster_metadata::GetClusterMetadata;
+use iggy::cluster::request_vote::RequestVote;
 use iggy::command::*;
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
@@ -26,17 +29,25 @@ use iggy::consumer_groups::get_consumer_groups::GetConsumerGroups;
 use iggy::consumer_groups::join_consumer_group::JoinConsumerGroup;
 use iggy::consumer_groups::leave_consumer_group::LeaveConsumerGroup;
 use iggy::consumer_offsets::delete_consumer_offset::DeleteConsumerOffset;
+use iggy::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use iggy::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use iggy::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use iggy::error::IggyError;
+use iggy::messages::init_producer::InitProducer;
+use iggy::messages::nack_message::NackMessage;
+use iggy::messages::poll_assigned_messages::PollAssignedMessages;
 use iggy::messages::poll_messages::PollMessages;
 use iggy::messages::send_messages::SendMessages;
+use iggy::messages::subscribe_messages::SubscribeMessages;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::partitions::delete_partitions::DeletePartitions;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
 use iggy::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokens;
 use iggy::personal_access_tokens::login_with_personal_access_token::LoginWithPersonalAccessToken;
+use iggy::replication::fetch_replica_messages::FetchReplicaMessages;
+use iggy::segments::delete_segments::DeleteSegments;
+use iggy::segments::restore_segments::RestoreSegments;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::get_stream::GetStream;
@@ -48,6 +59,7 @@ use iggy::system::get_clients::GetClients;
 use iggy::system::get_me::GetMe;
 use iggy::system::get_snapshot::GetSnapshot;
 use iggy::system::get_stats::GetStats;
+use iggy::system::handshake::Handshake;
 use iggy::system::ping::Ping;
 use iggy::topics::create_topic::CreateTopic;
 use iggy::topics::delete_topic::DeleteTopic;
@@ -55,12 +67,16 @@ use iggy::topics::get_topic::GetTopic;
 use iggy::topics::get_topics::GetTopics;
 use iggy::topics::purge_topic::PurgeTopic;
 use iggy::topics::update_topic::UpdateTopic;
+use iggy::transactions::abort_transaction::AbortTransaction;
+use iggy::transactions::begin_transaction::BeginTransaction;
+use iggy::transactions::commit_transaction::CommitTransaction;
 use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::get_user::GetUser;
 use iggy::users::get_users::GetUsers;
 use iggy::users::login_user::LoginUser;
+use iggy::users::login_with_oidc_token::LoginWithOidcToken;
 use iggy::users::logout_user::LogoutUser;
 use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
@@ -75,6 +91,7 @@ use tracing::error;
 #[derive(Debug, PartialEq, EnumString)]
 pub enum ServerCommand {
     Ping(Ping),
+    Handshake(Handshake),
     GetStats(GetStats),
     GetMe(GetMe),
     GetClient(GetClient),
@@ -87,6 +104,7 @@ pub enum ServerCommand {
     UpdatePermissions(UpdatePermissions),
     ChangePassword(ChangePassword),
     LoginUser(LoginUser),
+    LoginWithOidcToken(LoginWithOidcToken),
     LogoutUser(LogoutUser),
     GetPersonalAccessTokens(GetPersonalAccessTokens),
     CreatePersonalAccessToken(CreatePersonalAccessToken),
@@ -95,9 +113,14 @@ pub enum ServerCommand {
     SendMessages(SendMessages),
     PollMessages(PollMessages),
     FlushUnsavedBuffer(FlushUnsavedBuffer),
+    NackMessage(NackMessage),
+    InitProducer(InitProducer),
+    PollAssignedMessages(PollAssignedMessages),
+    SubscribeMessages(SubscribeMessages),
     GetConsumerOffset(GetConsumerOffset),
     StoreConsumerOffset(StoreConsumerOffset),
     DeleteConsumerOffset(DeleteConsumerOffset),
+    GetConsumerLag(GetConsumerLag),
     GetStream(GetStream),
     GetStreams(GetStreams),
     CreateStream(CreateStream),
@@ -112,6 +135,8 @@ pub enum ServerCommand {
     PurgeTopic(PurgeTopic),
     CreatePartitions(CreatePartitions),
     DeletePartitions(DeletePartitions),
+    DeleteSegments(DeleteSegments),
+    RestoreSegments(RestoreSegments),
     GetConsumerGroup(GetConsumerGroup),
     GetConsumerGroups(GetConsumerGroups),
     CreateConsumerGroup(CreateConsumerGroup),
@@ -119,12 +144,20 @@ pub enum ServerCommand {
     JoinConsumerGroup(JoinConsumerGroup),
     LeaveConsumerGroup(LeaveConsumerGroup),
     GetSnapshotFile(GetSnapshot),
+    FetchReplicaMessages(FetchReplicaMessages),
+    GetClusterMetadata(GetClusterMetadata),
+    RequestVote(RequestVote),
+    AppendEntries(AppendEntries),
+    BeginTransaction(BeginTransaction),
+    CommitTransaction(CommitTransaction),
+    AbortTransaction(AbortTransaction),
 }
 
 impl BytesSerializable for ServerCommand {
     fn to_bytes(&self) -> Bytes {
         match self {
             ServerCommand::Ping(payload) => as_bytes(payload),
+            ServerCommand::Handshake(payload) => as_bytes(payload),
             ServerCommand::GetStats(payload) => as_bytes(payload),
             ServerCommand::GetMe(payload) => as_bytes(payload),
             ServerCommand::GetClient(payload) => as_bytes(payload),
@@ -137,6 +170,7 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::UpdatePermissions(payload) => as_bytes(payload),
             ServerCommand::ChangePassword(payload) => as_bytes(payload),
             ServerCommand::LoginUser(payload) => as_bytes(payload),
+            ServerCommand::LoginWithOidcToken(payload) => as_bytes(payload),
             ServerCommand::LogoutUser(payload) => as_bytes(payload),
             ServerCommand::GetPersonalAccessTokens(payload) => as_bytes(payload),
             ServerCommand::CreatePersonalAccessToken(payload) => as_bytes(payload),
@@ -146,6 +180,7 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::PollMessages(payload) => as_bytes(payload),
             ServerCommand::StoreConsumerOffset(payload) => as_bytes(payload),
             ServerCommand::DeleteConsumerOffset(payload) => as_bytes(payload),
+            ServerCommand::GetConsumerLag(payload) => as_bytes(payload),
             ServerCommand::GetConsumerOffset(payload) => as_bytes(payload),
             ServerCommand::GetStream(payload) => as_bytes(payload),
             ServerCommand::GetStreams(payload) => as_bytes(payload),
@@ -161,6 +196,8 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::PurgeTopic(payload) => as_bytes(payload),
             ServerCommand::CreatePartitions(payload) => as_bytes(payload),
             ServerCommand::DeletePartitions(payload) => as_bytes(payload),
+            ServerCommand::DeleteSegments(payload) => as_bytes(payload),
+            ServerCommand::RestoreSegments(payload) => as_bytes(payload),
             ServerCommand::GetConsumerGroup(payload) => as_bytes(payload),
             ServerCommand::GetConsumerGroups(payload) => as_bytes(payload),
             ServerCommand::CreateConsumerGroup(payload) => as_bytes(payload),
@@ -168,7 +205,18 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::JoinConsumerGroup(payload) => as_bytes(payload),
             ServerCommand::LeaveConsumerGroup(payload) => as_bytes(payload),
             ServerCommand::FlushUnsavedBuffer(payload) => as_bytes(payload),
+            ServerCommand::NackMessage(payload) => as_bytes(payload),
+            ServerCommand::InitProducer(payload) => as_bytes(payload),
+            ServerCommand::PollAssignedMessages(payload) => as_bytes(payload),
+            ServerCommand::SubscribeMessages(payload) => as_bytes(payload),
             ServerCommand::GetSnapshotFile(payload) => as_bytes(payload),
+            ServerCommand::FetchReplicaMessages(payload) => as_bytes(payload),
+            ServerCommand::GetClusterMetadata(payload) => as_bytes(payload),
+            ServerCommand::RequestVote(payload) => as_bytes(payload),
+            ServerCommand::AppendEntries(payload) => as_bytes(payload),
+            ServerCommand::BeginTransaction(payload) => as_bytes(payload),
+            ServerCommand::CommitTransaction(payload) => as_bytes(payload),
+            ServerCommand::AbortTransaction(payload) => as_bytes(payload),
         }
     }
 
@@ -182,6 +230,7 @@ impl BytesSerializable for ServerCommand {
         let payload = bytes.slice(4..);
         match code {
             PING_CODE => Ok(ServerCommand::Ping(Ping::from_bytes(payload)?)),
+            HANDSHAKE_CODE => Ok(ServerCommand::Handshake(Handshake::from_bytes(payload)?)),
             GET_STATS_CODE => Ok(ServerCommand::GetStats(GetStats::from_bytes(payload)?)),
             GET_ME_CODE => Ok(ServerCommand::GetMe(GetMe::from_bytes(payload)?)),
             GET_CLIENT_CODE => Ok(ServerCommand::GetClient(GetClient::from_bytes(payload)?)),
@@ -198,6 +247,9 @@ impl BytesSerializable for ServerCommand {
                 payload,
             )?)),
             LOGIN_USER_CODE => Ok(ServerCommand::LoginUser(LoginUser::from_bytes(payload)?)),
+            LOGIN_WITH_OIDC_TOKEN_CODE => Ok(ServerCommand::LoginWithOidcToken(
+                LoginWithOidcToken::from_bytes(payload)?,
+            )),
             LOGOUT_USER_CODE => Ok(ServerCommand::LogoutUser(LogoutUser::from_bytes(payload)?)),
             GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(ServerCommand::GetPersonalAccessTokens(
                 GetPersonalAccessTokens::from_bytes(payload)?,
@@ -222,6 +274,21 @@ impl BytesSerializable for ServerCommand {
             FLUSH_UNSAVED_BUFFER_CODE => Ok(ServerCommand::FlushUnsavedBuffer(
                 FlushUnsavedBuffer::from_bytes(payload)?,
             )),
+            NACK_MESSAGE_CODE => Ok(ServerCommand::NackMessage(NackMessage::from_bytes(
+                payload,
+            )?)),
+            INIT_PRODUCER_CODE => Ok(ServerCommand::InitProducer(InitProducer::from_bytes(
+                payload,
+            )?)),
+            SEND_IDEMPOTENT_MESSAGES_CODE => Ok(ServerCommand::SendMessages(
+                SendMessages::from_idempotent_bytes(payload)?,
+            )),
+            POLL_ASSIGNED_MESSAGES_CODE => Ok(ServerCommand::PollAssignedMessages(
+                PollAssignedMessages::from_bytes(payload)?,
+            )),
+            SUBSCRIBE_MESSAGES_CODE => Ok(ServerCommand::SubscribeMessages(
+                SubscribeMessages::from_bytes(payload)?,
+            )),
             STORE_CONSUMER_OFFSET_CODE => Ok(ServerCommand::StoreConsumerOffset(
                 StoreConsumerOffset::from_bytes(payload)?,
             )),
@@ -231,6 +298,9 @@ impl BytesSerializable for ServerCommand {
             GET_CONSUMER_OFFSET_CODE => Ok(ServerCommand::GetConsumerOffset(
                 GetConsumerOffset::from_bytes(payload)?,
             )),
+            GET_CONSUMER_LAG_CODE => Ok(ServerCommand::GetConsumerLag(GetConsumerLag::from_bytes(
+                payload,
+            )?)),
             GET_STREAM_CODE => Ok(ServerCommand::GetStream(GetStream::from_bytes(payload)?)),
             GET_STREAMS_CODE => Ok(ServerCommand::GetStreams(GetStreams::from_bytes(payload)?)),
             CREATE_STREAM_CODE => Ok(ServerCommand::CreateStream(CreateStream::from_bytes(
@@ -263,6 +333,12 @@ impl BytesSerializable for ServerCommand {
             DELETE_PARTITIONS_CODE => Ok(ServerCommand::DeletePartitions(
                 DeletePartitions::from_bytes(payload)?,
             )),
+            DELETE_SEGMENTS_CODE => Ok(ServerCommand::DeleteSegments(DeleteSegments::from_bytes(
+                payload,
+            )?)),
+            RESTORE_SEGMENTS_CODE => Ok(ServerCommand::RestoreSegments(
+                RestoreSegments::from_bytes(payload)?,
+            )),
             GET_CONSUMER_GROUP_CODE => Ok(ServerCommand::GetConsumerGroup(
                 GetConsumerGroup::from_bytes(payload)?,
             )),
@@ -284,6 +360,27 @@ impl BytesSerializable for ServerCommand {
             GET_SNAPSHOT_FILE_CODE => Ok(ServerCommand::GetSnapshotFile(GetSnapshot::from_bytes(
                 payload,
             )?)),
+            FETCH_REPLICA_MESSAGES_CODE => Ok(ServerCommand::FetchReplicaMessages(
+                FetchReplicaMessages::from_bytes(payload)?,
+            )),
+            GET_CLUSTER_METADATA_CODE => Ok(ServerCommand::GetClusterMetadata(
+                GetClusterMetadata::from_bytes(payload)?,
+            )),
+            REQUEST_VOTE_CODE => Ok(ServerCommand::RequestVote(RequestVote::from_bytes(
+                payload,
+            )?)),
+            APPEND_ENTRIES_CODE => Ok(ServerCommand::AppendEntries(AppendEntries::from_bytes(
+                payload,
+            )?)),
+            BEGIN_TRANSACTION_CODE => Ok(ServerCommand::BeginTransaction(
+                BeginTransaction::from_bytes(payload)?,
+            )),
+            COMMIT_TRANSACTION_CODE => Ok(ServerCommand::CommitTransaction(
+                CommitTransaction::from_bytes(payload)?,
+            )),
+            ABORT_TRANSACTION_CODE => Ok(ServerCommand::AbortTransaction(
+                AbortTransaction::from_bytes(payload)?,
+            )),
             _ => {
                 error!("Invalid server command: {code}");
                 Err(IggyError::InvalidCommand)
@@ -292,6 +389,102 @@ impl BytesSerializable for ServerCommand {
     }
 }
 
+impl ServerCommand {
+    /// Returns whether the command changes the metadata stored in the state log,
+    /// which is only allowed on the leader node when the cluster is enabled.
+    pub fn changes_metadata(&self) -> bool {
+        matches!(
+            self,
+            ServerCommand::CreateUser(_)
+                | ServerCommand::DeleteUser(_)
+                | ServerCommand::UpdateUser(_)
+                | ServerCommand::UpdatePermissions(_)
+                | ServerCommand::ChangePassword(_)
+                | ServerCommand::CreatePersonalAccessToken(_)
+                | ServerCommand::DeletePersonalAccessToken(_)
+                | ServerCommand::CreateStream(_)
+                | ServerCommand::DeleteStream(_)
+                | ServerCommand::UpdateStream(_)
+                | ServerCommand::PurgeStream(_)
+                | ServerCommand::CreateTopic(_)
+                | ServerCommand::DeleteTopic(_)
+                | ServerCommand::UpdateTopic(_)
+                | ServerCommand::PurgeTopic(_)
+                | ServerCommand::CreatePartitions(_)
+                | ServerCommand::DeletePartitions(_)
+                | ServerCommand::CreateConsumerGroup(_)
+                | ServerCommand::DeleteConsumerGroup(_)
+        )
+    }
+
+    /// Returns the name of the command, e.g. to be used as a label of the metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ServerCommand::Ping(_) => PING,
+            ServerCommand::Handshake(_) => HANDSHAKE,
+            ServerCommand::GetStats(_) => GET_STATS,
+            ServerCommand::GetMe(_) => GET_ME,
+            ServerCommand::GetClient(_) => GET_CLIENT,
+            ServerCommand::GetClients(_) => GET_CLIENTS,
+            ServerCommand::GetUser(_) => GET_USER,
+            ServerCommand::GetUsers(_) => GET_USERS,
+            ServerCommand::CreateUser(_) => CREATE_USER,
+            ServerCommand::DeleteUser(_) => DELETE_USER,
+            ServerCommand::UpdateUser(_) => UPDATE_USER,
+            ServerCommand::UpdatePermissions(_) => UPDATE_PERMISSIONS,
+            ServerCommand::ChangePassword(_) => CHANGE_PASSWORD,
+            ServerCommand::LoginUser(_) => LOGIN_USER,
+            ServerCommand::LoginWithOidcToken(_) => LOGIN_WITH_OIDC_TOKEN,
+            ServerCommand::LogoutUser(_) => LOGOUT_USER,
+            ServerCommand::GetPersonalAccessTokens(_) => GET_PERSONAL_ACCESS_TOKENS,
+            ServerCommand::CreatePersonalAccessToken(_) => CREATE_PERSONAL_ACCESS_TOKEN,
+            ServerCommand::DeletePersonalAccessToken(_) => DELETE_PERSONAL_ACCESS_TOKEN,
+            ServerCommand::LoginWithPersonalAccessToken(_) => LOGIN_WITH_PERSONAL_ACCESS_TOKEN,
+            ServerCommand::GetStream(_) => GET_STREAM,
+            ServerCommand::GetStreams(_) => GET_STREAMS,
+            ServerCommand::CreateStream(_) => CREATE_STREAM,
+            ServerCommand::DeleteStream(_) => DELETE_STREAM,
+            ServerCommand::UpdateStream(_) => UPDATE_STREAM,
+            ServerCommand::PurgeStream(_) => PURGE_STREAM,
+            ServerCommand::GetTopic(_) => GET_TOPIC,
+            ServerCommand::GetTopics(_) => GET_TOPICS,
+            ServerCommand::CreateTopic(_) => CREATE_TOPIC,
+            ServerCommand::DeleteTopic(_) => DELETE_TOPIC,
+            ServerCommand::UpdateTopic(_) => UPDATE_TOPIC,
+            ServerCommand::PurgeTopic(_) => PURGE_TOPIC,
+            ServerCommand::CreatePartitions(_) => CREATE_PARTITIONS,
+            ServerCommand::DeletePartitions(_) => DELETE_PARTITIONS,
+            ServerCommand::DeleteSegments(_) => DELETE_SEGMENTS,
+            ServerCommand::RestoreSegments(_) => RESTORE_SEGMENTS,
+            ServerCommand::PollMessages(_) => POLL_MESSAGES,
+            ServerCommand::SendMessages(_) => SEND_MESSAGES,
+            ServerCommand::StoreConsumerOffset(_) => STORE_CONSUMER_OFFSET,
+            ServerCommand::DeleteConsumerOffset(_) => DELETE_CONSUMER_OFFSET,
+            ServerCommand::GetConsumerOffset(_) => GET_CONSUMER_OFFSET,
+            ServerCommand::GetConsumerLag(_) => GET_CONSUMER_LAG,
+            ServerCommand::GetConsumerGroup(_) => GET_CONSUMER_GROUP,
+            ServerCommand::GetConsumerGroups(_) => GET_CONSUMER_GROUPS,
+            ServerCommand::CreateConsumerGroup(_) => CREATE_CONSUMER_GROUP,
+            ServerCommand::DeleteConsumerGroup(_) => DELETE_CONSUMER_GROUP,
+            ServerCommand::JoinConsumerGroup(_) => JOIN_CONSUMER_GROUP,
+            ServerCommand::LeaveConsumerGroup(_) => LEAVE_CONSUMER_GROUP,
+            ServerCommand::FlushUnsavedBuffer(_) => FLUSH_UNSAVED_BUFFER,
+            ServerCommand::NackMessage(_) => NACK_MESSAGE,
+            ServerCommand::InitProducer(_) => INIT_PRODUCER,
+            ServerCommand::PollAssignedMessages(_) => POLL_ASSIGNED_MESSAGES,
+            ServerCommand::SubscribeMessages(_) => SUBSCRIBE_MESSAGES,
+            ServerCommand::GetSnapshotFile(_) => GET_SNAPSHOT_FILE,
+            ServerCommand::FetchReplicaMessages(_) => FETCH_REPLICA_MESSAGES,
+            ServerCommand::GetClusterMetadata(_) => GET_CLUSTER_METADATA,
+            ServerCommand::RequestVote(_) => REQUEST_VOTE,
+            ServerCommand::AppendEntries(_) => APPEND_ENTRIES,
+            ServerCommand::BeginTransaction(_) => BEGIN_TRANSACTION,
+            ServerCommand::CommitTransaction(_) => COMMIT_TRANSACTION,
+            ServerCommand::AbortTransaction(_) => ABORT_TRANSACTION,
+        }
+    }
+}
+
 fn as_bytes<T: Command>(command: &T) -> Bytes {
     let payload = command.to_bytes();
     let mut bytes = BytesMut::with_capacity(4 + payload.len());
@@ -304,6 +497,7 @@ impl Validatable<IggyError> for ServerCommand {
     fn validate(&self) -> Result<(), IggyError> {
         match self {
             ServerCommand::Ping(command) => command.validate(),
+            ServerCommand::Handshake(command) => command.validate(),
             ServerCommand::GetStats(command) => command.validate(),
             ServerCommand::GetMe(command) => command.validate(),
             ServerCommand::GetClient(command) => command.validate(),
@@ -316,6 +510,7 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::UpdatePermissions(command) => command.validate(),
             ServerCommand::ChangePassword(command) => command.validate(),
             ServerCommand::LoginUser(command) => command.validate(),
+            ServerCommand::LoginWithOidcToken(command) => command.validate(),
             ServerCommand::LogoutUser(command) => command.validate(),
             ServerCommand::GetPersonalAccessTokens(command) => command.validate(),
             ServerCommand::CreatePersonalAccessToken(command) => command.validate(),
@@ -325,6 +520,7 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::PollMessages(command) => command.validate(),
             ServerCommand::StoreConsumerOffset(command) => command.validate(),
             ServerCommand::DeleteConsumerOffset(command) => command.validate(),
+            ServerCommand::GetConsumerLag(command) => command.validate(),
             ServerCommand::GetConsumerOffset(command) => command.validate(),
             ServerCommand::GetStream(command) => command.validate(),
             ServerCommand::GetStreams(command) => command.validate(),
@@ -340,6 +536,8 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::PurgeTopic(command) => command.validate(),
             ServerCommand::CreatePartitions(command) => command.validate(),
             ServerCommand::DeletePartitions(command) => command.validate(),
+            ServerCommand::DeleteSegments(command) => command.validate(),
+            ServerCommand::RestoreSegments(command) => command.validate(),
             ServerCommand::GetConsumerGroup(command) => command.validate(),
             ServerCommand::GetConsumerGroups(command) => command.validate(),
             ServerCommand::CreateConsumerGroup(command) => command.validate(),
@@ -347,7 +545,18 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::JoinConsumerGroup(command) => command.validate(),
             ServerCommand::LeaveConsumerGroup(command) => command.validate(),
             ServerCommand::FlushUnsavedBuffer(command) => command.validate(),
+            ServerCommand::NackMessage(command) => command.validate(),
+            ServerCommand::InitProducer(command) => command.validate(),
+            ServerCommand::PollAssignedMessages(command) => command.validate(),
+            ServerCommand::SubscribeMessages(command) => command.validate(),
             ServerCommand::GetSnapshotFile(command) => command.validate(),
+            ServerCommand::FetchReplicaMessages(command) => command.validate(),
+            ServerCommand::GetClusterMetadata(command) => command.validate(),
+            ServerCommand::RequestVote(command) => command.validate(),
+            ServerCommand::AppendEntries(command) => command.validate(),
+            ServerCommand::BeginTransaction(command) => command.validate(),
+            ServerCommand::CommitTransaction(command) => command.validate(),
+            ServerCommand::AbortTransaction(command) => command.validate(),
         }
     }
 }
@@ -356,6 +565,7 @@ impl Display for ServerCommand {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ServerCommand::Ping(_) => write!(formatter, "{PING}"),
+            ServerCommand::Handshake(payload) => write!(formatter, "{HANDSHAKE}|{payload}"),
             ServerCommand::GetStats(_) => write!(formatter, "{GET_STATS}"),
             ServerCommand::GetMe(_) => write!(formatter, "{GET_ME}"),
             ServerCommand::GetClient(payload) => write!(formatter, "{GET_CLIENT}|{payload}"),
@@ -372,6 +582,9 @@ impl Display for ServerCommand {
                 write!(formatter, "{CHANGE_PASSWORD}|{payload}")
             }
             ServerCommand::LoginUser(payload) => write!(formatter, "{LOGIN_USER}|{payload}"),
+            ServerCommand::LoginWithOidcToken(payload) => {
+                write!(formatter, "{LOGIN_WITH_OIDC_TOKEN}|{payload}")
+            }
             ServerCommand::LogoutUser(_) => write!(formatter, "{LOGOUT_USER}"),
             ServerCommand::GetPersonalAccessTokens(_) => {
                 write!(formatter, "{GET_PERSONAL_ACCESS_TOKENS}")
@@ -403,6 +616,12 @@ impl Display for ServerCommand {
             ServerCommand::DeletePartitions(payload) => {
                 write!(formatter, "{DELETE_PARTITIONS}|{payload}")
             }
+            ServerCommand::DeleteSegments(payload) => {
+                write!(formatter, "{DELETE_SEGMENTS}|{payload}")
+            }
+            ServerCommand::RestoreSegments(payload) => {
+                write!(formatter, "{RESTORE_SEGMENTS}|{payload}")
+            }
             ServerCommand::PollMessages(payload) => write!(formatter, "{POLL_MESSAGES}|{payload}"),
             ServerCommand::SendMessages(payload) => write!(formatter, "{SEND_MESSAGES}|{payload}"),
             ServerCommand::StoreConsumerOffset(payload) => {
@@ -414,6 +633,9 @@ impl Display for ServerCommand {
             ServerCommand::GetConsumerOffset(payload) => {
                 write!(formatter, "{GET_CONSUMER_OFFSET}|{payload}")
             }
+            ServerCommand::GetConsumerLag(payload) => {
+                write!(formatter, "{GET_CONSUMER_LAG}|{payload}")
+            }
             ServerCommand::GetConsumerGroup(payload) => {
                 write!(formatter, "{GET_CONSUMER_GROUP}|{payload}")
             }
@@ -435,9 +657,34 @@ impl Display for ServerCommand {
             ServerCommand::FlushUnsavedBuffer(payload) => {
                 write!(formatter, "{FLUSH_UNSAVED_BUFFER}|{payload}")
             }
+            ServerCommand::NackMessage(payload) => {
+                write!(formatter, "{NACK_MESSAGE}|{payload}")
+            }
+            ServerCommand::InitProducer(_) => write!(formatter, "{INIT_PRODUCER}"),
+            ServerCommand::PollAssignedMessages(payload) => {
+                write!(formatter, "{POLL_ASSIGNED_MESSAGES}|{payload}")
+            }
+            ServerCommand::SubscribeMessages(payload) => {
+                write!(formatter, "{SUBSCRIBE_MESSAGES}|{payload}")
+            }
             ServerCommand::GetSnapshotFile(payload) => {
                 write!(formatter, "{GET_SNAPSHOT_FILE}|{payload}")
             }
+            ServerCommand::FetchReplicaMessages(payload) => {
+                write!(formatter, "{FETCH_REPLICA_MESSAGES}|{payload}")
+            }
+            ServerCommand::GetClusterMetadata(_) => write!(formatter, "{GET_CLUSTER_METADATA}"),
+            ServerCommand::RequestVote(payload) => write!(formatter, "{REQUEST_VOTE}|{payload}"),
+            ServerCommand::AppendEntries(payload) => {
+                write!(formatter, "{APPEND_ENTRIES}|{payload}")
+            }
+            ServerCommand::BeginTransaction(_) => write!(formatter, "{BEGIN_TRANSACTION}"),
+            ServerCommand::CommitTransaction(payload) => {
+                write!(formatter, "{COMMIT_TRANSACTION}|{payload}")
+            }
+            ServerCommand::AbortTransaction(payload) => {
+                write!(formatter, "{ABORT_TRANSACTION}|{payload}")
+            }
         }
     }
 }
@@ -445,6 +692,7 @@ impl Display for ServerCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use iggy::messages::send_messages::{Partitioning, ProducerSequence};
 
     #[test]
     fn should_be_serialized_as_bytes_and_deserialized_from_bytes() {
@@ -453,6 +701,11 @@ mod tests {
             PING_CODE,
             &Ping::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::Handshake(Handshake::default()),
+            HANDSHAKE_CODE,
+            &Handshake::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetStats(GetStats::default()),
             GET_STATS_CODE,
@@ -513,6 +766,11 @@ mod tests {
             LOGIN_USER_CODE,
             &LoginUser::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::LoginWithOidcToken(LoginWithOidcToken::default()),
+            LOGIN_WITH_OIDC_TOKEN_CODE,
+            &LoginWithOidcToken::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::LogoutUser(LogoutUser::default()),
             LOGOUT_USER_CODE,
@@ -558,6 +816,11 @@ mod tests {
             GET_CONSUMER_OFFSET_CODE,
             &GetConsumerOffset::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetConsumerLag(GetConsumerLag::default()),
+            GET_CONSUMER_LAG_CODE,
+            &GetConsumerLag::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetStream(GetStream::default()),
             GET_STREAM_CODE,
@@ -628,6 +891,16 @@ mod tests {
             DELETE_PARTITIONS_CODE,
             &DeletePartitions::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::DeleteSegments(DeleteSegments::default()),
+            DELETE_SEGMENTS_CODE,
+            &DeleteSegments::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::RestoreSegments(RestoreSegments::default()),
+            RESTORE_SEGMENTS_CODE,
+            &RestoreSegments::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetConsumerGroup(GetConsumerGroup::default()),
             GET_CONSUMER_GROUP_CODE,
@@ -663,6 +936,74 @@ mod tests {
             FLUSH_UNSAVED_BUFFER_CODE,
             &FlushUnsavedBuffer::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::NackMessage(NackMessage::default()),
+            NACK_MESSAGE_CODE,
+            &NackMessage::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::InitProducer(InitProducer::default()),
+            INIT_PRODUCER_CODE,
+            &InitProducer::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SubscribeMessages(SubscribeMessages::default()),
+            SUBSCRIBE_MESSAGES_CODE,
+            &SubscribeMessages::default(),
+        );
+        let idempotent_send_messages = || SendMessages {
+            partitioning: Partitioning::partition_id(1),
+            producer_sequence: Some(ProducerSequence {
+                producer_id: 1,
+                sequence: 2,
+            }),
+            ..SendMessages::default()
+        };
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::SendMessages(idempotent_send_messages()),
+            SEND_IDEMPOTENT_MESSAGES_CODE,
+            &idempotent_send_messages(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::PollAssignedMessages(PollAssignedMessages::default()),
+            POLL_ASSIGNED_MESSAGES_CODE,
+            &PollAssignedMessages::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::FetchReplicaMessages(FetchReplicaMessages::default()),
+            FETCH_REPLICA_MESSAGES_CODE,
+            &FetchReplicaMessages::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetClusterMetadata(GetClusterMetadata::default()),
+            GET_CLUSTER_METADATA_CODE,
+            &GetClusterMetadata::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::RequestVote(RequestVote::default()),
+            REQUEST_VOTE_CODE,
+            &RequestVote::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::AppendEntries(AppendEntries::default()),
+            APPEND_ENTRIES_CODE,
+            &AppendEntries::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::BeginTransaction(BeginTransaction::default()),
+            BEGIN_TRANSACTION_CODE,
+            &BeginTransaction::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::CommitTransaction(CommitTransaction::default()),
+            COMMIT_TRANSACTION_CODE,
+            &CommitTransaction::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::AbortTransaction(AbortTransaction::default()),
+            ABORT_TRANSACTION_CODE,
+            &AbortTransaction::default(),
+        );
     }
 
     fn assert_serialized_as_bytes_and_deserialized_from_bytes(
@@ -18,7 +18,10 @@
 
 use crate::streaming::utils::file;
 use crate::{
-    server_error::CompatError, streaming::batching::message_batch::RETAINED_BATCH_HEADER_LEN,
+    server_error::CompatError,
+    streaming::batching::message_batch::{
+        RETAINED_BATCH_COMPRESSED_FLAG, RETAINED_BATCH_HEADER_LEN,
+    },
 };
 use std::io::SeekFrom;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
@@ -50,7 +53,8 @@ impl IndexRebuilder {
         reader: &mut BufReader<tokio::fs::File>,
     ) -> Result<BatchHeader, std::io::Error> {
         let base_offset = reader.read_u64_le().await?;
-        let length = reader.read_u32_le().await?;
+        // The compression flag isn't a part of the payload length
+        let length = reader.read_u32_le().await? & !RETAINED_BATCH_COMPRESSED_FLAG;
         let last_offset_delta = reader.read_u32_le().await?;
         let max_timestamp = reader.read_u64_le().await?;
 
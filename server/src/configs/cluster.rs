@@ -0,0 +1,112 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::system::ReplicaRole;
+use iggy::utils::duration::IggyDuration;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterConfig {
+    pub enabled: bool,
+    pub id: u32,
+    pub name: String,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub nodes: Vec<ClusterNodeConfig>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub heartbeat_interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub election_timeout: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub commit_timeout: IggyDuration,
+    pub identity: ClusterIdentityConfig,
+}
+
+/// Identity of the node shared by the cluster (metadata state log) and the partition replication.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClusterIdentityConfig {
+    pub role: ReplicaRole,
+    pub username: String,
+    pub password: String,
+}
+
+/// Cluster node in the `<id>@<address>` format, e.g. `1@127.0.0.1:8090`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterNodeConfig {
+    pub id: u32,
+    pub address: String,
+}
+
+impl ClusterConfig {
+    pub fn get_peers(&self) -> Vec<&ClusterNodeConfig> {
+        self.nodes
+            .iter()
+            .filter(|node| node.id != self.id)
+            .collect()
+    }
+}
+
+impl FromStr for ClusterNodeConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((id, address)) = s.split_once('@') else {
+            return Err(format!(
+                "Invalid cluster node: {s}, expected format: <id>@<address>"
+            ));
+        };
+
+        let id = id
+            .trim()
+            .parse::<u32>()
+            .map_err(|error| format!("Invalid cluster node ID: {id}, error: {error}"))?;
+        Ok(ClusterNodeConfig {
+            id,
+            address: address.trim().to_string(),
+        })
+    }
+}
+
+impl Display for ClusterNodeConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.id, self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cluster_node_should_be_parsed_from_id_and_address() {
+        let node = ClusterNodeConfig::from_str("2@127.0.0.1:8091").unwrap();
+        assert_eq!(node.id, 2);
+        assert_eq!(node.address, "127.0.0.1:8091");
+        assert_eq!(node.to_string(), "2@127.0.0.1:8091");
+    }
+
+    #[test]
+    fn cluster_node_without_id_should_not_be_parsed() {
+        assert!(ClusterNodeConfig::from_str("127.0.0.1:8091").is_err());
+        assert!(ClusterNodeConfig::from_str("node@127.0.0.1:8091").is_err());
+    }
+}
@@ -19,20 +19,23 @@
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
 
+use crate::configs::cluster::{ClusterConfig, ClusterIdentityConfig};
 use crate::configs::http::{
     HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig,
 };
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, HeartbeatConfig, MessageSaverConfig,
-    MessagesMaintenanceConfig, PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig,
-    ServerConfig, StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig,
-    TelemetryTracesConfig,
+    ArchiverConfig, BackupMaintenanceConfig, ClientCertificateConfig, DataMaintenanceConfig,
+    DiskArchiverConfig, HeartbeatConfig, MessageSaverConfig, MessagesMaintenanceConfig,
+    PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig, ServerConfig,
+    StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig, TelemetryTracesConfig,
 };
 use crate::configs::system::{
-    BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig, EncryptionConfig,
-    LoggingConfig, MessageDeduplicationConfig, PartitionConfig, RecoveryConfig, RuntimeConfig,
-    SegmentConfig, StateConfig, StreamConfig, SystemConfig, TopicConfig,
+    BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig, DeadLetterConfig,
+    EncryptionConfig, LoggingConfig, MessageDeduplicationConfig, PartitionConfig, QuotasConfig,
+    RecoveryConfig, ReplicationConfig, RuntimeConfig, SegmentConfig, StateConfig, StreamConfig,
+    SystemConfig, TopicConfig, WireCompressionConfig,
 };
 use crate::configs::tcp::{TcpConfig, TcpTlsConfig};
 use std::sync::Arc;
@@ -57,6 +60,8 @@ impl Default for ServerConfig {
             tcp: TcpConfig::default(),
             http: HttpConfig::default(),
             telemetry: TelemetryConfig::default(),
+            cluster: ClusterConfig::default(),
+            oidc: OidcConfig::default(),
         }
     }
 }
@@ -71,7 +76,16 @@ impl Default for ArchiverConfig {
                 .kind
                 .parse()
                 .unwrap(),
-            disk: None,
+            // The disk archiver is configured by default, so its path can be overridden with the environment variable.
+            disk: Some(DiskArchiverConfig {
+                path: SERVER_CONFIG
+                    .data_maintenance
+                    .archiver
+                    .disk
+                    .path
+                    .parse()
+                    .unwrap(),
+            }),
             s3: None,
         }
     }
@@ -92,6 +106,21 @@ impl Default for MessagesMaintenanceConfig {
     }
 }
 
+impl Default for BackupMaintenanceConfig {
+    fn default() -> BackupMaintenanceConfig {
+        BackupMaintenanceConfig {
+            enabled: SERVER_CONFIG.data_maintenance.backup.enabled,
+            interval: SERVER_CONFIG
+                .data_maintenance
+                .backup
+                .interval
+                .parse()
+                .unwrap(),
+            retained_backups: SERVER_CONFIG.data_maintenance.backup.retained_backups as u32,
+        }
+    }
+}
+
 impl Default for StateMaintenanceConfig {
     fn default() -> StateMaintenanceConfig {
         StateMaintenanceConfig {
@@ -124,6 +153,21 @@ impl Default for QuicConfig {
             keep_alive_interval: SERVER_CONFIG.quic.keep_alive_interval.parse().unwrap(),
             max_idle_timeout: SERVER_CONFIG.quic.max_idle_timeout.parse().unwrap(),
             certificate: QuicCertificateConfig::default(),
+            client_certificate: ClientCertificateConfig {
+                enabled: SERVER_CONFIG.quic.client_certificate.enabled,
+                ca_file: SERVER_CONFIG
+                    .quic
+                    .client_certificate
+                    .ca_file
+                    .parse()
+                    .unwrap(),
+                identity: SERVER_CONFIG
+                    .quic
+                    .client_certificate
+                    .identity
+                    .parse()
+                    .unwrap(),
+            },
         }
     }
 }
@@ -154,8 +198,27 @@ impl Default for TcpTlsConfig {
     fn default() -> TcpTlsConfig {
         TcpTlsConfig {
             enabled: SERVER_CONFIG.tcp.tls.enabled,
+            cert_file: SERVER_CONFIG.tcp.tls.cert_file.parse().unwrap(),
+            key_file: SERVER_CONFIG.tcp.tls.key_file.parse().unwrap(),
             certificate: SERVER_CONFIG.tcp.tls.certificate.parse().unwrap(),
             password: SERVER_CONFIG.tcp.tls.password.parse().unwrap(),
+            client_certificate: ClientCertificateConfig {
+                enabled: SERVER_CONFIG.tcp.tls.client_certificate.enabled,
+                ca_file: SERVER_CONFIG
+                    .tcp
+                    .tls
+                    .client_certificate
+                    .ca_file
+                    .parse()
+                    .unwrap(),
+                identity: SERVER_CONFIG
+                    .tcp
+                    .tls
+                    .client_certificate
+                    .identity
+                    .parse()
+                    .unwrap(),
+            },
         }
     }
 }
@@ -260,6 +323,7 @@ impl Default for HttpMetricsConfig {
         HttpMetricsConfig {
             enabled: SERVER_CONFIG.http.metrics.enabled,
             endpoint: SERVER_CONFIG.http.metrics.endpoint.parse().unwrap(),
+            max_labeled_partitions: SERVER_CONFIG.http.metrics.max_labeled_partitions as u32,
         }
     }
 }
@@ -270,6 +334,23 @@ impl Default for HttpTlsConfig {
             enabled: SERVER_CONFIG.http.tls.enabled,
             cert_file: SERVER_CONFIG.http.tls.cert_file.parse().unwrap(),
             key_file: SERVER_CONFIG.http.tls.key_file.parse().unwrap(),
+            client_certificate: ClientCertificateConfig {
+                enabled: SERVER_CONFIG.http.tls.client_certificate.enabled,
+                ca_file: SERVER_CONFIG
+                    .http
+                    .tls
+                    .client_certificate
+                    .ca_file
+                    .parse()
+                    .unwrap(),
+                identity: SERVER_CONFIG
+                    .http
+                    .tls
+                    .client_certificate
+                    .identity
+                    .parse()
+                    .unwrap(),
+            },
         }
     }
 }
@@ -322,7 +403,11 @@ impl Default for SystemConfig {
             segment: SegmentConfig::default(),
             state: StateConfig::default(),
             compression: CompressionConfig::default(),
+            wire_compression: WireCompressionConfig::default(),
             message_deduplication: MessageDeduplicationConfig::default(),
+            dead_letter: DeadLetterConfig::default(),
+            replication: ReplicationConfig::default(),
+            quotas: QuotasConfig::default(),
             recovery: RecoveryConfig::default(),
         }
     }
@@ -364,6 +449,12 @@ impl Default for RuntimeConfig {
     fn default() -> RuntimeConfig {
         RuntimeConfig {
             path: SERVER_CONFIG.system.runtime.path.parse().unwrap(),
+            archive_cache_size: SERVER_CONFIG
+                .system
+                .runtime
+                .archive_cache_size
+                .parse()
+                .unwrap(),
         }
     }
 }
@@ -421,6 +512,7 @@ impl Default for StreamConfig {
     fn default() -> StreamConfig {
         StreamConfig {
             path: SERVER_CONFIG.system.stream.path.parse().unwrap(),
+            delete_oldest_segments: SERVER_CONFIG.system.stream.delete_oldest_segments,
         }
     }
 }
@@ -490,6 +582,72 @@ impl Default for MessageDeduplicationConfig {
     }
 }
 
+impl Default for DeadLetterConfig {
+    fn default() -> DeadLetterConfig {
+        DeadLetterConfig {
+            enabled: SERVER_CONFIG.system.dead_letter.enabled,
+            max_failed_deliveries: SERVER_CONFIG.system.dead_letter.max_failed_deliveries as u32,
+            topic_suffix: SERVER_CONFIG
+                .system
+                .dead_letter
+                .topic_suffix
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> ReplicationConfig {
+        ReplicationConfig {
+            enabled: SERVER_CONFIG.system.replication.enabled,
+            node_id: SERVER_CONFIG.system.replication.node_id as u32,
+            leader_address: SERVER_CONFIG
+                .system
+                .replication
+                .leader_address
+                .parse()
+                .unwrap(),
+            fetch_interval: SERVER_CONFIG
+                .system
+                .replication
+                .fetch_interval
+                .parse()
+                .unwrap(),
+            max_fetch_messages: SERVER_CONFIG.system.replication.max_fetch_messages as u32,
+            ack_timeout: SERVER_CONFIG
+                .system
+                .replication
+                .ack_timeout
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for WireCompressionConfig {
+    fn default() -> WireCompressionConfig {
+        WireCompressionConfig {
+            enabled: SERVER_CONFIG.system.wire_compression.enabled,
+            threshold: SERVER_CONFIG
+                .system
+                .wire_compression
+                .threshold
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for QuotasConfig {
+    fn default() -> QuotasConfig {
+        QuotasConfig {
+            client_requests_per_second: SERVER_CONFIG.system.quotas.client_requests_per_second
+                as u32,
+        }
+    }
+}
+
 impl Default for RecoveryConfig {
     fn default() -> RecoveryConfig {
         RecoveryConfig {
@@ -526,3 +684,64 @@ impl Default for TelemetryTracesConfig {
         }
     }
 }
+
+impl Default for ClusterConfig {
+    fn default() -> ClusterConfig {
+        ClusterConfig {
+            enabled: SERVER_CONFIG.cluster.enabled,
+            id: SERVER_CONFIG.cluster.id as u32,
+            name: SERVER_CONFIG.cluster.name.parse().unwrap(),
+            nodes: SERVER_CONFIG
+                .cluster
+                .nodes
+                .iter()
+                .map(|s| s.parse().unwrap())
+                .collect(),
+            heartbeat_interval: SERVER_CONFIG.cluster.heartbeat_interval.parse().unwrap(),
+            election_timeout: SERVER_CONFIG.cluster.election_timeout.parse().unwrap(),
+            commit_timeout: SERVER_CONFIG.cluster.commit_timeout.parse().unwrap(),
+            identity: ClusterIdentityConfig::default(),
+        }
+    }
+}
+
+impl Default for ClusterIdentityConfig {
+    fn default() -> ClusterIdentityConfig {
+        ClusterIdentityConfig {
+            role: SERVER_CONFIG.cluster.identity.role.parse().unwrap(),
+            username: SERVER_CONFIG.cluster.identity.username.parse().unwrap(),
+            password: SERVER_CONFIG.cluster.identity.password.parse().unwrap(),
+        }
+    }
+}
+
+impl Default for OidcConfig {
+    fn default() -> OidcConfig {
+        OidcConfig {
+            enabled: SERVER_CONFIG.oidc.enabled,
+            issuers: SERVER_CONFIG
+                .oidc
+                .issuers
+                .iter()
+                .map(|s| s.parse().unwrap())
+                .collect(),
+            audiences: SERVER_CONFIG
+                .oidc
+                .audiences
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            username_claim: SERVER_CONFIG.oidc.username_claim.parse().unwrap(),
+            roles_claim: SERVER_CONFIG.oidc.roles_claim.parse().unwrap(),
+            auto_provision: SERVER_CONFIG.oidc.auto_provision,
+            role_rules: SERVER_CONFIG
+                .oidc
+                .role_rules
+                .iter()
+                .map(|s| s.parse().unwrap())
+                .collect(),
+            clock_skew: SERVER_CONFIG.oidc.clock_skew.parse().unwrap(),
+            jwks_refresh_interval: SERVER_CONFIG.oidc.jwks_refresh_interval.parse().unwrap(),
+        }
+    }
+}
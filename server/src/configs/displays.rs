@@ -16,13 +16,18 @@
  * under the License.
  */
 
+use crate::configs::cluster::{ClusterConfig, ClusterIdentityConfig};
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, DiskArchiverConfig, HeartbeatConfig,
-    MessagesMaintenanceConfig, S3ArchiverConfig, StateMaintenanceConfig, TelemetryConfig,
-    TelemetryLogsConfig, TelemetryTracesConfig,
+    ArchiverConfig, BackupMaintenanceConfig, ClientCertificateConfig, DataMaintenanceConfig,
+    DiskArchiverConfig, HeartbeatConfig, MessagesMaintenanceConfig, S3ArchiverConfig,
+    StateMaintenanceConfig, TelemetryConfig, TelemetryLogsConfig, TelemetryTracesConfig,
+};
+use crate::configs::system::{
+    DeadLetterConfig, MessageDeduplicationConfig, QuotasConfig, ReplicationConfig,
+    WireCompressionConfig,
 };
-use crate::configs::system::MessageDeduplicationConfig;
 use crate::configs::{
     http::{HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig},
     resource_quota::MemoryResourceQuota,
@@ -69,8 +74,8 @@ impl Display for HttpMetricsConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, endpoint: {} }}",
-            self.enabled, self.endpoint
+            "{{ enabled: {}, endpoint: {}, max_labeled_partitions: {} }}",
+            self.enabled, self.endpoint, self.max_labeled_partitions
         )
     }
 }
@@ -79,8 +84,18 @@ impl Display for HttpTlsConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, cert_file: {}, key_file: {} }}",
-            self.enabled, self.cert_file, self.key_file
+            "{{ enabled: {}, cert_file: {}, key_file: {}, client_certificate: {} }}",
+            self.enabled, self.cert_file, self.key_file, self.client_certificate
+        )
+    }
+}
+
+impl Display for ClientCertificateConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, ca_file: {}, identity: {} }}",
+            self.enabled, self.ca_file, self.identity
         )
     }
 }
@@ -89,7 +104,7 @@ impl Display for QuicConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
           f,
-          "{{ enabled: {}, address: {}, max_concurrent_bidi_streams: {}, datagram_send_buffer_size: {}, initial_mtu: {}, send_window: {}, receive_window: {}, keep_alive_interval: {}, max_idle_timeout: {}, certificate: {} }}",
+          "{{ enabled: {}, address: {}, max_concurrent_bidi_streams: {}, datagram_send_buffer_size: {}, initial_mtu: {}, send_window: {}, receive_window: {}, keep_alive_interval: {}, max_idle_timeout: {}, certificate: {}, client_certificate: {} }}",
           self.enabled,
           self.address,
           self.max_concurrent_bidi_streams,
@@ -99,7 +114,8 @@ impl Display for QuicConfig {
           self.receive_window,
           self.keep_alive_interval,
           self.max_idle_timeout,
-          self.certificate
+          self.certificate,
+          self.client_certificate
       )
     }
 }
@@ -137,8 +153,8 @@ impl Display for DataMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ archiver: {}, messages: {}, state: {} }}",
-            self.archiver, self.messages, self.state
+            "{{ archiver: {}, messages: {}, state: {}, backup: {} }}",
+            self.archiver, self.messages, self.state, self.backup
         )
     }
 }
@@ -200,12 +216,22 @@ impl Display for StateMaintenanceConfig {
     }
 }
 
+impl Display for BackupMaintenanceConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, interval: {}, retained_backups: {} }}",
+            self.enabled, self.interval, self.retained_backups
+        )
+    }
+}
+
 impl Display for ServerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, system: {}, quic: {}, tcp: {}, http: {}, telemetry: {} }}",
-            self.data_maintenance, self.message_saver, self.heartbeat, self.system, self.quic, self.tcp, self.http, self.telemetry
+            "{{ data_maintenance: {}, message_saver: {}, heartbeat: {}, system: {}, quic: {}, tcp: {}, http: {}, telemetry: {}, cluster: {}, oidc: {} }}",
+            self.data_maintenance, self.message_saver, self.heartbeat, self.system, self.quic, self.tcp, self.http, self.telemetry, self.cluster, self.oidc
         )
     }
 }
@@ -244,7 +270,11 @@ impl Display for EncryptionConfig {
 
 impl Display for StreamConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{{ path: {} }}", self.path)
+        write!(
+            f,
+            "{{ path: {}, delete_oldest_segments: {} }}",
+            self.path, self.delete_oldest_segments
+        )
     }
 }
 
@@ -281,6 +311,104 @@ impl Display for MessageDeduplicationConfig {
     }
 }
 
+impl Display for DeadLetterConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, max_failed_deliveries: {}, topic_suffix: {} }}",
+            self.enabled, self.max_failed_deliveries, self.topic_suffix
+        )
+    }
+}
+
+impl Display for WireCompressionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, threshold: {} }}",
+            self.enabled, self.threshold
+        )
+    }
+}
+
+impl Display for QuotasConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ client_requests_per_second: {} }}",
+            self.client_requests_per_second
+        )
+    }
+}
+
+impl Display for ReplicationConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, node_id: {}, leader_address: {}, fetch_interval: {}, max_fetch_messages: {}, ack_timeout: {} }}",
+            self.enabled,
+            self.node_id,
+            self.leader_address,
+            self.fetch_interval,
+            self.max_fetch_messages,
+            self.ack_timeout
+        )
+    }
+}
+
+impl Display for ClusterConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, id: {}, name: {}, nodes: [{}], heartbeat_interval: {}, election_timeout: {}, commit_timeout: {}, identity: {} }}",
+            self.enabled,
+            self.id,
+            self.name,
+            self.nodes
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.heartbeat_interval,
+            self.election_timeout,
+            self.commit_timeout,
+            self.identity
+        )
+    }
+}
+
+impl Display for ClusterIdentityConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{ role: {}, username: {} }}", self.role, self.username)
+    }
+}
+
+impl Display for OidcConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, issuers: [{}], audiences: [{}], username_claim: {}, roles_claim: {}, auto_provision: {}, role_rules: [{}], clock_skew: {}, jwks_refresh_interval: {} }}",
+            self.enabled,
+            self.issuers
+                .iter()
+                .map(|issuer| issuer.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.audiences.join(", "),
+            self.username_claim,
+            self.roles_claim,
+            self.auto_provision,
+            self.role_rules
+                .iter()
+                .map(|rule| rule.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.clock_skew,
+            self.jwks_refresh_interval
+        )
+    }
+}
+
 impl Display for SegmentConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -318,8 +446,8 @@ impl Display for TcpTlsConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ enabled: {}, certificate: {} }}",
-            self.enabled, self.certificate
+            "{{ enabled: {}, cert_file: {}, key_file: {}, certificate: {}, client_certificate: {} }}",
+            self.enabled, self.cert_file, self.key_file, self.certificate, self.client_certificate
         )
     }
 }
@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::configs::server::ClientCertificateConfig;
 use iggy::error::IggyError;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
@@ -70,6 +71,7 @@ pub struct HttpJwtConfig {
 pub struct HttpMetricsConfig {
     pub enabled: bool,
     pub endpoint: String,
+    pub max_labeled_partitions: u32,
 }
 
 #[derive(Debug)]
@@ -83,6 +85,7 @@ pub struct HttpTlsConfig {
     pub enabled: bool,
     pub cert_file: String,
     pub key_file: String,
+    pub client_certificate: ClientCertificateConfig,
 }
 
 impl HttpJwtConfig {
@@ -19,7 +19,9 @@
 pub mod server;
 pub mod system;
 
+pub mod cluster;
 pub mod http;
+pub mod oidc;
 pub mod quic;
 pub mod tcp;
 
@@ -0,0 +1,201 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use iggy::models::permissions::GlobalPermissions;
+use iggy::utils::duration::IggyDuration;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const GLOBAL_PERMISSIONS: &[&str] = &[
+    "manage_servers",
+    "read_servers",
+    "manage_users",
+    "read_users",
+    "manage_streams",
+    "read_streams",
+    "manage_topics",
+    "read_topics",
+    "poll_messages",
+    "send_messages",
+];
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OidcConfig {
+    pub enabled: bool,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub issuers: Vec<OidcIssuerConfig>,
+    pub audiences: Vec<String>,
+    pub username_claim: String,
+    pub roles_claim: String,
+    pub auto_provision: bool,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub role_rules: Vec<OidcRoleRule>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub clock_skew: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub jwks_refresh_interval: IggyDuration,
+}
+
+/// Trusted identity provider in the `<issuer>|<jwks>` format,
+/// where `jwks` is either an `http(s)://` URL or a path to the local JWKS file,
+/// e.g. `https://idp.example.com|https://idp.example.com/.well-known/jwks.json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcIssuerConfig {
+    pub issuer: String,
+    pub jwks: String,
+}
+
+/// Claim-to-role rule in the `<role>=<permission>+<permission>` format, e.g. `iggy-readers=read_streams+poll_messages`.
+/// The permissions are the names of the global permissions granted to the auto-provisioned user having the role.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcRoleRule {
+    pub role: String,
+    pub permissions: Vec<String>,
+}
+
+impl OidcIssuerConfig {
+    pub fn is_remote(&self) -> bool {
+        self.jwks.starts_with("http://") || self.jwks.starts_with("https://")
+    }
+}
+
+impl OidcRoleRule {
+    pub fn grant(&self, permissions: &mut GlobalPermissions) {
+        for permission in &self.permissions {
+            match permission.as_str() {
+                "manage_servers" => permissions.manage_servers = true,
+                "read_servers" => permissions.read_servers = true,
+                "manage_users" => permissions.manage_users = true,
+                "read_users" => permissions.read_users = true,
+                "manage_streams" => permissions.manage_streams = true,
+                "read_streams" => permissions.read_streams = true,
+                "manage_topics" => permissions.manage_topics = true,
+                "read_topics" => permissions.read_topics = true,
+                "poll_messages" => permissions.poll_messages = true,
+                "send_messages" => permissions.send_messages = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl FromStr for OidcIssuerConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((issuer, jwks)) = s.split_once('|') else {
+            return Err(format!(
+                "Invalid OIDC issuer: {s}, expected format: <issuer>|<jwks>"
+            ));
+        };
+
+        Ok(OidcIssuerConfig {
+            issuer: issuer.trim().to_string(),
+            jwks: jwks.trim().to_string(),
+        })
+    }
+}
+
+impl Display for OidcIssuerConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.issuer, self.jwks)
+    }
+}
+
+impl FromStr for OidcRoleRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((role, permissions)) = s.split_once('=') else {
+            return Err(format!(
+                "Invalid OIDC role rule: {s}, expected format: <role>=<permission>+<permission>"
+            ));
+        };
+
+        let permissions = permissions
+            .split('+')
+            .map(|permission| permission.trim().to_string())
+            .collect::<Vec<_>>();
+        if let Some(permission) = permissions
+            .iter()
+            .find(|permission| !GLOBAL_PERMISSIONS.contains(&permission.as_str()))
+        {
+            return Err(format!("Invalid OIDC role rule permission: {permission}"));
+        }
+
+        Ok(OidcRoleRule {
+            role: role.trim().to_string(),
+            permissions,
+        })
+    }
+}
+
+impl Display for OidcRoleRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.role, self.permissions.join("+"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issuer_should_be_parsed_from_issuer_and_jwks() {
+        let issuer = OidcIssuerConfig::from_str(
+            "https://idp.example.com|https://idp.example.com/.well-known/jwks.json",
+        )
+        .unwrap();
+        assert_eq!(issuer.issuer, "https://idp.example.com");
+        assert_eq!(issuer.jwks, "https://idp.example.com/.well-known/jwks.json");
+        assert!(issuer.is_remote());
+
+        let issuer = OidcIssuerConfig::from_str("local-idp|certs/jwks.json").unwrap();
+        assert!(!issuer.is_remote());
+        assert_eq!(issuer.to_string(), "local-idp|certs/jwks.json");
+    }
+
+    #[test]
+    fn issuer_without_jwks_should_not_be_parsed() {
+        assert!(OidcIssuerConfig::from_str("https://idp.example.com").is_err());
+    }
+
+    #[test]
+    fn role_rule_should_grant_the_listed_permissions() {
+        let rule = OidcRoleRule::from_str("readers=read_streams+poll_messages").unwrap();
+        assert_eq!(rule.role, "readers");
+        assert_eq!(rule.to_string(), "readers=read_streams+poll_messages");
+
+        let mut permissions = GlobalPermissions::default();
+        rule.grant(&mut permissions);
+        assert!(permissions.read_streams);
+        assert!(permissions.poll_messages);
+        assert!(!permissions.send_messages);
+        assert!(!permissions.manage_servers);
+    }
+
+    #[test]
+    fn role_rule_with_unknown_permission_should_not_be_parsed() {
+        assert!(OidcRoleRule::from_str("readers").is_err());
+        assert!(OidcRoleRule::from_str("readers=read_everything").is_err());
+    }
+}
@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::configs::server::ClientCertificateConfig;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::duration::IggyDuration;
 use serde::{Deserialize, Serialize};
@@ -37,6 +38,7 @@ pub struct QuicConfig {
     #[serde_as(as = "DisplayFromStr")]
     pub max_idle_timeout: IggyDuration,
     pub certificate: QuicCertificateConfig,
+    pub client_certificate: ClientCertificateConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -17,8 +17,10 @@
  */
 
 use crate::archiver::ArchiverKindType;
+use crate::configs::cluster::ClusterConfig;
 use crate::configs::config_provider::ConfigProviderKind;
 use crate::configs::http::HttpConfig;
+use crate::configs::oidc::OidcConfig;
 use crate::configs::quic::QuicConfig;
 use crate::configs::system::SystemConfig;
 use crate::configs::tcp::TcpConfig;
@@ -45,6 +47,8 @@ pub struct ServerConfig {
     pub tcp: TcpConfig,
     pub http: HttpConfig,
     pub telemetry: TelemetryConfig,
+    pub cluster: ClusterConfig,
+    pub oidc: OidcConfig,
 }
 
 #[serde_as]
@@ -53,6 +57,7 @@ pub struct DataMaintenanceConfig {
     pub archiver: ArchiverConfig,
     pub messages: MessagesMaintenanceConfig,
     pub state: StateMaintenanceConfig,
+    pub backup: BackupMaintenanceConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -81,6 +86,15 @@ pub struct StateMaintenanceConfig {
     pub interval: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BackupMaintenanceConfig {
+    pub enabled: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub interval: IggyDuration,
+    pub retained_backups: u32,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DiskArchiverConfig {
     pub path: String,
@@ -167,6 +181,33 @@ impl FromStr for TelemetryTransport {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ClientCertificateConfig {
+    pub enabled: bool,
+    pub ca_file: String,
+    pub identity: ClientCertificateIdentity,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Display, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientCertificateIdentity {
+    #[display("common_name")]
+    CommonName,
+    #[display("subject_alt_name")]
+    SubjectAltName,
+}
+
+impl FromStr for ClientCertificateIdentity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "common_name" => Ok(ClientCertificateIdentity::CommonName),
+            "subject_alt_name" => Ok(ClientCertificateIdentity::SubjectAltName),
+            _ => Err(format!("Invalid client certificate identity: {s}")),
+        }
+    }
+}
+
 impl ServerConfig {
     pub async fn load(config_provider: &ConfigProviderKind) -> Result<ServerConfig, ConfigError> {
         let server_config = config_provider
@@ -16,7 +16,9 @@
  * under the License.
  */
 
+use crate::configs::cluster::ClusterIdentityConfig;
 use crate::configs::resource_quota::MemoryResourceQuota;
+use derive_more::Display;
 use iggy::confirmation::Confirmation;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
@@ -27,6 +29,7 @@ use iggy::{
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SystemConfig {
@@ -42,7 +45,11 @@ pub struct SystemConfig {
     pub segment: SegmentConfig,
     pub encryption: EncryptionConfig,
     pub compression: CompressionConfig,
+    pub wire_compression: WireCompressionConfig,
     pub message_deduplication: MessageDeduplicationConfig,
+    pub dead_letter: DeadLetterConfig,
+    pub replication: ReplicationConfig,
+    pub quotas: QuotasConfig,
     pub recovery: RecoveryConfig,
 }
 
@@ -65,6 +72,7 @@ pub struct DatabaseConfig {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RuntimeConfig {
     pub path: String,
+    pub archive_cache_size: IggyByteSize,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -73,6 +81,12 @@ pub struct CompressionConfig {
     pub default_algorithm: CompressionAlgorithm,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WireCompressionConfig {
+    pub enabled: bool,
+    pub threshold: IggyByteSize,
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LoggingConfig {
@@ -100,6 +114,7 @@ pub struct EncryptionConfig {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StreamConfig {
     pub path: String,
+    pub delete_oldest_segments: bool,
 }
 
 #[serde_as]
@@ -128,6 +143,51 @@ pub struct MessageDeduplicationConfig {
     pub expiry: IggyDuration,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeadLetterConfig {
+    pub enabled: bool,
+    pub max_failed_deliveries: u32,
+    pub topic_suffix: String,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReplicationConfig {
+    pub enabled: bool,
+    pub node_id: u32,
+    pub leader_address: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fetch_interval: IggyDuration,
+    pub max_fetch_messages: u32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub ack_timeout: IggyDuration,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Display, Copy, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplicaRole {
+    #[display("leader")]
+    Leader,
+    #[display("follower")]
+    Follower,
+}
+
+impl FromStr for ReplicaRole {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "leader" => Ok(ReplicaRole::Leader),
+            "follower" => Ok(ReplicaRole::Follower),
+            _ => Err(format!("Invalid replica role: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuotasConfig {
+    pub client_requests_per_second: u32,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RecoveryConfig {
     pub recreate_missing_state: bool,
@@ -154,6 +214,16 @@ pub struct StateConfig {
     pub retry_delay: IggyDuration,
 }
 
+impl ReplicationConfig {
+    pub fn is_leader(&self, identity: &ClusterIdentityConfig) -> bool {
+        self.enabled && identity.role == ReplicaRole::Leader
+    }
+
+    pub fn is_follower(&self, identity: &ClusterIdentityConfig) -> bool {
+        self.enabled && identity.role == ReplicaRole::Follower
+    }
+}
+
 impl SystemConfig {
     pub fn get_system_path(&self) -> String {
         self.path.to_string()
@@ -167,6 +237,10 @@ impl SystemConfig {
         format!("{}/log", self.get_state_path())
     }
 
+    pub fn get_state_cluster_path(&self) -> String {
+        format!("{}/cluster", self.get_state_path())
+    }
+
     pub fn get_state_info_path(&self) -> String {
         format!("{}/info", self.get_state_path())
     }
@@ -174,6 +248,10 @@ impl SystemConfig {
         format!("{}/tokens", self.get_state_path())
     }
 
+    pub fn get_state_transactions_path(&self) -> String {
+        format!("{}/transactions", self.get_state_path())
+    }
+
     pub fn get_backup_path(&self) -> String {
         format!("{}/{}", self.get_system_path(), self.backup.path)
     }
@@ -186,6 +264,10 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_online_backups_path(&self) -> String {
+        format!("{}/online", self.get_backup_path())
+    }
+
     pub fn get_runtime_path(&self) -> String {
         format!("{}/{}", self.get_system_path(), self.runtime.path)
     }
@@ -222,6 +304,69 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_partition_transactions_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/transactions",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_partition_producers_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/producers",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_partition_archived_segments_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/archived_segments",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_partition_poisoned_offsets_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/poisoned",
+            self.get_partition_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    pub fn get_archive_cache_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/archive/{}/{}/{}",
+            self.get_runtime_path(),
+            stream_id,
+            topic_id,
+            partition_id
+        )
+    }
+
     pub fn get_offsets_path(&self, stream_id: u32, topic_id: u32, partition_id: u32) -> String {
         format!(
             "{}/offsets",
@@ -253,6 +398,16 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_restore_path(&self, stream_id: u32, topic_id: u32, partition_id: u32) -> String {
+        format!(
+            "{}/restore/{}/{}/{}",
+            self.get_runtime_path(),
+            stream_id,
+            topic_id,
+            partition_id
+        )
+    }
+
     pub fn get_segment_path(
         &self,
         stream_id: u32,
@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use crate::configs::server::ClientCertificateConfig;
 use iggy::utils::{byte_size::IggyByteSize, duration::IggyDuration};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -33,8 +34,15 @@ pub struct TcpConfig {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TcpTlsConfig {
     pub enabled: bool,
+    pub cert_file: String,
+    pub key_file: String,
+    /// Deprecated, the PKCS#12 file with the certificate and the key, used instead of the PEM files if set.
+    #[serde(default)]
     pub certificate: String,
+    /// Deprecated, the password of the PKCS#12 file.
+    #[serde(default)]
     pub password: String,
+    pub client_certificate: ClientCertificateConfig,
 }
 
 #[serde_as]
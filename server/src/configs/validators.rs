@@ -18,11 +18,13 @@
 
 extern crate sysinfo;
 
+use super::cluster::ClusterConfig;
+use super::oidc::OidcConfig;
 use super::server::{
-    ArchiverConfig, DataMaintenanceConfig, MessageSaverConfig, MessagesMaintenanceConfig,
-    StateMaintenanceConfig, TelemetryConfig,
+    ArchiverConfig, BackupMaintenanceConfig, ClientCertificateConfig, DataMaintenanceConfig,
+    MessageSaverConfig, MessagesMaintenanceConfig, StateMaintenanceConfig, TelemetryConfig,
 };
-use super::system::CompressionConfig;
+use super::system::{CompressionConfig, DeadLetterConfig, ReplicationConfig};
 use crate::archiver::ArchiverKindType;
 use crate::configs::server::{PersonalAccessTokenConfig, ServerConfig};
 use crate::configs::system::{CacheConfig, SegmentConfig};
@@ -35,6 +37,7 @@ use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use iggy::validatable::Validatable;
+use std::collections::HashSet;
 use sysinfo::{Pid, ProcessesToUpdate, System};
 
 impl Validatable<ConfigError> for ServerConfig {
@@ -61,9 +64,52 @@ impl Validatable<ConfigError> for ServerConfig {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to validate compression config")
             })?;
+        self.system
+            .dead_letter
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate dead letter config")
+            })?;
+        self.system
+            .replication
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate replication config")
+            })?;
         self.telemetry.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate telemetry config")
         })?;
+        self.cluster.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate cluster config")
+        })?;
+        if self.system.replication.is_follower(&self.cluster.identity)
+            && self.system.replication.leader_address.trim().is_empty()
+        {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+        self.oidc.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate OIDC config")
+        })?;
+        self.tcp
+            .tls
+            .client_certificate
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate TCP client certificate config")
+            })?;
+        self.quic
+            .client_certificate
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate QUIC client certificate config")
+            })?;
+        self.http
+            .tls
+            .client_certificate
+            .validate()
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to validate HTTP client certificate config")
+            })?;
 
         let topic_size = match self.system.topic.max_size {
             MaxTopicSize::Custom(size) => Ok(size.as_bytes_u64()),
@@ -93,10 +139,9 @@ impl Validatable<ConfigError> for CompressionConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         let compression_alg = &self.default_algorithm;
         if *compression_alg != CompressionAlgorithm::None {
-            // TODO(numinex): Change this message once server side compression is fully developed.
             println!(
-                "Server started with server-side compression enabled, using algorithm: {}, this feature is not implemented yet!",
-                compression_alg
+                "Server started with server-side compression enabled, using default algorithm: {compression_alg}, allow override: {}",
+                self.allow_override
             );
         }
 
@@ -104,6 +149,112 @@ impl Validatable<ConfigError> for CompressionConfig {
     }
 }
 
+impl Validatable<ConfigError> for DeadLetterConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.max_failed_deliveries == 0 {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.topic_suffix.trim().is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for ReplicationConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.node_id == 0 || self.max_fetch_messages == 0 {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        if self.fetch_interval.get_duration().is_zero() || self.ack_timeout.get_duration().is_zero()
+        {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for ClusterConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.id == 0 || self.name.trim().is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        let mut ids = HashSet::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            if node.id == 0 || node.address.trim().is_empty() || !ids.insert(node.id) {
+                return Err(ConfigError::InvalidConfiguration);
+            }
+        }
+
+        if !ids.contains(&self.id) {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        let heartbeat_interval = self.heartbeat_interval.get_duration();
+        if heartbeat_interval.is_zero()
+            || self.commit_timeout.get_duration().is_zero()
+            || self.election_timeout.get_duration() <= heartbeat_interval * 2
+        {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for ClientCertificateConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && self.ca_file.trim().is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ConfigError> for OidcConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.issuers.is_empty() || self.username_claim.trim().is_empty() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        let mut issuers = HashSet::with_capacity(self.issuers.len());
+        for issuer in &self.issuers {
+            if issuer.issuer.is_empty() || issuer.jwks.is_empty() || !issuers.insert(&issuer.issuer)
+            {
+                return Err(ConfigError::InvalidConfiguration);
+            }
+        }
+
+        if self.jwks_refresh_interval.get_duration().is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for TelemetryConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if !self.enabled {
@@ -199,6 +350,9 @@ impl Validatable<ConfigError> for DataMaintenanceConfig {
         self.state.validate().with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to validate state maintenance config")
         })?;
+        self.backup.validate().with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate backup maintenance config")
+        })?;
         Ok(())
     }
 }
@@ -274,6 +428,16 @@ impl Validatable<ConfigError> for StateMaintenanceConfig {
     }
 }
 
+impl Validatable<ConfigError> for BackupMaintenanceConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && self.interval.is_zero() {
+            return Err(ConfigError::InvalidConfiguration);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ConfigError> for PersonalAccessTokenConfig {
     fn validate(&self) -> Result<(), ConfigError> {
         if self.max_tokens_per_user == 0 {
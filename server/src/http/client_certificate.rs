@@ -0,0 +1,75 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::server::ClientCertificateIdentity;
+use crate::tls;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::RustlsAcceptor;
+use futures::future::BoxFuture;
+use std::io;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+
+/// The names of the verified client certificate, attached to every request sent over the connection.
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    pub names: Vec<String>,
+}
+
+/// Accepts the TLS connections, exposing the identity of the client certificate to the request handlers.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor,
+    identity: ClientCertificateIdentity,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(inner: RustlsAcceptor, identity: ClientCertificateIdentity) -> Self {
+        Self { inner, identity }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientCertificate>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+        let identity = self.identity;
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let names = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certificates| certificates.first())
+                .map(|certificate| tls::get_client_certificate_names(certificate, identity))
+                .unwrap_or_default();
+            Ok((
+                stream,
+                AddExtension::new(service, ClientCertificate { names }),
+            ))
+        })
+    }
+}
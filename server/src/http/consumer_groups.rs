@@ -103,31 +103,35 @@ async fn create_consumer_group(
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.topic_id = Identifier::from_str_value(&topic_id)?;
     command.validate()?;
-    let mut system = state.system.write().await;
-    let consumer_group = system
-            .create_consumer_group(
-                &Session::stateless(identity.user_id, identity.ip_address),
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let identifier_stream_id = command.stream_id.clone();
+    let identifier_topic_id = command.topic_id.clone();
+    let group_id = state
+        .system
+        .apply_entry(&session, |system| {
+            let group_id = system.validate_create_consumer_group(
+                &session,
                 &command.stream_id,
                 &command.topic_id,
                 command.group_id,
                 &command.name,
-            )
-            .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create consumer group, stream ID: {}, topic ID: {}, group ID: {:?}", stream_id, topic_id, command.group_id))?;
-    let consumer_group = consumer_group.read().await;
-    let group_id = consumer_group.group_id;
-    let consumer_group_details = mapper::map_consumer_group(&consumer_group).await;
-    drop(consumer_group);
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::CreateConsumerGroup(CreateConsumerGroupWithId { group_id, command }),
-        )
-        .await?;
+            )?;
+            Ok((
+                EntryCommand::CreateConsumerGroup(CreateConsumerGroupWithId { group_id, command }),
+                group_id,
+            ))
+        })
+        .await
+        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create consumer group, stream ID: {}, topic ID: {}", stream_id, topic_id))?;
 
+    let system = state.system.read().await;
+    let consumer_group = system
+        .get_stream(&identifier_stream_id)?
+        .get_topic(&identifier_topic_id)?
+        .get_consumer_group(&Identifier::numeric(group_id)?)?
+        .read()
+        .await;
+    let consumer_group_details = mapper::map_consumer_group(&consumer_group).await;
     Ok((StatusCode::CREATED, Json(consumer_group_details)))
 }
 
@@ -141,29 +145,27 @@ async fn delete_consumer_group(
     let identifier_topic_id = Identifier::from_str_value(&topic_id)?;
     let identifier_group_id = Identifier::from_str_value(&group_id)?;
 
-    let mut system = state.system.write().await;
-    system
-            .delete_consumer_group(
-                &Session::stateless(identity.user_id, identity.ip_address),
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_consumer_group(
+                &session,
                 &identifier_stream_id,
                 &identifier_topic_id,
                 &identifier_group_id,
-            )
-            .await
-            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to delete consumer group with ID: {group_id} for topic with ID: {topic_id} in stream with ID: {stream_id}"))?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeleteConsumerGroup(DeleteConsumerGroup {
-                stream_id: identifier_stream_id,
-                topic_id: identifier_topic_id,
-                group_id: identifier_group_id,
-            }),
-        )
-        .await?;
+            )?;
+            Ok((
+                EntryCommand::DeleteConsumerGroup(DeleteConsumerGroup {
+                    stream_id: identifier_stream_id,
+                    topic_id: identifier_topic_id,
+                    group_id: identifier_group_id,
+                }),
+                (),
+            ))
+        })
+        .await
+        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to delete consumer group with ID: {group_id} for topic with ID: {topic_id} in stream with ID: {stream_id}"))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
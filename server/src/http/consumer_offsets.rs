@@ -28,9 +28,11 @@ use axum::{Extension, Json, Router};
 use error_set::ErrContext;
 use iggy::consumer::Consumer;
 use iggy::consumer_offsets::delete_consumer_offset::DeleteConsumerOffset;
+use iggy::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use iggy::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use iggy::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use iggy::identifier::Identifier;
+use iggy::models::consumer_lag::ConsumerLag;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
@@ -45,6 +47,14 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/consumer-offsets/{consumer_id}",
             delete(delete_consumer_offset),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/consumer-lag",
+            get(get_consumer_lag),
+        )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/consumer-groups/{group_id}/lag",
+            get(get_consumer_group_lag),
+        )
         .with_state(state)
 }
 
@@ -79,6 +89,48 @@ async fn get_consumer_offset(
     Ok(Json(offset))
 }
 
+async fn get_consumer_lag(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut query: Query<GetConsumerLag>,
+) -> Result<Json<ConsumerLag>, CustomError> {
+    query.stream_id = Identifier::from_str_value(&stream_id)?;
+    query.topic_id = Identifier::from_str_value(&topic_id)?;
+    query.validate()?;
+    let consumer = Consumer::new(query.0.consumer.id);
+    let system = state.system.read().await;
+    let lag = system
+        .get_consumer_lag(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &consumer,
+            &query.0.stream_id,
+            &query.0.topic_id,
+        )
+        .await
+        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get consumer lag, stream ID: {}, topic ID: {}, consumer: {}", stream_id, topic_id, consumer))?;
+    Ok(Json(lag))
+}
+
+async fn get_consumer_group_lag(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id, group_id)): Path<(String, String, String)>,
+) -> Result<Json<ConsumerLag>, CustomError> {
+    let consumer = Consumer::group(Identifier::from_str_value(&group_id)?);
+    let system = state.system.read().await;
+    let lag = system
+        .get_consumer_lag(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &consumer,
+            &Identifier::from_str_value(&stream_id)?,
+            &Identifier::from_str_value(&topic_id)?,
+        )
+        .await
+        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get consumer group lag, stream ID: {}, topic ID: {}, group ID: {}", stream_id, topic_id, group_id))?;
+    Ok(Json(lag))
+}
+
 async fn store_consumer_offset(
     State(state): State<Arc<AppState>>,
     Extension(identity): Extension<Identity>,
@@ -98,6 +150,7 @@ async fn store_consumer_offset(
             &command.0.topic_id,
             command.0.partition_id,
             command.0.offset,
+            command.0.generation_id,
         )
         .await
         .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset, stream ID: {}, topic ID: {}, partition ID: {:?}", stream_id, topic_id, command.0.partition_id))?;
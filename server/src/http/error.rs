@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use axum::http::header::RETRY_AFTER;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -50,6 +51,7 @@ impl IntoResponse for CustomError {
                     IggyError::TopicIdNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::PartitionNotFound(_, _, _) => StatusCode::NOT_FOUND,
                     IggyError::SegmentNotFound => StatusCode::NOT_FOUND,
+                    IggyError::ArchivedSegmentsNotFound(_, _, _) => StatusCode::NOT_FOUND,
                     IggyError::ClientNotFound(_) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupIdNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupNameNotFound(_, _) => StatusCode::NOT_FOUND,
@@ -61,8 +63,18 @@ impl IntoResponse for CustomError {
                     IggyError::InvalidAccessToken => StatusCode::UNAUTHORIZED,
                     IggyError::InvalidPersonalAccessToken => StatusCode::UNAUTHORIZED,
                     IggyError::Unauthorized => StatusCode::FORBIDDEN,
+                    IggyError::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
                     _ => StatusCode::BAD_REQUEST,
                 };
+                if let Some(retry_after) = error.retry_after() {
+                    let retry_after = retry_after.as_secs_f64().ceil() as u64;
+                    return (
+                        status_code,
+                        [(RETRY_AFTER, retry_after.to_string())],
+                        Json(ErrorResponse::from_error(error)),
+                    )
+                        .into_response();
+                }
                 (status_code, Json(ErrorResponse::from_error(error)))
             }
             CustomError::ResourceNotFound => (
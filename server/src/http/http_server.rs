@@ -17,6 +17,7 @@
  */
 
 use crate::configs::http::{HttpConfig, HttpCorsConfig};
+use crate::http::client_certificate::ClientCertificateAcceptor;
 use crate::http::diagnostics::request_diagnostics;
 use crate::http::jwt::cleaner::start_expired_tokens_cleaner;
 use crate::http::jwt::jwt_manager::JwtManager;
@@ -25,10 +26,11 @@ use crate::http::metrics::metrics;
 use crate::http::shared::AppState;
 use crate::http::*;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tls;
 use axum::extract::DefaultBodyLimit;
 use axum::http::Method;
 use axum::{middleware, Router};
-use axum_server::tls_rustls::RustlsConfig;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -55,6 +57,7 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         .merge(consumer_offsets::router(app_state.clone()))
         .merge(partitions::router(app_state.clone()))
         .merge(messages::router(app_state.clone()))
+        .merge(websocket::router(app_state.clone()))
         .layer(DefaultBodyLimit::max(
             config.max_request_size.as_bytes_u64() as usize,
         ))
@@ -90,6 +93,37 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
             }
         });
 
+        address
+    } else if config.tls.client_certificate.enabled {
+        let mut server_config = tls::create_server_config(
+            &config.tls.cert_file,
+            &config.tls.key_file,
+            &config.tls.client_certificate,
+        )
+        .unwrap_or_else(|error| panic!("Failed to create {api_name} TLS config. {error}"));
+        server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        let acceptor = ClientCertificateAcceptor::new(
+            RustlsAcceptor::new(RustlsConfig::from_config(Arc::new(server_config))),
+            config.tls.client_certificate.identity,
+        );
+
+        let listener = std::net::TcpListener::bind(config.address).unwrap();
+        let address = listener
+            .local_addr()
+            .expect("Failed to get local address for HTTPS / TLS server");
+
+        info!("Started {api_name} with client certificate verification on: {address}");
+
+        tokio::task::spawn(async move {
+            if let Err(error) = axum_server::from_tcp(listener)
+                .acceptor(acceptor)
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+            {
+                error!("Failed to start {api_name} server, error: {}", error);
+            }
+        });
+
         address
     } else {
         let tls_config = RustlsConfig::from_pem_file(
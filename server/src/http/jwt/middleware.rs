@@ -16,6 +16,8 @@
  * under the License.
  */
 
+use crate::http::client_certificate::ClientCertificate;
+use crate::http::error::CustomError;
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::{AppState, RequestDetails};
 use axum::body::Body;
@@ -23,14 +25,16 @@ use axum::{
     extract::State,
     http::{Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use error_set::ErrContext;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 const COMPONENT: &str = "JWT_MIDDLEWARE";
 const AUTHORIZATION: &str = "authorization";
 const BEARER: &str = "Bearer ";
+const ACCESS_TOKEN_QUERY_PARAMETER: &str = "access_token";
 const UNAUTHORIZED: StatusCode = StatusCode::UNAUTHORIZED;
 
 const PUBLIC_PATHS: &[&str] = &[
@@ -39,37 +43,65 @@ const PUBLIC_PATHS: &[&str] = &[
     "/ping",
     "/stats",
     "/users/login",
+    "/users/login/oidc",
     "/users/refresh-token",
     "/personal-access-tokens/login",
 ];
 
+// The client connected over the WebSocket may sign in later with the regular login command instead.
+const OPTIONAL_AUTH_PATHS: &[&str] = &["/ws"];
+
+// The browsers cannot set the Authorization header for the WebSocket and the event source,
+// so the token may be passed with the query parameter to these endpoints instead.
+const STREAMING_PATHS_SUFFIXES: &[&str] = &["/ws", "/messages/events"];
+
 pub async fn jwt_auth(
     State(state): State<Arc<AppState>>,
-    mut request: Request<Body>,
+    request: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
     if PUBLIC_PATHS.contains(&request.uri().path()) {
         return Ok(next.run(request).await);
     }
 
-    let bearer = request
-        .headers()
-        .get(AUTHORIZATION)
-        .ok_or(UNAUTHORIZED)
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - missing or inaccessible Authorization header")
-        })?
-        .to_str()
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - invalid authorization header format")
-        })
-        .map_err(|_| UNAUTHORIZED)?;
+    let jwt_token = match request.headers().get(AUTHORIZATION) {
+        Some(authorization) => {
+            let bearer = authorization
+                .to_str()
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - invalid authorization header format")
+                })
+                .map_err(|_| UNAUTHORIZED)?;
+            if !bearer.starts_with(BEARER) {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
 
-    if !bearer.starts_with(BEARER) {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
+            // The client without the access token, e.g. relying on the client certificate, sends the empty one.
+            Some(bearer[BEARER.len()..].to_owned()).filter(|token| !token.is_empty())
+        }
+        None => get_query_access_token(&request),
+    };
+
+    let Some(jwt_token) = jwt_token else {
+        let client_certificate = request.extensions().get::<ClientCertificate>().cloned();
+        let request_details = request.extensions().get::<RequestDetails>().unwrap();
+        let ip_address = request_details.ip_address;
+        if let Some(identity) =
+            get_client_certificate_identity(&state, client_certificate, ip_address).await
+        {
+            return authorize(&state, identity, request, next).await;
+        }
+
+        if OPTIONAL_AUTH_PATHS.contains(&request.uri().path()) {
+            return Ok(next.run(request).await);
+        }
+
+        return Err(UNAUTHORIZED).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - missing or inaccessible Authorization header")
+        });
+    };
 
-    let jwt_token = &bearer[BEARER.len()..];
+    let jwt_token = jwt_token.as_str();
     let token_header = jsonwebtoken::decode_header(jwt_token)
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to decode JWT header")
@@ -97,6 +129,62 @@ pub async fn jwt_auth(
         user_id: jwt_claims.claims.sub,
         ip_address: request_details.ip_address,
     };
+    authorize(&state, identity, request, next).await
+}
+
+async fn authorize(
+    state: &AppState,
+    identity: Identity,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    // The HTTP API is stateless, thus only the per-user request quota applies, without the per-client one.
+    if let Err(error) = state
+        .system
+        .read()
+        .await
+        .throttler
+        .throttle_request(identity.user_id, 0)
+    {
+        return Ok(CustomError::from(error).into_response());
+    }
+
     request.extensions_mut().insert(identity);
     Ok(next.run(request).await)
 }
+
+// The client authenticated with the verified certificate has no token to revoke or refresh.
+async fn get_client_certificate_identity(
+    state: &AppState,
+    client_certificate: Option<ClientCertificate>,
+    ip_address: SocketAddr,
+) -> Option<Identity> {
+    let client_certificate = client_certificate?;
+    let system = state.system.read().await;
+    let user = system
+        .get_user_by_client_certificate(&client_certificate.names)
+        .ok()?;
+    Some(Identity {
+        token_id: String::new(),
+        token_expiry: 0,
+        user_id: user.id,
+        ip_address,
+    })
+}
+
+fn get_query_access_token(request: &Request<Body>) -> Option<String> {
+    let path = request.uri().path();
+    if !STREAMING_PATHS_SUFFIXES
+        .iter()
+        .any(|suffix| path.ends_with(suffix))
+    {
+        return None;
+    }
+
+    request.uri().query()?.split('&').find_map(|parameter| {
+        parameter
+            .strip_prefix(ACCESS_TOKEN_QUERY_PARAMETER)?
+            .strip_prefix('=')
+            .map(|token| token.to_owned())
+    })
+}
@@ -32,6 +32,7 @@ use iggy::models::personal_access_token::PersonalAccessTokenInfo;
 use iggy::models::stream::StreamDetails;
 use iggy::models::topic::TopicDetails;
 use iggy::models::user_info::{UserInfo, UserInfoDetails};
+use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::sizeable::Sizeable;
 use tokio::sync::RwLock;
 
@@ -82,6 +83,7 @@ pub fn map_topics(topics: &[&Topic]) -> Vec<iggy::models::topic::Topic> {
             compression_algorithm: topic.compression_algorithm,
             max_topic_size: topic.max_topic_size,
             replication_factor: topic.replication_factor,
+            cleanup_policy: topic.cleanup_policy.clone(),
         };
         topics_data.push(topic);
     }
@@ -95,6 +97,7 @@ pub async fn map_topic(topic: &Topic) -> TopicDetails {
         created_at: topic.created_at,
         name: topic.name.clone(),
         size: topic.get_size_bytes(),
+        uncompressed_size: IggyByteSize::default(),
         messages_count: topic.get_messages_count(),
         partitions_count: topic.get_partitions().len() as u32,
         partitions: Vec::new(),
@@ -102,6 +105,7 @@ pub async fn map_topic(topic: &Topic) -> TopicDetails {
         compression_algorithm: topic.compression_algorithm,
         max_topic_size: topic.max_topic_size,
         replication_factor: topic.replication_factor,
+        cleanup_policy: topic.cleanup_policy.clone(),
     };
     for partition in topic.get_partitions() {
         let partition = partition.read().await;
@@ -113,10 +117,16 @@ pub async fn map_topic(topic: &Topic) -> TopicDetails {
                 segments_count: partition.get_segments().len() as u32,
                 current_offset: partition.current_offset,
                 size: partition.get_size_bytes(),
+                uncompressed_size: partition.get_uncompressed_size_bytes(),
                 messages_count: partition.get_messages_count(),
             });
     }
     topic_details.partitions.sort_by(|a, b| a.id.cmp(&b.id));
+    topic_details.uncompressed_size = topic_details
+        .partitions
+        .iter()
+        .map(|partition| partition.uncompressed_size)
+        .sum();
     topic_details
 }
 
@@ -127,6 +137,7 @@ pub fn map_user(user: &User) -> UserInfoDetails {
         created_at: user.created_at,
         status: user.status,
         permissions: user.permissions.clone(),
+        quotas: user.quotas.clone(),
     }
 }
 
@@ -211,6 +222,7 @@ pub async fn map_consumer_groups(
             name: consumer_group.name.clone(),
             partitions_count: consumer_group.partitions_count,
             members_count: consumer_group.get_members().len() as u32,
+            assignment_strategy: consumer_group.assignment_strategy,
         };
         groups.push(consumer_group);
     }
@@ -224,6 +236,7 @@ pub async fn map_consumer_group(consumer_group: &ConsumerGroup) -> ConsumerGroup
         name: consumer_group.name.clone(),
         partitions_count: consumer_group.partitions_count,
         members_count: consumer_group.get_members().len() as u32,
+        assignment_strategy: consumer_group.assignment_strategy,
         members: Vec::new(),
     };
     let members = consumer_group.get_members();
@@ -16,7 +16,7 @@
  * under the License.
  */
 
-use crate::http::error::CustomError;
+use crate::http::error::{CustomError, ErrorResponse};
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::AppState;
 use crate::http::COMPONENT;
@@ -25,17 +25,26 @@ use crate::streaming::systems::messages::PollingArgs;
 use crate::streaming::utils::random_id;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::routing::get;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
+use futures::Stream;
 use iggy::consumer::Consumer;
 use iggy::identifier::Identifier;
-use iggy::messages::poll_messages::PollMessages;
+use iggy::messages::nack_message::NackMessage;
+use iggy::messages::poll_messages::{PollMessages, PollingStrategy, PollingWait};
 use iggy::messages::send_messages::SendMessages;
 use iggy::models::messages::PolledMessages;
+use iggy::utils::duration::IggyDuration;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{error, instrument};
+
+const MESSAGES_EVENT: &str = "messages";
+const ERROR_EVENT: &str = "error";
+// Unless the longer wait is requested, the tail checks for the new messages at least this often.
+const TAIL_WAIT: IggyDuration = IggyDuration::ONE_SECOND;
 
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
@@ -43,10 +52,19 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/messages",
             get(poll_messages).post(send_messages),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/messages/events",
+            get(tail_messages),
+        )
         .route(
             "/streams/{stream_id}/topics/{topic_id}/messages/flush/{partition_id}/{fsync}",
             get(flush_unsaved_buffer),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/messages/nack",
+            post(nack_message),
+        )
+        .route("/producers", post(init_producer))
         .with_state(state)
 }
 
@@ -61,15 +79,22 @@ async fn poll_messages(
     query.validate()?;
 
     let consumer = Consumer::new(query.0.consumer.id);
-    let system = state.system.read().await;
-    let polled_messages = system
-        .poll_messages(
+    let polled_messages = state
+        .system
+        .poll_messages_with_wait(
             &Session::stateless(identity.user_id, identity.ip_address),
             &consumer,
             &query.0.stream_id,
             &query.0.topic_id,
             query.0.partition_id,
-            PollingArgs::new(query.0.strategy, query.0.count, query.0.auto_commit),
+            PollingArgs::new(
+                query.0.strategy,
+                query.0.count,
+                query.0.auto_commit,
+                query.0.isolation_level,
+                query.0.filter.take(),
+            ),
+            &query.0.wait,
         )
         .await
         .with_error_context(|error| {
@@ -81,6 +106,135 @@ async fn poll_messages(
     Ok(Json(polled_messages))
 }
 
+/// Streams the messages of the partition as the server-sent events, starting from the requested polling strategy.
+/// Each non-empty batch is sent as the `messages` event, and the failed poll ends the stream with the `error` event,
+/// which carries the same payload as the regular error response.
+async fn tail_messages(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut query: Query<PollMessages>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, CustomError> {
+    query.stream_id = Identifier::from_str_value(&stream_id)?;
+    query.topic_id = Identifier::from_str_value(&topic_id)?;
+    query.validate()?;
+
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let consumer = Consumer::new(query.0.consumer.id.clone());
+    // The access is verified upfront, so the failed tail is rejected with the regular error response.
+    state
+        .system
+        .read()
+        .await
+        .resolve_partition_to_wait_on(
+            &session,
+            &consumer,
+            &query.0.stream_id,
+            &query.0.topic_id,
+            query.0.partition_id,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to tail messages, stream ID: {}, topic ID: {}, partition ID: {:?}",
+                stream_id, topic_id, query.0.partition_id
+            )
+        })?;
+
+    let wait = if query.0.wait.is_enabled() {
+        query.0.wait
+    } else {
+        PollingWait::messages(TAIL_WAIT, 1)
+    };
+    let tail = MessagesTail {
+        state: state.clone(),
+        session,
+        consumer,
+        command: query.0,
+        wait,
+        next_offset: None,
+        closed: false,
+    };
+    let events = futures::stream::unfold(tail, |mut tail| async move {
+        let event = tail.next_event().await?;
+        Some((event, tail))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+struct MessagesTail {
+    state: Arc<AppState>,
+    session: Session,
+    consumer: Consumer,
+    command: PollMessages,
+    wait: PollingWait,
+    next_offset: Option<u64>,
+    closed: bool,
+}
+
+impl MessagesTail {
+    async fn next_event(&mut self) -> Option<Result<Event, axum::Error>> {
+        if self.closed {
+            return None;
+        }
+
+        loop {
+            let strategy = self
+                .next_offset
+                .map_or(self.command.strategy, PollingStrategy::offset);
+            let polled = self
+                .state
+                .system
+                .poll_messages_with_wait_and_last_offset(
+                    &self.session,
+                    &self.consumer,
+                    &self.command.stream_id,
+                    &self.command.topic_id,
+                    self.command.partition_id,
+                    PollingArgs::new(
+                        strategy,
+                        self.command.count,
+                        self.command.auto_commit,
+                        self.command.isolation_level,
+                        self.command.filter.clone(),
+                    ),
+                    &self.wait,
+                )
+                .await;
+            match polled {
+                Ok((polled_messages, last_offset)) => {
+                    // The offset is moved past the scanned messages, even if all of them were skipped by the filter.
+                    if let Some(offset) = last_offset {
+                        self.next_offset = Some(offset + 1);
+                    }
+
+                    if polled_messages.messages.is_empty() {
+                        continue;
+                    }
+
+                    let mut event = Event::default().event(MESSAGES_EVENT);
+                    if let Some(message) = polled_messages.messages.last() {
+                        event = event.id(message.offset.to_string());
+                    }
+                    return Some(event.json_data(&polled_messages));
+                }
+                Err(error) => {
+                    error!(
+                        "{COMPONENT} (error: {error}) - failed to tail messages, stream ID: {}, topic ID: {}, partition ID: {:?}",
+                        self.command.stream_id, self.command.topic_id, self.command.partition_id
+                    );
+                    self.closed = true;
+                    return Some(
+                        Event::default()
+                            .event(ERROR_EVENT)
+                            .json_data(ErrorResponse::from_error(error)),
+                    );
+                }
+            }
+        }
+    }
+}
+
 async fn send_messages(
     State(state): State<Arc<AppState>>,
     Extension(identity): Extension<Identity>,
@@ -101,14 +255,18 @@ async fn send_messages(
     let command_stream_id = command.stream_id;
     let command_topic_id = command.topic_id;
     let partitioning = command.partitioning;
-    let system = state.system.read().await;
+    let producer_sequence = command.producer_sequence;
     // TODO(haze): Add confirmation level after testing is complete
-    system
+    let acknowledgement = state
+        .system
+        .read()
+        .await
         .append_messages(
             &Session::stateless(identity.user_id, identity.ip_address),
             command_stream_id,
             command_topic_id,
             partitioning,
+            producer_sequence,
             messages,
             None,
         )
@@ -119,9 +277,33 @@ async fn send_messages(
                 stream_id, topic_id
             )
         })?;
+    // The system lock is already released, so waiting for the replicas doesn't block the other requests.
+    if let Some(acknowledgement) = acknowledgement {
+        acknowledgement.wait().await.with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to replicate messages, stream ID: {}, topic ID: {}",
+                stream_id, topic_id
+            )
+        })?;
+    }
     Ok(StatusCode::CREATED)
 }
 
+#[instrument(skip_all, name = "trace_init_producer", fields(iggy_user_id = identity.user_id))]
+async fn init_producer(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<u64>, CustomError> {
+    let system = state.system.read().await;
+    let producer_id = system
+        .init_producer(&Session::stateless(identity.user_id, identity.ip_address))
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to init producer")
+        })?;
+    Ok(Json(producer_id))
+}
+
 #[instrument(skip_all, name = "trace_flush_unsaved_buffer", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id, iggy_partition_id = partition_id, iggy_fsync = fsync))]
 async fn flush_unsaved_buffer(
     State(state): State<Arc<AppState>>,
@@ -142,3 +324,33 @@ async fn flush_unsaved_buffer(
         .await?;
     Ok(StatusCode::OK)
 }
+
+#[instrument(skip_all, name = "trace_nack_message", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
+async fn nack_message(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    Json(mut command): Json<NackMessage>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.validate()?;
+
+    state
+        .system
+        .nack_message(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.topic_id,
+            command.partition_id,
+            command.offset,
+        )
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to nack message, stream ID: {}, topic ID: {}, partition ID: {}, offset: {}",
+                stream_id, topic_id, command.partition_id, command.offset
+            )
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -18,6 +18,7 @@
 
 use crate::http::shared::AppState;
 use axum::body::Body;
+use axum::extract::MatchedPath;
 use axum::{
     extract::State,
     http::{Request, StatusCode},
@@ -25,6 +26,9 @@ use axum::{
     response::Response,
 };
 use std::sync::Arc;
+use std::time::Instant;
+
+const TRANSPORT: &str = "HTTP";
 
 pub async fn metrics(
     State(state): State<Arc<AppState>>,
@@ -32,5 +36,18 @@ pub async fn metrics(
     next: Next,
 ) -> Result<Response, StatusCode> {
     state.system.read().await.metrics.increment_http_requests();
-    Ok(next.run(request).await)
+    // The route template rather than the actual path is used, so the IDs don't blow up the number of labels.
+    let Some(route) = request.extensions().get::<MatchedPath>() else {
+        return Ok(next.run(request).await);
+    };
+
+    let command = format!("{} {}", request.method(), route.as_str());
+    let handled_at = Instant::now();
+    let response = next.run(request).await;
+    state.system.read().await.metrics.record_command_latency(
+        &command,
+        TRANSPORT,
+        handled_at.elapsed(),
+    );
+    Ok(response)
 }
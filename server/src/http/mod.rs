@@ -16,6 +16,7 @@
  * under the License.
  */
 
+pub mod client_certificate;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod diagnostics;
@@ -32,5 +33,7 @@ pub mod streams;
 pub mod system;
 pub mod topics;
 pub mod users;
+pub mod websocket;
+pub mod websocket_sender;
 
 pub const COMPONENT: &str = "HTTP";
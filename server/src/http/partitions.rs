@@ -24,12 +24,14 @@ use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::routing::post;
+use axum::routing::{delete, post};
 use axum::{Extension, Json, Router};
 use error_set::ErrContext;
 use iggy::identifier::Identifier;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::partitions::delete_partitions::DeletePartitions;
+use iggy::segments::delete_segments::DeleteSegments;
+use iggy::segments::restore_segments::RestoreSegments;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
 use tracing::instrument;
@@ -40,6 +42,14 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/{stream_id}/topics/{topic_id}/partitions",
             post(create_partitions).delete(delete_partitions),
         )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/partitions/{partition_id}",
+            delete(delete_segments),
+        )
+        .route(
+            "/streams/{stream_id}/topics/{topic_id}/partitions/{partition_id}/restore",
+            post(restore_segments),
+        )
         .with_state(state)
 }
 
@@ -54,30 +64,22 @@ async fn create_partitions(
     command.topic_id = Identifier::from_str_value(&topic_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-            .create_partitions(
-                &Session::stateless(identity.user_id, identity.ip_address),
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_create_partitions(
+                &session,
                 &command.stream_id,
                 &command.topic_id,
                 command.partitions_count,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create partitions, stream ID: {}, topic ID: {}",
-                    stream_id, topic_id
-                )
-            })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::CreatePartitions(command))
+            )?;
+            Ok((EntryCommand::CreatePartitions(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create partitions, stream ID: {}, topic ID: {}",
+                "{COMPONENT} (error: {error}) - failed to create partitions, stream ID: {}, topic ID: {}",
                 stream_id, topic_id
             )
         })?;
@@ -95,39 +97,100 @@ async fn delete_partitions(
     query.topic_id = Identifier::from_str_value(&topic_id)?;
     query.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-            .delete_partitions(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &query.stream_id.clone(),
-                &query.topic_id.clone(),
-                query.partitions_count,
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_partitions(&session, &query.stream_id, &query.topic_id)?;
+            Ok((
+                EntryCommand::DeletePartitions(DeletePartitions {
+                    stream_id: query.stream_id.clone(),
+                    topic_id: query.topic_id.clone(),
+                    partitions_count: query.partitions_count,
+                }),
+                (),
+            ))
+        })
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to delete partitions for topic with ID: {} in stream with ID: {}",
+                stream_id, topic_id
             )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete partitions for topic with ID: {} in stream with ID: {}",
-                    stream_id, topic_id
-                )
-            })?;
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeletePartitions(DeletePartitions {
-                stream_id: query.stream_id.clone(),
-                topic_id: query.topic_id.clone(),
-                partitions_count: query.partitions_count,
-            }),
-        )
+#[instrument(skip_all, name = "trace_delete_segments", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id, iggy_partition_id = partition_id))]
+async fn delete_segments(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id, partition_id)): Path<(String, String, u32)>,
+    mut query: Query<DeleteSegments>,
+) -> Result<StatusCode, CustomError> {
+    query.stream_id = Identifier::from_str_value(&stream_id)?;
+    query.topic_id = Identifier::from_str_value(&topic_id)?;
+    query.partition_id = partition_id;
+    query.validate()?;
+
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_segments(
+                &session,
+                &query.stream_id,
+                &query.topic_id,
+                query.partition_id,
+            )?;
+            Ok((
+                EntryCommand::DeleteSegments(DeleteSegments {
+                    stream_id: query.stream_id.clone(),
+                    topic_id: query.topic_id.clone(),
+                    partition_id: query.partition_id,
+                    segments_count: query.segments_count,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete partitions, stream ID: {}, topic ID: {}",
-                stream_id, topic_id
+                "{COMPONENT} (error: {error}) - failed to delete segments for partition with ID: {} in topic with ID: {} in stream with ID: {}",
+                partition_id, topic_id, stream_id
             )
         })?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[instrument(skip_all, name = "trace_restore_segments", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id, iggy_partition_id = partition_id))]
+async fn restore_segments(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id, partition_id)): Path<(String, String, u32)>,
+    Json(mut command): Json<RestoreSegments>,
+) -> Result<StatusCode, CustomError> {
+    command.stream_id = Identifier::from_str_value(&stream_id)?;
+    command.topic_id = Identifier::from_str_value(&topic_id)?;
+    command.partition_id = partition_id;
+    command.validate()?;
+
+    state
+        .system
+            .restore_segments(
+                &Session::stateless(identity.user_id, identity.ip_address),
+                &command.stream_id,
+                &command.topic_id,
+                command.partition_id,
+                &command.source,
+                &command.range,
+            )
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to restore segments for partition with ID: {partition_id} in topic with ID: {} in stream with ID: {}",
+                    topic_id, stream_id
+                )
+            })?;
+    Ok(StatusCode::NO_CONTENT)
+}
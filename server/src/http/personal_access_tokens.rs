@@ -36,6 +36,7 @@ use iggy::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAc
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
 use iggy::personal_access_tokens::login_with_personal_access_token::LoginWithPersonalAccessToken;
+use iggy::utils::timestamp::IggyTimestamp;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
 use tracing::instrument;
@@ -82,35 +83,29 @@ async fn create_personal_access_token(
     Json(command): Json<CreatePersonalAccessToken>,
 ) -> Result<Json<RawPersonalAccessToken>, CustomError> {
     command.validate()?;
-    let system = state.system.read().await;
-    let token = system
-            .create_personal_access_token(
-                &Session::stateless(identity.user_id, identity.ip_address),
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let token = state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_create_personal_access_token(&session, &command.name)?;
+            let (_, token) = PersonalAccessToken::new(
+                identity.user_id,
                 &command.name,
+                IggyTimestamp::now(),
                 command.expiry,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to create personal access token, user ID: {}",
-                    identity.user_id
-                )
-            })?;
-
-    let token_hash = PersonalAccessToken::hash_token(&token);
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash {
-                command,
-                hash: token_hash,
-            }),
-        )
+            );
+            Ok((
+                EntryCommand::CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash {
+                    hash: PersonalAccessToken::hash_token(&token),
+                    command,
+                }),
+                token,
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create personal access token with hash, user ID: {}",
+                "{COMPONENT} (error: {error}) - failed to create personal access token, user ID: {}",
                 identity.user_id
             )
         })?;
@@ -123,31 +118,20 @@ async fn delete_personal_access_token(
     Extension(identity): Extension<Identity>,
     Path(name): Path<String>,
 ) -> Result<StatusCode, CustomError> {
-    let mut system = state.system.write().await;
-    system
-            .delete_personal_access_token(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &name,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete personal access token, user ID: {}",
-                    identity.user_id
-                )
-            })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeletePersonalAccessToken(DeletePersonalAccessToken { name }),
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_personal_access_token(&session, &name)?;
+            Ok((
+                EntryCommand::DeletePersonalAccessToken(DeletePersonalAccessToken { name }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete personal access token, user ID: {}",
+                "{COMPONENT} (error: {error}) - failed to delete personal access token, user ID: {}",
                 identity.user_id
             )
         })?;
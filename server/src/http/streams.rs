@@ -97,37 +97,25 @@ async fn create_stream(
 ) -> Result<Json<StreamDetails>, CustomError> {
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    let stream = system
-        .create_stream(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            command.stream_id,
-            &command.name,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let stream_id = state
+        .system
+        .apply_entry(&session, |system| {
+            let stream_id =
+                system.validate_create_stream(&session, command.stream_id, &command.name)?;
+            Ok((
+                EntryCommand::CreateStream(CreateStreamWithId { stream_id, command }),
+                stream_id,
+            ))
+        })
         .await
         .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to create stream, stream ID: {:?}",
-                command.stream_id
-            )
+            format!("{COMPONENT} (error: {error}) - failed to create stream")
         })?;
-    let stream_id = stream.stream_id;
-    let response = Json(mapper::map_stream(stream));
 
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::CreateStream(CreateStreamWithId {
-            stream_id,
-            command
-        }))
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply create stream, stream ID: {stream_id}",
-            )
-        })?;
-    Ok(response)
+    let system = state.system.read().await;
+    let stream = system.get_stream(&Identifier::numeric(stream_id)?)?;
+    Ok(Json(mapper::map_stream(stream)))
 }
 
 #[instrument(skip_all, name = "trace_update_stream", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id))]
@@ -140,13 +128,13 @@ async fn update_stream(
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-        .update_stream(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.stream_id,
-            &command.name,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_update_stream(&session, &command.stream_id, &command.name)?;
+            Ok((EntryCommand::UpdateStream(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -154,18 +142,6 @@ async fn update_stream(
                 stream_id
             )
         })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::UpdateStream(command))
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply update stream, stream ID: {}",
-                stream_id
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -177,32 +153,22 @@ async fn delete_stream(
 ) -> Result<StatusCode, CustomError> {
     let identifier_stream_id = Identifier::from_str_value(&stream_id)?;
 
-    let mut system = state.system.write().await;
-    system
-        .delete_stream(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &identifier_stream_id,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_stream(&session, &identifier_stream_id)?;
+            Ok((
+                EntryCommand::DeleteStream(DeleteStream {
+                    stream_id: identifier_stream_id,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to delete stream with ID: {stream_id}",)
         })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeleteStream(DeleteStream {
-                stream_id: identifier_stream_id,
-            }),
-        )
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete stream with ID: {stream_id}",
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -213,12 +179,18 @@ async fn purge_stream(
     Path(stream_id): Path<String>,
 ) -> Result<StatusCode, CustomError> {
     let identifier_stream_id = Identifier::from_str_value(&stream_id)?;
-    let system = state.system.read().await;
-    system
-        .purge_stream(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &identifier_stream_id,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_purge_stream(&session, &identifier_stream_id)?;
+            Ok((
+                EntryCommand::PurgeStream(PurgeStream {
+                    stream_id: identifier_stream_id,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -226,20 +198,5 @@ async fn purge_stream(
                 stream_id
             )
         })?;
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::PurgeStream(PurgeStream {
-                stream_id: identifier_stream_id,
-            }),
-        )
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply purge stream, stream ID: {}",
-                stream_id
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
@@ -34,6 +34,7 @@ use chrono::Local;
 use error_set::ErrContext;
 use iggy::locking::IggySharedMutFn;
 use iggy::models::client_info::{ClientInfo, ClientInfoDetails};
+use iggy::models::cluster::ClusterMetadata;
 use iggy::models::stats::Stats;
 use iggy::system::get_snapshot::GetSnapshot;
 use iggy::validatable::Validatable;
@@ -49,7 +50,8 @@ pub fn router(state: Arc<AppState>, metrics_config: &HttpMetricsConfig) -> Route
         .route("/stats", get(get_stats))
         .route("/clients", get(get_clients))
         .route("/clients/{client_id}", get(get_client))
-        .route("/snapshot", post(get_snapshot));
+        .route("/snapshot", post(get_snapshot))
+        .route("/cluster/metadata", get(get_cluster_metadata));
     if metrics_config.enabled {
         router = router.route(&metrics_config.endpoint, get(get_metrics));
     }
@@ -59,6 +61,8 @@ pub fn router(state: Arc<AppState>, metrics_config: &HttpMetricsConfig) -> Route
 
 async fn get_metrics(State(state): State<Arc<AppState>>) -> Result<String, CustomError> {
     let system = state.system.read().await;
+    system.update_partition_metrics().await;
+    system.update_consumer_lag_metrics().await;
     Ok(system.metrics.get_formatted_output())
 }
 
@@ -70,6 +74,23 @@ async fn get_stats(State(state): State<Arc<AppState>>) -> Result<Json<Stats>, Cu
     Ok(Json(stats))
 }
 
+async fn get_cluster_metadata(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<ClusterMetadata>, CustomError> {
+    let system = state.system.read().await;
+    let metadata = system
+        .get_cluster_metadata(&Session::stateless(identity.user_id, identity.ip_address))
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to get cluster metadata, user ID: {}",
+                identity.user_id
+            )
+        })?;
+    Ok(Json(metadata))
+}
+
 async fn get_client(
     State(state): State<Arc<AppState>>,
     Extension(identity): Extension<Identity>,
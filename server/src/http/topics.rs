@@ -24,6 +24,7 @@ use crate::http::COMPONENT;
 use crate::state::command::EntryCommand;
 use crate::state::models::CreateTopicWithId;
 use crate::streaming::session::Session;
+use crate::streaming::topics::topic::Topic as StreamingTopic;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::routing::{delete, get};
@@ -111,19 +112,32 @@ async fn create_topic(
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    let topic = system
-        .create_topic(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.stream_id,
-            command.topic_id,
-            &command.name,
-            command.partitions_count,
-            command.message_expiry,
-            command.compression_algorithm,
-            command.max_topic_size,
-            command.replication_factor,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let identifier_stream_id = command.stream_id.clone();
+    let topic_id = state
+        .system
+        .apply_entry(&session, |system| {
+            let topic_id = system.validate_create_topic(
+                &session,
+                &command.stream_id,
+                command.topic_id,
+                &command.name,
+                command.partitions_count,
+                command.max_topic_size,
+            )?;
+            command.message_expiry =
+                StreamingTopic::get_message_expiry(command.message_expiry, &system.config);
+            command.max_topic_size =
+                StreamingTopic::get_max_topic_size(command.max_topic_size, &system.config)?;
+            Ok((
+                EntryCommand::CreateTopic(CreateTopicWithId {
+                    topic_id,
+                    command,
+                    dead_letter: false,
+                }),
+                topic_id,
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -131,25 +145,12 @@ async fn create_topic(
                 stream_id
             )
         })?;
-    command.message_expiry = topic.message_expiry;
-    command.max_topic_size = topic.max_topic_size;
-    let topic_id = topic.topic_id;
-    let response = Json(mapper::map_topic(topic).await);
 
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::CreateTopic(CreateTopicWithId {
-            topic_id,
-            command
-        }))
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply create topic, stream ID: {stream_id}",
-            )
-        })?;
-    Ok(response)
+    let system = state.system.read().await;
+    let topic = system
+        .get_stream(&identifier_stream_id)?
+        .get_topic(&Identifier::numeric(topic_id)?)?;
+    Ok(Json(mapper::map_topic(topic).await))
 }
 
 #[instrument(skip_all, name = "trace_update_topic", fields(iggy_user_id = identity.user_id, iggy_stream_id = stream_id, iggy_topic_id = topic_id))]
@@ -163,36 +164,27 @@ async fn update_topic(
     command.topic_id = Identifier::from_str_value(&topic_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    let topic = system
-            .update_topic(
-                &Session::stateless(identity.user_id, identity.ip_address),
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_update_topic(
+                &session,
                 &command.stream_id,
                 &command.topic_id,
                 &command.name,
-                command.message_expiry,
-                command.compression_algorithm,
                 command.max_topic_size,
-                command.replication_factor,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to update topic, stream ID: {}, topic ID: {}",
-                    stream_id, topic_id
-                )
-            })?;
-    command.message_expiry = topic.message_expiry;
-    command.max_topic_size = topic.max_topic_size;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::UpdateTopic(command))
+            )?;
+            command.message_expiry =
+                StreamingTopic::get_message_expiry(command.message_expiry, &system.config);
+            command.max_topic_size =
+                StreamingTopic::get_max_topic_size(command.max_topic_size, &system.config)?;
+            Ok((EntryCommand::UpdateTopic(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply update topic, stream ID: {}, topic ID: {}",
+                "{COMPONENT} (error: {error}) - failed to update topic, stream ID: {}, topic ID: {}",
                 stream_id, topic_id
             )
         })?;
@@ -208,35 +200,23 @@ async fn delete_topic(
     let identifier_stream_id = Identifier::from_str_value(&stream_id)?;
     let identifier_topic_id = Identifier::from_str_value(&topic_id)?;
 
-    let mut system = state.system.write().await;
-    system
-            .delete_topic(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &identifier_stream_id,
-                &identifier_topic_id,
-            )
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to delete topic with ID: {topic_id} in stream with ID: {stream_id}",
-                )
-            })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeleteTopic(DeleteTopic {
-                stream_id: identifier_stream_id,
-                topic_id: identifier_topic_id,
-            }),
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_topic(&session, &identifier_stream_id, &identifier_topic_id)?;
+            Ok((
+                EntryCommand::DeleteTopic(DeleteTopic {
+                    stream_id: identifier_stream_id,
+                    topic_id: identifier_topic_id,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply delete topic, stream ID: {}, topic ID: {}",
-                stream_id, topic_id
+                "{COMPONENT} (error: {error}) - failed to delete topic with ID: {topic_id} in stream with ID: {stream_id}",
             )
         })?;
     Ok(StatusCode::NO_CONTENT)
@@ -250,13 +230,19 @@ async fn purge_topic(
 ) -> Result<StatusCode, CustomError> {
     let identifier_stream_id = Identifier::from_str_value(&stream_id)?;
     let identifier_topic_id = Identifier::from_str_value(&topic_id)?;
-    let system = state.system.read().await;
-    system
-        .purge_topic(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &identifier_stream_id,
-            &identifier_topic_id,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_purge_topic(&session, &identifier_stream_id, &identifier_topic_id)?;
+            Ok((
+                EntryCommand::PurgeTopic(PurgeTopic {
+                    stream_id: identifier_stream_id,
+                    topic_id: identifier_topic_id,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -264,21 +250,5 @@ async fn purge_topic(
                 stream_id, topic_id
             )
         })?;
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::PurgeTopic(PurgeTopic {
-                stream_id: identifier_stream_id,
-                topic_id: identifier_topic_id,
-            }),
-        )
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply purge topic, stream ID: {}, topic ID: {}",
-                stream_id, topic_id
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
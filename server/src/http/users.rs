@@ -38,6 +38,7 @@ use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::login_user::LoginUser;
+use iggy::users::login_with_oidc_token::LoginWithOidcToken;
 use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
 use iggy::validatable::Validatable;
@@ -55,6 +56,7 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/users/{user_id}/permissions", put(update_permissions))
         .route("/users/{user_id}/password", put(change_password))
         .route("/users/login", post(login_user))
+        .route("/users/login/oidc", post(login_with_oidc_token))
         .route("/users/logout", delete(logout_user))
         .route("/users/refresh-token", post(refresh_token))
         .with_state(state)
@@ -107,50 +109,39 @@ async fn create_user(
 ) -> Result<Json<UserInfoDetails>, CustomError> {
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    let user = system
-        .create_user(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.username,
-            &command.password,
-            command.status,
-            command.permissions.clone(),
-        )
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to create user, username: {}",
-                command.username
-            )
-        })?;
-    let user_id = user.id;
-    let response = Json(mapper::map_user(user));
-
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    let username = command.username.clone();
     // For the security of the system, we hash the password before storing it in metadata.
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::CreateUser(CreateUserWithId {
+    let password = crypto::hash_password(&command.password);
+    let user_id = state
+        .system
+        .apply_entry(&session, |system| {
+            let user_id = system.validate_create_user(&session, &command.username)?;
+            Ok((
+                EntryCommand::CreateUser(CreateUserWithId {
+                    user_id,
+                    command: CreateUser {
+                        username: command.username,
+                        password,
+                        status: command.status,
+                        permissions: command.permissions,
+                    },
+                    oidc_subject: None,
+                }),
                 user_id,
-                command: CreateUser {
-                    username: command.username.to_owned(),
-                    password: crypto::hash_password(&command.password),
-                    status: command.status,
-                    permissions: command.permissions.clone(),
-                },
-            }),
-        )
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply create user, username: {}",
-                command.username
+                "{COMPONENT} (error: {error}) - failed to create user, username: {}",
+                username
             )
         })?;
 
-    Ok(response)
+    let system = state.system.read().await;
+    let user = system.get_user(&Identifier::numeric(user_id)?)?;
+    Ok(Json(mapper::map_user(user)))
 }
 
 #[instrument(skip_all, name = "trace_update_user", fields(iggy_user_id = identity.user_id, iggy_updated_user_id = user_id))]
@@ -163,14 +154,13 @@ async fn update_user(
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-        .update_user(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.user_id,
-            command.username.clone(),
-            command.status,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_update_user(&session, &command.user_id, command.username.as_deref())?;
+            Ok((EntryCommand::UpdateUser(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -178,18 +168,6 @@ async fn update_user(
                 user_id
             )
         })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::UpdateUser(command))
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply update user, user ID: {}",
-                user_id
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -203,13 +181,13 @@ async fn update_permissions(
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-        .update_permissions(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.user_id,
-            command.permissions.clone(),
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_update_permissions(&session, &command.user_id)?;
+            Ok((EntryCommand::UpdatePermissions(command), ()))
+        })
         .await
         .with_error_context(|error| {
             format!(
@@ -217,18 +195,6 @@ async fn update_permissions(
                 user_id
             )
         })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(identity.user_id, EntryCommand::UpdatePermissions(command))
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to apply update permissions, user ID: {}",
-                user_id
-            )
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -242,38 +208,30 @@ async fn change_password(
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
 
-    let mut system = state.system.write().await;
-    system
-        .change_password(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &command.user_id,
-            &command.current_password,
-            &command.new_password,
-        )
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to change password, user ID: {}",
-                user_id
-            )
-        })?;
-
+    let session = Session::stateless(identity.user_id, identity.ip_address);
     // For the security of the system, we hash the password before storing it in metadata.
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::ChangePassword(ChangePassword {
-                user_id: command.user_id,
-                current_password: "".into(),
-                new_password: crypto::hash_password(&command.new_password),
-            }),
-        )
+    let new_password = crypto::hash_password(&command.new_password);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_change_password(
+                &session,
+                &command.user_id,
+                &command.current_password,
+            )?;
+            Ok((
+                EntryCommand::ChangePassword(ChangePassword {
+                    user_id: command.user_id,
+                    current_password: "".into(),
+                    new_password,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!(
-                "{COMPONENT} (error: {error}) - failed to apply change password, user ID: {}",
+                "{COMPONENT} (error: {error}) - failed to change password, user ID: {}",
                 user_id
             )
         })?;
@@ -288,30 +246,22 @@ async fn delete_user(
 ) -> Result<StatusCode, CustomError> {
     let identifier_user_id = Identifier::from_str_value(&user_id)?;
 
-    let mut system = state.system.write().await;
-    system
-        .delete_user(
-            &Session::stateless(identity.user_id, identity.ip_address),
-            &identifier_user_id,
-        )
+    let session = Session::stateless(identity.user_id, identity.ip_address);
+    state
+        .system
+        .apply_entry(&session, |system| {
+            system.validate_delete_user(&session, &identifier_user_id)?;
+            Ok((
+                EntryCommand::DeleteUser(DeleteUser {
+                    user_id: identifier_user_id,
+                }),
+                (),
+            ))
+        })
         .await
         .with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to delete user with ID: {user_id}")
         })?;
-
-    let system = system.downgrade();
-    system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::DeleteUser(DeleteUser {
-                user_id: identifier_user_id,
-            }),
-        )
-        .await
-        .with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to apply delete user with ID: {user_id}")
-        })?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -335,6 +285,34 @@ async fn login_user(
     Ok(Json(map_generated_access_token_to_identity_info(tokens)))
 }
 
+#[instrument(skip_all, name = "trace_login_with_oidc_token")]
+async fn login_with_oidc_token(
+    State(state): State<Arc<AppState>>,
+    Json(command): Json<LoginWithOidcToken>,
+) -> Result<Json<IdentityInfo>, CustomError> {
+    command.validate()?;
+    let validator = state.system.read().await.get_oidc_validator()?;
+    let identity = validator
+        .validate(&command.token)
+        .await
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate OIDC token")
+        })?;
+
+    let user_id = state
+        .system
+        .login_with_oidc_identity(&identity, None)
+        .await
+        .with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - failed to login with OIDC token, username: {}",
+                identity.username
+            )
+        })?;
+    let tokens = state.jwt_manager.generate(user_id)?;
+    Ok(Json(map_generated_access_token_to_identity_info(tokens)))
+}
+
 #[instrument(skip_all, name = "trace_logout_user", fields(iggy_user_id = identity.user_id))]
 async fn logout_user(
     State(state): State<Arc<AppState>>,
@@ -350,16 +328,18 @@ async fn logout_user(
                 identity.user_id
             )
         })?;
-    state
-        .jwt_manager
-        .revoke_token(&identity.token_id, identity.token_expiry)
-        .await
-        .with_error_context(|error| {
-            format!(
-                "{COMPONENT} (error: {error}) - failed to revoke token, user ID: {}",
-                identity.user_id
-            )
-        })?;
+    if !identity.token_id.is_empty() {
+        state
+            .jwt_manager
+            .revoke_token(&identity.token_id, identity.token_expiry)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to revoke token, user ID: {}",
+                    identity.user_id
+                )
+            })?;
+    }
     Ok(StatusCode::NO_CONTENT)
 }
 
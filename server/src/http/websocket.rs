@@ -0,0 +1,158 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::binary::sender::SenderKind;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::shared::{AppState, RequestDetails};
+use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::systems::system::SharedSystem;
+use crate::tcp::connection_handler::{handle_connection, handle_error};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use futures::{SinkExt, StreamExt};
+use iggy::locking::IggySharedMutFn;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+use tracing::{debug, error, info};
+
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 8;
+const BRIDGE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The WebSocket endpoint speaks the binary protocol, each binary message carries the request frame(s)
+/// `[length][code][payload]` and each response is sent back as a single binary message `[status][length][payload]`.
+/// The connection opened with the valid JWT is already authenticated, otherwise the client has to sign in
+/// with the regular login command, the same way as over TCP or QUIC. The requests carrying the request ID
+/// are rejected, as the responses are relayed with the regular header only and can't be multiplexed.
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new().route("/ws", get(connect)).with_state(state)
+}
+
+async fn connect(
+    State(state): State<Arc<AppState>>,
+    Extension(request_details): Extension<RequestDetails>,
+    identity: Option<Extension<Identity>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let system = state.system.clone();
+    let identity = identity.map(|Extension(identity)| identity);
+    upgrade.on_upgrade(move |socket| {
+        handle_socket(socket, system, identity, request_details.ip_address)
+    })
+}
+
+async fn handle_socket(
+    socket: WebSocket,
+    system: SharedSystem,
+    identity: Option<Identity>,
+    address: SocketAddr,
+) {
+    info!("Accepted new WebSocket connection: {address}");
+    let session = system
+        .read()
+        .await
+        .add_client(&address, Transport::WebSocket)
+        .await;
+    let client_id = session.client_id;
+    info!("Created new session: {session}");
+    if let Some(identity) = identity {
+        session.set_user_id(identity.user_id);
+        let system = system.read().await;
+        let mut client_manager = system.client_manager.write().await;
+        if let Err(error) = client_manager
+            .set_user_id(client_id, identity.user_id)
+            .await
+        {
+            error!(
+                "Failed to authenticate WebSocket client: {client_id}, address: {address}. {error}"
+            );
+            return;
+        }
+    }
+
+    let (connection, bridged_connection) = tokio::io::duplex(BRIDGE_BUFFER_SIZE);
+    let bridge = tokio::spawn(bridge(socket, bridged_connection));
+    let mut sender = SenderKind::get_websocket_sender(connection);
+    if let Err(error) = handle_connection(session, &mut sender, system.clone()).await {
+        handle_error(error);
+    }
+
+    system.read().await.delete_client(client_id).await;
+    if let Err(error) = sender.shutdown().await {
+        error!("Failed to shutdown WebSocket stream for client: {client_id}, address: {address}. {error}");
+    }
+    drop(sender);
+    if bridge.await.is_ok() {
+        info!(
+            "Successfully closed WebSocket connection for client: {client_id}, address: {address}."
+        );
+    }
+}
+
+/// Relays the binary messages received over the WebSocket to the connection handler and its responses back,
+/// until either side is closed.
+async fn bridge(socket: WebSocket, connection: DuplexStream) {
+    let (mut socket_sender, mut socket_receiver) = socket.split();
+    let (mut reader, mut writer) = tokio::io::split(connection);
+    let requests = async {
+        while let Some(Ok(message)) = socket_receiver.next().await {
+            match message {
+                Message::Binary(request) => {
+                    if writer.write_all(&request).await.is_err() {
+                        break;
+                    }
+                }
+                Message::Close(_) => break,
+                Message::Text(_) => debug!("Ignoring the WebSocket text message."),
+                Message::Ping(_) | Message::Pong(_) => {}
+            }
+        }
+    };
+    let responses = async {
+        let mut header = [0u8; RESPONSE_INITIAL_BYTES_LENGTH];
+        while reader.read_exact(&mut header).await.is_ok() {
+            let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+            let mut response = vec![0u8; RESPONSE_INITIAL_BYTES_LENGTH + length];
+            response[..RESPONSE_INITIAL_BYTES_LENGTH].copy_from_slice(&header);
+            if reader
+                .read_exact(&mut response[RESPONSE_INITIAL_BYTES_LENGTH..])
+                .await
+                .is_err()
+            {
+                break;
+            }
+
+            if socket_sender
+                .send(Message::Binary(response.into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        let _ = socket_sender.send(Message::Close(None)).await;
+    };
+
+    tokio::select! {
+        _ = requests => {}
+        _ = responses => {}
+    }
+}
@@ -40,6 +40,7 @@ pub mod server_error;
 pub mod state;
 pub mod streaming;
 pub mod tcp;
+pub(crate) mod tls;
 pub mod versioning;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
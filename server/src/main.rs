@@ -22,9 +22,12 @@ use dotenvy::dotenv;
 use figlet_rs::FIGfont;
 use server::args::Args;
 use server::channels::commands::archive_state::ArchiveStateExecutor;
+use server::channels::commands::backup_system::BackupSystemExecutor;
 use server::channels::commands::clean_personal_access_tokens::CleanPersonalAccessTokensExecutor;
+use server::channels::commands::maintain_cluster::MaintainClusterExecutor;
 use server::channels::commands::maintain_messages::MaintainMessagesExecutor;
 use server::channels::commands::print_sysinfo::SysInfoPrintExecutor;
+use server::channels::commands::replicate_messages::ReplicateMessagesExecutor;
 use server::channels::commands::save_messages::SaveMessagesExecutor;
 use server::channels::commands::verify_heartbeats::VerifyHeartbeatsExecutor;
 use server::channels::handler::ServerCommandHandler;
@@ -94,11 +97,25 @@ async fn main() -> Result<(), ServerError> {
     #[cfg(not(feature = "disable-mimalloc"))]
     info!("Using mimalloc allocator");
 
-    let system = SharedSystem::new(System::new(
+    let mut system = System::new(
         config.system.clone(),
         config.data_maintenance.clone(),
         config.personal_access_token.clone(),
-    ));
+        config.cluster.clone(),
+    );
+    system.limit_labeled_partitions(config.http.metrics.max_labeled_partitions);
+    if config.oidc.enabled {
+        system.enable_oidc(config.oidc.clone());
+    }
+    let system = SharedSystem::new(system);
+
+    if let Some(backup_path) = &args.restore_backup {
+        system
+            .read()
+            .await
+            .restore_backup(backup_path, args.restore_state_index)
+            .await?;
+    }
 
     // Workaround to ensure that the statistics are initialized before the server
     // loads streams and starts accepting connections. This is necessary to
@@ -110,9 +127,12 @@ async fn main() -> Result<(), ServerError> {
         .install_handler(SaveMessagesExecutor)
         .install_handler(MaintainMessagesExecutor)
         .install_handler(ArchiveStateExecutor)
+        .install_handler(BackupSystemExecutor)
         .install_handler(CleanPersonalAccessTokensExecutor)
         .install_handler(SysInfoPrintExecutor)
-        .install_handler(VerifyHeartbeatsExecutor);
+        .install_handler(VerifyHeartbeatsExecutor)
+        .install_handler(ReplicateMessagesExecutor::default())
+        .install_handler(MaintainClusterExecutor::default());
 
     #[cfg(unix)]
     let (mut ctrl_c, mut sigterm) = {
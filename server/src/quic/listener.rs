@@ -17,26 +17,35 @@
  */
 
 use crate::binary::command;
+use crate::binary::compression::decompress_request;
 use crate::binary::sender::SenderKind;
 use crate::command::ServerCommand;
+use crate::configs::server::ClientCertificateConfig;
 use crate::server_error::ConnectionError;
 use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use crate::tls;
 use anyhow::{anyhow, Context};
 use bytes::Bytes;
 use iggy::validatable::Validatable;
 use iggy::{bytes_serializable::BytesSerializable, messages::MAX_PAYLOAD_SIZE};
 use quinn::{Connection, Endpoint, RecvStream, SendStream};
+use rustls::pki_types::CertificateDer;
 use tracing::{debug, error, info};
 
 const LISTENERS_COUNT: u32 = 10;
 const INITIAL_BYTES_LENGTH: usize = 4;
 
-pub fn start(endpoint: Endpoint, system: SharedSystem) {
+pub fn start(
+    endpoint: Endpoint,
+    system: SharedSystem,
+    client_certificate: ClientCertificateConfig,
+) {
     for _ in 0..LISTENERS_COUNT {
         let endpoint = endpoint.clone();
         let system = system.clone();
+        let client_certificate = client_certificate.clone();
         tokio::spawn(async move {
             while let Some(incoming_connection) = endpoint.accept().await {
                 info!(
@@ -44,6 +53,7 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
                     incoming_connection.remote_address()
                 );
                 let system = system.clone();
+                let client_certificate = client_certificate.clone();
                 let incoming_connection = incoming_connection.accept();
                 if incoming_connection.is_err() {
                     error!(
@@ -54,7 +64,9 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
                 }
                 let incoming_connection = incoming_connection.unwrap();
                 tokio::spawn(async move {
-                    if let Err(error) = handle_connection(incoming_connection, system).await {
+                    if let Err(error) =
+                        handle_connection(incoming_connection, system, client_certificate).await
+                    {
                         error!("Connection has failed: {error}");
                     }
                 });
@@ -66,6 +78,7 @@ pub fn start(endpoint: Endpoint, system: SharedSystem) {
 async fn handle_connection(
     incoming_connection: quinn::Connecting,
     system: SharedSystem,
+    client_certificate: ClientCertificateConfig,
 ) -> Result<(), ConnectionError> {
     let connection = incoming_connection.await?;
     let address = connection.remote_address();
@@ -75,6 +88,20 @@ async fn handle_connection(
         .await
         .add_client(&address, Transport::Quic)
         .await;
+    if client_certificate.enabled {
+        let certificates = connection
+            .peer_identity()
+            .and_then(|identity| identity.downcast::<Vec<CertificateDer<'static>>>().ok());
+        tls::login_with_client_certificate(
+            &system,
+            &session,
+            certificates
+                .as_deref()
+                .map(|certificates| certificates.as_slice()),
+            client_certificate.identity,
+        )
+        .await;
+    }
 
     let client_id = session.client_id;
     while let Some(stream) = accept_stream(&connection, &system, client_id).await? {
@@ -119,27 +146,32 @@ async fn handle_stream(
     session: impl AsRef<Session>,
 ) -> anyhow::Result<()> {
     let (send_stream, mut recv_stream) = stream;
+    // The request is read up to its length rather than to the end of the stream,
+    // as the subscription keeps the stream open for the credit granted by the client.
+    let mut length_buffer = [0u8; INITIAL_BYTES_LENGTH];
+    recv_stream
+        .read_exact(&mut length_buffer)
+        .await
+        .with_context(|| "Unable to read the QUIC request length.")?;
+    let length = u32::from_le_bytes(length_buffer);
+    if length < INITIAL_BYTES_LENGTH as u32 || length > MAX_PAYLOAD_SIZE {
+        return Err(anyhow!("Invalid QUIC request length: {length} bytes."));
+    }
+
     // TODO: read to BytesMut instead of Vec<u8>
-    let request = recv_stream
-        .read_to_end(MAX_PAYLOAD_SIZE as usize)
+    let mut request = vec![0u8; length as usize];
+    recv_stream
+        .read_exact(&mut request)
         .await
         .with_context(|| "Error when reading the QUIC request.")?;
 
-    if request.len() < INITIAL_BYTES_LENGTH {
-        return Err(anyhow!(
-            "Unable to read the QUIC request length, expected: {INITIAL_BYTES_LENGTH} bytes, received: {} bytes.",
-            request.len()
-        ));
-    }
-
     debug!("Trying to read command...");
-    let length = request[..INITIAL_BYTES_LENGTH]
-        .try_into()
-        .map(u32::from_le_bytes)
-        .unwrap_or_default();
-    let command =
-        ServerCommand::from_bytes(Bytes::copy_from_slice(&request[INITIAL_BYTES_LENGTH..]))
-            .with_context(|| "Error when reading the QUIC request command.")?;
+    let session = session.as_ref();
+    let compression = session.get_compression();
+    let request = decompress_request(Bytes::from(request), compression.as_ref())
+        .with_context(|| "Error when decompressing the QUIC request.")?;
+    let command = ServerCommand::from_bytes(request)
+        .with_context(|| "Error when reading the QUIC request command.")?;
     command
         .validate()
         .with_context(|| "Error when validating the QUIC command.")?;
@@ -147,7 +179,8 @@ async fn handle_stream(
     debug!("Received a QUIC command: {command}, payload size: {length}");
 
     let mut sender = SenderKind::get_quic_sender(send_stream, recv_stream);
-    command::handle(command, &mut sender, session.as_ref(), system.clone())
+    sender.set_compression(compression);
+    command::handle(command, &mut sender, session, system.clone())
         .await
         .with_context(|| "Error when handling the QUIC request.")
 }
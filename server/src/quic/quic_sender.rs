@@ -19,6 +19,7 @@
 use crate::quic::COMPONENT;
 use crate::{binary::sender::Sender, server_error::ServerError};
 use error_set::ErrContext;
+use iggy::compression::wire_compression::{WireCompression, COMPRESSION_FLAG};
 use iggy::error::IggyError;
 use quinn::{RecvStream, SendStream};
 use tracing::{debug, error};
@@ -29,6 +30,7 @@ const STATUS_OK: &[u8] = &[0; 4];
 pub struct QuicSender {
     pub(crate) send: SendStream,
     pub(crate) recv: RecvStream,
+    pub(crate) compression: Option<WireCompression>,
 }
 
 impl Sender for QuicSender {
@@ -46,11 +48,21 @@ impl Sender for QuicSender {
     }
 
     async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
-        self.send_response(STATUS_OK, payload).await
+        let compressed = self
+            .compression
+            .as_ref()
+            .and_then(|compression| compression.compress(payload));
+        match compressed {
+            Some(compressed) => {
+                self.send_response(&COMPRESSION_FLAG.to_le_bytes(), &compressed)
+                    .await
+            }
+            None => self.send_response(STATUS_OK, payload).await,
+        }
     }
 
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
-        self.send_response(&error.as_code().to_le_bytes(), &[])
+        self.send_response(&error.as_code().to_le_bytes(), &error.details_to_bytes())
             .await
     }
 
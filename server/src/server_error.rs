@@ -22,7 +22,7 @@ use std::array::TryFromSliceError;
 use tokio::io;
 
 error_set!(
-    ServerError = ConfigError || ArchiverError || ConnectionError || LogError || CompatError || QuicError;
+    ServerError = ConfigError || ArchiverError || ConnectionError || LogError || CompatError || QuicError || TlsError;
 
     IoError = {
         #[display("IO error")]
@@ -61,6 +61,15 @@ error_set!(
 
         #[display("Cannot archive file: {}", file_path)]
         CannotArchiveFile { file_path: String },
+
+        #[display("Archived file not found: {}", file_path)]
+        ArchivedFileNotFound { file_path: String },
+
+        #[display("Cannot fetch archived file: {}", file_path)]
+        CannotFetchArchivedFile { file_path: String },
+
+        #[display("Cannot list archived files in directory: {}", directory)]
+        CannotListArchivedFiles { directory: String },
     } || IoError;
 
     ConnectionError = {
@@ -93,13 +102,20 @@ error_set!(
     };
 
     QuicError = {
-        #[display("Cert load error")]
-        CertLoadError,
         #[display("Cert generation error")]
         CertGenerationError,
         #[display("Config creation error")]
         ConfigCreationError,
         #[display("Transport config error")]
         TransportConfigError,
+    } || TlsError;
+
+    TlsError = {
+        #[display("Cert load error")]
+        CertLoadError,
+        #[display("Client cert verifier creation error")]
+        ClientCertVerifierCreationError,
+        #[display("TLS config creation error")]
+        TlsConfigCreationError,
     };
 );
@@ -26,8 +26,9 @@ use iggy::command::{
     Command, CHANGE_PASSWORD_CODE, CREATE_CONSUMER_GROUP_CODE, CREATE_PARTITIONS_CODE,
     CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_STREAM_CODE, CREATE_TOPIC_CODE, CREATE_USER_CODE,
     DELETE_CONSUMER_GROUP_CODE, DELETE_PARTITIONS_CODE, DELETE_PERSONAL_ACCESS_TOKEN_CODE,
-    DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE,
-    UPDATE_PERMISSIONS_CODE, UPDATE_STREAM_CODE, UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
+    DELETE_SEGMENTS_CODE, DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE,
+    PURGE_STREAM_CODE, PURGE_TOPIC_CODE, UPDATE_PERMISSIONS_CODE, UPDATE_STREAM_CODE,
+    UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
 };
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
 use iggy::error::IggyError;
@@ -47,8 +48,12 @@ use iggy::users::update_permissions::UpdatePermissions;
 use iggy::users::update_user::UpdateUser;
 use std::fmt::{Display, Formatter};
 
+/// The code of the entry appended by the new cluster leader, which doesn't change the state.
+pub const NOOP_CODE: u32 = 0;
+
 #[derive(Debug, PartialEq)]
 pub enum EntryCommand {
+    Noop,
     CreateStream(CreateStreamWithId),
     UpdateStream(UpdateStream),
     DeleteStream(DeleteStream),
@@ -74,6 +79,7 @@ pub enum EntryCommand {
 impl BytesSerializable for EntryCommand {
     fn to_bytes(&self) -> Bytes {
         let (code, command) = match self {
+            EntryCommand::Noop => (NOOP_CODE, Bytes::new()),
             EntryCommand::CreateStream(command) => (command.code(), command.to_bytes()),
             EntryCommand::UpdateStream(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteStream(command) => (command.code(), command.to_bytes()),
@@ -115,6 +121,7 @@ impl BytesSerializable for EntryCommand {
         let length = bytes.slice(4..8).get_u32_le();
         let payload = bytes.slice(8..8 + length as usize);
         match code {
+            NOOP_CODE => Ok(EntryCommand::Noop),
             CREATE_STREAM_CODE => Ok(EntryCommand::CreateStream(CreateStreamWithId::from_bytes(
                 payload,
             )?)),
@@ -137,6 +144,9 @@ impl BytesSerializable for EntryCommand {
             DELETE_PARTITIONS_CODE => Ok(EntryCommand::DeletePartitions(
                 DeletePartitions::from_bytes(payload)?,
             )),
+            DELETE_SEGMENTS_CODE => Ok(EntryCommand::DeleteSegments(DeleteSegments::from_bytes(
+                payload,
+            )?)),
             CREATE_CONSUMER_GROUP_CODE => Ok(EntryCommand::CreateConsumerGroup(
                 CreateConsumerGroupWithId::from_bytes(payload)?,
             )),
@@ -168,6 +178,7 @@ impl BytesSerializable for EntryCommand {
 impl Display for EntryCommand {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            EntryCommand::Noop => write!(f, "Noop"),
             EntryCommand::CreateStream(command) => write!(f, "CreateStream({})", command),
             EntryCommand::UpdateStream(command) => write!(f, "UpdateStream({})", command),
             EntryCommand::DeleteStream(command) => write!(f, "DeleteStream({})", command),
@@ -36,7 +36,7 @@ use std::fmt::{Display, Formatter};
 /// - `code` - Command code
 /// - `command` - Payload of the command
 /// - `context` - Optional context e.g. used to enrich the payload with additional data
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StateEntry {
     pub index: u64,
     pub term: u64,
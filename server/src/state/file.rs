@@ -80,6 +80,162 @@ impl FileState {
     pub fn term(&self) -> u64 {
         self.term.load(Ordering::SeqCst)
     }
+
+    /// Sets the term and the leader ID, which are stored in the subsequently appended entries.
+    pub fn set_term(&self, term: u64, leader_id: u32) {
+        self.term.store(term, Ordering::SeqCst);
+        self.current_leader.store(leader_id, Ordering::SeqCst);
+    }
+
+    /// Appends the new entry with the given command to the log and returns it (with the unencrypted command).
+    pub async fn append(
+        &self,
+        user_id: u32,
+        command: EntryCommand,
+    ) -> Result<StateEntry, IggyError> {
+        debug!("Applying state entry with command: {command}, user ID: {user_id}");
+        let entry = self.create_entry(user_id, command);
+        self.append_entry(&entry).await?;
+        debug!("Applied state entry: {entry}");
+        Ok(entry)
+    }
+
+    /// Creates the entry with the given command which directly follows the current one, without appending it to the log.
+    pub fn create_entry(&self, user_id: u32, command: EntryCommand) -> StateEntry {
+        let timestamp = IggyTimestamp::now();
+        let index = if self.entries_count.load(Ordering::SeqCst) == 0 {
+            0
+        } else {
+            self.current_index.load(Ordering::SeqCst) + 1
+        };
+        let term = self.term.load(Ordering::SeqCst);
+        let current_leader = self.current_leader.load(Ordering::SeqCst);
+        let version = self.version;
+        let flags = 0;
+        let context = Bytes::new();
+        let command = command.to_bytes();
+        let checksum = StateEntry::calculate_checksum(
+            index,
+            term,
+            current_leader,
+            version,
+            flags,
+            timestamp,
+            user_id,
+            &context,
+            &command,
+        );
+        StateEntry::new(
+            index,
+            term,
+            current_leader,
+            version,
+            flags,
+            timestamp,
+            user_id,
+            checksum,
+            context,
+            command,
+        )
+    }
+
+    /// Appends the already existing entry e.g. replicated from the other node, its index must directly follow the current one.
+    pub async fn append_entry(&self, entry: &StateEntry) -> Result<(), IggyError> {
+        let entries_count = self.entries_count.load(Ordering::SeqCst);
+        if entry.index != entries_count {
+            error!(
+                "Cannot append state entry with index: {}, expected index: {entries_count}",
+                entry.index
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        self.persister
+            .append(&self.path, &self.entry_to_bytes(entry)?)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to append state entry data to file, path: {}, index: {}",
+                    self.path, entry.index
+                )
+            })?;
+        self.current_index.store(entry.index, Ordering::SeqCst);
+        self.entries_count.fetch_add(1, Ordering::SeqCst);
+        debug!("Appended state entry: {entry}");
+        Ok(())
+    }
+
+    /// Replaces the whole log with the given entries, e.g. to remove the conflicting ones.
+    pub async fn overwrite(&self, entries: &[StateEntry]) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::new();
+        for entry in entries {
+            bytes.extend(self.entry_to_bytes(entry)?);
+        }
+
+        self.persister
+            .overwrite(&self.path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to overwrite state file, path: {}",
+                    self.path
+                )
+            })?;
+        self.entries_count
+            .store(entries.len() as u64, Ordering::SeqCst);
+        self.current_index.store(
+            entries.last().map(|entry| entry.index).unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+        info!("Overwritten state file with {} entries.", entries.len());
+        Ok(())
+    }
+
+    /// Removes the entries following the one with the given index, e.g. to restore the state to the chosen point in time.
+    pub async fn truncate(&self, index: u64) -> Result<(), IggyError> {
+        let mut entries = self.load_entries().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load entries to truncate")
+        })?;
+        entries.retain(|entry| entry.index <= index);
+        self.overwrite(&entries).await
+    }
+
+    fn entry_to_bytes(&self, entry: &StateEntry) -> Result<Bytes, IggyError> {
+        let Some(encryptor) = &self.encryptor else {
+            return Ok(entry.to_bytes());
+        };
+
+        debug!("Encrypting state entry command with index: {}", entry.index);
+        let command_code = entry.command.slice(0..4).get_u32_le();
+        let command_length = entry.command.slice(4..8).get_u32_le() as usize;
+        let command_payload = entry.command.slice(8..8 + command_length);
+        let encrypted_command_payload =
+            encryptor
+                .encrypt(&command_payload)
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to encrypt state entry command, index: {}",
+                        entry.index
+                    )
+                })?;
+        let mut command = BytesMut::with_capacity(4 + 4 + encrypted_command_payload.len());
+        command.put_u32_le(command_code);
+        command.put_u32_le(encrypted_command_payload.len() as u32);
+        command.extend(encrypted_command_payload);
+        let encrypted_entry = StateEntry::new(
+            entry.index,
+            entry.term,
+            entry.leader_id,
+            entry.version,
+            entry.flags,
+            entry.timestamp,
+            entry.user_id,
+            entry.checksum,
+            entry.context.clone(),
+            command.freeze(),
+        );
+        Ok(encrypted_entry.to_bytes())
+    }
 }
 
 impl State for FileState {
@@ -305,78 +461,7 @@ impl State for FileState {
         Ok(entries)
     }
 
-    async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
-        debug!("Applying state entry with command: {command}, user ID: {user_id}");
-        let timestamp = IggyTimestamp::now();
-        let index = if self.entries_count.load(Ordering::SeqCst) == 0 {
-            0
-        } else {
-            self.current_index.fetch_add(1, Ordering::SeqCst) + 1
-        };
-        let term = self.term.load(Ordering::SeqCst);
-        let current_leader = self.current_leader.load(Ordering::SeqCst);
-        let version = self.version;
-        let flags = 0;
-        let context = Bytes::new();
-        let mut command = command.to_bytes();
-        let checksum = StateEntry::calculate_checksum(
-            index,
-            term,
-            current_leader,
-            version,
-            flags,
-            timestamp,
-            user_id,
-            &context,
-            &command,
-        );
-
-        if let Some(encryptor) = &self.encryptor {
-            debug!("Encrypting state entry command with index: {index}");
-            let command_code = command.slice(0..4).get_u32_le();
-            let mut command_length = command.slice(4..8).get_u32_le() as usize;
-            let command_payload = command.slice(8..8 + command_length);
-            let encrypted_command_payload = encryptor
-                .encrypt(&command_payload)
-                .with_error_context(|error| {
-                    format!(
-                        "{COMPONENT} (error: {error}) - failed to encrypt state entry command, index: {}",
-                        index
-                    )
-                })?;
-            command_length = encrypted_command_payload.len();
-            let mut command_bytes = BytesMut::with_capacity(4 + 4 + command_length);
-            command_bytes.put_u32_le(command_code);
-            command_bytes.put_u32_le(command_length as u32);
-            command_bytes.extend(encrypted_command_payload);
-            command = command_bytes.freeze();
-        }
-
-        let entry = StateEntry::new(
-            index,
-            term,
-            current_leader,
-            version,
-            flags,
-            timestamp,
-            user_id,
-            checksum,
-            context,
-            command,
-        );
-        let bytes = entry.to_bytes();
-        self.entries_count.fetch_add(1, Ordering::SeqCst);
-        self.persister
-            .append(&self.path, &bytes)
-            .await
-            .with_error_context(|error| {
-                format!(
-                    "{COMPONENT} (error: {error}) - failed to append state entry data to file, path: {}, data size: {}",
-                    self.path,
-                    bytes.len()
-                )
-            })?;
-        debug!("Applied state entry: {entry}");
-        Ok(())
+    async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<StateEntry, IggyError> {
+        self.append(user_id, command).await
     }
 }
@@ -28,6 +28,7 @@ pub mod command;
 pub mod entry;
 pub mod file;
 pub mod models;
+pub mod raft;
 pub mod system;
 
 pub const COMPONENT: &str = "STATE";
@@ -35,6 +36,7 @@ pub const COMPONENT: &str = "STATE";
 #[derive(Debug)]
 pub enum StateKind {
     File(file::FileState),
+    Raft(Box<raft::RaftState>),
     #[cfg(test)]
     Mock(MockState),
 }
@@ -47,13 +49,14 @@ pub trait State: Send {
         &self,
         user_id: u32,
         command: EntryCommand,
-    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    ) -> impl Future<Output = Result<StateEntry, IggyError>> + Send;
 }
 
 impl StateKind {
     pub async fn init(&self) -> Result<Vec<StateEntry>, IggyError> {
         match self {
             Self::File(s) => s.init().await,
+            Self::Raft(s) => s.init().await,
             #[cfg(test)]
             Self::Mock(s) => s.init().await,
         }
@@ -62,16 +65,39 @@ impl StateKind {
     pub async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
         match self {
             Self::File(s) => s.load_entries().await,
+            Self::Raft(s) => s.load_entries().await,
             #[cfg(test)]
             Self::Mock(s) => s.load_entries().await,
         }
     }
 
-    pub async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
+    pub async fn apply(
+        &self,
+        user_id: u32,
+        command: EntryCommand,
+    ) -> Result<StateEntry, IggyError> {
         match self {
             Self::File(s) => s.apply(user_id, command).await,
+            Self::Raft(s) => s.apply(user_id, command).await,
             #[cfg(test)]
             Self::Mock(s) => s.apply(user_id, command).await,
         }
     }
+
+    /// Returns the log storing the state entries, which is shared by the file and the cluster state.
+    pub fn log(&self) -> Option<&file::FileState> {
+        match self {
+            Self::File(s) => Some(s),
+            Self::Raft(s) => Some(s.log()),
+            #[cfg(test)]
+            Self::Mock(_) => None,
+        }
+    }
+
+    pub fn as_raft(&self) -> Option<&raft::RaftState> {
+        match self {
+            Self::Raft(s) => Some(s),
+            _ => None,
+        }
+    }
 }
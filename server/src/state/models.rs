@@ -17,6 +17,7 @@
  */
 
 use crate::state::COMPONENT;
+use crate::streaming::users::oidc::OidcSubject;
 use bytes::{BufMut, Bytes, BytesMut};
 use error_set::ErrContext;
 use iggy::bytes_serializable::BytesSerializable;
@@ -43,6 +44,9 @@ pub struct CreateStreamWithId {
 pub struct CreateTopicWithId {
     pub topic_id: u32,
     pub command: CreateTopic,
+    /// Whether the topic stores the messages moved from the other ones, stored after the command for the compatibility with the existing entries.
+    #[serde(skip)]
+    pub dead_letter: bool,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -55,6 +59,9 @@ pub struct CreateConsumerGroupWithId {
 pub struct CreateUserWithId {
     pub user_id: u32,
     pub command: CreateUser,
+    /// The OIDC subject the user is provisioned for, stored after the command for the compatibility with the existing entries.
+    #[serde(skip)]
+    pub oidc_subject: Option<OidcSubject>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -137,8 +144,8 @@ impl Display for CreateTopicWithId {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
             f,
-            "CreateTopicWithId {{ command: {}, topic ID: {} }}",
-            self.command, self.topic_id
+            "CreateTopicWithId {{ command: {}, topic ID: {}, dead letter: {} }}",
+            self.command, self.topic_id, self.dead_letter
         )
     }
 }
@@ -157,8 +164,13 @@ impl Display for CreateUserWithId {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
             f,
-            "CreateUserWithId {{ command: {}, user_id: {} }}",
-            self.command, self.user_id
+            "CreateUserWithId {{ command: {}, user_id: {}, oidc_subject: {} }}",
+            self.command,
+            self.user_id,
+            self.oidc_subject
+                .as_ref()
+                .map(|subject| subject.to_string())
+                .unwrap_or_default()
         )
     }
 }
@@ -221,6 +233,9 @@ impl BytesSerializable for CreateTopicWithId {
         let command_bytes = self.command.to_bytes();
         bytes.put_u32_le(command_bytes.len() as u32);
         bytes.put_slice(&command_bytes);
+        if self.dead_letter {
+            bytes.put_u8(1);
+        }
         bytes.freeze()
     }
 
@@ -251,7 +266,13 @@ impl BytesSerializable for CreateTopicWithId {
         let command = CreateTopic::from_bytes(command_bytes).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to parse topic command")
         })?;
-        Ok(Self { topic_id, command })
+        position += command_length as usize;
+        let dead_letter = bytes.get(position).is_some_and(|flag| *flag == 1);
+        Ok(Self {
+            topic_id,
+            command,
+            dead_letter,
+        })
     }
 }
 
@@ -304,6 +325,12 @@ impl BytesSerializable for CreateUserWithId {
         let command_bytes = self.command.to_bytes();
         bytes.put_u32_le(command_bytes.len() as u32);
         bytes.put_slice(&command_bytes);
+        if let Some(oidc_subject) = &self.oidc_subject {
+            bytes.put_u32_le(oidc_subject.issuer.len() as u32);
+            bytes.put_slice(oidc_subject.issuer.as_bytes());
+            bytes.put_u32_le(oidc_subject.subject.len() as u32);
+            bytes.put_slice(oidc_subject.subject.as_bytes());
+        }
         bytes.freeze()
     }
 
@@ -334,7 +361,21 @@ impl BytesSerializable for CreateUserWithId {
         let command = CreateUser::from_bytes(command_bytes).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to parse user command")
         })?;
-        Ok(Self { user_id, command })
+        position += command_length as usize;
+        let oidc_subject = match position < bytes.len() {
+            true => {
+                let (issuer, read_bytes) = read_string(&bytes, position)?;
+                position += read_bytes;
+                let (subject, _) = read_string(&bytes, position)?;
+                Some(OidcSubject { issuer, subject })
+            }
+            false => None,
+        };
+        Ok(Self {
+            user_id,
+            command,
+            oidc_subject,
+        })
     }
 }
 
@@ -387,3 +428,24 @@ impl BytesSerializable for CreatePersonalAccessTokenWithHash {
         Ok(Self { hash, command })
     }
 }
+
+/// Reads the string prefixed with its length, returning it along with the number of the read bytes.
+fn read_string(bytes: &Bytes, position: usize) -> Result<(String, usize), IggyError> {
+    if bytes.len() < position + 4 {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let length = u32::from_le_bytes(
+        bytes[position..position + 4]
+            .try_into()
+            .map_err(|_| IggyError::InvalidNumberEncoding)?,
+    ) as usize;
+    if bytes.len() < position + 4 + length {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let value = from_utf8(&bytes[position + 4..position + 4 + length])
+        .map_err(|_| IggyError::InvalidUtf8)?
+        .to_string();
+    Ok((value, 4 + length))
+}
@@ -0,0 +1,946 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::cluster::ClusterConfig;
+use crate::state::command::EntryCommand;
+use crate::state::file::FileState;
+use crate::state::{State, StateEntry, COMPONENT};
+use crate::streaming::persistence::persister::PersisterKind;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use futures::future::join_all;
+use iggy::binary::{BinaryTransport, ClientState};
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::client::{AutoLogin, Client, Credentials};
+use iggy::cluster::append_entries::AppendEntries;
+use iggy::cluster::request_vote::RequestVote;
+use iggy::command::Command;
+use iggy::error::IggyError;
+use iggy::models::cluster::{ClusterMetadata, ClusterNode, ClusterNodeRole, ClusterNodeStatus};
+use iggy::tcp::client::TcpClient;
+use iggy::tcp::config::{TcpClientConfig, TcpClientReconnectionConfig};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+use tokio::time::{timeout, Instant};
+use tracing::{debug, error, info, warn};
+
+/// Maximum number of the state log entries sent to the follower in a single `AppendEntries` request.
+const MAX_ENTRIES_PER_REQUEST: usize = 100;
+const METADATA_SIZE: usize = 8 + 4 + 8;
+const APPEND_ENTRIES_RESPONSE_SIZE: usize = 8 + 1 + 8;
+const VOTE_RESPONSE_SIZE: usize = 8 + 1;
+const STATE_ENTRY_MIN_SIZE: usize = 52 + 8;
+
+/// State replicated across the cluster nodes with the Raft consensus algorithm.
+///
+/// Every node stores the entries in its own `FileState` log, while the current term, the vote
+/// and the number of the committed entries are persisted in the separate metadata file.
+/// Only the elected leader accepts the new entries, which are committed once stored by the majority
+/// of the nodes. Every node (including the leader) applies the committed entries in the order of the log
+/// via `take_committed_entries`. The new leader appends the no-op entry to commit the ones from the previous terms.
+///
+/// The very first entry (the root user created during the initialization) is written by each node
+/// on its own as the common bootstrap entry, so the replication always starts from the second one.
+#[derive(Debug)]
+pub struct RaftState {
+    node_id: u32,
+    cluster_name: String,
+    address: String,
+    username: String,
+    password: String,
+    heartbeat_interval: Duration,
+    election_timeout: Duration,
+    commit_timeout: Duration,
+    log: FileState,
+    metadata_path: String,
+    persister: Arc<PersisterKind>,
+    peers: Vec<RaftPeer>,
+    status: Mutex<RaftStatus>,
+    commits: watch::Sender<u64>,
+}
+
+#[derive(Debug)]
+struct RaftStatus {
+    role: ClusterNodeRole,
+    term: u64,
+    voted_for: Option<u32>,
+    leader_id: Option<u32>,
+    entries: Vec<StateEntry>,
+    commit_length: u64,
+    applied_length: u64,
+    election_deadline: Instant,
+}
+
+#[derive(Debug)]
+struct RaftPeer {
+    id: u32,
+    address: String,
+    client: Mutex<Option<TcpClient>>,
+    next_index: AtomicU64,
+    match_length: AtomicU64,
+    status: AtomicU8,
+}
+
+/// Response to the `AppendEntries` command.
+/// - `term` - current term of the follower, used by the leader to step down if it's outdated.
+/// - `success` - whether the follower contained the entry matching the previous index and term.
+/// - `log_length` - number of the entries matching the leader log on success, otherwise the hint for the next index.
+#[derive(Debug, PartialEq)]
+pub struct AppendEntriesResponse {
+    pub term: u64,
+    pub success: bool,
+    pub log_length: u64,
+}
+
+/// Response to the `RequestVote` command.
+/// - `term` - current term of the voter, used by the candidate to step down if it's outdated.
+/// - `vote_granted` - whether the candidate received the vote.
+#[derive(Debug, PartialEq)]
+pub struct VoteResponse {
+    pub term: u64,
+    pub vote_granted: bool,
+}
+
+impl RaftState {
+    pub fn new(
+        config: &ClusterConfig,
+        log: FileState,
+        metadata_path: &str,
+        persister: Arc<PersisterKind>,
+    ) -> Self {
+        let address = config
+            .nodes
+            .iter()
+            .find(|node| node.id == config.id)
+            .map(|node| node.address.clone())
+            .unwrap_or_default();
+        let peers = config
+            .get_peers()
+            .into_iter()
+            .map(|node| RaftPeer {
+                id: node.id,
+                address: node.address.clone(),
+                client: Mutex::new(None),
+                next_index: AtomicU64::new(1),
+                match_length: AtomicU64::new(0),
+                status: AtomicU8::new(ClusterNodeStatus::Unknown.as_code()),
+            })
+            .collect();
+        let election_timeout = config.election_timeout.get_duration();
+        Self {
+            node_id: config.id,
+            cluster_name: config.name.clone(),
+            address,
+            username: config.identity.username.clone(),
+            password: config.identity.password.clone(),
+            heartbeat_interval: config.heartbeat_interval.get_duration(),
+            election_timeout,
+            commit_timeout: config.commit_timeout.get_duration(),
+            log,
+            metadata_path: metadata_path.to_owned(),
+            persister,
+            peers,
+            status: Mutex::new(RaftStatus {
+                role: ClusterNodeRole::Follower,
+                term: 0,
+                voted_for: None,
+                leader_id: None,
+                entries: Vec::new(),
+                commit_length: 0,
+                applied_length: 0,
+                election_deadline: Instant::now() + election_timeout,
+            }),
+            commits: watch::channel(0).0,
+        }
+    }
+
+    /// Returns the metadata of the cluster as seen by this node.
+    pub fn log(&self) -> &FileState {
+        &self.log
+    }
+
+    pub async fn get_metadata(&self) -> ClusterMetadata {
+        let status = self.status.lock().await;
+        let mut nodes = Vec::with_capacity(self.peers.len() + 1);
+        nodes.push(ClusterNode {
+            id: self.node_id,
+            address: self.address.clone(),
+            role: status.role,
+            status: ClusterNodeStatus::Healthy,
+        });
+        for peer in &self.peers {
+            let role = if status.leader_id == Some(peer.id) {
+                ClusterNodeRole::Leader
+            } else {
+                ClusterNodeRole::Follower
+            };
+            nodes.push(ClusterNode {
+                id: peer.id,
+                address: peer.address.clone(),
+                role,
+                status: peer.get_status(),
+            });
+        }
+        nodes.sort_by_key(|node| node.id);
+        ClusterMetadata {
+            name: self.cluster_name.clone(),
+            id: self.node_id,
+            term: status.term,
+            leader_id: status.leader_id,
+            nodes,
+        }
+    }
+
+    /// Fails unless this node is the leader which has already applied all the entries of its log,
+    /// as only then the new metadata changes can be accepted.
+    pub async fn ensure_leader(&self) -> Result<(), IggyError> {
+        let status = self.status.lock().await;
+        if status.role == ClusterNodeRole::Leader
+            && status.applied_length == status.entries.len() as u64
+        {
+            return Ok(());
+        }
+
+        Err(IggyError::NotClusterLeader)
+    }
+
+    pub async fn has_unapplied_entries(&self) -> bool {
+        let status = self.status.lock().await;
+        status.applied_length < status.commit_length
+    }
+
+    /// Returns the committed entries which haven't been applied yet, and marks them as applied.
+    /// It must be called while holding the system lock, so that the entries are applied in order.
+    pub async fn take_committed_entries(&self) -> Vec<StateEntry> {
+        let mut status = self.status.lock().await;
+        let entries =
+            status.entries[status.applied_length as usize..status.commit_length as usize].to_vec();
+        status.applied_length = status.commit_length;
+        entries
+    }
+
+    /// Sends the heartbeat (and the missing entries) to the followers when being the leader,
+    /// or starts the election once the election timeout has elapsed without hearing from the leader.
+    pub async fn tick(&self) {
+        let (role, election_timeout_elapsed) = {
+            let status = self.status.lock().await;
+            (status.role, Instant::now() >= status.election_deadline)
+        };
+
+        if role == ClusterNodeRole::Leader {
+            self.replicate().await;
+        } else if election_timeout_elapsed {
+            self.start_election().await;
+        }
+    }
+
+    pub async fn handle_append_entries(
+        &self,
+        command: AppendEntries,
+    ) -> Result<AppendEntriesResponse, IggyError> {
+        let mut status = self.status.lock().await;
+        if command.term < status.term {
+            return Ok(AppendEntriesResponse {
+                term: status.term,
+                success: false,
+                log_length: status.entries.len() as u64,
+            });
+        }
+
+        if command.term > status.term || status.role != ClusterNodeRole::Follower {
+            self.step_down(&mut status, command.term).await;
+        }
+
+        if status.leader_id != Some(command.leader_id) {
+            info!(
+                "Following the cluster leader with ID: {} in term: {}.",
+                command.leader_id, command.term
+            );
+            status.leader_id = Some(command.leader_id);
+        }
+        status.election_deadline = Instant::now() + self.random_election_timeout();
+        if let Some(peer) = self.peers.iter().find(|peer| peer.id == command.leader_id) {
+            peer.set_status(ClusterNodeStatus::Healthy);
+        }
+
+        let prev_log_index = command.prev_log_index as usize;
+        match status.entries.get(prev_log_index) {
+            Some(entry) if entry.term == command.prev_log_term => {}
+            Some(_) => {
+                return Ok(AppendEntriesResponse {
+                    term: status.term,
+                    success: false,
+                    log_length: command.prev_log_index,
+                })
+            }
+            None => {
+                return Ok(AppendEntriesResponse {
+                    term: status.term,
+                    success: false,
+                    log_length: status.entries.len() as u64,
+                })
+            }
+        }
+
+        let mut index = prev_log_index + 1;
+        for bytes in command.entries {
+            let entry = parse_entry(bytes, index as u64)?;
+            if let Some(existing_entry) = status.entries.get(index) {
+                if existing_entry.term == entry.term {
+                    index += 1;
+                    continue;
+                }
+
+                if (index as u64) < status.commit_length {
+                    error!(
+                        "Cannot remove the committed state entry with index: {index}, term: {}.",
+                        existing_entry.term
+                    );
+                    return Err(IggyError::StateFileCorrupted);
+                }
+
+                if (index as u64) < status.applied_length {
+                    error!("Removing the already applied, but not committed state entries starting at index: {index}, the node should be restarted.");
+                    status.applied_length = index as u64;
+                }
+
+                warn!("Removing the conflicting state entries starting at index: {index}.");
+                status.entries.truncate(index);
+                self.log
+                    .overwrite(&status.entries)
+                    .await
+                    .with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to remove the conflicting state entries starting at index: {index}")
+                    })?;
+            }
+
+            self.log.append_entry(&entry).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append replicated state entry with index: {index}")
+            })?;
+            status.entries.push(entry);
+            index += 1;
+        }
+
+        let commit_length = command.leader_commit.min(index as u64);
+        if commit_length > status.commit_length {
+            status.commit_length = commit_length;
+            self.persist_metadata(&status).await?;
+            self.commits.send_replace(commit_length);
+        }
+
+        Ok(AppendEntriesResponse {
+            term: status.term,
+            success: true,
+            log_length: index as u64,
+        })
+    }
+
+    pub async fn handle_request_vote(
+        &self,
+        command: RequestVote,
+    ) -> Result<VoteResponse, IggyError> {
+        let mut status = self.status.lock().await;
+        if command.term > status.term {
+            self.step_down(&mut status, command.term).await;
+        }
+
+        let (last_log_index, last_log_term) = get_last_entry(&status);
+        let is_log_up_to_date = command.last_log_term > last_log_term
+            || (command.last_log_term == last_log_term && command.last_log_index >= last_log_index);
+        let vote_granted = command.term == status.term
+            && status
+                .voted_for
+                .is_none_or(|voted_for| voted_for == command.candidate_id)
+            && is_log_up_to_date;
+        if vote_granted {
+            status.voted_for = Some(command.candidate_id);
+            status.election_deadline = Instant::now() + self.random_election_timeout();
+            self.persist_metadata(&status).await?;
+            info!(
+                "Voted for the node with ID: {} in term: {}.",
+                command.candidate_id, command.term
+            );
+        }
+
+        Ok(VoteResponse {
+            term: status.term,
+            vote_granted,
+        })
+    }
+
+    async fn start_election(&self) {
+        let command = {
+            let mut status = self.status.lock().await;
+            status.term += 1;
+            status.role = ClusterNodeRole::Candidate;
+            status.voted_for = Some(self.node_id);
+            status.leader_id = None;
+            status.election_deadline = Instant::now() + self.random_election_timeout();
+            if let Err(error) = self.persist_metadata(&status).await {
+                error!(
+                    "Failed to start the election for term: {}. Error: {error}",
+                    status.term
+                );
+                return;
+            }
+
+            info!("Starting the election for term: {}.", status.term);
+            let (last_log_index, last_log_term) = get_last_entry(&status);
+            RequestVote {
+                term: status.term,
+                candidate_id: self.node_id,
+                last_log_index,
+                last_log_term,
+            }
+        };
+
+        let responses = join_all(
+            self.peers
+                .iter()
+                .map(|peer| self.send_to_peer(peer, &command)),
+        )
+        .await;
+
+        let mut status = self.status.lock().await;
+        let mut votes = 1;
+        for response in responses.into_iter().flatten() {
+            let response = match VoteResponse::from_bytes(response) {
+                Ok(response) => response,
+                Err(error) => {
+                    warn!("Received invalid vote response. Error: {error}");
+                    continue;
+                }
+            };
+
+            if response.term > status.term {
+                self.step_down(&mut status, response.term).await;
+                return;
+            }
+
+            if response.vote_granted {
+                votes += 1;
+            }
+        }
+
+        if status.role != ClusterNodeRole::Candidate || status.term != command.term {
+            return;
+        }
+
+        if votes < self.majority() {
+            info!(
+                "Received {votes} vote(s) in term: {}, the majority was not reached.",
+                status.term
+            );
+            return;
+        }
+
+        status.role = ClusterNodeRole::Leader;
+        status.leader_id = Some(self.node_id);
+        for peer in &self.peers {
+            peer.next_index
+                .store(status.entries.len() as u64, Ordering::SeqCst);
+            peer.match_length.store(0, Ordering::SeqCst);
+        }
+        info!(
+            "Became the cluster leader in term: {} with {votes} vote(s).",
+            status.term
+        );
+        // The entries from the previous terms are committed only along with the one from the current term.
+        if !status.entries.is_empty() {
+            self.log.set_term(status.term, self.node_id);
+            match self.log.append(0, EntryCommand::Noop).await {
+                Ok(entry) => {
+                    status.entries.push(entry);
+                    self.update_commit_length(&mut status).await;
+                }
+                Err(error) => error!(
+                    "Failed to append the no-op entry in term: {}. Error: {error}",
+                    status.term
+                ),
+            }
+        }
+        drop(status);
+        self.replicate().await;
+    }
+
+    async fn replicate(&self) {
+        join_all(self.peers.iter().map(|peer| self.replicate_to(peer))).await;
+    }
+
+    async fn replicate_to(&self, peer: &RaftPeer) {
+        let (command, sent_length) = {
+            let status = self.status.lock().await;
+            if status.role != ClusterNodeRole::Leader || status.entries.is_empty() {
+                return;
+            }
+
+            let next_index =
+                (peer.next_index.load(Ordering::SeqCst) as usize).clamp(1, status.entries.len());
+            let prev_entry = &status.entries[next_index - 1];
+            let entries = status.entries[next_index..]
+                .iter()
+                .take(MAX_ENTRIES_PER_REQUEST)
+                .map(|entry| entry.to_bytes())
+                .collect::<Vec<_>>();
+            let sent_length = (next_index + entries.len()) as u64;
+            let command = AppendEntries {
+                term: status.term,
+                leader_id: self.node_id,
+                prev_log_index: prev_entry.index,
+                prev_log_term: prev_entry.term,
+                leader_commit: status.commit_length,
+                entries,
+            };
+            (command, sent_length)
+        };
+
+        let Ok(response) = self.send_to_peer(peer, &command).await else {
+            return;
+        };
+
+        let response = match AppendEntriesResponse::from_bytes(response) {
+            Ok(response) => response,
+            Err(error) => {
+                warn!(
+                    "Received invalid append entries response from the node with ID: {}. Error: {error}",
+                    peer.id
+                );
+                return;
+            }
+        };
+
+        let mut status = self.status.lock().await;
+        if response.term > status.term {
+            self.step_down(&mut status, response.term).await;
+            return;
+        }
+
+        if status.role != ClusterNodeRole::Leader || status.term != command.term {
+            return;
+        }
+
+        if response.success {
+            peer.match_length.fetch_max(sent_length, Ordering::SeqCst);
+            peer.next_index.store(sent_length, Ordering::SeqCst);
+        } else {
+            let next_index = peer.next_index.load(Ordering::SeqCst);
+            peer.next_index.store(
+                next_index.saturating_sub(1).min(response.log_length).max(1),
+                Ordering::SeqCst,
+            );
+            return;
+        }
+
+        self.update_commit_length(&mut status).await;
+    }
+
+    /// Commits the entries stored by the majority of the nodes, as long as the last one of them
+    /// is from the current term, as the entries from the previous terms might still be overwritten.
+    async fn update_commit_length(&self, status: &mut RaftStatus) {
+        let mut lengths = self
+            .peers
+            .iter()
+            .map(|peer| peer.match_length.load(Ordering::SeqCst))
+            .collect::<Vec<_>>();
+        lengths.push(status.entries.len() as u64);
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        let commit_length = lengths[self.majority() - 1];
+        if commit_length <= status.commit_length
+            || status.entries[commit_length as usize - 1].term != status.term
+        {
+            return;
+        }
+
+        status.commit_length = commit_length;
+        if let Err(error) = self.persist_metadata(status).await {
+            error!("Failed to persist the commit length: {commit_length}. Error: {error}");
+        }
+        self.commits.send_replace(commit_length);
+    }
+
+    async fn wait_for_commit(&self, index: u64) -> Result<(), IggyError> {
+        let mut commits = self.commits.subscribe();
+        let deadline = Instant::now() + self.commit_timeout;
+        loop {
+            self.replicate().await;
+            if *commits.borrow_and_update() > index {
+                return Ok(());
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                error!("State entry with index: {index} was not committed in time.");
+                return Err(IggyError::StateEntryNotCommitted(index));
+            }
+
+            let _ = timeout(remaining.min(self.heartbeat_interval), commits.changed()).await;
+        }
+    }
+
+    async fn step_down(&self, status: &mut RaftStatus, term: u64) {
+        if status.role != ClusterNodeRole::Follower {
+            info!("Stepping down to the follower in term: {term}.");
+        }
+
+        status.role = ClusterNodeRole::Follower;
+        status.election_deadline = Instant::now() + self.random_election_timeout();
+        if term == status.term {
+            return;
+        }
+
+        status.term = term;
+        status.voted_for = None;
+        status.leader_id = None;
+        if let Err(error) = self.persist_metadata(status).await {
+            error!("Failed to persist the term: {term}. Error: {error}");
+        }
+    }
+
+    async fn send_to_peer<T: Command>(
+        &self,
+        peer: &RaftPeer,
+        command: &T,
+    ) -> Result<Bytes, IggyError> {
+        // The RPC can't take longer than half of the election timeout, so that the slow node
+        // doesn't delay the heartbeats sent to the other ones.
+        let rpc_timeout = self.election_timeout / 2;
+        let mut client = peer.client.lock().await;
+        let result = match timeout(rpc_timeout, self.send(&mut client, peer, command)).await {
+            Ok(result) => result,
+            Err(_) => Err(IggyError::NotConnected),
+        };
+        match &result {
+            Ok(_) => peer.set_status(ClusterNodeStatus::Healthy),
+            Err(error) => {
+                debug!(
+                    "Failed to send command: {} to the node with ID: {}. Error: {error}",
+                    command.code(),
+                    peer.id
+                );
+                peer.set_status(ClusterNodeStatus::Unavailable);
+                if let Some(client) = client.take() {
+                    let _ = client.shutdown().await;
+                }
+            }
+        }
+        result
+    }
+
+    async fn send<T: Command>(
+        &self,
+        client: &mut Option<TcpClient>,
+        peer: &RaftPeer,
+        command: &T,
+    ) -> Result<Bytes, IggyError> {
+        if let Some(existing_client) = client.as_ref() {
+            if existing_client.get_state().await != ClientState::Authenticated {
+                let _ = existing_client.shutdown().await;
+                *client = None;
+            }
+        }
+
+        if client.is_none() {
+            let new_client = TcpClient::create(Arc::new(TcpClientConfig {
+                server_address: peer.address.clone(),
+                auto_login: AutoLogin::Enabled(Credentials::UsernamePassword(
+                    self.username.clone(),
+                    self.password.clone(),
+                )),
+                reconnection: TcpClientReconnectionConfig {
+                    enabled: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))?;
+            new_client.connect().await?;
+            *client = Some(new_client);
+        }
+
+        client
+            .as_ref()
+            .expect("Cluster node client must be connected")
+            .send_with_response(command)
+            .await
+    }
+
+    async fn load_metadata(&self) -> Result<(u64, Option<u32>, u64), IggyError> {
+        if !Path::new(&self.metadata_path).exists() {
+            return Ok((0, None, 0));
+        }
+
+        let bytes = tokio::fs::read(&self.metadata_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read cluster metadata file, path: {}",
+                    self.metadata_path
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if bytes.len() != METADATA_SIZE {
+            error!(
+                "Cluster metadata file is corrupted, expected size: {METADATA_SIZE}, got: {}",
+                bytes.len()
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let mut bytes = Bytes::from(bytes);
+        let term = bytes.get_u64_le();
+        let voted_for = bytes.get_u32_le();
+        let commit_length = bytes.get_u64_le();
+        let voted_for = if voted_for == 0 {
+            None
+        } else {
+            Some(voted_for)
+        };
+        Ok((term, voted_for, commit_length))
+    }
+
+    async fn persist_metadata(&self, status: &RaftStatus) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(METADATA_SIZE);
+        bytes.put_u64_le(status.term);
+        bytes.put_u32_le(status.voted_for.unwrap_or_default());
+        bytes.put_u64_le(status.commit_length);
+        self.persister
+            .overwrite(&self.metadata_path, &bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to persist cluster metadata, path: {}",
+                    self.metadata_path
+                )
+            })
+    }
+
+    fn majority(&self) -> usize {
+        let nodes_count = self.peers.len() + 1;
+        nodes_count / 2 + 1
+    }
+
+    fn random_election_timeout(&self) -> Duration {
+        let mut bytes = [0u8; 4];
+        if SystemRandom::new().fill(&mut bytes).is_err() {
+            return self.election_timeout;
+        }
+
+        let factor = u32::from_le_bytes(bytes) as f64 / u32::MAX as f64;
+        self.election_timeout + self.election_timeout.mul_f64(factor)
+    }
+}
+
+impl State for RaftState {
+    async fn init(&self) -> Result<Vec<StateEntry>, IggyError> {
+        let entries = self.log.init().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to initialize state log")
+        })?;
+        let (term, voted_for, commit_length) =
+            self.load_metadata().await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load cluster metadata")
+            })?;
+        let mut status = self.status.lock().await;
+        status.term = term;
+        status.voted_for = voted_for;
+        status.commit_length = commit_length.min(entries.len() as u64);
+        status.applied_length = status.commit_length;
+        status.election_deadline = Instant::now() + self.random_election_timeout();
+        status.entries = entries;
+        self.commits.send_replace(status.commit_length);
+        info!(
+            "Initialized cluster state for node with ID: {}, term: {term}, entries: {}, committed: {}.",
+            self.node_id,
+            status.entries.len(),
+            status.commit_length
+        );
+        Ok(status.entries[..status.commit_length as usize].to_vec())
+    }
+
+    async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
+        self.log.load_entries().await
+    }
+
+    /// Appends the entry to the log of the leader and waits until it's committed. The entry is not
+    /// marked as applied, so that it's applied along with the other committed entries in the order of the log.
+    async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<StateEntry, IggyError> {
+        let entry = {
+            let mut status = self.status.lock().await;
+            if status.entries.is_empty() {
+                self.log.set_term(0, 0);
+                let entry = self.log.append(user_id, command).await?;
+                status.entries.push(entry.clone());
+                status.commit_length = 1;
+                status.applied_length = 1;
+                self.persist_metadata(&status).await?;
+                self.commits.send_replace(1);
+                info!("Bootstrapped the cluster state log with the initial entry.");
+                return Ok(entry);
+            }
+
+            if status.role != ClusterNodeRole::Leader
+                || status.applied_length != status.entries.len() as u64
+            {
+                return Err(IggyError::NotClusterLeader);
+            }
+
+            self.log.set_term(status.term, self.node_id);
+            let entry = self.log.append(user_id, command).await?;
+            status.entries.push(entry.clone());
+            if self.peers.is_empty() {
+                self.update_commit_length(&mut status).await;
+            }
+            entry
+        };
+
+        self.wait_for_commit(entry.index).await?;
+        Ok(entry)
+    }
+}
+
+impl RaftPeer {
+    fn get_status(&self) -> ClusterNodeStatus {
+        ClusterNodeStatus::from_code(self.status.load(Ordering::SeqCst)).unwrap_or_default()
+    }
+
+    fn set_status(&self, status: ClusterNodeStatus) {
+        self.status.store(status.as_code(), Ordering::SeqCst);
+    }
+}
+
+impl BytesSerializable for AppendEntriesResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(APPEND_ENTRIES_RESPONSE_SIZE);
+        bytes.put_u64_le(self.term);
+        bytes.put_u8(self.success as u8);
+        bytes.put_u64_le(self.log_length);
+        bytes.freeze()
+    }
+
+    fn from_bytes(mut bytes: Bytes) -> Result<Self, IggyError> {
+        if bytes.len() != APPEND_ENTRIES_RESPONSE_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(AppendEntriesResponse {
+            term: bytes.get_u64_le(),
+            success: bytes.get_u8() == 1,
+            log_length: bytes.get_u64_le(),
+        })
+    }
+}
+
+impl BytesSerializable for VoteResponse {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(VOTE_RESPONSE_SIZE);
+        bytes.put_u64_le(self.term);
+        bytes.put_u8(self.vote_granted as u8);
+        bytes.freeze()
+    }
+
+    fn from_bytes(mut bytes: Bytes) -> Result<Self, IggyError> {
+        if bytes.len() != VOTE_RESPONSE_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        Ok(VoteResponse {
+            term: bytes.get_u64_le(),
+            vote_granted: bytes.get_u8() == 1,
+        })
+    }
+}
+
+fn get_last_entry(status: &RaftStatus) -> (u64, u64) {
+    status
+        .entries
+        .last()
+        .map(|entry| (entry.index, entry.term))
+        .unwrap_or_default()
+}
+
+fn parse_entry(bytes: Bytes, expected_index: u64) -> Result<StateEntry, IggyError> {
+    if bytes.len() < STATE_ENTRY_MIN_SIZE {
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let entry = StateEntry::from_bytes(bytes)?;
+    if entry.index != expected_index {
+        error!(
+            "Received state entry with index: {}, expected index: {expected_index}",
+            entry.index
+        );
+        return Err(IggyError::InvalidCommand);
+    }
+
+    let checksum = StateEntry::calculate_checksum(
+        entry.index,
+        entry.term,
+        entry.leader_id,
+        entry.version,
+        entry.flags,
+        entry.timestamp,
+        entry.user_id,
+        &entry.context,
+        &entry.command,
+    );
+    if checksum != entry.checksum {
+        return Err(IggyError::InvalidStateEntryChecksum(
+            checksum,
+            entry.checksum,
+            entry.index,
+        ));
+    }
+
+    entry.command()?;
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_entries_response_should_be_serialized_and_deserialized() {
+        let response = AppendEntriesResponse {
+            term: 3,
+            success: true,
+            log_length: 10,
+        };
+
+        let deserialized = AppendEntriesResponse::from_bytes(response.to_bytes()).unwrap();
+        assert_eq!(deserialized, response);
+    }
+
+    #[test]
+    fn vote_response_should_be_serialized_and_deserialized() {
+        let response = VoteResponse {
+            term: 2,
+            vote_granted: true,
+        };
+
+        let deserialized = VoteResponse::from_bytes(response.to_bytes()).unwrap();
+        assert_eq!(deserialized, response);
+    }
+
+    #[test]
+    fn too_short_state_entry_should_not_be_parsed() {
+        assert!(parse_entry(Bytes::from_static(&[0; 10]), 1).is_err());
+    }
+}
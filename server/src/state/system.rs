@@ -18,13 +18,18 @@
 
 use crate::state::{EntryCommand, StateEntry, COMPONENT};
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
+use crate::streaming::users::oidc::OidcSubject;
 use ahash::AHashMap;
 use error_set::ErrContext;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::models::permissions::Permissions;
+use iggy::models::stream_limits::StreamLimits;
+use iggy::models::user_quotas::UserQuotas;
 use iggy::models::user_status::UserStatus;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::timestamp::IggyTimestamp;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -42,6 +47,7 @@ pub struct StreamState {
     pub id: u32,
     pub name: String,
     pub created_at: IggyTimestamp,
+    pub limits: StreamLimits,
     pub topics: AHashMap<u32, TopicState>,
 }
 
@@ -55,6 +61,8 @@ pub struct TopicState {
     pub message_expiry: IggyExpiry,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: Option<u8>,
+    pub cleanup_policy: CleanupPolicy,
+    pub dead_letter: bool,
     pub created_at: IggyTimestamp,
 }
 
@@ -79,13 +87,16 @@ pub struct UserState {
     pub status: UserStatus,
     pub created_at: IggyTimestamp,
     pub permissions: Option<Permissions>,
+    pub quotas: Option<UserQuotas>,
     pub personal_access_tokens: AHashMap<String, PersonalAccessTokenState>,
+    pub oidc_subject: Option<OidcSubject>,
 }
 
 #[derive(Debug)]
 pub struct ConsumerGroupState {
     pub id: u32,
     pub name: String,
+    pub assignment_strategy: AssignmentStrategy,
 }
 
 impl SystemState {
@@ -97,6 +108,7 @@ impl SystemState {
             match entry.command().with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to retrieve state entry command: {entry}")
             })? {
+                EntryCommand::Noop => {}
                 EntryCommand::CreateStream(command) => {
                     info!("Creating stream: {command:?}");
                     let stream_id = command.stream_id;
@@ -106,6 +118,7 @@ impl SystemState {
                         name: command.name.clone(),
                         topics: AHashMap::new(),
                         created_at: entry.timestamp,
+                        limits: command.limits.unwrap_or_default(),
                     };
                     streams.insert(stream.id, stream);
                 }
@@ -115,6 +128,9 @@ impl SystemState {
                         .get_mut(&stream_id)
                         .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
                     stream.name = command.name;
+                    if let Some(limits) = command.limits {
+                        stream.limits = limits;
+                    }
                 }
                 EntryCommand::DeleteStream(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
@@ -133,6 +149,7 @@ impl SystemState {
                         .get_mut(&stream_id)
                         .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
                     let topic_id = command.topic_id;
+                    let dead_letter = command.dead_letter;
                     let command = command.command;
                     let topic = TopicState {
                         id: topic_id,
@@ -142,6 +159,8 @@ impl SystemState {
                         message_expiry: command.message_expiry,
                         max_topic_size: command.max_topic_size,
                         replication_factor: command.replication_factor,
+                        cleanup_policy: command.cleanup_policy,
+                        dead_letter,
                         created_at: entry.timestamp,
                         partitions: if command.partitions_count > 0 {
                             let mut partitions = AHashMap::new();
@@ -176,6 +195,7 @@ impl SystemState {
                     topic.message_expiry = command.message_expiry;
                     topic.max_topic_size = command.max_topic_size;
                     topic.replication_factor = command.replication_factor;
+                    topic.cleanup_policy = command.cleanup_policy;
                 }
                 EntryCommand::DeleteTopic(command) => {
                     let stream_id = find_stream_id(&streams, &command.stream_id);
@@ -286,6 +306,7 @@ impl SystemState {
                     let consumer_group = ConsumerGroupState {
                         id: consumer_group_id,
                         name: command.name,
+                        assignment_strategy: command.assignment_strategy,
                     };
                     topic
                         .consumer_groups
@@ -307,6 +328,7 @@ impl SystemState {
                 }
                 EntryCommand::CreateUser(command) => {
                     let user_id = command.user_id;
+                    let oidc_subject = command.oidc_subject;
                     let command = command.command;
                     let user = UserState {
                         id: user_id,
@@ -315,7 +337,9 @@ impl SystemState {
                         status: command.status,
                         created_at: entry.timestamp,
                         permissions: command.permissions,
+                        quotas: None,
                         personal_access_tokens: AHashMap::new(),
+                        oidc_subject,
                     };
                     users.insert(user.id, user);
                 }
@@ -330,6 +354,9 @@ impl SystemState {
                     if let Some(status) = &command.status {
                         user.status = *status;
                     }
+                    if let Some(quotas) = command.quotas {
+                        user.quotas = (!quotas.is_unlimited()).then_some(quotas);
+                    }
                 }
                 EntryCommand::DeleteUser(command) => {
                     let user_id = find_user_id(&users, &command.user_id);
@@ -502,7 +529,11 @@ impl Display for SystemState {
 
 impl Display for ConsumerGroupState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ConsumerGroup -> ID: {}, Name: {}", self.id, self.name)
+        write!(
+            f,
+            "ConsumerGroup -> ID: {}, Name: {}, Assignment strategy: {}",
+            self.id, self.name, self.assignment_strategy
+        )
     }
 }
 
@@ -513,17 +544,26 @@ impl Display for UserState {
         } else {
             "no_permissions".to_string()
         };
+        let quotas = if let Some(quotas) = &self.quotas {
+            quotas.to_string()
+        } else {
+            "no_quotas".to_string()
+        };
         write!(
             f,
-            "User -> ID: {}, Username: {}, Status: {}, Permissions: {}",
-            self.id, self.username, self.status, permissions
+            "User -> ID: {}, Username: {}, Status: {}, Permissions: {}, Quotas: {}",
+            self.id, self.username, self.status, permissions, quotas
         )
     }
 }
 
 impl Display for StreamState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Stream -> ID: {}, Name: {}", self.id, self.name,)?;
+        write!(
+            f,
+            "Stream -> ID: {}, Name: {}, Limits: {}",
+            self.id, self.name, self.limits
+        )?;
         for topic in self.topics.iter() {
             write!(f, "\n {}", topic.1)?;
         }
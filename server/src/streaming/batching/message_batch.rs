@@ -19,10 +19,16 @@
 use crate::streaming::batching::batch_filter::BatchItemizer;
 use crate::streaming::batching::iterator::IntoMessagesIterator;
 use crate::streaming::models::messages::RetainedMessage;
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
 use iggy::utils::{byte_size::IggyByteSize, sizeable::Sizeable};
 
 pub const RETAINED_BATCH_HEADER_LEN: u64 = 8 + 8 + 4 + 4;
+/// The most significant bit of the batch length marks the compressed payload.
+pub const RETAINED_BATCH_COMPRESSED_FLAG: u32 = 1 << 31;
+/// The compressed payload starts with the algorithm code and the uncompressed length.
+pub const RETAINED_BATCH_COMPRESSION_HEADER_LEN: usize = 1 + 4;
 
 #[derive(Debug)]
 pub struct RetainedMessageBatch {
@@ -31,6 +37,7 @@ pub struct RetainedMessageBatch {
     pub max_timestamp: u64,
     pub length: IggyByteSize,
     pub bytes: Bytes,
+    pub compression_algorithm: CompressionAlgorithm,
 }
 
 impl RetainedMessageBatch {
@@ -47,9 +54,83 @@ impl RetainedMessageBatch {
             max_timestamp,
             length,
             bytes,
+            compression_algorithm: CompressionAlgorithm::None,
         }
     }
 
+    /// Creates the batch from the compressed payload read from disk, decompressing it in place.
+    pub fn from_compressed(
+        base_offset: u64,
+        last_offset_delta: u32,
+        max_timestamp: u64,
+        payload: Bytes,
+    ) -> Result<Self, IggyError> {
+        if payload.len() < RETAINED_BATCH_COMPRESSION_HEADER_LEN {
+            return Err(IggyError::CannotDecompressData);
+        }
+
+        let compression_algorithm = CompressionAlgorithm::from_code(payload[0])
+            .map_err(|_| IggyError::CannotDecompressData)?;
+        let uncompressed_length = Self::get_uncompressed_length(&payload)?;
+        let bytes = compression_algorithm.decompress(
+            &payload[RETAINED_BATCH_COMPRESSION_HEADER_LEN..],
+            uncompressed_length as usize,
+        )?;
+        if bytes.len() != uncompressed_length as usize {
+            return Err(IggyError::CannotDecompressData);
+        }
+
+        Ok(RetainedMessageBatch::new(
+            base_offset,
+            last_offset_delta,
+            max_timestamp,
+            IggyByteSize::from(uncompressed_length as u64),
+            Bytes::from(bytes),
+        ))
+    }
+
+    /// Reads the uncompressed payload length from the compressed payload header.
+    pub fn get_uncompressed_length(compressed_payload: &[u8]) -> Result<u32, IggyError> {
+        compressed_payload
+            .get(1..RETAINED_BATCH_COMPRESSION_HEADER_LEN)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(IggyError::CannotDecompressData)
+    }
+
+    /// Compresses the batch payload using the provided algorithm.
+    /// The batch is left uncompressed if the compression doesn't reduce its size.
+    pub fn compress(self, compression_algorithm: CompressionAlgorithm) -> Result<Self, IggyError> {
+        if compression_algorithm == CompressionAlgorithm::None
+            || self.compression_algorithm != CompressionAlgorithm::None
+        {
+            return Ok(self);
+        }
+
+        let compressed = compression_algorithm.compress(&self.bytes)?;
+        let compressed_length = RETAINED_BATCH_COMPRESSION_HEADER_LEN + compressed.len();
+        if compressed_length >= self.bytes.len() {
+            return Ok(self);
+        }
+
+        let mut bytes = BytesMut::with_capacity(compressed_length);
+        bytes.put_u8(compression_algorithm.as_code());
+        bytes.put_u32_le(self.bytes.len() as u32);
+        bytes.put_slice(&compressed);
+        Ok(RetainedMessageBatch {
+            base_offset: self.base_offset,
+            last_offset_delta: self.last_offset_delta,
+            max_timestamp: self.max_timestamp,
+            length: IggyByteSize::from(compressed_length as u64),
+            bytes: bytes.freeze(),
+            compression_algorithm,
+        })
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.compression_algorithm != CompressionAlgorithm::None
+    }
+
     pub fn is_contained_or_overlapping_within_offset_range(
         &self,
         start_offset: u64,
@@ -68,12 +149,71 @@ impl RetainedMessageBatch {
         let mut header: [u8; 24] = [0u8; 24];
 
         header[0..8].copy_from_slice(&self.base_offset.to_le_bytes());
-        header[8..12].copy_from_slice(&(self.length.as_bytes_u64() as u32).to_le_bytes());
+        let mut length = self.length.as_bytes_u64() as u32;
+        if self.is_compressed() {
+            length |= RETAINED_BATCH_COMPRESSED_FLAG;
+        }
+        header[8..12].copy_from_slice(&length.to_le_bytes());
         header[12..16].copy_from_slice(&self.last_offset_delta.to_le_bytes());
         header[16..24].copy_from_slice(&self.max_timestamp.to_le_bytes());
 
         header
     }
+
+    /// Serializes the batch using the same layout as in the segment log file (header followed by the payload).
+    pub fn to_bytes(&self) -> Bytes {
+        let mut bytes =
+            BytesMut::with_capacity(RETAINED_BATCH_HEADER_LEN as usize + self.bytes.len());
+        bytes.put_slice(&self.header_as_bytes());
+        bytes.put_slice(&self.bytes);
+        bytes.freeze()
+    }
+
+    /// Deserializes the batch using the same layout as in the segment log file, decompressing its payload if needed.
+    pub fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        let header_len = RETAINED_BATCH_HEADER_LEN as usize;
+        if bytes.len() < header_len {
+            return Err(IggyError::CannotReadBatchBaseOffset);
+        }
+
+        let base_offset = u64::from_le_bytes(
+            bytes[0..8]
+                .try_into()
+                .map_err(|_| IggyError::CannotReadBatchBaseOffset)?,
+        );
+        let length = u32::from_le_bytes(
+            bytes[8..12]
+                .try_into()
+                .map_err(|_| IggyError::CannotReadBatchLength)?,
+        );
+        let last_offset_delta = u32::from_le_bytes(
+            bytes[12..16]
+                .try_into()
+                .map_err(|_| IggyError::CannotReadLastOffsetDelta)?,
+        );
+        let max_timestamp = u64::from_le_bytes(
+            bytes[16..24]
+                .try_into()
+                .map_err(|_| IggyError::CannotReadMaxTimestamp)?,
+        );
+        let payload_len = (length & !RETAINED_BATCH_COMPRESSED_FLAG) as usize;
+        if bytes.len() != header_len + payload_len {
+            return Err(IggyError::CannotReadBatchPayload);
+        }
+
+        let payload = bytes.slice(header_len..);
+        if length & RETAINED_BATCH_COMPRESSED_FLAG != 0 {
+            return Self::from_compressed(base_offset, last_offset_delta, max_timestamp, payload);
+        }
+
+        Ok(RetainedMessageBatch::new(
+            base_offset,
+            last_offset_delta,
+            max_timestamp,
+            IggyByteSize::from(payload_len as u64),
+            payload,
+        ))
+    }
 }
 
 impl<'a, T, U> BatchItemizer<RetainedMessage, &'a U, T> for T
@@ -102,3 +242,92 @@ impl Sizeable for RetainedMessageBatch {
         self.length + RETAINED_BATCH_HEADER_LEN.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_batch() -> RetainedMessageBatch {
+        let bytes = Bytes::from("message".repeat(100));
+        RetainedMessageBatch::new(10, 5, 1000, IggyByteSize::from(bytes.len() as u64), bytes)
+    }
+
+    #[test]
+    fn should_compress_and_decompress_batch() {
+        let batch = create_batch();
+        let uncompressed_bytes = batch.bytes.clone();
+        let compressed = batch.compress(CompressionAlgorithm::Zstd).unwrap();
+        assert!(compressed.is_compressed());
+        assert!(compressed.length.as_bytes_u64() < uncompressed_bytes.len() as u64);
+        assert_eq!(
+            RetainedMessageBatch::get_uncompressed_length(&compressed.bytes).unwrap(),
+            uncompressed_bytes.len() as u32
+        );
+
+        let header = compressed.header_as_bytes();
+        let length = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        assert_ne!(length & RETAINED_BATCH_COMPRESSED_FLAG, 0);
+        assert_eq!(
+            (length & !RETAINED_BATCH_COMPRESSED_FLAG) as u64,
+            compressed.length.as_bytes_u64()
+        );
+
+        let decompressed =
+            RetainedMessageBatch::from_compressed(10, 5, 1000, compressed.bytes).unwrap();
+        assert!(!decompressed.is_compressed());
+        assert_eq!(decompressed.base_offset, 10);
+        assert_eq!(decompressed.last_offset_delta, 5);
+        assert_eq!(decompressed.max_timestamp, 1000);
+        assert_eq!(decompressed.bytes, uncompressed_bytes);
+        assert_eq!(
+            decompressed.length.as_bytes_u64(),
+            uncompressed_bytes.len() as u64
+        );
+    }
+
+    #[test]
+    fn should_be_serialized_and_deserialized_from_bytes() {
+        let batch = create_batch();
+        let uncompressed_bytes = batch.bytes.clone();
+        let compressed = batch.compress(CompressionAlgorithm::Lz4).unwrap();
+
+        let bytes = compressed.to_bytes();
+        assert_eq!(
+            bytes.len() as u64,
+            compressed.get_size_bytes().as_bytes_u64()
+        );
+
+        let deserialized = RetainedMessageBatch::from_bytes(bytes).unwrap();
+        assert!(!deserialized.is_compressed());
+        assert_eq!(deserialized.base_offset, 10);
+        assert_eq!(deserialized.last_offset_delta, 5);
+        assert_eq!(deserialized.max_timestamp, 1000);
+        assert_eq!(deserialized.bytes, uncompressed_bytes);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_from_truncated_bytes() {
+        let bytes = create_batch().to_bytes();
+        let truncated = bytes.slice(..bytes.len() - 1);
+        assert!(RetainedMessageBatch::from_bytes(truncated).is_err());
+    }
+
+    #[test]
+    fn should_not_compress_batch_given_none_algorithm() {
+        let batch = create_batch().compress(CompressionAlgorithm::None).unwrap();
+        assert!(!batch.is_compressed());
+        let header = batch.header_as_bytes();
+        let length = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        assert_eq!(length & RETAINED_BATCH_COMPRESSED_FLAG, 0);
+    }
+
+    #[test]
+    fn should_not_compress_batch_given_incompressible_payload() {
+        let bytes = Bytes::from_static(&[7, 3, 1]);
+        let batch = RetainedMessageBatch::new(0, 0, 0, IggyByteSize::from(3), bytes)
+            .compress(CompressionAlgorithm::Gzip)
+            .unwrap();
+        assert!(!batch.is_compressed());
+        assert_eq!(batch.length.as_bytes_u64(), 3);
+    }
+}
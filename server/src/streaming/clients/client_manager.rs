@@ -22,6 +22,7 @@ use ahash::AHashMap;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::handshake::{ProtocolFeature, ProtocolFeatures};
 use iggy::models::user_info::UserId;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt::{Display, Formatter};
@@ -31,6 +32,7 @@ use std::sync::Arc;
 #[derive(Debug, Default)]
 pub struct ClientManager {
     clients: AHashMap<u32, IggySharedMut<Client>>,
+    last_generated_id: u64,
 }
 
 #[derive(Debug)]
@@ -39,7 +41,9 @@ pub struct Client {
     pub session: Arc<Session>,
     pub transport: Transport,
     pub consumer_groups: Vec<ConsumerGroup>,
+    pub transaction: Option<Transaction>,
     pub last_heartbeat: IggyTimestamp,
+    pub sdk_version: Option<String>,
 }
 
 #[derive(Debug)]
@@ -49,10 +53,43 @@ pub struct ConsumerGroup {
     pub group_id: u32,
 }
 
+#[derive(Debug)]
+pub struct Transaction {
+    pub transaction_id: u64,
+    pub partitions: Vec<TransactionPartition>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TransactionPartition {
+    pub stream_id: u32,
+    pub topic_id: u32,
+    pub partition_id: u32,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Transport {
     Tcp,
     Quic,
+    WebSocket,
+}
+
+impl Transport {
+    /// Returns the protocol features enabled for the transport, offered to the clients during the handshake.
+    pub fn protocol_features(&self) -> ProtocolFeatures {
+        match self {
+            Transport::Tcp => ProtocolFeatures::new(&[
+                ProtocolFeature::Multiplexing,
+                ProtocolFeature::Subscriptions,
+                ProtocolFeature::ConsumerGroupGenerations,
+            ]),
+            // Each QUIC request has its own stream already, so there's nothing to multiplex,
+            // while the WebSocket responses are relayed with the regular header, without the request ID.
+            Transport::Quic | Transport::WebSocket => ProtocolFeatures::new(&[
+                ProtocolFeature::Subscriptions,
+                ProtocolFeature::ConsumerGroupGenerations,
+            ]),
+        }
+    }
 }
 
 impl Display for Transport {
@@ -60,6 +97,7 @@ impl Display for Transport {
         match self {
             Transport::Tcp => write!(f, "TCP"),
             Transport::Quic => write!(f, "QUIC"),
+            Transport::WebSocket => write!(f, "WebSocket"),
         }
     }
 }
@@ -73,7 +111,9 @@ impl ClientManager {
             session: session.clone(),
             transport,
             consumer_groups: Vec::new(),
+            transaction: None,
             last_heartbeat: IggyTimestamp::now(),
+            sdk_version: None,
         };
         self.clients.insert(client_id, IggySharedMut::new(client));
         session
@@ -189,6 +229,80 @@ impl ClientManager {
         Ok(())
     }
 
+    pub async fn begin_transaction(&mut self, client_id: u32) -> Result<u64, IggyError> {
+        let client = self.clients.get(&client_id);
+        if client.is_none() {
+            return Err(IggyError::ClientNotFound(client_id));
+        }
+
+        let mut client = client.unwrap().write().await;
+        if let Some(transaction) = client.transaction.as_ref() {
+            return Err(IggyError::TransactionAlreadyOpen(
+                transaction.transaction_id,
+            ));
+        }
+
+        let transaction_id = generate_id(&mut self.last_generated_id);
+        client.transaction = Some(Transaction {
+            transaction_id,
+            partitions: Vec::new(),
+        });
+        Ok(transaction_id)
+    }
+
+    pub fn generate_producer_id(&mut self) -> u64 {
+        generate_id(&mut self.last_generated_id)
+    }
+
+    pub async fn get_transaction_id(&self, client_id: u32) -> Option<u64> {
+        let client = self.clients.get(&client_id)?;
+        let client = client.read().await;
+        client
+            .transaction
+            .as_ref()
+            .map(|transaction| transaction.transaction_id)
+    }
+
+    pub async fn add_transaction_partition(
+        &self,
+        client_id: u32,
+        partition: TransactionPartition,
+    ) -> Result<(), IggyError> {
+        let client = self.clients.get(&client_id);
+        if client.is_none() {
+            return Err(IggyError::ClientNotFound(client_id));
+        }
+
+        let mut client = client.unwrap().write().await;
+        let Some(transaction) = client.transaction.as_mut() else {
+            return Ok(());
+        };
+
+        if !transaction.partitions.contains(&partition) {
+            transaction.partitions.push(partition);
+        }
+        Ok(())
+    }
+
+    pub async fn complete_transaction(
+        &self,
+        client_id: u32,
+        transaction_id: u64,
+    ) -> Result<Transaction, IggyError> {
+        let client = self.clients.get(&client_id);
+        if client.is_none() {
+            return Err(IggyError::ClientNotFound(client_id));
+        }
+
+        let mut client = client.unwrap().write().await;
+        match client.transaction.as_ref() {
+            Some(transaction) if transaction.transaction_id == transaction_id => {
+                Ok(client.transaction.take().unwrap())
+            }
+            _ => Err(IggyError::TransactionNotFound(transaction_id)),
+        }
+    }
+
     pub async fn delete_consumer_groups_for_stream(&self, stream_id: u32) {
         for client in self.clients.values() {
             let mut client = client.write().await;
@@ -232,3 +346,10 @@ impl ClientManager {
         }
     }
 }
+
+/// Generates the transaction and producer IDs, which are based on the timestamp to remain unique also after the server restart.
+fn generate_id(last_id: &mut u64) -> u64 {
+    let id = IggyTimestamp::now().as_micros().max(*last_id + 1);
+    *last_id = id;
+    id
+}
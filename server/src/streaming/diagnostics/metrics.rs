@@ -16,12 +16,43 @@
  * under the License.
  */
 
+use ahash::AHashSet;
+use iggy::consumer::ConsumerKind;
+use iggy::models::consumer_lag::PartitionLag;
 use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::EncodeLabelSet;
 use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
 use prometheus_client::registry::Registry;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
 use tracing::error;
 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PartitionLabels {
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct CommandLabels {
+    command: String,
+    transport: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ConsumerLagLabels {
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+    consumer_kind: String,
+    consumer_id: u32,
+}
+
 #[derive(Debug)]
 pub(crate) struct Metrics {
     registry: Registry,
@@ -33,6 +64,29 @@ pub(crate) struct Metrics {
     messages: Gauge,
     users: Gauge,
     clients: Gauge,
+    consumer_lag_messages: Family<ConsumerLagLabels, Gauge>,
+    consumer_lag_bytes: Family<ConsumerLagLabels, Gauge>,
+    consumer_lag_seconds: Family<ConsumerLagLabels, Gauge<f64, AtomicU64>>,
+    partition_messages_in: Family<PartitionLabels, Counter>,
+    partition_bytes_in: Family<PartitionLabels, Counter>,
+    partition_messages_out: Family<PartitionLabels, Counter>,
+    partition_bytes_out: Family<PartitionLabels, Counter>,
+    partition_deduplicated_messages: Family<PartitionLabels, Counter>,
+    partition_segments: Family<PartitionLabels, Gauge>,
+    partition_unsaved_messages: Family<PartitionLabels, Gauge>,
+    partition_unsaved_bytes: Family<PartitionLabels, Gauge>,
+    partition_append_latency: Family<PartitionLabels, Histogram, fn() -> Histogram>,
+    partition_poll_latency: Family<PartitionLabels, Histogram, fn() -> Histogram>,
+    command_latency: Family<CommandLabels, Histogram, fn() -> Histogram>,
+    /// The partitions having their own label sets, limited to `max_labeled_partitions` (0 means no limit),
+    /// so the number of the exported series can't grow unbounded in the huge deployments.
+    labeled_partitions: RwLock<AHashSet<PartitionLabels>>,
+    max_labeled_partitions: u32,
+}
+
+fn latency_histogram() -> Histogram {
+    // From 50 microseconds up to ~1.6 seconds.
+    Histogram::new(exponential_buckets(0.00005, 2.0, 16))
 }
 
 impl Metrics {
@@ -47,6 +101,22 @@ impl Metrics {
             messages: Gauge::default(),
             users: Gauge::default(),
             clients: Gauge::default(),
+            consumer_lag_messages: Family::default(),
+            consumer_lag_bytes: Family::default(),
+            consumer_lag_seconds: Family::default(),
+            partition_messages_in: Family::default(),
+            partition_bytes_in: Family::default(),
+            partition_messages_out: Family::default(),
+            partition_bytes_out: Family::default(),
+            partition_deduplicated_messages: Family::default(),
+            partition_segments: Family::default(),
+            partition_unsaved_messages: Family::default(),
+            partition_unsaved_bytes: Family::default(),
+            partition_append_latency: Family::new_with_constructor(latency_histogram),
+            partition_poll_latency: Family::new_with_constructor(latency_histogram),
+            command_latency: Family::new_with_constructor(latency_histogram),
+            labeled_partitions: RwLock::new(AHashSet::new()),
+            max_labeled_partitions: 0,
         };
 
         metrics.register_counter("http_requests", metrics.http_requests.clone());
@@ -57,10 +127,84 @@ impl Metrics {
         metrics.register_gauge("messages", metrics.messages.clone());
         metrics.register_gauge("users", metrics.users.clone());
         metrics.register_gauge("clients", metrics.clients.clone());
+        metrics.registry.register(
+            "consumer_lag_messages",
+            "number of messages yet to be consumed by the consumer in the partition",
+            metrics.consumer_lag_messages.clone(),
+        );
+        metrics.registry.register(
+            "consumer_lag_bytes",
+            "size of messages yet to be consumed by the consumer in the partition",
+            metrics.consumer_lag_bytes.clone(),
+        );
+        metrics.registry.register(
+            "consumer_lag_seconds",
+            "age of the oldest message yet to be consumed by the consumer in the partition",
+            metrics.consumer_lag_seconds.clone(),
+        );
+        metrics.registry.register(
+            "partition_messages_in",
+            "number of messages appended to the partition",
+            metrics.partition_messages_in.clone(),
+        );
+        metrics.registry.register(
+            "partition_bytes_in",
+            "size of messages appended to the partition",
+            metrics.partition_bytes_in.clone(),
+        );
+        metrics.registry.register(
+            "partition_messages_out",
+            "number of messages polled from the partition",
+            metrics.partition_messages_out.clone(),
+        );
+        metrics.registry.register(
+            "partition_bytes_out",
+            "size of messages polled from the partition",
+            metrics.partition_bytes_out.clone(),
+        );
+        metrics.registry.register(
+            "partition_deduplicated_messages",
+            "number of messages ignored by the partition due to the duplicated ID",
+            metrics.partition_deduplicated_messages.clone(),
+        );
+        metrics.registry.register(
+            "partition_segments",
+            "number of segments in the partition",
+            metrics.partition_segments.clone(),
+        );
+        metrics.registry.register(
+            "partition_unsaved_messages",
+            "number of messages in the partition which are not saved on disk yet",
+            metrics.partition_unsaved_messages.clone(),
+        );
+        metrics.registry.register(
+            "partition_unsaved_bytes",
+            "size of messages in the partition which are not saved on disk yet",
+            metrics.partition_unsaved_bytes.clone(),
+        );
+        metrics.registry.register(
+            "partition_append_latency_seconds",
+            "latency of appending messages to the partition",
+            metrics.partition_append_latency.clone(),
+        );
+        metrics.registry.register(
+            "partition_poll_latency_seconds",
+            "latency of polling messages from the partition",
+            metrics.partition_poll_latency.clone(),
+        );
+        metrics.registry.register(
+            "command_latency_seconds",
+            "latency of handling the command by the transport",
+            metrics.command_latency.clone(),
+        );
 
         metrics
     }
 
+    pub fn limit_labeled_partitions(&mut self, max_labeled_partitions: u32) {
+        self.max_labeled_partitions = max_labeled_partitions;
+    }
+
     fn register_counter(&mut self, name: &str, counter: Counter) {
         self.registry
             .register(name, format!("total count of {name}"), counter)
@@ -138,4 +282,254 @@ impl Metrics {
     pub fn decrement_clients(&self, count: u32) {
         self.clients.dec_by(count as i64);
     }
+
+    pub fn clear_consumer_lag(&self) {
+        self.consumer_lag_messages.clear();
+        self.consumer_lag_bytes.clear();
+        self.consumer_lag_seconds.clear();
+    }
+
+    pub fn set_consumer_lag(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        consumer_kind: ConsumerKind,
+        consumer_id: u32,
+        lag: &PartitionLag,
+    ) {
+        if self
+            .get_partition_labels(stream_id, topic_id, lag.partition_id)
+            .is_none()
+        {
+            return;
+        }
+
+        let labels = ConsumerLagLabels {
+            stream_id,
+            topic_id,
+            partition_id: lag.partition_id,
+            consumer_kind: consumer_kind.to_string(),
+            consumer_id,
+        };
+        self.consumer_lag_messages
+            .get_or_create(&labels)
+            .set(lag.lag_messages as i64);
+        self.consumer_lag_bytes
+            .get_or_create(&labels)
+            .set(lag.lag_bytes.as_bytes_u64() as i64);
+        self.consumer_lag_seconds
+            .get_or_create(&labels)
+            .set(lag.lag_time.as_secs_f64());
+    }
+
+    pub fn record_appended_messages(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        messages_count: u64,
+        size_bytes: u64,
+        latency: Duration,
+    ) {
+        let Some(labels) = self.get_partition_labels(stream_id, topic_id, partition_id) else {
+            return;
+        };
+
+        self.partition_messages_in
+            .get_or_create(&labels)
+            .inc_by(messages_count);
+        self.partition_bytes_in
+            .get_or_create(&labels)
+            .inc_by(size_bytes);
+        self.partition_append_latency
+            .get_or_create(&labels)
+            .observe(latency.as_secs_f64());
+    }
+
+    pub fn record_polled_messages(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        messages_count: u64,
+        size_bytes: u64,
+        latency: Duration,
+    ) {
+        let Some(labels) = self.get_partition_labels(stream_id, topic_id, partition_id) else {
+            return;
+        };
+
+        self.partition_messages_out
+            .get_or_create(&labels)
+            .inc_by(messages_count);
+        self.partition_bytes_out
+            .get_or_create(&labels)
+            .inc_by(size_bytes);
+        self.partition_poll_latency
+            .get_or_create(&labels)
+            .observe(latency.as_secs_f64());
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_partition_state(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        segments_count: u32,
+        unsaved_messages_count: u64,
+        unsaved_size_bytes: u64,
+        deduplicated_messages_count: u64,
+    ) {
+        let Some(labels) = self.get_partition_labels(stream_id, topic_id, partition_id) else {
+            return;
+        };
+
+        self.partition_segments
+            .get_or_create(&labels)
+            .set(segments_count as i64);
+        self.partition_unsaved_messages
+            .get_or_create(&labels)
+            .set(unsaved_messages_count as i64);
+        self.partition_unsaved_bytes
+            .get_or_create(&labels)
+            .set(unsaved_size_bytes as i64);
+        // The counter is kept by the partition itself, as the duplicates are dropped deep in the append path.
+        self.partition_deduplicated_messages
+            .get_or_create(&labels)
+            .inner()
+            .store(deduplicated_messages_count, Ordering::Relaxed);
+    }
+
+    /// Removes the label sets of the partitions which no longer exist, freeing the slots for the new ones.
+    pub fn retain_partitions(&self, existing_partitions: &AHashSet<(u32, u32, u32)>) {
+        let mut labeled_partitions = self.labeled_partitions.write().unwrap();
+        labeled_partitions.retain(|labels| {
+            if existing_partitions.contains(&(
+                labels.stream_id,
+                labels.topic_id,
+                labels.partition_id,
+            )) {
+                return true;
+            }
+
+            self.partition_messages_in.remove(labels);
+            self.partition_bytes_in.remove(labels);
+            self.partition_messages_out.remove(labels);
+            self.partition_bytes_out.remove(labels);
+            self.partition_deduplicated_messages.remove(labels);
+            self.partition_segments.remove(labels);
+            self.partition_unsaved_messages.remove(labels);
+            self.partition_unsaved_bytes.remove(labels);
+            self.partition_append_latency.remove(labels);
+            self.partition_poll_latency.remove(labels);
+            false
+        });
+    }
+
+    pub fn record_command_latency(&self, command: &str, transport: &str, latency: Duration) {
+        let labels = CommandLabels {
+            command: command.to_owned(),
+            transport: transport.to_owned(),
+        };
+        self.command_latency
+            .get_or_create(&labels)
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Returns the label set of the partition, unless the limit of the labeled partitions has been reached.
+    fn get_partition_labels(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> Option<PartitionLabels> {
+        let labels = PartitionLabels {
+            stream_id,
+            topic_id,
+            partition_id,
+        };
+        if self.labeled_partitions.read().unwrap().contains(&labels) {
+            return Some(labels);
+        }
+
+        let mut labeled_partitions = self.labeled_partitions.write().unwrap();
+        if self.max_labeled_partitions > 0
+            && labeled_partitions.len() >= self.max_labeled_partitions as usize
+            && !labeled_partitions.contains(&labels)
+        {
+            return None;
+        }
+
+        labeled_partitions.insert(labels);
+        Some(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_metrics_should_be_exported_with_labels() {
+        let metrics = Metrics::init();
+        metrics.record_appended_messages(1, 2, 3, 10, 1000, Duration::from_millis(1));
+        metrics.record_polled_messages(1, 2, 3, 5, 500, Duration::from_millis(1));
+        metrics.set_partition_state(1, 2, 3, 4, 6, 600, 7);
+
+        let output = metrics.get_formatted_output();
+        let labels = r#"{stream_id="1",topic_id="2",partition_id="3"}"#;
+        assert!(output.contains(&format!("partition_messages_in_total{labels} 10")));
+        assert!(output.contains(&format!("partition_bytes_in_total{labels} 1000")));
+        assert!(output.contains(&format!("partition_messages_out_total{labels} 5")));
+        assert!(output.contains(&format!("partition_bytes_out_total{labels} 500")));
+        assert!(output.contains(&format!("partition_segments{labels} 4")));
+        assert!(output.contains(&format!("partition_unsaved_messages{labels} 6")));
+        assert!(output.contains(&format!("partition_unsaved_bytes{labels} 600")));
+        assert!(output.contains(&format!("partition_deduplicated_messages_total{labels} 7")));
+        assert!(output.contains(&format!("partition_append_latency_seconds_count{labels} 1")));
+        assert!(output.contains(&format!("partition_poll_latency_seconds_count{labels} 1")));
+    }
+
+    #[test]
+    fn partition_metrics_should_not_be_exported_above_the_labeled_partitions_limit() {
+        let mut metrics = Metrics::init();
+        metrics.limit_labeled_partitions(2);
+        for partition_id in 1..=3 {
+            metrics.record_appended_messages(1, 1, partition_id, 1, 100, Duration::ZERO);
+        }
+
+        let output = metrics.get_formatted_output();
+        assert!(output.contains(r#"partition_id="1""#));
+        assert!(output.contains(r#"partition_id="2""#));
+        assert!(!output.contains(r#"partition_id="3""#));
+    }
+
+    #[test]
+    fn deleted_partitions_should_release_their_labels() {
+        let mut metrics = Metrics::init();
+        metrics.limit_labeled_partitions(1);
+        metrics.record_appended_messages(1, 1, 1, 1, 100, Duration::ZERO);
+        metrics.retain_partitions(&AHashSet::from([(1, 1, 2)]));
+        metrics.record_appended_messages(1, 1, 2, 1, 100, Duration::ZERO);
+
+        let output = metrics.get_formatted_output();
+        assert!(!output.contains(r#"partition_id="1""#));
+        assert!(output.contains(r#"partition_id="2""#));
+    }
+
+    #[test]
+    fn command_latency_should_be_exported_by_transport() {
+        let metrics = Metrics::init();
+        metrics.record_command_latency("message.send", "TCP", Duration::from_millis(1));
+        metrics.record_command_latency("message.send", "QUIC", Duration::from_millis(1));
+
+        let output = metrics.get_formatted_output();
+        assert!(output.contains(
+            r#"command_latency_seconds_count{command="message.send",transport="TCP"} 1"#
+        ));
+        assert!(output.contains(
+            r#"command_latency_seconds_count{command="message.send",transport="QUIC"} 1"#
+        ));
+    }
 }
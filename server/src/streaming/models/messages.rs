@@ -39,6 +39,7 @@ use std::sync::Arc;
 pub struct PolledMessages {
     pub partition_id: u32,
     pub current_offset: u64,
+    pub generation_id: u32,
     pub messages: Vec<Arc<PolledMessage>>,
 }
 
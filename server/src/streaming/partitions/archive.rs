@@ -0,0 +1,638 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::segments::*;
+use crate::streaming::utils::file;
+use bytes::{BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::utils::byte_size::IggyByteSize;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
+use tokio::fs;
+use tracing::{error, info, trace, warn};
+
+/// The size of the single archived segment stored in the partition archived segments file.
+pub const ARCHIVED_SEGMENT_SIZE: usize = 24;
+
+/// The closed segment which has been archived, so its messages can still be fetched from the archive once the local copy is deleted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchivedSegment {
+    pub start_offset: u64,
+    pub end_offset: u64,
+    pub end_timestamp: u64,
+}
+
+impl ArchivedSegment {
+    pub fn as_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(ARCHIVED_SEGMENT_SIZE);
+        bytes.put_u64_le(self.start_offset);
+        bytes.put_u64_le(self.end_offset);
+        bytes.put_u64_le(self.end_timestamp);
+        bytes.freeze()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() != ARCHIVED_SEGMENT_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let read_u64 = |position: usize| {
+            bytes[position..position + 8]
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding)
+        };
+        Ok(ArchivedSegment {
+            start_offset: read_u64(0)?,
+            end_offset: read_u64(8)?,
+            end_timestamp: read_u64(16)?,
+        })
+    }
+}
+
+/// The archived segment fetched into the local cache, its files are removed once it's evicted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedSegment {
+    pub log_path: String,
+    pub index_path: String,
+    pub size: u64,
+}
+
+/// Keeps the archived segments fetched into the runtime directory within the configured size,
+/// shared by all the partitions, so the least recently used segments are evicted first.
+#[derive(Debug)]
+pub struct ArchiveCache {
+    max_size: u64,
+    state: Mutex<ArchiveCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct ArchiveCacheState {
+    size: u64,
+    // The least recently used segment comes first.
+    segments: VecDeque<CachedSegment>,
+}
+
+impl ArchiveCache {
+    pub fn new(max_size: IggyByteSize) -> Self {
+        Self {
+            max_size: max_size.as_bytes_u64(),
+            state: Mutex::new(ArchiveCacheState::default()),
+        }
+    }
+
+    /// Marks the cached segment as the most recently used one, returns `false` if it's not cached.
+    pub fn touch(&self, log_path: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let Some(position) = state
+            .segments
+            .iter()
+            .position(|segment| segment.log_path == log_path)
+        else {
+            return false;
+        };
+        let segment = state.segments.remove(position).unwrap();
+        state.segments.push_back(segment);
+        true
+    }
+
+    /// Adds the fetched segment as the most recently used one and returns the least recently used segments
+    /// which have to be evicted to stay within the size limit. The added segment itself is never evicted.
+    pub fn insert(&self, segment: CachedSegment) -> Vec<CachedSegment> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(position) = state
+            .segments
+            .iter()
+            .position(|cached| cached.log_path == segment.log_path)
+        {
+            let cached = state.segments.remove(position).unwrap();
+            state.size -= cached.size;
+        }
+        state.size += segment.size;
+        state.segments.push_back(segment);
+
+        let mut evicted = Vec::new();
+        while state.size > self.max_size && state.segments.len() > 1 {
+            let segment = state.segments.pop_front().unwrap();
+            state.size -= segment.size;
+            evicted.push(segment);
+        }
+        evicted
+    }
+
+    /// Forgets the cached segments stored in the specified directory, e.g. once the partition cache is deleted.
+    pub fn remove_directory(&self, directory: &str) {
+        let mut state = self.state.lock().unwrap();
+        let prefix = format!("{directory}/");
+        let mut removed_size = 0;
+        state.segments.retain(|segment| {
+            let is_removed = segment.log_path.starts_with(&prefix);
+            if is_removed {
+                removed_size += segment.size;
+            }
+            !is_removed
+        });
+        state.size -= removed_size;
+    }
+
+    pub fn size(&self) -> u64 {
+        self.state.lock().unwrap().size
+    }
+}
+
+impl Partition {
+    /// Registers the segment which has been archived, so that its messages can be served from the archive after the local copy is deleted.
+    pub async fn add_archived_segment(
+        &mut self,
+        archived_segment: ArchivedSegment,
+    ) -> Result<(), IggyError> {
+        self.archived_segments
+            .retain(|segment| segment.start_offset != archived_segment.start_offset);
+        self.archived_segments.push(archived_segment);
+        self.archived_segments
+            .sort_by_key(|segment| segment.start_offset);
+        self.storage
+            .partition
+            .overwrite_archived_segments(&self.archived_segments_path, &self.archived_segments)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to store archived segment: {archived_segment:?}, partition: {self}")
+            })
+    }
+
+    pub async fn load_archived_segments(&mut self) -> Result<(), IggyError> {
+        self.archived_segments = self
+            .storage
+            .partition
+            .load_archived_segments(&self.archived_segments_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segments, partition: {self}")
+            })?;
+        if !self.archived_segments.is_empty() {
+            info!(
+                "Loaded {} archived segments for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+                self.archived_segments.len(),
+                self.partition_id,
+                self.stream_id,
+                self.topic_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Forgets the archived segments, e.g. when the partition is purged, and removes their locally cached copies.
+    pub async fn delete_archived_segments(&mut self) -> Result<(), IggyError> {
+        self.archived_segments.clear();
+        self.storage
+            .partition
+            .overwrite_archived_segments(&self.archived_segments_path, &[])
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete archived segments, partition: {self}")
+            })?;
+        self.delete_archive_cache().await
+    }
+
+    pub(super) async fn delete_archive_cache(&self) -> Result<(), IggyError> {
+        let cache_path =
+            self.config
+                .get_archive_cache_path(self.stream_id, self.topic_id, self.partition_id);
+        self.storage.archive_cache.remove_directory(&cache_path);
+        if Path::new(&cache_path).exists() && fs::remove_dir_all(&cache_path).await.is_err() {
+            error!("Failed to delete archive cache directory: {cache_path} for partition: {self}");
+            return Err(IggyError::CannotDeleteFile);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the messages from the specified offset are no longer stored locally, but can be fetched from the archive.
+    pub(super) fn is_offset_archived(&self, offset: u64) -> bool {
+        self.segments
+            .first()
+            .is_some_and(|segment| offset < segment.start_offset)
+            && self
+                .get_archived_only_segments()
+                .any(|segment| segment.end_offset >= offset)
+    }
+
+    /// Returns `true` if the messages from the specified timestamp are no longer stored locally, but can be fetched from the archive.
+    pub(super) fn is_timestamp_archived(&self, timestamp: u64) -> bool {
+        self.get_archived_only_segments()
+            .any(|segment| segment.end_timestamp >= timestamp)
+    }
+
+    /// Retrieves messages by offset (up to a specified count) from the archived segments whose local copies have been deleted.
+    pub(super) async fn get_archived_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut remaining_count = count;
+        for archived_segment in self.get_archived_only_segments() {
+            if remaining_count == 0 {
+                break;
+            }
+
+            if archived_segment.end_offset < start_offset {
+                continue;
+            }
+
+            let segment = self.load_archived_segment(archived_segment).await?;
+            let segment_messages = segment
+                .get_messages_by_offset(start_offset, remaining_count)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get messages from archived segment, segment: {segment}, \
+                         offset: {start_offset}, count: {remaining_count}"
+                    )
+                })?;
+            remaining_count = remaining_count.saturating_sub(segment_messages.len() as u32);
+            messages.extend(segment_messages);
+        }
+        Ok(messages)
+    }
+
+    /// Retrieves messages by timestamp (up to a specified count) from the archived segments whose local copies have been deleted.
+    pub(super) async fn get_archived_messages_by_timestamp(
+        &self,
+        timestamp: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut remaining_count = count as usize;
+        for archived_segment in self.get_archived_only_segments() {
+            if remaining_count == 0 {
+                break;
+            }
+
+            if archived_segment.end_timestamp < timestamp {
+                continue;
+            }
+
+            let segment = self.load_archived_segment(archived_segment).await?;
+            let segment_messages = segment
+                .get_messages_by_timestamp(timestamp, remaining_count)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get messages from archived segment, segment: {segment}, \
+                         timestamp: {timestamp}, count: {remaining_count}"
+                    )
+                })?;
+            remaining_count -= segment_messages.len();
+            messages.extend(segment_messages);
+        }
+        Ok(messages)
+    }
+
+    fn get_archived_only_segments(&self) -> impl Iterator<Item = &ArchivedSegment> {
+        let first_local_offset = self
+            .segments
+            .first()
+            .map(|segment| segment.start_offset)
+            .unwrap_or(u64::MAX);
+        self.archived_segments
+            .iter()
+            .filter(move |segment| segment.start_offset < first_local_offset)
+    }
+
+    /// Opens the archived segment for reading, fetching its log and index files into the local cache, unless they have been fetched before.
+    async fn load_archived_segment(
+        &self,
+        archived_segment: &ArchivedSegment,
+    ) -> Result<Segment, IggyError> {
+        let start_offset = archived_segment.start_offset;
+        let Some(archiver) = self.storage.archiver.as_ref() else {
+            error!("Cannot fetch archived segment with start offset: {start_offset} for partition: {self}, archiver is disabled.");
+            return Err(IggyError::CannotFetchArchivedSegment(
+                start_offset,
+                self.partition_id,
+            ));
+        };
+
+        let mut segment = Segment::create(
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            start_offset,
+            self.config.clone(),
+            self.message_expiry,
+            self.compression_algorithm,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+        );
+        let cache_path =
+            self.config
+                .get_archive_cache_path(self.stream_id, self.topic_id, self.partition_id);
+        let mut cached_paths = Vec::with_capacity(2);
+        let mut is_fetched = false;
+        for archived_path in [&segment.log_path, &segment.index_path] {
+            let file_name = Path::new(archived_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let cached_path = format!("{cache_path}/{file_name}");
+            if !file::exists(&cached_path).await.unwrap_or(false) {
+                is_fetched = true;
+                // The file is fetched under a temporary name, so the concurrent poll never reads it partially.
+                let fetched_path = format!("{cached_path}.{}", uuid::Uuid::now_v7());
+                trace!(
+                    "Fetching archived file: {archived_path} to: {fetched_path}, partition: {self}"
+                );
+                archiver
+                    .fetch(archived_path, &fetched_path, None)
+                    .await
+                    .map_err(|error| {
+                        error!("Failed to fetch archived file: {archived_path} for partition: {self}. {error}");
+                        IggyError::CannotFetchArchivedSegment(start_offset, self.partition_id)
+                    })?;
+                file::rename(&fetched_path, &cached_path)
+                    .await
+                    .with_error_context(|error| {
+                        format!("{COMPONENT} (error: {error}) - failed to rename fetched file: {fetched_path} to: {cached_path}")
+                    })
+                    .map_err(|_| IggyError::CannotFetchArchivedSegment(start_offset, self.partition_id))?;
+            }
+            cached_paths.push(cached_path);
+        }
+
+        let index_path = cached_paths.pop().unwrap();
+        let log_path = cached_paths.pop().unwrap();
+        let archive_cache = &self.storage.archive_cache;
+        if is_fetched || !archive_cache.touch(&log_path) {
+            let mut size = 0;
+            for cached_path in [&log_path, &index_path] {
+                size += fs::metadata(cached_path)
+                    .await
+                    .map(|metadata| metadata.len())
+                    .unwrap_or_default();
+            }
+            let evicted_segments = archive_cache.insert(CachedSegment {
+                log_path: log_path.clone(),
+                index_path: index_path.clone(),
+                size,
+            });
+            for evicted_segment in evicted_segments {
+                // The segments being read already have their files opened, so they can be removed meanwhile.
+                trace!("Evicting archived segment: {evicted_segment:?} from the cache.");
+                for evicted_path in [&evicted_segment.log_path, &evicted_segment.index_path] {
+                    if let Err(error) = file::remove(evicted_path).await {
+                        warn!("Failed to remove evicted archived file: {evicted_path}. {error}");
+                    }
+                }
+            }
+        }
+
+        segment
+            .load_archived(log_path, index_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segment with start offset: {start_offset}, partition: {self}")
+            })?;
+        Ok(segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::{RuntimeConfig, SegmentConfig, SystemConfig};
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::partitions::create_messages;
+    use crate::streaming::partitions::test_utils::{
+        create_archiver, create_config, create_partition,
+    };
+    use iggy::utils::sizeable::Sizeable;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use tempfile::TempDir;
+
+    #[test]
+    fn archived_segment_should_be_serialized_and_deserialized() {
+        let archived_segment = ArchivedSegment {
+            start_offset: 1,
+            end_offset: 2,
+            end_timestamp: 3,
+        };
+
+        let bytes = archived_segment.as_bytes();
+
+        assert_eq!(bytes.len(), ARCHIVED_SEGMENT_SIZE);
+        assert_eq!(
+            ArchivedSegment::from_bytes(&bytes).unwrap(),
+            archived_segment
+        );
+    }
+
+    #[tokio::test]
+    async fn messages_of_deleted_segments_should_be_fetched_from_archive() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_archive_config(&tempdir, IggyByteSize::from(u64::MAX));
+        let mut partition = create_partition(config, Some(create_archiver(&tempdir)), 1).await;
+        let messages_count = append_messages_in_segments(&mut partition, 3).await;
+        archive_and_delete_closed_segments(&mut partition).await;
+
+        let messages = partition
+            .get_messages_by_offset(0, messages_count as u32)
+            .await
+            .unwrap();
+        let timestamp_messages = partition
+            .get_messages_by_timestamp(IggyTimestamp::zero(), messages_count as u32)
+            .await
+            .unwrap();
+
+        assert_eq!(partition.segments.len(), 1);
+        assert_eq!(messages.len() as u64, messages_count);
+        assert_eq!(timestamp_messages.len() as u64, messages_count);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn archived_segments_should_be_loaded() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_archive_config(&tempdir, IggyByteSize::from(u64::MAX));
+        let mut partition = create_partition(config, Some(create_archiver(&tempdir)), 1).await;
+        let archived_segment = ArchivedSegment {
+            start_offset: 0,
+            end_offset: 5,
+            end_timestamp: 10,
+        };
+        partition
+            .add_archived_segment(archived_segment)
+            .await
+            .unwrap();
+        partition.archived_segments.clear();
+
+        partition.load_archived_segments().await.unwrap();
+
+        assert_eq!(partition.archived_segments, vec![archived_segment]);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_archived_segments_should_be_evicted_from_cache() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_archive_config(&tempdir, IggyByteSize::from(1));
+        let mut partition = create_partition(config, Some(create_archiver(&tempdir)), 1).await;
+        append_messages_in_segments(&mut partition, 3).await;
+        archive_and_delete_closed_segments(&mut partition).await;
+        let cache_path = partition.config.get_archive_cache_path(
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+        );
+        let archived_segments = partition.archived_segments.clone();
+        assert_eq!(archived_segments.len(), 2);
+
+        for archived_segment in &archived_segments {
+            partition
+                .get_messages_by_offset(archived_segment.start_offset, 1)
+                .await
+                .unwrap();
+        }
+
+        let cached_files = std::fs::read_dir(&cache_path).unwrap().count();
+        assert_eq!(cached_files, 2);
+        let last_segment = archived_segments.last().unwrap();
+        let segment = partition.load_archived_segment(last_segment).await.unwrap();
+        assert!(file::exists(&segment.log_path).await.unwrap());
+        assert_eq!(
+            partition.storage.archive_cache.size(),
+            std::fs::metadata(&segment.log_path).unwrap().len()
+                + std::fs::metadata(&segment.index_path).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn archive_cache_should_evict_least_recently_used_segments() {
+        let cache = ArchiveCache::new(IggyByteSize::from(20));
+        let first = create_cached_segment("cache/1/0", 10);
+        let second = create_cached_segment("cache/1/1", 10);
+        let third = create_cached_segment("cache/1/2", 10);
+
+        assert!(cache.insert(first.clone()).is_empty());
+        assert!(cache.insert(second.clone()).is_empty());
+        assert!(cache.touch(&first.log_path));
+        let evicted = cache.insert(third);
+
+        assert_eq!(evicted, vec![second.clone()]);
+        assert!(!cache.touch(&second.log_path));
+        assert_eq!(cache.size(), 20);
+    }
+
+    #[test]
+    fn archive_cache_should_forget_segments_of_removed_directory() {
+        let cache = ArchiveCache::new(IggyByteSize::from(100));
+        let first = create_cached_segment("cache/1/0", 10);
+        let second = create_cached_segment("cache/2/0", 10);
+        cache.insert(first.clone());
+        cache.insert(second.clone());
+
+        cache.remove_directory("cache/1");
+
+        assert!(!cache.touch(&first.log_path));
+        assert!(cache.touch(&second.log_path));
+        assert_eq!(cache.size(), 10);
+    }
+
+    fn create_cached_segment(path: &str, size: u64) -> CachedSegment {
+        CachedSegment {
+            log_path: format!("{path}.log"),
+            index_path: format!("{path}.index"),
+            size,
+        }
+    }
+
+    async fn archive_and_delete_closed_segments(partition: &mut Partition) {
+        let archiver = partition.storage.archiver.clone().unwrap();
+        let archived_offsets = partition
+            .segments
+            .iter()
+            .take(partition.segments.len() - 1)
+            .map(|segment| segment.start_offset)
+            .collect::<Vec<_>>();
+        for start_offset in archived_offsets {
+            let segment = partition.get_segment(start_offset).unwrap();
+            let files = [segment.index_path.as_ref(), segment.log_path.as_ref()];
+            archiver.archive(&files, None).await.unwrap();
+            let archived_segment = ArchivedSegment {
+                start_offset,
+                end_offset: segment.end_offset,
+                end_timestamp: segment.end_timestamp,
+            };
+            partition
+                .add_archived_segment(archived_segment)
+                .await
+                .unwrap();
+            partition.delete_segment(start_offset).await.unwrap();
+        }
+        if let Some(cache) = partition.cache.as_mut() {
+            cache.purge();
+        }
+    }
+
+    async fn append_messages_in_segments(partition: &mut Partition, batches: u32) -> u64 {
+        let mut messages_count = 0;
+        for _ in 0..batches {
+            let messages = create_messages();
+            messages_count += messages.len() as u64;
+            let appendable_batch_info = AppendableBatchInfo {
+                batch_size: messages
+                    .iter()
+                    .map(|m| m.get_size_bytes())
+                    .sum::<IggyByteSize>(),
+                partition_id: partition.partition_id,
+            };
+            partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await
+                .unwrap();
+        }
+        messages_count
+    }
+
+    fn create_archive_config(
+        tempdir: &TempDir,
+        archive_cache_size: IggyByteSize,
+    ) -> Arc<SystemConfig> {
+        Arc::new(SystemConfig {
+            segment: SegmentConfig {
+                size: IggyByteSize::from(1),
+                ..Default::default()
+            },
+            runtime: RuntimeConfig {
+                archive_cache_size,
+                ..Default::default()
+            },
+            ..create_config(tempdir)
+        })
+    }
+}
@@ -23,6 +23,10 @@ use dashmap::DashMap;
 use error_set::ErrContext;
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use iggy::models::consumer_lag::PartitionLag;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
 use tracing::trace;
 
 impl Partition {
@@ -55,6 +59,87 @@ impl Partition {
         Ok(None)
     }
 
+    /// Calculates how far behind the consumer is, i.e. the messages following its stored offset, or all of them if it hasn't stored any.
+    /// The size of the partially consumed segment is estimated based on its average message size,
+    /// while the time is measured since the first message which is yet to be consumed was appended.
+    pub async fn get_consumer_lag(
+        &self,
+        kind: ConsumerKind,
+        consumer_id: u32,
+    ) -> Result<PartitionLag, IggyError> {
+        let stored_offset = self
+            .get_consumer_offsets(kind)
+            .get(&consumer_id)
+            .map(|consumer_offset| consumer_offset.offset);
+        let mut lag = PartitionLag {
+            partition_id: self.partition_id,
+            current_offset: self.current_offset,
+            stored_offset,
+            lag_messages: 0,
+            lag_bytes: IggyByteSize::default(),
+            lag_time: IggyDuration::default(),
+        };
+        if !self.should_increment_offset || self.segments.is_empty() {
+            return Ok(lag);
+        }
+
+        let first_local_offset = self.segments[0].start_offset;
+        let start_offset = stored_offset
+            .map(|offset| offset + 1)
+            .unwrap_or(first_local_offset)
+            .max(first_local_offset);
+        if start_offset > self.current_offset {
+            return Ok(lag);
+        }
+
+        lag.lag_messages = self.current_offset + 1 - start_offset;
+        let mut lag_bytes = 0;
+        for segment in &self.segments {
+            if segment.start_offset >= start_offset {
+                lag_bytes += segment.size_bytes.as_bytes_u64();
+            } else if segment.current_offset >= start_offset {
+                let messages_count = segment.current_offset - segment.start_offset + 1;
+                let unconsumed_messages_count = segment.current_offset + 1 - start_offset;
+                lag_bytes +=
+                    segment.size_bytes.as_bytes_u64() * unconsumed_messages_count / messages_count;
+            }
+        }
+        lag.lag_bytes = lag_bytes.into();
+
+        let messages = self
+            .get_messages_by_offset(start_offset, 1)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get the first unconsumed message, offset: {start_offset}, partition: {}", self.partition_id)
+            })?;
+        if let Some(message) = messages.first() {
+            lag.lag_time = IggyTimestamp::now()
+                .as_micros()
+                .saturating_sub(message.timestamp)
+                .into();
+        }
+        Ok(lag)
+    }
+
+    /// Calculates the lag of all the consumers and consumer groups which have stored their offsets in the partition.
+    pub async fn get_consumers_lag(
+        &self,
+    ) -> Result<Vec<(ConsumerKind, u32, PartitionLag)>, IggyError> {
+        let mut consumers_lag = Vec::new();
+        for kind in [ConsumerKind::Consumer, ConsumerKind::ConsumerGroup] {
+            let consumer_ids = self
+                .get_consumer_offsets(kind)
+                .iter()
+                .map(|consumer_offset| consumer_offset.consumer_id)
+                .collect::<Vec<_>>();
+            for consumer_id in consumer_ids {
+                let lag = self.get_consumer_lag(kind, consumer_id).await?;
+                consumers_lag.push((kind, consumer_id, lag));
+            }
+        }
+        Ok(consumers_lag)
+    }
+
     pub async fn store_consumer_offset(
         &self,
         consumer: PollingConsumer,
@@ -84,9 +169,28 @@ impl Partition {
             }
         };
 
+        self.remove_committed_failed_deliveries();
         Ok(())
     }
 
+    /// Removes the failed deliveries of the messages which all the consumers and consumer groups have already committed.
+    fn remove_committed_failed_deliveries(&self) {
+        if self.failed_deliveries.is_empty() {
+            return;
+        }
+
+        let committed_offset = self
+            .consumer_offsets
+            .iter()
+            .chain(self.consumer_group_offsets.iter())
+            .map(|consumer_offset| consumer_offset.offset)
+            .min();
+        if let Some(committed_offset) = committed_offset {
+            self.failed_deliveries
+                .retain(|offset, _| *offset > committed_offset);
+        }
+    }
+
     async fn store_offset(
         &self,
         kind: ConsumerKind,
@@ -219,3 +323,117 @@ impl Partition {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::partitions::create_messages;
+    use crate::streaming::partitions::test_utils::{create_config, create_partition};
+    use crate::streaming::polling_consumer::PollingConsumer;
+    use iggy::utils::sizeable::Sizeable;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const CONSUMER_ID: u32 = 1;
+
+    #[tokio::test]
+    async fn lag_of_empty_partition_should_be_zero() {
+        let tempdir = TempDir::new().unwrap();
+        let partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::Consumer, CONSUMER_ID)
+            .await
+            .unwrap();
+
+        assert_eq!(lag.stored_offset, None);
+        assert_eq!(lag.lag_messages, 0);
+        assert_eq!(lag.lag_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn lag_of_consumer_without_stored_offset_should_include_all_messages() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        let messages_count = append_messages(&mut partition).await;
+
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::Consumer, CONSUMER_ID)
+            .await
+            .unwrap();
+
+        assert_eq!(lag.stored_offset, None);
+        assert_eq!(lag.lag_messages, messages_count);
+        assert_eq!(lag.lag_bytes, partition.get_size_bytes());
+    }
+
+    #[tokio::test]
+    async fn lag_should_include_messages_following_stored_offset() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        let messages_count = append_messages(&mut partition).await;
+        let stored_offset = 1;
+        partition
+            .store_consumer_offset(
+                PollingConsumer::consumer_group(CONSUMER_ID, 1),
+                stored_offset,
+            )
+            .await
+            .unwrap();
+
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::ConsumerGroup, CONSUMER_ID)
+            .await
+            .unwrap();
+        let consumer_lag = partition
+            .get_consumer_lag(ConsumerKind::Consumer, CONSUMER_ID)
+            .await
+            .unwrap();
+
+        assert_eq!(lag.stored_offset, Some(stored_offset));
+        assert_eq!(lag.lag_messages, messages_count - stored_offset - 1);
+        assert!(lag.lag_bytes > 0 && lag.lag_bytes < partition.get_size_bytes());
+        assert_eq!(consumer_lag.lag_messages, messages_count);
+    }
+
+    #[tokio::test]
+    async fn lag_of_consumer_which_consumed_all_messages_should_be_zero() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition).await;
+        partition
+            .store_consumer_offset(
+                PollingConsumer::consumer(&CONSUMER_ID.try_into().unwrap(), 1),
+                partition.current_offset,
+            )
+            .await
+            .unwrap();
+
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::Consumer, CONSUMER_ID)
+            .await
+            .unwrap();
+
+        assert_eq!(lag.lag_messages, 0);
+        assert_eq!(lag.lag_bytes, 0);
+        assert_eq!(lag.lag_time, IggyDuration::default());
+    }
+
+    async fn append_messages(partition: &mut Partition) -> u64 {
+        let messages = create_messages();
+        let messages_count = messages.len() as u64;
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        messages_count
+    }
+}
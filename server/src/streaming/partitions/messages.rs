@@ -27,10 +27,12 @@ use error_set::ErrContext;
 use iggy::confirmation::Confirmation;
 use iggy::error::IggyError;
 use iggy::messages::send_messages::Message;
-use iggy::models::messages::POLLED_MESSAGE_METADATA;
+use iggy::models::messages::{MessageState, POLLED_MESSAGE_METADATA};
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::sizeable::Sizeable;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::sync::{atomic::Ordering, Arc};
-use tracing::{trace, warn};
+use tracing::{info, trace, warn};
 
 const EMPTY_MESSAGES: Vec<RetainedMessage> = vec![];
 
@@ -55,6 +57,19 @@ impl Partition {
         let mut messages = Vec::new();
         let mut remaining = count as usize;
 
+        if self.is_timestamp_archived(query_ts) {
+            messages = self
+                .get_archived_messages_by_timestamp(query_ts, count)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to get archived messages by timestamp: {query_ts}, partition: {self}")
+                })?;
+            remaining -= messages.len();
+            if remaining == 0 {
+                return Ok(messages);
+            }
+        }
+
         for segment in &self.segments {
             if segment.end_timestamp < query_ts {
                 continue;
@@ -98,6 +113,33 @@ impl Partition {
             return Ok(Vec::new());
         }
 
+        if !self.is_offset_archived(start_offset) {
+            return self.get_local_messages_by_offset(start_offset, count).await;
+        }
+
+        // The messages whose local segments have been deleted are fetched from the archive, followed by the local ones.
+        let mut messages = self
+            .get_archived_messages_by_offset(start_offset, count)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get archived messages by offset: {start_offset}, partition: {self}")
+            })?;
+        let remaining_count = count - messages.len() as u32;
+        if remaining_count > 0 {
+            let first_local_offset = self.segments[0].start_offset;
+            let local_messages = self
+                .get_local_messages_by_offset(first_local_offset, remaining_count)
+                .await?;
+            messages.extend(local_messages);
+        }
+        Ok(messages)
+    }
+
+    async fn get_local_messages_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
         let end_offset = self.get_end_offset(start_offset, count);
         if let Some(cached) = self.try_get_messages_from_cache(start_offset, end_offset) {
             return Ok(cached);
@@ -371,29 +413,44 @@ impl Partition {
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
     ) -> Result<(), IggyError> {
-        {
-            let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
-            if last_segment.is_closed {
-                let start_offset = last_segment.end_offset + 1;
-                trace!(
-                    "Current segment is closed, creating new segment with start offset: {} for partition with ID: {}...",
-                    start_offset, self.partition_id
-                );
-                self.add_persisted_segment(start_offset).await.with_error_context(|error| format!(
-                    "{COMPONENT} (error: {error}) - failed to add persisted segment, partition: {}, start offset: {}",
-                    self, start_offset,
-                ))?;
-            }
+        self.append_messages_with_state(
+            appendable_batch_info,
+            messages,
+            MessageState::Available,
+            confirmation,
+        )
+        .await
+    }
+
+    pub async fn append_messages_with_state(
+        &mut self,
+        appendable_batch_info: AppendableBatchInfo,
+        messages: Vec<Message>,
+        message_state: MessageState,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(), IggyError> {
+        let (batch_size, retained_messages) = self
+            .prepare_retained_messages(appendable_batch_info, messages, message_state)
+            .await;
+        if retained_messages.is_empty() {
+            return Ok(());
         }
 
+        self.append_retained_messages(batch_size, retained_messages, confirmation)
+            .await
+    }
+
+    /// Assigns the offsets to the messages which are not the duplicates, without appending them yet,
+    /// and returns them along with the size of the batch to append.
+    pub(crate) async fn prepare_retained_messages(
+        &mut self,
+        appendable_batch_info: AppendableBatchInfo,
+        messages: Vec<Message>,
+        message_state: MessageState,
+    ) -> (IggyByteSize, Vec<Arc<RetainedMessage>>) {
         let batch_size = appendable_batch_info.batch_size
             + ((POLLED_MESSAGE_METADATA * messages.len() as u32) as u64).into();
-        let base_offset = if !self.should_increment_offset {
-            0
-        } else {
-            self.current_offset + 1
-        };
-
+        let base_offset = self.get_next_offset();
         let mut messages_count = 0u32;
         let mut retained_messages = Vec::with_capacity(messages.len());
         if let Some(message_deduplicator) = &self.message_deduplicator {
@@ -403,11 +460,14 @@ impl Partition {
                         "Ignored the duplicated message ID: {} for partition with ID: {}.",
                         message.id, self.partition_id
                     );
+                    self.deduplicated_messages_count += 1;
                     continue;
                 }
                 let now = IggyTimestamp::now().as_micros();
                 let message_offset = base_offset + messages_count as u64;
-                let message = Arc::new(RetainedMessage::new(message_offset, now, message));
+                let mut message = RetainedMessage::new(message_offset, now, message);
+                message.message_state = message_state;
+                let message = Arc::new(message);
                 retained_messages.push(message.clone());
                 messages_count += 1;
             }
@@ -415,16 +475,77 @@ impl Partition {
             for message in messages {
                 let now = IggyTimestamp::now().as_micros();
                 let message_offset = base_offset + messages_count as u64;
-                let message = Arc::new(RetainedMessage::new(message_offset, now, message));
+                let mut message = RetainedMessage::new(message_offset, now, message);
+                message.message_state = message_state;
+                let message = Arc::new(message);
                 retained_messages.push(message.clone());
                 messages_count += 1;
             }
         }
-        if messages_count == 0 {
+        (batch_size, retained_messages)
+    }
+
+    /// Appends the messages fetched from the leader partition, keeping their offsets, timestamps and states.
+    /// The first message must directly follow the last message stored in the partition.
+    pub async fn append_replicated_messages(
+        &mut self,
+        messages: Vec<RetainedMessage>,
+    ) -> Result<(), IggyError> {
+        let Some(first_message) = messages.first() else {
             return Ok(());
+        };
+
+        let next_offset = self.get_next_offset();
+        if first_message.offset != next_offset {
+            return Err(IggyError::InvalidOffset(first_message.offset));
+        }
+
+        let mut batch_size = IggyByteSize::default();
+        let mut retained_messages = Vec::with_capacity(messages.len());
+        for (expected_offset, message) in (next_offset..).zip(messages) {
+            if message.offset != expected_offset {
+                return Err(IggyError::InvalidOffset(message.offset));
+            }
+            batch_size += message.get_size_bytes();
+            retained_messages.push(Arc::new(message));
+        }
+
+        self.append_retained_messages(batch_size, retained_messages, None)
+            .await
+    }
+
+    /// Returns the offset that will be assigned to the next appended message.
+    pub fn get_next_offset(&self) -> u64 {
+        if self.should_increment_offset {
+            self.current_offset + 1
+        } else {
+            0
+        }
+    }
+
+    pub(crate) async fn append_retained_messages(
+        &mut self,
+        batch_size: IggyByteSize,
+        retained_messages: Vec<Arc<RetainedMessage>>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(), IggyError> {
+        {
+            let last_segment = self.segments.last_mut().ok_or(IggyError::SegmentNotFound)?;
+            if last_segment.is_closed {
+                let start_offset = last_segment.end_offset + 1;
+                trace!(
+                    "Current segment is closed, creating new segment with start offset: {} for partition with ID: {}...",
+                    start_offset, self.partition_id
+                );
+                self.add_persisted_segment(start_offset).await.with_error_context(|error| format!(
+                    "{COMPONENT} (error: {error}) - failed to add persisted segment, partition: {}, start offset: {}",
+                    self, start_offset,
+                ))?;
+            }
         }
 
-        let last_offset = base_offset + (messages_count - 1) as u64;
+        let messages_count = retained_messages.len() as u32;
+        let last_offset = retained_messages[retained_messages.len() - 1].offset;
         if self.should_increment_offset {
             self.current_offset = last_offset;
         } else {
@@ -465,6 +586,7 @@ impl Partition {
             }
         }
 
+        self.messages_appended.notify_waiters();
         Ok(())
     }
 
@@ -472,6 +594,88 @@ impl Partition {
         self.messages_count.load(Ordering::SeqCst)
     }
 
+    /// Registers the failed delivery (nack) of the message with the given offset and returns the number of its failed deliveries so far.
+    pub fn register_failed_delivery(&self, offset: u64) -> u32 {
+        let mut failed_deliveries = self.failed_deliveries.entry(offset).or_insert(0);
+        *failed_deliveries += 1;
+        *failed_deliveries
+    }
+
+    pub fn remove_failed_deliveries(&self, offset: u64) {
+        self.failed_deliveries.remove(&offset);
+    }
+
+    pub fn is_poisoned(&self, offset: u64) -> bool {
+        self.poisoned_offsets.contains(&offset)
+    }
+
+    /// Marks the message with the given offset as poisoned, once it has been moved to the dead letter topic.
+    pub async fn mark_poisoned(&mut self, offset: u64) -> Result<(), IggyError> {
+        if !self.poisoned_offsets.insert(offset) {
+            return Ok(());
+        }
+
+        self.storage
+            .partition
+            .append_poisoned_offset(&self.poisoned_offsets_path, offset)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to mark offset: {offset} as poisoned, partition: {self}")
+            })
+    }
+
+    pub async fn load_poisoned_offsets(&mut self) -> Result<(), IggyError> {
+        let offsets = self
+            .storage
+            .partition
+            .load_poisoned_offsets(&self.poisoned_offsets_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load poisoned offsets, partition: {self}")
+            })?;
+        // The offsets beyond the current one belong to the messages which haven't been persisted.
+        self.poisoned_offsets = offsets
+            .into_iter()
+            .filter(|offset| self.should_increment_offset && *offset <= self.current_offset)
+            .collect();
+        Ok(())
+    }
+
+    /// Removes the poisoned offsets (and the failed deliveries) of the messages which have already been deleted
+    /// from the partition, e.g. due to the message expiry or the topic size limit, so they are not kept forever.
+    pub async fn remove_deleted_poisoned_offsets(&mut self) -> Result<(), IggyError> {
+        let first_offset = match self.segments.first() {
+            Some(segment) => segment.start_offset,
+            None => self.current_offset + 1,
+        };
+        self.failed_deliveries
+            .retain(|offset, _| *offset >= first_offset);
+        let offsets_count = self.poisoned_offsets.len();
+        self.poisoned_offsets
+            .retain(|offset| *offset >= first_offset);
+        let deleted_offsets_count = offsets_count - self.poisoned_offsets.len();
+        if deleted_offsets_count == 0 {
+            return Ok(());
+        }
+
+        let mut offsets = self.poisoned_offsets.iter().copied().collect::<Vec<_>>();
+        offsets.sort_unstable();
+        self.storage
+            .partition
+            .overwrite_poisoned_offsets(&self.poisoned_offsets_path, &offsets)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to overwrite poisoned offsets, partition: {self}")
+            })?;
+        info!(
+            "Removed {deleted_offsets_count} deleted poisoned offsets for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+            self.partition_id,
+            self.stream_id,
+            self.topic_id
+        );
+        Ok(())
+    }
+
     pub async fn flush_unsaved_buffer(&mut self, fsync: bool) -> Result<(), IggyError> {
         let _fsync = fsync;
         if self.unsaved_messages_count == 0 {
@@ -498,20 +702,18 @@ impl Partition {
 #[cfg(test)]
 mod tests {
     use iggy::utils::byte_size::IggyByteSize;
-    use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::sizeable::Sizeable;
-    use std::sync::atomic::{AtomicU32, AtomicU64};
     use tempfile::TempDir;
 
     use super::*;
     use crate::configs::system::{MessageDeduplicationConfig, SystemConfig};
     use crate::streaming::partitions::create_messages;
-    use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
-    use crate::streaming::storage::SystemStorage;
+    use crate::streaming::partitions::test_utils::{create_config, create_partition};
 
     #[tokio::test]
     async fn given_disabled_message_deduplication_all_messages_should_be_appended() {
-        let (mut partition, _tempdir) = create_partition(false).await;
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
         let messages = create_messages();
         let messages_count = messages.len() as u32;
         let appendable_batch_info = AppendableBatchInfo {
@@ -531,11 +733,20 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(loaded_messages.len(), messages_count as usize);
+        assert_eq!(partition.deduplicated_messages_count, 0);
     }
 
     #[tokio::test]
     async fn given_enabled_message_deduplication_only_messages_with_unique_id_should_be_appended() {
-        let (mut partition, _tempdir) = create_partition(true).await;
+        let tempdir = TempDir::new().unwrap();
+        let config = Arc::new(SystemConfig {
+            message_deduplication: MessageDeduplicationConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..create_config(&tempdir)
+        });
+        let mut partition = create_partition(config, None, 1).await;
         let messages = create_messages();
         let messages_count = messages.len() as u32;
         let unique_messages_count = 3;
@@ -556,45 +767,90 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(loaded_messages.len(), unique_messages_count);
+        assert_eq!(
+            partition.deduplicated_messages_count,
+            messages_count as u64 - unique_messages_count as u64
+        );
     }
 
-    async fn create_partition(deduplication_enabled: bool) -> (Partition, TempDir) {
-        let stream_id = 1;
-        let topic_id = 2;
-        let partition_id = 3;
-        let with_segment = true;
-        let temp_dir = TempDir::new().unwrap();
-        let config = Arc::new(SystemConfig {
-            path: temp_dir.path().to_path_buf().to_str().unwrap().to_string(),
-            message_deduplication: MessageDeduplicationConfig {
-                enabled: deduplication_enabled,
-                ..Default::default()
-            },
-            ..Default::default()
-        });
-        let storage = Arc::new(SystemStorage::new(
-            config.clone(),
-            Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
-        ));
-
-        (
-            Partition::create(
-                stream_id,
-                topic_id,
-                partition_id,
-                with_segment,
-                config,
-                storage,
-                IggyExpiry::NeverExpire,
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU64::new(0)),
-                Arc::new(AtomicU32::new(0)),
-                IggyTimestamp::now(),
-            )
-            .await,
-            temp_dir,
-        )
+    #[tokio::test]
+    async fn given_replicated_messages_they_should_be_appended_with_their_offsets_and_timestamps() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        let timestamp = IggyTimestamp::now().as_micros() - 1000;
+        let messages = create_messages()
+            .into_iter()
+            .enumerate()
+            .map(|(offset, message)| {
+                RetainedMessage::new(offset as u64, timestamp + offset as u64, message)
+            })
+            .collect::<Vec<_>>();
+        let messages_count = messages.len() as u32;
+
+        partition
+            .append_replicated_messages(messages)
+            .await
+            .unwrap();
+
+        let loaded_messages = partition
+            .get_messages_by_offset(0, messages_count)
+            .await
+            .unwrap();
+        assert_eq!(loaded_messages.len(), messages_count as usize);
+        assert_eq!(partition.get_next_offset(), messages_count as u64);
+        for (offset, message) in loaded_messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+            assert_eq!(message.timestamp, timestamp + offset as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn given_replicated_messages_not_following_the_last_offset_they_should_be_rejected() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        let timestamp = IggyTimestamp::now().as_micros();
+        let messages = create_messages()
+            .into_iter()
+            .enumerate()
+            .map(|(offset, message)| RetainedMessage::new(offset as u64 + 1, timestamp, message))
+            .collect::<Vec<_>>();
+
+        let result = partition.append_replicated_messages(messages).await;
+
+        assert_eq!(result.unwrap_err(), IggyError::InvalidOffset(1));
+        assert_eq!(partition.get_next_offset(), 0);
+    }
+
+    #[tokio::test]
+    async fn poisoned_offset_should_be_removed_once_its_message_is_deleted() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        partition.persist().await.unwrap();
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_messages(appendable_batch_info, messages, None)
+            .await
+            .unwrap();
+        partition.register_failed_delivery(0);
+        partition.mark_poisoned(0).await.unwrap();
+
+        partition.delete_segment(0).await.unwrap();
+
+        assert!(!partition.is_poisoned(0));
+        assert!(partition.failed_deliveries.is_empty());
+        let offsets = partition
+            .storage
+            .partition
+            .load_poisoned_offsets(&partition.poisoned_offsets_path)
+            .await
+            .unwrap();
+        assert!(offsets.is_empty());
     }
 }
@@ -19,12 +19,19 @@
 use bytes::Bytes;
 use iggy::messages::send_messages;
 
+pub mod archive;
 pub mod consumer_offsets;
 pub mod messages;
 pub mod partition;
 pub mod persistence;
+pub mod producers;
+pub mod replicas;
+pub mod restore;
 pub mod segments;
 pub mod storage;
+#[cfg(test)]
+mod test_utils;
+pub mod transactions;
 
 pub const COMPONENT: &str = "STREAMING_PARTITIONS";
 
@@ -21,9 +21,14 @@ use crate::streaming::cache::buffer::SmartCache;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
 use crate::streaming::deduplication::message_deduplicator::MessageDeduplicator;
 use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::archive::ArchivedSegment;
+use crate::streaming::partitions::producers::ProducerState;
+use crate::streaming::partitions::replicas::ReplicaOffsets;
 use crate::streaming::segments::*;
 use crate::streaming::storage::SystemStorage;
+use ahash::{AHashMap, AHashSet};
 use dashmap::DashMap;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::ConsumerKind;
 use iggy::models::stats::CacheMetrics;
 use iggy::utils::byte_size::IggyByteSize;
@@ -34,6 +39,7 @@ use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::Notify;
 
 #[derive(Debug)]
 pub struct Partition {
@@ -44,11 +50,16 @@ pub struct Partition {
     pub offsets_path: String,
     pub consumer_offsets_path: String,
     pub consumer_group_offsets_path: String,
+    pub transactions_path: String,
+    pub producers_path: String,
+    pub archived_segments_path: String,
+    pub poisoned_offsets_path: String,
     pub current_offset: u64,
     pub cache: Option<SmartCache<Arc<RetainedMessage>>>,
     pub cached_memory_tracker: Option<Arc<CacheMemoryTracker>>,
     pub message_deduplicator: Option<MessageDeduplicator>,
     pub unsaved_messages_count: u32,
+    pub deduplicated_messages_count: u64,
     pub should_increment_offset: bool,
     pub created_at: IggyTimestamp,
     pub avg_timestamp_delta: IggyDuration,
@@ -60,8 +71,22 @@ pub struct Partition {
     pub size_bytes: Arc<AtomicU64>,
     pub segments_count_of_parent_stream: Arc<AtomicU32>,
     pub(crate) message_expiry: IggyExpiry,
+    pub(crate) compression_algorithm: CompressionAlgorithm,
     pub(crate) consumer_offsets: DashMap<u32, ConsumerOffset>,
     pub(crate) consumer_group_offsets: DashMap<u32, ConsumerOffset>,
+    /// The number of failed deliveries (nacks) per offset, kept in memory only, thus reset on restart.
+    /// The entries are removed once all the consumers and consumer groups have stored an offset past them.
+    pub(crate) failed_deliveries: DashMap<u64, u32>,
+    /// The offsets of the messages moved to the dead letter topic, which are polled as poisoned.
+    pub(crate) poisoned_offsets: AHashSet<u64>,
+    pub(crate) replica_offsets: Arc<ReplicaOffsets>,
+    pub(crate) open_transactions: AHashMap<u64, Vec<(u64, u64)>>,
+    pub(crate) aborted_offsets: Vec<(u64, u64)>,
+    pub(crate) producer_states: AHashMap<u64, ProducerState>,
+    pub(crate) appended_producer_states: usize,
+    pub(crate) archived_segments: Vec<ArchivedSegment>,
+    /// Wakes up the polls waiting for the new messages, whenever they're appended or become visible to the read committed consumers.
+    pub(crate) messages_appended: Arc<Notify>,
     pub(crate) segments: Vec<Segment>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) storage: Arc<SystemStorage>,
@@ -96,6 +121,7 @@ impl Partition {
         config: Arc<SystemConfig>,
         storage: Arc<SystemStorage>,
         message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
         messages_count_of_parent_stream: Arc<AtomicU64>,
         messages_count_of_parent_topic: Arc<AtomicU64>,
         size_of_parent_stream: Arc<AtomicU64>,
@@ -109,6 +135,13 @@ impl Partition {
             config.get_consumer_offsets_path(stream_id, topic_id, partition_id);
         let consumer_group_offsets_path =
             config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id);
+        let transactions_path =
+            config.get_partition_transactions_path(stream_id, topic_id, partition_id);
+        let producers_path = config.get_partition_producers_path(stream_id, topic_id, partition_id);
+        let archived_segments_path =
+            config.get_partition_archived_segments_path(stream_id, topic_id, partition_id);
+        let poisoned_offsets_path =
+            config.get_partition_poisoned_offsets_path(stream_id, topic_id, partition_id);
         let (cached_memory_tracker, messages) = match config.cache.enabled {
             false => (None, None),
             true => (
@@ -125,7 +158,12 @@ impl Partition {
             offsets_path,
             consumer_offsets_path,
             consumer_group_offsets_path,
+            transactions_path,
+            producers_path,
+            archived_segments_path,
+            poisoned_offsets_path,
             message_expiry,
+            compression_algorithm,
             cache: messages,
             cached_memory_tracker,
             message_deduplicator: match config.message_deduplication.enabled {
@@ -148,9 +186,19 @@ impl Partition {
             segments: vec![],
             current_offset: 0,
             unsaved_messages_count: 0,
+            deduplicated_messages_count: 0,
             should_increment_offset: false,
             consumer_offsets: DashMap::new(),
             consumer_group_offsets: DashMap::new(),
+            failed_deliveries: DashMap::new(),
+            poisoned_offsets: AHashSet::new(),
+            replica_offsets: Arc::new(ReplicaOffsets::default()),
+            open_transactions: AHashMap::new(),
+            aborted_offsets: Vec::new(),
+            producer_states: AHashMap::new(),
+            appended_producer_states: 0,
+            archived_segments: Vec::new(),
+            messages_appended: Arc::new(Notify::new()),
             config,
             storage,
             created_at,
@@ -172,6 +220,7 @@ impl Partition {
                 0,
                 partition.config.clone(),
                 partition.message_expiry,
+                partition.compression_algorithm,
                 partition.size_of_parent_stream.clone(),
                 partition.size_of_parent_topic.clone(),
                 partition.size_bytes.clone(),
@@ -188,6 +237,13 @@ impl Partition {
         partition
     }
 
+    pub fn get_uncompressed_size_bytes(&self) -> IggyByteSize {
+        self.segments
+            .iter()
+            .map(|segment| segment.uncompressed_size_bytes)
+            .sum()
+    }
+
     pub fn get_cache_metrics(&self) -> CacheMetrics {
         if let Some(cache) = self.cache.as_ref() {
             let cache_metrics = cache.get_metrics();
@@ -232,6 +288,7 @@ mod tests {
     use crate::streaming::partitions::partition::Partition;
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
+    use iggy::compression::compression_algorithm::CompressionAlgorithm;
     use iggy::utils::duration::IggyDuration;
     use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::timestamp::IggyTimestamp;
@@ -264,6 +321,7 @@ mod tests {
             config,
             storage,
             message_expiry,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -314,6 +372,7 @@ mod tests {
             }),
             storage,
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -346,6 +405,7 @@ mod tests {
             Arc::new(SystemConfig::default()),
             storage,
             IggyExpiry::NeverExpire,
+            CompressionAlgorithm::None,
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
             Arc::new(AtomicU64::new(0)),
@@ -45,6 +45,9 @@ impl Partition {
             self.segments_count_of_parent_stream
                 .fetch_sub(1, Ordering::SeqCst);
         }
+        self.delete_archive_cache().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to delete archive cache in partition: {self}")
+        })?;
         self.storage.partition.delete(self).await
     }
 
@@ -65,6 +68,11 @@ impl Partition {
                 .fetch_sub(1, Ordering::SeqCst);
         }
         self.segments.clear();
+        self.delete_archived_segments()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete archived segments in partition: {self}")
+            })?;
         self.storage
             .partition
             .delete_consumer_offsets(&self.consumer_offsets_path)
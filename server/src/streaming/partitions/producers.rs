@@ -0,0 +1,363 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use bytes::{BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use iggy::confirmation::Confirmation;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::{Message, ProducerSequence};
+use tracing::{info, trace};
+
+/// The size of the single producer state stored in the partition producers file.
+pub const PRODUCER_STATE_SIZE: usize = 24;
+
+/// The number of the producer states appended to the file, after which it's rewritten to contain only the latest state of each producer.
+const PRODUCER_STATES_COMPACTION_THRESHOLD: usize = 10_000;
+
+/// The last sequence number stored in the partition by the idempotent producer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProducerState {
+    pub producer_id: u64,
+    pub sequence: u64,
+    pub last_offset: u64,
+}
+
+impl ProducerState {
+    pub fn as_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(PRODUCER_STATE_SIZE);
+        bytes.put_u64_le(self.producer_id);
+        bytes.put_u64_le(self.sequence);
+        bytes.put_u64_le(self.last_offset);
+        bytes.freeze()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() != PRODUCER_STATE_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let read_u64 = |position: usize| {
+            bytes[position..position + 8]
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding)
+        };
+        Ok(ProducerState {
+            producer_id: read_u64(0)?,
+            sequence: read_u64(8)?,
+            last_offset: read_u64(16)?,
+        })
+    }
+}
+
+impl Partition {
+    /// Appends the messages sent by the idempotent producer, optionally as a part of the open transaction.
+    /// The batch with the sequence number which has already been stored is the retried one, so its messages are not appended again,
+    /// while the batch with the sequence number greater than the next expected one is rejected, as the previous batch might have been lost.
+    /// The producer without the stored state has to start with the sequence number 1, otherwise it's rejected as the unknown one.
+    /// Returns `true` if the messages have been appended.
+    pub async fn append_idempotent_messages(
+        &mut self,
+        producer_sequence: ProducerSequence,
+        transaction_id: Option<u64>,
+        appendable_batch_info: AppendableBatchInfo,
+        messages: Vec<Message>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<bool, IggyError> {
+        let producer_id = producer_sequence.producer_id;
+        let sequence = producer_sequence.sequence;
+        // The unknown producer is either the new one, or its state has expired, or was not stored before the server stopped.
+        match self.producer_states.get(&producer_id) {
+            Some(state) if sequence <= state.sequence => {
+                trace!("Ignored the duplicated batch with sequence: {sequence} for producer with ID: {producer_id}, last sequence: {}, partition: {self}", state.sequence);
+                return Ok(false);
+            }
+            Some(state) if sequence != state.sequence + 1 => {
+                return Err(IggyError::OutOfOrderSequence(
+                    producer_id,
+                    state.sequence + 1,
+                    sequence,
+                ));
+            }
+            None if sequence != 1 => {
+                return Err(IggyError::UnknownProducer(producer_id, sequence));
+            }
+            _ => {}
+        }
+
+        match transaction_id {
+            Some(transaction_id) => {
+                self.append_transactional_messages(
+                    transaction_id,
+                    appendable_batch_info,
+                    messages,
+                    confirmation,
+                )
+                .await
+            }
+            None => {
+                self.append_messages(appendable_batch_info, messages, confirmation)
+                    .await
+            }
+        }
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append messages with sequence: {sequence} for producer with ID: {producer_id}, partition: {self}")
+        })?;
+
+        let state = ProducerState {
+            producer_id,
+            sequence,
+            last_offset: self.current_offset,
+        };
+        self.storage
+            .partition
+            .append_producer_state(&self.producers_path, state)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append state for producer with ID: {producer_id}, partition: {self}")
+            })?;
+        self.producer_states.insert(producer_id, state);
+        self.appended_producer_states += 1;
+        if self.appended_producer_states >= PRODUCER_STATES_COMPACTION_THRESHOLD {
+            self.compact_producer_states().await?;
+        }
+        Ok(true)
+    }
+
+    /// Restores the last sequence number of each producer. The states pointing to the messages which haven't been saved to disk
+    /// before the server stopped are skipped, so the producer is allowed to send such a batch again.
+    pub async fn load_producer_states(&mut self) -> Result<(), IggyError> {
+        let states = self
+            .storage
+            .partition
+            .load_producer_states(&self.producers_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load producer states, partition: {self}")
+            })?;
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        self.producer_states.clear();
+        for state in states {
+            if !self.should_increment_offset || state.last_offset > self.current_offset {
+                continue;
+            }
+
+            self.producer_states.insert(state.producer_id, state);
+        }
+
+        self.compact_producer_states().await?;
+        info!(
+            "Loaded {} producer states for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+            self.producer_states.len(),
+            self.partition_id,
+            self.stream_id,
+            self.topic_id
+        );
+        Ok(())
+    }
+
+    /// Removes the states of the producers whose last messages have already been deleted from the partition,
+    /// e.g. due to the message expiry or the topic size limit, so the idle producers are not kept forever.
+    pub async fn remove_expired_producer_states(&mut self) -> Result<(), IggyError> {
+        let first_offset = match self.segments.first() {
+            Some(segment) => segment.start_offset,
+            None => self.current_offset + 1,
+        };
+        let states_count = self.producer_states.len();
+        self.producer_states
+            .retain(|_, state| state.last_offset >= first_offset);
+        let expired_states_count = states_count - self.producer_states.len();
+        if expired_states_count == 0 {
+            return Ok(());
+        }
+
+        self.compact_producer_states().await?;
+        info!(
+            "Removed {expired_states_count} expired producer states for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+            self.partition_id,
+            self.stream_id,
+            self.topic_id
+        );
+        Ok(())
+    }
+
+    async fn compact_producer_states(&mut self) -> Result<(), IggyError> {
+        let states = self.producer_states.values().copied().collect::<Vec<_>>();
+        self.storage
+            .partition
+            .overwrite_producer_states(&self.producers_path, &states)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to overwrite producer states, partition: {self}")
+            })?;
+        self.appended_producer_states = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::partitions::create_messages;
+    use crate::streaming::partitions::test_utils::{create_config, create_partition};
+    use iggy::utils::byte_size::IggyByteSize;
+    use iggy::utils::sizeable::Sizeable;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const PRODUCER_ID: u64 = 1;
+
+    #[test]
+    fn producer_state_should_be_serialized_and_deserialized() {
+        let state = ProducerState {
+            producer_id: 1,
+            sequence: 2,
+            last_offset: 3,
+        };
+
+        let bytes = state.as_bytes();
+
+        assert_eq!(bytes.len(), PRODUCER_STATE_SIZE);
+        assert_eq!(ProducerState::from_bytes(&bytes).unwrap(), state);
+    }
+
+    #[tokio::test]
+    async fn retried_batch_should_not_be_appended_again() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        let messages_count = create_messages().len() as u64;
+
+        assert!(append_messages(&mut partition, 1).await.unwrap());
+        assert!(!append_messages(&mut partition, 1).await.unwrap());
+        assert!(append_messages(&mut partition, 2).await.unwrap());
+
+        assert_eq!(partition.get_messages_count(), 2 * messages_count);
+        assert_eq!(
+            partition
+                .producer_states
+                .get(&PRODUCER_ID)
+                .unwrap()
+                .sequence,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_with_out_of_order_sequence_should_be_rejected() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, 1).await.unwrap();
+
+        let result = append_messages(&mut partition, 3).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            IggyError::OutOfOrderSequence(PRODUCER_ID, 2, 3)
+        );
+        assert_eq!(
+            partition.get_messages_count(),
+            create_messages().len() as u64
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_of_unknown_producer_should_be_rejected_unless_it_is_the_first_one() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+
+        let result = append_messages(&mut partition, 2).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            IggyError::UnknownProducer(PRODUCER_ID, 2)
+        );
+        assert_eq!(partition.get_messages_count(), 0);
+        assert!(append_messages(&mut partition, 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn producer_state_should_be_removed_once_its_messages_are_deleted() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, 1).await.unwrap();
+
+        partition.delete_segment(0).await.unwrap();
+
+        assert!(partition.producer_states.is_empty());
+        let states = partition
+            .storage
+            .partition
+            .load_producer_states(&partition.producers_path)
+            .await
+            .unwrap();
+        assert!(states.is_empty());
+    }
+
+    #[tokio::test]
+    async fn producer_states_of_unsaved_messages_should_be_skipped_when_loaded() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, 1).await.unwrap();
+        let saved_state = *partition.producer_states.get(&PRODUCER_ID).unwrap();
+        append_messages(&mut partition, 2).await.unwrap();
+        partition.current_offset = saved_state.last_offset;
+
+        partition.load_producer_states().await.unwrap();
+
+        assert_eq!(
+            partition.producer_states.get(&PRODUCER_ID),
+            Some(&saved_state)
+        );
+        let states = partition
+            .storage
+            .partition
+            .load_producer_states(&partition.producers_path)
+            .await
+            .unwrap();
+        assert_eq!(states, vec![saved_state]);
+    }
+
+    async fn append_messages(partition: &mut Partition, sequence: u64) -> Result<bool, IggyError> {
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        partition
+            .append_idempotent_messages(
+                ProducerSequence {
+                    producer_id: PRODUCER_ID,
+                    sequence,
+                },
+                None,
+                appendable_batch_info,
+                messages,
+                None,
+            )
+            .await
+    }
+}
@@ -0,0 +1,150 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use dashmap::DashMap;
+use iggy::error::IggyError;
+use iggy::utils::duration::IggyDuration;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// Tracks the messages acknowledged by the follower replicas of the partition.
+/// Each replica acknowledges all the messages preceding the offset it fetches next.
+#[derive(Debug, Default)]
+pub struct ReplicaOffsets {
+    next_offsets: DashMap<u32, u64>,
+    acknowledged: Notify,
+}
+
+impl ReplicaOffsets {
+    pub fn acknowledge(&self, replica_id: u32, next_offset: u64) {
+        self.next_offsets.insert(replica_id, next_offset);
+        self.acknowledged.notify_waiters();
+    }
+
+    /// Returns the number of replicas which have already acknowledged the message with the given offset.
+    pub fn get_acknowledgements_count(&self, offset: u64) -> usize {
+        self.next_offsets
+            .iter()
+            .filter(|next_offset| *next_offset.value() > offset)
+            .count()
+    }
+
+    /// Waits until the given number of replicas acknowledge the message with the given offset.
+    /// Returns `false` if the acknowledgements were not received within the timeout.
+    pub async fn wait_for_acknowledgements(
+        &self,
+        offset: u64,
+        required_count: usize,
+        timeout: IggyDuration,
+    ) -> bool {
+        let deadline = Instant::now() + timeout.get_duration();
+        loop {
+            let acknowledged = self.acknowledged.notified();
+            if self.get_acknowledgements_count(offset) >= required_count {
+                return true;
+            }
+
+            if tokio::time::timeout_at(deadline, acknowledged)
+                .await
+                .is_err()
+            {
+                return self.get_acknowledgements_count(offset) >= required_count;
+            }
+        }
+    }
+}
+
+/// The acknowledgement of the appended messages by the quorum of the follower replicas.
+/// It's awaited once the system lock is released, so the followers and the other clients are not blocked in the meantime.
+#[derive(Debug)]
+pub struct QuorumAcknowledgement {
+    pub replica_offsets: Arc<ReplicaOffsets>,
+    pub offset: u64,
+    pub required_count: usize,
+    pub timeout: IggyDuration,
+    pub stream_id: u32,
+    pub topic_id: u32,
+    pub partition_id: u32,
+}
+
+impl QuorumAcknowledgement {
+    pub async fn wait(self) -> Result<(), IggyError> {
+        let acknowledged = self
+            .replica_offsets
+            .wait_for_acknowledgements(self.offset, self.required_count, self.timeout)
+            .await;
+        if !acknowledged {
+            return Err(IggyError::ReplicationQuorumNotReached(
+                self.offset,
+                self.partition_id,
+                self.topic_id,
+                self.stream_id,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acknowledgements_should_be_counted_per_replica() {
+        let replica_offsets = ReplicaOffsets::default();
+        replica_offsets.acknowledge(2, 10);
+        replica_offsets.acknowledge(3, 5);
+        replica_offsets.acknowledge(3, 6);
+
+        assert_eq!(replica_offsets.get_acknowledgements_count(4), 2);
+        assert_eq!(replica_offsets.get_acknowledgements_count(5), 2);
+        assert_eq!(replica_offsets.get_acknowledgements_count(6), 1);
+        assert_eq!(replica_offsets.get_acknowledgements_count(10), 0);
+    }
+
+    #[tokio::test]
+    async fn waiting_for_acknowledgements_should_complete_once_replicas_acknowledge_offset() {
+        let replica_offsets = Arc::new(ReplicaOffsets::default());
+        let waiting_replica_offsets = replica_offsets.clone();
+        let waiter = tokio::spawn(async move {
+            waiting_replica_offsets
+                .wait_for_acknowledgements(3, 2, IggyDuration::from(5_000_000))
+                .await
+        });
+
+        replica_offsets.acknowledge(2, 4);
+        tokio::task::yield_now().await;
+        replica_offsets.acknowledge(3, 4);
+
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn waiting_for_acknowledgements_should_time_out_without_quorum() {
+        let replica_offsets = ReplicaOffsets::default();
+        replica_offsets.acknowledge(2, 4);
+
+        let acknowledged = replica_offsets
+            .wait_for_acknowledgements(3, 2, IggyDuration::from(10_000))
+            .await;
+
+        assert!(!acknowledged);
+    }
+}
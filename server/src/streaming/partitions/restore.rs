@@ -0,0 +1,593 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::archiver::ArchiverKind;
+use crate::compat::index_rebuilding::index_rebuilder::IndexRebuilder;
+use crate::configs::system::SystemConfig;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use crate::streaming::segments::*;
+use crate::streaming::utils::file;
+use error_set::ErrContext;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::segments::restore_segments::{ArchiveSource, RestoreRange, RestoreRangeKind};
+use iggy::utils::expiry::IggyExpiry;
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{error, info, warn};
+
+impl Partition {
+    /// Restores the archived segments of the source partition containing the messages from the specified range, keeping their original offsets.
+    /// The segments are fetched into the staging directory, their indexes rebuilt and checksums validated, before being moved into the partition.
+    /// Unless the partition is empty, only the segments preceding its first segment are restored, together with the ones following them,
+    /// so that the offsets remain contiguous. Returns the number of restored messages.
+    pub async fn restore_segments(
+        &mut self,
+        archiver: &ArchiverKind,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<u64, IggyError> {
+        let target = self.get_restore_target();
+        let result = match target
+            .stage_archived_segments(archiver, source, range)
+            .await
+        {
+            Ok(staged_segments) => self.add_restored_segments(&target, staged_segments).await,
+            Err(error) => Err(error),
+        };
+        target.delete_staging_directory().await;
+        result
+    }
+
+    /// Returns the details of the partition required to stage the archived segments, which doesn't need holding the partition lock.
+    pub fn get_restore_target(&self) -> RestoreTarget {
+        let base_staging_path =
+            self.config
+                .get_restore_path(self.stream_id, self.topic_id, self.partition_id);
+        let first_local_offset = match self.should_increment_offset {
+            true => self.segments.first().map(|segment| segment.start_offset),
+            false => None,
+        };
+        RestoreTarget {
+            stream_id: self.stream_id,
+            topic_id: self.topic_id,
+            partition_id: self.partition_id,
+            config: self.config.clone(),
+            message_expiry: self.message_expiry,
+            compression_algorithm: self.compression_algorithm,
+            is_empty: !self.should_increment_offset,
+            first_local_offset,
+            // The concurrent restores of the same partition must not share the staging directory.
+            staging_path: format!("{base_staging_path}/{}", uuid::Uuid::now_v7()),
+        }
+    }
+
+    /// Moves the staged segments into the partition, unless it has changed since the restore target was taken,
+    /// as the restored segments might no longer precede its first segment. Returns the number of restored messages.
+    pub async fn add_restored_segments(
+        &mut self,
+        target: &RestoreTarget,
+        staged_segments: Vec<Segment>,
+    ) -> Result<u64, IggyError> {
+        let current_target = self.get_restore_target();
+        if current_target.is_empty != target.is_empty
+            || current_target.first_local_offset != target.first_local_offset
+        {
+            let start_offset = staged_segments
+                .first()
+                .map(|segment| segment.start_offset)
+                .unwrap_or_default();
+            error!("Partition: {self} has changed while restoring the archived segments.");
+            return Err(IggyError::CannotRestoreSegment(
+                start_offset,
+                self.partition_id,
+            ));
+        }
+
+        // The empty partition still has its initial segment, which would be overwritten by the restored ones.
+        if target.is_empty {
+            let start_offsets = self
+                .segments
+                .iter()
+                .map(|segment| segment.start_offset)
+                .collect::<Vec<_>>();
+            for start_offset in start_offsets {
+                self.delete_segment(start_offset).await.with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to delete empty segment with start offset: {start_offset}, partition: {self}")
+                })?;
+            }
+        }
+
+        let mut messages_count = 0;
+        for staged_segment in staged_segments {
+            let start_offset = staged_segment.start_offset;
+            let mut segment = Segment::create(
+                self.stream_id,
+                self.topic_id,
+                self.partition_id,
+                start_offset,
+                self.config.clone(),
+                self.message_expiry,
+                self.compression_algorithm,
+                self.size_of_parent_stream.clone(),
+                self.size_of_parent_topic.clone(),
+                self.size_bytes.clone(),
+                self.messages_count_of_parent_stream.clone(),
+                self.messages_count_of_parent_topic.clone(),
+                self.messages_count.clone(),
+            );
+            for (staged_path, path) in [
+                (&staged_segment.log_path, &segment.log_path),
+                (&staged_segment.index_path, &segment.index_path),
+            ] {
+                file::rename(staged_path, path).await.with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to move restored file: {staged_path} to: {path}")
+                }).map_err(|_| IggyError::CannotRestoreSegment(start_offset, self.partition_id))?;
+            }
+            drop(staged_segment);
+
+            segment.load_from_disk().await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load restored segment: {segment}")
+            })?;
+            segment.is_closed = true;
+            segment.end_offset = segment.current_offset;
+            if let Some(message_deduplicator) = &self.message_deduplicator {
+                let message_ids = segment.load_message_ids().await.with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to load message ids, segment: {segment}")
+                })?;
+                for message_id in message_ids {
+                    message_deduplicator.try_insert(&message_id).await;
+                }
+            }
+
+            messages_count += segment.get_messages_count();
+            info!(
+                "Restored segment with start offset: {start_offset} and end offset: {} for partition with ID: {}, stream with ID: {}, topic with ID: {}.",
+                segment.end_offset, self.partition_id, self.stream_id, self.topic_id
+            );
+            self.segments_count_of_parent_stream
+                .fetch_add(1, Ordering::SeqCst);
+            self.segments.push(segment);
+        }
+
+        self.segments.sort_by_key(|segment| segment.start_offset);
+        if !self.should_increment_offset {
+            if let Some(last_segment) = self.segments.last() {
+                self.current_offset = last_segment.current_offset;
+                self.should_increment_offset = true;
+            }
+        }
+        Ok(messages_count)
+    }
+}
+
+/// The details of the partition required to stage the archived segments before adding them to the partition.
+#[derive(Debug)]
+pub struct RestoreTarget {
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+    config: Arc<SystemConfig>,
+    message_expiry: IggyExpiry,
+    compression_algorithm: CompressionAlgorithm,
+    is_empty: bool,
+    first_local_offset: Option<u64>,
+    staging_path: String,
+}
+
+impl RestoreTarget {
+    /// Fetches the archived segments matching the range into the staging directory.
+    pub async fn stage_archived_segments(
+        &self,
+        archiver: &ArchiverKind,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<Vec<Segment>, IggyError> {
+        let not_found = || {
+            IggyError::ArchivedSegmentsNotFound(
+                source.stream_id,
+                source.topic_id,
+                source.partition_id,
+            )
+        };
+        let source_path =
+            self.config
+                .get_partition_path(source.stream_id, source.topic_id, source.partition_id);
+        let archived_files = archiver.list(&source_path, None).await.map_err(|error| {
+            error!(
+                "Failed to list archived files in: {source_path} for partition: {self}. {error}"
+            );
+            not_found()
+        })?;
+        let log_extension = format!(".{LOG_EXTENSION}");
+        let mut archived_logs = archived_files
+            .into_iter()
+            .filter_map(|archived_file| {
+                let start_offset = Path::new(&archived_file)
+                    .file_name()?
+                    .to_str()?
+                    .strip_suffix(&log_extension)?
+                    .parse::<u64>()
+                    .ok()?;
+                Some((start_offset, archived_file))
+            })
+            .collect::<Vec<_>>();
+        archived_logs.sort_by_key(|(start_offset, _)| *start_offset);
+
+        let first_local_offset = self.first_local_offset.unwrap_or(u64::MAX);
+        let mut staged_segments: Vec<Segment> = Vec::new();
+        for (index, (start_offset, archived_log)) in archived_logs.iter().enumerate() {
+            let start_offset = *start_offset;
+            if start_offset >= first_local_offset {
+                break;
+            }
+
+            let next_start_offset = archived_logs
+                .get(index + 1)
+                .map(|(start_offset, _)| *start_offset)
+                .unwrap_or(u64::MAX)
+                .min(first_local_offset);
+            // Once the first matching segment is found, the following ones are restored too for the partition with messages,
+            // as otherwise there would be a gap between the restored segments and the local ones.
+            let is_required = !self.is_empty && !staged_segments.is_empty();
+            if !is_required
+                && range.kind == RestoreRangeKind::Offset
+                && !range.overlaps(start_offset, next_start_offset - 1)
+            {
+                continue;
+            }
+
+            let segment = self
+                .stage_archived_segment(archiver, archived_log, start_offset)
+                .await?;
+            if segment.current_offset >= next_start_offset {
+                error!(
+                    "Archived segment with start offset: {start_offset} and end offset: {} overlaps the next segment starting at: {next_start_offset}, partition: {self}.",
+                    segment.current_offset
+                );
+                return Err(IggyError::CannotRestoreSegment(
+                    start_offset,
+                    self.partition_id,
+                ));
+            }
+
+            if !is_required && range.kind == RestoreRangeKind::Timestamp {
+                let (start_timestamp, end_timestamp) = get_timestamps(&segment).await?;
+                if !range.overlaps(start_timestamp, end_timestamp) {
+                    continue;
+                }
+            }
+
+            staged_segments.push(segment);
+        }
+
+        if staged_segments.is_empty() {
+            return Err(not_found());
+        }
+
+        Ok(staged_segments)
+    }
+
+    async fn stage_archived_segment(
+        &self,
+        archiver: &ArchiverKind,
+        archived_log: &str,
+        start_offset: u64,
+    ) -> Result<Segment, IggyError> {
+        let staging_path = &self.staging_path;
+        let cannot_restore = || IggyError::CannotRestoreSegment(start_offset, self.partition_id);
+        let log_path = format!("{staging_path}/{start_offset:0>20}.{LOG_EXTENSION}");
+        let index_path = format!("{staging_path}/{start_offset:0>20}.{INDEX_EXTENSION}");
+        archiver
+            .fetch(archived_log, &log_path, None)
+            .await
+            .map_err(|error| {
+                error!(
+                    "Failed to fetch archived file: {archived_log} for partition: {self}. {error}"
+                );
+                cannot_restore()
+            })?;
+        IndexRebuilder::new(log_path.clone(), index_path.clone(), start_offset)
+            .rebuild()
+            .await
+            .map_err(|error| {
+                error!("Failed to rebuild index for archived file: {archived_log} for partition: {self}. {error}");
+                cannot_restore()
+            })?;
+
+        let mut segment = Segment::create(
+            self.stream_id,
+            self.topic_id,
+            self.partition_id,
+            start_offset,
+            self.config.clone(),
+            self.message_expiry,
+            self.compression_algorithm,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+        );
+        segment
+            .load_archived(log_path, index_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segment with start offset: {start_offset}, partition: {self}")
+            })?;
+        segment.load_message_checksums().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to validate checksums of archived segment with start offset: {start_offset}, partition: {self}")
+        })?;
+        Ok(segment)
+    }
+
+    /// Deletes the staging directory along with the segments which were not moved into the partition.
+    pub async fn delete_staging_directory(&self) {
+        if Path::new(&self.staging_path).exists()
+            && fs::remove_dir_all(&self.staging_path).await.is_err()
+        {
+            warn!(
+                "Failed to delete restore staging directory: {} for partition: {self}",
+                self.staging_path
+            );
+        }
+    }
+}
+
+impl fmt::Display for RestoreTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Partition {{ stream ID: {}, topic ID: {}, partition_id: {} }}",
+            self.stream_id, self.topic_id, self.partition_id,
+        )
+    }
+}
+
+async fn get_timestamps(segment: &Segment) -> Result<(u64, u64), IggyError> {
+    let first_message = segment
+        .get_messages_by_offset(segment.start_offset, 1)
+        .await?;
+    let last_message = segment
+        .get_messages_by_offset(segment.current_offset, 1)
+        .await?;
+    let start_timestamp = first_message.first().map(|message| message.timestamp);
+    let end_timestamp = last_message.first().map(|message| message.timestamp);
+    Ok((
+        start_timestamp.unwrap_or_default(),
+        end_timestamp.unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::system::{SegmentConfig, SystemConfig};
+    use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+    use crate::streaming::partitions::create_messages;
+    use crate::streaming::partitions::test_utils::{
+        create_archiver, create_config, create_partition,
+    };
+    use iggy::utils::byte_size::IggyByteSize;
+    use iggy::utils::sizeable::Sizeable;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use tempfile::TempDir;
+
+    const SOURCE_PARTITION_ID: u32 = 1;
+    const TARGET_PARTITION_ID: u32 = 2;
+
+    #[tokio::test]
+    async fn all_archived_segments_should_be_restored_into_empty_partition() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_restore_config(&tempdir);
+        let archiver = create_archiver(&tempdir);
+        let mut source_partition =
+            create_partition(config.clone(), None, SOURCE_PARTITION_ID).await;
+        let messages_count = append_messages_in_segments(&mut source_partition, 3).await;
+        archive_segments(&source_partition, &archiver).await;
+        let mut partition = create_partition(config, None, TARGET_PARTITION_ID).await;
+
+        let restored_messages_count = partition
+            .restore_segments(&archiver, &create_source(), &RestoreRange::default())
+            .await
+            .unwrap();
+
+        let messages = partition
+            .get_messages_by_offset(0, messages_count as u32)
+            .await
+            .unwrap();
+        assert_eq!(restored_messages_count, messages_count);
+        assert_eq!(
+            partition.get_segments_count(),
+            source_partition.get_segments_count()
+        );
+        assert_eq!(partition.current_offset, source_partition.current_offset);
+        assert_eq!(partition.get_next_offset(), messages_count);
+        assert_eq!(messages.len() as u64, messages_count);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn archived_segments_matching_offset_range_should_be_restored() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_restore_config(&tempdir);
+        let archiver = create_archiver(&tempdir);
+        let mut source_partition =
+            create_partition(config.clone(), None, SOURCE_PARTITION_ID).await;
+        append_messages_in_segments(&mut source_partition, 3).await;
+        archive_segments(&source_partition, &archiver).await;
+        let second_segment = &source_partition.segments[1];
+        let range = RestoreRange::offsets(second_segment.start_offset, second_segment.end_offset);
+        let mut partition = create_partition(config, None, TARGET_PARTITION_ID).await;
+
+        let restored_messages_count = partition
+            .restore_segments(&archiver, &create_source(), &range)
+            .await
+            .unwrap();
+
+        assert_eq!(partition.get_segments_count(), 1);
+        let segment = &partition.segments[0];
+        assert_eq!(segment.start_offset, second_segment.start_offset);
+        assert_eq!(segment.end_offset, second_segment.end_offset);
+        assert_eq!(restored_messages_count, second_segment.get_messages_count());
+        assert_eq!(partition.current_offset, second_segment.end_offset);
+    }
+
+    #[tokio::test]
+    async fn deleted_segments_should_be_restored_into_the_same_partition() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_restore_config(&tempdir);
+        let archiver = create_archiver(&tempdir);
+        let mut partition = create_partition(config, None, SOURCE_PARTITION_ID).await;
+        let messages_count = append_messages_in_segments(&mut partition, 3).await;
+        archive_segments(&partition, &archiver).await;
+        let segments_count = partition.get_segments_count();
+        let current_offset = partition.current_offset;
+        let deleted_offsets = partition
+            .segments
+            .iter()
+            .take(partition.segments.len() - 1)
+            .map(|segment| segment.start_offset)
+            .collect::<Vec<_>>();
+        for start_offset in deleted_offsets {
+            partition.delete_segment(start_offset).await.unwrap();
+        }
+
+        partition
+            .restore_segments(&archiver, &create_source(), &RestoreRange::offsets(0, 0))
+            .await
+            .unwrap();
+
+        let messages = partition
+            .get_messages_by_offset(0, messages_count as u32)
+            .await
+            .unwrap();
+        assert_eq!(partition.get_segments_count(), segments_count);
+        assert_eq!(partition.current_offset, current_offset);
+        assert_eq!(messages.len() as u64, messages_count);
+    }
+
+    #[tokio::test]
+    async fn restoring_segments_not_matching_range_should_fail() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_restore_config(&tempdir);
+        let archiver = create_archiver(&tempdir);
+        let mut source_partition =
+            create_partition(config.clone(), None, SOURCE_PARTITION_ID).await;
+        append_messages_in_segments(&mut source_partition, 1).await;
+        archive_segments(&source_partition, &archiver).await;
+        let mut partition = create_partition(config, None, TARGET_PARTITION_ID).await;
+        let range = RestoreRange::timestamps(IggyTimestamp::from(u64::MAX - 1), u64::MAX.into());
+
+        let result = partition
+            .restore_segments(&archiver, &create_source(), &range)
+            .await;
+
+        assert_eq!(
+            result,
+            Err(IggyError::ArchivedSegmentsNotFound(
+                1,
+                1,
+                SOURCE_PARTITION_ID
+            ))
+        );
+        assert!(!partition.should_increment_offset);
+    }
+
+    #[tokio::test]
+    async fn restoring_segments_into_partition_changed_while_staging_should_fail() {
+        let tempdir = TempDir::new().unwrap();
+        let config = create_restore_config(&tempdir);
+        let archiver = create_archiver(&tempdir);
+        let mut source_partition =
+            create_partition(config.clone(), None, SOURCE_PARTITION_ID).await;
+        append_messages_in_segments(&mut source_partition, 2).await;
+        archive_segments(&source_partition, &archiver).await;
+        let mut partition = create_partition(config, None, TARGET_PARTITION_ID).await;
+        let target = partition.get_restore_target();
+        let staged_segments = target
+            .stage_archived_segments(&archiver, &create_source(), &RestoreRange::default())
+            .await
+            .unwrap();
+        append_messages_in_segments(&mut partition, 1).await;
+        let segments_count = partition.get_segments_count();
+
+        let result = partition
+            .add_restored_segments(&target, staged_segments)
+            .await;
+        target.delete_staging_directory().await;
+
+        assert_eq!(
+            result,
+            Err(IggyError::CannotRestoreSegment(0, TARGET_PARTITION_ID))
+        );
+        assert_eq!(partition.get_segments_count(), segments_count);
+    }
+
+    fn create_source() -> ArchiveSource {
+        ArchiveSource {
+            stream_id: 1,
+            topic_id: 1,
+            partition_id: SOURCE_PARTITION_ID,
+            archive_path: None,
+        }
+    }
+
+    async fn archive_segments(partition: &Partition, archiver: &ArchiverKind) {
+        for segment in &partition.segments {
+            let files = [segment.index_path.as_ref(), segment.log_path.as_ref()];
+            archiver.archive(&files, None).await.unwrap();
+        }
+    }
+
+    async fn append_messages_in_segments(partition: &mut Partition, batches: u32) -> u64 {
+        let mut messages_count = 0;
+        for _ in 0..batches {
+            let messages = create_messages();
+            messages_count += messages.len() as u64;
+            let appendable_batch_info = AppendableBatchInfo {
+                batch_size: messages
+                    .iter()
+                    .map(|m| m.get_size_bytes())
+                    .sum::<IggyByteSize>(),
+                partition_id: partition.partition_id,
+            };
+            partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await
+                .unwrap();
+        }
+        messages_count
+    }
+
+    fn create_restore_config(tempdir: &TempDir) -> Arc<SystemConfig> {
+        Arc::new(SystemConfig {
+            segment: SegmentConfig {
+                size: IggyByteSize::from(1),
+                ..Default::default()
+            },
+            ..create_config(tempdir)
+        })
+    }
+}
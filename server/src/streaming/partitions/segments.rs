@@ -16,13 +16,21 @@
  * under the License.
  */
 
-use std::sync::atomic::Ordering;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
+use crate::streaming::models::messages::RetainedMessage;
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::partitions::COMPONENT;
 use crate::streaming::segments::*;
+use ahash::AHashMap;
+use bytes::Bytes;
 use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
 use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::timestamp::IggyTimestamp;
 use tracing::info;
 
@@ -80,6 +88,7 @@ impl Partition {
             start_offset,
             self.config.clone(),
             self.message_expiry,
+            self.compression_algorithm,
             self.size_of_parent_stream.clone(),
             self.size_of_parent_topic.clone(),
             self.size_bytes.clone(),
@@ -128,6 +137,128 @@ impl Partition {
             "Segment with start offset: {} has been deleted from partition with ID: {}, stream with ID: {}, topic with ID: {}",
             start_offset, self.partition_id, self.stream_id, self.topic_id
         );
+        self.remove_expired_producer_states()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to remove expired producer states after deleting segment with start offset: {start_offset}")
+            })?;
+        self.remove_deleted_poisoned_offsets()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to remove deleted poisoned offsets after deleting segment with start offset: {start_offset}")
+            })?;
         Ok(deleted_segment)
     }
+
+    /// Compacts the closed segments, keeping only the latest message for each key stored in the specified header.
+    /// The key whose latest message is a tombstone (empty payload) is removed, while the messages without the key are retained.
+    /// The open segment is neither compacted nor taken into account, as it's still being appended to.
+    ///
+    /// The segments are read and rewritten one at a time from their read-only copies, thus the partition lock
+    /// is held only to open them and then to replace the files of each compacted segment.
+    /// Returns the number of removed messages.
+    pub async fn compact_segments(
+        partition: &IggySharedMut<Partition>,
+        header_key: &HeaderKey,
+    ) -> Result<u64, IggyError> {
+        let segments = partition.read().await.open_closed_segments().await?;
+        if segments.is_empty() {
+            return Ok(0);
+        }
+
+        let mut latest_offsets = AHashMap::new();
+        for segment in &segments {
+            let messages = segment.get_all_messages().await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load messages for compaction from segment: {segment}")
+            })?;
+            for message in messages {
+                if let Some(key) = get_message_key(&message, header_key) {
+                    latest_offsets.insert(key, message.offset);
+                }
+            }
+        }
+
+        let mut removed_messages = 0;
+        for segment in &segments {
+            let compacted_segment = segment
+                .write_compacted(|message| match get_message_key(message, header_key) {
+                    Some(key) => {
+                        !message.payload.is_empty()
+                            && latest_offsets.get(&key) == Some(&message.offset)
+                    }
+                    None => true,
+                })
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to compact segment with start offset: {}", segment.start_offset)
+                })?;
+            let Some(compacted_segment) = compacted_segment else {
+                continue;
+            };
+
+            removed_messages += partition
+                .write()
+                .await
+                .replace_compacted_segment(compacted_segment)
+                .await?;
+        }
+        Ok(removed_messages)
+    }
+
+    /// Opens the read-only copies of the closed segments, which don't affect the stats of the partition.
+    async fn open_closed_segments(&self) -> Result<Vec<Segment>, IggyError> {
+        let mut segments = Vec::new();
+        for closed_segment in self.segments.iter().filter(|segment| segment.is_closed) {
+            let mut segment = Segment::create(
+                self.stream_id,
+                self.topic_id,
+                self.partition_id,
+                closed_segment.start_offset,
+                self.config.clone(),
+                self.message_expiry,
+                self.compression_algorithm,
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+            );
+            segment
+                .load_archived(closed_segment.log_path.clone(), closed_segment.index_path.clone())
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to open segment: {closed_segment} for compaction")
+                })?;
+            segments.push(segment);
+        }
+        Ok(segments)
+    }
+
+    /// Replaces the files of the segment with the compacted ones, unless the segment was deleted in the meantime.
+    async fn replace_compacted_segment(
+        &mut self,
+        compacted_segment: CompactedSegment,
+    ) -> Result<u64, IggyError> {
+        let Some(segment) = self.segments.iter_mut().find(|segment| {
+            segment.is_closed
+                && segment.start_offset == compacted_segment.start_offset
+                && segment.current_offset == compacted_segment.end_offset
+        }) else {
+            compacted_segment.discard().await;
+            return Ok(0);
+        };
+
+        segment
+            .replace_with_compacted(compacted_segment)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to replace segment: {segment} with the compacted one")
+            })
+    }
+}
+
+fn get_message_key(message: &RetainedMessage, header_key: &HeaderKey) -> Option<Bytes> {
+    let headers = HashMap::<HeaderKey, HeaderValue>::from_bytes(message.headers.clone()?).ok()?;
+    headers.get(header_key).map(|value| value.value.clone())
 }
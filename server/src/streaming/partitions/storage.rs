@@ -19,7 +19,10 @@
 use crate::compat::index_rebuilding::index_rebuilder::IndexRebuilder;
 use crate::state::system::PartitionState;
 use crate::streaming::batching::batch_accumulator::BatchAccumulator;
+use crate::streaming::partitions::archive::{ArchivedSegment, ARCHIVED_SEGMENT_SIZE};
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::{ProducerState, PRODUCER_STATE_SIZE};
+use crate::streaming::partitions::transactions::{TransactionMarker, TRANSACTION_MARKER_SIZE};
 use crate::streaming::partitions::COMPONENT;
 use crate::streaming::persistence::persister::PersisterKind;
 use crate::streaming::segments::*;
@@ -101,6 +104,7 @@ impl PartitionStorage for FilePartitionStorage {
                 start_offset,
                 partition.config.clone(),
                 partition.message_expiry,
+                partition.compression_algorithm,
                 partition.size_of_parent_stream.clone(),
                 partition.size_of_parent_topic.clone(),
                 partition.size_bytes.clone(),
@@ -113,6 +117,12 @@ impl PartitionStorage for FilePartitionStorage {
             let log_path = segment.log_path.to_owned();
             let time_index_path = index_path.replace(INDEX_EXTENSION, "timeindex");
 
+            Segment::recover_compaction(&log_path, &index_path)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to recover compaction of segment: {log_path}")
+                })?;
+
             let index_cache_enabled = partition.config.segment.cache_indexes;
 
             let index_path_exists = tokio::fs::try_exists(&index_path).await.unwrap();
@@ -228,6 +238,24 @@ impl PartitionStorage for FilePartitionStorage {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load consumer offsets, partition: {partition}",)
             })?;
+        partition
+            .load_producer_states()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load producer states, partition: {partition}",)
+            })?;
+        partition
+            .load_archived_segments()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load archived segments, partition: {partition}",)
+            })?;
+        partition
+            .load_poisoned_offsets()
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load poisoned offsets, partition: {partition}",)
+            })?;
         info!(
             "Loaded partition with ID: {} for stream with ID: {} and topic with ID: {}, current offset: {}.",
             partition.partition_id, partition.stream_id, partition.topic_id, partition.current_offset
@@ -459,4 +487,245 @@ impl PartitionStorage for FilePartitionStorage {
         }
         Ok(())
     }
+
+    async fn append_transaction_marker(
+        &self,
+        path: &str,
+        marker: TransactionMarker,
+    ) -> Result<(), IggyError> {
+        let bytes = marker.as_bytes();
+        let result = if Path::new(path).exists() {
+            self.persister.append(path, &bytes).await
+        } else {
+            self.persister.overwrite(path, &bytes).await
+        };
+        result.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append transaction marker: {marker:?}, path: {path}")
+        })?;
+        trace!("Appended transaction marker: {marker:?}, path: {path}");
+        Ok(())
+    }
+
+    async fn overwrite_transaction_markers(
+        &self,
+        path: &str,
+        markers: &[TransactionMarker],
+    ) -> Result<(), IggyError> {
+        if Path::new(path).exists() {
+            self.persister.delete(path).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete transaction markers, path: {path}")
+            })?;
+        }
+
+        if markers.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::with_capacity(markers.len() * TRANSACTION_MARKER_SIZE);
+        for marker in markers {
+            bytes.extend_from_slice(&marker.as_bytes());
+        }
+        self.persister.overwrite(path, &bytes).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to overwrite transaction markers, path: {path}")
+        })?;
+        trace!(
+            "Stored: {} transaction markers, path: {path}",
+            markers.len()
+        );
+        Ok(())
+    }
+
+    async fn load_transaction_markers(
+        &self,
+        path: &str,
+    ) -> Result<Vec<TransactionMarker>, IggyError> {
+        if !Path::new(path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to read transaction markers, path: {path}")
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        // The last marker might have been written only partially, if the server stopped in the meantime.
+        let mut markers = Vec::with_capacity(bytes.len() / TRANSACTION_MARKER_SIZE);
+        for chunk in bytes.chunks_exact(TRANSACTION_MARKER_SIZE) {
+            markers.push(TransactionMarker::from_bytes(chunk)?);
+        }
+        Ok(markers)
+    }
+
+    async fn append_producer_state(
+        &self,
+        path: &str,
+        state: ProducerState,
+    ) -> Result<(), IggyError> {
+        let bytes = state.as_bytes();
+        let result = if Path::new(path).exists() {
+            self.persister.append(path, &bytes).await
+        } else {
+            self.persister.overwrite(path, &bytes).await
+        };
+        result.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append producer state: {state:?}, path: {path}")
+        })?;
+        trace!("Appended producer state: {state:?}, path: {path}");
+        Ok(())
+    }
+
+    async fn overwrite_producer_states(
+        &self,
+        path: &str,
+        states: &[ProducerState],
+    ) -> Result<(), IggyError> {
+        if Path::new(path).exists() {
+            self.persister.delete(path).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete producer states, path: {path}")
+            })?;
+        }
+
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::with_capacity(states.len() * PRODUCER_STATE_SIZE);
+        for state in states {
+            bytes.extend_from_slice(&state.as_bytes());
+        }
+        self.persister.overwrite(path, &bytes).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to overwrite producer states, path: {path}")
+        })?;
+        trace!("Stored: {} producer states, path: {path}", states.len());
+        Ok(())
+    }
+
+    async fn load_producer_states(&self, path: &str) -> Result<Vec<ProducerState>, IggyError> {
+        if !Path::new(path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read producer states, path: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        // The last state might have been written only partially, if the server stopped in the meantime.
+        let mut states = Vec::with_capacity(bytes.len() / PRODUCER_STATE_SIZE);
+        for chunk in bytes.chunks_exact(PRODUCER_STATE_SIZE) {
+            states.push(ProducerState::from_bytes(chunk)?);
+        }
+        Ok(states)
+    }
+    async fn overwrite_archived_segments(
+        &self,
+        path: &str,
+        archived_segments: &[ArchivedSegment],
+    ) -> Result<(), IggyError> {
+        if Path::new(path).exists() {
+            self.persister.delete(path).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete archived segments, path: {path}")
+            })?;
+        }
+
+        if archived_segments.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::with_capacity(archived_segments.len() * ARCHIVED_SEGMENT_SIZE);
+        for archived_segment in archived_segments {
+            bytes.extend_from_slice(&archived_segment.as_bytes());
+        }
+        self.persister.overwrite(path, &bytes).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to overwrite archived segments, path: {path}")
+        })?;
+        trace!(
+            "Stored: {} archived segments, path: {path}",
+            archived_segments.len()
+        );
+        Ok(())
+    }
+
+    async fn load_archived_segments(&self, path: &str) -> Result<Vec<ArchivedSegment>, IggyError> {
+        if !Path::new(path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read archived segments, path: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        let mut archived_segments = Vec::with_capacity(bytes.len() / ARCHIVED_SEGMENT_SIZE);
+        for chunk in bytes.chunks_exact(ARCHIVED_SEGMENT_SIZE) {
+            archived_segments.push(ArchivedSegment::from_bytes(chunk)?);
+        }
+        Ok(archived_segments)
+    }
+
+    async fn append_poisoned_offset(&self, path: &str, offset: u64) -> Result<(), IggyError> {
+        let bytes = offset.to_le_bytes();
+        let result = if Path::new(path).exists() {
+            self.persister.append(path, &bytes).await
+        } else {
+            self.persister.overwrite(path, &bytes).await
+        };
+        result.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to append poisoned offset: {offset}, path: {path}")
+        })?;
+        trace!("Appended poisoned offset: {offset}, path: {path}");
+        Ok(())
+    }
+
+    async fn overwrite_poisoned_offsets(
+        &self,
+        path: &str,
+        offsets: &[u64],
+    ) -> Result<(), IggyError> {
+        if Path::new(path).exists() {
+            self.persister.delete(path).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete poisoned offsets, path: {path}")
+            })?;
+        }
+
+        if offsets.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::with_capacity(offsets.len() * 8);
+        for offset in offsets {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        self.persister.overwrite(path, &bytes).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to overwrite poisoned offsets, path: {path}")
+        })?;
+        trace!("Stored: {} poisoned offsets, path: {path}", offsets.len());
+        Ok(())
+    }
+
+    async fn load_poisoned_offsets(&self, path: &str) -> Result<Vec<u64>, IggyError> {
+        if !Path::new(path).exists() {
+            return Ok(Vec::new());
+        }
+
+        let bytes = fs::read(path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to read poisoned offsets, path: {path}"
+                )
+            })
+            .map_err(|_| IggyError::CannotReadFile)?;
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
 }
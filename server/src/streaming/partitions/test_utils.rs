@@ -0,0 +1,75 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::archiver::ArchiverKind;
+use crate::configs::server::DiskArchiverConfig;
+use crate::configs::system::SystemConfig;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
+use crate::streaming::storage::SystemStorage;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::sync::atomic::{AtomicU32, AtomicU64};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+pub fn create_config(tempdir: &TempDir) -> SystemConfig {
+    SystemConfig {
+        path: tempdir.path().join("data").to_str().unwrap().to_string(),
+        ..Default::default()
+    }
+}
+
+pub fn create_archiver(tempdir: &TempDir) -> ArchiverKind {
+    ArchiverKind::get_disk_archiver(DiskArchiverConfig {
+        path: tempdir.path().join("archive").to_str().unwrap().to_string(),
+    })
+}
+
+/// Creates and persists the partition with a single segment, using the archiver (if any) as its storage archiver.
+pub async fn create_partition(
+    config: Arc<SystemConfig>,
+    archiver: Option<ArchiverKind>,
+    partition_id: u32,
+) -> Partition {
+    let mut storage = SystemStorage::new(
+        config.clone(),
+        Arc::new(PersisterKind::FileWithSync(FileWithSyncPersister {})),
+    );
+    storage.archiver = archiver.map(Arc::new);
+    let mut partition = Partition::create(
+        1,
+        1,
+        partition_id,
+        true,
+        config,
+        Arc::new(storage),
+        IggyExpiry::NeverExpire,
+        CompressionAlgorithm::None,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU32::new(0)),
+        IggyTimestamp::now(),
+    )
+    .await;
+    partition.persist().await.unwrap();
+    partition
+}
@@ -0,0 +1,509 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::COMPONENT;
+use ahash::{AHashMap, AHashSet};
+use bytes::{BufMut, Bytes, BytesMut};
+use error_set::ErrContext;
+use iggy::confirmation::Confirmation;
+use iggy::error::IggyError;
+use iggy::messages::send_messages::Message;
+use iggy::models::messages::MessageState;
+use std::sync::Arc;
+use tracing::{info, trace};
+
+/// The size of the single transaction marker stored in the partition transactions file.
+pub const TRANSACTION_MARKER_SIZE: usize = 25;
+
+/// The ID of the transaction used for the aborted offsets rewritten after the server restart,
+/// so that they can't be confused with the pending offsets of any new transaction.
+const RESOLVED_TRANSACTION_ID: u64 = 0;
+
+/// The marker appended to the partition transactions file, which allows restoring the open and aborted transactions on startup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionMarker {
+    /// The messages within the offsets range have been appended as a part of the transaction.
+    Pending {
+        transaction_id: u64,
+        first_offset: u64,
+        last_offset: u64,
+    },
+    /// The transaction has been committed.
+    Committed { transaction_id: u64 },
+    /// The transaction has been aborted.
+    Aborted { transaction_id: u64 },
+}
+
+impl TransactionMarker {
+    pub fn as_bytes(&self) -> Bytes {
+        let (kind, transaction_id, first_offset, last_offset) = match *self {
+            TransactionMarker::Pending {
+                transaction_id,
+                first_offset,
+                last_offset,
+            } => (1, transaction_id, first_offset, last_offset),
+            TransactionMarker::Committed { transaction_id } => (2, transaction_id, 0, 0),
+            TransactionMarker::Aborted { transaction_id } => (3, transaction_id, 0, 0),
+        };
+        let mut bytes = BytesMut::with_capacity(TRANSACTION_MARKER_SIZE);
+        bytes.put_u8(kind);
+        bytes.put_u64_le(transaction_id);
+        bytes.put_u64_le(first_offset);
+        bytes.put_u64_le(last_offset);
+        bytes.freeze()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IggyError> {
+        if bytes.len() != TRANSACTION_MARKER_SIZE {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let read_u64 = |position: usize| {
+            bytes[position..position + 8]
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| IggyError::InvalidNumberEncoding)
+        };
+        let transaction_id = read_u64(1)?;
+        match bytes[0] {
+            1 => Ok(TransactionMarker::Pending {
+                transaction_id,
+                first_offset: read_u64(9)?,
+                last_offset: read_u64(17)?,
+            }),
+            2 => Ok(TransactionMarker::Committed { transaction_id }),
+            3 => Ok(TransactionMarker::Aborted { transaction_id }),
+            _ => Err(IggyError::InvalidCommand),
+        }
+    }
+}
+
+impl Partition {
+    /// Appends the messages as a part of the open transaction, they remain invisible to the read committed consumers until it's committed.
+    pub async fn append_transactional_messages(
+        &mut self,
+        transaction_id: u64,
+        appendable_batch_info: AppendableBatchInfo,
+        messages: Vec<Message>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(), IggyError> {
+        let (batch_size, retained_messages) = self
+            .prepare_retained_messages(appendable_batch_info, messages, MessageState::Available)
+            .await;
+        // All the messages might have been ignored as the duplicates.
+        let (Some(first_message), Some(last_message)) =
+            (retained_messages.first(), retained_messages.last())
+        else {
+            return Ok(());
+        };
+
+        // The marker is persisted before the messages, so they can't be restored as the regular ones after the server stops in between.
+        // The marker of the messages which haven't been saved is ignored on startup, as it's beyond the current offset.
+        let first_offset = first_message.offset;
+        let last_offset = last_message.offset;
+        self.storage
+            .partition
+            .append_transaction_marker(
+                &self.transactions_path,
+                TransactionMarker::Pending {
+                    transaction_id,
+                    first_offset,
+                    last_offset,
+                },
+            )
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append pending marker for transaction with ID: {transaction_id}, partition: {self}")
+            })?;
+        self.append_retained_messages(batch_size, retained_messages, confirmation)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append messages for transaction with ID: {transaction_id}, partition: {self}")
+            })?;
+        self.open_transactions
+            .entry(transaction_id)
+            .or_default()
+            .push((first_offset, last_offset));
+        trace!("Appended messages with offsets: {first_offset}..={last_offset} for transaction with ID: {transaction_id}, partition: {self}");
+        Ok(())
+    }
+
+    /// Makes the messages appended as a part of the transaction visible to the read committed consumers.
+    pub async fn commit_transaction(&mut self, transaction_id: u64) -> Result<(), IggyError> {
+        if self.open_transactions.remove(&transaction_id).is_none() {
+            return Ok(());
+        }
+
+        self.storage
+            .partition
+            .append_transaction_marker(
+                &self.transactions_path,
+                TransactionMarker::Committed { transaction_id },
+            )
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append commit marker for transaction with ID: {transaction_id}, partition: {self}")
+            })?;
+        self.messages_appended.notify_waiters();
+        Ok(())
+    }
+
+    /// Hides the messages appended as a part of the transaction from the read committed consumers.
+    pub async fn abort_transaction(&mut self, transaction_id: u64) -> Result<(), IggyError> {
+        let Some(offsets) = self.open_transactions.remove(&transaction_id) else {
+            return Ok(());
+        };
+
+        self.storage
+            .partition
+            .append_transaction_marker(
+                &self.transactions_path,
+                TransactionMarker::Aborted { transaction_id },
+            )
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append abort marker for transaction with ID: {transaction_id}, partition: {self}")
+            })?;
+        self.add_aborted_offsets(offsets);
+        self.messages_appended.notify_waiters();
+        Ok(())
+    }
+
+    /// Restores the aborted offsets from the transaction markers. The transactions which were neither committed nor aborted
+    /// in the partition are committed only if they're a part of the committed transactions, as the server might have stopped
+    /// in the middle of committing them, otherwise they're aborted. The transactions file is then rewritten to contain only the aborted offsets.
+    pub async fn load_transactions(
+        &mut self,
+        committed_transactions: &AHashSet<u64>,
+    ) -> Result<(), IggyError> {
+        let markers = self
+            .storage
+            .partition
+            .load_transaction_markers(&self.transactions_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to load transaction markers, partition: {self}")
+            })?;
+        if markers.is_empty() {
+            return Ok(());
+        }
+
+        let mut pending_offsets = AHashMap::<u64, Vec<(u64, u64)>>::new();
+        let mut aborted_transactions = AHashSet::new();
+        let mut completed_transactions = AHashSet::new();
+        for marker in markers {
+            match marker {
+                TransactionMarker::Pending {
+                    transaction_id,
+                    first_offset,
+                    last_offset,
+                } => pending_offsets
+                    .entry(transaction_id)
+                    .or_default()
+                    .push((first_offset, last_offset)),
+                TransactionMarker::Committed { transaction_id } => {
+                    completed_transactions.insert(transaction_id);
+                }
+                TransactionMarker::Aborted { transaction_id } => {
+                    aborted_transactions.insert(transaction_id);
+                    completed_transactions.insert(transaction_id);
+                }
+            }
+        }
+
+        // The offsets of the messages which haven't been saved to disk before the server stopped might be reused by the new messages.
+        let max_offset = match self.should_increment_offset {
+            true => Some(self.current_offset),
+            false => None,
+        };
+        let mut aborted_offsets = Vec::new();
+        for (transaction_id, offsets) in pending_offsets {
+            let is_aborted = aborted_transactions.contains(&transaction_id)
+                || (!completed_transactions.contains(&transaction_id)
+                    && !committed_transactions.contains(&transaction_id));
+            if !is_aborted {
+                continue;
+            }
+
+            for (first_offset, last_offset) in offsets {
+                let Some(max_offset) = max_offset else {
+                    continue;
+                };
+                if first_offset > max_offset {
+                    continue;
+                }
+                aborted_offsets.push((first_offset, last_offset.min(max_offset)));
+            }
+        }
+
+        self.aborted_offsets.clear();
+        self.add_aborted_offsets(aborted_offsets);
+        let mut markers = Vec::with_capacity(self.aborted_offsets.len() + 1);
+        for (first_offset, last_offset) in self.aborted_offsets.iter().copied() {
+            markers.push(TransactionMarker::Pending {
+                transaction_id: RESOLVED_TRANSACTION_ID,
+                first_offset,
+                last_offset,
+            });
+        }
+        if !markers.is_empty() {
+            markers.push(TransactionMarker::Aborted {
+                transaction_id: RESOLVED_TRANSACTION_ID,
+            });
+        }
+        self.storage
+            .partition
+            .overwrite_transaction_markers(&self.transactions_path, &markers)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to overwrite transaction markers, partition: {self}")
+            })?;
+        info!(
+            "Loaded {} aborted offsets ranges for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+            self.aborted_offsets.len(),
+            self.partition_id,
+            self.stream_id,
+            self.topic_id
+        );
+        Ok(())
+    }
+
+    /// Returns the first offset of the oldest open transaction, the read committed consumers can't read beyond it.
+    pub fn get_last_stable_offset(&self) -> Option<u64> {
+        self.open_transactions
+            .values()
+            .flat_map(|offsets| offsets.iter().map(|(first_offset, _)| *first_offset))
+            .min()
+    }
+
+    pub fn is_aborted(&self, offset: u64) -> bool {
+        let index = self
+            .aborted_offsets
+            .partition_point(|(first_offset, _)| *first_offset <= offset);
+        index > 0 && self.aborted_offsets[index - 1].1 >= offset
+    }
+
+    /// Returns only the committed messages, skipping the aborted ones and stopping at the last stable offset.
+    /// If `refill` is set, the next messages are fetched to replace the skipped ones, up to the specified count.
+    pub async fn get_committed_messages(
+        &self,
+        messages: Vec<Arc<RetainedMessage>>,
+        count: u32,
+        refill: bool,
+    ) -> Result<Vec<Arc<RetainedMessage>>, IggyError> {
+        if self.open_transactions.is_empty() && self.aborted_offsets.is_empty() {
+            return Ok(messages);
+        }
+
+        let last_stable_offset = self.get_last_stable_offset();
+        let mut committed_messages = Vec::with_capacity(messages.len());
+        let mut messages = messages;
+        let mut requested_count = count as usize;
+        while let Some(last_offset) = messages.last().map(|message| message.offset) {
+            let fetched_count = messages.len();
+            for message in messages {
+                if last_stable_offset.is_some_and(|offset| message.offset >= offset) {
+                    return Ok(committed_messages);
+                }
+
+                if !self.is_aborted(message.offset) {
+                    committed_messages.push(message);
+                }
+            }
+
+            let remaining_count = (count as usize).saturating_sub(committed_messages.len());
+            if !refill || remaining_count == 0 || fetched_count < requested_count {
+                break;
+            }
+
+            requested_count = remaining_count;
+            messages = self
+                .get_messages_by_offset(last_offset + 1, remaining_count as u32)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to get messages by offset: {}, partition: {self}", last_offset + 1)
+                })?;
+        }
+
+        Ok(committed_messages)
+    }
+
+    fn add_aborted_offsets(&mut self, offsets: Vec<(u64, u64)>) {
+        self.aborted_offsets.extend(offsets);
+        self.aborted_offsets
+            .sort_by_key(|(first_offset, _)| *first_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::partitions::create_messages;
+    use crate::streaming::partitions::test_utils::{create_config, create_partition};
+    use iggy::utils::byte_size::IggyByteSize;
+    use iggy::utils::sizeable::Sizeable;
+    use tempfile::TempDir;
+
+    #[test]
+    fn transaction_marker_should_be_serialized_and_deserialized() {
+        let markers = [
+            TransactionMarker::Pending {
+                transaction_id: 1,
+                first_offset: 2,
+                last_offset: 3,
+            },
+            TransactionMarker::Committed { transaction_id: 4 },
+            TransactionMarker::Aborted { transaction_id: 5 },
+        ];
+
+        for marker in markers {
+            let bytes = marker.as_bytes();
+            assert_eq!(bytes.len(), TRANSACTION_MARKER_SIZE);
+            assert_eq!(TransactionMarker::from_bytes(&bytes).unwrap(), marker);
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_of_open_transaction_should_not_be_returned_as_committed() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, None).await;
+        append_messages(&mut partition, Some(1)).await;
+        let messages_count = partition.get_messages_count() as u32;
+
+        let messages = get_committed_messages(&partition, messages_count).await;
+
+        assert_eq!(partition.get_last_stable_offset(), Some(6));
+        assert_eq!(messages.len(), 6);
+        assert!(messages.iter().all(|message| message.offset < 6));
+    }
+
+    #[tokio::test]
+    async fn messages_of_committed_transaction_should_be_returned_as_committed() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, Some(1)).await;
+        append_messages(&mut partition, None).await;
+        partition.commit_transaction(1).await.unwrap();
+        let messages_count = partition.get_messages_count() as u32;
+
+        let messages = get_committed_messages(&partition, messages_count).await;
+
+        assert_eq!(partition.get_last_stable_offset(), None);
+        assert_eq!(messages.len(), messages_count as usize);
+    }
+
+    #[tokio::test]
+    async fn messages_of_aborted_transaction_should_be_skipped_and_refilled() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, Some(1)).await;
+        append_messages(&mut partition, None).await;
+        partition.abort_transaction(1).await.unwrap();
+
+        let messages = partition.get_messages_by_offset(0, 6).await.unwrap();
+        let messages = partition
+            .get_committed_messages(messages, 6, true)
+            .await
+            .unwrap();
+
+        assert!(partition.is_aborted(0));
+        assert!(partition.is_aborted(5));
+        assert!(!partition.is_aborted(6));
+        assert_eq!(messages.len(), 6);
+        assert!(messages.iter().all(|message| message.offset >= 6));
+    }
+
+    #[tokio::test]
+    async fn unresolved_transactions_should_be_aborted_when_loaded() {
+        let tempdir = TempDir::new().unwrap();
+        let mut partition = create_partition(Arc::new(create_config(&tempdir)), None, 1).await;
+        append_messages(&mut partition, Some(1)).await;
+        append_messages(&mut partition, Some(2)).await;
+        append_messages(&mut partition, Some(3)).await;
+        partition.commit_transaction(1).await.unwrap();
+        partition.open_transactions.clear();
+
+        partition
+            .load_transactions(&AHashSet::from([2]))
+            .await
+            .unwrap();
+
+        assert!(partition.open_transactions.is_empty());
+        assert_eq!(partition.aborted_offsets, vec![(12, 17)]);
+        let markers = partition
+            .storage
+            .partition
+            .load_transaction_markers(&partition.transactions_path)
+            .await
+            .unwrap();
+        assert_eq!(
+            markers,
+            vec![
+                TransactionMarker::Pending {
+                    transaction_id: RESOLVED_TRANSACTION_ID,
+                    first_offset: 12,
+                    last_offset: 17,
+                },
+                TransactionMarker::Aborted {
+                    transaction_id: RESOLVED_TRANSACTION_ID,
+                },
+            ]
+        );
+    }
+
+    async fn append_messages(partition: &mut Partition, transaction_id: Option<u64>) {
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo {
+            batch_size: messages
+                .iter()
+                .map(|m| m.get_size_bytes())
+                .sum::<IggyByteSize>(),
+            partition_id: partition.partition_id,
+        };
+        match transaction_id {
+            Some(transaction_id) => partition
+                .append_transactional_messages(
+                    transaction_id,
+                    appendable_batch_info,
+                    messages,
+                    None,
+                )
+                .await
+                .unwrap(),
+            None => partition
+                .append_messages(appendable_batch_info, messages, None)
+                .await
+                .unwrap(),
+        }
+    }
+
+    async fn get_committed_messages(
+        partition: &Partition,
+        count: u32,
+    ) -> Vec<Arc<RetainedMessage>> {
+        let messages = partition.get_messages_by_offset(0, count).await.unwrap();
+        partition
+            .get_committed_messages(messages, count, false)
+            .await
+            .unwrap()
+    }
+}
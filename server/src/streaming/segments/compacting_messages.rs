@@ -0,0 +1,271 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use super::indexes::*;
+use crate::streaming::batching::iterator::IntoMessagesIterator;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::persistence::persister::{FileWithSyncPersister, Persister};
+use crate::streaming::segments::segment::Segment;
+use crate::streaming::utils::file;
+use bytes::{BufMut, BytesMut};
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::utils::byte_size::IggyByteSize;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use tracing::{info, trace, warn};
+
+const COMPACTED_EXTENSION: &str = "compacted";
+
+/// The compacted copy of the closed segment, stored next to its files until it replaces them.
+#[derive(Debug)]
+pub struct CompactedSegment {
+    pub start_offset: u64,
+    pub end_offset: u64,
+    pub removed_messages: u64,
+    log_path: String,
+    index_path: String,
+    log_size_bytes: u64,
+    index_size_bytes: u64,
+    indexes: Vec<Index>,
+}
+
+impl CompactedSegment {
+    /// Removes the compacted files, e.g. once the segment was deleted in the meantime.
+    pub async fn discard(self) {
+        for path in [&self.log_path, &self.index_path] {
+            if let Err(error) = file::remove(path).await {
+                warn!("Failed to remove compacted file: {path}. {error}");
+            }
+        }
+    }
+}
+
+impl Segment {
+    /// Writes the compacted copy of the closed segment keeping only the messages matching the predicate,
+    /// and returns it only if any message was removed. The last message is always retained, so that the segment
+    /// keeps its offset range once loaded from disk, and the offsets of the retained messages don't change.
+    /// The segment itself is not modified, thus it can be called on its read-only copy (see `load_archived`).
+    pub async fn write_compacted(
+        &self,
+        retain: impl Fn(&RetainedMessage) -> bool,
+    ) -> Result<Option<CompactedSegment>, IggyError> {
+        if !self.is_closed {
+            return Ok(None);
+        }
+
+        let batches = self.get_all_batches().await.with_error_context(|error| {
+            format!("Failed to load batches for compaction of {self}. {error}")
+        })?;
+
+        let mut removed_messages = 0;
+        let mut log_bytes = BytesMut::new();
+        let mut index_bytes = BytesMut::new();
+        let mut indexes = Vec::new();
+        for batch in batches {
+            let mut payload = BytesMut::new();
+            for message in batch.into_messages_iter() {
+                if message.offset != self.current_offset && !retain(&message) {
+                    removed_messages += 1;
+                    continue;
+                }
+                message.extend(&mut payload);
+            }
+
+            if payload.is_empty() {
+                continue;
+            }
+
+            let payload_length = IggyByteSize::from(payload.len() as u64);
+            let compacted_batch = RetainedMessageBatch::new(
+                batch.base_offset,
+                batch.last_offset_delta,
+                batch.max_timestamp,
+                payload_length,
+                payload.freeze(),
+            )
+            .compress(self.compression_algorithm)?;
+            let index = Index {
+                offset: (compacted_batch.get_last_offset() - self.start_offset) as u32,
+                position: log_bytes.len() as u32,
+                timestamp: compacted_batch.max_timestamp,
+            };
+            index_bytes.put_u32_le(index.offset);
+            index_bytes.put_u32_le(index.position);
+            index_bytes.put_u64_le(index.timestamp);
+            indexes.push(index);
+            log_bytes.put_slice(&compacted_batch.to_bytes());
+        }
+
+        if removed_messages == 0 {
+            trace!("No messages to remove when compacting {self}.");
+            return Ok(None);
+        }
+
+        let compacted_segment = CompactedSegment {
+            start_offset: self.start_offset,
+            end_offset: self.current_offset,
+            removed_messages,
+            log_path: format!("{}.{COMPACTED_EXTENSION}", self.log_path),
+            index_path: format!("{}.{COMPACTED_EXTENSION}", self.index_path),
+            log_size_bytes: log_bytes.len() as u64,
+            index_size_bytes: index_bytes.len() as u64,
+            indexes,
+        };
+        // Both files are synced before replacing the segment ones, so that the crash never leaves them incomplete.
+        let persister = FileWithSyncPersister;
+        persister
+            .overwrite(&compacted_segment.log_path, &log_bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "Failed to write compacted log file: {}. {error}",
+                    compacted_segment.log_path
+                )
+            })?;
+        persister
+            .overwrite(&compacted_segment.index_path, &index_bytes)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "Failed to write compacted index file: {}. {error}",
+                    compacted_segment.index_path
+                )
+            })?;
+        Ok(Some(compacted_segment))
+    }
+
+    /// Replaces the files of the segment with the compacted ones and returns the number of removed messages.
+    /// The log file is replaced first, so that `recover_compaction` can complete the replacement interrupted by the crash.
+    pub async fn replace_with_compacted(
+        &mut self,
+        compacted_segment: CompactedSegment,
+    ) -> Result<u64, IggyError> {
+        self.shutdown_reading().await;
+        file::rename(&compacted_segment.log_path, &self.log_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "Failed to replace log file: {} with the compacted one. {error}",
+                    self.log_path
+                )
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        file::rename(&compacted_segment.index_path, &self.index_path)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "Failed to replace index file: {} with the compacted one. {error}",
+                    self.index_path
+                )
+            })
+            .map_err(|_| IggyError::CannotWriteToFile)?;
+        sync_parent_directory(&self.log_path).await?;
+
+        let log_size_bytes = compacted_segment.log_size_bytes;
+        self.log_size_bytes.store(log_size_bytes, Ordering::Release);
+        self.index_size_bytes
+            .store(compacted_segment.index_size_bytes, Ordering::Release);
+        self.initialize_reading().await?;
+
+        let removed_bytes = self
+            .size_bytes
+            .as_bytes_u64()
+            .saturating_sub(log_size_bytes);
+        self.size_bytes = IggyByteSize::from(log_size_bytes);
+        self.last_index_position = log_size_bytes as u32;
+        self.uncompressed_size_bytes = IggyByteSize::from(
+            self.log_reader
+                .as_ref()
+                .unwrap()
+                .calculate_uncompressed_size()
+                .await
+                .with_error_context(|error| {
+                    format!("Failed to calculate uncompressed size for {self}. {error}")
+                })?,
+        );
+        if self.indexes.is_some() {
+            self.indexes = Some(compacted_segment.indexes);
+        }
+        self.size_of_parent_stream
+            .fetch_sub(removed_bytes, Ordering::AcqRel);
+        self.size_of_parent_topic
+            .fetch_sub(removed_bytes, Ordering::AcqRel);
+        self.size_of_parent_partition
+            .fetch_sub(removed_bytes, Ordering::AcqRel);
+
+        let removed_messages = compacted_segment.removed_messages;
+        info!(
+            "Compacted segment with start offset: {} for partition with ID: {}, removed {removed_messages} messages and {}.",
+            self.start_offset,
+            self.partition_id,
+            IggyByteSize::from(removed_bytes)
+        );
+        Ok(removed_messages)
+    }
+
+    /// Completes or discards the replacement of the segment files with the compacted ones interrupted by the crash.
+    /// The compacted log file which is still present means that none of the files was replaced yet,
+    /// while the compacted index file alone means that only the log file was replaced.
+    pub async fn recover_compaction(log_path: &str, index_path: &str) -> Result<(), IggyError> {
+        let compacted_log_path = format!("{log_path}.{COMPACTED_EXTENSION}");
+        let compacted_index_path = format!("{index_path}.{COMPACTED_EXTENSION}");
+        let compacted_log_exists = file::exists(&compacted_log_path)
+            .await
+            .map_err(|_| IggyError::CannotReadFile)?;
+        let compacted_index_exists = file::exists(&compacted_index_path)
+            .await
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if compacted_log_exists {
+            warn!("Discarding the incomplete compaction of segment: {log_path}.");
+            file::remove(&compacted_log_path)
+                .await
+                .map_err(|_| IggyError::CannotDeleteFile)?;
+            if compacted_index_exists {
+                file::remove(&compacted_index_path)
+                    .await
+                    .map_err(|_| IggyError::CannotDeleteFile)?;
+            }
+            return Ok(());
+        }
+
+        if compacted_index_exists {
+            warn!("Completing the interrupted compaction of segment: {log_path}.");
+            file::rename(&compacted_index_path, index_path)
+                .await
+                .map_err(|_| IggyError::CannotWriteToFile)?;
+            sync_parent_directory(index_path).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn sync_parent_directory(path: &str) -> Result<(), IggyError> {
+    let Some(directory_path) = Path::new(path).parent().and_then(|path| path.to_str()) else {
+        return Ok(());
+    };
+
+    file::open(directory_path)
+        .await
+        .map_err(|_| IggyError::CannotReadFile)?
+        .sync_all()
+        .await
+        .with_error_context(|error| format!("Failed to sync directory: {directory_path}. {error}"))
+        .map_err(|_| IggyError::CannotSyncFile)
+}
@@ -19,7 +19,10 @@
 use crate::streaming::{
     batching::{
         iterator::IntoMessagesIterator,
-        message_batch::{RetainedMessageBatch, RETAINED_BATCH_HEADER_LEN},
+        message_batch::{
+            RetainedMessageBatch, RETAINED_BATCH_COMPRESSED_FLAG,
+            RETAINED_BATCH_COMPRESSION_HEADER_LEN, RETAINED_BATCH_HEADER_LEN,
+        },
     },
     segments::indexes::IndexRange,
 };
@@ -234,6 +237,57 @@ impl SegmentLogReader {
         Ok(())
     }
 
+    /// Calculates the size of the log file as if none of its batches were compressed.
+    /// Only the batch headers (and compression headers) are read, the payloads are skipped.
+    pub async fn calculate_uncompressed_size(&self) -> Result<u64, IggyError> {
+        let file_size = self.file_size();
+        let mut offset = 0_u64;
+        let mut uncompressed_size = 0_u64;
+
+        while offset + RETAINED_BATCH_HEADER_LEN <= file_size {
+            let header_buf = self
+                .read_at(offset + 8, 4)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "Failed to read batch length at offset {offset} in file {}: {error}",
+                        self.file_path
+                    )
+                })
+                .map_err(|_| IggyError::CannotReadBatchLength)?;
+            let batch_length = u32::from_le_bytes(
+                header_buf[0..4]
+                    .try_into()
+                    .map_err(|_| IggyError::CannotReadBatchLength)?,
+            );
+            let payload_len = (batch_length & !RETAINED_BATCH_COMPRESSED_FLAG) as u64;
+            let payload_offset = offset + RETAINED_BATCH_HEADER_LEN;
+            if payload_offset + payload_len > file_size {
+                break;
+            }
+
+            if batch_length & RETAINED_BATCH_COMPRESSED_FLAG != 0 {
+                let compression_header = self
+                    .read_at(payload_offset, RETAINED_BATCH_COMPRESSION_HEADER_LEN as u64)
+                    .await
+                    .with_error_context(|error| {
+                        format!(
+                            "Failed to read compression header at offset {payload_offset} in file {}: {error}",
+                            self.file_path
+                        )
+                    })
+                    .map_err(|_| IggyError::CannotReadBatchPayload)?;
+                uncompressed_size += RETAINED_BATCH_HEADER_LEN
+                    + RetainedMessageBatch::get_uncompressed_length(&compression_header)? as u64;
+            } else {
+                uncompressed_size += RETAINED_BATCH_HEADER_LEN + payload_len;
+            }
+            offset = payload_offset + payload_len;
+        }
+
+        Ok(uncompressed_size)
+    }
+
     async fn read_next_batch(
         &self,
         offset: u64,
@@ -308,7 +362,8 @@ impl SegmentLogReader {
                 .map_err(|_| IggyError::CannotReadMaxTimestamp)?,
         );
 
-        let payload_len = batch_length as usize;
+        let is_compressed = batch_length & RETAINED_BATCH_COMPRESSED_FLAG != 0;
+        let payload_len = (batch_length & !RETAINED_BATCH_COMPRESSED_FLAG) as usize;
         let payload_offset = offset + batch_header_size;
         if payload_offset + payload_len as u64 > file_size {
             warn!(
@@ -331,13 +386,29 @@ impl SegmentLogReader {
         };
 
         let bytes_read = batch_header_size + payload_len as u64;
-        let batch = RetainedMessageBatch::new(
-            batch_base_offset,
-            last_offset_delta,
-            max_timestamp,
-            IggyByteSize::from(payload_len as u64),
-            BytesMut::from(&payload_buf[..]).freeze(),
-        );
+        let payload = BytesMut::from(&payload_buf[..]).freeze();
+        let batch = if is_compressed {
+            RetainedMessageBatch::from_compressed(
+                batch_base_offset,
+                last_offset_delta,
+                max_timestamp,
+                payload,
+            )
+            .with_error_context(|error| {
+                format!(
+                    "Failed to decompress batch payload at offset {payload_offset} in file {}: {error}",
+                    self.file_path
+                )
+            })?
+        } else {
+            RetainedMessageBatch::new(
+                batch_base_offset,
+                last_offset_delta,
+                max_timestamp,
+                IggyByteSize::from(payload_len as u64),
+                payload,
+            )
+        };
 
         Ok(Some((batch, bytes_read)))
     }
@@ -97,7 +97,7 @@ impl SegmentLogWriter {
                 );
                 (None, Some(persister))
             }
-            Confirmation::Wait => (Some(file), None),
+            Confirmation::Wait | Confirmation::Quorum => (Some(file), None),
         };
 
         Ok(Self {
@@ -117,7 +117,8 @@ impl SegmentLogWriter {
     ) -> Result<IggyByteSize, IggyError> {
         let batch_size = batch.get_size_bytes();
         match confirmation {
-            Confirmation::Wait => {
+            // The replicas acknowledgements are awaited by the topic, once the batch is written.
+            Confirmation::Wait | Confirmation::Quorum => {
                 self.write_batch(batch).await?;
                 self.log_size_bytes
                     .fetch_add(batch_size.as_bytes_u64(), Ordering::AcqRel);
@@ -16,12 +16,14 @@
  * under the License.
  */
 
+mod compacting_messages;
 mod indexes;
 mod logs;
 mod reading_messages;
 mod segment;
 mod writing_messages;
 
+pub use compacting_messages::CompactedSegment;
 pub use indexes::Index;
 pub use segment::Segment;
 
@@ -22,6 +22,7 @@ use crate::configs::system::SystemConfig;
 use crate::streaming::batching::batch_accumulator::BatchAccumulator;
 use crate::streaming::segments::*;
 use error_set::ErrContext;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
@@ -44,6 +45,7 @@ pub struct Segment {
     pub index_path: String,
     pub log_path: String,
     pub size_bytes: IggyByteSize,
+    pub uncompressed_size_bytes: IggyByteSize,
     pub last_index_position: u32,
     pub max_size_bytes: IggyByteSize,
     pub size_of_parent_stream: Arc<AtomicU64>,
@@ -58,6 +60,7 @@ pub struct Segment {
     pub(super) index_writer: Option<SegmentIndexWriter>,
     pub(super) index_reader: Option<SegmentIndexReader>,
     pub message_expiry: IggyExpiry,
+    pub compression_algorithm: CompressionAlgorithm,
     pub unsaved_messages: Option<BatchAccumulator>,
     pub config: Arc<SystemConfig>,
     pub indexes: Option<Vec<Index>>,
@@ -74,6 +77,7 @@ impl Segment {
         start_offset: u64,
         config: Arc<SystemConfig>,
         message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
         size_of_parent_stream: Arc<AtomicU64>,
         size_of_parent_topic: Arc<AtomicU64>,
         size_of_parent_partition: Arc<AtomicU64>,
@@ -105,9 +109,11 @@ impl Segment {
             log_path,
             index_path,
             size_bytes: IggyByteSize::from(0),
+            uncompressed_size_bytes: IggyByteSize::from(0),
             last_index_position: 0,
             max_size_bytes: config.segment.size,
             message_expiry,
+            compression_algorithm,
             indexes,
             unsaved_messages: None,
             is_closed: false,
@@ -145,6 +151,16 @@ impl Segment {
         // TODO(hubcio): in future, remove size_bytes and use only atomic log_size_bytes everywhere
         self.size_bytes = IggyByteSize::from(log_size_bytes);
         self.last_index_position = log_size_bytes as _;
+        self.uncompressed_size_bytes = IggyByteSize::from(
+            self.log_reader
+                .as_ref()
+                .unwrap()
+                .calculate_uncompressed_size()
+                .await
+                .with_error_context(|error| {
+                    format!("Failed to calculate uncompressed size for {self}. {error}")
+                })?,
+        );
 
         self.indexes = Some(
             self.index_reader
@@ -208,6 +224,40 @@ impl Segment {
         Ok(())
     }
 
+    /// Load the closed segment fetched from the archive only for reading, without affecting the stats of its parents.
+    pub async fn load_archived(
+        &mut self,
+        log_path: String,
+        index_path: String,
+    ) -> Result<(), IggyError> {
+        self.log_path = log_path;
+        self.index_path = index_path;
+        self.initialize_reading().await?;
+
+        let log_size_bytes = self.log_size_bytes.load(Ordering::Acquire);
+        self.size_bytes = IggyByteSize::from(log_size_bytes);
+        self.last_index_position = log_size_bytes as _;
+        let indexes = self
+            .index_reader
+            .as_ref()
+            .unwrap()
+            .load_all_indexes_impl()
+            .await
+            .with_error_context(|error| format!("Failed to load indexes for {self}. {error}"))
+            .map_err(|_| IggyError::CannotReadFile)?;
+        if let Some(index) = indexes.last() {
+            self.current_offset = self.start_offset + index.offset as u64;
+        }
+
+        self.end_offset = self.current_offset;
+        self.is_closed = true;
+        self.indexes = match self.config.segment.cache_indexes {
+            true => Some(indexes),
+            false => None,
+        };
+        Ok(())
+    }
+
     /// Save the segment state to disk.
     pub async fn persist(&mut self) -> Result<(), IggyError> {
         info!("Saving segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}",
@@ -430,6 +480,7 @@ mod tests {
             start_offset,
             config,
             message_expiry,
+            CompressionAlgorithm::None,
             size_of_parent_stream,
             size_of_parent_topic,
             size_of_parent_partition,
@@ -482,6 +533,7 @@ mod tests {
             start_offset,
             config,
             message_expiry,
+            CompressionAlgorithm::None,
             size_of_parent_stream,
             size_of_parent_topic,
             size_of_parent_partition,
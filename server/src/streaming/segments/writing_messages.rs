@@ -60,6 +60,7 @@ impl Segment {
 
         self.current_offset = curr_offset;
         self.size_bytes += batch_size;
+        self.uncompressed_size_bytes += batch_size;
         let batch_size = batch_size.as_bytes_u64();
         self.size_of_parent_stream
             .fetch_add(batch_size, Ordering::AcqRel);
@@ -124,6 +125,15 @@ impl Segment {
         );
 
         let batch = batch_accumulator.materialize_batch_and_update_state();
+        let uncompressed_batch_size = batch.get_size_bytes();
+        let batch = batch
+            .compress(self.compression_algorithm)
+            .with_error_context(|error| {
+                format!(
+                    "Failed to compress batch of size {uncompressed_batch_size} using {} for {self}. {error}",
+                    self.compression_algorithm
+                )
+            })?;
         let batch_size = batch.get_size_bytes();
         if batch_size > 0 {
             self.unsaved_messages = Some(batch_accumulator);
@@ -151,6 +161,7 @@ impl Segment {
 
         self.last_index_position += batch_size.as_bytes_u64() as u32;
         self.size_bytes += IggyByteSize::from(RETAINED_BATCH_HEADER_LEN);
+        self.uncompressed_size_bytes += IggyByteSize::from(RETAINED_BATCH_HEADER_LEN);
         self.size_of_parent_stream
             .fetch_add(RETAINED_BATCH_HEADER_LEN, Ordering::AcqRel);
         self.size_of_parent_topic
@@ -158,6 +169,20 @@ impl Segment {
         self.size_of_parent_partition
             .fetch_add(RETAINED_BATCH_HEADER_LEN, Ordering::AcqRel);
 
+        // The appended messages were accounted with their uncompressed size, so the difference
+        // has to be subtracted once the batch is stored on disk in the compressed form.
+        let compression_savings =
+            uncompressed_batch_size.as_bytes_u64() - batch_size.as_bytes_u64();
+        if compression_savings > 0 {
+            self.size_bytes -= IggyByteSize::from(compression_savings);
+            self.size_of_parent_stream
+                .fetch_sub(compression_savings, Ordering::AcqRel);
+            self.size_of_parent_topic
+                .fetch_sub(compression_savings, Ordering::AcqRel);
+            self.size_of_parent_partition
+                .fetch_sub(compression_savings, Ordering::AcqRel);
+        }
+
         trace!(
             "Saved {} messages on disk in segment with start offset: {} for partition with ID: {}, total bytes written: {}.",
             unsaved_messages_number,
@@ -16,16 +16,21 @@
  * under the License.
  */
 
+use iggy::compression::wire_compression::WireCompression;
+use iggy::models::handshake::ProtocolFeatures;
 use iggy::models::user_info::{AtomicUserId, UserId};
+use iggy::utils::byte_size::IggyByteSize;
 use std::fmt::Display;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
 // This might be extended with more fields in the future e.g. custom name, permissions etc.
 #[derive(Debug)]
 pub struct Session {
     user_id: AtomicUserId,
     active: AtomicBool,
+    features: AtomicU32,
+    compression_threshold: AtomicU64,
     pub client_id: u32,
     pub ip_address: SocketAddr,
 }
@@ -35,6 +40,8 @@ impl Session {
         Self {
             client_id,
             active: AtomicBool::new(true),
+            features: AtomicU32::new(0),
+            compression_threshold: AtomicU64::new(0),
             user_id: AtomicUserId::new(user_id),
             ip_address,
         }
@@ -71,6 +78,28 @@ impl Session {
     pub fn is_authenticated(&self) -> bool {
         self.get_user_id() > 0
     }
+
+    /// Returns the protocol features negotiated during the handshake, none if there was no handshake.
+    pub fn get_features(&self) -> ProtocolFeatures {
+        ProtocolFeatures::from_flags(self.features.load(Ordering::Acquire))
+    }
+
+    pub fn set_features(&self, features: ProtocolFeatures) {
+        self.features.store(features.as_flags(), Ordering::Release)
+    }
+
+    /// Returns the compression of the frame payloads negotiated during the handshake, if any.
+    pub fn get_compression(&self) -> Option<WireCompression> {
+        WireCompression::negotiated(
+            &self.get_features(),
+            IggyByteSize::from(self.compression_threshold.load(Ordering::Acquire)),
+        )
+    }
+
+    pub fn set_compression_threshold(&self, threshold: IggyByteSize) {
+        self.compression_threshold
+            .store(threshold.as_bytes_u64(), Ordering::Release)
+    }
 }
 
 impl Display for Session {
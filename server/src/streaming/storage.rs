@@ -17,10 +17,14 @@
  */
 
 use super::persistence::persister::PersisterKind;
+use crate::archiver::ArchiverKind;
 use crate::configs::system::SystemConfig;
 use crate::state::system::{PartitionState, StreamState, TopicState};
+use crate::streaming::partitions::archive::{ArchiveCache, ArchivedSegment};
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
+use crate::streaming::partitions::producers::ProducerState;
 use crate::streaming::partitions::storage::FilePartitionStorage;
+use crate::streaming::partitions::transactions::TransactionMarker;
 use crate::streaming::streams::storage::FileStreamStorage;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::info::SystemInfo;
@@ -77,6 +81,7 @@ pub enum TopicStorageKind {
 }
 
 #[derive(Debug)]
+#[cfg_attr(test, allow(clippy::large_enum_variant))]
 pub enum PartitionStorageKind {
     File(FilePartitionStorage),
     #[cfg(test)]
@@ -139,6 +144,57 @@ pub trait PartitionStorage: Send {
         &self,
         path: &str,
     ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn append_transaction_marker(
+        &self,
+        path: &str,
+        marker: TransactionMarker,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn overwrite_transaction_markers(
+        &self,
+        path: &str,
+        markers: &[TransactionMarker],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_transaction_markers(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<TransactionMarker>, IggyError>> + Send;
+    fn append_producer_state(
+        &self,
+        path: &str,
+        state: ProducerState,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn overwrite_producer_states(
+        &self,
+        path: &str,
+        states: &[ProducerState],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_producer_states(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<ProducerState>, IggyError>> + Send;
+    fn overwrite_archived_segments(
+        &self,
+        path: &str,
+        archived_segments: &[ArchivedSegment],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_archived_segments(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<ArchivedSegment>, IggyError>> + Send;
+    fn append_poisoned_offset(
+        &self,
+        path: &str,
+        offset: u64,
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn overwrite_poisoned_offsets(
+        &self,
+        path: &str,
+        offsets: &[u64],
+    ) -> impl Future<Output = Result<(), IggyError>> + Send;
+    fn load_poisoned_offsets(
+        &self,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<u64>, IggyError>> + Send;
 }
 
 #[derive(Debug)]
@@ -148,6 +204,8 @@ pub struct SystemStorage {
     pub topic: Arc<TopicStorageKind>,
     pub partition: Arc<PartitionStorageKind>,
     pub persister: Arc<PersisterKind>,
+    pub archiver: Option<Arc<ArchiverKind>>,
+    pub archive_cache: Arc<ArchiveCache>,
 }
 
 impl SystemStorage {
@@ -163,6 +221,8 @@ impl SystemStorage {
                 persister.clone(),
             ))),
             persister,
+            archiver: None,
+            archive_cache: Arc::new(ArchiveCache::new(config.runtime.archive_cache_size)),
         }
     }
 }
@@ -204,5 +264,45 @@ impl PartitionStorageKind {
         ) -> Result<Vec<ConsumerOffset>, IggyError>;
         async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError>;
         async fn delete_consumer_offset(&self, path: &str) -> Result<(), IggyError>;
+        async fn append_transaction_marker(
+            &self,
+            path: &str,
+            marker: TransactionMarker
+        ) -> Result<(), IggyError>;
+        async fn overwrite_transaction_markers(
+            &self,
+            path: &str,
+            markers: &[TransactionMarker]
+        ) -> Result<(), IggyError>;
+        async fn load_transaction_markers(
+            &self,
+            path: &str
+        ) -> Result<Vec<TransactionMarker>, IggyError>;
+        async fn append_producer_state(
+            &self,
+            path: &str,
+            state: ProducerState
+        ) -> Result<(), IggyError>;
+        async fn overwrite_producer_states(
+            &self,
+            path: &str,
+            states: &[ProducerState]
+        ) -> Result<(), IggyError>;
+        async fn load_producer_states(
+            &self,
+            path: &str
+        ) -> Result<Vec<ProducerState>, IggyError>;
+        async fn overwrite_archived_segments(
+            &self,
+            path: &str,
+            archived_segments: &[ArchivedSegment]
+        ) -> Result<(), IggyError>;
+        async fn load_archived_segments(
+            &self,
+            path: &str
+        ) -> Result<Vec<ArchivedSegment>, IggyError>;
+        async fn append_poisoned_offset(&self, path: &str, offset: u64) -> Result<(), IggyError>;
+        async fn overwrite_poisoned_offsets(&self, path: &str, offsets: &[u64]) -> Result<(), IggyError>;
+        async fn load_poisoned_offsets(&self, path: &str) -> Result<Vec<u64>, IggyError>;
     }
 }
@@ -17,6 +17,7 @@
  */
 
 use crate::streaming::streams::stream::Stream;
+use iggy::error::IggyError;
 
 impl Stream {
     pub fn get_partitions_count(&self) -> u32 {
@@ -27,4 +28,19 @@ impl Stream {
 
         partitions_count
     }
+
+    pub fn ensure_partitions_limit(&self, partitions_count: u32) -> Result<(), IggyError> {
+        let Some(max_partitions) = self.limits.max_partitions else {
+            return Ok(());
+        };
+
+        if self.get_partitions_count() + partitions_count > max_partitions {
+            return Err(IggyError::StreamPartitionsLimitReached(
+                self.stream_id,
+                max_partitions,
+            ));
+        }
+
+        Ok(())
+    }
 }
@@ -20,12 +20,15 @@ use crate::configs::system::SystemConfig;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::topics::topic::Topic;
 use ahash::AHashMap;
+use iggy::models::stream_limits::StreamLimits;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::fmt::Display;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 
+const ALMOST_FULL_THRESHOLD: f64 = 0.9;
+
 #[derive(Debug)]
 pub struct Stream {
     pub stream_id: u32,
@@ -37,6 +40,7 @@ pub struct Stream {
     pub size_bytes: Arc<AtomicU64>,
     pub messages_count: Arc<AtomicU64>,
     pub segments_count: Arc<AtomicU32>,
+    pub limits: StreamLimits,
     pub(crate) topics: AHashMap<u32, Topic>,
     pub(crate) topics_ids: AHashMap<String, u32>,
     pub(crate) config: Arc<SystemConfig>,
@@ -72,6 +76,7 @@ impl Stream {
             size_bytes: Arc::new(AtomicU64::new(0)),
             messages_count: Arc::new(AtomicU64::new(0)),
             segments_count: Arc::new(AtomicU32::new(0)),
+            limits: StreamLimits::default(),
             topics: AHashMap::new(),
             topics_ids: AHashMap::new(),
             storage,
@@ -82,6 +87,27 @@ impl Stream {
     pub fn get_size(&self) -> IggyByteSize {
         IggyByteSize::from(self.size_bytes.load(Ordering::SeqCst))
     }
+
+    pub fn is_full(&self) -> bool {
+        match self.limits.max_size {
+            Some(max_size) => self.size_bytes.load(Ordering::SeqCst) >= max_size.as_bytes_u64(),
+            None => false,
+        }
+    }
+
+    pub fn is_almost_full(&self) -> bool {
+        match self.limits.max_size {
+            Some(max_size) => {
+                self.size_bytes.load(Ordering::SeqCst)
+                    >= (max_size.as_bytes_u64() as f64 * ALMOST_FULL_THRESHOLD) as u64
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.limits.max_size.is_none()
+    }
 }
 
 impl Display for Stream {
@@ -24,6 +24,7 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use std::sync::atomic::Ordering;
@@ -34,18 +35,15 @@ impl Stream {
         self.topics.len() as u32
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn create_topic(
-        &mut self,
+    /// Returns the ID of the topic to be created, which is either the given one or the next available one.
+    pub fn validate_create_topic(
+        &self,
         topic_id: Option<u32>,
         name: &str,
         partitions_count: u32,
-        message_expiry: IggyExpiry,
-        compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
-        replication_factor: u8,
     ) -> Result<u32, IggyError> {
-        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
+        Topic::get_max_topic_size(max_topic_size, &self.config)?;
         if self.topics_ids.contains_key(name) {
             return Err(IggyError::TopicNameAlreadyExists(
                 name.to_owned(),
@@ -53,6 +51,16 @@ impl Stream {
             ));
         }
 
+        if let Some(max_topics) = self.limits.max_topics {
+            if self.get_topics_count() >= max_topics {
+                return Err(IggyError::StreamTopicsLimitReached(
+                    self.stream_id,
+                    max_topics,
+                ));
+            }
+        }
+
+        self.ensure_partitions_limit(partitions_count)?;
         let mut id;
         if topic_id.is_none() {
             id = self.current_topic_id.fetch_add(1, Ordering::SeqCst);
@@ -74,6 +82,23 @@ impl Stream {
             return Err(IggyError::TopicIdAlreadyExists(id, self.stream_id));
         }
 
+        Ok(id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_topic(
+        &mut self,
+        topic_id: Option<u32>,
+        name: &str,
+        partitions_count: u32,
+        message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
+        max_topic_size: MaxTopicSize,
+        replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
+    ) -> Result<u32, IggyError> {
+        let id = self.validate_create_topic(topic_id, name, partitions_count, max_topic_size)?;
+        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
         let topic = Topic::create(
             self.stream_id,
             id,
@@ -88,6 +113,7 @@ impl Stream {
             compression_algorithm,
             max_topic_size,
             replication_factor,
+            cleanup_policy,
         )
         .await?;
         topic.persist().await.with_error_context(|error| {
@@ -99,6 +125,7 @@ impl Stream {
         Ok(id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_topic(
         &mut self,
         id: &Identifier,
@@ -107,9 +134,11 @@ impl Stream {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<(), IggyError> {
         let message_expiry = Topic::get_message_expiry(message_expiry, &self.config);
-        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
+        let segments_compression_algorithm =
+            Topic::get_compression_algorithm(compression_algorithm, &self.config);
         let topic_id;
         {
             let topic = self.get_topic(id).with_error_context(|error| {
@@ -118,16 +147,8 @@ impl Stream {
             topic_id = topic.topic_id;
         }
 
-        {
-            if let Some(topic_id_by_name) = self.topics_ids.get(name) {
-                if *topic_id_by_name != topic_id {
-                    return Err(IggyError::TopicNameAlreadyExists(
-                        name.to_owned(),
-                        self.stream_id,
-                    ));
-                }
-            }
-        }
+        self.validate_update_topic(topic_id, name, max_topic_size)?;
+        let max_topic_size = Topic::get_max_topic_size(max_topic_size, &self.config)?;
 
         let old_topic_name = {
             let topic = self.get_topic(id).with_error_context(|error| {
@@ -149,12 +170,15 @@ impl Stream {
             for partition in topic.partitions.values_mut() {
                 let mut partition = partition.write().await;
                 partition.message_expiry = message_expiry;
+                partition.compression_algorithm = segments_compression_algorithm;
                 for segment in partition.segments.iter_mut() {
                     segment.message_expiry = message_expiry;
+                    segment.compression_algorithm = segments_compression_algorithm;
                 }
             }
             topic.max_topic_size = max_topic_size;
             topic.replication_factor = replication_factor;
+            topic.cleanup_policy = cleanup_policy;
             topic.persist().await.with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to persist topic: {topic}")
             })?;
@@ -164,6 +188,25 @@ impl Stream {
         Ok(())
     }
 
+    pub fn validate_update_topic(
+        &self,
+        topic_id: u32,
+        name: &str,
+        max_topic_size: MaxTopicSize,
+    ) -> Result<(), IggyError> {
+        Topic::get_max_topic_size(max_topic_size, &self.config)?;
+        if let Some(topic_id_by_name) = self.topics_ids.get(name) {
+            if *topic_id_by_name != topic_id {
+                return Err(IggyError::TopicNameAlreadyExists(
+                    name.to_owned(),
+                    self.stream_id,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn remove_topic(&mut self, identifier: &Identifier) -> Result<Topic, IggyError> {
         match identifier.kind {
             IdKind::Numeric => self.remove_topic_by_id(identifier.get_u32_value()?),
@@ -312,6 +355,7 @@ mod tests {
                 compression_algorithm,
                 max_topic_size,
                 1,
+                CleanupPolicy::default(),
             )
             .await
             .unwrap();
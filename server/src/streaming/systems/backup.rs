@@ -0,0 +1,675 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+use crate::streaming::segments::{INDEX_EXTENSION, LOG_EXTENSION};
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use crate::streaming::utils::file;
+use crate::versioning::SemanticVersion;
+use ahash::AHashSet;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
+use iggy::utils::timestamp::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+use tracing::{error, info, warn};
+
+pub const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+
+/// The manifest of the consistent backup of the whole server, written once all of its files have been stored.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BackupManifest {
+    pub version: String,
+    pub created_at: u64,
+    /// Index of the last state log entry included in the backup, `None` if the state log was empty.
+    pub state_index: Option<u64>,
+    /// Directories relative to the system directory, including the empty ones expected to exist when the partitions are loaded.
+    pub directories: Vec<String>,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BackupFile {
+    /// Path relative to the system directory.
+    pub path: String,
+    pub size: u64,
+}
+
+/// The backup whose consistent cut has been taken, while the open segments are yet to be copied up to their sizes at the time of the cut.
+/// Their files are opened during the cut, so they can be copied even if the segments are deleted or compacted in the meantime.
+#[derive(Debug)]
+pub struct PendingBackup {
+    pub path: String,
+    manifest: BackupManifest,
+    open_segment_files: Vec<(BackupFile, fs::File)>,
+}
+
+impl System {
+    /// Takes the consistent cut of the server for the online backup, which must happen while no other operation is in progress.
+    /// The buffered messages are saved, the state and the partition files (consumer offsets, producers etc.) are copied,
+    /// and the closed segments are hard-linked (or copied, if not supported), as they are never modified in place.
+    pub async fn start_backup(&self) -> Result<PendingBackup, IggyError> {
+        let created_at = IggyTimestamp::now().as_micros();
+        let backup_path = format!("{}/{created_at}", self.config.get_online_backups_path());
+        let cannot_create_backup = || IggyError::CannotCreateBackup(backup_path.clone());
+        fs::create_dir_all(&backup_path)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to create backup directory: {backup_path}")
+            })
+            .map_err(|_| cannot_create_backup())?;
+
+        self.persist_messages().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to persist messages before backup")
+        })?;
+        let state_index = self
+            .state
+            .log()
+            .filter(|log| log.entries_count() > 0)
+            .map(|log| log.current_index());
+
+        let mut open_segment_paths = AHashSet::new();
+        for stream in self.streams.values() {
+            for topic in stream.get_topics() {
+                for partition in topic.get_partitions() {
+                    let partition = partition.read().await;
+                    for segment in partition.get_segments() {
+                        if !segment.is_closed {
+                            open_segment_paths.insert(PathBuf::from(&segment.log_path));
+                            open_segment_paths.insert(PathBuf::from(&segment.index_path));
+                        }
+                    }
+                }
+            }
+        }
+
+        let system_path = PathBuf::from(self.config.get_system_path());
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+        let mut open_segment_files = Vec::new();
+        for directory in [self.config.get_state_path(), self.config.get_streams_path()] {
+            let (directory_paths, file_paths) = get_files(&directory).await?;
+            for path in directory_paths {
+                let relative_path = path
+                    .strip_prefix(&system_path)
+                    .map_err(|_| cannot_create_backup())?;
+                directories.push(relative_path.to_string_lossy().to_string());
+            }
+
+            for path in file_paths {
+                let relative_path = path
+                    .strip_prefix(&system_path)
+                    .map_err(|_| cannot_create_backup())?;
+                let size = fs::metadata(&path)
+                    .await
+                    .map_err(|_| IggyError::CannotReadFileMetadata)?
+                    .len();
+                let backup_file = BackupFile {
+                    path: relative_path.to_string_lossy().to_string(),
+                    size,
+                };
+                if open_segment_paths.contains(&path) {
+                    let file = file::open(&path.to_string_lossy())
+                        .await
+                        .map_err(|_| IggyError::CannotReadFile)?;
+                    open_segment_files.push((backup_file, file));
+                    continue;
+                }
+
+                let destination = Path::new(&backup_path).join(relative_path);
+                create_parent_directory(&destination).await?;
+                let is_segment_file = path.extension().is_some_and(|extension| {
+                    extension == LOG_EXTENSION || extension == INDEX_EXTENSION
+                });
+                if !is_segment_file || fs::hard_link(&path, &destination).await.is_err() {
+                    fs::copy(&path, &destination)
+                        .await
+                        .with_error_context(|error| {
+                            format!(
+                                "{COMPONENT} (error: {error}) - failed to copy file: {} to backup: {backup_path}",
+                                path.display()
+                            )
+                        })
+                        .map_err(|_| cannot_create_backup())?;
+                }
+                files.push(backup_file);
+            }
+        }
+
+        info!(
+            "Started backup at: {backup_path}, state index: {state_index:?}, files: {}, open segment files: {}.",
+            files.len(),
+            open_segment_files.len()
+        );
+        Ok(PendingBackup {
+            manifest: BackupManifest {
+                version: SemanticVersion::current()?.to_string(),
+                created_at,
+                state_index,
+                directories,
+                files,
+            },
+            path: backup_path,
+            open_segment_files,
+        })
+    }
+
+    /// Restores the backup described by the manifest stored in the given directory, replacing the current state and streams.
+    /// The state log can be truncated to the chosen index, in which case the streams, topics and partitions created afterwards
+    /// are removed when the system is initialized, while the messages are restored as they were at the time of the backup.
+    /// It must be called before the system is initialized.
+    pub async fn restore_backup(
+        &self,
+        backup_path: &str,
+        state_index: Option<u64>,
+    ) -> Result<BackupManifest, IggyError> {
+        let invalid_backup = || IggyError::InvalidBackup(backup_path.to_string());
+        let manifest_path = Path::new(backup_path).join(BACKUP_MANIFEST_FILE);
+        let manifest = fs::read(&manifest_path).await.map_err(|error| {
+            error!(
+                "Cannot read backup manifest: {}. {error}",
+                manifest_path.display()
+            );
+            invalid_backup()
+        })?;
+        let manifest: BackupManifest = serde_json::from_slice(&manifest).map_err(|error| {
+            error!(
+                "Cannot parse backup manifest: {}. {error}",
+                manifest_path.display()
+            );
+            invalid_backup()
+        })?;
+
+        let version = manifest
+            .version
+            .parse::<SemanticVersion>()
+            .map_err(|_| invalid_backup())?;
+        if version.is_greater_than(&SemanticVersion::current()?) {
+            error!("Backup at: {backup_path} was created by the newer server version: {version}.");
+            return Err(invalid_backup());
+        }
+
+        if let Some(index) = state_index {
+            match manifest.state_index {
+                Some(last_index) if index <= last_index => {}
+                last_index => {
+                    return Err(IggyError::InvalidBackupStateIndex(
+                        index,
+                        last_index.unwrap_or_default(),
+                    ))
+                }
+            }
+        }
+
+        for file in &manifest.files {
+            let path = Path::new(backup_path).join(&file.path);
+            let size = fs::metadata(&path).await.map(|metadata| metadata.len());
+            if size.as_ref().ok() != Some(&file.size) {
+                error!(
+                    "Backup file: {} is missing or its size doesn't match the manifest: {}.",
+                    path.display(),
+                    file.size
+                );
+                return Err(invalid_backup());
+            }
+        }
+
+        info!(
+            "Restoring backup from: {backup_path}, created at: {}, state index: {:?}...",
+            IggyTimestamp::from(manifest.created_at),
+            manifest.state_index
+        );
+        for directory in [self.config.get_state_path(), self.config.get_streams_path()] {
+            if Path::new(&directory).exists() && fs::remove_dir_all(&directory).await.is_err() {
+                error!("Cannot remove directory: {directory} to restore the backup.");
+                return Err(IggyError::CannotDeleteFile);
+            }
+        }
+
+        let system_path = self.config.get_system_path();
+        for directory in &manifest.directories {
+            let path = Path::new(&system_path).join(directory);
+            fs::create_dir_all(&path).await.map_err(|error| {
+                error!("Cannot create directory: {}. {error}", path.display());
+                IggyError::CannotCreateBaseDirectory(path.display().to_string())
+            })?;
+        }
+
+        for file in &manifest.files {
+            let destination = Path::new(&system_path).join(&file.path);
+            create_parent_directory(&destination).await?;
+            fs::copy(Path::new(backup_path).join(&file.path), &destination)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to restore backup file: {}",
+                        file.path
+                    )
+                })
+                .map_err(|_| IggyError::CannotWriteToFile)?;
+        }
+
+        if let (Some(index), Some(log)) = (state_index, self.state.log()) {
+            log.truncate(index).await.with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to truncate state log to index: {index}"
+                )
+            })?;
+            info!("Truncated restored state log to index: {index}.");
+        }
+
+        info!("Restored backup from: {backup_path}.");
+        Ok(manifest)
+    }
+
+    /// Removes the oldest online backups, keeping only the specified number of the most recent ones.
+    pub async fn delete_old_backups(&self, retained_backups: u32) -> Result<(), IggyError> {
+        let backups_path = self.config.get_online_backups_path();
+        let Ok(mut entries) = fs::read_dir(&backups_path).await else {
+            return Ok(());
+        };
+
+        let mut backups = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name();
+            if let Ok(created_at) = name.to_string_lossy().parse::<u64>() {
+                backups.push((created_at, entry.path()));
+            }
+        }
+
+        backups.sort_by_key(|(created_at, _)| *created_at);
+        let deleted_backups_count = backups.len().saturating_sub(retained_backups as usize);
+        for (_, path) in backups.into_iter().take(deleted_backups_count) {
+            if fs::remove_dir_all(&path).await.is_err() {
+                warn!("Cannot remove old backup at: {}.", path.display());
+                continue;
+            }
+            info!("Removed old backup at: {}.", path.display());
+        }
+        Ok(())
+    }
+}
+
+impl PendingBackup {
+    /// Copies the open segments up to their sizes at the time of the cut and writes the manifest, which completes the backup.
+    pub async fn complete(self) -> Result<BackupManifest, IggyError> {
+        let backup_path = self.path;
+        let cannot_create_backup = || IggyError::CannotCreateBackup(backup_path.clone());
+        let mut manifest = self.manifest;
+        for (backup_file, file) in self.open_segment_files {
+            let destination = Path::new(&backup_path).join(&backup_file.path);
+            create_parent_directory(&destination).await?;
+            let mut destination_file = fs::File::create(&destination)
+                .await
+                .map_err(|_| cannot_create_backup())?;
+            let copied_bytes =
+                tokio::io::copy(&mut file.take(backup_file.size), &mut destination_file)
+                    .await
+                    .with_error_context(|error| {
+                        format!(
+                            "{COMPONENT} (error: {error}) - failed to copy open segment file: {} to backup: {backup_path}",
+                            backup_file.path
+                        )
+                    })
+                    .map_err(|_| cannot_create_backup())?;
+            if copied_bytes != backup_file.size {
+                error!(
+                    "Copied: {copied_bytes} bytes of open segment file: {} to backup: {backup_path}, expected: {}.",
+                    backup_file.path, backup_file.size
+                );
+                return Err(cannot_create_backup());
+            }
+            destination_file
+                .sync_all()
+                .await
+                .map_err(|_| cannot_create_backup())?;
+            manifest.files.push(backup_file);
+        }
+
+        let manifest_bytes =
+            serde_json::to_vec_pretty(&manifest).map_err(|_| cannot_create_backup())?;
+        let manifest_path = format!("{backup_path}/{BACKUP_MANIFEST_FILE}");
+        // The manifest is written under a temporary name, so the incomplete backup is never mistaken for a valid one.
+        let temporary_manifest_path = format!("{manifest_path}.tmp");
+        fs::write(&temporary_manifest_path, manifest_bytes)
+            .await
+            .map_err(|_| cannot_create_backup())?;
+        file::rename(&temporary_manifest_path, &manifest_path)
+            .await
+            .map_err(|_| cannot_create_backup())?;
+        info!(
+            "Completed backup at: {backup_path}, state index: {:?}, files: {}.",
+            manifest.state_index,
+            manifest.files.len()
+        );
+        Ok(manifest)
+    }
+}
+
+/// Returns all the directories (including the given one) and files found recursively in the given directory.
+async fn get_files(directory: &str) -> Result<(Vec<PathBuf>, Vec<PathBuf>), IggyError> {
+    let mut directories = Vec::new();
+    let mut files = Vec::new();
+    let mut pending_directories = vec![PathBuf::from(directory)];
+    while let Some(directory) = pending_directories.pop() {
+        let Ok(mut entries) = fs::read_dir(&directory).await else {
+            continue;
+        };
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|_| IggyError::CannotReadFile)?
+        {
+            let path = entry.path();
+            if path.is_dir() {
+                pending_directories.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+        directories.push(directory);
+    }
+    Ok((directories, files))
+}
+
+async fn create_parent_directory(path: &Path) -> Result<(), IggyError> {
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+
+    fs::create_dir_all(parent).await.map_err(|error| {
+        error!("Cannot create directory: {}. {error}", parent.display());
+        IggyError::CannotCreateBaseDirectory(parent.display().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::cluster::ClusterConfig;
+    use crate::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
+    use crate::configs::system::SystemConfig;
+    use crate::state::command::EntryCommand;
+    use crate::state::models::{CreateStreamWithId, CreateTopicWithId};
+    use crate::streaming::session::Session;
+    use bytes::Bytes;
+    use iggy::identifier::Identifier;
+    use iggy::messages::send_messages::{Message, Partitioning};
+    use iggy::streams::create_stream::CreateStream;
+    use iggy::topics::create_topic::CreateTopic;
+    use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const STREAM_ID: u32 = 1;
+    const TOPIC_ID: u32 = 1;
+    const PARTITION_ID: u32 = 1;
+    const MESSAGES_COUNT: u32 = 10;
+    const STREAM_STATE_INDEX: u64 = 1;
+
+    #[tokio::test]
+    async fn backup_should_be_restored_into_another_system() {
+        let tempdir = TempDir::new().unwrap();
+        let system = init_system_with_messages(&tempdir).await;
+
+        let manifest = system
+            .start_backup()
+            .await
+            .unwrap()
+            .complete()
+            .await
+            .unwrap();
+
+        let backup_path = get_backup_path(&system, &manifest);
+        let mut restored_system = create_system(&tempdir, "restored");
+        let restored_manifest = restored_system
+            .restore_backup(&backup_path, None)
+            .await
+            .unwrap();
+        restored_system.init().await.unwrap();
+
+        assert_eq!(restored_manifest, manifest);
+        assert_eq!(manifest.state_index, Some(STREAM_STATE_INDEX + 1));
+        let messages = get_messages(&restored_system).await;
+        assert_eq!(messages.len() as u32, MESSAGES_COUNT);
+        for (offset, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, offset as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn backup_should_be_restored_to_chosen_state_index() {
+        let tempdir = TempDir::new().unwrap();
+        let system = init_system_with_messages(&tempdir).await;
+        let manifest = system
+            .start_backup()
+            .await
+            .unwrap()
+            .complete()
+            .await
+            .unwrap();
+
+        let backup_path = get_backup_path(&system, &manifest);
+        let mut restored_system = create_system(&tempdir, "restored");
+        restored_system
+            .restore_backup(&backup_path, Some(STREAM_STATE_INDEX))
+            .await
+            .unwrap();
+        restored_system.init().await.unwrap();
+
+        let stream = restored_system
+            .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+            .unwrap();
+        assert_eq!(stream.get_topics_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn backup_should_not_be_restored_to_state_index_greater_than_backed_up_one() {
+        let tempdir = TempDir::new().unwrap();
+        let system = init_system_with_messages(&tempdir).await;
+        let manifest = system
+            .start_backup()
+            .await
+            .unwrap()
+            .complete()
+            .await
+            .unwrap();
+        let backup_path = get_backup_path(&system, &manifest);
+        let restored_system = create_system(&tempdir, "restored");
+
+        let result = restored_system
+            .restore_backup(&backup_path, Some(STREAM_STATE_INDEX + 2))
+            .await;
+
+        assert_eq!(
+            result,
+            Err(IggyError::InvalidBackupStateIndex(
+                STREAM_STATE_INDEX + 2,
+                STREAM_STATE_INDEX + 1
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn incomplete_backup_should_not_be_restored() {
+        let tempdir = TempDir::new().unwrap();
+        let system = init_system_with_messages(&tempdir).await;
+        let pending_backup = system.start_backup().await.unwrap();
+        let restored_system = create_system(&tempdir, "restored");
+
+        let result = restored_system
+            .restore_backup(&pending_backup.path, None)
+            .await;
+
+        assert_eq!(
+            result,
+            Err(IggyError::InvalidBackup(pending_backup.path.clone()))
+        );
+    }
+
+    #[tokio::test]
+    async fn only_retained_number_of_backups_should_be_kept() {
+        let tempdir = TempDir::new().unwrap();
+        let system = init_system_with_messages(&tempdir).await;
+        let mut manifests = Vec::new();
+        for _ in 0..3 {
+            manifests.push(
+                system
+                    .start_backup()
+                    .await
+                    .unwrap()
+                    .complete()
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        system.delete_old_backups(1).await.unwrap();
+
+        assert!(!Path::new(&get_backup_path(&system, &manifests[0])).exists());
+        assert!(!Path::new(&get_backup_path(&system, &manifests[1])).exists());
+        assert!(Path::new(&get_backup_path(&system, &manifests[2])).exists());
+    }
+
+    async fn init_system_with_messages(tempdir: &TempDir) -> System {
+        let mut system = create_system(tempdir, "data");
+        system.init().await.unwrap();
+        let session = Session::new(
+            1,
+            DEFAULT_ROOT_USER_ID,
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234),
+        );
+        let stream_name = "stream";
+        system
+            .create_stream(&session, Some(STREAM_ID), stream_name, None)
+            .await
+            .unwrap();
+        system
+            .state
+            .apply(
+                DEFAULT_ROOT_USER_ID,
+                EntryCommand::CreateStream(CreateStreamWithId {
+                    stream_id: STREAM_ID,
+                    command: CreateStream {
+                        stream_id: Some(STREAM_ID),
+                        name: stream_name.to_string(),
+                        limits: None,
+                    },
+                }),
+            )
+            .await
+            .unwrap();
+        let create_topic = CreateTopic {
+            stream_id: Identifier::numeric(STREAM_ID).unwrap(),
+            topic_id: Some(TOPIC_ID),
+            partitions_count: 1,
+            name: "topic".to_string(),
+            ..Default::default()
+        };
+        system
+            .create_topic(
+                &session,
+                &create_topic.stream_id,
+                create_topic.topic_id,
+                &create_topic.name,
+                create_topic.partitions_count,
+                create_topic.message_expiry,
+                create_topic.compression_algorithm,
+                create_topic.max_topic_size,
+                create_topic.replication_factor,
+                create_topic.cleanup_policy.clone(),
+            )
+            .await
+            .unwrap();
+        system
+            .state
+            .apply(
+                DEFAULT_ROOT_USER_ID,
+                EntryCommand::CreateTopic(CreateTopicWithId {
+                    topic_id: TOPIC_ID,
+                    command: create_topic,
+                    dead_letter: false,
+                }),
+            )
+            .await
+            .unwrap();
+        let messages = (0..MESSAGES_COUNT)
+            .map(|id| {
+                Message::new(
+                    Some(id as u128 + 1),
+                    Bytes::from(format!("message {id}")),
+                    None,
+                )
+            })
+            .collect();
+        system
+            .append_messages(
+                &session,
+                Identifier::numeric(STREAM_ID).unwrap(),
+                Identifier::numeric(TOPIC_ID).unwrap(),
+                Partitioning::partition_id(PARTITION_ID),
+                None,
+                messages,
+                None,
+            )
+            .await
+            .unwrap();
+        system
+    }
+
+    async fn get_messages(
+        system: &System,
+    ) -> Vec<std::sync::Arc<crate::streaming::models::messages::RetainedMessage>> {
+        let topic = system
+            .get_stream(&Identifier::numeric(STREAM_ID).unwrap())
+            .unwrap()
+            .get_topic(&Identifier::numeric(TOPIC_ID).unwrap())
+            .unwrap();
+        let partition = topic.get_partition(PARTITION_ID).unwrap();
+        let partition = partition.read().await;
+        partition
+            .get_messages_by_offset(0, MESSAGES_COUNT)
+            .await
+            .unwrap()
+    }
+
+    fn get_backup_path(system: &System, manifest: &BackupManifest) -> String {
+        format!(
+            "{}/{}",
+            system.config.get_online_backups_path(),
+            manifest.created_at
+        )
+    }
+
+    fn create_system(tempdir: &TempDir, path: &str) -> System {
+        let config = Arc::new(SystemConfig {
+            path: tempdir.path().join(path).to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        System::new(
+            config,
+            DataMaintenanceConfig::default(),
+            PersonalAccessTokenConfig::default(),
+            ClusterConfig::default(),
+        )
+    }
+}
@@ -20,11 +20,15 @@ use crate::streaming::clients::client_manager::{Client, Transport};
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
 use crate::streaming::systems::COMPONENT;
+use crate::versioning::SemanticVersion;
+use crate::VERSION;
 use error_set::ErrContext;
+use iggy::compression::wire_compression::WireCompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::handshake::{HandshakeInfo, ProtocolFeatures};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::{error, info};
@@ -40,6 +44,7 @@ impl System {
 
     pub async fn delete_client(&self, client_id: u32) {
         let consumer_groups: Vec<(u32, u32, u32)>;
+        let transaction;
 
         {
             let mut client_manager = self.client_manager.write().await;
@@ -50,8 +55,10 @@ impl System {
             }
 
             self.metrics.decrement_clients(1);
+            self.throttler.delete_client(client_id);
             let client = client.unwrap();
-            let client = client.read().await;
+            let mut client = client.write().await;
+            transaction = client.transaction.take();
             consumer_groups = client
                 .consumer_groups
                 .iter()
@@ -74,6 +81,46 @@ impl System {
                 )
                 .await
         }
+
+        if let Some(transaction) = transaction {
+            self.abort_client_transaction(client_id, transaction).await;
+        }
+    }
+
+    /// Agrees on the protocol features with the client, the negotiated ones are kept in its session.
+    /// The server responds with all the features enabled for the transport, so the client can negotiate them on its own.
+    pub async fn handshake(
+        &self,
+        session: &Session,
+        sdk_version: &str,
+        features: ProtocolFeatures,
+    ) -> Result<HandshakeInfo, IggyError> {
+        let client = self
+            .client_manager
+            .read()
+            .await
+            .try_get_client(session.client_id)
+            .ok_or(IggyError::ClientNotFound(session.client_id))?;
+        let mut client = client.write().await;
+        let mut enabled_features = client.transport.protocol_features();
+        if self.config.wire_compression.enabled {
+            enabled_features = enabled_features.union(&WireCompressionAlgorithm::all_features());
+        }
+        let negotiated_features = features.intersection(&enabled_features);
+        session.set_features(negotiated_features);
+        session.set_compression_threshold(self.config.wire_compression.threshold);
+        client.sdk_version = Some(sdk_version.to_owned());
+        info!(
+            "Client with session: {session} using SDK version: {sdk_version} has negotiated the protocol features: {negotiated_features}"
+        );
+        Ok(HandshakeInfo {
+            iggy_server_version: VERSION.to_owned(),
+            iggy_server_semver: SemanticVersion::current()
+                .ok()
+                .and_then(|version| version.get_numeric_version().ok()),
+            features: enabled_features,
+            user_id: session.is_authenticated().then(|| session.get_user_id()),
+        })
     }
 
     pub async fn get_client(
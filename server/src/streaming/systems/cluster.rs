@@ -0,0 +1,331 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::state::command::EntryCommand;
+use crate::state::entry::StateEntry;
+use crate::state::StateKind;
+use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::{SharedSystem, System};
+use crate::streaming::systems::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::models::cluster::ClusterMetadata;
+use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+impl SharedSystem {
+    /// Changes the metadata only once the state entry with the given command is committed, so that
+    /// the change which might still be lost (e.g. not replicated across the cluster in time) is never visible.
+    /// Without the cluster, the change is applied first and its entry is appended to the log afterwards.
+    ///
+    /// The command is returned by `prepare` after validating it against the current metadata, along with
+    /// the value passed to the caller, e.g. the ID of the created resource. The changes are serialized,
+    /// thus the command can't be invalidated by the other one before it's applied.
+    pub async fn apply_entry<T>(
+        &self,
+        session: &Session,
+        prepare: impl FnOnce(&System) -> Result<(EntryCommand, T), IggyError>,
+    ) -> Result<T, IggyError> {
+        let _metadata_guard = self.metadata_lock.lock().await;
+        let (state, command, value) = {
+            let system = self.read().await;
+            system.ensure_cluster_leader().await?;
+            let (command, value) = prepare(&system)?;
+            (system.state.clone(), command, value)
+        };
+
+        let Some(raft) = state.as_raft() else {
+            // Without the cluster, the entry is appended only once it's applied, thus the log never
+            // contains the entry which failed to apply (and would fail again when loaded on startup).
+            let log = state.log().ok_or(IggyError::FeatureUnavailable)?;
+            let entry = log.create_entry(session.get_user_id(), command);
+            let mut system = self.write().await;
+            system.apply_state_entry(entry.clone()).await?;
+            log.append_entry(&entry).await.with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to append applied state entry with index: {}",
+                    entry.index
+                )
+            })?;
+            return Ok(value);
+        };
+
+        let entry = state.apply(session.get_user_id(), command).await?;
+        let mut system = self.write().await;
+
+        // The entries committed before this one might not be applied yet, and the order must be preserved.
+        let mut result = Ok(value);
+        for committed_entry in raft.take_committed_entries().await {
+            let index = committed_entry.index;
+            match system.apply_state_entry(committed_entry).await {
+                Err(error) if index == entry.index => result = Err(error),
+                Err(error) => error!("{COMPONENT} (error: {error}) - failed to apply committed state entry with index: {index}"),
+                Ok(()) => {}
+            }
+        }
+        result
+    }
+}
+
+impl System {
+    pub async fn get_cluster_metadata(
+        &self,
+        session: &Session,
+    ) -> Result<ClusterMetadata, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .get_cluster_metadata(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to get cluster metadata for user with id: {}",
+                    session.get_user_id()
+                )
+            })?;
+        let Some(raft) = self.state.as_raft() else {
+            return Err(IggyError::FeatureUnavailable);
+        };
+
+        Ok(raft.get_metadata().await)
+    }
+
+    /// Returns the state used to handle the Raft requests sent by the other cluster nodes.
+    pub fn get_cluster_state(&self, session: &Session) -> Result<Arc<StateKind>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .replicate_state(session.get_user_id())
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to replicate state for user with id: {}",
+                    session.get_user_id()
+                )
+            })?;
+        Ok(self.state.clone())
+    }
+
+    /// Ensures that the metadata can be changed on this node, which is always the case unless
+    /// the cluster is enabled and this node is not its leader.
+    pub async fn ensure_cluster_leader(&self) -> Result<(), IggyError> {
+        match self.state.as_raft() {
+            Some(raft) => raft.ensure_leader().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the state entries committed by the cluster leader to this node.
+    /// The entry which cannot be applied is skipped, so that it doesn't block the subsequent ones.
+    pub async fn apply_state_entries(&mut self, entries: Vec<StateEntry>) {
+        for entry in entries {
+            let index = entry.index;
+            if let Err(error) = self.apply_state_entry(entry).await {
+                error!("{COMPONENT} (error: {error}) - failed to apply committed state entry with index: {index}");
+            }
+        }
+    }
+
+    /// Applies the committed state entry. Its command was already validated (including the permissions
+    /// of the user) by the node which appended it, thus it's applied on behalf of the root user.
+    async fn apply_state_entry(&mut self, entry: StateEntry) -> Result<(), IggyError> {
+        debug!("Applying committed state entry: {entry}");
+        let session = &Session::stateless(
+            DEFAULT_ROOT_USER_ID,
+            SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+        );
+        match entry.command()? {
+            EntryCommand::Noop => {}
+            EntryCommand::CreateStream(command) => {
+                self.create_stream(
+                    session,
+                    Some(command.stream_id),
+                    &command.command.name,
+                    command.command.limits,
+                )
+                .await?;
+            }
+            EntryCommand::UpdateStream(command) => {
+                self.update_stream(session, &command.stream_id, &command.name, command.limits)
+                    .await?;
+            }
+            EntryCommand::DeleteStream(command) => {
+                self.delete_stream(session, &command.stream_id).await?;
+            }
+            EntryCommand::PurgeStream(command) => {
+                self.purge_stream(session, &command.stream_id).await?;
+            }
+            EntryCommand::CreateTopic(command) => {
+                let topic_id = command.topic_id;
+                let dead_letter = command.dead_letter;
+                let command = command.command;
+                self.create_topic(
+                    session,
+                    &command.stream_id,
+                    Some(topic_id),
+                    &command.name,
+                    command.partitions_count,
+                    command.message_expiry,
+                    command.compression_algorithm,
+                    command.max_topic_size,
+                    command.replication_factor,
+                    command.cleanup_policy,
+                )
+                .await?;
+                if dead_letter {
+                    self.get_stream_mut(&command.stream_id)?
+                        .get_topic_mut(&Identifier::numeric(topic_id)?)?
+                        .dead_letter = true;
+                }
+            }
+            EntryCommand::UpdateTopic(command) => {
+                self.update_topic(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    &command.name,
+                    command.message_expiry,
+                    command.compression_algorithm,
+                    command.max_topic_size,
+                    command.replication_factor,
+                    command.cleanup_policy,
+                )
+                .await?;
+            }
+            EntryCommand::DeleteTopic(command) => {
+                self.delete_topic(session, &command.stream_id, &command.topic_id)
+                    .await?;
+            }
+            EntryCommand::PurgeTopic(command) => {
+                self.purge_topic(session, &command.stream_id, &command.topic_id)
+                    .await?;
+            }
+            EntryCommand::CreatePartitions(command) => {
+                self.create_partitions(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partitions_count,
+                )
+                .await?;
+            }
+            EntryCommand::DeletePartitions(command) => {
+                self.delete_partitions(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partitions_count,
+                )
+                .await?;
+            }
+            EntryCommand::DeleteSegments(command) => {
+                self.delete_segments(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    command.partition_id,
+                    command.segments_count,
+                )
+                .await?;
+            }
+            EntryCommand::CreateConsumerGroup(command) => {
+                let group_id = command.group_id;
+                let command = command.command;
+                self.create_consumer_group(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    Some(group_id),
+                    &command.name,
+                    command.assignment_strategy,
+                )
+                .await?;
+            }
+            EntryCommand::DeleteConsumerGroup(command) => {
+                self.delete_consumer_group(
+                    session,
+                    &command.stream_id,
+                    &command.topic_id,
+                    &command.group_id,
+                )
+                .await?;
+            }
+            EntryCommand::CreateUser(command) => {
+                let user_id = command.user_id;
+                let oidc_subject = command.oidc_subject;
+                let command = command.command;
+                // The password is already hashed.
+                self.create_user_with_password_hash(
+                    user_id,
+                    &command.username,
+                    command.password,
+                    command.status,
+                    command.permissions,
+                    oidc_subject,
+                )?;
+            }
+            EntryCommand::UpdateUser(command) => {
+                self.update_user(
+                    session,
+                    &command.user_id,
+                    command.username,
+                    command.status,
+                    command.quotas,
+                )
+                .await?;
+            }
+            EntryCommand::DeleteUser(command) => {
+                self.delete_user(session, &command.user_id).await?;
+            }
+            EntryCommand::ChangePassword(command) => {
+                // The new password is already hashed.
+                self.change_password_hash(&command.user_id, command.new_password)?;
+            }
+            EntryCommand::UpdatePermissions(command) => {
+                self.update_permissions(session, &command.user_id, command.permissions)
+                    .await?;
+            }
+            EntryCommand::CreatePersonalAccessToken(command) => {
+                let user = self.get_user(&entry.user_id.try_into()?)?;
+                let expiry_at = PersonalAccessToken::calculate_expiry_at(
+                    entry.timestamp,
+                    command.command.expiry,
+                );
+                let personal_access_token = PersonalAccessToken::raw(
+                    entry.user_id,
+                    &command.command.name,
+                    &command.hash,
+                    expiry_at,
+                );
+                user.personal_access_tokens
+                    .insert(Arc::new(command.hash), personal_access_token);
+                info!(
+                    "Created personal access token: {} for user with ID: {}.",
+                    command.command.name, entry.user_id
+                );
+            }
+            EntryCommand::DeletePersonalAccessToken(command) => {
+                let user_session = Session::stateless(entry.user_id, session.ip_address);
+                self.delete_personal_access_token(&user_session, &command.name)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@ use crate::streaming::systems::system::System;
 use crate::streaming::systems::COMPONENT;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use error_set::ErrContext;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMutFn;
@@ -73,61 +74,80 @@ impl System {
         Ok(topic.get_consumer_groups())
     }
 
-    pub async fn create_consumer_group(
-        &mut self,
+    /// Returns the ID of the consumer group to be created, which is either the given one or the next available one.
+    pub fn validate_create_consumer_group(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: Option<u32>,
         name: &str,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let topic = self.find_topic(session, stream_id, topic_id)
-                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        let topic = self.find_topic(session, stream_id, topic_id)
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
 
-            self.permissioner.create_consumer_group(
-                session.get_user_id(),
-                topic.stream_id,
-                topic.topic_id,
-            ).with_error_context(|error| format!("{COMPONENT} (error: {error}) - permission denied to create consumer group for user {} on stream ID: {}, topic ID: {}", session.get_user_id(), topic.stream_id, topic.topic_id))?;
-        }
+        self.permissioner.create_consumer_group(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| format!("{COMPONENT} (error: {error}) - permission denied to create consumer group for user {} on stream ID: {}, topic ID: {}", session.get_user_id(), topic.stream_id, topic.topic_id))?;
+        topic.validate_create_consumer_group(group_id, name)
+    }
 
+    pub async fn create_consumer_group(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        group_id: Option<u32>,
+        name: &str,
+        assignment_strategy: AssignmentStrategy,
+    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+        let group_id =
+            self.validate_create_consumer_group(session, stream_id, topic_id, group_id, name)?;
         let topic = self.get_stream_mut(stream_id)?
             .get_topic_mut(topic_id)
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
 
         topic
-            .create_consumer_group(group_id, name)
+            .create_consumer_group(Some(group_id), name, assignment_strategy)
             .await
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to create consumer group with name: {name}")
             })
     }
 
-    pub async fn delete_consumer_group(
-        &mut self,
+    /// Returns the numeric IDs of the stream and the topic containing the consumer group to be deleted.
+    pub fn validate_delete_consumer_group(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         consumer_group_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<(u32, u32), IggyError> {
         self.ensure_authenticated(session)?;
-        let stream_id_value;
-        let topic_id_value;
-        {
-            let topic = self.find_topic(session, stream_id, topic_id)
-                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        let topic = self.find_topic(session, stream_id, topic_id)
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
 
-            self.permissioner.delete_consumer_group(
-                session.get_user_id(),
-                topic.stream_id,
-                topic.topic_id,
-            ).with_error_context(|error| format!("{COMPONENT} (error: {error}) - permission denied to delete consumer group for user {} on stream ID: {}, topic ID: {}", session.get_user_id(), topic.stream_id, topic.topic_id))?;
+        self.permissioner.delete_consumer_group(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| format!("{COMPONENT} (error: {error}) - permission denied to delete consumer group for user {} on stream ID: {}, topic ID: {}", session.get_user_id(), topic.stream_id, topic.topic_id))?;
+        topic.get_consumer_group(consumer_group_id)?;
+        Ok((topic.stream_id, topic.topic_id))
+    }
 
-            stream_id_value = topic.stream_id;
-            topic_id_value = topic.topic_id;
-        }
+    pub async fn delete_consumer_group(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        consumer_group_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        let (stream_id_value, topic_id_value) =
+            self.validate_delete_consumer_group(session, stream_id, topic_id, consumer_group_id)?;
 
         let consumer_group;
         {
@@ -23,9 +23,13 @@ use error_set::ErrContext;
 use iggy::consumer::Consumer;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_lag::ConsumerLag;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
+use tracing::warn;
 
 impl System {
+    #[allow(clippy::too_many_arguments)]
     pub async fn store_consumer_offset(
         &self,
         session: &Session,
@@ -34,6 +38,7 @@ impl System {
         topic_id: &Identifier,
         partition_id: Option<u32>,
         offset: u64,
+        generation_id: Option<u32>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let topic = self.find_topic(session, stream_id, topic_id)
@@ -45,7 +50,13 @@ impl System {
         )?;
 
         topic
-            .store_consumer_offset(consumer, offset, partition_id, session.client_id)
+            .store_consumer_offset(
+                consumer,
+                offset,
+                partition_id,
+                generation_id,
+                session.client_id,
+            )
             .await
     }
 
@@ -78,6 +89,68 @@ impl System {
             .await
     }
 
+    pub async fn get_consumer_lag(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<ConsumerLag, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id)
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic with ID: {topic_id} was not found in stream with ID: {stream_id}"))?;
+        self.permissioner.get_consumer_offset(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| {
+            format!(
+                "{COMPONENT} (error: {error}) - permission denied to get consumer lag for user with ID: {}, consumer: {consumer} in topic with ID: {topic_id} and stream with ID: {stream_id}",
+                session.get_user_id(),
+            )
+        })?;
+
+        let partitions = topic.get_consumer_lag(consumer).await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get consumer lag for consumer: {consumer} in topic with ID: {topic_id} and stream with ID: {stream_id}")
+        })?;
+        Ok(ConsumerLag {
+            stream_id: topic.stream_id,
+            topic_id: topic.topic_id,
+            partitions,
+        })
+    }
+
+    /// Refreshes the consumer lag metrics of all the consumers and consumer groups which have stored their offsets.
+    pub async fn update_consumer_lag_metrics(&self) {
+        self.metrics.clear_consumer_lag();
+        for stream in self.streams.values() {
+            for topic in stream.get_topics() {
+                for partition in topic.get_partitions() {
+                    let partition = partition.read().await;
+                    let consumers_lag = match partition.get_consumers_lag().await {
+                        Ok(consumers_lag) => consumers_lag,
+                        Err(error) => {
+                            warn!(
+                                "Cannot get consumers lag for partition with ID: {} for topic with ID: {} and stream with ID: {}. {error}",
+                                partition.partition_id, topic.topic_id, stream.stream_id
+                            );
+                            continue;
+                        }
+                    };
+                    for (kind, consumer_id, lag) in consumers_lag {
+                        self.metrics.set_consumer_lag(
+                            stream.stream_id,
+                            topic.topic_id,
+                            kind,
+                            consumer_id,
+                            &lag,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     pub async fn delete_consumer_offset(
         &self,
         session: &Session,
@@ -16,22 +16,50 @@
  * under the License.
  */
 
+use crate::state::command::EntryCommand;
+use crate::state::models::CreateTopicWithId;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
+use crate::streaming::clients::client_manager::TransactionPartition;
+use crate::streaming::partitions::partition::Partition;
+use crate::streaming::partitions::replicas::QuorumAcknowledgement;
+use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::session::Session;
-use crate::streaming::systems::system::System;
+use crate::streaming::systems::system::{SharedSystem, System};
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::topics::topic::Topic;
 use bytes::Bytes;
 use error_set::ErrContext;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::confirmation::Confirmation;
-use iggy::consumer::Consumer;
-use iggy::messages::poll_messages::PollingStrategy;
+use iggy::consumer::{Consumer, ConsumerKind};
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::messages::header_filter::HeaderFilter;
+use iggy::messages::nack_message::{
+    DEAD_LETTER_FAILED_DELIVERIES_HEADER, DEAD_LETTER_OFFSET_HEADER,
+    DEAD_LETTER_PARTITION_ID_HEADER, DEAD_LETTER_STREAM_ID_HEADER, DEAD_LETTER_TIMESTAMP_HEADER,
+    DEAD_LETTER_TOPIC_ID_HEADER,
+};
+use iggy::messages::poll_messages::{IsolationLevel, PollingKind, PollingStrategy, PollingWait};
 use iggy::messages::send_messages::Message;
-use iggy::messages::send_messages::Partitioning;
+use iggy::messages::send_messages::{Partitioning, ProducerSequence};
+use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::models::messages::{PolledMessage, PolledMessages};
+use iggy::topics::cleanup_policy::CleanupPolicy;
+use iggy::topics::create_topic::CreateTopic;
+use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
 use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
+use iggy::utils::topic_size::MaxTopicSize;
+use iggy::validatable::Validatable;
 use iggy::{error::IggyError, identifier::Identifier};
-use tracing::{error, trace};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::{timeout_at, Instant};
+use tracing::{error, info, trace};
 
 impl System {
     pub async fn poll_messages(
@@ -43,6 +71,28 @@ impl System {
         partition_id: Option<u32>,
         args: PollingArgs,
     ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_last_offset(
+            session,
+            consumer,
+            stream_id,
+            topic_id,
+            partition_id,
+            args,
+        )
+        .await
+        .map(|(polled_messages, _)| polled_messages)
+    }
+
+    /// Polls the messages along with the offset of the last scanned one, which might have been skipped by the header filter.
+    pub async fn poll_messages_with_last_offset(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        args: PollingArgs,
+    ) -> Result<(PolledMessages, Option<u64>), IggyError> {
         self.ensure_authenticated(session)?;
         if args.count == 0 {
             return Err(IggyError::InvalidMessagesCount);
@@ -57,33 +107,71 @@ impl System {
                 topic.stream_id,
                 topic.topic_id
             ))?;
+        self.throttler
+            .throttle_consume(session.get_user_id(), topic.stream_id)?;
 
         if !topic.has_partitions() {
             return Err(IggyError::NoPartitions(topic.topic_id, topic.stream_id));
         }
 
+        // The generation is read before resolving the partition, so in case of the concurrent rebalancing
+        // the member gets the outdated one, and its offset will be rejected rather than stored for the wrong partition.
+        let generation_id = match consumer.kind {
+            ConsumerKind::Consumer => 0,
+            ConsumerKind::ConsumerGroup => topic
+                .get_consumer_group(&consumer.id)?
+                .read()
+                .await
+                .get_generation_id(),
+        };
+
         // There might be no partition assigned, if it's the consumer group member without any partitions.
         let Some((polling_consumer, partition_id)) = topic
             .resolve_consumer_with_partition_id(consumer, session.client_id, partition_id, true)
             .await
             .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to resolve consumer with partition id, consumer: {consumer}, client ID: {}, partition ID: {:?}", session.client_id, partition_id))? else {
-            return Ok(PolledMessages {
+            return Ok((PolledMessages {
                 messages: vec![],
                 partition_id: 0,
                 current_offset: 0,
-            })
+                generation_id,
+            }, None))
         };
 
-        let mut polled_messages = topic
-            .get_messages(polling_consumer, partition_id, args.strategy, args.count)
+        let polled_at = Instant::now();
+        let (mut polled_messages, last_offset) = topic
+            .get_filtered_messages(
+                polling_consumer,
+                partition_id,
+                args.strategy,
+                args.count,
+                args.isolation_level,
+                args.filter.as_ref(),
+            )
             .await?;
+        let polled_size_bytes = polled_messages
+            .messages
+            .iter()
+            .map(|message| message.get_size_bytes().as_bytes_u64())
+            .sum();
+        self.metrics.record_polled_messages(
+            topic.stream_id,
+            topic.topic_id,
+            partition_id,
+            polled_messages.messages.len() as u64,
+            polled_size_bytes,
+            polled_at.elapsed(),
+        );
+        self.throttler
+            .record_consumed(session.get_user_id(), topic.stream_id, polled_size_bytes);
+        polled_messages.generation_id = generation_id;
 
-        if polled_messages.messages.is_empty() {
-            return Ok(polled_messages);
-        }
+        // With the header filter, the last scanned message might have been skipped, but its offset is committed anyway.
+        let Some(offset) = last_offset else {
+            return Ok((polled_messages, None));
+        };
 
-        let offset = polled_messages.messages.last().unwrap().offset;
-        if args.auto_commit {
+        if args.should_store_offset(&polled_messages) {
             trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", offset, consumer, stream_id, topic_id, partition_id);
             topic
                 .store_consumer_offset_internal(polling_consumer, offset, partition_id)
@@ -91,14 +179,187 @@ impl System {
                 .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset internal, polling consumer: {}, offset: {}, partition ID: {}", polling_consumer, offset, partition_id)) ?;
         }
 
-        if self.encryptor.is_none() {
-            return Ok(polled_messages);
+        self.decrypt_messages(&mut polled_messages)?;
+        Ok((polled_messages, last_offset))
+    }
+
+    /// Resolves the partition which the consumer polls from, along with the notification of the messages appended to it,
+    /// so the poll can wait for the new messages without holding the lock of the system.
+    pub async fn resolve_partition_to_wait_on(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Option<(u32, Arc<Notify>)>, IggyError> {
+        self.ensure_authenticated(session)?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner
+            .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+            .with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to poll messages for user {} on stream ID: {}, topic ID: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+
+        let Some((_, partition_id)) = topic
+            .resolve_consumer_with_partition_id(consumer, session.client_id, partition_id, true)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let partition = topic.get_partition(partition_id)?;
+        let messages_appended = partition.read().await.messages_appended.clone();
+        Ok(Some((partition_id, messages_appended)))
+    }
+
+    /// Returns whether the messages available to the consumer in the partition are enough to complete the waiting poll.
+    /// Nothing is committed, so the messages can be polled for real afterwards.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn has_messages_to_poll(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        args: &PollingArgs,
+        wait: &PollingWait,
+    ) -> Result<bool, IggyError> {
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        let Some((polling_consumer, partition_id)) = topic
+            .resolve_consumer_with_partition_id(
+                consumer,
+                session.client_id,
+                Some(partition_id),
+                false,
+            )
+            .await?
+        else {
+            return Ok(true);
+        };
+
+        let (polled_messages, _) = topic
+            .get_filtered_messages(
+                polling_consumer,
+                partition_id,
+                args.strategy,
+                args.count,
+                args.isolation_level,
+                args.filter.as_ref(),
+            )
+            .await?;
+        let size_bytes = polled_messages
+            .messages
+            .iter()
+            .map(|message| message.get_size_bytes().as_bytes_u64())
+            .sum();
+        Ok(wait.is_satisfied(
+            args.count,
+            polled_messages.messages.len() as u32,
+            size_bytes,
+        ))
+    }
+
+    pub async fn poll_assigned_messages(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        group_id: &Identifier,
+        args: PollingArgs,
+    ) -> Result<Vec<PolledMessages>, IggyError> {
+        self.ensure_authenticated(session)?;
+        if args.count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
         }
 
-        let encryptor = self.encryptor.as_ref().unwrap();
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner
+            .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+            .with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to poll assigned messages for user {} on stream ID: {}, topic ID: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+        self.throttler
+            .throttle_consume(session.get_user_id(), topic.stream_id)?;
+
+        if !topic.has_partitions() {
+            return Err(IggyError::NoPartitions(topic.topic_id, topic.stream_id));
+        }
+
+        // The generation is read together with the assigned partitions, so the offsets committed later by the member
+        // are rejected if the group has been rebalanced in the meantime.
+        let (polling_consumer, generation_id, partitions) = {
+            let consumer_group = topic.get_consumer_group(group_id)?.read().await;
+            let partitions = consumer_group
+                .get_member_partitions(session.client_id)
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get partitions assigned to member with ID: {} in consumer group: {group_id}", session.client_id))?;
+            (
+                PollingConsumer::consumer_group(consumer_group.group_id, session.client_id),
+                consumer_group.get_generation_id(),
+                partitions,
+            )
+        };
+
+        let mut partitions_messages = Vec::with_capacity(partitions.len());
+        for partition_id in partitions {
+            let (mut polled_messages, last_offset) = topic
+                .get_filtered_messages(
+                    polling_consumer,
+                    partition_id,
+                    args.strategy,
+                    args.count,
+                    args.isolation_level,
+                    args.filter.as_ref(),
+                )
+                .await?;
+            polled_messages.generation_id = generation_id;
+            self.throttler.record_consumed(
+                session.get_user_id(),
+                topic.stream_id,
+                polled_messages
+                    .messages
+                    .iter()
+                    .map(|message| message.get_size_bytes().as_bytes_u64())
+                    .sum(),
+            );
+            if args.should_store_offset(&polled_messages) {
+                if let Some(offset) = last_offset {
+                    trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", offset, polling_consumer, stream_id, topic_id, partition_id);
+                    topic
+                        .store_consumer_offset_internal(polling_consumer, offset, partition_id)
+                        .await
+                        .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to store consumer offset internal, polling consumer: {}, offset: {}, partition ID: {}", polling_consumer, offset, partition_id))?;
+                }
+            }
+
+            self.decrypt_messages(&mut polled_messages)?;
+            partitions_messages.push(polled_messages);
+        }
+
+        Ok(partitions_messages)
+    }
+
+    fn decrypt_messages(&self, polled_messages: &mut PolledMessages) -> Result<(), IggyError> {
+        let Some(encryptor) = self.encryptor.as_ref() else {
+            return Ok(());
+        };
+
         let mut decrypted_messages = Vec::with_capacity(polled_messages.messages.len());
         for message in polled_messages.messages.iter() {
-            let payload = encryptor.decrypt(&message.payload);
+            // The tombstone is stored unencrypted, as there's no payload to protect.
+            let payload = if message.payload.is_empty() {
+                Ok(Vec::new())
+            } else {
+                encryptor.decrypt(&message.payload)
+            };
             match payload {
                 Ok(payload) => {
                     decrypted_messages.push(PolledMessage {
@@ -119,18 +380,22 @@ impl System {
             }
         }
         polled_messages.messages = decrypted_messages;
-        Ok(polled_messages)
+        Ok(())
     }
 
+    /// Appends the messages and returns the acknowledgement of the quorum of the replicas, if required by the confirmation,
+    /// which should be awaited once the system lock is released.
+    #[allow(clippy::too_many_arguments)]
     pub async fn append_messages(
         &self,
         session: &Session,
         stream_id: Identifier,
         topic_id: Identifier,
         partitioning: Partitioning,
+        producer_sequence: Option<ProducerSequence>,
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<Option<QuorumAcknowledgement>, IggyError> {
         self.ensure_authenticated(session)?;
         let topic = self.find_topic(session, &stream_id, &topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
         self.permissioner.append_messages(
@@ -144,10 +409,24 @@ impl System {
             topic.topic_id
         ))?;
 
+        if self.config.replication.is_follower(&self.cluster_identity)
+            && topic.replication_factor > 1
+        {
+            return Err(IggyError::NotReplicationLeader);
+        }
+
+        self.ensure_stream_is_not_full(topic.stream_id)?;
         let mut batch_size_bytes = IggyByteSize::default();
         let mut messages = messages;
+        topic.prepare_messages_for_cleanup_policy(&partitioning, &mut messages)?;
         if let Some(encryptor) = &self.encryptor {
             for message in messages.iter_mut() {
+                // The tombstone has to remain empty to be recognized when compacting the topic.
+                if message.payload.is_empty() {
+                    batch_size_bytes += message.get_size_bytes();
+                    continue;
+                }
+
                 let payload = encryptor.encrypt(&message.payload);
                 match payload {
                     Ok(payload) => {
@@ -168,17 +447,83 @@ impl System {
                 .sum::<IggyByteSize>();
         }
 
+        self.throttler.throttle_produce(
+            session.get_user_id(),
+            topic.stream_id,
+            batch_size_bytes.as_bytes_u64(),
+        )?;
         if let Some(memory_tracker) = CacheMemoryTracker::get_instance() {
             if !memory_tracker.will_fit_into_cache(batch_size_bytes) {
                 self.clean_cache(batch_size_bytes).await;
             }
         }
         let messages_count = messages.len() as u64;
-        topic
-            .append_messages(batch_size_bytes, partitioning, messages, confirmation)
-            .await?;
+        let transaction_id = self
+            .client_manager
+            .read()
+            .await
+            .get_transaction_id(session.client_id)
+            .await;
+        let appended_at = Instant::now();
+        let mut acknowledgement = None;
+        let partition_id = if let Some(producer_sequence) = producer_sequence {
+            topic
+                .append_idempotent_messages(
+                    producer_sequence,
+                    transaction_id,
+                    batch_size_bytes,
+                    partitioning,
+                    messages,
+                    confirmation,
+                )
+                .await?
+        } else if let Some(transaction_id) = transaction_id {
+            topic
+                .append_transactional_messages(
+                    transaction_id,
+                    batch_size_bytes,
+                    partitioning,
+                    messages,
+                    confirmation,
+                )
+                .await?
+        } else {
+            let (partition_id, quorum_acknowledgement) = topic
+                .append_unacknowledged_messages(
+                    batch_size_bytes,
+                    partitioning,
+                    messages,
+                    confirmation,
+                )
+                .await?;
+            acknowledgement = quorum_acknowledgement;
+            partition_id
+        };
+        self.metrics.record_appended_messages(
+            topic.stream_id,
+            topic.topic_id,
+            partition_id,
+            messages_count,
+            batch_size_bytes.as_bytes_u64(),
+            appended_at.elapsed(),
+        );
+        if let Some(transaction_id) = transaction_id {
+            self.client_manager
+                .read()
+                .await
+                .add_transaction_partition(
+                    session.client_id,
+                    TransactionPartition {
+                        stream_id: topic.stream_id,
+                        topic_id: topic.topic_id,
+                        partition_id,
+                    },
+                )
+                .await
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to add partition ID: {partition_id} to transaction with ID: {transaction_id}"))?;
+        }
         self.metrics.increment_messages(messages_count);
-        Ok(())
+        Ok(acknowledgement)
     }
 
     pub async fn flush_unsaved_buffer(
@@ -205,6 +550,386 @@ impl System {
         topic.flush_unsaved_buffer(partition_id, fsync).await?;
         Ok(())
     }
+
+    /// Registers the failed delivery of the message and returns it, along with the headers describing its origin,
+    /// once it has failed too many times and must be moved to the dead letter topic of its stream.
+    async fn get_dead_letter_message(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<Option<DeadLetterMessage>, IggyError> {
+        self.ensure_authenticated(session)?;
+        if !self.config.dead_letter.enabled {
+            return Err(IggyError::FeatureUnavailable);
+        }
+
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        // Reuse those permissions as if you can poll the messages you can nack them
+        self.permissioner
+            .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+            .with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to nack message for user {} on stream ID: {}, topic ID: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+        if self.config.replication.is_follower(&self.cluster_identity)
+            && topic.replication_factor > 1
+        {
+            return Err(IggyError::NotReplicationLeader);
+        }
+        // Moving the message from the dead letter topic would create yet another one, e.g. "orders-dlq-dlq".
+        if topic.dead_letter {
+            return Err(IggyError::CannotNackDeadLetterMessage(
+                topic.topic_id,
+                topic.stream_id,
+            ));
+        }
+
+        let source_stream_id = topic.stream_id;
+        let source_topic_id = topic.topic_id;
+        let partition = topic.get_partition(partition_id)?;
+        let message = {
+            let partition = partition.read().await;
+            if !partition.should_increment_offset || offset > partition.current_offset {
+                return Err(IggyError::InvalidOffset(offset));
+            }
+
+            if partition.is_poisoned(offset) {
+                trace!("Message with offset: {offset} in partition: {partition_id}, topic: {topic_id}, stream: {stream_id} has already been moved to the dead letter topic.");
+                return Ok(None);
+            }
+
+            let failed_deliveries = partition.register_failed_delivery(offset);
+            if failed_deliveries < self.config.dead_letter.max_failed_deliveries {
+                trace!("Message with offset: {offset} in partition: {partition_id}, topic: {topic_id}, stream: {stream_id} has failed {failed_deliveries} delivery attempt(s).");
+                return Ok(None);
+            }
+
+            let message = partition
+                .get_messages_by_offset(offset, 1)
+                .await?
+                .into_iter()
+                .find(|message| message.offset == offset)
+                .ok_or(IggyError::InvalidOffset(offset))?;
+            let mut headers = message
+                .headers
+                .clone()
+                .map(HashMap::from_bytes)
+                .transpose()?
+                .unwrap_or_default();
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_STREAM_ID_HEADER)?,
+                HeaderValue::from_uint32(source_stream_id)?,
+            );
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_TOPIC_ID_HEADER)?,
+                HeaderValue::from_uint32(source_topic_id)?,
+            );
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_PARTITION_ID_HEADER)?,
+                HeaderValue::from_uint32(partition_id)?,
+            );
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_OFFSET_HEADER)?,
+                HeaderValue::from_uint64(offset)?,
+            );
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_TIMESTAMP_HEADER)?,
+                HeaderValue::from_uint64(message.timestamp)?,
+            );
+            headers.insert(
+                HeaderKey::new(DEAD_LETTER_FAILED_DELIVERIES_HEADER)?,
+                HeaderValue::from_uint32(failed_deliveries)?,
+            );
+            Message::new(Some(message.id), message.payload.clone(), Some(headers))
+        };
+
+        Ok(Some(DeadLetterMessage {
+            stream_id: Identifier::numeric(source_stream_id)?,
+            topic_name: format!("{}{}", topic.name, self.config.dead_letter.topic_suffix),
+            compression_algorithm: topic.compression_algorithm,
+            partition,
+            message,
+        }))
+    }
+
+    /// Returns the ID of the dead letter topic with the given name, unless it doesn't exist yet.
+    /// The regular topic having the same name can't be used as the dead letter one.
+    fn find_dead_letter_topic(
+        &self,
+        stream_id: &Identifier,
+        name: &str,
+    ) -> Result<Option<u32>, IggyError> {
+        let stream = self.get_stream(stream_id)?;
+        let Some(topic_id) = stream.topics_ids.get(name) else {
+            return Ok(None);
+        };
+
+        if !stream
+            .get_topic(&Identifier::numeric(*topic_id)?)?
+            .dead_letter
+        {
+            error!("Cannot use topic: {name} with ID: {topic_id} in stream ID: {stream_id} as the dead letter topic.");
+            return Err(IggyError::TopicNameAlreadyExists(
+                name.to_owned(),
+                stream.stream_id,
+            ));
+        }
+
+        Ok(Some(*topic_id))
+    }
+
+    /// Returns the command creating the dead letter topic with the given name, unless it already exists.
+    fn prepare_dead_letter_topic(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        name: &str,
+        compression_algorithm: CompressionAlgorithm,
+    ) -> Result<EntryCommand, IggyError> {
+        if self.find_dead_letter_topic(stream_id, name)?.is_some() {
+            return Ok(EntryCommand::Noop);
+        }
+
+        let mut command = CreateTopic {
+            stream_id: stream_id.clone(),
+            topic_id: None,
+            partitions_count: 1,
+            compression_algorithm,
+            message_expiry: IggyExpiry::ServerDefault,
+            max_topic_size: MaxTopicSize::ServerDefault,
+            replication_factor: None,
+            name: name.to_owned(),
+            cleanup_policy: CleanupPolicy::default(),
+        };
+        command.validate()?;
+        let topic_id = self.validate_create_topic(
+            session,
+            &command.stream_id,
+            command.topic_id,
+            &command.name,
+            command.partitions_count,
+            command.max_topic_size,
+        )?;
+        command.message_expiry = Topic::get_message_expiry(command.message_expiry, &self.config);
+        command.max_topic_size = Topic::get_max_topic_size(command.max_topic_size, &self.config)?;
+        info!(
+            "Creating dead letter topic: {name} with ID: {topic_id} in stream ID: {stream_id}..."
+        );
+        Ok(EntryCommand::CreateTopic(CreateTopicWithId {
+            topic_id,
+            command,
+            dead_letter: true,
+        }))
+    }
+}
+
+impl SharedSystem {
+    /// Registers the failed delivery of the message, and once it has failed too many times, moves it to the dead letter
+    /// topic of its stream. The dead letter topic is created on behalf of the server, thus the permission to create it isn't required.
+    pub async fn nack_message(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+    ) -> Result<(), IggyError> {
+        let (dead_letter, dead_letter_topic_exists) = {
+            let system = self.read().await;
+            let Some(dead_letter) = system
+                .get_dead_letter_message(session, stream_id, topic_id, partition_id, offset)
+                .await?
+            else {
+                return Ok(());
+            };
+            let dead_letter_topic_exists = system
+                .find_dead_letter_topic(&dead_letter.stream_id, &dead_letter.topic_name)?
+                .is_some();
+            (dead_letter, dead_letter_topic_exists)
+        };
+
+        let dead_letter_topic_name = &dead_letter.topic_name;
+        let source_stream_id = &dead_letter.stream_id;
+        if !dead_letter_topic_exists {
+            let root_session = Session::stateless(
+                DEFAULT_ROOT_USER_ID,
+                SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+            );
+            self.apply_entry(&root_session, |system| {
+                // The concurrent nack might have already created the topic.
+                let command = system.prepare_dead_letter_topic(
+                    &root_session,
+                    source_stream_id,
+                    dead_letter_topic_name,
+                    dead_letter.compression_algorithm,
+                )?;
+                Ok((command, ()))
+            })
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to create dead letter topic: {dead_letter_topic_name} in stream ID: {source_stream_id}"))?;
+        }
+
+        let system = self.read().await;
+        let dead_letter_topic_id = system
+            .find_dead_letter_topic(source_stream_id, dead_letter_topic_name)?
+            .ok_or_else(|| {
+                IggyError::TopicNameNotFound(
+                    dead_letter_topic_name.to_owned(),
+                    source_stream_id.to_string(),
+                )
+            })?;
+        let mut partition = dead_letter.partition.write().await;
+        // The concurrent nack might have already moved the message.
+        if partition.is_poisoned(offset) {
+            return Ok(());
+        }
+
+        // The payload is moved as it is stored, so it's already encrypted if the encryption is enabled.
+        let message = dead_letter.message;
+        let batch_size_bytes = message.get_size_bytes();
+        if let Some(memory_tracker) = CacheMemoryTracker::get_instance() {
+            if !memory_tracker.will_fit_into_cache(batch_size_bytes) {
+                system.clean_cache(batch_size_bytes).await;
+            }
+        }
+        system
+            .get_stream(source_stream_id)?
+            .get_topic(&Identifier::numeric(dead_letter_topic_id)?)?
+            .append_poisoned_messages(batch_size_bytes, vec![message])
+            .await
+            .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to move message with offset: {offset} to dead letter topic: {dead_letter_topic_name} in stream ID: {source_stream_id}"))?;
+        system.metrics.increment_messages(1);
+
+        partition.mark_poisoned(offset).await.with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to mark message with offset: {offset} as poisoned in partition: {partition_id}, topic: {topic_id}, stream: {stream_id}"))?;
+        partition.remove_failed_deliveries(offset);
+        info!("Moved message with offset: {offset} from partition: {partition_id}, topic: {topic_id}, stream: {stream_id} to dead letter topic: {dead_letter_topic_name}.");
+        Ok(())
+    }
+
+    /// Polls the messages, and if there are not enough of them yet, waits until they're appended to the partition
+    /// or the maximum wait time expires. The lock of the system is released while waiting, so the appends are not blocked.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn poll_messages_with_wait(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        args: PollingArgs,
+        wait: &PollingWait,
+    ) -> Result<PolledMessages, IggyError> {
+        self.poll_messages_with_wait_and_last_offset(
+            session,
+            consumer,
+            stream_id,
+            topic_id,
+            partition_id,
+            args,
+            wait,
+        )
+        .await
+        .map(|(polled_messages, _)| polled_messages)
+    }
+
+    /// Polls the messages the same way as `poll_messages_with_wait`, along with the offset of the last scanned one.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn poll_messages_with_wait_and_last_offset(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+        args: PollingArgs,
+        wait: &PollingWait,
+    ) -> Result<(PolledMessages, Option<u64>), IggyError> {
+        if !wait.is_enabled() {
+            return self
+                .read()
+                .await
+                .poll_messages_with_last_offset(
+                    session,
+                    consumer,
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    args,
+                )
+                .await;
+        }
+
+        let deadline = Instant::now() + wait.max_wait.get_duration();
+        // The partition is resolved only once, so the consumer group member keeps waiting on the same one.
+        let resolved_partition = self
+            .read()
+            .await
+            .resolve_partition_to_wait_on(session, consumer, stream_id, topic_id, partition_id)
+            .await?;
+        let Some((partition_id, messages_appended)) = resolved_partition else {
+            return self
+                .read()
+                .await
+                .poll_messages_with_last_offset(
+                    session,
+                    consumer,
+                    stream_id,
+                    topic_id,
+                    partition_id,
+                    args,
+                )
+                .await;
+        };
+
+        loop {
+            // The notification is registered before checking the messages, so the ones appended in the meantime are not missed.
+            let appended = messages_appended.notified();
+            {
+                let system = self.read().await;
+                if Instant::now() >= deadline
+                    || system
+                        .has_messages_to_poll(
+                            session,
+                            consumer,
+                            stream_id,
+                            topic_id,
+                            partition_id,
+                            &args,
+                            wait,
+                        )
+                        .await?
+                {
+                    return system
+                        .poll_messages_with_last_offset(
+                            session,
+                            consumer,
+                            stream_id,
+                            topic_id,
+                            Some(partition_id),
+                            args,
+                        )
+                        .await;
+                }
+            }
+
+            // Once the time is up, the messages available so far are polled in the next iteration.
+            let _ = timeout_at(deadline, appended).await;
+        }
+    }
+}
+
+/// The message which failed too many times, along with the partition it must be marked as poisoned in.
+struct DeadLetterMessage {
+    stream_id: Identifier,
+    topic_name: String,
+    compression_algorithm: CompressionAlgorithm,
+    partition: IggySharedMut<Partition>,
+    message: Message,
 }
 
 #[derive(Debug)]
@@ -212,14 +937,34 @@ pub struct PollingArgs {
     pub strategy: PollingStrategy,
     pub count: u32,
     pub auto_commit: bool,
+    pub isolation_level: IsolationLevel,
+    pub filter: Option<HeaderFilter>,
 }
 
 impl PollingArgs {
-    pub fn new(strategy: PollingStrategy, count: u32, auto_commit: bool) -> Self {
+    pub fn new(
+        strategy: PollingStrategy,
+        count: u32,
+        auto_commit: bool,
+        isolation_level: IsolationLevel,
+        filter: Option<HeaderFilter>,
+    ) -> Self {
         Self {
             strategy,
             count,
             auto_commit,
+            isolation_level,
+            filter,
         }
     }
+
+    /// Returns whether the offset of the last scanned message should be stored after polling the messages.
+    /// Besides the auto commit, it's stored when the header filter has skipped all the messages polled with the next strategy,
+    /// as the consumer committing manually has nothing to commit then, and would keep scanning the same messages.
+    fn should_store_offset(&self, polled_messages: &PolledMessages) -> bool {
+        self.auto_commit
+            || (self.filter.is_some()
+                && self.strategy.kind == PollingKind::Next
+                && polled_messages.messages.is_empty())
+    }
 }
@@ -16,13 +16,17 @@
  * under the License.
  */
 
+pub mod backup;
 pub mod clients;
+pub mod cluster;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod info;
 pub mod messages;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod producers;
+pub mod replication;
 pub mod segments;
 pub mod snapshot;
 pub mod stats;
@@ -30,6 +34,7 @@ pub mod storage;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod transactions;
 pub mod users;
 
 pub const COMPONENT: &str = "STREAMING_SYSTEMS";
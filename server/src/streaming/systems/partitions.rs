@@ -19,13 +19,17 @@
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::topics::partitions::MAX_PARTITIONS_COUNT;
+use ahash::AHashSet;
 use error_set::ErrContext;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::utils::sizeable::Sizeable;
 
 impl System {
-    pub async fn create_partitions(
-        &mut self,
+    pub fn validate_create_partitions(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
@@ -44,10 +48,25 @@ impl System {
                 topic.stream_id,
                 topic.topic_id
             ))?;
+            if topic.get_partitions_count() + partitions_count > MAX_PARTITIONS_COUNT {
+                return Err(IggyError::TooManyPartitions);
+            }
         }
 
-        let topic = self
-            .get_stream_mut(stream_id)?
+        self.get_stream(stream_id)?
+            .ensure_partitions_limit(partitions_count)
+    }
+
+    pub async fn create_partitions(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+    ) -> Result<(), IggyError> {
+        self.validate_create_partitions(session, stream_id, topic_id, partitions_count)?;
+        let stream = self.get_stream_mut(stream_id)?;
+        let topic = stream
             .get_topic_mut(topic_id)
             .with_error_context(|error| {
                 format!(
@@ -66,17 +85,15 @@ impl System {
         Ok(())
     }
 
-    pub async fn delete_partitions(
-        &mut self,
+    pub fn validate_delete_partitions(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
-        partitions_count: u32,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
-            self.permissioner.delete_partitions(
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner.delete_partitions(
                 session.get_user_id(),
                 topic.stream_id,
                 topic.topic_id,
@@ -85,9 +102,17 @@ impl System {
                 session.get_user_id(),
                 topic.stream_id,
                 topic.topic_id
-            ))?;
-        }
+            ))
+    }
 
+    pub async fn delete_partitions(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partitions_count: u32,
+    ) -> Result<(), IggyError> {
+        self.validate_delete_partitions(session, stream_id, topic_id)?;
         let topic = self
             .get_stream_mut(stream_id)?
             .get_topic_mut(topic_id)
@@ -110,4 +135,37 @@ impl System {
         }
         Ok(())
     }
+
+    /// Refreshes the state metrics of all the partitions and removes the ones of the deleted partitions.
+    pub async fn update_partition_metrics(&self) {
+        let mut existing_partitions = AHashSet::new();
+        for stream in self.streams.values() {
+            for topic in stream.get_topics() {
+                for partition in topic.get_partitions() {
+                    let partition = partition.read().await;
+                    let unsaved_size_bytes = partition
+                        .get_segments()
+                        .iter()
+                        .filter_map(|segment| segment.unsaved_messages.as_ref())
+                        .map(|batch| batch.get_size_bytes().as_bytes_u64())
+                        .sum();
+                    self.metrics.set_partition_state(
+                        stream.stream_id,
+                        topic.topic_id,
+                        partition.partition_id,
+                        partition.get_segments_count(),
+                        partition.unsaved_messages_count as u64,
+                        unsaved_size_bytes,
+                        partition.deduplicated_messages_count,
+                    );
+                    existing_partitions.insert((
+                        stream.stream_id,
+                        topic.topic_id,
+                        partition.partition_id,
+                    ));
+                }
+            }
+        }
+        self.metrics.retain_partitions(&existing_partitions);
+    }
 }
@@ -53,12 +53,11 @@ impl System {
         Ok(personal_access_tokens)
     }
 
-    pub async fn create_personal_access_token(
+    pub fn validate_create_personal_access_token(
         &self,
         session: &Session,
         name: &str,
-        expiry: IggyExpiry,
-    ) -> Result<String, IggyError> {
+    ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let user_id = session.get_user_id();
         let identifier = user_id.try_into()?;
@@ -94,6 +93,18 @@ impl System {
             ));
         }
 
+        Ok(())
+    }
+
+    pub async fn create_personal_access_token(
+        &self,
+        session: &Session,
+        name: &str,
+        expiry: IggyExpiry,
+    ) -> Result<String, IggyError> {
+        self.validate_create_personal_access_token(session, name)?;
+        let user_id = session.get_user_id();
+        let user = self.get_user(&user_id.try_into()?)?;
         info!("Creating personal access token: {name} for user with ID: {user_id}...");
         let (personal_access_token, token) =
             PersonalAccessToken::new(user_id, name, IggyTimestamp::now(), expiry);
@@ -103,6 +114,30 @@ impl System {
         Ok(token)
     }
 
+    pub fn validate_delete_personal_access_token(
+        &self,
+        session: &Session,
+        name: &str,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let user_id = session.get_user_id();
+        let user = self
+            .get_user(&user_id.try_into()?)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
+            })?;
+        if !user
+            .personal_access_tokens
+            .iter()
+            .any(|pat| pat.name.as_str() == name)
+        {
+            error!("Personal access token: {name} for user with ID: {user_id} does not exist.",);
+            return Err(IggyError::ResourceNotFound(name.to_owned()));
+        }
+
+        Ok(())
+    }
+
     pub async fn delete_personal_access_token(
         &mut self,
         session: &Session,
@@ -0,0 +1,103 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::batching::batch_accumulator::BatchAccumulator;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::local_sizeable::LocalSizeable;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::utils::byte_size::IggyByteSize;
+
+impl System {
+    /// Returns the batch of messages starting at the given offset to the follower replica,
+    /// and registers the acknowledgement of all the messages preceding that offset.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn fetch_replica_messages(
+        &self,
+        session: &Session,
+        replica_id: u32,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        offset: u64,
+        count: u32,
+    ) -> Result<Option<RetainedMessageBatch>, IggyError> {
+        self.ensure_authenticated(session)?;
+        if !self.config.replication.enabled {
+            return Err(IggyError::FeatureUnavailable);
+        }
+
+        if !self.config.replication.is_leader(&self.cluster_identity) {
+            return Err(IggyError::NotReplicationLeader);
+        }
+
+        // The acknowledgements count towards the quorum, so only the replication user may send them on behalf of the other nodes.
+        let user = self
+            .get_user(&Identifier::numeric(session.get_user_id())?)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - user not found for replica with ID: {replica_id}, session: {session}")
+            })?;
+        if user.username != self.cluster_identity.username
+            || replica_id == 0
+            || replica_id == self.config.replication.node_id
+        {
+            return Err(IggyError::InvalidReplica(replica_id));
+        }
+
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream ID: {stream_id}, topic_id: {topic_id}"))?;
+        self.permissioner
+            .poll_messages(session.get_user_id(), topic.stream_id, topic.topic_id)
+            .with_error_context(|error| format!(
+                "{COMPONENT} (error: {error}) - permission denied to fetch replica messages for user {} on stream ID: {}, topic ID: {}",
+                session.get_user_id(),
+                topic.stream_id,
+                topic.topic_id
+            ))?;
+
+        let partition = topic.get_partition(partition_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - partition with ID: {partition_id} not found for stream ID: {stream_id}, topic_id: {topic_id}")
+        })?;
+        let partition = partition.read().await;
+        partition.replica_offsets.acknowledge(replica_id, offset);
+        let messages = partition
+            .get_messages_by_offset(offset, count)
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to get messages for replica with ID: {replica_id}, partition: {partition}, offset: {offset}")
+            })?;
+        let Some(first_message) = messages.first() else {
+            return Ok(None);
+        };
+
+        let batch_size = messages
+            .iter()
+            .map(|message| message.get_size_bytes())
+            .sum::<IggyByteSize>();
+        let mut batch_accumulator = BatchAccumulator::new(first_message.offset, messages.len());
+        batch_accumulator.append(batch_size, &messages);
+        let batch = batch_accumulator
+            .materialize_batch_and_update_state()
+            .compress(partition.compression_algorithm)?;
+        Ok(Some(batch))
+    }
+}
@@ -15,30 +15,35 @@
  * specific language governing permissions and limitations
  * under the License.
  */
+use crate::archiver::ArchiverKind;
+use crate::configs::server::DiskArchiverConfig;
+use crate::streaming::partitions::partition::Partition;
 use crate::streaming::session::Session;
-use crate::streaming::systems::system::System;
+use crate::streaming::systems::system::{SharedSystem, System};
 use crate::streaming::systems::COMPONENT;
 use error_set::ErrContext;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
-use iggy::locking::IggySharedMutFn;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::segments::restore_segments::{ArchiveSource, RestoreRange};
+use iggy::users::defaults::DEFAULT_ROOT_USER_ID;
+use std::sync::Arc;
+use tracing::error;
 
 impl System {
-    pub async fn delete_segments(
-        &mut self,
+    pub fn validate_delete_segments(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         partition_id: u32,
-        segments_count: u32,
     ) -> Result<(), IggyError> {
         // Assert authentication.
         self.ensure_authenticated(session)?;
 
-        {
-            let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
 
-            self.permissioner.delete_segments(
+        self.permissioner.delete_segments(
                 session.get_user_id(),
                 topic.stream_id,
                 topic.topic_id,
@@ -48,8 +53,19 @@ impl System {
                 topic.stream_id,
                 topic.topic_id
             ))?;
-        }
+        topic.get_partition(partition_id)?;
+        Ok(())
+    }
 
+    pub async fn delete_segments(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        segments_count: u32,
+    ) -> Result<(), IggyError> {
+        self.validate_delete_segments(session, stream_id, topic_id, partition_id)?;
         let topic = self
             .get_stream_mut(stream_id)?
             .get_topic_mut(topic_id)
@@ -106,4 +122,124 @@ impl System {
         self.metrics.decrement_messages(deleted_messages_count);
         Ok(())
     }
+
+    /// Validates the restore of the archived segments and returns the archiver to fetch them from, along with the target partition.
+    pub fn prepare_restore_segments(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+    ) -> Result<(Arc<ArchiverKind>, IggySharedMut<Partition>), IggyError> {
+        // Assert authentication.
+        self.ensure_authenticated(session)?;
+
+        let topic = self.find_topic(session, stream_id, topic_id).with_error_context(|error| format!("{COMPONENT} (error: {error}) - topic not found for stream_id: {stream_id}, topic_id: {topic_id}"))?;
+
+        self.permissioner.restore_segments(
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id,
+        ).with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - permission denied to restore segments for user {} on Stream ID: {}, Topic ID: {}",
+            session.get_user_id(),
+            topic.stream_id,
+            topic.topic_id
+        ))?;
+
+        // The restored messages are readable in the target partition, so they must be readable in the source one too.
+        self.permissioner.poll_messages(
+            session.get_user_id(),
+            source.stream_id,
+            source.topic_id,
+        ).with_error_context(|error| format!(
+            "{COMPONENT} (error: {error}) - permission denied to read archived segments for user {} on Stream ID: {}, Topic ID: {}",
+            session.get_user_id(),
+            source.stream_id,
+            source.topic_id
+        ))?;
+
+        // The archive written by the disk archiver can be restored from any directory on the server, which only the root user is allowed to read,
+        // otherwise the configured archiver is used.
+        let archiver = match &source.archive_path {
+            Some(path) => {
+                if session.get_user_id() != DEFAULT_ROOT_USER_ID {
+                    error!(
+                        "{COMPONENT} - only the root user can restore segments from the archive path: {path}, user ID: {}",
+                        session.get_user_id()
+                    );
+                    return Err(IggyError::Unauthorized);
+                }
+
+                Arc::new(ArchiverKind::get_disk_archiver(DiskArchiverConfig {
+                    path: path.clone(),
+                }))
+            }
+            None => self.archiver.clone().ok_or(IggyError::ArchiverNotEnabled)?,
+        };
+
+        let partition = topic.get_partition(partition_id)?;
+        Ok((archiver, partition))
+    }
+}
+
+impl SharedSystem {
+    /// Restores the archived segments into the partition. Neither the system nor the partition is locked
+    /// while the segments are fetched and validated, so the restore doesn't block the other operations.
+    pub async fn restore_segments(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: u32,
+        source: &ArchiveSource,
+        range: &RestoreRange,
+    ) -> Result<(), IggyError> {
+        let (archiver, partition_lock) = self.read().await.prepare_restore_segments(
+            session,
+            stream_id,
+            topic_id,
+            partition_id,
+            source,
+        )?;
+        let target = partition_lock.read().await.get_restore_target();
+        let result = match target
+            .stage_archived_segments(&archiver, source, range)
+            .await
+        {
+            Ok(staged_segments) => {
+                let mut partition = partition_lock.write().await;
+                let segments_count = partition.get_segments_count();
+                partition
+                    .add_restored_segments(&target, staged_segments)
+                    .await
+                    .map(|restored_messages_count| {
+                        (
+                            segments_count,
+                            partition.get_segments_count(),
+                            restored_messages_count,
+                        )
+                    })
+            }
+            Err(error) => Err(error),
+        };
+        target.delete_staging_directory().await;
+        let (segments_count, restored_segments_count, restored_messages_count) = result
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to restore segments for partition with ID: {partition_id}, topic with ID: {topic_id}, stream with ID: {stream_id}"
+                )
+            })?;
+
+        let system = self.read().await;
+        system
+            .metrics
+            .increment_segments(restored_segments_count.saturating_sub(segments_count));
+        system
+            .metrics
+            .decrement_segments(segments_count.saturating_sub(restored_segments_count));
+        system.metrics.increment_messages(restored_messages_count);
+        Ok(())
+    }
 }
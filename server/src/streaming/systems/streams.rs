@@ -27,6 +27,7 @@ use futures::future::try_join_all;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
+use iggy::models::stream_limits::StreamLimits;
 use std::cell::RefCell;
 use std::sync::atomic::{AtomicU32, Ordering};
 use tokio::fs;
@@ -74,6 +75,7 @@ impl System {
                 self.storage.clone(),
             );
             stream.created_at = stream_state.created_at;
+            stream.limits = stream_state.limits;
             unloaded_streams.push(stream);
         }
 
@@ -98,12 +100,13 @@ impl System {
                 for stream_id in missing_ids.iter() {
                     let stream_id = *stream_id;
                     let stream_state = streams.iter().find(|s| s.id == stream_id).unwrap();
-                    let stream = Stream::create(
+                    let mut stream = Stream::create(
                         stream_id,
                         &stream_state.name,
                         self.config.clone(),
                         self.storage.clone(),
                     );
+                    stream.limits = stream_state.limits;
                     stream.persist().await?;
                     unloaded_streams.push(stream);
                     info!(
@@ -289,12 +292,13 @@ impl System {
         Ok(stream.unwrap())
     }
 
-    pub async fn create_stream(
-        &mut self,
+    /// Returns the ID of the stream to be created, which is either the given one or the next available one.
+    pub fn validate_create_stream(
+        &self,
         session: &Session,
         stream_id: Option<u32>,
         name: &str,
-    ) -> Result<&Stream, IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
         self.permissioner.create_stream(session.get_user_id())?;
         if self.streams_ids.contains_key(name) {
@@ -322,7 +326,19 @@ impl System {
             return Err(IggyError::StreamIdAlreadyExists(id));
         }
 
-        let stream = Stream::create(id, name, self.config.clone(), self.storage.clone());
+        Ok(id)
+    }
+
+    pub async fn create_stream(
+        &mut self,
+        session: &Session,
+        stream_id: Option<u32>,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<&Stream, IggyError> {
+        let id = self.validate_create_stream(session, stream_id, name)?;
+        let mut stream = Stream::create(id, name, self.config.clone(), self.storage.clone());
+        stream.limits = limits.unwrap_or_default();
         stream.persist().await?;
         info!("Created stream with ID: {id}, name: '{name}'.");
         self.streams_ids.insert(name.to_owned(), stream.stream_id);
@@ -331,12 +347,13 @@ impl System {
         self.get_stream_by_id(id)
     }
 
-    pub async fn update_stream(
-        &mut self,
+    /// Returns the numeric ID of the stream to be updated.
+    pub fn validate_update_stream(
+        &self,
         session: &Session,
         id: &Identifier,
         name: &str,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
         let stream_id;
         {
@@ -364,6 +381,17 @@ impl System {
             }
         }
 
+        Ok(stream_id)
+    }
+
+    pub async fn update_stream(
+        &mut self,
+        session: &Session,
+        id: &Identifier,
+        name: &str,
+        limits: Option<StreamLimits>,
+    ) -> Result<(), IggyError> {
+        let stream_id = self.validate_update_stream(session, id, name)?;
         let old_name;
         {
             let stream = self.get_stream_mut(id).with_error_context(|error| {
@@ -371,6 +399,9 @@ impl System {
             })?;
             old_name = stream.name.clone();
             stream.name = name.to_owned();
+            if let Some(limits) = limits {
+                stream.limits = limits;
+            }
             stream.persist().await?;
         }
 
@@ -380,11 +411,26 @@ impl System {
         }
 
         info!("Stream with ID '{id}' updated. Old name: '{old_name}' changed to: '{name}'.");
+        if let Some(limits) = limits {
+            info!("Stream with ID '{id}' limits updated to: {limits}.");
+        }
         Ok(())
     }
 
-    pub async fn delete_stream(
-        &mut self,
+    /// Rejects the messages appended to a full stream, unless the oldest segments are deleted
+    /// by the background job to make room for the new ones.
+    pub(crate) fn ensure_stream_is_not_full(&self, stream_id: u32) -> Result<(), IggyError> {
+        let stream = self.get_stream_by_id(stream_id)?;
+        if stream.is_full() && !self.config.stream.delete_oldest_segments {
+            return Err(IggyError::StreamFull(stream_id));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the numeric ID of the stream to be deleted.
+    pub fn validate_delete_stream(
+        &self,
         session: &Session,
         id: &Identifier,
     ) -> Result<u32, IggyError> {
@@ -402,6 +448,16 @@ impl System {
                     stream.stream_id,
                 )
             })?;
+        Ok(stream_id)
+    }
+
+    pub async fn delete_stream(
+        &mut self,
+        session: &Session,
+        id: &Identifier,
+    ) -> Result<u32, IggyError> {
+        let stream_id = self.validate_delete_stream(session, id)?;
+        let stream = self.get_stream_by_id(stream_id)?;
         let stream_name = stream.name.clone();
         if stream.delete().await.is_err() {
             return Err(IggyError::CannotDeleteStream(stream_id));
@@ -427,11 +483,12 @@ impl System {
         Ok(stream_id)
     }
 
-    pub async fn purge_stream(
+    pub fn validate_purge_stream(
         &self,
         session: &Session,
         stream_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<&Stream, IggyError> {
+        self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
         })?;
@@ -444,6 +501,15 @@ impl System {
                     stream.stream_id,
                 )
             })?;
+        Ok(stream)
+    }
+
+    pub async fn purge_stream(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        let stream = self.validate_purge_stream(session, stream_id)?;
         stream.purge().await
     }
 }
@@ -496,7 +562,7 @@ mod tests {
             .permissioner
             .init_permissions_for_user(root.id, permissions);
         system
-            .create_stream(&session, Some(stream_id), stream_name)
+            .create_stream(&session, Some(stream_id), stream_name, None)
             .await
             .unwrap();
 
@@ -17,10 +17,13 @@
  */
 
 use crate::archiver::{ArchiverKind, ArchiverKindType};
+use crate::configs::cluster::{ClusterConfig, ClusterIdentityConfig};
+use crate::configs::oidc::OidcConfig;
 use crate::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
 use crate::configs::system::SystemConfig;
 use crate::map_toggle_str;
 use crate::state::file::FileState;
+use crate::state::raft::RaftState;
 use crate::state::system::SystemState;
 use crate::state::StateKind;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
@@ -31,7 +34,9 @@ use crate::streaming::session::Session;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::users::oidc::OidcValidator;
 use crate::streaming::users::permissioner::Permissioner;
+use crate::streaming::users::throttler::Throttler;
 use crate::streaming::users::user::User;
 use crate::versioning::SemanticVersion;
 use ahash::AHashMap;
@@ -45,19 +50,22 @@ use iggy::utils::crypto::{Aes256GcmEncryptor, EncryptorKind};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::fs::{create_dir_all, remove_dir_all};
-use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::Instant;
 use tracing::{error, info, instrument, trace};
 
 #[derive(Debug)]
 pub struct SharedSystem {
     system: Arc<RwLock<System>>,
+    /// Serializes the metadata changes, see `apply_entry`.
+    pub(crate) metadata_lock: Arc<Mutex<()>>,
 }
 
 impl SharedSystem {
     pub fn new(system: System) -> SharedSystem {
         SharedSystem {
             system: Arc::new(RwLock::new(system)),
+            metadata_lock: Arc::new(Mutex::new(())),
         }
     }
 
@@ -74,6 +82,7 @@ impl Clone for SharedSystem {
     fn clone(&self) -> Self {
         SharedSystem {
             system: self.system.clone(),
+            metadata_lock: self.metadata_lock.clone(),
         }
     }
 }
@@ -81,6 +90,7 @@ impl Clone for SharedSystem {
 #[derive(Debug)]
 pub struct System {
     pub permissioner: Permissioner,
+    pub(crate) throttler: Throttler,
     pub(crate) storage: Arc<SystemStorage>,
     pub(crate) streams: AHashMap<u32, Stream>,
     pub(crate) streams_ids: AHashMap<String, u32>,
@@ -92,6 +102,8 @@ pub struct System {
     pub(crate) state: Arc<StateKind>,
     pub(crate) archiver: Option<Arc<ArchiverKind>>,
     pub personal_access_token: PersonalAccessTokenConfig,
+    pub(crate) oidc_validator: Option<Arc<OidcValidator>>,
+    pub(crate) cluster_identity: ClusterIdentityConfig,
 }
 
 /// For each cache eviction, we want to remove more than the size we need.
@@ -103,6 +115,7 @@ impl System {
         config: Arc<SystemConfig>,
         data_maintenance_config: DataMaintenanceConfig,
         pat_config: PersonalAccessTokenConfig,
+        cluster_config: ClusterConfig,
     ) -> System {
         let version = SemanticVersion::current().expect("Invalid version");
         info!(
@@ -120,20 +133,38 @@ impl System {
         let state_persister = Self::resolve_persister(config.state.enforce_fsync);
         let partition_persister = Self::resolve_persister(config.partition.enforce_fsync);
 
-        let state = Arc::new(StateKind::File(FileState::new(
+        let log = FileState::new(
             &config.get_state_log_path(),
             &version,
-            state_persister,
+            state_persister.clone(),
             encryptor.clone(),
-        )));
-        Self::create(
+        );
+        let state = if cluster_config.enabled {
+            info!(
+                "Cluster is enabled, node ID: {}, nodes: {}.",
+                cluster_config.id,
+                cluster_config.nodes.len()
+            );
+            StateKind::Raft(Box::new(RaftState::new(
+                &cluster_config,
+                log,
+                &config.get_state_cluster_path(),
+                state_persister,
+            )))
+        } else {
+            StateKind::File(log)
+        };
+        let state = Arc::new(state);
+        let mut system = Self::create(
             config.clone(),
             SystemStorage::new(config, partition_persister),
             state,
             encryptor,
             data_maintenance_config,
             pat_config,
-        )
+        );
+        system.cluster_identity = cluster_config.identity;
+        system
     }
 
     fn resolve_persister(enforce_fsync: bool) -> Arc<PersisterKind> {
@@ -145,7 +176,7 @@ impl System {
 
     pub fn create(
         system_config: Arc<SystemConfig>,
-        storage: SystemStorage,
+        mut storage: SystemStorage,
         state: Arc<StateKind>,
         encryptor: Option<Arc<EncryptorKind>>,
         data_maintenance_config: DataMaintenanceConfig,
@@ -175,8 +206,10 @@ impl System {
             info!("Archiving is disabled.");
             None
         };
+        storage.archiver = archiver.clone();
 
         System {
+            throttler: Throttler::new(system_config.quotas.client_requests_per_second),
             config: system_config,
             streams: AHashMap::new(),
             streams_ids: AHashMap::new(),
@@ -189,9 +222,26 @@ impl System {
             state,
             personal_access_token: pat_config,
             archiver,
+            oidc_validator: None,
+            cluster_identity: ClusterIdentityConfig::default(),
         }
     }
 
+    /// Limits the number of partitions exporting their own labeled metrics, 0 means no limit.
+    pub fn limit_labeled_partitions(&mut self, max_labeled_partitions: u32) {
+        self.metrics
+            .limit_labeled_partitions(max_labeled_partitions);
+    }
+
+    /// Enables the login with the tokens issued by the configured OIDC identity providers.
+    pub fn enable_oidc(&mut self, config: OidcConfig) {
+        info!(
+            "Login with OIDC tokens is enabled for {} issuer(s).",
+            config.issuers.len()
+        );
+        self.oidc_validator = Some(Arc::new(OidcValidator::new(config)));
+    }
+
     #[instrument(skip_all, name = "trace_system_init")]
     pub async fn init(&mut self) -> Result<(), IggyError> {
         let system_path = self.config.get_system_path();
@@ -245,6 +295,9 @@ impl System {
             .with_error_context(|error| {
                 format!("{COMPONENT} (error: {error}) - failed to load streams")
             })?;
+        self.load_transactions().await.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load transactions")
+        })?;
         if let Some(archiver) = self.archiver.as_ref() {
             archiver
                 .init()
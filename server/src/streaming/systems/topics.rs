@@ -25,6 +25,7 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
 use iggy::locking::IggySharedMutFn;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 
@@ -108,6 +109,31 @@ impl System {
         Ok(Some(topic))
     }
 
+    /// Returns the ID of the topic to be created, which is either the given one or the next available one.
+    pub fn validate_create_topic(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: Option<u32>,
+        name: &str,
+        partitions_count: u32,
+        max_topic_size: MaxTopicSize,
+    ) -> Result<u32, IggyError> {
+        self.ensure_authenticated(session)?;
+        let stream = self.get_stream(stream_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
+        })?;
+        self.permissioner
+            .create_topic(session.get_user_id(), stream.stream_id)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - permission denied to create topic with name: {name} in stream with ID: {stream_id} for user with ID: {}",
+                    session.get_user_id(),
+                )
+            })?;
+        stream.validate_create_topic(topic_id, name, partitions_count, max_topic_size)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_topic(
         &mut self,
@@ -120,32 +146,27 @@ impl System {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<&Topic, IggyError> {
-        self.ensure_authenticated(session)?;
-        {
-            let stream = self.get_stream(stream_id).with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to get stream with ID: {stream_id}")
-            })?;
-            self.permissioner
-                .create_topic(session.get_user_id(), stream.stream_id)
-                .with_error_context(|error| {
-                    format!(
-                        "{COMPONENT} (error: {error}) - permission denied to create topic with name: {name} in stream with ID: {stream_id} for user with ID: {}",
-                        session.get_user_id(),
-                    )
-                })?;
-        }
-
+        let topic_id = self.validate_create_topic(
+            session,
+            stream_id,
+            topic_id,
+            name,
+            partitions_count,
+            max_topic_size,
+        )?;
         let created_topic_id = self
             .get_stream_mut(stream_id)?
             .create_topic(
-                topic_id,
+                Some(topic_id),
                 name,
                 partitions_count,
                 message_expiry,
                 compression_algorithm,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                cleanup_policy,
             )
             .await
             .with_error_context(|error| {
@@ -168,28 +189,21 @@ impl System {
             })
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn update_topic(
-        &mut self,
+    pub fn validate_update_topic(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         name: &str,
-        message_expiry: IggyExpiry,
-        compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
-        replication_factor: Option<u8>,
-    ) -> Result<&Topic, IggyError> {
+    ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let topic = self
-                .find_topic(session, stream_id, topic_id)
-                .with_error_context(|error| {
-                    format!(
-                        "{COMPONENT} (error: {error}) - failed to find topic with ID: {topic_id}"
-                    )
-                })?;
-            self.permissioner.update_topic(
+        let topic = self
+            .find_topic(session, stream_id, topic_id)
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to find topic with ID: {topic_id}")
+            })?;
+        self.permissioner.update_topic(
                 session.get_user_id(),
                 topic.stream_id,
                 topic.topic_id,
@@ -201,8 +215,24 @@ impl System {
                     topic.topic_id,
                 )
             })?;
-        }
+        self.get_stream(stream_id)?
+            .validate_update_topic(topic.topic_id, name, max_topic_size)
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_topic(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        name: &str,
+        message_expiry: IggyExpiry,
+        compression_algorithm: CompressionAlgorithm,
+        max_topic_size: MaxTopicSize,
+        replication_factor: Option<u8>,
+        cleanup_policy: CleanupPolicy,
+    ) -> Result<&Topic, IggyError> {
+        self.validate_update_topic(session, stream_id, topic_id, name, max_topic_size)?;
         self.get_stream_mut(stream_id)?
             .update_topic(
                 topic_id,
@@ -211,6 +241,7 @@ impl System {
                 compression_algorithm,
                 max_topic_size,
                 replication_factor.unwrap_or(1),
+                cleanup_policy,
             )
             .await
             .with_error_context(|error| {
@@ -232,21 +263,20 @@ impl System {
             })
     }
 
-    pub async fn delete_topic(
-        &mut self,
+    /// Returns the numeric ID of the stream containing the topic to be deleted.
+    pub fn validate_delete_topic(
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        let stream_id_value;
-        {
-            let topic = self
+        let topic = self
                 .find_topic(session, stream_id, topic_id)
                 .with_error_context(|error| {
                     format!("{COMPONENT} (error: {error}) - failed to find topic with ID: {topic_id} in stream with ID: {stream_id}")
                 })?;
-            self.permissioner.delete_topic(
+        self.permissioner.delete_topic(
                 session.get_user_id(),
                 topic.stream_id,
                 topic.topic_id,
@@ -256,9 +286,16 @@ impl System {
                     session.get_user_id(),
                 )
             })?;
-            stream_id_value = topic.stream_id;
-        }
+        Ok(topic.stream_id)
+    }
 
+    pub async fn delete_topic(
+        &mut self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        let stream_id_value = self.validate_delete_topic(session, stream_id, topic_id)?;
         let topic = self
             .get_stream_mut(stream_id)?
             .delete_topic(topic_id)
@@ -278,12 +315,13 @@ impl System {
         Ok(())
     }
 
-    pub async fn purge_topic(
+    pub fn validate_purge_topic(
         &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
-    ) -> Result<(), IggyError> {
+    ) -> Result<&Topic, IggyError> {
+        self.ensure_authenticated(session)?;
         let topic = self
             .find_topic(session, stream_id, topic_id)
             .with_error_context(|error| {
@@ -297,6 +335,16 @@ impl System {
                     session.get_user_id(),
                 )
             })?;
+        Ok(topic)
+    }
+
+    pub async fn purge_topic(
+        &self,
+        session: &Session,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        let topic = self.validate_purge_topic(session, stream_id, topic_id)?;
         topic.purge().await.with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to purge topic with ID: {topic_id} in stream with ID: {stream_id}")
         })
@@ -0,0 +1,217 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::clients::client_manager::Transaction;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::systems::COMPONENT;
+use ahash::AHashSet;
+use error_set::ErrContext;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use std::path::Path;
+use tokio::fs;
+use tracing::{info, warn};
+
+impl System {
+    pub async fn begin_transaction(&self, session: &Session) -> Result<u64, IggyError> {
+        self.ensure_authenticated(session)?;
+        let transaction_id = self
+            .client_manager
+            .write()
+            .await
+            .begin_transaction(session.client_id)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to begin transaction for client with ID: {}",
+                    session.client_id
+                )
+            })?;
+        info!(
+            "Began transaction with ID: {transaction_id} for client with ID: {}.",
+            session.client_id
+        );
+        Ok(transaction_id)
+    }
+
+    pub async fn commit_transaction(
+        &self,
+        session: &Session,
+        transaction_id: u64,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let transaction = self
+            .client_manager
+            .read()
+            .await
+            .complete_transaction(session.client_id, transaction_id)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to commit transaction with ID: {transaction_id} for client with ID: {}",
+                    session.client_id
+                )
+            })?;
+
+        // Once the transaction ID is stored, the transaction is committed, even if the server stops before marking all of its partitions.
+        let path = self.config.get_state_transactions_path();
+        let bytes = transaction_id.to_le_bytes();
+        let result = if Path::new(&path).exists() {
+            self.storage.persister.append(&path, &bytes).await
+        } else {
+            self.storage.persister.overwrite(&path, &bytes).await
+        };
+        result.with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to store committed transaction with ID: {transaction_id}, path: {path}")
+        })?;
+
+        self.complete_transaction_partitions(&transaction, true)
+            .await?;
+        info!(
+            "Committed transaction with ID: {transaction_id} for client with ID: {}.",
+            session.client_id
+        );
+        Ok(())
+    }
+
+    pub async fn abort_transaction(
+        &self,
+        session: &Session,
+        transaction_id: u64,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let transaction = self
+            .client_manager
+            .read()
+            .await
+            .complete_transaction(session.client_id, transaction_id)
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to abort transaction with ID: {transaction_id} for client with ID: {}",
+                    session.client_id
+                )
+            })?;
+        self.complete_transaction_partitions(&transaction, false)
+            .await?;
+        info!(
+            "Aborted transaction with ID: {transaction_id} for client with ID: {}.",
+            session.client_id
+        );
+        Ok(())
+    }
+
+    /// Aborts the transaction which has been left open by the disconnected client.
+    pub(crate) async fn abort_client_transaction(&self, client_id: u32, transaction: Transaction) {
+        let transaction_id = transaction.transaction_id;
+        if let Err(error) = self
+            .complete_transaction_partitions(&transaction, false)
+            .await
+        {
+            warn!("Failed to abort transaction with ID: {transaction_id} for client with ID: {client_id}. Error: {error}");
+            return;
+        }
+
+        info!("Aborted transaction with ID: {transaction_id} for disconnected client with ID: {client_id}.");
+    }
+
+    async fn complete_transaction_partitions(
+        &self,
+        transaction: &Transaction,
+        commit: bool,
+    ) -> Result<(), IggyError> {
+        let transaction_id = transaction.transaction_id;
+        for transaction_partition in &transaction.partitions {
+            // The stream, topic or partition might have been deleted in the meantime, along with the messages.
+            let Ok(stream) =
+                self.get_stream(&Identifier::numeric(transaction_partition.stream_id)?)
+            else {
+                continue;
+            };
+            let Ok(topic) = stream.get_topic(&Identifier::numeric(transaction_partition.topic_id)?)
+            else {
+                continue;
+            };
+            let Ok(partition) = topic.get_partition(transaction_partition.partition_id) else {
+                continue;
+            };
+
+            let mut partition = partition.write().await;
+            if commit {
+                partition.commit_transaction(transaction_id).await
+            } else {
+                partition.abort_transaction(transaction_id).await
+            }
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to complete transaction with ID: {transaction_id} for partition with ID: {}, topic ID: {}, stream ID: {}",
+                    transaction_partition.partition_id,
+                    transaction_partition.topic_id,
+                    transaction_partition.stream_id
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Restores the aborted transactions in all the partitions, and removes the committed transactions which are no longer needed.
+    pub(crate) async fn load_transactions(&self) -> Result<(), IggyError> {
+        let path = self.config.get_state_transactions_path();
+        let mut committed_transactions = AHashSet::new();
+        if Path::new(&path).exists() {
+            let bytes = fs::read(&path)
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to read committed transactions, path: {path}")
+                })
+                .map_err(|_| IggyError::CannotReadFile)?;
+            for chunk in bytes.chunks_exact(8) {
+                let transaction_id = u64::from_le_bytes(
+                    chunk
+                        .try_into()
+                        .map_err(|_| IggyError::InvalidNumberEncoding)?,
+                );
+                committed_transactions.insert(transaction_id);
+            }
+        }
+
+        for stream in self.streams.values() {
+            for topic in stream.get_topics() {
+                for partition in topic.get_partitions() {
+                    partition
+                        .write()
+                        .await
+                        .load_transactions(&committed_transactions)
+                        .await
+                        .with_error_context(|error| {
+                            format!("{COMPONENT} (error: {error}) - failed to load transactions for topic with ID: {}, stream ID: {}", topic.topic_id, stream.stream_id)
+                        })?;
+                }
+            }
+        }
+
+        if Path::new(&path).exists() {
+            self.storage.persister.delete(&path).await.with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to delete committed transactions, path: {path}")
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -21,8 +21,9 @@ use crate::state::models::CreateUserWithId;
 use crate::state::system::UserState;
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::session::Session;
-use crate::streaming::systems::system::System;
+use crate::streaming::systems::system::{SharedSystem, System};
 use crate::streaming::systems::COMPONENT;
+use crate::streaming::users::oidc::{OidcIdentity, OidcSubject, OidcValidator};
 use crate::streaming::users::user::User;
 use crate::streaming::utils::crypto;
 use crate::{IGGY_ROOT_PASSWORD_ENV, IGGY_ROOT_USERNAME_ENV};
@@ -31,10 +32,15 @@ use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
 use iggy::models::permissions::Permissions;
+use iggy::models::user_quotas::UserQuotas;
 use iggy::models::user_status::UserStatus;
 use iggy::users::create_user::CreateUser;
 use iggy::users::defaults::*;
+use iggy::users::update_permissions::UpdatePermissions;
+use iggy::utils::text::as_base64;
+use ring::rand::SecureRandom;
 use std::env;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -57,7 +63,8 @@ impl System {
             self.state
                 .apply(0, EntryCommand::CreateUser(CreateUserWithId {
                     user_id: root.id,
-                    command
+                    command,
+                    oidc_subject: None,
                 }))
                 .await
                 .with_error_context(|error| {
@@ -81,6 +88,8 @@ impl System {
             );
 
             user.created_at = user_state.created_at;
+            user.quotas = user_state.quotas;
+            user.oidc_subject = user_state.oidc_subject;
             user.personal_access_tokens = user_state
                 .personal_access_tokens
                 .into_values()
@@ -104,6 +113,8 @@ impl System {
         USER_ID.store(current_user_id + 1, Ordering::SeqCst);
         self.permissioner
             .init(&self.users.values().collect::<Vec<&User>>());
+        self.throttler
+            .init(&self.users.values().collect::<Vec<&User>>());
         self.metrics.increment_users(users_count as u32);
         info!("Initialized {users_count} user(s).");
         Ok(())
@@ -213,14 +224,12 @@ impl System {
         Ok(self.users.values().collect())
     }
 
-    pub async fn create_user(
-        &mut self,
+    /// Returns the ID of the user to be created.
+    pub fn validate_create_user(
+        &self,
         session: &Session,
         username: &str,
-        password: &str,
-        status: UserStatus,
-        permissions: Option<Permissions>,
-    ) -> Result<&User, IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
         self.permissioner
             .create_user(session.get_user_id())
@@ -241,7 +250,18 @@ impl System {
             return Err(IggyError::UsersLimitReached);
         }
 
-        let user_id = USER_ID.fetch_add(1, Ordering::SeqCst);
+        Ok(USER_ID.fetch_add(1, Ordering::SeqCst))
+    }
+
+    pub async fn create_user(
+        &mut self,
+        session: &Session,
+        username: &str,
+        password: &str,
+        status: UserStatus,
+        permissions: Option<Permissions>,
+    ) -> Result<&User, IggyError> {
+        let user_id = self.validate_create_user(session, username)?;
         info!("Creating user: {username} with ID: {user_id}...");
         let user = User::new(user_id, username, password, status, permissions.clone());
         self.permissioner
@@ -255,16 +275,48 @@ impl System {
             })
     }
 
-    pub async fn delete_user(
+    /// Creates the user with the given ID and the already hashed password, e.g. replicated from the cluster leader.
+    pub(crate) fn create_user_with_password_hash(
         &mut self,
+        user_id: u32,
+        username: &str,
+        password_hash: String,
+        status: UserStatus,
+        permissions: Option<Permissions>,
+        oidc_subject: Option<OidcSubject>,
+    ) -> Result<(), IggyError> {
+        if self.users.contains_key(&user_id)
+            || self.users.values().any(|user| user.username == username)
+        {
+            error!("User: {username} with ID: {user_id} already exists.");
+            return Err(IggyError::UserAlreadyExists);
+        }
+
+        USER_ID.fetch_max(user_id + 1, Ordering::SeqCst);
+        let mut user = User::with_password(
+            user_id,
+            username,
+            password_hash,
+            status,
+            permissions.clone(),
+        );
+        user.oidc_subject = oidc_subject;
+        self.permissioner
+            .init_permissions_for_user(user_id, permissions);
+        self.users.insert(user.id, user);
+        self.metrics.increment_users(1);
+        info!("Created user: {username} with ID: {user_id}.");
+        Ok(())
+    }
+
+    /// Returns the numeric ID of the user to be deleted.
+    pub fn validate_delete_user(
+        &self,
         session: &Session,
         user_id: &Identifier,
-    ) -> Result<User, IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        let existing_user_id;
-        let existing_username;
-        {
-            self.permissioner
+        self.permissioner
                 .delete_user(session.get_user_id())
                 .with_error_context(|error| {
                     format!(
@@ -272,18 +324,25 @@ impl System {
                         session.get_user_id()
                     )
                 })?;
-            let user = self.get_user(user_id).with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
-            })?;
-            if user.is_root() {
-                error!("Cannot delete the root user.");
-                return Err(IggyError::CannotDeleteUser(user.id));
-            }
-
-            existing_user_id = user.id;
-            existing_username = user.username.clone();
+        let user = self.get_user(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
+        })?;
+        if user.is_root() {
+            error!("Cannot delete the root user.");
+            return Err(IggyError::CannotDeleteUser(user.id));
         }
 
+        Ok(user.id)
+    }
+
+    pub async fn delete_user(
+        &mut self,
+        session: &Session,
+        user_id: &Identifier,
+    ) -> Result<User, IggyError> {
+        let existing_user_id = self.validate_delete_user(session, user_id)?;
+        let existing_username = self.get_user(user_id)?.username.clone();
+
         info!("Deleting user: {existing_username} with ID: {user_id}...");
         let user = self
             .users
@@ -291,6 +350,7 @@ impl System {
             .ok_or(IggyError::ResourceNotFound(user_id.to_string()))?;
         self.permissioner
             .delete_permissions_for_user(existing_user_id);
+        self.throttler.delete_quotas_for_user(existing_user_id);
         let mut client_manager = self.client_manager.write().await;
         client_manager
             .delete_clients_for_user(existing_user_id)
@@ -305,13 +365,12 @@ impl System {
         Ok(user)
     }
 
-    pub async fn update_user(
-        &mut self,
+    pub fn validate_update_user(
+        &self,
         session: &Session,
         user_id: &Identifier,
-        username: Option<String>,
-        status: Option<UserStatus>,
-    ) -> Result<&User, IggyError> {
+        username: Option<&str>,
+    ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         self.permissioner
             .update_user(session.get_user_id())
@@ -322,15 +381,34 @@ impl System {
                 )
             })?;
 
-        if let Some(username) = username.to_owned() {
-            let user = self.get_user(user_id)?;
-            let existing_user = self.get_user(&username.to_owned().try_into()?);
+        let user = self.get_user(user_id)?;
+        if let Some(username) = username {
+            let existing_user = self.get_user(&username.try_into()?);
             if existing_user.is_ok() && existing_user.unwrap().id != user.id {
                 error!("User: {username} already exists.");
                 return Err(IggyError::UserAlreadyExists);
             }
         }
 
+        Ok(())
+    }
+
+    pub async fn update_user(
+        &mut self,
+        session: &Session,
+        user_id: &Identifier,
+        username: Option<String>,
+        status: Option<UserStatus>,
+        quotas: Option<UserQuotas>,
+    ) -> Result<&User, IggyError> {
+        self.validate_update_user(session, user_id, username.as_deref())?;
+
+        if let Some(quotas) = &quotas {
+            let id = self.get_user(user_id)?.id;
+            let quotas = (!quotas.is_unlimited()).then_some(quotas);
+            self.throttler.update_quotas_for_user(id, quotas);
+        }
+
         let user = self.get_user_mut(user_id).with_error_context(|error| {
             format!("{COMPONENT} (error: {error}) - failed to get mutable reference to the user with id: {user_id}")
         })?;
@@ -342,38 +420,49 @@ impl System {
             user.status = status;
         }
 
+        if let Some(quotas) = quotas {
+            user.quotas = (!quotas.is_unlimited()).then_some(quotas);
+        }
+
         info!("Updated user: {} with ID: {}.", user.username, user.id);
         Ok(user)
     }
 
-    pub async fn update_permissions(
-        &mut self,
+    /// Returns the numeric ID of the user whose permissions are to be updated.
+    pub fn validate_update_permissions(
+        &self,
         session: &Session,
         user_id: &Identifier,
-        permissions: Option<Permissions>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-
-        {
-            self.permissioner
+        self.permissioner
                 .update_permissions(session.get_user_id())
                 .with_error_context(|error| {
                     format!(
                         "{COMPONENT} (error: {error}) - permission denied to update permissions for user with id: {}", session.get_user_id()
                     )
                 })?;
-            let user = self.get_user(user_id).with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
-            })?;
-            if user.is_root() {
-                error!("Cannot change the root user permissions.");
-                return Err(IggyError::CannotChangePermissions(user.id));
-            }
-
-            self.permissioner
-                .update_permissions_for_user(user.id, permissions.clone());
+        let user = self.get_user(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
+        })?;
+        if user.is_root() {
+            error!("Cannot change the root user permissions.");
+            return Err(IggyError::CannotChangePermissions(user.id));
         }
 
+        Ok(user.id)
+    }
+
+    pub async fn update_permissions(
+        &mut self,
+        session: &Session,
+        user_id: &Identifier,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        let id = self.validate_update_permissions(session, user_id)?;
+        self.permissioner
+            .update_permissions_for_user(id, permissions.clone());
+
         {
             let user = self.get_user_mut(user_id).with_error_context(|error| {
                 format!(
@@ -390,28 +479,21 @@ impl System {
         Ok(())
     }
 
-    pub async fn change_password(
-        &mut self,
+    pub fn validate_change_password(
+        &self,
         session: &Session,
         user_id: &Identifier,
         current_password: &str,
-        new_password: &str,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-
-        {
-            let user = self.get_user(user_id).with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
-            })?;
-            let session_user_id = session.get_user_id();
-            if user.id != session_user_id {
-                self.permissioner.change_password(session_user_id)?;
-            }
+        let user = self.get_user(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get user with id: {user_id}")
+        })?;
+        let session_user_id = session.get_user_id();
+        if user.id != session_user_id {
+            self.permissioner.change_password(session_user_id)?;
         }
 
-        let user = self.get_user_mut(user_id).with_error_context(|error| {
-            format!("{COMPONENT} (error: {error}) - failed to get mutable reference to the user with id: {user_id}")
-        })?;
         if !crypto::verify_password(current_password, &user.password) {
             error!(
                 "Invalid current password for user: {} with ID: {user_id}.",
@@ -420,6 +502,20 @@ impl System {
             return Err(IggyError::InvalidCredentials);
         }
 
+        Ok(())
+    }
+
+    pub async fn change_password(
+        &mut self,
+        session: &Session,
+        user_id: &Identifier,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<(), IggyError> {
+        self.validate_change_password(session, user_id, current_password)?;
+        let user = self.get_user_mut(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get mutable reference to the user with id: {user_id}")
+        })?;
         user.password = crypto::hash_password(new_password);
         info!(
             "Changed password for user: {} with ID: {user_id}.",
@@ -428,6 +524,23 @@ impl System {
         Ok(())
     }
 
+    /// Sets the already hashed password of the user, e.g. replicated from the cluster leader.
+    pub(crate) fn change_password_hash(
+        &mut self,
+        user_id: &Identifier,
+        password_hash: String,
+    ) -> Result<(), IggyError> {
+        let user = self.get_user_mut(user_id).with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to get mutable reference to the user with id: {user_id}")
+        })?;
+        user.password = password_hash;
+        info!(
+            "Changed password for user: {} with ID: {user_id}.",
+            user.username
+        );
+        Ok(())
+    }
+
     pub async fn login_user(
         &self,
         username: &str,
@@ -497,6 +610,130 @@ impl System {
         Ok(user)
     }
 
+    pub async fn login_with_client_certificate(
+        &self,
+        names: &[String],
+        session: Option<&Session>,
+    ) -> Result<&User, IggyError> {
+        let user = self.get_user_by_client_certificate(names)?;
+        self.login_user_with_credentials(&user.username, None, session)
+            .await
+    }
+
+    pub fn get_user_by_client_certificate(&self, names: &[String]) -> Result<&User, IggyError> {
+        let Some(user) = names
+            .iter()
+            .find_map(|name| self.users.values().find(|user| &user.username == name))
+        else {
+            error!(
+                "Cannot authenticate client certificate with names: {names:?} (user not found)."
+            );
+            return Err(IggyError::InvalidCredentials);
+        };
+
+        if !user.is_active() {
+            warn!(
+                "User: {} with ID: {} authenticated with client certificate is inactive.",
+                user.username, user.id
+            );
+            return Err(IggyError::UserInactive);
+        }
+
+        Ok(user)
+    }
+
+    /// Returns the validator of the OIDC tokens, unless the login with OIDC tokens is disabled.
+    pub fn get_oidc_validator(&self) -> Result<Arc<OidcValidator>, IggyError> {
+        self.oidc_validator.clone().ok_or_else(|| {
+            error!("Login with OIDC tokens is disabled.");
+            IggyError::FeatureUnavailable
+        })
+    }
+
+    /// Returns the user provisioned for the OIDC subject, if any.
+    pub fn find_oidc_user(&self, subject: &OidcSubject) -> Option<&User> {
+        self.users
+            .values()
+            .find(|user| user.oidc_subject.as_ref() == Some(subject))
+    }
+
+    /// Returns the command which must be applied before the user authenticated with the OIDC token can log in, if any.
+    /// The user is created on the first login of the OIDC subject, unless its username belongs to the other user,
+    /// and the permissions granted by the matching role rules are updated on the subsequent ones, if they've changed.
+    /// The created user has the given password hash, so it can't log in with the password.
+    pub fn prepare_oidc_user(
+        &self,
+        identity: &OidcIdentity,
+        password_hash: Option<&str>,
+    ) -> Result<Option<EntryCommand>, IggyError> {
+        let validator = self.get_oidc_validator()?;
+        let permissions = validator.get_permissions(&identity.roles);
+        if let Some(user) = self.find_oidc_user(&identity.subject) {
+            if user.permissions.as_ref() == Some(&permissions) {
+                return Ok(None);
+            }
+
+            info!(
+                "Updating permissions of user: {} with ID: {} authenticated by OIDC subject: {} with roles: {:?}...",
+                user.username, user.id, identity.subject, identity.roles
+            );
+            return Ok(Some(EntryCommand::UpdatePermissions(UpdatePermissions {
+                user_id: Identifier::numeric(user.id)?,
+                permissions: Some(permissions),
+            })));
+        }
+
+        // The local user, or the one provisioned for the other subject, can't be taken over by the token having its username.
+        if let Some(user) = self
+            .users
+            .values()
+            .find(|user| user.username == identity.username)
+        {
+            error!(
+                "Cannot login user: {} authenticated by OIDC subject: {} (username belongs to the other user with ID: {}).",
+                identity.username, identity.subject, user.id
+            );
+            return Err(IggyError::InvalidCredentials);
+        }
+
+        if !validator.is_auto_provisioning_enabled() {
+            error!(
+                "Cannot login user: {} authenticated by OIDC subject: {} (not found).",
+                identity.username, identity.subject
+            );
+            return Err(IggyError::InvalidCredentials);
+        }
+
+        let Some(password_hash) = password_hash else {
+            error!(
+                "Cannot provision user: {} authenticated by OIDC subject: {} (missing password).",
+                identity.username, identity.subject
+            );
+            return Err(IggyError::InvalidCredentials);
+        };
+
+        if self.users.len() >= MAX_USERS {
+            error!("Available users limit reached.");
+            return Err(IggyError::UsersLimitReached);
+        }
+
+        let user_id = USER_ID.fetch_add(1, Ordering::SeqCst);
+        info!(
+            "Provisioning user: {} with ID: {user_id} authenticated by OIDC subject: {} with roles: {:?}...",
+            identity.username, identity.subject, identity.roles
+        );
+        Ok(Some(EntryCommand::CreateUser(CreateUserWithId {
+            user_id,
+            command: CreateUser {
+                username: identity.username.clone(),
+                password: password_hash.to_owned(),
+                status: UserStatus::Active,
+                permissions: Some(permissions),
+            },
+            oidc_subject: Some(identity.subject.clone()),
+        })))
+    }
+
     pub async fn logout_user(&self, session: &Session) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let user = self
@@ -523,3 +760,72 @@ impl System {
         Ok(())
     }
 }
+
+impl SharedSystem {
+    /// Logs in the user authenticated with the OIDC token, provisioning it or updating its permissions first if needed.
+    /// Returns the ID of the logged in user.
+    pub async fn login_with_oidc_identity(
+        &self,
+        identity: &OidcIdentity,
+        session: Option<&Session>,
+    ) -> Result<u32, IggyError> {
+        let (is_provisioned, is_up_to_date) = {
+            let system = self.read().await;
+            let permissions = system
+                .get_oidc_validator()?
+                .get_permissions(&identity.roles);
+            match system.find_oidc_user(&identity.subject) {
+                Some(user) => (true, user.permissions.as_ref() == Some(&permissions)),
+                None => (false, false),
+            }
+        };
+
+        if !is_up_to_date {
+            // The random password is hashed without holding the lock, only if the user might need to be created.
+            let password_hash = match is_provisioned {
+                true => None,
+                false => {
+                    let mut password = [0; 32];
+                    ring::rand::SystemRandom::new()
+                        .fill(&mut password)
+                        .map_err(|_| IggyError::InvalidPassword)?;
+                    Some(crypto::hash_password(&as_base64(&password)))
+                }
+            };
+            // The user is provisioned by the server itself, thus on behalf of the root user.
+            let root_session = Session::stateless(
+                DEFAULT_ROOT_USER_ID,
+                SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0),
+            );
+            self.apply_entry(&root_session, |system| {
+                // The concurrent login of the same subject might have already applied the change.
+                let command = system
+                    .prepare_oidc_user(identity, password_hash.as_deref())?
+                    .unwrap_or(EntryCommand::Noop);
+                Ok((command, ()))
+            })
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to provision user: {} authenticated by OIDC subject: {}",
+                    identity.username, identity.subject
+                )
+            })?;
+        }
+
+        let system = self.read().await;
+        let Some(user) = system.find_oidc_user(&identity.subject) else {
+            error!(
+                "Cannot login user: {} authenticated by OIDC subject: {} (not found).",
+                identity.username, identity.subject
+            );
+            return Err(IggyError::InvalidCredentials);
+        };
+
+        let username = user.username.clone();
+        let user = system
+            .login_user_with_credentials(&username, None, session)
+            .await?;
+        Ok(user.id)
+    }
+}
@@ -16,7 +16,9 @@
  * under the License.
  */
 
+use crate::streaming::topics::partition_assignor::PartitionAssignorKind;
 use ahash::AHashMap;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use tokio::sync::RwLock;
 use tracing::trace;
@@ -27,6 +29,9 @@ pub struct ConsumerGroup {
     pub group_id: u32,
     pub name: String,
     pub partitions_count: u32,
+    pub assignment_strategy: AssignmentStrategy,
+    assignor: PartitionAssignorKind,
+    generation_id: u32,
     members: AHashMap<u32, RwLock<ConsumerGroupMember>>,
 }
 
@@ -39,16 +44,47 @@ pub struct ConsumerGroupMember {
 }
 
 impl ConsumerGroup {
-    pub fn new(topic_id: u32, group_id: u32, name: &str, partitions_count: u32) -> ConsumerGroup {
+    pub fn new(
+        topic_id: u32,
+        group_id: u32,
+        name: &str,
+        partitions_count: u32,
+        assignment_strategy: AssignmentStrategy,
+    ) -> ConsumerGroup {
         ConsumerGroup {
             topic_id,
             group_id,
             name: name.to_string(),
             partitions_count,
+            assignment_strategy,
+            assignor: PartitionAssignorKind::new(assignment_strategy),
+            generation_id: 0,
             members: AHashMap::new(),
         }
     }
 
+    /// Returns the generation of the partitions assignment, which is incremented on every rebalancing.
+    /// It's kept only in memory, like the members themselves, so it starts again from 0 after the server restart,
+    /// when all the members have to rejoin the group anyway.
+    pub fn get_generation_id(&self) -> u32 {
+        self.generation_id
+    }
+
+    /// Ensures that the generation known to the member is the current one, so that its offset doesn't overwrite
+    /// the progress of the partition which has been already reassigned to another member.
+    pub fn ensure_generation(&self, generation_id: u32) -> Result<(), IggyError> {
+        if generation_id != self.generation_id {
+            return Err(IggyError::StaleConsumerGroupGeneration(
+                generation_id,
+                self.group_id,
+                self.topic_id,
+                self.generation_id,
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn get_members(&self) -> Vec<&RwLock<ConsumerGroupMember>> {
         self.members.values().collect()
     }
@@ -70,6 +106,19 @@ impl ConsumerGroup {
         ))
     }
 
+    /// Returns all the partitions assigned to the member, in the order of polling them.
+    pub async fn get_member_partitions(&self, member_id: u32) -> Result<Vec<u32>, IggyError> {
+        let member = self.members.get(&member_id);
+        if let Some(member) = member {
+            return Ok(member.read().await.get_partitions_in_order());
+        }
+        Err(IggyError::ConsumerGroupMemberNotFound(
+            member_id,
+            self.group_id,
+            self.topic_id,
+        ))
+    }
+
     pub async fn get_current_partition_id(&self, member_id: u32) -> Result<Option<u32>, IggyError> {
         let member = self.members.get(&member_id);
         if let Some(member) = member {
@@ -114,34 +163,25 @@ impl ConsumerGroup {
     }
 
     async fn assign_partitions(&mut self) {
-        let mut members = self.members.values_mut().collect::<Vec<_>>();
-        if members.is_empty() {
+        self.generation_id = self.generation_id.checked_add(1).unwrap_or(1);
+        if self.members.is_empty() {
             return;
         }
 
-        let members_count = members.len() as u32;
-        for member in members.iter_mut() {
-            let mut member = member.write().await;
-            member.current_partition_index = None;
-            member.current_partition_id = None;
-            member.partitions.clear();
+        let mut member_ids = self.members.keys().copied().collect::<Vec<_>>();
+        member_ids.sort_unstable();
+        let mut members = Vec::with_capacity(member_ids.len());
+        for member_id in member_ids {
+            let member = self.members.get(&member_id).unwrap().read().await;
+            members.push((member_id, member.get_partitions_in_order()));
         }
 
-        for partition_index in 0..self.partitions_count {
-            let partition_id = partition_index + 1;
-            let member_index = partition_index % members_count;
-            let member = members.get(member_index as usize).unwrap();
-            let mut member = member.write().await;
-            let member_partition_index = member.partitions.len() as u32;
-            member
-                .partitions
-                .insert(member_partition_index, partition_id);
-            if member.current_partition_id.is_none() {
-                member.current_partition_id = Some(partition_id);
-                member.current_partition_index = Some(member_partition_index);
-            }
-            trace!("Assigned partition ID: {} to member with ID: {} for topic with ID: {} in consumer group: {}",
-                partition_id, member.id, self.topic_id, self.group_id)
+        let assignments = self.assignor.assign(&members, self.partitions_count);
+        for ((member_id, _), partitions) in members.into_iter().zip(assignments) {
+            let mut member = self.members.get(&member_id).unwrap().write().await;
+            trace!("Assigned partitions: {:?} to member with ID: {} for topic with ID: {} in consumer group: {}, generation: {}",
+                partitions, member.id, self.topic_id, self.group_id, self.generation_id);
+            member.assign_partitions(partitions);
         }
     }
 }
@@ -151,6 +191,35 @@ impl ConsumerGroupMember {
         self.partitions.values().copied().collect()
     }
 
+    fn get_partitions_in_order(&self) -> Vec<u32> {
+        (0..self.partitions.len() as u32)
+            .filter_map(|index| self.partitions.get(&index).copied())
+            .collect()
+    }
+
+    /// Replaces the assigned partitions, and continues polling after the current partition if it's still assigned.
+    fn assign_partitions(&mut self, partitions: Vec<u32>) {
+        let current_partition_index = self.current_partition_id.and_then(|current_partition_id| {
+            partitions
+                .iter()
+                .position(|partition_id| *partition_id == current_partition_id)
+        });
+        match current_partition_index {
+            Some(index) => {
+                self.current_partition_index = Some(((index + 1) % partitions.len()) as u32);
+            }
+            None => {
+                self.current_partition_id = partitions.first().copied();
+                self.current_partition_index = self.current_partition_id.map(|_| 0);
+            }
+        }
+        self.partitions = partitions
+            .into_iter()
+            .enumerate()
+            .map(|(index, partition_id)| (index as u32, partition_id))
+            .collect();
+    }
+
     pub fn calculate_partition_id(&mut self) -> Option<u32> {
         let partition_index = self.current_partition_index?;
         let Some(partition_id) = self.partitions.get(&partition_index) else {
@@ -190,6 +259,9 @@ mod tests {
             group_id: 1,
             name: "test".to_string(),
             partitions_count: 3,
+            assignment_strategy: AssignmentStrategy::Sticky,
+            assignor: PartitionAssignorKind::new(AssignmentStrategy::Sticky),
+            generation_id: 0,
             members: AHashMap::new(),
         };
 
@@ -212,6 +284,9 @@ mod tests {
             group_id: 1,
             name: "test".to_string(),
             partitions_count: 3,
+            assignment_strategy: AssignmentStrategy::Sticky,
+            assignor: PartitionAssignorKind::new(AssignmentStrategy::Sticky),
+            generation_id: 0,
             members: AHashMap::new(),
         };
 
@@ -237,6 +312,9 @@ mod tests {
             group_id: 1,
             name: "test".to_string(),
             partitions_count: 3,
+            assignment_strategy: AssignmentStrategy::Sticky,
+            assignor: PartitionAssignorKind::new(AssignmentStrategy::Sticky),
+            generation_id: 0,
             members: AHashMap::new(),
         };
 
@@ -274,6 +352,9 @@ mod tests {
             group_id: 1,
             name: "test".to_string(),
             partitions_count: 1,
+            assignment_strategy: AssignmentStrategy::Sticky,
+            assignor: PartitionAssignorKind::new(AssignmentStrategy::Sticky),
+            generation_id: 0,
             members: AHashMap::new(),
         };
 
@@ -290,4 +371,152 @@ mod tests {
             assert_eq!(member2.partitions.len(), 1);
         }
     }
+
+    #[tokio::test]
+    async fn should_keep_assigned_partitions_when_member_joins() {
+        let member1_id = 123;
+        let member2_id = 456;
+        let mut consumer_group = ConsumerGroup::new(1, 1, "test", 4, AssignmentStrategy::Sticky);
+
+        consumer_group.add_member(member1_id).await;
+        assert_eq!(consumer_group.get_generation_id(), 1);
+        consumer_group.add_member(member2_id).await;
+        assert_eq!(consumer_group.get_generation_id(), 2);
+
+        let member1 = consumer_group.members.get(&member1_id).unwrap();
+        let member2 = consumer_group.members.get(&member2_id).unwrap();
+        let member1 = member1.read().await;
+        let member2 = member2.read().await;
+        assert_eq!(member1.get_partitions_in_order(), vec![1, 2]);
+        assert_eq!(member2.get_partitions_in_order(), vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn should_move_only_partitions_of_the_deleted_member() {
+        let member1_id = 123;
+        let member2_id = 456;
+        let member3_id = 789;
+        let mut consumer_group = ConsumerGroup::new(1, 1, "test", 6, AssignmentStrategy::Sticky);
+
+        consumer_group.add_member(member1_id).await;
+        consumer_group.add_member(member2_id).await;
+        consumer_group.add_member(member3_id).await;
+        let member2_partitions = consumer_group
+            .members
+            .get(&member2_id)
+            .unwrap()
+            .read()
+            .await
+            .get_partitions_in_order();
+        let member3_partitions = consumer_group
+            .members
+            .get(&member3_id)
+            .unwrap()
+            .read()
+            .await
+            .get_partitions_in_order();
+        assert_eq!(member2_partitions.len(), 2);
+        assert_eq!(member3_partitions.len(), 2);
+
+        consumer_group.delete_member(member1_id).await;
+        assert_eq!(consumer_group.get_generation_id(), 4);
+        let member2 = consumer_group.members.get(&member2_id).unwrap();
+        let member3 = consumer_group.members.get(&member3_id).unwrap();
+        let member2 = member2.read().await;
+        let member3 = member3.read().await;
+        assert_eq!(member2.partitions.len(), 3);
+        assert_eq!(member3.partitions.len(), 3);
+        let member2_new_partitions = member2.get_partitions_in_order();
+        let member3_new_partitions = member3.get_partitions_in_order();
+        assert!(member2_partitions
+            .iter()
+            .all(|partition_id| member2_new_partitions.contains(partition_id)));
+        assert!(member3_partitions
+            .iter()
+            .all(|partition_id| member3_new_partitions.contains(partition_id)));
+        for partition_id in 1..=consumer_group.partitions_count {
+            assert!(
+                member2_new_partitions.contains(&partition_id)
+                    || member3_new_partitions.contains(&partition_id)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_continue_polling_from_the_current_partition_after_rebalancing() {
+        let member1_id = 123;
+        let member2_id = 456;
+        let mut consumer_group = ConsumerGroup::new(1, 1, "test", 4, AssignmentStrategy::Sticky);
+
+        consumer_group.add_member(member1_id).await;
+        for _ in 0..2 {
+            consumer_group
+                .calculate_partition_id(member1_id)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            consumer_group
+                .get_current_partition_id(member1_id)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+
+        consumer_group.add_member(member2_id).await;
+        assert_eq!(
+            consumer_group
+                .get_current_partition_id(member1_id)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        let partition_id = consumer_group
+            .calculate_partition_id(member1_id)
+            .await
+            .unwrap();
+        assert_eq!(partition_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn should_assign_partitions_using_the_selected_strategy() {
+        let member1_id = 123;
+        let member2_id = 456;
+        let mut consumer_group =
+            ConsumerGroup::new(1, 1, "test", 5, AssignmentStrategy::RoundRobin);
+
+        consumer_group.add_member(member1_id).await;
+        consumer_group.add_member(member2_id).await;
+        assert_eq!(
+            consumer_group
+                .get_member_partitions(member1_id)
+                .await
+                .unwrap(),
+            vec![1, 3, 5]
+        );
+        assert_eq!(
+            consumer_group
+                .get_member_partitions(member2_id)
+                .await
+                .unwrap(),
+            vec![2, 4]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_stale_generation() {
+        let mut consumer_group = ConsumerGroup::new(1, 1, "test", 3, AssignmentStrategy::Sticky);
+        consumer_group.add_member(123).await;
+        let generation_id = consumer_group.get_generation_id();
+        assert!(consumer_group.ensure_generation(generation_id).is_ok());
+
+        consumer_group.add_member(456).await;
+        assert!(matches!(
+            consumer_group.ensure_generation(generation_id),
+            Err(IggyError::StaleConsumerGroupGeneration(_, 1, 1, _))
+        ));
+        assert!(consumer_group
+            .ensure_generation(consumer_group.get_generation_id())
+            .is_ok());
+    }
 }
@@ -20,6 +20,7 @@ use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
 use error_set::ErrContext;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
@@ -100,11 +101,12 @@ impl Topic {
         Ok(consumer_group.unwrap())
     }
 
-    pub async fn create_consumer_group(
-        &mut self,
+    /// Returns the ID of the consumer group to be created, which is either the given one or the next available one.
+    pub fn validate_create_consumer_group(
+        &self,
         group_id: Option<u32>,
         name: &str,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<u32, IggyError> {
         if self.consumer_groups_ids.contains_key(name) {
             return Err(IggyError::ConsumerGroupNameAlreadyExists(
                 name.to_owned(),
@@ -137,8 +139,23 @@ impl Topic {
             return Err(IggyError::ConsumerGroupIdAlreadyExists(id, self.topic_id));
         }
 
-        let consumer_group =
-            ConsumerGroup::new(self.topic_id, id, name, self.partitions.len() as u32);
+        Ok(id)
+    }
+
+    pub async fn create_consumer_group(
+        &mut self,
+        group_id: Option<u32>,
+        name: &str,
+        assignment_strategy: AssignmentStrategy,
+    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+        let id = self.validate_create_consumer_group(group_id, name)?;
+        let consumer_group = ConsumerGroup::new(
+            self.topic_id,
+            id,
+            name,
+            self.partitions.len() as u32,
+            assignment_strategy,
+        );
         self.consumer_groups.insert(id, RwLock::new(consumer_group));
         self.consumer_groups_ids.insert(name.to_owned(), id);
         info!(
@@ -237,6 +254,7 @@ mod tests {
     use crate::streaming::persistence::persister::{FileWithSyncPersister, PersisterKind};
     use crate::streaming::storage::SystemStorage;
     use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::topics::cleanup_policy::CleanupPolicy;
     use iggy::utils::expiry::IggyExpiry;
     use iggy::utils::topic_size::MaxTopicSize;
     use std::sync::atomic::{AtomicU32, AtomicU64};
@@ -248,7 +266,9 @@ mod tests {
         let name = "test";
         let mut topic = get_topic().await;
         let topic_id = topic.topic_id;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         {
             let created_consumer_group = result.unwrap().read().await;
@@ -276,10 +296,14 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
-        let result = topic.create_consumer_group(Some(group_id), "test2").await;
+        let result = topic
+            .create_consumer_group(Some(group_id), "test2", AssignmentStrategy::default())
+            .await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err, IggyError::ConsumerGroupIdAlreadyExists(_, _)));
@@ -291,11 +315,15 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let group_id = group_id + 1;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(
@@ -310,7 +338,9 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let result = topic
@@ -325,7 +355,9 @@ mod tests {
         let group_id = 1;
         let name = "test";
         let mut topic = get_topic().await;
-        let result = topic.create_consumer_group(Some(group_id), name).await;
+        let result = topic
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
+            .await;
         assert!(result.is_ok());
         assert_eq!(topic.consumer_groups.len(), 1);
         let group_id = group_id + 1;
@@ -343,7 +375,7 @@ mod tests {
         let member_id = 1;
         let mut topic = get_topic().await;
         topic
-            .create_consumer_group(Some(group_id), name)
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
             .await
             .unwrap();
         let result = topic
@@ -366,7 +398,7 @@ mod tests {
         let member_id = 1;
         let mut topic = get_topic().await;
         topic
-            .create_consumer_group(Some(group_id), name)
+            .create_consumer_group(Some(group_id), name, AssignmentStrategy::default())
             .await
             .unwrap();
         topic
@@ -419,6 +451,7 @@ mod tests {
             compression_algorithm,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap()
@@ -20,9 +20,10 @@ use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
 use error_set::ErrContext;
-use iggy::consumer::Consumer;
+use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_lag::PartitionLag;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 
 impl Topic {
@@ -31,8 +32,17 @@ impl Topic {
         consumer: Consumer,
         offset: u64,
         partition_id: Option<u32>,
+        generation_id: Option<u32>,
         client_id: u32,
     ) -> Result<(), IggyError> {
+        if let (ConsumerKind::ConsumerGroup, Some(generation_id)) = (consumer.kind, generation_id) {
+            self.get_consumer_group(&consumer.id)?
+                .read()
+                .await
+                .ensure_generation(generation_id)
+                .with_error_context(|error| format!("{COMPONENT} (error: {error}) - rejected consumer offset: {offset} for consumer group: {}, client ID: {client_id}", consumer.id))?;
+        }
+
         let Some((polling_consumer, partition_id)) = self
             .resolve_consumer_with_partition_id(&consumer, client_id, partition_id, false)
             .await
@@ -99,6 +109,35 @@ impl Topic {
         }))
     }
 
+    pub async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+    ) -> Result<Vec<PartitionLag>, IggyError> {
+        let consumer_id = match consumer.kind {
+            ConsumerKind::Consumer => PollingConsumer::resolve_consumer_id(&consumer.id),
+            ConsumerKind::ConsumerGroup => {
+                self.get_consumer_group(&consumer.id)?.read().await.group_id
+            }
+        };
+
+        let mut partitions_lag = Vec::with_capacity(self.partitions.len());
+        for partition in self.partitions.values() {
+            let partition = partition.read().await;
+            let lag = partition
+                .get_consumer_lag(consumer.kind, consumer_id)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to get consumer lag for consumer: {consumer}, partition ID: {}",
+                        partition.partition_id
+                    )
+                })?;
+            partitions_lag.push(lag);
+        }
+        partitions_lag.sort_by_key(|lag| lag.partition_id);
+        Ok(partitions_lag)
+    }
+
     pub async fn delete_consumer_offset(
         &self,
         consumer: Consumer,
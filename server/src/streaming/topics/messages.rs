@@ -18,6 +18,7 @@
 
 use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
 use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::partitions::replicas::QuorumAcknowledgement;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::topics::topic::Topic;
 use crate::streaming::topics::COMPONENT;
@@ -28,17 +29,25 @@ use error_set::ErrContext;
 use iggy::confirmation::Confirmation;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
-use iggy::messages::poll_messages::{PollingKind, PollingStrategy};
-use iggy::messages::send_messages::{Message, Partitioning, PartitioningKind};
-use iggy::models::messages::PolledMessages;
+use iggy::messages::header_filter::HeaderFilter;
+use iggy::messages::poll_messages::{IsolationLevel, PollingKind, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning, PartitioningKind, ProducerSequence};
+use iggy::models::header::HeaderValue;
+use iggy::models::messages::{MessageState, PolledMessages};
+use iggy::topics::cleanup_policy::CompactionKey;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
 use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
 
+/// The maximum number of batches scanned when polling the messages with the header filter,
+/// so that the rarely matching filter doesn't make the single request scan the whole partition.
+const MAX_FILTERED_BATCHES: u32 = 10;
+
 impl Topic {
     pub fn get_messages_count(&self) -> u64 {
         self.messages_count.load(Ordering::SeqCst)
@@ -50,6 +59,99 @@ impl Topic {
         partition_id: u32,
         strategy: PollingStrategy,
         count: u32,
+    ) -> Result<PolledMessages, IggyError> {
+        self.get_messages_with_isolation_level(
+            consumer,
+            partition_id,
+            strategy,
+            count,
+            IsolationLevel::ReadUncommitted,
+        )
+        .await
+    }
+
+    /// Returns only the messages matching the header filter, fetching the next ones to replace the skipped messages, up to the specified count.
+    /// Along with the messages, the offset of the last scanned message is returned, so that the skipped ones can be committed as well.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_filtered_messages(
+        &self,
+        consumer: PollingConsumer,
+        partition_id: u32,
+        strategy: PollingStrategy,
+        count: u32,
+        isolation_level: IsolationLevel,
+        filter: Option<&HeaderFilter>,
+    ) -> Result<(PolledMessages, Option<u64>), IggyError> {
+        let mut polled_messages = self
+            .get_messages_with_isolation_level(
+                consumer,
+                partition_id,
+                strategy,
+                count,
+                isolation_level,
+            )
+            .await?;
+        let Some(filter) = filter else {
+            let last_offset = polled_messages
+                .messages
+                .last()
+                .map(|message| message.offset);
+            return Ok((polled_messages, last_offset));
+        };
+
+        let mut matching_messages = Vec::new();
+        let mut last_offset = None;
+        let mut scanned_batches = 0;
+        while let Some(last_scanned_offset) = polled_messages
+            .messages
+            .last()
+            .map(|message| message.offset)
+        {
+            last_offset = Some(last_scanned_offset);
+            scanned_batches += 1;
+            matching_messages.extend(
+                polled_messages
+                    .messages
+                    .drain(..)
+                    .filter(|message| filter.matches(message.headers.as_ref())),
+            );
+            if matching_messages.len() >= count as usize {
+                // The messages following the last returned one haven't been consumed yet.
+                matching_messages.truncate(count as usize);
+                last_offset = matching_messages.last().map(|message| message.offset);
+                break;
+            }
+
+            // The skipped messages are replaced with the following ones, unless polling the last messages.
+            if strategy.kind == PollingKind::Last
+                || last_scanned_offset >= polled_messages.current_offset
+                || scanned_batches >= MAX_FILTERED_BATCHES
+            {
+                break;
+            }
+
+            polled_messages = self
+                .get_messages_with_isolation_level(
+                    consumer,
+                    partition_id,
+                    PollingStrategy::offset(last_scanned_offset + 1),
+                    count,
+                    isolation_level,
+                )
+                .await?;
+        }
+
+        polled_messages.messages = matching_messages;
+        Ok((polled_messages, last_offset))
+    }
+
+    pub async fn get_messages_with_isolation_level(
+        &self,
+        consumer: PollingConsumer,
+        partition_id: u32,
+        strategy: PollingStrategy,
+        count: u32,
+        isolation_level: IsolationLevel,
     ) -> Result<PolledMessages, IggyError> {
         if !self.has_partitions() {
             return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
@@ -80,24 +182,62 @@ impl Topic {
             PollingKind::Next => partition.get_next_messages(consumer, count).await,
         }?;
 
+        let messages = match isolation_level {
+            IsolationLevel::ReadUncommitted => messages,
+            IsolationLevel::ReadCommitted => {
+                // The skipped messages are replaced with the following ones, unless polling the last messages.
+                let refill = strategy.kind != PollingKind::Last;
+                partition
+                    .get_committed_messages(messages, count, refill)
+                    .await
+                    .with_error_context(|error| format!("{COMPONENT} (error: {error}) - failed to get committed messages, partition ID: {partition_id}, count: {count}"))?
+            }
+        };
+
         let messages = messages
             .into_iter()
-            .map(|msg| msg.to_polled_message())
+            .map(|msg| {
+                let mut message = msg.to_polled_message()?;
+                if partition.is_poisoned(message.offset) {
+                    message.state = MessageState::Poisoned;
+                }
+                Ok(message)
+            })
             .collect::<Result<Vec<_>, IggyError>>()?;
         Ok(PolledMessages {
             partition_id,
             current_offset: partition.current_offset,
+            generation_id: 0,
             messages,
         })
     }
 
+    /// Appends the messages and returns the ID of the partition they were appended to.
     pub async fn append_messages(
         &self,
         batch_size: IggyByteSize,
         partitioning: Partitioning,
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
+        let (partition_id, acknowledgement) = self
+            .append_unacknowledged_messages(batch_size, partitioning, messages, confirmation)
+            .await?;
+        if let Some(acknowledgement) = acknowledgement {
+            acknowledgement.wait().await?;
+        }
+        Ok(partition_id)
+    }
+
+    /// Appends the messages and returns the ID of the partition they were appended to,
+    /// along with the acknowledgement of the quorum of the replicas to be awaited, if required by the confirmation.
+    pub async fn append_unacknowledged_messages(
+        &self,
+        batch_size: IggyByteSize,
+        partitioning: Partitioning,
+        messages: Vec<Message>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<(u32, Option<QuorumAcknowledgement>), IggyError> {
         if !self.has_partitions() {
             return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
         }
@@ -108,10 +248,126 @@ impl Topic {
             return Err(IggyError::TopicFull(self.topic_id, self.stream_id));
         }
 
+        let partition_id = self.resolve_partition_id(&partitioning)?;
         if messages.is_empty() {
-            return Ok(());
+            return Ok((partition_id, None));
+        }
+
+        let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
+        let acknowledgement = self
+            .append_messages_to_partition(appendable_batch_info, messages, confirmation)
+            .await?;
+        Ok((partition_id, acknowledgement))
+    }
+
+    /// Appends the messages as a part of the open transaction and returns the ID of the partition they were appended to.
+    pub async fn append_transactional_messages(
+        &self,
+        transaction_id: u64,
+        batch_size: IggyByteSize,
+        partitioning: Partitioning,
+        messages: Vec<Message>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<u32, IggyError> {
+        if !self.has_partitions() {
+            return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
+        }
+
+        if self.is_full() && self.config.topic.delete_oldest_segments {
+            return Err(IggyError::TopicFull(self.topic_id, self.stream_id));
+        }
+
+        // The transaction markers are stored only on the node handling the transaction, so they would be lost on the leader change.
+        if self.replication_factor > 1 {
+            return Err(IggyError::TransactionalReplicatedTopic(
+                self.topic_id,
+                self.stream_id,
+            ));
+        }
+
+        let partition_id = self.resolve_partition_id(&partitioning)?;
+        if messages.is_empty() {
+            return Ok(partition_id);
         }
 
+        let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
+        let confirmation = confirmation.unwrap_or(self.config.segment.server_confirmation);
+        self.get_partition(partition_id)?
+            .write()
+            .await
+            .append_transactional_messages(
+                transaction_id,
+                appendable_batch_info,
+                messages,
+                Some(confirmation),
+            )
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append messages for transaction with ID: {transaction_id}")
+            })?;
+        Ok(partition_id)
+    }
+
+    /// Appends the messages sent by the idempotent producer, optionally as a part of the open transaction,
+    /// and returns the ID of the partition they were appended to.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn append_idempotent_messages(
+        &self,
+        producer_sequence: ProducerSequence,
+        transaction_id: Option<u64>,
+        batch_size: IggyByteSize,
+        partitioning: Partitioning,
+        messages: Vec<Message>,
+        confirmation: Option<Confirmation>,
+    ) -> Result<u32, IggyError> {
+        if !self.has_partitions() {
+            return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
+        }
+
+        if self.is_full() && self.config.topic.delete_oldest_segments {
+            return Err(IggyError::TopicFull(self.topic_id, self.stream_id));
+        }
+
+        // The producer states are stored only on the node handling the messages, so they would be lost on the leader change.
+        if self.replication_factor > 1 {
+            return Err(IggyError::IdempotentReplicatedTopic(
+                self.topic_id,
+                self.stream_id,
+            ));
+        }
+
+        if partitioning.kind != PartitioningKind::PartitionId {
+            return Err(IggyError::InvalidIdempotentPartitioning);
+        }
+
+        let partition_id = self.resolve_partition_id(&partitioning)?;
+        if messages.is_empty() {
+            return Ok(partition_id);
+        }
+
+        let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
+        let confirmation = confirmation.unwrap_or(self.config.segment.server_confirmation);
+        self.get_partition(partition_id)?
+            .write()
+            .await
+            .append_idempotent_messages(
+                producer_sequence,
+                transaction_id,
+                appendable_batch_info,
+                messages,
+                Some(confirmation),
+            )
+            .await
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to append messages for producer with ID: {}",
+                    producer_sequence.producer_id
+                )
+            })?;
+        Ok(partition_id)
+    }
+
+    fn resolve_partition_id(&self, partitioning: &Partitioning) -> Result<u32, IggyError> {
         let partition_id = match partitioning.kind {
             PartitioningKind::Balanced => self.get_next_partition_id(),
             PartitioningKind::PartitionId => u32::from_le_bytes(
@@ -123,10 +379,69 @@ impl Topic {
                 self.calculate_partition_id_by_messages_key_hash(&partitioning.value)
             }
         };
+        Ok(partition_id)
+    }
+
+    /// Validates the messages against the cleanup policy of the topic. The empty payload is allowed only
+    /// as a tombstone in the compacted topic, and for the topic compacted by the messages key,
+    /// the key is stored in the reserved header, so that it's available once the segments are compacted.
+    pub fn prepare_messages_for_cleanup_policy(
+        &self,
+        partitioning: &Partitioning,
+        messages: &mut [Message],
+    ) -> Result<(), IggyError> {
+        let Some(compaction_key) = self.cleanup_policy.compaction_key() else {
+            if messages.iter().any(|message| message.payload.is_empty()) {
+                return Err(IggyError::EmptyMessagePayload);
+            }
+            return Ok(());
+        };
 
+        if compaction_key != &CompactionKey::MessagesKey
+            || partitioning.kind != PartitioningKind::MessagesKey
+        {
+            return Ok(());
+        }
+
+        let key = HeaderValue::from_raw(&partitioning.value[..partitioning.length as usize])?;
+        for message in messages.iter_mut() {
+            message
+                .headers
+                .get_or_insert_with(HashMap::new)
+                .insert(compaction_key.header_key(), key.clone());
+        }
+        Ok(())
+    }
+
+    /// Appends the messages marked as poisoned (e.g. moved from the source topic after too many failed deliveries).
+    pub async fn append_poisoned_messages(
+        &self,
+        batch_size: IggyByteSize,
+        messages: Vec<Message>,
+    ) -> Result<(), IggyError> {
+        if !self.has_partitions() {
+            return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
+        }
+
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let partition_id = self.get_next_partition_id();
         let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
-        self.append_messages_to_partition(appendable_batch_info, messages, confirmation)
+        self.get_partition(partition_id)?
+            .write()
             .await
+            .append_messages_with_state(
+                appendable_batch_info,
+                messages,
+                MessageState::Poisoned,
+                None,
+            )
+            .await
+            .with_error_context(|error| {
+                format!("{COMPONENT} (error: {error}) - failed to append poisoned messages")
+            })
     }
 
     pub async fn flush_unsaved_buffer(
@@ -152,25 +467,54 @@ impl Topic {
         appendable_batch_info: AppendableBatchInfo,
         messages: Vec<Message>,
         confirmation: Option<Confirmation>,
-    ) -> Result<(), IggyError> {
-        let partition = self.partitions.get(&appendable_batch_info.partition_id);
-        partition
-            .ok_or({
-                IggyError::PartitionNotFound(
-                    appendable_batch_info.partition_id,
-                    self.stream_id,
-                    self.stream_id,
-                )
-            })?
-            .write()
-            .await
-            .append_messages(appendable_batch_info, messages, confirmation)
-            .await
-            .with_error_context(|error| {
-                format!("{COMPONENT} (error: {error}) - failed to append messages")
-            })?;
+    ) -> Result<Option<QuorumAcknowledgement>, IggyError> {
+        let partition_id = appendable_batch_info.partition_id;
+        let partition = self.partitions.get(&partition_id).ok_or({
+            IggyError::PartitionNotFound(partition_id, self.stream_id, self.stream_id)
+        })?;
+        let confirmation = confirmation.unwrap_or(self.config.segment.server_confirmation);
+        let (next_offset, replica_offsets) = {
+            let mut partition = partition.write().await;
+            partition
+                .append_messages(appendable_batch_info, messages, Some(confirmation))
+                .await
+                .with_error_context(|error| {
+                    format!("{COMPONENT} (error: {error}) - failed to append messages")
+                })?;
+            (
+                partition.get_next_offset(),
+                partition.replica_offsets.clone(),
+            )
+        };
 
-        Ok(())
+        // The partition lock must be released, as the followers need it to fetch the appended messages.
+        let required_acknowledgements = self.get_required_replica_acknowledgements();
+        if confirmation != Confirmation::Quorum
+            || required_acknowledgements == 0
+            || next_offset == 0
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(QuorumAcknowledgement {
+            replica_offsets,
+            offset: next_offset - 1,
+            required_count: required_acknowledgements,
+            timeout: self.config.replication.ack_timeout,
+            stream_id: self.stream_id,
+            topic_id: self.topic_id,
+            partition_id,
+        }))
+    }
+
+    /// Returns the number of the follower acknowledgements required to reach the quorum (majority) of the replicas.
+    fn get_required_replica_acknowledgements(&self) -> usize {
+        // The followers reject the messages sent by the clients to the replicated topics.
+        if !self.config.replication.enabled || self.replication_factor <= 1 {
+            return 0;
+        }
+
+        self.replication_factor as usize / 2
     }
 
     fn get_next_partition_id(&self) -> u32 {
@@ -337,6 +681,7 @@ mod tests {
     use crate::streaming::storage::SystemStorage;
     use bytes::Bytes;
     use iggy::compression::compression_algorithm::CompressionAlgorithm;
+    use iggy::topics::cleanup_policy::CleanupPolicy;
     use iggy::utils::topic_size::MaxTopicSize;
     use std::sync::atomic::AtomicU32;
     use std::sync::atomic::AtomicU64;
@@ -478,6 +823,7 @@ mod tests {
             compression_algorithm,
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -20,6 +20,7 @@ pub mod consumer_group;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod messages;
+pub mod partition_assignor;
 pub mod partitions;
 pub mod persistence;
 pub mod segments;
@@ -0,0 +1,193 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use ahash::AHashSet;
+use iggy::consumer_groups::assignment_strategy::AssignmentStrategy;
+
+/// Assigns the partitions of the topic to the consumer group members.
+/// The members are ordered by their ID and passed along with the partitions they currently own,
+/// and the returned assignments are in the same order.
+pub trait PartitionAssignor {
+    fn assign(&self, members: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>>;
+}
+
+#[derive(Debug)]
+pub enum PartitionAssignorKind {
+    Range(RangeAssignor),
+    RoundRobin(RoundRobinAssignor),
+    Sticky(StickyAssignor),
+}
+
+impl PartitionAssignorKind {
+    pub fn new(assignment_strategy: AssignmentStrategy) -> Self {
+        match assignment_strategy {
+            AssignmentStrategy::Range => PartitionAssignorKind::Range(RangeAssignor),
+            AssignmentStrategy::RoundRobin => PartitionAssignorKind::RoundRobin(RoundRobinAssignor),
+            AssignmentStrategy::Sticky => PartitionAssignorKind::Sticky(StickyAssignor),
+        }
+    }
+
+    pub fn assign(&self, members: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>> {
+        match self {
+            PartitionAssignorKind::Range(a) => a.assign(members, partitions_count),
+            PartitionAssignorKind::RoundRobin(a) => a.assign(members, partitions_count),
+            PartitionAssignorKind::Sticky(a) => a.assign(members, partitions_count),
+        }
+    }
+}
+
+/// Assigns the contiguous ranges of partitions, the first members get the remainder of the division.
+#[derive(Debug)]
+pub struct RangeAssignor;
+
+/// Deals the partitions one by one to the subsequent members.
+#[derive(Debug)]
+pub struct RoundRobinAssignor;
+
+/// Keeps the partitions already owned by the members, up to their fair share, and moves only the remaining ones.
+#[derive(Debug)]
+pub struct StickyAssignor;
+
+impl PartitionAssignor for RangeAssignor {
+    fn assign(&self, members: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>> {
+        if members.is_empty() {
+            return Vec::new();
+        }
+
+        let members_count = members.len() as u32;
+        let mut first_partition_id = 1;
+        (0..members_count)
+            .map(|index| {
+                let mut length = partitions_count / members_count;
+                if index < partitions_count % members_count {
+                    length += 1;
+                }
+                let partitions = (first_partition_id..first_partition_id + length).collect();
+                first_partition_id += length;
+                partitions
+            })
+            .collect()
+    }
+}
+
+impl PartitionAssignor for RoundRobinAssignor {
+    fn assign(&self, members: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>> {
+        let mut assignments = vec![Vec::new(); members.len()];
+        if members.is_empty() {
+            return assignments;
+        }
+
+        for partition_id in 1..=partitions_count {
+            assignments[(partition_id - 1) as usize % members.len()].push(partition_id);
+        }
+        assignments
+    }
+}
+
+impl PartitionAssignor for StickyAssignor {
+    fn assign(&self, members: &[(u32, Vec<u32>)], partitions_count: u32) -> Vec<Vec<u32>> {
+        if members.is_empty() {
+            return Vec::new();
+        }
+
+        let mut owned_partitions = AHashSet::new();
+        let mut assignments = members
+            .iter()
+            .map(|(_, partitions)| {
+                let mut partitions = partitions.clone();
+                partitions.retain(|partition_id| {
+                    *partition_id <= partitions_count && owned_partitions.insert(*partition_id)
+                });
+                partitions
+            })
+            .collect::<Vec<_>>();
+
+        // The members already owning the most partitions get the remainder of the division, so that as few partitions as possible are moved.
+        let members_count = assignments.len() as u32;
+        let mut limits = vec![partitions_count / members_count; assignments.len()];
+        let mut members_by_owned_partitions = (0..assignments.len()).collect::<Vec<_>>();
+        members_by_owned_partitions
+            .sort_by_key(|index| std::cmp::Reverse(assignments[*index].len()));
+        for index in members_by_owned_partitions
+            .into_iter()
+            .take((partitions_count % members_count) as usize)
+        {
+            limits[index] += 1;
+        }
+
+        let mut unassigned_partitions = (1..=partitions_count)
+            .filter(|partition_id| !owned_partitions.contains(partition_id))
+            .collect::<Vec<_>>();
+        for (partitions, limit) in assignments.iter_mut().zip(&limits) {
+            if partitions.len() > *limit as usize {
+                unassigned_partitions.extend(partitions.drain(*limit as usize..));
+            }
+        }
+        unassigned_partitions.sort_unstable();
+
+        let mut unassigned_partitions = unassigned_partitions.into_iter();
+        for (partitions, limit) in assignments.iter_mut().zip(&limits) {
+            while partitions.len() < *limit as usize {
+                let Some(partition_id) = unassigned_partitions.next() else {
+                    break;
+                };
+                partitions.push(partition_id);
+            }
+        }
+        assignments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_assignor_should_assign_contiguous_partitions() {
+        let members = vec![(1, Vec::new()), (2, Vec::new()), (3, Vec::new())];
+        let assignments = RangeAssignor.assign(&members, 7);
+        assert_eq!(assignments, vec![vec![1, 2, 3], vec![4, 5], vec![6, 7]]);
+    }
+
+    #[test]
+    fn round_robin_assignor_should_deal_partitions_to_subsequent_members() {
+        let members = vec![(1, Vec::new()), (2, Vec::new()), (3, Vec::new())];
+        let assignments = RoundRobinAssignor.assign(&members, 7);
+        assert_eq!(assignments, vec![vec![1, 4, 7], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn sticky_assignor_should_keep_owned_partitions() {
+        let members = vec![(1, vec![4, 5, 6]), (2, vec![1, 2, 3]), (3, Vec::new())];
+        let assignments = StickyAssignor.assign(&members, 6);
+        assert_eq!(assignments, vec![vec![4, 5], vec![1, 2], vec![3, 6]]);
+    }
+
+    #[test]
+    fn assignors_should_leave_members_without_partitions_when_there_are_not_enough() {
+        let members = vec![(1, Vec::new()), (2, Vec::new()), (3, Vec::new())];
+        for assignor in [
+            PartitionAssignorKind::new(AssignmentStrategy::Range),
+            PartitionAssignorKind::new(AssignmentStrategy::RoundRobin),
+            PartitionAssignorKind::new(AssignmentStrategy::Sticky),
+        ] {
+            let assignments = assignor.assign(&members, 2);
+            assert_eq!(assignments, vec![vec![1], vec![2], vec![]]);
+        }
+    }
+}
@@ -16,7 +16,11 @@
  * under the License.
  */
 
+use crate::streaming::partitions::partition::Partition;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::topics::COMPONENT;
+use error_set::ErrContext;
+use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
 
 impl Topic {
@@ -28,4 +32,24 @@ impl Topic {
 
         segments_count
     }
+
+    /// Compacts the closed segments of all the partitions if the topic is compacted, and returns the number of removed messages.
+    pub async fn compact_segments(&self) -> Result<u64, IggyError> {
+        let Some(compaction_key) = self.cleanup_policy.compaction_key() else {
+            return Ok(0);
+        };
+
+        let header_key = compaction_key.header_key();
+        let mut removed_messages = 0;
+        for (partition_id, partition) in &self.partitions {
+            removed_messages += Partition::compact_segments(partition, &header_key)
+                .await
+                .with_error_context(|error| {
+                    format!(
+                        "{COMPONENT} (error: {error}) - failed to compact segments for partition with ID: {partition_id}"
+                    )
+                })?;
+        }
+        Ok(removed_messages)
+    }
 }
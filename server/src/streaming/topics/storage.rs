@@ -55,11 +55,15 @@ impl TopicStorage for FileTopicStorage {
 
         let message_expiry = Topic::get_message_expiry(state.message_expiry, &topic.config);
         let max_topic_size = Topic::get_max_topic_size(state.max_topic_size, &topic.config)?;
+        let compression_algorithm =
+            Topic::get_compression_algorithm(state.compression_algorithm, &topic.config);
         topic.created_at = state.created_at;
         topic.message_expiry = message_expiry;
         topic.max_topic_size = max_topic_size;
         topic.compression_algorithm = state.compression_algorithm;
         topic.replication_factor = state.replication_factor.unwrap_or(1);
+        topic.cleanup_policy = state.cleanup_policy.clone();
+        topic.dead_letter = state.dead_letter;
 
         let mut dir_entries = fs::read_dir(&topic.partitions_path).await
             .with_context(|| format!("Failed to read partition with ID: {} for stream with ID: {} for topic with ID: {} and path: {}",
@@ -103,6 +107,7 @@ impl TopicStorage for FileTopicStorage {
                 topic.config.clone(),
                 topic.storage.clone(),
                 message_expiry,
+                compression_algorithm,
                 topic.messages_count_of_parent_stream.clone(),
                 topic.messages_count.clone(),
                 topic.size_of_parent_stream.clone(),
@@ -149,6 +154,7 @@ impl TopicStorage for FileTopicStorage {
                         topic.config.clone(),
                         topic.storage.clone(),
                         message_expiry,
+                        compression_algorithm,
                         topic.messages_count_of_parent_stream.clone(),
                         topic.messages_count.clone(),
                         topic.size_of_parent_stream.clone(),
@@ -209,6 +215,7 @@ impl TopicStorage for FileTopicStorage {
                 consumer_group.id,
                 &consumer_group.name,
                 topic.get_partitions_count(),
+                consumer_group.assignment_strategy,
             );
             topic
                 .consumer_groups_ids
@@ -27,6 +27,7 @@ use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMut;
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::sizeable::Sizeable;
@@ -62,6 +63,9 @@ pub struct Topic {
     pub compression_algorithm: CompressionAlgorithm,
     pub max_topic_size: MaxTopicSize,
     pub replication_factor: u8,
+    pub cleanup_policy: CleanupPolicy,
+    /// Whether the topic stores the messages moved from the other topics of its stream after too many failed deliveries.
+    pub dead_letter: bool,
     pub created_at: IggyTimestamp,
 }
 
@@ -91,6 +95,7 @@ impl Topic {
             Default::default(),
             MaxTopicSize::ServerDefault,
             1,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap()
@@ -111,6 +116,7 @@ impl Topic {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
+        cleanup_policy: CleanupPolicy,
     ) -> Result<Topic, IggyError> {
         let path = config.get_topic_path(stream_id, topic_id);
         let partitions_path = config.get_partitions_path(stream_id, topic_id);
@@ -135,6 +141,8 @@ impl Topic {
             max_topic_size: Topic::get_max_topic_size(max_topic_size, &config)?,
             compression_algorithm,
             replication_factor,
+            cleanup_policy,
+            dead_letter: false,
             config,
             created_at: IggyTimestamp::now(),
         };
@@ -248,6 +256,18 @@ impl Topic {
         }
     }
 
+    /// Returns the algorithm used to compress the segments of the topic, which is the one set for the topic
+    /// only if overriding the default compression algorithm is allowed.
+    pub fn get_compression_algorithm(
+        compression_algorithm: CompressionAlgorithm,
+        config: &SystemConfig,
+    ) -> CompressionAlgorithm {
+        match config.compression.allow_override {
+            true => compression_algorithm,
+            false => config.compression.default_algorithm,
+        }
+    }
+
     pub fn get_message_expiry(message_expiry: IggyExpiry, config: &SystemConfig) -> IggyExpiry {
         match message_expiry {
             IggyExpiry::ServerDefault => config.segment.message_expiry,
@@ -266,15 +286,17 @@ impl fmt::Display for Topic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Topic {{ id: {}, stream ID: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, max_topic_size: {}, replication_factor: {} }}",
+            "Topic {{ id: {}, stream ID: {}, name: {}, path: {}, partitions: {}, message_expiry: {}, compression_algorithm: {}, max_topic_size: {}, replication_factor: {}, cleanup_policy: {} }}",
             self.topic_id,
             self.stream_id,
             self.name,
             self.path,
             self.partitions.len(),
             self.message_expiry,
+            self.compression_algorithm,
             self.max_topic_size,
             self.replication_factor,
+            self.cleanup_policy,
         )
     }
 }
@@ -325,6 +347,7 @@ mod tests {
             compression_algorithm,
             max_topic_size,
             replication_factor,
+            CleanupPolicy::default(),
         )
         .await
         .unwrap();
@@ -16,6 +16,8 @@
  * under the License.
  */
 
+pub mod oidc;
 pub mod permissioner;
 pub mod permissioner_rules;
+pub mod throttler;
 pub mod user;
@@ -0,0 +1,646 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::oidc::{OidcConfig, OidcIssuerConfig};
+use ahash::AHashMap;
+use iggy::error::IggyError;
+use iggy::models::permissions::{GlobalPermissions, Permissions};
+use iggy::users::defaults::{MAX_USERNAME_LENGTH, MIN_USERNAME_LENGTH};
+use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+const COMPONENT: &str = "OIDC";
+
+/// Identity of the user extracted from the validated OIDC token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcIdentity {
+    pub subject: OidcSubject,
+    pub username: String,
+    pub roles: Vec<String>,
+}
+
+/// Issuer and subject (`sub` claim) of the OIDC token, which identify the user permanently,
+/// unlike the username claim, which might be changed or reused by the identity provider.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OidcSubject {
+    pub issuer: String,
+    pub subject: String,
+}
+
+impl Display for OidcSubject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.issuer, self.subject)
+    }
+}
+
+/// Validates the JWTs issued by the external identity providers against the configured issuers and their JWKS.
+/// The key sets are loaded lazily and reloaded when the token is signed with an unknown key ID,
+/// at most once per `jwks_refresh_interval`.
+#[derive(Debug)]
+pub struct OidcValidator {
+    config: OidcConfig,
+    issuers: AHashMap<String, OidcIssuer>,
+    http_client: reqwest::Client,
+}
+
+#[derive(Debug)]
+struct OidcIssuer {
+    config: OidcIssuerConfig,
+    jwks: RwLock<Option<LoadedJwks>>,
+}
+
+#[derive(Debug)]
+struct LoadedJwks {
+    keys: Vec<OidcKey>,
+    loaded_at: Instant,
+}
+
+struct OidcKey {
+    id: Option<String>,
+    algorithm: Option<Algorithm>,
+    key: DecodingKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnverifiedClaims {
+    iss: Option<String>,
+}
+
+impl OidcValidator {
+    pub fn new(config: OidcConfig) -> Self {
+        let issuers = config
+            .issuers
+            .iter()
+            .map(|issuer| {
+                (
+                    issuer.issuer.clone(),
+                    OidcIssuer {
+                        config: issuer.clone(),
+                        jwks: RwLock::new(None),
+                    },
+                )
+            })
+            .collect();
+        Self {
+            config,
+            issuers,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Returns the permissions granted by the role rules matching any of the given roles.
+    pub fn get_permissions(&self, roles: &[String]) -> Permissions {
+        let mut global = GlobalPermissions::default();
+        for rule in &self.config.role_rules {
+            if roles.contains(&rule.role) {
+                rule.grant(&mut global);
+            }
+        }
+
+        Permissions {
+            global,
+            streams: None,
+        }
+    }
+
+    pub fn is_auto_provisioning_enabled(&self) -> bool {
+        self.config.auto_provision
+    }
+
+    pub async fn validate(&self, token: &str) -> Result<OidcIdentity, IggyError> {
+        let header = decode_header(token).map_err(|error| {
+            error!("{COMPONENT} - failed to decode OIDC token header, error: {error}");
+            IggyError::InvalidAccessToken
+        })?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            error!(
+                "{COMPONENT} - OIDC token signed with symmetric algorithm: {:?} is not supported.",
+                header.alg
+            );
+            return Err(IggyError::Unauthenticated);
+        }
+
+        let issuer = Self::peek_issuer(token)?;
+        let Some(issuer) = self.issuers.get(&issuer) else {
+            error!("{COMPONENT} - OIDC token issuer: {issuer} is not trusted.");
+            return Err(IggyError::Unauthenticated);
+        };
+
+        let key = self
+            .get_key(issuer, header.kid.as_deref(), header.alg)
+            .await?;
+        let claims = decode::<HashMap<String, Value>>(
+            token,
+            &key,
+            &self.create_validation(issuer, header.alg),
+        )
+        .map_err(|error| {
+            error!(
+                "{COMPONENT} - failed to validate OIDC token issued by: {}, error: {error}",
+                issuer.config.issuer
+            );
+            IggyError::Unauthenticated
+        })?
+        .claims;
+
+        let Some(subject) = claims
+            .get("sub")
+            .and_then(|value| value.as_str())
+            .filter(|subject| !subject.is_empty())
+        else {
+            error!(
+                "{COMPONENT} - OIDC token issued by: {} has no subject.",
+                issuer.config.issuer
+            );
+            return Err(IggyError::Unauthenticated);
+        };
+
+        let Some(username) = get_claim(&claims, &self.config.username_claim)
+            .and_then(|value| value.as_str())
+            .map(|username| username.trim().to_string())
+        else {
+            error!(
+                "{COMPONENT} - OIDC token issued by: {} has no username claim: {}.",
+                issuer.config.issuer, self.config.username_claim
+            );
+            return Err(IggyError::Unauthenticated);
+        };
+
+        if username.len() < MIN_USERNAME_LENGTH || username.len() > MAX_USERNAME_LENGTH {
+            error!("{COMPONENT} - OIDC token username: {username} is invalid.");
+            return Err(IggyError::InvalidUsername);
+        }
+
+        let roles = match get_claim(&claims, &self.config.roles_claim) {
+            Some(Value::String(role)) => vec![role.to_owned()],
+            Some(Value::Array(roles)) => roles
+                .iter()
+                .filter_map(|role| role.as_str().map(|role| role.to_owned()))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(OidcIdentity {
+            subject: OidcSubject {
+                issuer: issuer.config.issuer.clone(),
+                subject: subject.to_owned(),
+            },
+            username,
+            roles,
+        })
+    }
+
+    fn peek_issuer(token: &str) -> Result<String, IggyError> {
+        let mut validation = Validation::default();
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+        validation.required_spec_claims.clear();
+        let claims = decode::<UnverifiedClaims>(token, &DecodingKey::from_secret(&[]), &validation)
+            .map_err(|error| {
+                error!("{COMPONENT} - failed to decode OIDC token claims, error: {error}");
+                IggyError::InvalidAccessToken
+            })?
+            .claims;
+        claims.iss.ok_or_else(|| {
+            error!("{COMPONENT} - OIDC token has no issuer.");
+            IggyError::Unauthenticated
+        })
+    }
+
+    fn create_validation(&self, issuer: &OidcIssuer, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[&issuer.config.issuer]);
+        validation.leeway = self.config.clock_skew.as_secs() as u64;
+        validation.validate_nbf = true;
+        let audiences = self
+            .config
+            .audiences
+            .iter()
+            .filter(|audience| !audience.is_empty())
+            .collect::<Vec<_>>();
+        if audiences.is_empty() {
+            validation.validate_aud = false;
+            validation.set_required_spec_claims(&["exp", "iss", "sub"]);
+        } else {
+            validation.set_audience(&audiences);
+            validation.set_required_spec_claims(&["exp", "iss", "sub", "aud"]);
+        }
+        validation
+    }
+
+    async fn get_key(
+        &self,
+        issuer: &OidcIssuer,
+        key_id: Option<&str>,
+        algorithm: Algorithm,
+    ) -> Result<DecodingKey, IggyError> {
+        {
+            let jwks = issuer.jwks.read().await;
+            if let Some(key) = jwks
+                .as_ref()
+                .and_then(|jwks| jwks.find_key(key_id, algorithm))
+            {
+                return Ok(key);
+            }
+        }
+
+        let mut jwks = issuer.jwks.write().await;
+        let refresh_interval = self.config.jwks_refresh_interval.get_duration();
+        let can_refresh = jwks
+            .as_ref()
+            .is_none_or(|jwks| jwks.loaded_at.elapsed() >= refresh_interval);
+        if can_refresh {
+            *jwks = Some(self.load_jwks(&issuer.config).await?);
+        } else {
+            warn!(
+                "{COMPONENT} - JWKS for issuer: {} was reloaded recently, skipping the reload.",
+                issuer.config.issuer
+            );
+        }
+
+        jwks.as_ref()
+            .and_then(|jwks| jwks.find_key(key_id, algorithm))
+            .ok_or_else(|| {
+                error!(
+                    "{COMPONENT} - key with ID: {} was not found in JWKS for issuer: {}.",
+                    key_id.unwrap_or("none"),
+                    issuer.config.issuer
+                );
+                IggyError::Unauthenticated
+            })
+    }
+
+    async fn load_jwks(&self, issuer: &OidcIssuerConfig) -> Result<LoadedJwks, IggyError> {
+        info!(
+            "{COMPONENT} - loading JWKS for issuer: {} from: {}...",
+            issuer.issuer, issuer.jwks
+        );
+        let bytes = if issuer.is_remote() {
+            let response = self
+                .http_client
+                .get(&issuer.jwks)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|error| {
+                    error!(
+                        "{COMPONENT} - failed to fetch JWKS from: {}, error: {error}",
+                        issuer.jwks
+                    );
+                    IggyError::CannotReadFile
+                })?;
+            response
+                .bytes()
+                .await
+                .map_err(|error| {
+                    error!(
+                        "{COMPONENT} - failed to read JWKS from: {}, error: {error}",
+                        issuer.jwks
+                    );
+                    IggyError::CannotReadFile
+                })?
+                .to_vec()
+        } else {
+            tokio::fs::read(&issuer.jwks).await.map_err(|error| {
+                error!(
+                    "{COMPONENT} - failed to read JWKS file: {}, error: {error}",
+                    issuer.jwks
+                );
+                IggyError::CannotReadFile
+            })?
+        };
+
+        let jwks = serde_json::from_slice::<JwkSet>(&bytes).map_err(|error| {
+            error!(
+                "{COMPONENT} - failed to parse JWKS from: {}, error: {error}",
+                issuer.jwks
+            );
+            IggyError::CannotDeserializeResource
+        })?;
+
+        let mut keys = Vec::with_capacity(jwks.keys.len());
+        for jwk in &jwks.keys {
+            if let AlgorithmParameters::OctetKey(_) = jwk.algorithm {
+                warn!(
+                    "{COMPONENT} - skipping symmetric key with ID: {} in JWKS for issuer: {}.",
+                    jwk.common.key_id.as_deref().unwrap_or("none"),
+                    issuer.issuer
+                );
+                continue;
+            }
+
+            // Keys meant for the encryption (e.g. RSA-OAEP) can't be used to verify the signatures.
+            let algorithm = match jwk.common.key_algorithm {
+                Some(algorithm) => match algorithm.to_string().parse::<Algorithm>() {
+                    Ok(algorithm) => Some(algorithm),
+                    Err(_) => continue,
+                },
+                None => None,
+            };
+
+            match DecodingKey::from_jwk(jwk) {
+                Ok(key) => keys.push(OidcKey {
+                    id: jwk.common.key_id.clone(),
+                    algorithm,
+                    key,
+                }),
+                Err(error) => warn!(
+                    "{COMPONENT} - skipping invalid key with ID: {} in JWKS for issuer: {}, error: {error}",
+                    jwk.common.key_id.as_deref().unwrap_or("none"),
+                    issuer.issuer
+                ),
+            }
+        }
+
+        info!(
+            "{COMPONENT} - loaded {} key(s) in JWKS for issuer: {}.",
+            keys.len(),
+            issuer.issuer
+        );
+        Ok(LoadedJwks {
+            keys,
+            loaded_at: Instant::now(),
+        })
+    }
+}
+
+impl Debug for OidcKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OidcKey")
+            .field("id", &self.id)
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LoadedJwks {
+    fn find_key(&self, key_id: Option<&str>, algorithm: Algorithm) -> Option<DecodingKey> {
+        let mut keys = self.keys.iter().filter(|key| {
+            key.algorithm
+                .is_none_or(|key_algorithm| key_algorithm == algorithm)
+        });
+        match key_id {
+            Some(key_id) => keys
+                .find(|key| key.id.as_deref() == Some(key_id))
+                .map(|key| key.key.clone()),
+            None => {
+                let key = keys.next()?;
+                // Without the key ID, the key can be selected only if it's unambiguous.
+                if keys.next().is_some() {
+                    return None;
+                }
+                Some(key.key.clone())
+            }
+        }
+    }
+}
+
+/// Returns the claim by its name, or the nested claim by its path separated with dots, e.g. `realm_access.roles`.
+fn get_claim<'a>(claims: &'a HashMap<String, Value>, name: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(name) {
+        return Some(value);
+    }
+
+    let mut path = name.split('.');
+    let mut value = claims.get(path.next()?)?;
+    for segment in path {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::oidc::OidcRoleRule;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use iggy::utils::timestamp::IggyTimestamp;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use rcgen::KeyPair;
+    use serde_json::json;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    const ISSUER: &str = "https://idp.example.com";
+    const KEY_ID: &str = "test-key";
+
+    struct TestIdentityProvider {
+        key: EncodingKey,
+        jwks_path: String,
+        _directory: TempDir,
+    }
+
+    impl TestIdentityProvider {
+        fn new() -> Self {
+            let directory = tempfile::tempdir().unwrap();
+            let key_pair = KeyPair::generate().unwrap();
+            // The raw public key of P-256 is the uncompressed point: 0x04 || x || y.
+            let public_key = key_pair.public_key_raw();
+            let jwks = json!({
+                "keys": [{
+                    "kty": "EC",
+                    "crv": "P-256",
+                    "alg": "ES256",
+                    "kid": KEY_ID,
+                    "x": URL_SAFE_NO_PAD.encode(&public_key[1..33]),
+                    "y": URL_SAFE_NO_PAD.encode(&public_key[33..65]),
+                }]
+            });
+            let jwks_path = directory.path().join("jwks.json");
+            std::fs::write(&jwks_path, jwks.to_string()).unwrap();
+            Self {
+                key: EncodingKey::from_ec_pem(key_pair.serialize_pem().as_bytes()).unwrap(),
+                jwks_path: jwks_path.to_str().unwrap().to_owned(),
+                _directory: directory,
+            }
+        }
+
+        fn issue(&self, claims: Value) -> String {
+            let mut header = Header::new(Algorithm::ES256);
+            header.kid = Some(KEY_ID.to_owned());
+            encode(&header, &claims, &self.key).unwrap()
+        }
+
+        fn claims(&self, username: &str, roles: &[&str]) -> Value {
+            json!({
+                "iss": ISSUER,
+                "aud": "iggy",
+                "sub": format!("{username}-subject"),
+                "exp": IggyTimestamp::now().to_secs() + 60,
+                "preferred_username": username,
+                "groups": roles,
+            })
+        }
+
+        fn validator(&self) -> OidcValidator {
+            OidcValidator::new(OidcConfig {
+                enabled: true,
+                issuers: vec![
+                    OidcIssuerConfig::from_str(&format!("{ISSUER}|{}", self.jwks_path)).unwrap(),
+                ],
+                role_rules: vec![
+                    OidcRoleRule::from_str("readers=read_streams+poll_messages").unwrap(),
+                    OidcRoleRule::from_str("writers=send_messages").unwrap(),
+                ],
+                ..OidcConfig::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_token_should_be_mapped_to_identity() {
+        let provider = TestIdentityProvider::new();
+        let token = provider.issue(provider.claims("alice", &["readers", "unknown"]));
+
+        let identity = provider.validator().validate(&token).await.unwrap();
+
+        assert_eq!(identity.subject.issuer, ISSUER);
+        assert_eq!(identity.subject.subject, "alice-subject");
+        assert_eq!(identity.username, "alice");
+        assert_eq!(identity.roles, vec!["readers", "unknown"]);
+    }
+
+    #[tokio::test]
+    async fn roles_should_be_read_from_nested_claim() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims["realm_access"] = json!({ "roles": ["writers"] });
+        let token = provider.issue(claims);
+        let mut validator = provider.validator();
+        validator.config.roles_claim = "realm_access.roles".to_owned();
+
+        let identity = validator.validate(&token).await.unwrap();
+
+        assert_eq!(identity.roles, vec!["writers"]);
+    }
+
+    #[tokio::test]
+    async fn token_from_untrusted_issuer_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims["iss"] = json!("https://untrusted.example.com");
+        let token = provider.issue(claims);
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn expired_token_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims["exp"] = json!(IggyTimestamp::now().to_secs() - 3600);
+        let token = provider.issue(claims);
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn token_for_other_audience_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims["aud"] = json!("other");
+        let token = provider.issue(claims);
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn token_signed_with_other_key_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let other_provider = TestIdentityProvider::new();
+        let token = other_provider.issue(provider.claims("alice", &[]));
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn token_signed_with_symmetric_key_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let claims = provider.claims("alice", &[]);
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn token_without_username_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims.as_object_mut().unwrap().remove("preferred_username");
+        let token = provider.issue(claims);
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn token_without_subject_should_be_rejected() {
+        let provider = TestIdentityProvider::new();
+        let mut claims = provider.claims("alice", &[]);
+        claims.as_object_mut().unwrap().remove("sub");
+        let token = provider.issue(claims);
+
+        let result = provider.validator().validate(&token).await;
+
+        assert!(matches!(result, Err(IggyError::Unauthenticated)));
+    }
+
+    #[test]
+    fn permissions_should_be_granted_by_matching_role_rules() {
+        let provider = TestIdentityProvider::new();
+        let validator = provider.validator();
+
+        let permissions = validator.get_permissions(&["readers".to_owned(), "writers".to_owned()]);
+        assert!(permissions.global.read_streams);
+        assert!(permissions.global.poll_messages);
+        assert!(permissions.global.send_messages);
+        assert!(!permissions.global.manage_streams);
+        assert!(permissions.streams.is_none());
+
+        let permissions = validator.get_permissions(&["unknown".to_owned()]);
+        assert_eq!(permissions.global, GlobalPermissions::default());
+    }
+}
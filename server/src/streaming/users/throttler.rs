@@ -0,0 +1,324 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::streaming::users::user::User;
+use dashmap::DashMap;
+use iggy::error::IggyError;
+use iggy::models::user_info::UserId;
+use iggy::models::user_quotas::UserQuotas;
+use std::time::Instant;
+
+/// Throttles the users and clients exceeding their quotas. Each limited rate is tracked by the token bucket,
+/// refilled continuously at the configured rate, with the capacity equal to the rate (one second burst).
+/// The bucket is allowed to go into debt, so a batch larger than the rate is accepted once, and the following ones
+/// are rejected until the debt is paid back, which keeps the average rate within the quota.
+#[derive(Debug, Default)]
+pub struct Throttler {
+    client_requests_per_second: u32,
+    clients_requests: DashMap<u32, RateLimiter>,
+    users_requests: DashMap<UserId, RateLimiter>,
+    users_produce: DashMap<UserId, RateLimiter>,
+    users_consume: DashMap<UserId, RateLimiter>,
+    users_streams_produce: DashMap<(UserId, u32), RateLimiter>,
+    users_streams_consume: DashMap<(UserId, u32), RateLimiter>,
+}
+
+#[derive(Debug)]
+struct RateLimiter {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate: rate as f64,
+            tokens: rate as f64,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn check(&mut self) -> Result<(), IggyError> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled_at = now;
+        if self.tokens >= 1.0 {
+            return Ok(());
+        }
+
+        let retry_after = ((1.0 - self.tokens) / self.rate * 1000.0).ceil() as u64;
+        Err(IggyError::QuotaExceeded(retry_after))
+    }
+
+    fn consume(&mut self, amount: u64) {
+        self.tokens -= amount as f64;
+    }
+}
+
+impl Throttler {
+    pub fn new(client_requests_per_second: u32) -> Self {
+        Self {
+            client_requests_per_second,
+            ..Default::default()
+        }
+    }
+
+    pub fn init(&mut self, users: &[&User]) {
+        for user in users {
+            self.update_quotas_for_user(user.id, user.quotas.as_ref());
+        }
+    }
+
+    pub fn update_quotas_for_user(&mut self, user_id: UserId, quotas: Option<&UserQuotas>) {
+        self.delete_quotas_for_user(user_id);
+        let Some(quotas) = quotas else {
+            return;
+        };
+
+        if quotas.global.requests_per_second > 0 {
+            self.users_requests.insert(
+                user_id,
+                RateLimiter::new(quotas.global.requests_per_second as u64),
+            );
+        }
+        if quotas.global.produce_bytes_per_second > 0 {
+            self.users_produce.insert(
+                user_id,
+                RateLimiter::new(quotas.global.produce_bytes_per_second),
+            );
+        }
+        if quotas.global.consume_bytes_per_second > 0 {
+            self.users_consume.insert(
+                user_id,
+                RateLimiter::new(quotas.global.consume_bytes_per_second),
+            );
+        }
+
+        let Some(streams) = &quotas.streams else {
+            return;
+        };
+
+        for (stream_id, stream) in streams {
+            if stream.produce_bytes_per_second > 0 {
+                self.users_streams_produce.insert(
+                    (user_id, *stream_id),
+                    RateLimiter::new(stream.produce_bytes_per_second),
+                );
+            }
+            if stream.consume_bytes_per_second > 0 {
+                self.users_streams_consume.insert(
+                    (user_id, *stream_id),
+                    RateLimiter::new(stream.consume_bytes_per_second),
+                );
+            }
+        }
+    }
+
+    pub fn delete_quotas_for_user(&mut self, user_id: UserId) {
+        self.users_requests.remove(&user_id);
+        self.users_produce.remove(&user_id);
+        self.users_consume.remove(&user_id);
+        self.users_streams_produce
+            .retain(|(id, _), _| *id != user_id);
+        self.users_streams_consume
+            .retain(|(id, _), _| *id != user_id);
+    }
+
+    pub fn delete_client(&self, client_id: u32) {
+        self.clients_requests.remove(&client_id);
+    }
+
+    /// Counts the request of the client, the user ID and client ID equal to 0 are not limited
+    /// (the request is not authenticated yet or it has been sent via the stateless HTTP API).
+    pub fn throttle_request(&self, user_id: UserId, client_id: u32) -> Result<(), IggyError> {
+        let mut client = if client_id > 0 && self.client_requests_per_second > 0 {
+            Some(
+                self.clients_requests
+                    .entry(client_id)
+                    .or_insert_with(|| RateLimiter::new(self.client_requests_per_second as u64)),
+            )
+        } else {
+            None
+        };
+        let mut user = self.users_requests.get_mut(&user_id);
+        if let Some(client) = client.as_mut() {
+            client.check()?;
+        }
+        if let Some(user) = user.as_mut() {
+            user.check()?;
+        }
+
+        if let Some(client) = client.as_mut() {
+            client.consume(1);
+        }
+        if let Some(user) = user.as_mut() {
+            user.consume(1);
+        }
+        Ok(())
+    }
+
+    pub fn throttle_produce(
+        &self,
+        user_id: UserId,
+        stream_id: u32,
+        size_bytes: u64,
+    ) -> Result<(), IggyError> {
+        throttle(
+            &self.users_produce,
+            &self.users_streams_produce,
+            user_id,
+            stream_id,
+            size_bytes,
+        )
+    }
+
+    /// Checks if the user can poll the messages, the size of which is not known upfront,
+    /// thus it has to be recorded with `record_consumed` once the messages have been polled.
+    pub fn throttle_consume(&self, user_id: UserId, stream_id: u32) -> Result<(), IggyError> {
+        throttle(
+            &self.users_consume,
+            &self.users_streams_consume,
+            user_id,
+            stream_id,
+            0,
+        )
+    }
+
+    pub fn record_consumed(&self, user_id: UserId, stream_id: u32, size_bytes: u64) {
+        if let Some(mut user) = self.users_consume.get_mut(&user_id) {
+            user.consume(size_bytes);
+        }
+        if let Some(mut stream) = self.users_streams_consume.get_mut(&(user_id, stream_id)) {
+            stream.consume(size_bytes);
+        }
+    }
+}
+
+fn throttle(
+    users: &DashMap<UserId, RateLimiter>,
+    users_streams: &DashMap<(UserId, u32), RateLimiter>,
+    user_id: UserId,
+    stream_id: u32,
+    size_bytes: u64,
+) -> Result<(), IggyError> {
+    let mut user = users.get_mut(&user_id);
+    let mut stream = users_streams.get_mut(&(user_id, stream_id));
+    if let Some(user) = user.as_mut() {
+        user.check()?;
+    }
+    if let Some(stream) = stream.as_mut() {
+        stream.check()?;
+    }
+
+    if let Some(user) = user.as_mut() {
+        user.consume(size_bytes);
+    }
+    if let Some(stream) = stream.as_mut() {
+        stream.consume(size_bytes);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ahash::AHashMap;
+    use iggy::models::user_quotas::{GlobalQuotas, StreamQuotas};
+
+    #[test]
+    fn requests_exceeding_the_rate_should_be_rejected_with_retry_after() {
+        let mut throttler = Throttler::default();
+        throttler.update_quotas_for_user(
+            1,
+            Some(&UserQuotas {
+                global: GlobalQuotas {
+                    requests_per_second: 2,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        );
+
+        assert!(throttler.throttle_request(1, 1).is_ok());
+        assert!(throttler.throttle_request(1, 2).is_ok());
+        let error = throttler.throttle_request(1, 3).unwrap_err();
+        assert!(
+            matches!(error, IggyError::QuotaExceeded(retry_after) if retry_after > 0 && retry_after <= 500)
+        );
+        assert!(throttler.throttle_request(2, 3).is_ok());
+    }
+
+    #[test]
+    fn client_requests_exceeding_the_rate_should_be_rejected() {
+        let throttler = Throttler::new(1);
+        assert!(throttler.throttle_request(1, 1).is_ok());
+        assert!(throttler.throttle_request(1, 1).is_err());
+        assert!(throttler.throttle_request(1, 2).is_ok());
+        assert!(throttler.throttle_request(1, 0).is_ok());
+        throttler.delete_client(1);
+        assert!(throttler.throttle_request(1, 1).is_ok());
+    }
+
+    #[test]
+    fn batch_larger_than_the_rate_should_be_accepted_once() {
+        let mut throttler = Throttler::default();
+        throttler.update_quotas_for_user(
+            1,
+            Some(&UserQuotas {
+                global: GlobalQuotas {
+                    produce_bytes_per_second: 1000,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        );
+
+        assert!(throttler.throttle_produce(1, 1, 5000).is_ok());
+        let error = throttler.throttle_produce(1, 1, 1).unwrap_err();
+        assert!(matches!(error, IggyError::QuotaExceeded(retry_after) if retry_after >= 3900));
+    }
+
+    #[test]
+    fn stream_quotas_should_be_applied_only_to_the_given_stream() {
+        let mut throttler = Throttler::default();
+        throttler.update_quotas_for_user(
+            1,
+            Some(&UserQuotas {
+                global: GlobalQuotas::default(),
+                streams: Some(AHashMap::from([(
+                    1,
+                    StreamQuotas {
+                        produce_bytes_per_second: 0,
+                        consume_bytes_per_second: 100,
+                    },
+                )])),
+            }),
+        );
+
+        assert!(throttler.throttle_consume(1, 1).is_ok());
+        throttler.record_consumed(1, 1, 200);
+        assert!(throttler.throttle_consume(1, 1).is_err());
+        assert!(throttler.throttle_consume(1, 2).is_ok());
+        assert!(throttler.throttle_produce(1, 1, 1000).is_ok());
+
+        throttler.update_quotas_for_user(1, None);
+        assert!(throttler.throttle_consume(1, 1).is_ok());
+    }
+}
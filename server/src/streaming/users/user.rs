@@ -16,10 +16,11 @@
  * under the License.
  */
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
+use crate::streaming::users::oidc::OidcSubject;
 use crate::streaming::utils::crypto;
 use dashmap::DashMap;
 use iggy::models::user_status::UserStatus;
-use iggy::models::{permissions::Permissions, user_info::UserId};
+use iggy::models::{permissions::Permissions, user_info::UserId, user_quotas::UserQuotas};
 use iggy::users::defaults::*;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::sync::Arc;
@@ -32,7 +33,10 @@ pub struct User {
     pub password: String,
     pub created_at: IggyTimestamp,
     pub permissions: Option<Permissions>,
+    pub quotas: Option<UserQuotas>,
     pub personal_access_tokens: DashMap<Arc<String>, PersonalAccessToken>,
+    /// The OIDC subject the user was provisioned for, if any, which is the only one allowed to log in as this user with the OIDC token.
+    pub oidc_subject: Option<OidcSubject>,
 }
 
 impl Default for User {
@@ -44,7 +48,9 @@ impl Default for User {
             password: "secret".to_string(),
             created_at: IggyTimestamp::now(),
             permissions: None,
+            quotas: None,
             personal_access_tokens: DashMap::new(),
+            oidc_subject: None,
         }
     }
 }
@@ -87,7 +93,9 @@ impl User {
             created_at: IggyTimestamp::now(),
             status,
             permissions,
+            quotas: None,
             personal_access_tokens: DashMap::new(),
+            oidc_subject: None,
         }
     }
 
@@ -16,20 +16,34 @@
  * under the License.
  */
 
+use crate::binary::buffered_sender::BufferedResponse;
+use crate::binary::compression::decompress_request;
 use crate::binary::{command, sender::SenderKind};
 use crate::command::ServerCommand;
 use crate::server_error::ConnectionError;
+use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use bytes::{BufMut, BytesMut};
+use futures::stream::{self, FuturesUnordered};
+use futures::StreamExt;
 use iggy::bytes_serializable::BytesSerializable;
+use iggy::command::REQUEST_ID_FLAG;
+use iggy::compression::wire_compression::{WireCompression, COMPRESSION_FLAG};
 use iggy::error::IggyError;
 use iggy::validatable::Validatable;
 use std::io::ErrorKind;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tracing::{debug, error, info};
 
 const INITIAL_BYTES_LENGTH: usize = 4;
+const CODE_BYTES_LENGTH: usize = 4;
+const REQUEST_ID_BYTES_LENGTH: usize = 8;
+const RESPONSE_INITIAL_BYTES_LENGTH: usize = 16;
+/// The maximum number of requests handled at the same time over the single multiplexed connection,
+/// the next requests are not read until some of them complete.
+const MAX_IN_FLIGHT_REQUESTS: usize = 1024;
 
 pub(crate) async fn handle_connection(
     session: Arc<Session>,
@@ -62,7 +76,31 @@ pub(crate) async fn handle_connection(
         let mut command_buffer = BytesMut::with_capacity(length as usize);
         command_buffer.put_bytes(0, length as usize);
         sender.read(&mut command_buffer).await?;
-        let command = ServerCommand::from_bytes(command_buffer.freeze());
+        if is_multiplexed(&command_buffer) {
+            if matches!(sender.transport(), Transport::WebSocket) {
+                // The WebSocket bridge splits the responses by their regular header, without the request ID.
+                error!("Cannot multiplex the requests over the WebSocket connection.");
+                sender
+                    .send_error_response(IggyError::FeatureUnavailable)
+                    .await?;
+                continue;
+            }
+
+            // The connection switches to the multiplexed requests for good with the first one carrying the request ID.
+            debug!("Received a TCP request with the request ID, the connection is multiplexed.");
+            return handle_multiplexed_connection(command_buffer, session, sender, system).await;
+        }
+
+        let command_buffer =
+            match decompress_request(command_buffer.freeze(), session.get_compression().as_ref()) {
+                Ok(command_buffer) => command_buffer,
+                Err(error) => {
+                    error!("Failed to decompress the TCP request: {error}");
+                    sender.send_error_response(error).await?;
+                    continue;
+                }
+            };
+        let command = ServerCommand::from_bytes(command_buffer);
         if command.is_err() {
             sender
                 .send_error_response(IggyError::InvalidCommand)
@@ -77,10 +115,189 @@ pub(crate) async fn handle_connection(
         }
 
         debug!("Received a TCP command: {command}, payload size: {length}");
+        let is_handshake = matches!(command, ServerCommand::Handshake(_));
         command::handle(command, sender, &session, system.clone()).await?;
+        if is_handshake {
+            // The handshake response itself is sent as is, the compression applies to the following ones.
+            sender.set_compression(session.get_compression());
+        }
     }
 }
 
+fn is_multiplexed(frame: &[u8]) -> bool {
+    frame
+        .get(..CODE_BYTES_LENGTH)
+        .map(|code| u32::from_le_bytes(code.try_into().unwrap()) & REQUEST_ID_FLAG != 0)
+        .unwrap_or(false)
+}
+
+/// Handles the requests carrying their request IDs concurrently, so the long polls don't hold back the other requests.
+/// The responses are written in the order the requests complete, each of them carrying the ID of its request.
+async fn handle_multiplexed_connection(
+    first_frame: BytesMut,
+    session: Arc<Session>,
+    sender: &mut SenderKind,
+    system: SharedSystem,
+) -> Result<(), ConnectionError> {
+    let transport = sender.transport();
+    let compression = session.get_compression();
+    let (reader, mut writer) = sender.split();
+    let frames = stream::unfold(reader, |mut reader| async move {
+        let frame = read_frame(&mut reader).await;
+        Some((frame, reader))
+    });
+    let mut frames = Box::pin(stream::once(async { Ok(first_frame) }).chain(frames));
+    let mut in_flight = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            frame = frames.next(), if in_flight.len() < MAX_IN_FLIGHT_REQUESTS => {
+                let Some(frame) = frame else {
+                    return Ok(());
+                };
+                let (request_id, command) = parse_multiplexed_request(frame?, compression.as_ref())?;
+                let command = match command {
+                    Ok(command) => command,
+                    Err(error) => {
+                        let response = BufferedResponse {
+                            status: error.as_code(),
+                            payload: error.details_to_bytes(),
+                        };
+                        write_response(&mut writer, request_id, response, None).await?;
+                        continue;
+                    }
+                };
+                debug!("Received a multiplexed TCP command: {command}, request ID: {request_id}");
+                in_flight.push(handle_request(
+                    request_id,
+                    command,
+                    transport,
+                    session.clone(),
+                    system.clone(),
+                ));
+            }
+            Some((request_id, response, result)) = in_flight.next() => {
+                write_response(&mut writer, request_id, response, compression.as_ref()).await?;
+                result?;
+            }
+        }
+    }
+}
+
+/// Parses the frame `[code | REQUEST_ID_FLAG][request ID][payload]`, the command error is returned to the client,
+/// while the frame without the request ID breaks the connection, as there's no way to respond to it.
+fn parse_multiplexed_request(
+    mut frame: BytesMut,
+    compression: Option<&WireCompression>,
+) -> Result<(u64, Result<ServerCommand, IggyError>), ConnectionError> {
+    if !is_multiplexed(&frame) || frame.len() < CODE_BYTES_LENGTH + REQUEST_ID_BYTES_LENGTH {
+        error!("Received a TCP request without the request ID over the multiplexed connection.");
+        return Err(ConnectionError::from(IggyError::InvalidCommand));
+    }
+
+    let code =
+        u32::from_le_bytes(frame[..CODE_BYTES_LENGTH].try_into().unwrap()) & !REQUEST_ID_FLAG;
+    let request_id = u64::from_le_bytes(
+        frame[CODE_BYTES_LENGTH..CODE_BYTES_LENGTH + REQUEST_ID_BYTES_LENGTH]
+            .try_into()
+            .unwrap(),
+    );
+    // The code is moved in place of the request ID, so the command is parsed from the frame as is.
+    frame[REQUEST_ID_BYTES_LENGTH..CODE_BYTES_LENGTH + REQUEST_ID_BYTES_LENGTH]
+        .copy_from_slice(&code.to_le_bytes());
+    let command = frame.freeze().slice(REQUEST_ID_BYTES_LENGTH..);
+    let command = match decompress_request(command, compression) {
+        Ok(command) => command,
+        Err(error) => {
+            error!("Failed to decompress the multiplexed TCP request: {error}");
+            return Ok((request_id, Err(error)));
+        }
+    };
+    let command = match ServerCommand::from_bytes(command) {
+        Ok(command) => command,
+        Err(_) => return Ok((request_id, Err(IggyError::InvalidCommand))),
+    };
+    if let Err(error) = command.validate() {
+        error!("Command validation failed: {error}");
+        return Ok((request_id, Err(error)));
+    }
+
+    if let ServerCommand::SubscribeMessages(_) = command {
+        error!("Cannot subscribe over the multiplexed connection, it requires the dedicated one.");
+        return Ok((request_id, Err(IggyError::FeatureUnavailable)));
+    }
+
+    Ok((request_id, Ok(command)))
+}
+
+async fn handle_request(
+    request_id: u64,
+    command: ServerCommand,
+    transport: Transport,
+    session: Arc<Session>,
+    system: SharedSystem,
+) -> (u64, BufferedResponse, Result<(), IggyError>) {
+    let mut sender = SenderKind::get_buffered_sender(transport);
+    let result = command::handle(command, &mut sender, &session, system).await;
+    let SenderKind::Buffered(sender) = &mut sender else {
+        unreachable!("The multiplexed request is always handled by the buffered sender.");
+    };
+    (request_id, sender.take_response(), result)
+}
+
+async fn read_frame(reader: &mut (impl AsyncRead + Unpin + ?Sized)) -> Result<BytesMut, IggyError> {
+    let mut initial_buffer = [0u8; INITIAL_BYTES_LENGTH];
+    read_exact(reader, &mut initial_buffer).await?;
+    let length = u32::from_le_bytes(initial_buffer);
+    let mut frame = BytesMut::with_capacity(length as usize);
+    frame.put_bytes(0, length as usize);
+    read_exact(reader, &mut frame).await?;
+    Ok(frame)
+}
+
+async fn read_exact(
+    reader: &mut (impl AsyncRead + Unpin + ?Sized),
+    buffer: &mut [u8],
+) -> Result<(), IggyError> {
+    match reader.read_exact(buffer).await {
+        Ok(_) => Ok(()),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Err(IggyError::ConnectionClosed),
+        Err(_) => Err(IggyError::TcpError),
+    }
+}
+
+async fn write_response(
+    writer: &mut (impl AsyncWrite + Unpin + ?Sized),
+    request_id: u64,
+    mut response: BufferedResponse,
+    compression: Option<&WireCompression>,
+) -> Result<(), IggyError> {
+    if response.status == 0 {
+        if let Some(compressed) =
+            compression.and_then(|compression| compression.compress(&response.payload))
+        {
+            response = BufferedResponse {
+                status: COMPRESSION_FLAG,
+                payload: compressed,
+            };
+        }
+    }
+
+    debug!(
+        "Sending response with status: {}, request ID: {request_id}...",
+        response.status
+    );
+    let mut frame = BytesMut::with_capacity(RESPONSE_INITIAL_BYTES_LENGTH + response.payload.len());
+    frame.put_u32_le(response.status);
+    frame.put_u32_le(response.payload.len() as u32);
+    frame.put_u64_le(request_id);
+    frame.put_slice(&response.payload);
+    writer
+        .write_all(&frame)
+        .await
+        .map_err(|_| IggyError::TcpError)?;
+    writer.flush().await.map_err(|_| IggyError::TcpError)
+}
+
 pub(crate) fn handle_error(error: ConnectionError) {
     match error {
         ConnectionError::IoError(error) => match error.kind() {
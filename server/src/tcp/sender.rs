@@ -16,6 +16,7 @@
  * under the License.
  */
 
+use iggy::compression::wire_compression::{WireCompression, COMPRESSION_FLAG};
 use iggy::error::IggyError;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tracing::debug;
@@ -43,13 +44,22 @@ pub(crate) async fn send_empty_ok_response<T>(stream: &mut T) -> Result<(), Iggy
 where
     T: AsyncRead + AsyncWrite + Unpin,
 {
-    send_ok_response(stream, &[]).await
+    send_response(stream, STATUS_OK, &[]).await
 }
 
-pub(crate) async fn send_ok_response<T>(stream: &mut T, payload: &[u8]) -> Result<(), IggyError>
+/// Sends the payload compressed if the compression has been negotiated and the payload exceeds its threshold.
+pub(crate) async fn send_ok_response<T>(
+    stream: &mut T,
+    payload: &[u8],
+    compression: Option<&WireCompression>,
+) -> Result<(), IggyError>
 where
     T: AsyncRead + AsyncWrite + Unpin,
 {
+    if let Some(compressed) = compression.and_then(|compression| compression.compress(payload)) {
+        return send_response(stream, &COMPRESSION_FLAG.to_le_bytes(), &compressed).await;
+    }
+
     send_response(stream, STATUS_OK, payload).await
 }
 
@@ -60,7 +70,12 @@ pub(crate) async fn send_error_response<T>(
 where
     T: AsyncRead + AsyncWrite + Unpin,
 {
-    send_response(stream, &error.as_code().to_le_bytes(), &[]).await
+    send_response(
+        stream,
+        &error.as_code().to_le_bytes(),
+        &error.details_to_bytes(),
+    )
+    .await
 }
 
 pub(crate) async fn send_response<T>(
@@ -20,12 +20,14 @@ use crate::binary::sender::Sender;
 use crate::tcp::COMPONENT;
 use crate::{server_error::ServerError, tcp::sender};
 use error_set::ErrContext;
+use iggy::compression::wire_compression::WireCompression;
 use iggy::error::IggyError;
 use tokio::{io::AsyncWriteExt, net::TcpStream};
 
 #[derive(Debug)]
 pub struct TcpSender {
     pub(crate) stream: TcpStream,
+    pub(crate) compression: Option<WireCompression>,
 }
 
 impl Sender for TcpSender {
@@ -38,7 +40,7 @@ impl Sender for TcpSender {
     }
 
     async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
-        sender::send_ok_response(&mut self.stream, payload).await
+        sender::send_ok_response(&mut self.stream, payload, self.compression.as_ref()).await
     }
 
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
@@ -21,12 +21,13 @@ use crate::configs::tcp::TcpTlsConfig;
 use crate::streaming::clients::client_manager::Transport;
 use crate::streaming::systems::system::SharedSystem;
 use crate::tcp::connection_handler::{handle_connection, handle_error};
+use crate::tls;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::net::TcpSocket;
 use tokio::sync::oneshot;
-use tokio_native_tls::native_tls;
-use tokio_native_tls::native_tls::Identity;
-use tracing::{error, info};
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, info, warn};
 
 pub(crate) async fn start(
     address: &str,
@@ -37,21 +38,26 @@ pub(crate) async fn start(
     let address = address.to_string();
     let (tx, rx) = oneshot::channel();
     tokio::spawn(async move {
-        let certificate = std::fs::read(config.certificate.clone());
-        if certificate.is_err() {
-            panic!("Unable to read certificate file.");
-        }
-
-        let identity = Identity::from_pkcs12(&certificate.unwrap(), &config.password);
-        if identity.is_err() {
-            panic!("Unable to create identity from certificate.");
+        let server_config = match config.certificate.is_empty() {
+            true => tls::create_server_config(
+                &config.cert_file,
+                &config.key_file,
+                &config.client_certificate,
+            ),
+            false => {
+                warn!("TCP TLS `certificate` and `password` options are deprecated, use `cert_file` and `key_file` instead.");
+                tls::create_pkcs12_server_config(
+                    &config.certificate,
+                    &config.password,
+                    &config.client_certificate,
+                )
+            }
+        };
+        if let Err(error) = server_config {
+            panic!("Unable to create TLS server config. {error}");
         }
 
-        let acceptor = tokio_native_tls::TlsAcceptor::from(
-            native_tls::TlsAcceptor::builder(identity.unwrap())
-                .build()
-                .unwrap(),
-        );
+        let acceptor = TlsAcceptor::from(Arc::new(server_config.unwrap()));
 
         let addr = address.parse();
         if addr.is_err() {
@@ -81,18 +87,35 @@ pub(crate) async fn start(
             match listener.accept().await {
                 Ok((stream, address)) => {
                     info!("Accepted new TCP TLS connection: {}", address);
-                    let session = system
-                        .read()
-                        .await
-                        .add_client(&address, Transport::Tcp)
-                        .await;
-
-                    let client_id = session.client_id;
                     let acceptor = acceptor.clone();
-                    let stream = acceptor.accept(stream).await.unwrap();
                     let system = system.clone();
-                    let mut sender = SenderKind::get_tcp_tls_sender(stream);
+                    let client_certificate = config.client_certificate.clone();
                     tokio::spawn(async move {
+                        let stream = match acceptor.accept(stream).await {
+                            Ok(stream) => stream,
+                            Err(error) => {
+                                error!("Failed to establish TLS connection with client: {address}. {error}");
+                                return;
+                            }
+                        };
+
+                        let session = system
+                            .read()
+                            .await
+                            .add_client(&address, Transport::Tcp)
+                            .await;
+                        if client_certificate.enabled {
+                            tls::login_with_client_certificate(
+                                &system,
+                                &session,
+                                stream.get_ref().1.peer_certificates(),
+                                client_certificate.identity,
+                            )
+                            .await;
+                        }
+
+                        let client_id = session.client_id;
+                        let mut sender = SenderKind::get_tcp_tls_sender(stream);
                         if let Err(error) =
                             handle_connection(session, &mut sender, system.clone()).await
                         {
@@ -20,14 +20,16 @@ use crate::binary::sender::Sender;
 use crate::tcp::COMPONENT;
 use crate::{server_error::ServerError, tcp::sender};
 use error_set::ErrContext;
+use iggy::compression::wire_compression::WireCompression;
 use iggy::error::IggyError;
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
-use tokio_native_tls::TlsStream;
+use tokio_rustls::server::TlsStream;
 
 #[derive(Debug)]
 pub struct TcpTlsSender {
     pub(crate) stream: TlsStream<TcpStream>,
+    pub(crate) compression: Option<WireCompression>,
 }
 
 impl Sender for TcpTlsSender {
@@ -40,7 +42,7 @@ impl Sender for TcpTlsSender {
     }
 
     async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError> {
-        sender::send_ok_response(&mut self.stream, payload).await
+        sender::send_ok_response(&mut self.stream, payload, self.compression.as_ref()).await
     }
 
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
@@ -0,0 +1,426 @@
+/* Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+use crate::configs::server::{ClientCertificateConfig, ClientCertificateIdentity};
+use crate::server_error::TlsError;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use error_set::ErrContext;
+use openssl::pkcs12::Pkcs12;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use simple_asn1::{from_der, oid, ASN1Block, ASN1Class, BigUint};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+const COMPONENT: &str = "TLS";
+const EXTENSIONS_TAG: u8 = 3;
+const DNS_NAME_TAG: u8 = 2;
+
+/// Creates the TLS server config with the certificate and the key loaded from the PEM files,
+/// verifying the client certificates if enabled.
+pub(crate) fn create_server_config(
+    cert_file: &str,
+    key_file: &str,
+    client_certificate: &ClientCertificateConfig,
+) -> Result<ServerConfig, TlsError> {
+    let (certificates, key) = load_certificates(cert_file, key_file)?;
+    build_server_config(certificates, key, client_certificate)
+}
+
+/// Creates the TLS server config with the certificate and the key loaded from the password protected PKCS#12 file,
+/// verifying the client certificates if enabled.
+pub(crate) fn create_pkcs12_server_config(
+    file: &str,
+    password: &str,
+    client_certificate: &ClientCertificateConfig,
+) -> Result<ServerConfig, TlsError> {
+    let (certificates, key) = load_pkcs12_certificates(file, password)?;
+    build_server_config(certificates, key, client_certificate)
+}
+
+fn build_server_config(
+    certificates: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+    client_certificate: &ClientCertificateConfig,
+) -> Result<ServerConfig, TlsError> {
+    let builder = ServerConfig::builder_with_provider(crypto_provider())
+        .with_safe_default_protocol_versions()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to set protocol versions")
+        })
+        .map_err(|_| TlsError::TlsConfigCreationError)?;
+    let builder = match client_certificate.enabled {
+        true => builder.with_client_cert_verifier(create_client_cert_verifier(client_certificate)?),
+        false => builder.with_no_client_auth(),
+    };
+    builder
+        .with_single_cert(certificates, key)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to set server certificate")
+        })
+        .map_err(|_| TlsError::TlsConfigCreationError)
+}
+
+/// Creates the verifier accepting only the client certificates signed by the configured CA.
+pub(crate) fn create_client_cert_verifier(
+    config: &ClientCertificateConfig,
+) -> Result<Arc<dyn ClientCertVerifier>, TlsError> {
+    let mut roots = RootCertStore::empty();
+    for certificate in load_pem_certificates(&config.ca_file)? {
+        roots
+            .add(certificate)
+            .with_error_context(|error| {
+                format!(
+                    "{COMPONENT} (error: {error}) - failed to add CA certificate from file: {}",
+                    config.ca_file
+                )
+            })
+            .map_err(|_| TlsError::ClientCertVerifierCreationError)?;
+    }
+
+    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), crypto_provider())
+        .build()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to build client cert verifier")
+        })
+        .map_err(|_| TlsError::ClientCertVerifierCreationError)
+}
+
+pub(crate) fn crypto_provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn load_certificates(
+    cert_file: &str,
+    key_file: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), TlsError> {
+    let certificates = load_pem_certificates(cert_file)?;
+    let key = PrivateKeyDer::from_pem_file(key_file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load key file: {key_file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?;
+    Ok((certificates, key))
+}
+
+fn load_pkcs12_certificates(
+    file: &str,
+    password: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), TlsError> {
+    let bytes = std::fs::read(file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to read PKCS#12 file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?;
+    let pkcs12 = Pkcs12::from_der(&bytes)
+        .and_then(|pkcs12| pkcs12.parse2(password))
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to parse PKCS#12 file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?;
+    let (Some(certificate), Some(key)) = (pkcs12.cert, pkcs12.pkey) else {
+        warn!("{COMPONENT} - PKCS#12 file: {file} doesn't contain the certificate and the key.");
+        return Err(TlsError::CertLoadError);
+    };
+
+    let mut certificates = vec![certificate];
+    certificates.extend(pkcs12.ca.into_iter().flatten());
+    let certificates = certificates
+        .iter()
+        .map(|certificate| certificate.to_der().map(CertificateDer::from))
+        .collect::<Result<Vec<_>, _>>()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to encode certificates from PKCS#12 file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?;
+    let key = key
+        .private_key_to_pkcs8()
+        .map(|key| PrivateKeyDer::Pkcs8(key.into()))
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to encode key from PKCS#12 file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?;
+    Ok((certificates, key))
+}
+
+fn load_pem_certificates(file: &str) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    CertificateDer::pem_file_iter(file)
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to open cert file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)?
+        .collect::<Result<Vec<_>, _>>()
+        .with_error_context(|error| {
+            format!("{COMPONENT} (error: {error}) - failed to load cert file: {file}")
+        })
+        .map_err(|_| TlsError::CertLoadError)
+}
+
+/// Authenticates the session with the user matching the identity of the verified client certificate, if any.
+/// The client remains unauthenticated otherwise, and may still log in explicitly.
+pub(crate) async fn login_with_client_certificate(
+    system: &SharedSystem,
+    session: &Session,
+    certificates: Option<&[CertificateDer<'_>]>,
+    identity: ClientCertificateIdentity,
+) {
+    let Some(certificate) = certificates.and_then(|certificates| certificates.first()) else {
+        return;
+    };
+
+    let names = get_client_certificate_names(certificate, identity);
+    match system
+        .read()
+        .await
+        .login_with_client_certificate(&names, Some(session))
+        .await
+    {
+        Ok(user) => info!(
+            "Authenticated session: {session} as user: {} with ID: {} using client certificate.",
+            user.username, user.id
+        ),
+        Err(error) => warn!(
+            "Failed to authenticate session: {session} using client certificate with names: {names:?}. {error}"
+        ),
+    }
+}
+
+/// Returns the names of the certificate identity which may be mapped to the username.
+pub(crate) fn get_client_certificate_names(
+    certificate: &CertificateDer,
+    identity: ClientCertificateIdentity,
+) -> Vec<String> {
+    let Some(tbs_certificate) = get_tbs_certificate(certificate) else {
+        return Vec::new();
+    };
+
+    match identity {
+        ClientCertificateIdentity::CommonName => get_common_names(&tbs_certificate),
+        ClientCertificateIdentity::SubjectAltName => get_dns_names(&tbs_certificate),
+    }
+}
+
+fn get_tbs_certificate(certificate: &[u8]) -> Option<Vec<ASN1Block>> {
+    let Some(ASN1Block::Sequence(_, certificate)) = from_der(certificate).ok()?.into_iter().next()
+    else {
+        return None;
+    };
+    match certificate.into_iter().next()? {
+        ASN1Block::Sequence(_, tbs_certificate) => Some(tbs_certificate),
+        _ => None,
+    }
+}
+
+fn get_common_names(tbs_certificate: &[ASN1Block]) -> Vec<String> {
+    // The version is optional, followed by the serial number, signature, issuer, validity and subject.
+    let version = usize::from(matches!(
+        tbs_certificate.first(),
+        Some(ASN1Block::Explicit(..))
+    ));
+    let Some(ASN1Block::Sequence(_, subject)) = tbs_certificate.get(version + 4) else {
+        return Vec::new();
+    };
+
+    let common_name = oid!(2, 5, 4, 3);
+    subject
+        .iter()
+        .filter_map(|name| match name {
+            ASN1Block::Set(_, attributes) => Some(attributes),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|attribute| match attribute {
+            ASN1Block::Sequence(_, attribute) => match attribute.as_slice() {
+                [ASN1Block::ObjectIdentifier(_, oid), value] if *oid == common_name => {
+                    get_string(value)
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_dns_names(tbs_certificate: &[ASN1Block]) -> Vec<String> {
+    let extensions_tag = BigUint::from(EXTENSIONS_TAG);
+    let Some(extensions) = tbs_certificate.iter().find_map(|block| match block {
+        ASN1Block::Explicit(ASN1Class::ContextSpecific, _, tag, extensions)
+            if *tag == extensions_tag =>
+        {
+            match extensions.as_ref() {
+                ASN1Block::Sequence(_, extensions) => Some(extensions),
+                _ => None,
+            }
+        }
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let subject_alt_name = oid!(2, 5, 29, 17);
+    let Some(general_names) = extensions.iter().find_map(|extension| match extension {
+        ASN1Block::Sequence(_, extension) => match (extension.first(), extension.last()) {
+            (
+                Some(ASN1Block::ObjectIdentifier(_, oid)),
+                Some(ASN1Block::OctetString(_, general_names)),
+            ) if *oid == subject_alt_name => Some(general_names),
+            _ => None,
+        },
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let dns_name_tag = BigUint::from(DNS_NAME_TAG);
+    match from_der(general_names)
+        .ok()
+        .and_then(|blocks| blocks.into_iter().next())
+    {
+        Some(ASN1Block::Sequence(_, general_names)) => general_names
+            .iter()
+            .filter_map(|general_name| match general_name {
+                ASN1Block::Unknown(ASN1Class::ContextSpecific, false, _, tag, name)
+                    if *tag == dns_name_tag =>
+                {
+                    String::from_utf8(name.clone()).ok()
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn get_string(block: &ASN1Block) -> Option<String> {
+    match block {
+        ASN1Block::UTF8String(_, value)
+        | ASN1Block::PrintableString(_, value)
+        | ASN1Block::IA5String(_, value)
+        | ASN1Block::TeletexString(_, value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair};
+
+    fn generate_certificate(common_name: &str, dns_names: &[&str]) -> CertificateDer<'static> {
+        let mut params = CertificateParams::new(
+            dns_names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let mut distinguished_name = DistinguishedName::new();
+        distinguished_name.push(DnType::OrganizationName, "Iggy");
+        distinguished_name.push(DnType::CommonName, common_name);
+        params.distinguished_name = distinguished_name;
+        let key_pair = KeyPair::generate().unwrap();
+        params.self_signed(&key_pair).unwrap().der().clone()
+    }
+
+    #[test]
+    fn should_get_common_name_from_certificate_subject() {
+        let certificate = generate_certificate("orders-service", &["orders.iggy.local"]);
+
+        let names =
+            get_client_certificate_names(&certificate, ClientCertificateIdentity::CommonName);
+
+        assert_eq!(names, vec!["orders-service".to_string()]);
+    }
+
+    #[test]
+    fn should_get_dns_names_from_certificate_subject_alt_name() {
+        let certificate =
+            generate_certificate("orders-service", &["orders.iggy.local", "payments"]);
+
+        let names =
+            get_client_certificate_names(&certificate, ClientCertificateIdentity::SubjectAltName);
+
+        assert_eq!(
+            names,
+            vec!["orders.iggy.local".to_string(), "payments".to_string()]
+        );
+    }
+
+    #[test]
+    fn should_get_no_names_from_invalid_certificate() {
+        let certificate = CertificateDer::from(vec![1, 2, 3]);
+
+        let names =
+            get_client_certificate_names(&certificate, ClientCertificateIdentity::CommonName);
+
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn should_create_server_config_from_bundled_pem_files_verifying_client_certificates() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let ca_file = tempdir.path().join("ca_cert.pem");
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "Iggy Test CA");
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        std::fs::write(&ca_file, ca.pem()).unwrap();
+        let client_certificate = ClientCertificateConfig {
+            enabled: true,
+            ca_file: ca_file.to_str().unwrap().to_string(),
+            identity: ClientCertificateIdentity::CommonName,
+        };
+
+        let server_config = create_server_config(
+            &certs_path("iggy_cert.pem"),
+            &certs_path("iggy_key.pem"),
+            &client_certificate,
+        );
+
+        assert!(server_config.is_ok());
+    }
+
+    #[test]
+    fn should_create_server_config_from_bundled_pkcs12_file() {
+        let client_certificate = ClientCertificateConfig {
+            enabled: false,
+            ca_file: String::new(),
+            identity: ClientCertificateIdentity::CommonName,
+        };
+
+        let server_config =
+            create_pkcs12_server_config(&certs_path("iggy.pfx"), "iggy123", &client_certificate);
+        let invalid_password_server_config =
+            create_pkcs12_server_config(&certs_path("iggy.pfx"), "invalid", &client_certificate);
+
+        assert!(server_config.is_ok());
+        assert!(invalid_password_server_config.is_err());
+    }
+
+    fn certs_path(file: &str) -> String {
+        format!("{}/../certs/{file}", env!("CARGO_MANIFEST_DIR"))
+    }
+}
@@ -21,6 +21,7 @@ use iggy::clients::client::IggyClient;
 use iggy::error::IggyError;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::topics::cleanup_policy::CleanupPolicy;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use rand::Rng;
@@ -39,9 +40,15 @@ pub async fn seed(client: &IggyClient) -> Result<(), IggyError> {
 }
 
 async fn create_streams(client: &IggyClient) -> Result<(), IggyError> {
-    client.create_stream("prod", Some(PROD_STREAM_ID)).await?;
-    client.create_stream("test", Some(TEST_STREAM_ID)).await?;
-    client.create_stream("dev", Some(DEV_STREAM_ID)).await?;
+    client
+        .create_stream("prod", Some(PROD_STREAM_ID), None)
+        .await?;
+    client
+        .create_stream("test", Some(TEST_STREAM_ID), None)
+        .await?;
+    client
+        .create_stream("dev", Some(DEV_STREAM_ID), None)
+        .await?;
     Ok(())
 }
 
@@ -59,6 +66,7 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -72,6 +80,7 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -85,6 +94,7 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -98,6 +108,7 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
 
@@ -111,6 +122,7 @@ async fn create_topics(client: &IggyClient) -> Result<(), IggyError> {
                 None,
                 IggyExpiry::NeverExpire,
                 MaxTopicSize::ServerDefault,
+                CleanupPolicy::default(),
             )
             .await?;
     }